@@ -0,0 +1,134 @@
+//! Activity feed of a zone, merging Cloudflare's audit log with the local journal
+
+use chrono::{DateTime, Utc};
+
+use crate::cloudflare::AuditLogEntry;
+use crate::journal::JournalEntry;
+use crate::timestamps;
+
+/// Where an activity entry comes from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ActivitySource {
+    /// Cloudflare's audit log, covering changes made anywhere
+    AuditLog,
+    /// The app's local journal
+    Journal,
+}
+
+impl ActivitySource {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ActivitySource::AuditLog => "Cloudflare",
+            ActivitySource::Journal => "This app",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ActivityEntry {
+    pub time: DateTime<Utc>,
+    pub source: ActivitySource,
+    /// Who made the change, e.g. an email address or a part of the app
+    pub actor: String,
+    pub description: String,
+    /// Whether a DNS record was changed, rather than e.g. a zone setting
+    pub record_change: bool,
+}
+
+impl ActivityEntry {
+    /// Convert an audit log entry; entries with an unreadable time are skipped
+    pub fn from_audit_log(entry: &AuditLogEntry) -> Option<Self> {
+        let time = timestamps::parse(&entry.when)?;
+
+        let actor = entry
+            .actor
+            .email
+            .clone()
+            .or_else(|| entry.actor.actor_type.clone())
+            .unwrap_or_else(|| "Unknown".to_string());
+        let actor = match &entry.interface {
+            Some(interface) if !interface.is_empty() => format!("{} via {}", actor, interface),
+            _ => actor,
+        };
+
+        let mut description = match entry.action.action_type.as_str() {
+            "rec_add" => "Create record".to_string(),
+            "rec_set" => "Update record".to_string(),
+            "rec_del" => "Delete record".to_string(),
+            action_type => {
+                let action = action_type.replace('_', " ");
+                match entry
+                    .resource
+                    .as_ref()
+                    .and_then(|resource| resource.resource_type.as_deref())
+                {
+                    Some(resource_type) => format!("{} ({})", action, resource_type),
+                    None => action,
+                }
+            }
+        };
+        // DNS record changes carry the record's type and name
+        if let Some(metadata) = &entry.metadata {
+            let field = |name: &str| metadata.get(name).and_then(|value| value.as_str());
+            if let (Some(record_type), Some(name)) = (field("type"), field("name")) {
+                description = format!("{} {} {}", description, record_type, name);
+            }
+        }
+        if !entry.action.result {
+            description.push_str(" (failed)");
+        }
+
+        Some(Self {
+            time,
+            source: ActivitySource::AuditLog,
+            actor,
+            description,
+            record_change: entry.is_record_change(),
+        })
+    }
+}
+
+impl From<JournalEntry> for ActivityEntry {
+    fn from(entry: JournalEntry) -> Self {
+        Self {
+            time: entry.time,
+            source: ActivitySource::Journal,
+            actor: entry.actor,
+            description: entry.description,
+            // The journal only holds record changes
+            record_change: true,
+        }
+    }
+}
+
+/// Merge both sources into a single feed, newest first
+pub fn merge(audit_log: &[AuditLogEntry], journal: Vec<JournalEntry>) -> Vec<ActivityEntry> {
+    let mut entries: Vec<ActivityEntry> = audit_log
+        .iter()
+        .filter_map(ActivityEntry::from_audit_log)
+        .chain(journal.into_iter().map(ActivityEntry::from))
+        .collect();
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.time));
+    entries
+}
+
+/// Entries from `source` (or any source) whose actor or description contain `query`,
+/// optionally only the DNS record changes
+pub fn filter<'a>(
+    entries: &'a [ActivityEntry],
+    source: Option<ActivitySource>,
+    records_only: bool,
+    query: &str,
+) -> Vec<&'a ActivityEntry> {
+    let query = query.trim().to_lowercase();
+    entries
+        .iter()
+        .filter(|entry| source.is_none_or(|source| entry.source == source))
+        .filter(|entry| !records_only || entry.record_change)
+        .filter(|entry| {
+            query.is_empty()
+                || entry.actor.to_lowercase().contains(&query)
+                || entry.description.to_lowercase().contains(&query)
+        })
+        .collect()
+}
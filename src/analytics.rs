@@ -0,0 +1,161 @@
+//! Traffic of a zone for the Analytics page: time ranges, totals, how values are shown, and
+//! which records DNS queries ask for
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+
+use crate::cloudflare::{DnsQueryCount, DnsRecord, DnsRecordType, TrafficPoint};
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum TimeRange {
+    #[default]
+    Day,
+    Week,
+    Month,
+}
+
+impl TimeRange {
+    pub const ALL: [TimeRange; 3] = [TimeRange::Day, TimeRange::Week, TimeRange::Month];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TimeRange::Day => "24 hours",
+            TimeRange::Week => "7 days",
+            TimeRange::Month => "30 days",
+        }
+    }
+
+    /// Start of the range ending now
+    pub fn since(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        now - match self {
+            TimeRange::Day => Duration::hours(24),
+            TimeRange::Week => Duration::days(7),
+            TimeRange::Month => Duration::days(30),
+        }
+    }
+
+    /// Whether traffic is summed per hour rather than per day; hourly groups only reach back a
+    /// few days on most plans
+    pub fn hourly(&self) -> bool {
+        *self == TimeRange::Day
+    }
+}
+
+/// Traffic of a whole range
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TrafficTotals {
+    pub requests: u64,
+    pub bytes: u64,
+    pub cached_requests: u64,
+    pub threats: u64,
+}
+
+impl TrafficTotals {
+    pub fn new(points: &[TrafficPoint]) -> Self {
+        points.iter().fold(Self::default(), |totals, point| Self {
+            requests: totals.requests + point.requests,
+            bytes: totals.bytes + point.bytes,
+            cached_requests: totals.cached_requests + point.cached_requests,
+            threats: totals.threats + point.threats,
+        })
+    }
+
+    /// Share of requests served from the cache, in percent
+    pub fn cache_ratio(&self) -> f64 {
+        percent(self.cached_requests, self.requests)
+    }
+}
+
+/// Share of a point's requests served from the cache, in percent
+pub fn cache_ratio(point: &TrafficPoint) -> f64 {
+    percent(point.cached_requests, point.requests)
+}
+
+fn percent(part: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        part as f64 * 100.0 / total as f64
+    }
+}
+
+/// DNS queries for a name and type the zone has records of
+#[derive(Debug, Clone)]
+pub struct RecordUsage {
+    pub name: String,
+    pub record_type: DnsRecordType,
+    /// Records with this name and type
+    pub records: usize,
+    pub queries: u64,
+}
+
+/// Queries for each name and type of `records`, least queried first so unused records stand out
+///
+/// A CNAME answers queries of any type, so all queries for its name count.
+pub fn record_usage(records: &[DnsRecord], counts: &[DnsQueryCount]) -> Vec<RecordUsage> {
+    let mut usage: Vec<RecordUsage> = Vec::new();
+    for record in records {
+        match usage
+            .iter_mut()
+            .find(|usage| usage.name == record.name && usage.record_type == record.record_type)
+        {
+            Some(usage) => usage.records += 1,
+            None => usage.push(RecordUsage {
+                name: record.name.clone(),
+                record_type: record.record_type,
+                records: 1,
+                queries: 0,
+            }),
+        }
+    }
+
+    for usage in &mut usage {
+        usage.queries = counts
+            .iter()
+            .filter(|count| {
+                count
+                    .name
+                    .trim_end_matches('.')
+                    .eq_ignore_ascii_case(&usage.name)
+                    && (usage.record_type == DnsRecordType::CNAME
+                        || count.record_type == usage.record_type.as_str())
+            })
+            .map(|count| count.queries)
+            .sum();
+    }
+    usage.sort_by(|a, b| a.queries.cmp(&b.queries).then_with(|| a.name.cmp(&b.name)));
+    usage
+}
+
+/// Label of a point on the time axis, e.g. `Tue 14:00` for an hour or `May 07` for a day
+pub fn timeslot_label(timeslot: &str) -> String {
+    if let Ok(hour) = DateTime::parse_from_rfc3339(timeslot) {
+        return hour.format("%a %H:%M").to_string();
+    }
+    match NaiveDate::parse_from_str(timeslot, "%Y-%m-%d") {
+        Ok(day) => day.format("%b %d").to_string(),
+        Err(_) => timeslot.to_string(),
+    }
+}
+
+/// A count with a metric suffix, e.g. `12.3k` or `4.5M`
+pub fn format_count(count: u64) -> String {
+    scaled(count as f64, 1000.0, &["", "k", "M", "B", "T"])
+}
+
+/// An amount of data in bytes, e.g. `512 B` or `1.2 GB`
+pub fn format_bytes(bytes: u64) -> String {
+    scaled(bytes as f64, 1024.0, &[" B", " KB", " MB", " GB", " TB"])
+}
+
+fn scaled(mut value: f64, base: f64, units: &[&str]) -> String {
+    let mut unit = 0;
+    while value >= base && unit + 1 < units.len() {
+        value /= base;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{}{}", value, units[0])
+    } else {
+        format!("{:.1}{}", value, units[unit])
+    }
+}
@@ -0,0 +1,67 @@
+//! Explanations of common Cloudflare API error codes
+//!
+//! The API's own messages are terse ("Record already exists."), so errors with a known code are
+//! shown with what went wrong and what to do about it. The original code and message are kept
+//! at the end for searching Cloudflare's documentation.
+
+use crate::cloudflare::ApiError;
+
+/// What an error code means and how to get past it
+pub struct ErrorHelp {
+    pub explanation: &'static str,
+    pub next_step: &'static str,
+}
+
+/// Help for a Cloudflare error code, if it's a known one
+pub fn explain(code: i32) -> Option<ErrorHelp> {
+    let (explanation, next_step) = match code {
+        971 => (
+            "Too many requests were sent to Cloudflare in a short time.",
+            "Wait a few minutes before trying again.",
+        ),
+        1003 | 7003 => (
+            "Cloudflare doesn't know the zone or record this refers to.",
+            "Refresh the list; it may have been removed outside this app.",
+        ),
+        9109 => (
+            "The API token isn't valid; it may have been revoked, expired or mistyped.",
+            "Create a new token in the Cloudflare dashboard and enter it again.",
+        ),
+        10000 => (
+            "The API token isn't allowed to do this.",
+            "Give the token the Zone DNS Edit permission for this zone, or use another token.",
+        ),
+        81044 => (
+            "The record doesn't exist anymore; it was probably deleted elsewhere.",
+            "Refresh the records to see the zone's current state.",
+        ),
+        81053 => (
+            "A CNAME record can't share its name with other records, and this name already \
+             has an A, AAAA or CNAME record.",
+            "Edit or delete the existing record, or pick another name.",
+        ),
+        81058 => (
+            "An identical record already exists.",
+            "Nothing needs to be created; edit the existing record to change it.",
+        ),
+        _ => return None,
+    };
+    Some(ErrorHelp {
+        explanation,
+        next_step,
+    })
+}
+
+/// Error message for a failed request, e.g. `Failed to create DNS record: ...`
+pub fn describe(context: &str, errors: &[ApiError]) -> String {
+    let Some(error) = errors.first() else {
+        return format!("{}: no reason was given", context);
+    };
+    match explain(error.code) {
+        Some(help) => format!(
+            "{}: {} {} (error {}: {})",
+            context, help.explanation, help.next_step, error.code, error.message
+        ),
+        None => format!("{}: {} (error {})", context, error.message, error.code),
+    }
+}
@@ -0,0 +1,104 @@
+//! Count of the API requests the app sent today, per profile and zone
+//!
+//! Each request the client sends is counted under the profile whose token it carries and the
+//! zone in its path, or under no zone for account-wide ones such as listing zones. On accounts
+//! that scripts and CI also use, this is the app's own share of the rate limit. The counts are
+//! kept in the app's data directory, so they survive restarts, and start over each local day.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use chrono::{Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct ApiUsage {
+    /// Day the counts are of
+    pub date: Option<NaiveDate>,
+    /// Requests by profile, then by zone id; account-wide ones are under an empty id
+    pub counts: BTreeMap<String, BTreeMap<String, u32>>,
+}
+
+impl ApiUsage {
+    /// Today's counts as last saved
+    pub fn load() -> Self {
+        let mut usage: ApiUsage = usage_path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+        usage.roll_over(Local::now().date_naive());
+        usage
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let Some(path) = usage_path() else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Count a request sent on `today`
+    pub fn record(&mut self, profile: &str, zone_id: Option<&str>, today: NaiveDate) {
+        self.roll_over(today);
+        *self
+            .counts
+            .entry(profile.to_string())
+            .or_default()
+            .entry(zone_id.unwrap_or_default().to_string())
+            .or_default() += 1;
+    }
+
+    /// Forget the counts of an earlier day
+    pub fn roll_over(&mut self, today: NaiveDate) {
+        if self.date != Some(today) {
+            self.date = Some(today);
+            self.counts.clear();
+        }
+    }
+
+    /// Requests sent with a profile's token today
+    pub fn profile_total(&self, profile: &str) -> u32 {
+        self.counts
+            .get(profile)
+            .map_or(0, |zones| zones.values().sum())
+    }
+
+    /// Requests sent with a profile's token for a zone today
+    pub fn zone_total(&self, profile: &str, zone_id: &str) -> u32 {
+        self.counts
+            .get(profile)
+            .and_then(|zones| zones.get(zone_id))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Requests of a profile by zone id, most first, with account-wide ones under `None`
+    pub fn zone_counts(&self, profile: &str) -> Vec<(Option<&str>, u32)> {
+        let mut counts: Vec<(Option<&str>, u32)> = self
+            .counts
+            .get(profile)
+            .into_iter()
+            .flatten()
+            .map(|(zone_id, &count)| ((!zone_id.is_empty()).then_some(zone_id.as_str()), count))
+            .collect();
+        counts.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
+        counts
+    }
+}
+
+fn usage_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("cloudflare-admin").join("api_usage.json"))
+}
+
+/// Zone id in an API path, e.g. `/client/v4/zones/<id>/dns_records`
+pub fn zone_of(path: &str) -> Option<&str> {
+    let mut segments = path.split('/');
+    segments.find(|segment| *segment == "zones")?;
+    segments.next().filter(|zone_id| !zone_id.is_empty())
+}
@@ -0,0 +1,46 @@
+//! Icons bundled with the app, on top of gpui-component's icon set
+
+use std::borrow::Cow;
+
+use gpui::{AssetSource, Result, SharedString};
+
+const ICONS: &[(&str, &[u8])] = &[
+    (
+        "icons/providers/aws.svg",
+        include_bytes!("../assets/icons/providers/aws.svg"),
+    ),
+    (
+        "icons/providers/fastly.svg",
+        include_bytes!("../assets/icons/providers/fastly.svg"),
+    ),
+    (
+        "icons/providers/heroku.svg",
+        include_bytes!("../assets/icons/providers/heroku.svg"),
+    ),
+    (
+        "icons/providers/vercel.svg",
+        include_bytes!("../assets/icons/providers/vercel.svg"),
+    ),
+];
+
+pub struct Assets;
+
+impl AssetSource for Assets {
+    fn load(&self, path: &str) -> Result<Option<Cow<'static, [u8]>>> {
+        match ICONS.iter().find(|(icon_path, _)| *icon_path == path) {
+            Some((_, svg)) => Ok(Some(Cow::Borrowed(*svg))),
+            None => gpui_component_assets::Assets.load(path),
+        }
+    }
+
+    fn list(&self, path: &str) -> Result<Vec<SharedString>> {
+        let mut paths = gpui_component_assets::Assets.list(path)?;
+        paths.extend(
+            ICONS
+                .iter()
+                .filter(|(icon_path, _)| icon_path.starts_with(path))
+                .map(|(icon_path, _)| SharedString::from(*icon_path)),
+        );
+        Ok(paths)
+    }
+}
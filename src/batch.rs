@@ -0,0 +1,208 @@
+//! Bulk operations on DNS records with per-item results
+
+use anyhow::Result;
+
+use crate::cloudflare::{CloudflareClient, CreateDnsRecord, DnsRecord, UpdateDnsRecord};
+
+/// A single API call of a bulk operation
+#[derive(Debug, Clone)]
+pub enum BatchAction {
+    Create(CreateDnsRecord),
+    Update {
+        record_id: String,
+        record: UpdateDnsRecord,
+    },
+    Delete {
+        record_id: String,
+    },
+}
+
+impl BatchAction {
+    pub async fn run(&self, client: &CloudflareClient, zone_id: &str) -> Result<()> {
+        match self {
+            BatchAction::Create(record) => {
+                client.create_dns_record(zone_id, record).await?;
+            }
+            BatchAction::Update { record_id, record } => {
+                client.update_dns_record(zone_id, record_id, record).await?;
+            }
+            BatchAction::Delete { record_id } => {
+                client.delete_dns_record(zone_id, record_id).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Turn the proxy of a record on or off, labelled for the progress dialog
+    pub fn set_proxied(record: &DnsRecord, proxied: bool) -> (String, Self) {
+        (
+            format!(
+                "{} {} {}",
+                if proxied { "Proxy" } else { "Unproxy" },
+                record.record_type,
+                record.name
+            ),
+            BatchAction::Update {
+                record_id: record.id.clone(),
+                record: UpdateDnsRecord {
+                    proxied: Some(proxied),
+                    ..Default::default()
+                },
+            },
+        )
+    }
+
+    /// Id of the existing record the action changes or deletes
+    pub fn record_id(&self) -> Option<&str> {
+        match self {
+            BatchAction::Create(_) => None,
+            BatchAction::Update { record_id, .. } | BatchAction::Delete { record_id } => {
+                Some(record_id)
+            }
+        }
+    }
+
+    /// Description for the progress dialog, e.g. `Update A www.example.com`
+    ///
+    /// Records are looked up in `records` by id; unknown ids are shown as they are.
+    pub fn label(&self, records: &[DnsRecord]) -> String {
+        let describe = |id: &str| {
+            records
+                .iter()
+                .find(|record| record.id == id)
+                .map(|record| format!("{} {}", record.record_type, record.name))
+                .unwrap_or_else(|| id.to_string())
+        };
+
+        match self {
+            BatchAction::Create(record) => format!("Create {} {}", record.record_type, record.name),
+            BatchAction::Update { record_id, .. } => format!("Update {}", describe(record_id)),
+            BatchAction::Delete { record_id } => format!("Delete {}", describe(record_id)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BatchItemStatus {
+    Pending,
+    Running,
+    Success,
+    Failed(String),
+    Cancelled,
+}
+
+impl BatchItemStatus {
+    pub fn label(&self) -> &str {
+        match self {
+            BatchItemStatus::Pending => "Pending",
+            BatchItemStatus::Running => "Running",
+            BatchItemStatus::Success => "Success",
+            BatchItemStatus::Failed(_) => "Failed",
+            BatchItemStatus::Cancelled => "Cancelled",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BatchItem {
+    /// Human-readable description, e.g. `A www.example.com`
+    pub label: String,
+    pub action: BatchAction,
+    pub status: BatchItemStatus,
+}
+
+/// A bulk operation and the result of each of its items
+#[derive(Debug, Clone)]
+pub struct BatchRun {
+    pub title: String,
+    pub zone_id: String,
+    pub items: Vec<BatchItem>,
+    pub running: bool,
+}
+
+impl BatchRun {
+    pub fn new(title: String, zone_id: String, items: Vec<(String, BatchAction)>) -> Self {
+        Self {
+            title,
+            zone_id,
+            items: items
+                .into_iter()
+                .map(|(label, action)| BatchItem {
+                    label,
+                    action,
+                    status: BatchItemStatus::Pending,
+                })
+                .collect(),
+            running: false,
+        }
+    }
+
+    /// Number of items that are no longer pending or running
+    pub fn finished_count(&self) -> usize {
+        self.items
+            .iter()
+            .filter(|item| {
+                !matches!(
+                    item.status,
+                    BatchItemStatus::Pending | BatchItemStatus::Running
+                )
+            })
+            .count()
+    }
+
+    pub fn count(&self, status: fn(&BatchItemStatus) -> bool) -> usize {
+        self.items
+            .iter()
+            .filter(|item| status(&item.status))
+            .count()
+    }
+
+    pub fn failed_indices(&self) -> Vec<usize> {
+        self.items
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| matches!(item.status, BatchItemStatus::Failed(_)))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Mark items that never completed as cancelled
+    pub fn cancel_remaining(&mut self) {
+        for item in &mut self.items {
+            if matches!(
+                item.status,
+                BatchItemStatus::Pending | BatchItemStatus::Running
+            ) {
+                item.status = BatchItemStatus::Cancelled;
+            }
+        }
+        self.running = false;
+    }
+
+    /// Results as CSV, one line per item
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("item,status,error\n");
+        for item in &self.items {
+            let error = match &item.status {
+                BatchItemStatus::Failed(error) => error.as_str(),
+                _ => "",
+            };
+            csv.push_str(&format!(
+                "{},{},{}\n",
+                csv_field(&item.label),
+                item.status.label(),
+                csv_field(error)
+            ));
+        }
+        csv
+    }
+}
+
+/// Quote a CSV field if it contains separators, quotes, or line breaks
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,104 @@
+//! Targeted cache purges entered on the zone settings page
+
+use crate::cloudflare::CachePurge;
+
+/// Most items a single purge request accepts
+const MAX_PURGE_ITEMS: usize = 30;
+
+/// Kind of the items of a targeted purge
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PurgeTarget {
+    #[default]
+    Urls,
+    Prefixes,
+    Hosts,
+    Tags,
+}
+
+impl PurgeTarget {
+    pub fn all() -> &'static [PurgeTarget] {
+        &[
+            PurgeTarget::Urls,
+            PurgeTarget::Prefixes,
+            PurgeTarget::Hosts,
+            PurgeTarget::Tags,
+        ]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PurgeTarget::Urls => "URLs",
+            PurgeTarget::Prefixes => "Prefixes",
+            PurgeTarget::Hosts => "Hostnames",
+            PurgeTarget::Tags => "Tags",
+        }
+    }
+
+    pub fn placeholder(&self) -> &'static str {
+        match self {
+            PurgeTarget::Urls => "https://www.example.com/app.css, one per line",
+            PurgeTarget::Prefixes => "www.example.com/assets/, one per line",
+            PurgeTarget::Hosts => "images.example.com, one per line",
+            PurgeTarget::Tags => "Cache-Tag header values, one per line",
+        }
+    }
+
+    /// Purge of the items in `text`, one per line or separated by commas
+    pub fn parse(&self, text: &str) -> Result<CachePurge, String> {
+        let items: Vec<String> = text
+            .split([',', '\n'])
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(String::from)
+            .collect();
+        if items.is_empty() {
+            return Err(format!(
+                "Enter at least one of the {} to purge",
+                self.label()
+            ));
+        }
+        if items.len() > MAX_PURGE_ITEMS {
+            return Err(format!(
+                "At most {} {} can be purged at once",
+                MAX_PURGE_ITEMS,
+                self.label()
+            ));
+        }
+
+        let has_scheme =
+            |item: &String| item.starts_with("http://") || item.starts_with("https://");
+        let invalid = match self {
+            PurgeTarget::Urls => items.iter().find(|item| !has_scheme(item)),
+            PurgeTarget::Prefixes => items.iter().find(|item| has_scheme(item)),
+            PurgeTarget::Hosts => items.iter().find(|item| item.contains(['/', ':'])),
+            PurgeTarget::Tags => items.iter().find(|item| item.contains(char::is_whitespace)),
+        };
+        if let Some(item) = invalid {
+            let expected = match self {
+                PurgeTarget::Urls => "URLs start with http:// or https://",
+                PurgeTarget::Prefixes => "prefixes are written without http:// or https://",
+                PurgeTarget::Hosts => "hostnames have no scheme, port or path",
+                PurgeTarget::Tags => "tags can't contain spaces",
+            };
+            return Err(format!("Invalid entry {}: {}", item, expected));
+        }
+
+        Ok(match self {
+            PurgeTarget::Urls => CachePurge::Urls(items),
+            PurgeTarget::Prefixes => CachePurge::Prefixes(items),
+            PurgeTarget::Hosts => CachePurge::Hosts(items),
+            PurgeTarget::Tags => CachePurge::Tags(items),
+        })
+    }
+}
+
+/// The items a purge removes, for the confirmation, or `None` for everything
+pub fn purge_items(purge: &CachePurge) -> Option<&[String]> {
+    match purge {
+        CachePurge::Everything => None,
+        CachePurge::Urls(items)
+        | CachePurge::Prefixes(items)
+        | CachePurge::Hosts(items)
+        | CachePurge::Tags(items) => Some(items),
+    }
+}
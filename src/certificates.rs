@@ -0,0 +1,197 @@
+//! Expiry tracking of edge and origin certificates across zones
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+
+use crate::cloudflare::{CertificatePack, OriginCertificate, Zone};
+
+/// Certificates expiring within this many days are listed as expiring soon
+pub const EXPIRING_SOON_DAYS: i64 = 30;
+
+/// Days before expiry at which a desktop notification is shown
+pub const NOTIFICATION_THRESHOLDS: &[i64] = &[30, 14, 7, 1];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CertificateKind {
+    /// Certificate served by Cloudflare's edge to visitors
+    Edge,
+    /// Origin CA certificate installed on the origin server
+    Origin,
+}
+
+impl CertificateKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            CertificateKind::Edge => "Edge",
+            CertificateKind::Origin => "Origin",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TrackedCertificate {
+    pub id: String,
+    pub kind: CertificateKind,
+    pub hosts: Vec<String>,
+    pub expires_on: DateTime<Utc>,
+}
+
+impl TrackedCertificate {
+    /// Whole days until the certificate expires, negative once expired
+    pub fn days_until_expiry(&self, now: DateTime<Utc>) -> i64 {
+        (self.expires_on - now).num_days()
+    }
+
+    /// Cloudflare dashboard page where the certificate can be renewed
+    pub fn dashboard_url(&self, zone: &Zone) -> String {
+        let page = match self.kind {
+            CertificateKind::Edge => "edge-certificates",
+            CertificateKind::Origin => "origin",
+        };
+        format!("{}/ssl-tls/{}", zone.dashboard_url(), page)
+    }
+}
+
+/// Certificates of the active edge certificate packs
+pub fn edge_certificates(packs: &[CertificatePack]) -> Vec<TrackedCertificate> {
+    packs
+        .iter()
+        .filter(|pack| pack.status == "active")
+        .flat_map(|pack| {
+            pack.certificates.iter().filter_map(|certificate| {
+                Some(TrackedCertificate {
+                    id: certificate.id.clone(),
+                    kind: CertificateKind::Edge,
+                    hosts: if certificate.hosts.is_empty() {
+                        pack.hosts.clone()
+                    } else {
+                        certificate.hosts.clone()
+                    },
+                    expires_on: parse_expiry(certificate.expires_on.as_deref()?)?,
+                })
+            })
+        })
+        .collect()
+}
+
+pub fn origin_certificates(certificates: &[OriginCertificate]) -> Vec<TrackedCertificate> {
+    certificates
+        .iter()
+        .filter_map(|certificate| {
+            Some(TrackedCertificate {
+                id: certificate.id.clone(),
+                kind: CertificateKind::Origin,
+                hosts: certificate.hostnames.clone(),
+                expires_on: parse_expiry(&certificate.expires_on)?,
+            })
+        })
+        .collect()
+}
+
+/// Parse an expiry date in either of the formats used by the API
+///
+/// Edge certificates use RFC 3339, while Origin CA certificates use
+/// e.g. `2014-01-01 05:20:00 +0000 UTC`.
+pub fn parse_expiry(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .or_else(|_| {
+            DateTime::parse_from_str(value.trim_end_matches(" UTC"), "%Y-%m-%d %H:%M:%S%.f %z")
+        })
+        .ok()
+        .map(|expiry| expiry.with_timezone(&Utc))
+}
+
+/// Smallest notification threshold a certificate expiring in `days` has reached
+pub fn reached_threshold(days: i64) -> Option<i64> {
+    NOTIFICATION_THRESHOLDS
+        .iter()
+        .copied()
+        .filter(|threshold| days <= *threshold)
+        .min()
+}
+
+/// Certificates that reached a new notification threshold since they were last notified
+///
+/// `notified` maps certificate ids to the threshold they were last notified for and is
+/// updated accordingly; certificates no longer present are forgotten.
+pub fn due_notifications<'a>(
+    certificates: &[&'a TrackedCertificate],
+    notified: &mut HashMap<String, i64>,
+    now: DateTime<Utc>,
+) -> Vec<&'a TrackedCertificate> {
+    notified.retain(|id, _| certificates.iter().any(|c| &c.id == id));
+
+    certificates
+        .iter()
+        .copied()
+        .filter(|certificate| {
+            let Some(threshold) = reached_threshold(certificate.days_until_expiry(now)) else {
+                return false;
+            };
+            let is_new = notified
+                .get(&certificate.id)
+                .is_none_or(|previous| threshold < *previous);
+            if is_new {
+                notified.insert(certificate.id.clone(), threshold);
+            }
+            is_new
+        })
+        .collect()
+}
+
+/// How far an edge certificate pack is from serving traffic
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackState {
+    Active,
+    /// Being validated, issued or deployed
+    Pending,
+    /// Expired, or validation, issuance or deployment timed out
+    Failed,
+    /// Deleted or being deleted
+    Inactive,
+}
+
+impl PackState {
+    pub fn of(pack: &CertificatePack) -> Self {
+        match pack.status.as_str() {
+            "active" => PackState::Active,
+            "expired" => PackState::Failed,
+            "deleted" | "pending_deletion" | "inactive" => PackState::Inactive,
+            status if status.ends_with("_timed_out") => PackState::Failed,
+            _ => PackState::Pending,
+        }
+    }
+}
+
+/// e.g. `Pending validation` for `pending_validation`
+pub fn status_label(status: &str) -> String {
+    let label = status.replace('_', " ");
+    let mut chars = label.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => label,
+    }
+}
+
+/// Kind of certificate pack as the dashboard names it
+pub fn pack_type_label(pack_type: &str) -> String {
+    match pack_type {
+        "universal" => "Universal".to_string(),
+        "advanced" => "Advanced".to_string(),
+        "sni_custom" => "Custom".to_string(),
+        "legacy_custom" => "Custom (legacy)".to_string(),
+        "total_tls" => "Total TLS".to_string(),
+        other => status_label(other),
+    }
+}
+
+pub fn certificate_authority_label(authority: &str) -> &str {
+    match authority {
+        "lets_encrypt" => "Let's Encrypt",
+        "google" => "Google Trust Services",
+        "ssl_com" => "SSL.com",
+        "digicert" => "DigiCert",
+        other => other,
+    }
+}
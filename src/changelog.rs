@@ -0,0 +1,74 @@
+//! Changelog bundled with the app, shown as "What's New" after an update
+//!
+//! `changelog.json` lists releases newest first; each feature can link to the page it added.
+
+use serde::Deserialize;
+
+/// Version of the running app
+pub const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const CHANGELOG: &str = include_str!("../changelog.json");
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Release {
+    pub version: String,
+    pub features: Vec<Feature>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Feature {
+    pub title: String,
+    pub description: String,
+    #[serde(default)]
+    pub link: Option<FeatureLink>,
+}
+
+/// Page a feature can be opened at
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeatureLink {
+    Settings,
+    Console,
+    NetworkDiagnostics,
+    ZoneSettings,
+    PageRules,
+    RedirectRules,
+    WafRules,
+    WorkerRoutes,
+    PagesProjects,
+    Tunnels,
+    EdgeCertificates,
+    Analytics,
+}
+
+impl FeatureLink {
+    /// Whether the page works on the selected zone, or its account
+    pub fn needs_zone(self) -> bool {
+        !matches!(
+            self,
+            FeatureLink::Settings | FeatureLink::NetworkDiagnostics
+        )
+    }
+}
+
+/// All releases, newest first
+pub fn releases() -> Vec<Release> {
+    serde_json::from_str(CHANGELOG).unwrap_or_default()
+}
+
+/// Releases newer than `version`, newest first
+pub fn releases_since(version: &str) -> Vec<Release> {
+    let seen = parse_version(version);
+    releases()
+        .into_iter()
+        .filter(|release| parse_version(&release.version) > seen)
+        .collect()
+}
+
+/// `1.10.2` as `[1, 10, 2]`, so versions compare by number
+fn parse_version(version: &str) -> Vec<u64> {
+    version
+        .split('.')
+        .map(|part| part.parse().unwrap_or_default())
+        .collect()
+}
@@ -1,18 +1,122 @@
 //! Cloudflare API client for DNS record management
 
-use std::net::{Ipv4Addr, Ipv6Addr};
+use std::collections::{HashMap, VecDeque};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use anyhow::{Result, anyhow};
-use reqwest::Client;
+use chrono::{DateTime, Local, SecondsFormat, Utc};
+use reqwest::{Client, RequestBuilder, Response};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
+use crate::api_errors;
+use crate::api_usage::{self, ApiUsage};
+use crate::demo::DemoBackend;
+use crate::network_proxy::{self, ProxyRoute};
+use crate::record_data::{self, RecordData};
+
 const API_BASE: &str = "https://api.cloudflare.com/client/v4";
+/// Environment variable that replaces [`API_BASE`], e.g. to run the app against a mock server
+const API_BASE_ENV: &str = "CLOUDFLARE_API_BASE";
+
+/// Base URL of the API, [`API_BASE`] unless [`API_BASE_ENV`] replaces it
+pub fn api_base() -> String {
+    std::env::var(API_BASE_ENV)
+        .map(|base| base.trim_end_matches('/').to_string())
+        .unwrap_or_else(|_| API_BASE.to_string())
+}
+
+/// Cloudflare's global API rate limit is 1200 requests per five minutes
+pub const RATE_LIMIT_REQUESTS: u32 = 1200;
+const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(300);
+/// Requests wait for the window to move on once the remaining budget drops to this
+const RATE_LIMIT_RESERVE: u32 = 10;
+
+/// Remaining API request budget in the current rate limit window
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimitStatus {
+    pub remaining: u32,
+    pub limit: u32,
+}
+
+/// Tracks API usage from the client's own request count and the rate limit headers
+#[derive(Debug, Default)]
+struct RateLimitState {
+    requests: VecDeque<Instant>,
+    /// Remaining budget reported by the API and when the report expires
+    reported: Option<(u32, Instant)>,
+}
+
+impl RateLimitState {
+    fn prune(&mut self, now: Instant) {
+        while self
+            .requests
+            .front()
+            .is_some_and(|t| now.duration_since(*t) >= RATE_LIMIT_WINDOW)
+        {
+            self.requests.pop_front();
+        }
+        if self.reported.is_some_and(|(_, expires)| now >= expires) {
+            self.reported = None;
+        }
+    }
+
+    fn remaining(&self) -> u32 {
+        let counted = RATE_LIMIT_REQUESTS.saturating_sub(self.requests.len() as u32);
+        match self.reported {
+            Some((reported, _)) => counted.min(reported),
+            None => counted,
+        }
+    }
+
+    /// How long to wait before sending another request without exhausting the budget
+    fn wait_time(&self, now: Instant) -> Option<Duration> {
+        if self.remaining() > RATE_LIMIT_RESERVE {
+            return None;
+        }
+
+        let window_moves = self
+            .requests
+            .front()
+            .map(|t| RATE_LIMIT_WINDOW.saturating_sub(now.duration_since(*t)));
+        let report_expires = self
+            .reported
+            .map(|(_, expires)| expires.saturating_duration_since(now));
+        window_moves.into_iter().chain(report_expires).min()
+    }
+
+    fn update_from_headers(&mut self, headers: &reqwest::header::HeaderMap, now: Instant) {
+        // e.g. `ratelimit: "default";r=1150;t=240`
+        if let Some(value) = headers.get("ratelimit").and_then(|v| v.to_str().ok()) {
+            let param = |key: &str| {
+                value
+                    .split(';')
+                    .find_map(|part| part.trim().strip_prefix(key)?.parse::<u64>().ok())
+            };
+            if let Some(remaining) = param("r=") {
+                let reset = param("t=").unwrap_or(RATE_LIMIT_WINDOW.as_secs());
+                self.reported = Some((
+                    remaining.min(u32::MAX as u64) as u32,
+                    now + Duration::from_secs(reset),
+                ));
+            }
+        }
+    }
+}
 
 /// Cloudflare API client
 #[derive(Clone)]
 pub struct CloudflareClient {
     client: Client,
     token: String,
+    api_base: String,
+    rate_limit: Arc<Mutex<RateLimitState>>,
+    /// Today's request counts sent requests are added to, with the profile of the token
+    usage: Option<(Arc<Mutex<ApiUsage>>, String)>,
+    /// Fake zones and records answered instead of the API in demo mode
+    demo: Option<Arc<Mutex<DemoBackend>>>,
 }
 
 impl CloudflareClient {
@@ -20,16 +124,108 @@ impl CloudflareClient {
         Self {
             client: Client::new(),
             token,
+            api_base: api_base(),
+            rate_limit: Arc::default(),
+            usage: None,
+            demo: None,
+        }
+    }
+
+    /// Client that never calls the API and serves fake data instead
+    pub fn demo() -> Self {
+        Self {
+            demo: Some(Arc::new(Mutex::new(DemoBackend::new()))),
+            ..Self::new(String::new())
+        }
+    }
+
+    pub fn is_demo(&self) -> bool {
+        self.demo.is_some()
+    }
+
+    /// Send requests along `route` instead of through the proxy reqwest picks up itself
+    pub fn with_proxy(mut self, route: &ProxyRoute) -> Self {
+        self.client = network_proxy::http_client(route);
+        self
+    }
+
+    /// Count the requests sent under `profile` in `usage`
+    pub fn with_usage(mut self, usage: Arc<Mutex<ApiUsage>>, profile: &str) -> Self {
+        self.usage = Some((usage, profile.to_string()));
+        self
+    }
+
+    /// Reach the API without a token and return how long it took; any answer counts, even in
+    /// demo mode
+    pub async fn check_connectivity(&self) -> Result<Duration> {
+        let started = Instant::now();
+        self.client
+            .get(format!("{}/user/tokens/verify", self.api_base))
+            .timeout(Duration::from_secs(10))
+            .send()
+            .await
+            .map_err(|e| anyhow!("{:#}", anyhow::Error::from(e)))?;
+        Ok(started.elapsed())
+    }
+
+    /// Remaining API request budget in the current rate limit window
+    pub fn rate_limit(&self) -> RateLimitStatus {
+        let mut state = self.rate_limit.lock().unwrap();
+        state.prune(Instant::now());
+        RateLimitStatus {
+            remaining: state.remaining(),
+            limit: RATE_LIMIT_REQUESTS,
+        }
+    }
+
+    /// Send a request, waiting first if the rate limit budget is nearly exhausted
+    async fn send(&self, request: RequestBuilder) -> Result<Response> {
+        loop {
+            let wait = {
+                let mut state = self.rate_limit.lock().unwrap();
+                let now = Instant::now();
+                state.prune(now);
+                let wait = state.wait_time(now);
+                if wait.is_none() {
+                    state.requests.push_back(now);
+                }
+                wait
+            };
+            match wait {
+                Some(wait) => tokio::time::sleep(wait.max(Duration::from_millis(100))).await,
+                None => break,
+            }
+        }
+
+        let (client, request) = request.build_split();
+        let request = request?;
+        if let Some((usage, profile)) = &self.usage {
+            usage.lock().unwrap().record(
+                profile,
+                api_usage::zone_of(request.url().path()),
+                Local::now().date_naive(),
+            );
         }
+
+        let response = client.execute(request).await?;
+        self.rate_limit
+            .lock()
+            .unwrap()
+            .update_from_headers(response.headers(), Instant::now());
+        Ok(response)
     }
 
     /// Verify the API token is valid
     pub async fn verify_token(&self) -> Result<bool> {
+        if self.demo.is_some() {
+            return Ok(true);
+        }
         let resp: ApiResponse<TokenVerifyResult> = self
-            .client
-            .get(format!("{}/user/tokens/verify", API_BASE))
-            .bearer_auth(&self.token)
-            .send()
+            .send(
+                self.client
+                    .get(format!("{}/user/tokens/verify", self.api_base))
+                    .bearer_auth(&self.token),
+            )
             .await?
             .json()
             .await?;
@@ -37,244 +233,1976 @@ impl CloudflareClient {
         Ok(resp.success && resp.result.map(|r| r.status == "active").unwrap_or(false))
     }
 
+    /// When the token expires, `None` if it doesn't
+    pub async fn get_token_expiry(&self) -> Result<Option<DateTime<Utc>>> {
+        if self.demo.is_some() {
+            return Ok(None);
+        }
+        let verified: TokenVerifyResult = self
+            .get_result("user/tokens/verify", "Failed to verify token")
+            .await?;
+        Ok(verified.expires_on)
+    }
+
+    /// Read the token's name and policies, which needs the User › API Tokens › Read permission
+    pub async fn get_token_details(&self) -> Result<TokenDetails> {
+        if let Some(demo) = &self.demo {
+            return Ok(demo.lock().unwrap().token_details());
+        }
+        let verified: TokenVerifyResult = self
+            .get_result("user/tokens/verify", "Failed to verify token")
+            .await?;
+        self.get_result(
+            &format!("user/tokens/{}", verified.id),
+            "Failed to read token details",
+        )
+        .await
+    }
+
     /// List all zones (domains) accessible with this token
     pub async fn list_zones(&self) -> Result<Vec<Zone>> {
+        if let Some(demo) = &self.demo {
+            return Ok(demo.lock().unwrap().zones());
+        }
         let mut all_zones = Vec::new();
         let mut page = 1;
 
         loop {
             let resp: ApiResponse<Vec<Zone>> = self
-                .client
-                .get(format!("{}/zones", API_BASE))
-                .bearer_auth(&self.token)
-                .query(&[("page", page.to_string()), ("per_page", "50".to_string())])
-                .send()
+                .send(
+                    self.client
+                        .get(format!("{}/zones", self.api_base))
+                        .bearer_auth(&self.token)
+                        .query(&[("page", page.to_string()), ("per_page", "50".to_string())]),
+                )
+                .await?
+                .json()
+                .await?;
+
+            if !resp.success {
+                return Err(anyhow!(api_errors::describe(
+                    "Failed to list zones",
+                    &resp.errors
+                )));
+            }
+
+            let zones = resp.result.unwrap_or_default();
+            let is_last_page = zones.is_empty()
+                || resp
+                    .result_info
+                    .map(|info| page >= info.total_pages)
+                    .unwrap_or(true);
+
+            all_zones.extend(zones);
+
+            if is_last_page {
+                break;
+            }
+            page += 1;
+        }
+
+        Ok(all_zones)
+    }
+
+    /// List the accounts the token can add zones to
+    pub async fn list_accounts(&self) -> Result<Vec<Account>> {
+        if let Some(demo) = &self.demo {
+            return Ok(demo.lock().unwrap().accounts());
+        }
+        self.get_result("accounts?per_page=50", "Failed to list accounts")
+            .await
+    }
+
+    /// Get a single zone, e.g. to see whether it became active
+    pub async fn get_zone(&self, zone_id: &str) -> Result<Zone> {
+        if let Some(demo) = &self.demo {
+            return demo.lock().unwrap().zone(zone_id);
+        }
+        self.get_result(&format!("zones/{}", zone_id), "Failed to get zone")
+            .await
+    }
+
+    /// Add a domain to an account as a zone using Cloudflare's nameservers
+    pub async fn create_zone(&self, name: &str, account_id: &str) -> Result<Zone> {
+        if let Some(demo) = &self.demo {
+            return demo.lock().unwrap().create_zone(name, account_id);
+        }
+        let resp: ApiResponse<Zone> = self
+            .send(
+                self.client
+                    .post(format!("{}/zones", self.api_base))
+                    .bearer_auth(&self.token)
+                    .json(&serde_json::json!({
+                        "name": name,
+                        "account": { "id": account_id },
+                        "type": "full",
+                    })),
+            )
+            .await?
+            .json()
+            .await?;
+
+        if !resp.success {
+            return Err(anyhow!(api_errors::describe(
+                "Failed to add zone",
+                &resp.errors
+            )));
+        }
+
+        resp.result.ok_or_else(|| anyhow!("No result returned"))
+    }
+
+    /// Ask Cloudflare to check a pending zone's nameservers now rather than on its own schedule
+    pub async fn check_zone_activation(&self, zone_id: &str) -> Result<()> {
+        if let Some(demo) = &self.demo {
+            demo.lock().unwrap().activate_zone(zone_id);
+            return Ok(());
+        }
+        let resp: ApiResponse<serde_json::Value> = self
+            .send(
+                self.client
+                    .put(format!(
+                        "{}/zones/{}/activation_check",
+                        self.api_base, zone_id
+                    ))
+                    .bearer_auth(&self.token),
+            )
+            .await?
+            .json()
+            .await?;
+
+        if !resp.success {
+            return Err(anyhow!(api_errors::describe(
+                "Failed to check zone activation",
+                &resp.errors
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Delete a zone with all of its records and settings
+    pub async fn delete_zone(&self, zone_id: &str) -> Result<()> {
+        if let Some(demo) = &self.demo {
+            return demo.lock().unwrap().delete_zone(zone_id);
+        }
+        let resp: ApiResponse<DeleteResult> = self
+            .send(
+                self.client
+                    .delete(format!("{}/zones/{}", self.api_base, zone_id))
+                    .bearer_auth(&self.token),
+            )
+            .await?
+            .json()
+            .await?;
+
+        if !resp.success {
+            return Err(anyhow!(api_errors::describe(
+                "Failed to delete zone",
+                &resp.errors
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// List DNS records for a zone
+    pub async fn list_dns_records(&self, zone_id: &str) -> Result<Vec<DnsRecord>> {
+        if let Some(demo) = &self.demo {
+            return demo.lock().unwrap().records(zone_id);
+        }
+        let mut all_records = Vec::new();
+        let mut page = 1;
+
+        loop {
+            let resp: ApiResponse<Vec<DnsRecord>> = self
+                .send(
+                    self.client
+                        .get(format!("{}/zones/{}/dns_records", self.api_base, zone_id))
+                        .bearer_auth(&self.token)
+                        .query(&[("page", page.to_string()), ("per_page", "100".to_string())]),
+                )
                 .await?
                 .json()
                 .await?;
 
-            if !resp.success {
-                return Err(anyhow!(
-                    "Failed to list zones: {:?}",
-                    resp.errors
-                        .first()
-                        .map(|e| e.message.clone())
-                        .unwrap_or_default()
-                ));
-            }
+            if !resp.success {
+                return Err(anyhow!(api_errors::describe(
+                    "Failed to list DNS records",
+                    &resp.errors
+                )));
+            }
+
+            let records = resp.result.unwrap_or_default();
+            let is_last_page = records.is_empty()
+                || resp
+                    .result_info
+                    .map(|info| page >= info.total_pages)
+                    .unwrap_or(true);
+
+            all_records.extend(records);
+
+            if is_last_page {
+                break;
+            }
+            page += 1;
+        }
+
+        Ok(all_records)
+    }
+
+    /// Get a single DNS record
+    pub async fn get_dns_record(&self, zone_id: &str, record_id: &str) -> Result<DnsRecord> {
+        if let Some(demo) = &self.demo {
+            return demo.lock().unwrap().record(zone_id, record_id);
+        }
+        let resp: ApiResponse<DnsRecord> = self
+            .send(
+                self.client
+                    .get(format!(
+                        "{}/zones/{}/dns_records/{}",
+                        self.api_base, zone_id, record_id
+                    ))
+                    .bearer_auth(&self.token),
+            )
+            .await?
+            .json()
+            .await?;
+
+        if !resp.success {
+            return Err(anyhow!(api_errors::describe(
+                "Failed to get DNS record",
+                &resp.errors
+            )));
+        }
+
+        resp.result.ok_or_else(|| anyhow!("No result returned"))
+    }
+
+    /// Create a new DNS record
+    pub async fn create_dns_record(
+        &self,
+        zone_id: &str,
+        record: &CreateDnsRecord,
+    ) -> Result<DnsRecord> {
+        if let Some(demo) = &self.demo {
+            return demo.lock().unwrap().create_record(zone_id, record);
+        }
+        let resp: ApiResponse<DnsRecord> = self
+            .send(
+                self.client
+                    .post(format!("{}/zones/{}/dns_records", self.api_base, zone_id))
+                    .bearer_auth(&self.token)
+                    .json(&record_body(
+                        record,
+                        Some(record.record_type),
+                        Some(&record.content),
+                        record.data.as_ref(),
+                    )?),
+            )
+            .await?
+            .json()
+            .await?;
+
+        if !resp.success {
+            return Err(anyhow!(api_errors::describe(
+                "Failed to create DNS record",
+                &resp.errors
+            )));
+        }
+
+        resp.result.ok_or_else(|| anyhow!("No result returned"))
+    }
+
+    /// Update an existing DNS record
+    pub async fn update_dns_record(
+        &self,
+        zone_id: &str,
+        record_id: &str,
+        record: &UpdateDnsRecord,
+    ) -> Result<DnsRecord> {
+        if let Some(demo) = &self.demo {
+            return demo
+                .lock()
+                .unwrap()
+                .update_record(zone_id, record_id, record);
+        }
+        let resp: ApiResponse<DnsRecord> = self
+            .send(
+                self.client
+                    .patch(format!(
+                        "{}/zones/{}/dns_records/{}",
+                        self.api_base, zone_id, record_id
+                    ))
+                    .bearer_auth(&self.token)
+                    .json(&record_body(
+                        record,
+                        record.record_type,
+                        record.content.as_deref(),
+                        record.data.as_ref(),
+                    )?),
+            )
+            .await?
+            .json()
+            .await?;
+
+        if !resp.success {
+            return Err(anyhow!(api_errors::describe(
+                "Failed to update DNS record",
+                &resp.errors
+            )));
+        }
+
+        resp.result.ok_or_else(|| anyhow!("No result returned"))
+    }
+
+    /// Delete a DNS record
+    pub async fn delete_dns_record(&self, zone_id: &str, record_id: &str) -> Result<()> {
+        if let Some(demo) = &self.demo {
+            return demo.lock().unwrap().delete_record(zone_id, record_id);
+        }
+        let resp: ApiResponse<DeleteResult> = self
+            .send(
+                self.client
+                    .delete(format!(
+                        "{}/zones/{}/dns_records/{}",
+                        self.api_base, zone_id, record_id
+                    ))
+                    .bearer_auth(&self.token),
+            )
+            .await?
+            .json()
+            .await?;
+
+        if !resp.success {
+            return Err(anyhow!(api_errors::describe(
+                "Failed to delete DNS record",
+                &resp.errors
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Delete, update and create records of a zone in one request; if any change fails, none
+    /// are applied
+    pub async fn batch_dns_records(&self, zone_id: &str, batch: &DnsRecordBatch) -> Result<()> {
+        if let Some(demo) = &self.demo {
+            return demo.lock().unwrap().batch_records(zone_id, batch);
+        }
+        let resp: ApiResponse<serde_json::Value> = self
+            .send(
+                self.client
+                    .post(format!(
+                        "{}/zones/{}/dns_records/batch",
+                        self.api_base, zone_id
+                    ))
+                    .bearer_auth(&self.token)
+                    .json(&batch.body()?),
+            )
+            .await?
+            .json()
+            .await?;
+
+        if !resp.success {
+            return Err(anyhow!(api_errors::describe(
+                "Failed to apply DNS record changes",
+                &resp.errors
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Get the DNSSEC status of a zone
+    pub async fn get_dnssec(&self, zone_id: &str) -> Result<Dnssec> {
+        if let Some(demo) = &self.demo {
+            return Ok(demo.lock().unwrap().dnssec(zone_id));
+        }
+        self.get_result(
+            &format!("zones/{}/dnssec", zone_id),
+            "Failed to get DNSSEC status",
+        )
+        .await
+    }
+
+    /// List the edge certificate packs of a zone
+    pub async fn list_certificate_packs(&self, zone_id: &str) -> Result<Vec<CertificatePack>> {
+        if let Some(demo) = &self.demo {
+            return Ok(demo.lock().unwrap().certificate_packs(zone_id));
+        }
+        self.get_result(
+            &format!("zones/{}/ssl/certificate_packs?status=all", zone_id),
+            "Failed to list certificate packs",
+        )
+        .await
+    }
+
+    /// Get an edge certificate pack of a zone, with the records that validate it while it is
+    /// pending
+    pub async fn get_certificate_pack(
+        &self,
+        zone_id: &str,
+        pack_id: &str,
+    ) -> Result<CertificatePack> {
+        if let Some(demo) = &self.demo {
+            return demo.lock().unwrap().certificate_pack(zone_id, pack_id);
+        }
+        self.get_result(
+            &format!("zones/{}/ssl/certificate_packs/{}", zone_id, pack_id),
+            "Failed to get certificate pack",
+        )
+        .await
+    }
+
+    /// Whether Cloudflare issues a free Universal SSL certificate for a zone
+    pub async fn get_universal_ssl(&self, zone_id: &str) -> Result<UniversalSsl> {
+        if let Some(demo) = &self.demo {
+            return Ok(demo.lock().unwrap().universal_ssl(zone_id));
+        }
+        self.get_result(
+            &format!("zones/{}/ssl/universal/settings", zone_id),
+            "Failed to get Universal SSL setting",
+        )
+        .await
+    }
+
+    /// Turn Universal SSL of a zone on or off, returning the setting as Cloudflare stored it
+    pub async fn set_universal_ssl(&self, zone_id: &str, enabled: bool) -> Result<UniversalSsl> {
+        if let Some(demo) = &self.demo {
+            return Ok(demo.lock().unwrap().set_universal_ssl(zone_id, enabled));
+        }
+        let resp: ApiResponse<UniversalSsl> = self
+            .send(
+                self.client
+                    .patch(format!(
+                        "{}/zones/{}/ssl/universal/settings",
+                        self.api_base, zone_id
+                    ))
+                    .bearer_auth(&self.token)
+                    .json(&serde_json::json!({ "enabled": enabled })),
+            )
+            .await?
+            .json()
+            .await?;
+
+        if !resp.success {
+            return Err(anyhow!(api_errors::describe(
+                "Failed to update Universal SSL setting",
+                &resp.errors
+            )));
+        }
+
+        resp.result.ok_or_else(|| anyhow!("No result returned"))
+    }
+
+    /// List the Origin CA certificates issued for a zone
+    pub async fn list_origin_certificates(&self, zone_id: &str) -> Result<Vec<OriginCertificate>> {
+        if self.demo.is_some() {
+            return Ok(Vec::new());
+        }
+        self.get_result(
+            &format!("certificates?zone_id={}", zone_id),
+            "Failed to list origin certificates",
+        )
+        .await
+    }
+
+    /// List the most recent audit log entries of an account that concern a zone
+    pub async fn list_audit_logs(
+        &self,
+        account_id: &str,
+        zone_name: &str,
+    ) -> Result<Vec<AuditLogEntry>> {
+        if self.demo.is_some() {
+            return Ok(Vec::new());
+        }
+        self.get_result(
+            &format!(
+                "accounts/{}/audit_logs?zone.name={}&direction=desc&per_page=100",
+                account_id, zone_name
+            ),
+            "Failed to list audit logs",
+        )
+        .await
+    }
+
+    /// List the settings of a zone, e.g. its SSL mode
+    pub async fn list_zone_settings(&self, zone_id: &str) -> Result<Vec<ZoneSetting>> {
+        if let Some(demo) = &self.demo {
+            return Ok(demo.lock().unwrap().zone_settings(zone_id));
+        }
+        self.get_result(
+            &format!("zones/{}/settings", zone_id),
+            "Failed to list zone settings",
+        )
+        .await
+    }
+
+    /// Get a single setting of a zone, e.g. `development_mode`
+    pub async fn get_zone_setting(&self, zone_id: &str, setting_id: &str) -> Result<ZoneSetting> {
+        if let Some(demo) = &self.demo {
+            return demo.lock().unwrap().zone_setting(zone_id, setting_id);
+        }
+        self.get_result(
+            &format!("zones/{}/settings/{}", zone_id, setting_id),
+            "Failed to get zone setting",
+        )
+        .await
+    }
+
+    /// Change a single setting of a zone, returning it as Cloudflare stored it
+    pub async fn update_zone_setting(
+        &self,
+        zone_id: &str,
+        setting_id: &str,
+        value: serde_json::Value,
+    ) -> Result<ZoneSetting> {
+        if let Some(demo) = &self.demo {
+            let change = ZoneSetting {
+                id: setting_id.to_string(),
+                value,
+                editable: true,
+                time_remaining: None,
+            };
+            return demo
+                .lock()
+                .unwrap()
+                .update_zone_settings(zone_id, &[change])
+                .pop()
+                .ok_or_else(|| anyhow!("Unknown zone setting {}", setting_id));
+        }
+        let resp: ApiResponse<ZoneSetting> = self
+            .send(
+                self.client
+                    .patch(format!(
+                        "{}/zones/{}/settings/{}",
+                        self.api_base, zone_id, setting_id
+                    ))
+                    .bearer_auth(&self.token)
+                    .json(&serde_json::json!({ "value": value })),
+            )
+            .await?
+            .json()
+            .await?;
+
+        if !resp.success {
+            return Err(anyhow!(api_errors::describe(
+                "Failed to update zone setting",
+                &resp.errors
+            )));
+        }
+
+        resp.result.ok_or_else(|| anyhow!("No result returned"))
+    }
+
+    /// Change zone settings, returning them as Cloudflare stored them
+    pub async fn update_zone_settings(
+        &self,
+        zone_id: &str,
+        settings: &[ZoneSetting],
+    ) -> Result<Vec<ZoneSetting>> {
+        if let Some(demo) = &self.demo {
+            return Ok(demo.lock().unwrap().update_zone_settings(zone_id, settings));
+        }
+        let resp: ApiResponse<Vec<ZoneSetting>> = self
+            .send(
+                self.client
+                    .patch(format!("{}/zones/{}/settings", self.api_base, zone_id))
+                    .bearer_auth(&self.token)
+                    .json(&serde_json::json!({ "items": settings })),
+            )
+            .await?
+            .json()
+            .await?;
+
+        if !resp.success {
+            return Err(anyhow!(api_errors::describe(
+                "Failed to update zone settings",
+                &resp.errors
+            )));
+        }
+
+        resp.result.ok_or_else(|| anyhow!("No result returned"))
+    }
+
+    /// Remove cached content of a zone from Cloudflare's edge
+    pub async fn purge_cache(&self, zone_id: &str, purge: &CachePurge) -> Result<()> {
+        if self.demo.is_some() {
+            return Ok(());
+        }
+        let resp: ApiResponse<serde_json::Value> = self
+            .send(
+                self.client
+                    .post(format!("{}/zones/{}/purge_cache", self.api_base, zone_id))
+                    .bearer_auth(&self.token)
+                    .json(&purge.body()),
+            )
+            .await?
+            .json()
+            .await?;
+
+        if !resp.success {
+            return Err(anyhow!(api_errors::describe(
+                "Failed to purge cache",
+                &resp.errors
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// List the Page Rules of a zone
+    pub async fn list_page_rules(&self, zone_id: &str) -> Result<Vec<PageRule>> {
+        if let Some(demo) = &self.demo {
+            return Ok(demo.lock().unwrap().page_rules(zone_id));
+        }
+        self.get_result(
+            &format!("zones/{}/pagerules", zone_id),
+            "Failed to list Page Rules",
+        )
+        .await
+    }
+
+    /// Create a Page Rule
+    pub async fn create_page_rule(&self, zone_id: &str, rule: &PageRule) -> Result<PageRule> {
+        if let Some(demo) = &self.demo {
+            return Ok(demo.lock().unwrap().create_page_rule(zone_id, rule));
+        }
+        let resp: ApiResponse<PageRule> = self
+            .send(
+                self.client
+                    .post(format!("{}/zones/{}/pagerules", self.api_base, zone_id))
+                    .bearer_auth(&self.token)
+                    .json(rule),
+            )
+            .await?
+            .json()
+            .await?;
+
+        if !resp.success {
+            return Err(anyhow!(api_errors::describe(
+                "Failed to create Page Rule",
+                &resp.errors
+            )));
+        }
+
+        resp.result.ok_or_else(|| anyhow!("No result returned"))
+    }
+
+    /// Replace the pattern, actions, priority and status of a Page Rule
+    pub async fn update_page_rule(
+        &self,
+        zone_id: &str,
+        rule_id: &str,
+        rule: &PageRule,
+    ) -> Result<PageRule> {
+        if let Some(demo) = &self.demo {
+            return demo
+                .lock()
+                .unwrap()
+                .update_page_rule(zone_id, rule_id, rule);
+        }
+        let resp: ApiResponse<PageRule> = self
+            .send(
+                self.client
+                    .put(format!(
+                        "{}/zones/{}/pagerules/{}",
+                        self.api_base, zone_id, rule_id
+                    ))
+                    .bearer_auth(&self.token)
+                    .json(rule),
+            )
+            .await?
+            .json()
+            .await?;
+
+        if !resp.success {
+            return Err(anyhow!(api_errors::describe(
+                "Failed to update Page Rule",
+                &resp.errors
+            )));
+        }
+
+        resp.result.ok_or_else(|| anyhow!("No result returned"))
+    }
+
+    /// Move a Page Rule in the order rules are applied in
+    pub async fn set_page_rule_priority(
+        &self,
+        zone_id: &str,
+        rule_id: &str,
+        priority: i32,
+    ) -> Result<PageRule> {
+        if let Some(demo) = &self.demo {
+            return demo
+                .lock()
+                .unwrap()
+                .set_page_rule_priority(zone_id, rule_id, priority);
+        }
+        let resp: ApiResponse<PageRule> = self
+            .send(
+                self.client
+                    .patch(format!(
+                        "{}/zones/{}/pagerules/{}",
+                        self.api_base, zone_id, rule_id
+                    ))
+                    .bearer_auth(&self.token)
+                    .json(&serde_json::json!({ "priority": priority })),
+            )
+            .await?
+            .json()
+            .await?;
+
+        if !resp.success {
+            return Err(anyhow!(api_errors::describe(
+                "Failed to reorder Page Rules",
+                &resp.errors
+            )));
+        }
+
+        resp.result.ok_or_else(|| anyhow!("No result returned"))
+    }
+
+    /// Delete a Page Rule
+    pub async fn delete_page_rule(&self, zone_id: &str, rule_id: &str) -> Result<()> {
+        if let Some(demo) = &self.demo {
+            return demo.lock().unwrap().delete_page_rule(zone_id, rule_id);
+        }
+        let resp: ApiResponse<DeleteResult> = self
+            .send(
+                self.client
+                    .delete(format!(
+                        "{}/zones/{}/pagerules/{}",
+                        self.api_base, zone_id, rule_id
+                    ))
+                    .bearer_auth(&self.token),
+            )
+            .await?
+            .json()
+            .await?;
+
+        if !resp.success {
+            return Err(anyhow!(api_errors::describe(
+                "Failed to delete Page Rule",
+                &resp.errors
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// List the Workers routes of a zone
+    pub async fn list_worker_routes(&self, zone_id: &str) -> Result<Vec<WorkerRoute>> {
+        if let Some(demo) = &self.demo {
+            return Ok(demo.lock().unwrap().worker_routes(zone_id));
+        }
+        self.get_result(
+            &format!("zones/{}/workers/routes", zone_id),
+            "Failed to list Workers routes",
+        )
+        .await
+    }
+
+    /// Create a Workers route
+    pub async fn create_worker_route(
+        &self,
+        zone_id: &str,
+        route: &WorkerRoute,
+    ) -> Result<WorkerRoute> {
+        if let Some(demo) = &self.demo {
+            return Ok(demo.lock().unwrap().create_worker_route(zone_id, route));
+        }
+        let resp: ApiResponse<WorkerRoute> = self
+            .send(
+                self.client
+                    .post(format!(
+                        "{}/zones/{}/workers/routes",
+                        self.api_base, zone_id
+                    ))
+                    .bearer_auth(&self.token)
+                    .json(route),
+            )
+            .await?
+            .json()
+            .await?;
+
+        if !resp.success {
+            return Err(anyhow!(api_errors::describe(
+                "Failed to create Workers route",
+                &resp.errors
+            )));
+        }
+
+        resp.result.ok_or_else(|| anyhow!("No result returned"))
+    }
+
+    /// Replace the pattern and Worker of a Workers route
+    pub async fn update_worker_route(
+        &self,
+        zone_id: &str,
+        route_id: &str,
+        route: &WorkerRoute,
+    ) -> Result<WorkerRoute> {
+        if let Some(demo) = &self.demo {
+            return demo
+                .lock()
+                .unwrap()
+                .update_worker_route(zone_id, route_id, route);
+        }
+        let resp: ApiResponse<WorkerRoute> = self
+            .send(
+                self.client
+                    .put(format!(
+                        "{}/zones/{}/workers/routes/{}",
+                        self.api_base, zone_id, route_id
+                    ))
+                    .bearer_auth(&self.token)
+                    .json(route),
+            )
+            .await?
+            .json()
+            .await?;
+
+        if !resp.success {
+            return Err(anyhow!(api_errors::describe(
+                "Failed to update Workers route",
+                &resp.errors
+            )));
+        }
+
+        resp.result.ok_or_else(|| anyhow!("No result returned"))
+    }
+
+    /// Delete a Workers route
+    pub async fn delete_worker_route(&self, zone_id: &str, route_id: &str) -> Result<()> {
+        if let Some(demo) = &self.demo {
+            return demo.lock().unwrap().delete_worker_route(zone_id, route_id);
+        }
+        let resp: ApiResponse<DeleteResult> = self
+            .send(
+                self.client
+                    .delete(format!(
+                        "{}/zones/{}/workers/routes/{}",
+                        self.api_base, zone_id, route_id
+                    ))
+                    .bearer_auth(&self.token),
+            )
+            .await?
+            .json()
+            .await?;
+
+        if !resp.success {
+            return Err(anyhow!(api_errors::describe(
+                "Failed to delete Workers route",
+                &resp.errors
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// List the Worker scripts of an account, for binding routes to them
+    pub async fn list_worker_scripts(&self, account_id: &str) -> Result<Vec<WorkerScript>> {
+        if let Some(demo) = &self.demo {
+            return Ok(demo.lock().unwrap().worker_scripts());
+        }
+        self.get_result(
+            &format!("accounts/{}/workers/scripts", account_id),
+            "Failed to list Worker scripts",
+        )
+        .await
+    }
+
+    /// List the Pages projects of an account with their latest deployments
+    pub async fn list_pages_projects(&self, account_id: &str) -> Result<Vec<PagesProject>> {
+        if let Some(demo) = &self.demo {
+            return Ok(demo.lock().unwrap().pages_projects());
+        }
+        self.get_result(
+            &format!("accounts/{}/pages/projects", account_id),
+            "Failed to list Pages projects",
+        )
+        .await
+    }
+
+    /// List the deployments of a Pages project, newest first
+    pub async fn list_pages_deployments(
+        &self,
+        account_id: &str,
+        project: &str,
+    ) -> Result<Vec<PagesDeployment>> {
+        if let Some(demo) = &self.demo {
+            return demo.lock().unwrap().pages_deployments(project);
+        }
+        self.get_result(
+            &format!(
+                "accounts/{}/pages/projects/{}/deployments",
+                account_id, project
+            ),
+            "Failed to list deployments",
+        )
+        .await
+    }
+
+    /// Run a deployment of a Pages project again, e.g. after it failed
+    pub async fn retry_pages_deployment(
+        &self,
+        account_id: &str,
+        project: &str,
+        deployment_id: &str,
+    ) -> Result<PagesDeployment> {
+        if let Some(demo) = &self.demo {
+            return demo
+                .lock()
+                .unwrap()
+                .retry_pages_deployment(project, deployment_id);
+        }
+        let resp: ApiResponse<PagesDeployment> = self
+            .send(
+                self.client
+                    .post(format!(
+                        "{}/accounts/{}/pages/projects/{}/deployments/{}/retry",
+                        self.api_base, account_id, project, deployment_id
+                    ))
+                    .bearer_auth(&self.token),
+            )
+            .await?
+            .json()
+            .await?;
+
+        if !resp.success {
+            return Err(anyhow!(api_errors::describe(
+                "Failed to retry deployment",
+                &resp.errors
+            )));
+        }
+
+        resp.result.ok_or_else(|| anyhow!("No result returned"))
+    }
+
+    /// Delete a deployment of a Pages project
+    pub async fn delete_pages_deployment(
+        &self,
+        account_id: &str,
+        project: &str,
+        deployment_id: &str,
+    ) -> Result<()> {
+        if let Some(demo) = &self.demo {
+            return demo
+                .lock()
+                .unwrap()
+                .delete_pages_deployment(project, deployment_id);
+        }
+        let resp: ApiResponse<serde_json::Value> = self
+            .send(
+                self.client
+                    .delete(format!(
+                        "{}/accounts/{}/pages/projects/{}/deployments/{}",
+                        self.api_base, account_id, project, deployment_id
+                    ))
+                    .bearer_auth(&self.token),
+            )
+            .await?
+            .json()
+            .await?;
+
+        if !resp.success {
+            return Err(anyhow!(api_errors::describe(
+                "Failed to delete deployment",
+                &resp.errors
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// List the Cloudflare Tunnels of an account that weren't deleted, with their connections
+    pub async fn list_tunnels(&self, account_id: &str) -> Result<Vec<Tunnel>> {
+        if let Some(demo) = &self.demo {
+            return Ok(demo.lock().unwrap().tunnels());
+        }
+        self.get_result(
+            &format!("accounts/{}/cfd_tunnel?is_deleted=false", account_id),
+            "Failed to list tunnels",
+        )
+        .await
+    }
+
+    /// Ingress rules of a tunnel configured in the dashboard; tunnels configured with a local
+    /// file have none
+    pub async fn tunnel_ingress(
+        &self,
+        account_id: &str,
+        tunnel_id: &str,
+    ) -> Result<Vec<TunnelIngress>> {
+        if let Some(demo) = &self.demo {
+            return Ok(demo.lock().unwrap().tunnel_ingress(tunnel_id));
+        }
+        let configuration: TunnelConfiguration = self
+            .get_result(
+                &format!(
+                    "accounts/{}/cfd_tunnel/{}/configurations",
+                    account_id, tunnel_id
+                ),
+                "Failed to get the tunnel configuration",
+            )
+            .await?;
+        Ok(configuration
+            .config
+            .map(|config| config.ingress)
+            .unwrap_or_default())
+    }
+
+    /// Rules of a zone's ruleset for `phase`, e.g. `http_request_dynamic_redirect`
+    pub async fn list_phase_rules(&self, zone_id: &str, phase: &str) -> Result<Vec<RulesetRule>> {
+        if let Some(demo) = &self.demo {
+            return Ok(demo.lock().unwrap().phase_rules(zone_id, phase));
+        }
+        let resp: ApiResponse<Ruleset> = self
+            .send(
+                self.client
+                    .get(format!(
+                        "{}/zones/{}/rulesets/phases/{}/entrypoint",
+                        self.api_base, zone_id, phase
+                    ))
+                    .bearer_auth(&self.token),
+            )
+            .await?
+            .json()
+            .await?;
+
+        if !resp.success {
+            // Zones get the ruleset of a phase with its first rule
+            if resp.errors.iter().any(|e| e.code == RULESET_NOT_FOUND) {
+                return Ok(Vec::new());
+            }
+            return Err(anyhow!(api_errors::describe(
+                "Failed to list rules",
+                &resp.errors
+            )));
+        }
+
+        Ok(resp.result.map(|ruleset| ruleset.rules).unwrap_or_default())
+    }
+
+    /// Replace all rules of a zone's ruleset for `phase`, returning them as Cloudflare stored them
+    pub async fn update_phase_rules(
+        &self,
+        zone_id: &str,
+        phase: &str,
+        rules: &[RulesetRule],
+    ) -> Result<Vec<RulesetRule>> {
+        if let Some(demo) = &self.demo {
+            return Ok(demo
+                .lock()
+                .unwrap()
+                .update_phase_rules(zone_id, phase, rules));
+        }
+        let resp: ApiResponse<Ruleset> = self
+            .send(
+                self.client
+                    .put(format!(
+                        "{}/zones/{}/rulesets/phases/{}/entrypoint",
+                        self.api_base, zone_id, phase
+                    ))
+                    .bearer_auth(&self.token)
+                    .json(&serde_json::json!({ "rules": rules })),
+            )
+            .await?
+            .json()
+            .await?;
+
+        if !resp.success {
+            return Err(anyhow!(api_errors::describe(
+                "Failed to update rules",
+                &resp.errors
+            )));
+        }
+
+        Ok(resp.result.map(|ruleset| ruleset.rules).unwrap_or_default())
+    }
+
+    /// Requests, bandwidth, cache hits and threats of a zone from `since` on, summed per hour or
+    /// per day, oldest first
+    pub async fn zone_traffic(
+        &self,
+        zone_id: &str,
+        since: DateTime<Utc>,
+        hourly: bool,
+    ) -> Result<Vec<TrafficPoint>> {
+        if let Some(demo) = &self.demo {
+            return Ok(demo.lock().unwrap().zone_traffic(since, hourly));
+        }
+        let (dataset, time_field, time_type, since) = if hourly {
+            (
+                "httpRequests1hGroups",
+                "datetime",
+                "Time",
+                since.to_rfc3339_opts(SecondsFormat::Secs, true),
+            )
+        } else {
+            (
+                "httpRequests1dGroups",
+                "date",
+                "Date",
+                since.format("%Y-%m-%d").to_string(),
+            )
+        };
+        let query = format!(
+            "query ($zoneTag: string, $since: {time_type}) {{
+                viewer {{
+                    zones(filter: {{ zoneTag: $zoneTag }}) {{
+                        groups: {dataset}(
+                            limit: 1000
+                            filter: {{ {time_field}_geq: $since }}
+                            orderBy: [{time_field}_ASC]
+                        ) {{
+                            dimensions {{ timeslot: {time_field} }}
+                            sum {{ requests bytes cachedRequests cachedBytes threats }}
+                        }}
+                    }}
+                }}
+            }}"
+        );
+        let data: TrafficData = self
+            .graphql(
+                &query,
+                serde_json::json!({ "zoneTag": zone_id, "since": since }),
+                "Failed to load analytics",
+            )
+            .await?;
+
+        Ok(data
+            .viewer
+            .zones
+            .into_iter()
+            .next()
+            .map(|zone| zone.groups)
+            .unwrap_or_default())
+    }
+
+    /// DNS queries answered for each name and type of a zone from `since` on, most queried first
+    pub async fn dns_query_counts(
+        &self,
+        zone_id: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<DnsQueryCount>> {
+        if let Some(demo) = &self.demo {
+            return demo.lock().unwrap().dns_query_counts(zone_id);
+        }
+        let resp: ApiResponse<DnsAnalyticsReport> = self
+            .send(
+                self.client
+                    .get(format!(
+                        "{}/zones/{}/dns_analytics/report",
+                        self.api_base, zone_id
+                    ))
+                    .bearer_auth(&self.token)
+                    .query(&[
+                        ("dimensions", "queryName,queryType".to_string()),
+                        ("metrics", "queryCount".to_string()),
+                        ("sort", "-queryCount".to_string()),
+                        ("limit", "10000".to_string()),
+                        ("since", since.to_rfc3339_opts(SecondsFormat::Secs, true)),
+                        (
+                            "until",
+                            Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+                        ),
+                    ]),
+            )
+            .await?
+            .json()
+            .await?;
+
+        if !resp.success {
+            return Err(anyhow!(api_errors::describe(
+                "Failed to load DNS analytics",
+                &resp.errors
+            )));
+        }
+
+        Ok(resp.result.map(|report| report.data).unwrap_or_default())
+    }
+
+    /// Run a query against the GraphQL Analytics API and return its data, using `context` to
+    /// describe failures
+    async fn graphql<T: DeserializeOwned>(
+        &self,
+        query: &str,
+        variables: serde_json::Value,
+        context: &str,
+    ) -> Result<T> {
+        let resp: GraphqlResponse<T> = self
+            .send(
+                self.client
+                    .post(format!("{}/graphql", self.api_base))
+                    .bearer_auth(&self.token)
+                    .json(&serde_json::json!({ "query": query, "variables": variables })),
+            )
+            .await?
+            .json()
+            .await?;
+
+        if let Some(error) = resp.errors.unwrap_or_default().first() {
+            return Err(anyhow!("{}: {}", context, error.message));
+        }
+
+        resp.data.ok_or_else(|| anyhow!("No result returned"))
+    }
+
+    /// GET an endpoint and return its result, using `context` to describe failures
+    async fn get_result<T: DeserializeOwned>(&self, path: &str, context: &str) -> Result<T> {
+        let resp: ApiResponse<T> = self
+            .send(
+                self.client
+                    .get(format!("{}/{}", self.api_base, path))
+                    .bearer_auth(&self.token),
+            )
+            .await?
+            .json()
+            .await?;
+
+        if !resp.success {
+            return Err(anyhow!(api_errors::describe(context, &resp.errors)));
+        }
+
+        resp.result.ok_or_else(|| anyhow!("No result returned"))
+    }
+}
+
+// API Response types
+
+#[derive(Debug, Deserialize)]
+pub struct ApiResponse<T> {
+    pub success: bool,
+    pub result: Option<T>,
+    #[serde(default)]
+    pub errors: Vec<ApiError>,
+    pub result_info: Option<ResultInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ApiError {
+    pub code: i32,
+    pub message: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+pub struct ResultInfo {
+    pub page: u32,
+    pub per_page: u32,
+    pub count: u32,
+    pub total_count: u32,
+    pub total_pages: u32,
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+pub struct TokenVerifyResult {
+    pub id: String,
+    pub status: String,
+    /// `None` for tokens that don't expire
+    #[serde(default)]
+    pub expires_on: Option<DateTime<Utc>>,
+}
+
+/// An API token and the permissions it grants
+#[derive(Debug, Clone, Deserialize)]
+pub struct TokenDetails {
+    pub name: String,
+    #[serde(default)]
+    pub policies: Vec<TokenPolicy>,
+}
+
+/// Permission groups a token is granted or denied on some resources
+#[derive(Debug, Clone, Deserialize)]
+pub struct TokenPolicy {
+    /// `allow` or `deny`
+    pub effect: String,
+    #[serde(default)]
+    pub permission_groups: Vec<PermissionGroup>,
+    /// Resource names, e.g. `com.cloudflare.api.account.zone.*`, mapped to `*` or to the
+    /// resources nested in them
+    #[serde(default)]
+    pub resources: HashMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PermissionGroup {
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+pub struct DeleteResult {
+    pub id: String,
+}
+
+// Zone types
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Zone {
+    pub id: String,
+    pub name: String,
+    pub status: String,
+    pub account: ZoneAccount,
+    #[serde(default)]
+    pub paused: bool,
+    #[serde(default)]
+    pub plan: Option<ZonePlan>,
+    /// Cloudflare nameservers to set at the registrar
+    #[serde(default)]
+    pub name_servers: Vec<String>,
+    /// Nameservers the domain used before it was added to Cloudflare
+    #[serde(default)]
+    pub original_name_servers: Option<Vec<String>>,
+    /// Registrar of the domain when it was added to Cloudflare
+    #[serde(default)]
+    pub original_registrar: Option<String>,
+    /// DNS host of the domain when it was added to Cloudflare
+    #[serde(default)]
+    pub original_dnshost: Option<String>,
+    /// When the zone was last activated
+    #[serde(default)]
+    pub activated_on: Option<String>,
+}
+
+impl Zone {
+    /// The zone's overview in the Cloudflare dashboard
+    pub fn dashboard_url(&self) -> String {
+        format!(
+            "https://dash.cloudflare.com/{}/{}",
+            self.account.id, self.name
+        )
+    }
+
+    /// Human readable status, where a paused zone is paused whatever its status
+    pub fn status_label(&self) -> &'static str {
+        if self.paused {
+            return "Paused";
+        }
+        match self.status.as_str() {
+            "active" => "Active",
+            "pending" => "Pending",
+            "initializing" => "Initializing",
+            "moved" => "Moved",
+            "deleted" => "Deleted",
+            "deactivated" => "Deactivated",
+            _ => "Unknown",
+        }
+    }
+
+    /// What the status means for the zone's traffic, shown in the zone's details
+    pub fn status_description(&self) -> &'static str {
+        if self.paused {
+            return "DNS answers point to the origin directly: traffic bypasses Cloudflare's proxy, \
+                    cache and security";
+        }
+        match self.status.as_str() {
+            "active" => "Cloudflare answers the zone's DNS queries",
+            "pending" | "initializing" => {
+                "Waiting for the registrar to use the assigned nameservers; until then DNS \
+                 queries go to the original nameservers"
+            }
+            "moved" => {
+                "The registrar no longer uses the assigned nameservers, so Cloudflare stopped \
+                 answering the zone's DNS queries"
+            }
+            "deactivated" => "The zone was deactivated and doesn't serve DNS queries",
+            _ => "Cloudflare reported a status this app doesn't know",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ZonePlan {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ZoneAccount {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Account {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Dnssec {
+    /// One of `active`, `pending`, `disabled`, `pending-disabled` or `error`
+    pub status: String,
+}
+
+// Audit log types
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AuditLogEntry {
+    pub id: String,
+    pub when: String,
+    pub action: AuditLogAction,
+    pub actor: AuditLogActor,
+    /// How the change was made, e.g. `UI` or `API`
+    #[serde(default)]
+    pub interface: Option<String>,
+    #[serde(default)]
+    pub resource: Option<AuditLogResource>,
+    /// Details of the change, e.g. the name and type of a DNS record
+    #[serde(default)]
+    pub metadata: Option<serde_json::Value>,
+}
+
+impl AuditLogEntry {
+    /// Whether the entry is a DNS record being created, changed or deleted
+    pub fn is_record_change(&self) -> bool {
+        self.action.action_type.starts_with("rec_")
+            || self
+                .resource
+                .as_ref()
+                .and_then(|resource| resource.resource_type.as_deref())
+                .is_some_and(|resource_type| resource_type.to_lowercase().contains("dns"))
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AuditLogAction {
+    /// e.g. `rec_add`, `rec_set` or `rec_del`
+    #[serde(rename = "type")]
+    pub action_type: String,
+    #[serde(default)]
+    pub result: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AuditLogActor {
+    #[serde(default)]
+    pub email: Option<String>,
+    /// e.g. `user` or `cloudflare`
+    #[serde(rename = "type", default)]
+    pub actor_type: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AuditLogResource {
+    #[serde(rename = "type", default)]
+    pub resource_type: Option<String>,
+}
+
+// Zone setting types
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ZoneSetting {
+    /// e.g. `ssl` or `always_use_https`
+    pub id: String,
+    /// Mostly a string like `on`, `off` or `strict`
+    pub value: serde_json::Value,
+    /// Settings the zone's plan doesn't allow to change are not editable
+    #[serde(default = "default_editable", skip_serializing)]
+    pub editable: bool,
+    /// Seconds until the setting turns itself off again, only set for development mode
+    #[serde(default, skip_serializing)]
+    pub time_remaining: Option<i64>,
+}
+
+fn default_editable() -> bool {
+    true
+}
+
+// Cache types
+
+/// What to remove from a zone's cache
+#[derive(Debug, Clone, PartialEq)]
+pub enum CachePurge {
+    Everything,
+    /// Full URLs, e.g. `https://www.example.com/app.css`
+    Urls(Vec<String>),
+    /// URLs without scheme that everything below is purged of, e.g. `www.example.com/assets/`
+    Prefixes(Vec<String>),
+    Hosts(Vec<String>),
+    /// Values of the `Cache-Tag` response header
+    Tags(Vec<String>),
+}
+
+impl CachePurge {
+    fn body(&self) -> serde_json::Value {
+        match self {
+            CachePurge::Everything => serde_json::json!({ "purge_everything": true }),
+            CachePurge::Urls(urls) => serde_json::json!({ "files": urls }),
+            CachePurge::Prefixes(prefixes) => serde_json::json!({ "prefixes": prefixes }),
+            CachePurge::Hosts(hosts) => serde_json::json!({ "hosts": hosts }),
+            CachePurge::Tags(tags) => serde_json::json!({ "tags": tags }),
+        }
+    }
+}
+
+// Page Rule types
+
+/// Actions applied to requests whose URL matches a pattern
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PageRule {
+    #[serde(default, skip_serializing)]
+    pub id: String,
+    pub targets: Vec<PageRuleTarget>,
+    pub actions: Vec<PageRuleAction>,
+    /// Rules with a higher priority are applied first
+    pub priority: i32,
+    /// `active` or `disabled`
+    pub status: String,
+}
+
+impl PageRule {
+    /// URL pattern the rule matches, e.g. `example.com/blog/*`
+    pub fn pattern(&self) -> &str {
+        self.targets
+            .first()
+            .map(|target| target.constraint.value.as_str())
+            .unwrap_or_default()
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.status == "active"
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PageRuleTarget {
+    /// Always `url`
+    pub target: String,
+    pub constraint: PageRuleConstraint,
+}
+
+impl PageRuleTarget {
+    /// Target matching URLs against a pattern with `*` wildcards
+    pub fn matching(pattern: &str) -> Self {
+        Self {
+            target: "url".to_string(),
+            constraint: PageRuleConstraint {
+                operator: "matches".to_string(),
+                value: pattern.to_string(),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PageRuleConstraint {
+    pub operator: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PageRuleAction {
+    /// e.g. `forwarding_url` or `cache_level`
+    pub id: String,
+    /// Actions like `always_use_https` have no value
+    #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
+    pub value: serde_json::Value,
+}
+
+// Workers route types
+
+/// URLs of a zone whose requests run a Worker
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkerRoute {
+    #[serde(default, skip_serializing)]
+    pub id: String,
+    /// e.g. `example.com/api/*`
+    pub pattern: String,
+    /// Worker the requests run; without one, Workers don't run on the pattern
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub script: Option<String>,
+}
+
+/// A Worker of an account
+#[derive(Debug, Clone, Deserialize)]
+pub struct WorkerScript {
+    /// Name of the Worker
+    pub id: String,
+}
+
+// Pages types
+
+/// A Cloudflare Pages project of an account
+#[derive(Debug, Clone, Deserialize)]
+pub struct PagesProject {
+    pub name: String,
+    /// Host of the production deployment, e.g. `my-site.pages.dev`
+    #[serde(default)]
+    pub subdomain: String,
+    /// Custom domains the project is served on
+    #[serde(default)]
+    pub domains: Vec<String>,
+    #[serde(default)]
+    pub latest_deployment: Option<PagesDeployment>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PagesDeployment {
+    pub id: String,
+    /// URL of this deployment
+    #[serde(default)]
+    pub url: String,
+    /// `production` or `preview`
+    #[serde(default)]
+    pub environment: String,
+    #[serde(default)]
+    pub created_on: Option<String>,
+    /// Stage the deployment is at, or ended at
+    #[serde(default)]
+    pub latest_stage: Option<PagesStage>,
+    #[serde(default)]
+    pub deployment_trigger: Option<PagesTrigger>,
+}
+
+impl PagesDeployment {
+    /// Metadata of the commit the deployment was built from
+    pub fn source(&self) -> Option<&PagesTriggerMetadata> {
+        self.deployment_trigger.as_ref()?.metadata.as_ref()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PagesStage {
+    /// e.g. `build` or `deploy`
+    pub name: String,
+    /// `idle`, `active`, `success`, `failure` or `canceled`
+    pub status: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PagesTrigger {
+    #[serde(default)]
+    pub metadata: Option<PagesTriggerMetadata>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PagesTriggerMetadata {
+    #[serde(default)]
+    pub branch: String,
+    #[serde(default)]
+    pub commit_hash: String,
+    #[serde(default)]
+    pub commit_message: String,
+}
+
+// Tunnel types
+
+/// A Cloudflare Tunnel, connecting origins to Cloudflare through `cloudflared`
+#[derive(Debug, Clone, Deserialize)]
+pub struct Tunnel {
+    pub id: String,
+    pub name: String,
+    /// `healthy`, `degraded`, `down` or `inactive`
+    #[serde(default)]
+    pub status: String,
+    /// When the tunnel lost its last connection, if it has none
+    #[serde(default)]
+    pub conns_inactive_at: Option<String>,
+    #[serde(default)]
+    pub connections: Vec<TunnelConnection>,
+}
+
+/// A connection of a `cloudflared` instance to a Cloudflare data center
+#[derive(Debug, Clone, Deserialize)]
+pub struct TunnelConnection {
+    /// The connector, the `cloudflared` instance, the connection belongs to
+    #[serde(default)]
+    pub client_id: String,
+    /// Version of `cloudflared`, e.g. `2024.6.1`
+    #[serde(default)]
+    pub client_version: String,
+    /// Data center, e.g. `fra06`
+    #[serde(default)]
+    pub colo_name: String,
+    #[serde(default)]
+    pub origin_ip: String,
+    #[serde(default)]
+    pub is_pending_reconnect: bool,
+}
 
-            let zones = resp.result.unwrap_or_default();
-            let is_last_page = zones.is_empty()
-                || resp
-                    .result_info
-                    .map(|info| page >= info.total_pages)
-                    .unwrap_or(true);
+#[derive(Debug, Deserialize)]
+struct TunnelConfiguration {
+    #[serde(default)]
+    config: Option<TunnelConfig>,
+}
 
-            all_zones.extend(zones);
+#[derive(Debug, Deserialize)]
+struct TunnelConfig {
+    #[serde(default)]
+    ingress: Vec<TunnelIngress>,
+}
 
-            if is_last_page {
-                break;
-            }
-            page += 1;
-        }
+/// A rule routing requests for a hostname to a service behind a tunnel
+#[derive(Debug, Clone, Deserialize)]
+pub struct TunnelIngress {
+    /// `None` for the catch-all rule at the end
+    #[serde(default)]
+    pub hostname: Option<String>,
+    /// e.g. `http://localhost:8080`
+    pub service: String,
+}
 
-        Ok(all_zones)
-    }
+// Ruleset types
 
-    /// List DNS records for a zone
-    pub async fn list_dns_records(&self, zone_id: &str) -> Result<Vec<DnsRecord>> {
-        let mut all_records = Vec::new();
-        let mut page = 1;
+/// Error code of a phase that has no ruleset yet
+const RULESET_NOT_FOUND: i32 = 10003;
 
-        loop {
-            let resp: ApiResponse<Vec<DnsRecord>> = self
-                .client
-                .get(format!("{}/zones/{}/dns_records", API_BASE, zone_id))
-                .bearer_auth(&self.token)
-                .query(&[("page", page.to_string()), ("per_page", "100".to_string())])
-                .send()
-                .await?
-                .json()
-                .await?;
+#[derive(Debug, Deserialize)]
+pub struct Ruleset {
+    #[serde(default)]
+    pub rules: Vec<RulesetRule>,
+}
 
-            if !resp.success {
-                return Err(anyhow!(
-                    "Failed to list DNS records: {:?}",
-                    resp.errors
-                        .first()
-                        .map(|e| e.message.clone())
-                        .unwrap_or_default()
-                ));
-            }
+/// A rule of a ruleset: an action taken on requests matching an expression
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RulesetRule {
+    /// Empty for rules that haven't been created yet
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub id: String,
+    /// Filter expression in Cloudflare's Rules language
+    pub expression: String,
+    #[serde(default)]
+    pub description: String,
+    /// e.g. `redirect`
+    pub action: String,
+    #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
+    pub action_parameters: serde_json::Value,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
 
-            let records = resp.result.unwrap_or_default();
-            let is_last_page = records.is_empty()
-                || resp
-                    .result_info
-                    .map(|info| page >= info.total_pages)
-                    .unwrap_or(true);
+fn default_enabled() -> bool {
+    true
+}
 
-            all_records.extend(records);
+// DNS analytics types
 
-            if is_last_page {
-                break;
-            }
-            page += 1;
-        }
+#[derive(Debug, Deserialize)]
+struct DnsAnalyticsReport {
+    #[serde(default)]
+    data: Vec<DnsQueryCount>,
+}
 
-        Ok(all_records)
-    }
+/// DNS queries answered for a name and type
+#[derive(Debug, Clone, Deserialize)]
+#[serde(try_from = "DnsAnalyticsRow")]
+pub struct DnsQueryCount {
+    pub name: String,
+    /// e.g. `A`, or a type the app doesn't manage such as `SOA`
+    pub record_type: String,
+    pub queries: u64,
+}
 
-    /// Create a new DNS record
-    pub async fn create_dns_record(
-        &self,
-        zone_id: &str,
-        record: &CreateDnsRecord,
-    ) -> Result<DnsRecord> {
-        let resp: ApiResponse<DnsRecord> = self
-            .client
-            .post(format!("{}/zones/{}/dns_records", API_BASE, zone_id))
-            .bearer_auth(&self.token)
-            .json(record)
-            .send()
-            .await?
-            .json()
-            .await?;
+/// Row of a report, with values in the order of the requested dimensions and metrics
+#[derive(Debug, Deserialize)]
+struct DnsAnalyticsRow {
+    dimensions: Vec<String>,
+    metrics: Vec<f64>,
+}
 
-        if !resp.success {
-            return Err(anyhow!(
-                "Failed to create DNS record: {:?}",
-                resp.errors
-                    .first()
-                    .map(|e| e.message.clone())
-                    .unwrap_or_default()
-            ));
-        }
+impl TryFrom<DnsAnalyticsRow> for DnsQueryCount {
+    type Error = String;
 
-        resp.result.ok_or_else(|| anyhow!("No result returned"))
+    fn try_from(row: DnsAnalyticsRow) -> std::result::Result<Self, Self::Error> {
+        match (row.dimensions.as_slice(), row.metrics.as_slice()) {
+            ([name, record_type], [queries]) => Ok(Self {
+                name: name.clone(),
+                record_type: record_type.clone(),
+                queries: *queries as u64,
+            }),
+            _ => Err("Unexpected DNS analytics row".to_string()),
+        }
     }
+}
 
-    /// Update an existing DNS record
-    pub async fn update_dns_record(
-        &self,
-        zone_id: &str,
-        record_id: &str,
-        record: &UpdateDnsRecord,
-    ) -> Result<DnsRecord> {
-        let resp: ApiResponse<DnsRecord> = self
-            .client
-            .patch(format!(
-                "{}/zones/{}/dns_records/{}",
-                API_BASE, zone_id, record_id
-            ))
-            .bearer_auth(&self.token)
-            .json(record)
-            .send()
-            .await?
-            .json()
-            .await?;
+// GraphQL Analytics types
 
-        if !resp.success {
-            return Err(anyhow!(
-                "Failed to update DNS record: {:?}",
-                resp.errors
-                    .first()
-                    .map(|e| e.message.clone())
-                    .unwrap_or_default()
-            ));
-        }
+#[derive(Debug, Deserialize)]
+struct GraphqlResponse<T> {
+    data: Option<T>,
+    /// `null` rather than missing when the query succeeded
+    #[serde(default)]
+    errors: Option<Vec<GraphqlError>>,
+}
 
-        resp.result.ok_or_else(|| anyhow!("No result returned"))
-    }
+#[derive(Debug, Deserialize)]
+struct GraphqlError {
+    message: String,
+}
 
-    /// Delete a DNS record
-    pub async fn delete_dns_record(&self, zone_id: &str, record_id: &str) -> Result<()> {
-        let resp: ApiResponse<DeleteResult> = self
-            .client
-            .delete(format!(
-                "{}/zones/{}/dns_records/{}",
-                API_BASE, zone_id, record_id
-            ))
-            .bearer_auth(&self.token)
-            .send()
-            .await?
-            .json()
-            .await?;
+#[derive(Debug, Deserialize)]
+struct TrafficData {
+    viewer: TrafficViewer,
+}
 
-        if !resp.success {
-            return Err(anyhow!(
-                "Failed to delete DNS record: {:?}",
-                resp.errors
-                    .first()
-                    .map(|e| e.message.clone())
-                    .unwrap_or_default()
-            ));
-        }
+#[derive(Debug, Deserialize)]
+struct TrafficViewer {
+    zones: Vec<TrafficZone>,
+}
 
-        Ok(())
-    }
+#[derive(Debug, Deserialize)]
+struct TrafficZone {
+    groups: Vec<TrafficPoint>,
 }
 
-// API Response types
+/// Traffic of a zone in one hour or day
+#[derive(Debug, Clone, Deserialize)]
+#[serde(from = "TrafficGroup")]
+pub struct TrafficPoint {
+    /// Start of the hour, e.g. `2024-05-01T13:00:00Z`, or the day, e.g. `2024-05-01`
+    pub timeslot: String,
+    pub requests: u64,
+    pub bytes: u64,
+    pub cached_requests: u64,
+    pub cached_bytes: u64,
+    pub threats: u64,
+}
 
 #[derive(Debug, Deserialize)]
-pub struct ApiResponse<T> {
-    pub success: bool,
-    pub result: Option<T>,
-    #[serde(default)]
-    pub errors: Vec<ApiError>,
-    pub result_info: Option<ResultInfo>,
+struct TrafficGroup {
+    dimensions: TrafficDimensions,
+    sum: TrafficSum,
 }
 
 #[derive(Debug, Deserialize)]
-#[allow(dead_code)]
-pub struct ApiError {
-    pub code: i32,
-    pub message: String,
+struct TrafficDimensions {
+    timeslot: String,
 }
 
 #[derive(Debug, Deserialize)]
-#[allow(dead_code)]
-pub struct ResultInfo {
-    pub page: u32,
-    pub per_page: u32,
-    pub count: u32,
-    pub total_count: u32,
-    pub total_pages: u32,
+#[serde(rename_all = "camelCase")]
+struct TrafficSum {
+    requests: u64,
+    bytes: u64,
+    cached_requests: u64,
+    cached_bytes: u64,
+    threats: u64,
 }
 
-#[derive(Debug, Deserialize)]
-#[allow(dead_code)]
-pub struct TokenVerifyResult {
+impl From<TrafficGroup> for TrafficPoint {
+    fn from(group: TrafficGroup) -> Self {
+        Self {
+            timeslot: group.dimensions.timeslot,
+            requests: group.sum.requests,
+            bytes: group.sum.bytes,
+            cached_requests: group.sum.cached_requests,
+            cached_bytes: group.sum.cached_bytes,
+            threats: group.sum.threats,
+        }
+    }
+}
+
+// Certificate types
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CertificatePack {
     pub id: String,
+    #[serde(rename = "type")]
+    pub pack_type: String,
+    #[serde(default)]
+    pub hosts: Vec<String>,
     pub status: String,
+    #[serde(default)]
+    pub certificates: Vec<Certificate>,
+    /// e.g. `lets_encrypt` or `google`
+    #[serde(default)]
+    pub certificate_authority: Option<String>,
+    /// `txt`, `http` or `email`
+    #[serde(default)]
+    pub validation_method: Option<String>,
+    #[serde(default)]
+    pub validity_days: Option<u32>,
+    /// Records that prove control of the hosts while the pack is pending validation
+    #[serde(default)]
+    pub validation_records: Vec<ValidationRecord>,
+    #[serde(default)]
+    pub validation_errors: Vec<ValidationError>,
 }
 
-#[derive(Debug, Deserialize)]
-#[allow(dead_code)]
-pub struct DeleteResult {
+#[derive(Debug, Clone, Deserialize)]
+pub struct Certificate {
     pub id: String,
+    #[serde(default)]
+    pub hosts: Vec<String>,
+    pub status: String,
+    #[serde(default)]
+    pub expires_on: Option<String>,
+    #[serde(default)]
+    pub issuer: Option<String>,
+    /// e.g. `ECDSAWithSHA256`
+    #[serde(default)]
+    pub signature: Option<String>,
 }
 
-// Zone types
+#[derive(Debug, Clone, Deserialize)]
+pub struct ValidationRecord {
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub txt_name: Option<String>,
+    #[serde(default)]
+    pub txt_value: Option<String>,
+    #[serde(default)]
+    pub http_url: Option<String>,
+    #[serde(default)]
+    pub http_body: Option<String>,
+}
 
 #[derive(Debug, Clone, Deserialize)]
-pub struct Zone {
-    pub id: String,
-    pub name: String,
-    pub status: String,
-    pub account: ZoneAccount,
+pub struct ValidationError {
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct UniversalSsl {
+    pub enabled: bool,
 }
 
 #[derive(Debug, Clone, Deserialize)]
-pub struct ZoneAccount {
+pub struct OriginCertificate {
     pub id: String,
-    pub name: String,
+    #[serde(default)]
+    pub hostnames: Vec<String>,
+    pub expires_on: String,
+}
+
+/// JSON body of a record, where types whose content is made of several fields send their
+/// `data` instead: the record's own if it has the type's fields, or else parsed from `content`
+fn record_body(
+    record: &impl Serialize,
+    record_type: Option<DnsRecordType>,
+    content: Option<&str>,
+    data: Option<&RecordData>,
+) -> Result<serde_json::Value> {
+    let mut body = serde_json::to_value(record)?;
+    let Some(record_type) = record_type else {
+        return Ok(body);
+    };
+    let data = match (data.filter(|data| data.fits(record_type)), content) {
+        (Some(data), _) => Some(data.clone()),
+        (None, Some(content)) => {
+            record_data::parse(record_type, content).map_err(|e| anyhow!(e))?
+        }
+        (None, None) => None,
+    };
+    if let Some(fields) = body.as_object_mut() {
+        fields.remove("data");
+        if let Some(data) = data {
+            fields.remove("content");
+            fields.insert("data".to_string(), serde_json::to_value(data)?);
+        }
+    }
+    Ok(body)
 }
 
 // DNS Record types
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct DnsRecord {
     pub id: String,
     #[serde(rename = "type")]
@@ -286,10 +2214,34 @@ pub struct DnsRecord {
     pub proxied: bool,
     #[serde(default)]
     pub proxiable: bool,
+    /// Locked records are managed by Cloudflare and can't be changed through the API
+    #[serde(default)]
+    pub locked: bool,
     #[serde(default)]
     pub priority: Option<u16>,
     #[serde(default)]
     pub comment: Option<String>,
+    #[serde(default)]
+    pub created_on: Option<String>,
+    #[serde(default)]
+    pub modified_on: Option<String>,
+    /// Fields of the content, for types whose content is made of several
+    #[serde(
+        default,
+        deserialize_with = "record_data::deserialize_lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub data: Option<RecordData>,
+}
+
+impl DnsRecord {
+    /// The content, formatted from the record's fields where it has them
+    pub fn data_content(&self) -> String {
+        match &self.data {
+            Some(data) if data.fits(self.record_type) => data.content(),
+            _ => self.content.clone(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
@@ -305,6 +2257,19 @@ pub enum DnsRecordType {
     SRV,
     CAA,
     PTR,
+    HTTPS,
+    SVCB,
+    TLSA,
+    SSHFP,
+    NAPTR,
+    SMIMEA,
+    CERT,
+    DNSKEY,
+    DS,
+    LOC,
+    URI,
+    /// Deprecated in favour of TXT; only shown when a zone still has one
+    SPF,
     #[serde(other)]
     Other,
 }
@@ -321,6 +2286,18 @@ impl DnsRecordType {
             DnsRecordType::SRV => "SRV",
             DnsRecordType::CAA => "CAA",
             DnsRecordType::PTR => "PTR",
+            DnsRecordType::HTTPS => "HTTPS",
+            DnsRecordType::SVCB => "SVCB",
+            DnsRecordType::TLSA => "TLSA",
+            DnsRecordType::SSHFP => "SSHFP",
+            DnsRecordType::NAPTR => "NAPTR",
+            DnsRecordType::SMIMEA => "SMIMEA",
+            DnsRecordType::CERT => "CERT",
+            DnsRecordType::DNSKEY => "DNSKEY",
+            DnsRecordType::DS => "DS",
+            DnsRecordType::LOC => "LOC",
+            DnsRecordType::URI => "URI",
+            DnsRecordType::SPF => "SPF",
             DnsRecordType::Other => "Other",
         }
     }
@@ -336,6 +2313,17 @@ impl DnsRecordType {
             DnsRecordType::SRV,
             DnsRecordType::CAA,
             DnsRecordType::PTR,
+            DnsRecordType::HTTPS,
+            DnsRecordType::SVCB,
+            DnsRecordType::TLSA,
+            DnsRecordType::SSHFP,
+            DnsRecordType::NAPTR,
+            DnsRecordType::SMIMEA,
+            DnsRecordType::CERT,
+            DnsRecordType::DNSKEY,
+            DnsRecordType::DS,
+            DnsRecordType::LOC,
+            DnsRecordType::URI,
         ]
     }
 
@@ -349,7 +2337,105 @@ impl DnsRecordType {
 
     /// Check if this record type requires a priority field
     pub fn requires_priority(&self) -> bool {
-        matches!(self, DnsRecordType::MX | DnsRecordType::SRV)
+        matches!(
+            self,
+            DnsRecordType::MX | DnsRecordType::SRV | DnsRecordType::URI
+        )
+    }
+
+    /// What the record type is used for, shown when picking the type of a new record
+    pub fn description(&self) -> &'static str {
+        match self {
+            DnsRecordType::A => "Points a name to an IPv4 address",
+            DnsRecordType::AAAA => "Points a name to an IPv6 address",
+            DnsRecordType::CNAME => "Makes a name an alias of another host name",
+            DnsRecordType::MX => "Mail server receiving email for the name",
+            DnsRecordType::TXT => "Text, e.g. SPF policies and domain verification",
+            DnsRecordType::NS => "Delegates a subdomain to other name servers",
+            DnsRecordType::SRV => "Host and port of a service, e.g. SIP or XMPP",
+            DnsRecordType::CAA => "Certificate authorities allowed to issue certificates",
+            DnsRecordType::PTR => "Name of an address, for reverse DNS zones",
+            DnsRecordType::HTTPS => "Connection details for HTTPS clients, e.g. HTTP/3 support",
+            DnsRecordType::SVCB => "Connection details of a service",
+            DnsRecordType::TLSA => "Certificate of a TLS service, for DANE",
+            DnsRecordType::SSHFP => "SSH host key fingerprint",
+            DnsRecordType::NAPTR => "Rewrite rule mapping a name to a service, e.g. for SIP",
+            DnsRecordType::SMIMEA => "S/MIME certificate of an email address",
+            DnsRecordType::CERT => "Certificate or certificate revocation list",
+            DnsRecordType::DNSKEY => "Public key for DNSSEC",
+            DnsRecordType::DS => "Delegation signer of a DNSSEC-signed subdomain",
+            DnsRecordType::LOC => "Geographic location of a host",
+            DnsRecordType::URI => "URI of a service",
+            DnsRecordType::SPF => "Deprecated sender policy, replaced by TXT records",
+            DnsRecordType::Other => "Record type not supported by this app",
+        }
+    }
+
+    /// Label of the content field in the editor
+    pub fn content_label(&self) -> &'static str {
+        match self {
+            DnsRecordType::A => "IPv4 address",
+            DnsRecordType::AAAA => "IPv6 address",
+            DnsRecordType::CNAME => "Target",
+            DnsRecordType::MX => "Mail server",
+            DnsRecordType::NS => "Name server",
+            DnsRecordType::PTR => "Domain name",
+            DnsRecordType::TXT => "Text",
+            _ => "Content",
+        }
+    }
+
+    /// Fields of content made of several values, in order, shown in the editor as the format to
+    /// follow; empty for types whose content is a single value
+    pub fn content_fields(&self) -> &'static [&'static str] {
+        match self {
+            DnsRecordType::SRV => &["weight", "port", "target"],
+            DnsRecordType::CAA => &["flags", "tag", "value"],
+            DnsRecordType::HTTPS | DnsRecordType::SVCB => &["priority", "target", "parameters"],
+            DnsRecordType::TLSA | DnsRecordType::SMIMEA => {
+                &["usage", "selector", "matching-type", "certificate"]
+            }
+            DnsRecordType::SSHFP => &["algorithm", "type", "fingerprint"],
+            DnsRecordType::NAPTR => &[
+                "order",
+                "preference",
+                "flags",
+                "service",
+                "regex",
+                "replacement",
+            ],
+            DnsRecordType::CERT => &["type", "key-tag", "algorithm", "certificate"],
+            DnsRecordType::DNSKEY => &["flags", "protocol", "algorithm", "public-key"],
+            DnsRecordType::DS => &["key-tag", "algorithm", "digest-type", "digest"],
+            DnsRecordType::LOC => &["latitude N/S", "longitude E/W", "altitude"],
+            DnsRecordType::URI => &["weight", "target"],
+            _ => &[],
+        }
+    }
+
+    /// Example content shown in the editor
+    pub fn content_example(&self) -> Option<&'static str> {
+        match self {
+            DnsRecordType::A => Some("192.0.2.1"),
+            DnsRecordType::AAAA => Some("2001:db8::1"),
+            DnsRecordType::CNAME => Some("example.herokudns.com"),
+            DnsRecordType::MX => Some("mx1.example.com"),
+            DnsRecordType::TXT => Some("v=spf1 include:_spf.example.com ~all"),
+            DnsRecordType::NS => Some("ns1.example.net"),
+            DnsRecordType::PTR => Some("host.example.com"),
+            DnsRecordType::SRV => Some("5 5060 sip.example.com"),
+            DnsRecordType::CAA => Some("0 issue \"letsencrypt.org\""),
+            DnsRecordType::HTTPS | DnsRecordType::SVCB => Some("1 . alpn=h2,h3"),
+            DnsRecordType::TLSA | DnsRecordType::SMIMEA => Some("3 1 1 2b3c..."),
+            DnsRecordType::SSHFP => Some("4 2 9dc1..."),
+            DnsRecordType::NAPTR => Some("100 10 \"S\" \"SIP+D2U\" \"\" _sip._udp.example.com"),
+            DnsRecordType::CERT => Some("1 0 0 MIIB..."),
+            DnsRecordType::DNSKEY => Some("257 3 13 mdsw..."),
+            DnsRecordType::DS => Some("2371 13 2 1F98..."),
+            DnsRecordType::LOC => Some("51 30 12.7 N 0 7 39.6 W 0m"),
+            DnsRecordType::URI => Some("1 \"https://example.com\""),
+            _ => None,
+        }
     }
 
     /// Validate content for this record type
@@ -373,7 +2459,7 @@ impl DnsRecordType {
                 }
                 Ok(())
             }
-            _ => Ok(()),
+            _ => record_data::parse(*self, content).map(|_| ()),
         }
     }
 }
@@ -384,7 +2470,62 @@ impl std::fmt::Display for DnsRecordType {
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
+/// Record changes of a zone sent in one request; Cloudflare runs the deletions first, then the
+/// updates, then the creations
+#[derive(Debug, Clone, Default)]
+pub struct DnsRecordBatch {
+    /// Ids of the records to delete
+    pub deletes: Vec<String>,
+    /// Records to update by id
+    pub patches: Vec<(String, UpdateDnsRecord)>,
+    pub posts: Vec<CreateDnsRecord>,
+}
+
+impl DnsRecordBatch {
+    fn body(&self) -> Result<serde_json::Value> {
+        let deletes: Vec<serde_json::Value> = self
+            .deletes
+            .iter()
+            .map(|id| serde_json::json!({ "id": id }))
+            .collect();
+        let patches = self
+            .patches
+            .iter()
+            .map(|(id, record)| {
+                let mut body = record_body(
+                    record,
+                    record.record_type,
+                    record.content.as_deref(),
+                    record.data.as_ref(),
+                )?;
+                if let Some(fields) = body.as_object_mut() {
+                    fields.insert("id".to_string(), id.clone().into());
+                }
+                Ok(body)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let posts = self
+            .posts
+            .iter()
+            .map(|record| {
+                record_body(
+                    record,
+                    Some(record.record_type),
+                    Some(&record.content),
+                    record.data.as_ref(),
+                )
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(serde_json::json!({
+            "deletes": deletes,
+            "patches": patches,
+            "posts": posts,
+        }))
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct CreateDnsRecord {
     #[serde(rename = "type")]
     pub record_type: DnsRecordType,
@@ -397,9 +2538,12 @@ pub struct CreateDnsRecord {
     pub priority: Option<u16>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub comment: Option<String>,
+    /// Fields of the content, sent instead of it; parsed from the content when `None`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<RecordData>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
 pub struct UpdateDnsRecord {
     #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
     pub record_type: Option<DnsRecordType>,
@@ -415,4 +2559,81 @@ pub struct UpdateDnsRecord {
     pub priority: Option<u16>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub comment: Option<String>,
+    /// Fields of the content, sent instead of it; parsed from the content when `None`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<RecordData>,
+}
+
+/// A record name as the API completes it with the zone name, in lowercase: `www` and `@` become
+/// `www.example.com` and `example.com`
+pub fn qualified_name(name: &str, zone_name: &str) -> String {
+    let name = name.trim().trim_end_matches('.').to_lowercase();
+    let zone_name = zone_name.trim().trim_end_matches('.').to_lowercase();
+    if name == "@" {
+        zone_name
+    } else if name == zone_name || name.ends_with(&format!(".{}", zone_name)) {
+        name
+    } else {
+        format!("{}.{}", name, zone_name)
+    }
+}
+
+impl CreateDnsRecord {
+    /// Whether an existing record has the same type, name and content
+    ///
+    /// The name is completed with the zone name as the API does, so `www` matches
+    /// `www.example.com`, and addresses are compared by value.
+    pub fn duplicates(&self, existing: &DnsRecord, zone_name: &str) -> bool {
+        let normalize = |name: &str| name.trim().trim_end_matches('.').to_lowercase();
+        let name = qualified_name(&self.name, zone_name);
+
+        let same_content = match self.record_type {
+            DnsRecordType::A | DnsRecordType::AAAA => {
+                match (
+                    self.content.trim().parse::<IpAddr>(),
+                    existing.content.parse::<IpAddr>(),
+                ) {
+                    (Ok(new), Ok(existing)) => new == existing,
+                    _ => self.content.trim() == existing.content,
+                }
+            }
+            DnsRecordType::TXT => self.content == existing.content,
+            _ => normalize(&self.content) == normalize(&existing.content),
+        };
+
+        self.record_type == existing.record_type
+            && name == normalize(&existing.name)
+            && same_content
+    }
+}
+
+impl From<&DnsRecord> for CreateDnsRecord {
+    fn from(record: &DnsRecord) -> Self {
+        Self {
+            record_type: record.record_type,
+            name: record.name.clone(),
+            content: record.content.clone(),
+            ttl: record.ttl,
+            proxied: record.proxiable.then_some(record.proxied),
+            priority: record.priority,
+            comment: record.comment.clone(),
+            data: record.data.clone(),
+        }
+    }
+}
+
+impl From<&DnsRecord> for UpdateDnsRecord {
+    /// All fields of the record, so applying the update restores it exactly
+    fn from(record: &DnsRecord) -> Self {
+        Self {
+            record_type: Some(record.record_type),
+            name: Some(record.name.clone()),
+            content: Some(record.content.clone()),
+            ttl: Some(record.ttl),
+            proxied: record.proxiable.then_some(record.proxied),
+            priority: record.priority,
+            comment: Some(record.comment.clone().unwrap_or_default()),
+            data: record.data.clone(),
+        }
+    }
 }
@@ -0,0 +1,348 @@
+//! Optional HTTP server on localhost that lets local scripts and launchers drive the app
+//!
+//! Requests use the app's own Cloudflare client, so tools don't need a Cloudflare token of
+//! their own. Every request must carry the server's token as `Authorization: Bearer <token>`.
+//! Changes are refused while the app is in read-only mode, and to records protected in it.
+//! New records duplicating existing ones are refused while the app blocks duplicates.
+
+use std::collections::HashMap;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+
+use anyhow::Result;
+use axum::extract::{Path, Request, State};
+use axum::http::{StatusCode, header};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, patch};
+use axum::{Json, Router};
+use rand::Rng;
+use rand::distr::Alphanumeric;
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::task::JoinHandle;
+
+use crate::cloudflare::{CloudflareClient, CreateDnsRecord, DnsRecord, UpdateDnsRecord, Zone};
+
+/// Out of the way of dev servers, e.g. `wrangler dev` on 8787
+pub const DEFAULT_PORT: u16 = 47187;
+
+const TOKEN_LENGTH: usize = 32;
+
+/// Persisted server settings
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ControlServerSettings {
+    pub enabled: bool,
+    pub port: u16,
+    pub token: String,
+}
+
+impl Default for ControlServerSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: DEFAULT_PORT,
+            token: generate_token(),
+        }
+    }
+}
+
+pub fn generate_token() -> String {
+    rand::rng()
+        .sample_iter(&Alphanumeric)
+        .take(TOKEN_LENGTH)
+        .map(char::from)
+        .collect()
+}
+
+/// Record changed through the server, so the app can refresh its views
+#[derive(Debug, Clone)]
+pub struct RecordChanged {
+    pub zone_id: String,
+    pub record_id: String,
+    /// What was changed, e.g. `Create A www.example.com`
+    pub description: String,
+}
+
+#[derive(Clone)]
+struct ServerState {
+    client: Arc<RwLock<Option<CloudflareClient>>>,
+    read_only: Arc<AtomicBool>,
+    /// Reasons of protected records, by record id
+    protected_records: Arc<RwLock<HashMap<String, String>>>,
+    block_duplicates: Arc<AtomicBool>,
+    token: Arc<str>,
+    changes: UnboundedSender<RecordChanged>,
+}
+
+/// A running server, stopped when dropped
+pub struct ControlServer {
+    pub port: u16,
+    client: Arc<RwLock<Option<CloudflareClient>>>,
+    read_only: Arc<AtomicBool>,
+    protected_records: Arc<RwLock<HashMap<String, String>>>,
+    block_duplicates: Arc<AtomicBool>,
+    task: JoinHandle<()>,
+}
+
+impl ControlServer {
+    /// Start listening on `127.0.0.1:port`
+    pub async fn start(
+        settings: &ControlServerSettings,
+        client: Option<CloudflareClient>,
+        changes: UnboundedSender<RecordChanged>,
+    ) -> Result<Self> {
+        let client = Arc::new(RwLock::new(client));
+        let read_only = Arc::new(AtomicBool::new(false));
+        let protected_records = Arc::new(RwLock::new(HashMap::new()));
+        let block_duplicates = Arc::new(AtomicBool::new(false));
+        let state = ServerState {
+            client: client.clone(),
+            read_only: read_only.clone(),
+            protected_records: protected_records.clone(),
+            block_duplicates: block_duplicates.clone(),
+            token: settings.token.as_str().into(),
+            changes,
+        };
+
+        let router = Router::new()
+            .route("/zones", get(list_zones))
+            .route(
+                "/zones/{zone}/records",
+                get(list_records).post(create_record),
+            )
+            .route("/zones/{zone}/records/{record_id}", patch(update_record))
+            .layer(middleware::from_fn_with_state(state.clone(), authorize))
+            .with_state(state);
+
+        let address = SocketAddr::from((Ipv4Addr::LOCALHOST, settings.port));
+        let listener = tokio::net::TcpListener::bind(address).await?;
+        let task = tokio::spawn(async move {
+            axum::serve(listener, router).await.ok();
+        });
+
+        Ok(Self {
+            port: settings.port,
+            client,
+            read_only,
+            protected_records,
+            block_duplicates,
+            task,
+        })
+    }
+
+    /// Use a different Cloudflare client, e.g. after the API token changed
+    pub fn set_client(&self, client: Option<CloudflareClient>) {
+        *self.client.write().unwrap() = client;
+    }
+
+    /// Refuse or allow changes, following the app's read-only mode
+    pub fn set_read_only(&self, read_only: bool) {
+        self.read_only.store(read_only, Ordering::Relaxed);
+    }
+
+    /// Refuse changes to the records protected in the app
+    pub fn set_protected_records(&self, protected_records: &HashMap<String, String>) {
+        *self.protected_records.write().unwrap() = protected_records.clone();
+    }
+
+    /// Refuse or allow new records duplicating existing ones, following the app's guardrails
+    pub fn set_block_duplicates(&self, block: bool) {
+        self.block_duplicates.store(block, Ordering::Relaxed);
+    }
+}
+
+impl Drop for ControlServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Error response with a JSON body like `{"error": "..."}`
+struct ApiError(StatusCode, String);
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        #[derive(Serialize)]
+        struct Body {
+            error: String,
+        }
+        (self.0, Json(Body { error: self.1 })).into_response()
+    }
+}
+
+impl From<anyhow::Error> for ApiError {
+    fn from(e: anyhow::Error) -> Self {
+        ApiError(StatusCode::BAD_GATEWAY, e.to_string())
+    }
+}
+
+async fn authorize(State(state): State<ServerState>, request: Request, next: Next) -> Response {
+    let authorized = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|token| constant_time_eq(token.as_bytes(), state.token.as_bytes()));
+
+    if authorized {
+        next.run(request).await
+    } else {
+        ApiError(StatusCode::UNAUTHORIZED, "Invalid token".to_string()).into_response()
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+impl ServerState {
+    fn client(&self) -> Result<CloudflareClient, ApiError> {
+        self.client.read().unwrap().clone().ok_or_else(|| {
+            ApiError(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "No API token is set up in the app".to_string(),
+            )
+        })
+    }
+
+    /// The client for changes, unless the app is in read-only mode
+    fn writable_client(&self) -> Result<CloudflareClient, ApiError> {
+        if self.read_only.load(Ordering::Relaxed) {
+            return Err(ApiError(
+                StatusCode::FORBIDDEN,
+                "The app is in read-only mode".to_string(),
+            ));
+        }
+        self.client()
+    }
+
+    /// Refuse changes to a record protected in the app
+    fn check_unprotected(&self, record_id: &str) -> Result<(), ApiError> {
+        match self.protected_records.read().unwrap().get(record_id) {
+            Some(reason) => Err(ApiError(
+                StatusCode::FORBIDDEN,
+                format!("The record is protected in the app: {}", reason),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Refuse a new record duplicating an existing one while the app blocks duplicates
+    async fn check_not_duplicate(
+        &self,
+        client: &CloudflareClient,
+        zone: &Zone,
+        record: &CreateDnsRecord,
+    ) -> Result<(), ApiError> {
+        if !self.block_duplicates.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+        match client
+            .list_dns_records(&zone.id)
+            .await?
+            .iter()
+            .find(|existing| record.duplicates(existing, &zone.name))
+        {
+            Some(existing) => Err(ApiError(
+                StatusCode::CONFLICT,
+                format!(
+                    "{} already has a {} record with this content",
+                    existing.name, record.record_type
+                ),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Find a zone by id or name
+    async fn zone(&self, client: &CloudflareClient, zone: &str) -> Result<Zone, ApiError> {
+        client
+            .list_zones()
+            .await?
+            .into_iter()
+            .find(|z| z.id == zone || z.name == zone)
+            .ok_or_else(|| ApiError(StatusCode::NOT_FOUND, format!("No zone {}", zone)))
+    }
+
+    fn record_changed(&self, zone_id: String, record: &DnsRecord, action: &str) {
+        self.changes
+            .send(RecordChanged {
+                zone_id,
+                record_id: record.id.clone(),
+                description: format!("{} {} {}", action, record.record_type, record.name),
+            })
+            .ok();
+    }
+}
+
+#[derive(Serialize)]
+struct ZoneSummary {
+    id: String,
+    name: String,
+    status: String,
+}
+
+async fn list_zones(State(state): State<ServerState>) -> Result<impl IntoResponse, ApiError> {
+    let client = state.client()?;
+    let zones: Vec<ZoneSummary> = client
+        .list_zones()
+        .await?
+        .into_iter()
+        .map(|zone| ZoneSummary {
+            id: zone.id,
+            name: zone.name,
+            status: zone.status,
+        })
+        .collect();
+    Ok(Json(zones))
+}
+
+async fn list_records(
+    State(state): State<ServerState>,
+    Path(zone): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    let client = state.client()?;
+    let zone = state.zone(&client, &zone).await?;
+    Ok(Json(client.list_dns_records(&zone.id).await?))
+}
+
+async fn create_record(
+    State(state): State<ServerState>,
+    Path(zone): Path<String>,
+    Json(record): Json<CreateDnsRecord>,
+) -> Result<impl IntoResponse, ApiError> {
+    record
+        .record_type
+        .validate_content(&record.content)
+        .map_err(|e| ApiError(StatusCode::UNPROCESSABLE_ENTITY, e.to_string()))?;
+
+    let client = state.writable_client()?;
+    let zone = state.zone(&client, &zone).await?;
+    state.check_not_duplicate(&client, &zone, &record).await?;
+    let created = client.create_dns_record(&zone.id, &record).await?;
+    state.record_changed(zone.id, &created, "Create");
+    Ok((StatusCode::CREATED, Json(created)))
+}
+
+async fn update_record(
+    State(state): State<ServerState>,
+    Path((zone, record_id)): Path<(String, String)>,
+    Json(record): Json<UpdateDnsRecord>,
+) -> Result<impl IntoResponse, ApiError> {
+    if let (Some(record_type), Some(content)) = (record.record_type, &record.content) {
+        record_type
+            .validate_content(content)
+            .map_err(|e| ApiError(StatusCode::UNPROCESSABLE_ENTITY, e.to_string()))?;
+    }
+
+    let client = state.writable_client()?;
+    state.check_unprotected(&record_id)?;
+    let zone = state.zone(&client, &zone).await?;
+    let updated = client
+        .update_dns_record(&zone.id, &record_id, &record)
+        .await?;
+    state.record_changed(zone.id, &updated, "Update");
+    Ok(Json(updated))
+}
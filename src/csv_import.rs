@@ -0,0 +1,246 @@
+//! Import of DNS records from CSV files with user-defined column mapping
+
+use anyhow::{Result, bail};
+
+use crate::cloudflare::{CreateDnsRecord, DnsRecordType};
+
+/// Record field a CSV column can be mapped to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RecordField {
+    Type,
+    Name,
+    Content,
+    Ttl,
+    Proxied,
+    Priority,
+    Comment,
+}
+
+impl RecordField {
+    pub const ALL: [RecordField; 7] = [
+        RecordField::Type,
+        RecordField::Name,
+        RecordField::Content,
+        RecordField::Ttl,
+        RecordField::Proxied,
+        RecordField::Priority,
+        RecordField::Comment,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            RecordField::Type => "Type",
+            RecordField::Name => "Name",
+            RecordField::Content => "Content",
+            RecordField::Ttl => "TTL",
+            RecordField::Proxied => "Proxied",
+            RecordField::Priority => "Priority",
+            RecordField::Comment => "Comment",
+        }
+    }
+
+    pub fn is_required(&self) -> bool {
+        matches!(
+            self,
+            RecordField::Type | RecordField::Name | RecordField::Content
+        )
+    }
+
+    pub fn index(&self) -> usize {
+        Self::ALL
+            .iter()
+            .position(|field| field == self)
+            .unwrap_or_default()
+    }
+
+    /// Guess the field from a column header, e.g. `record type` or `Value`
+    fn guess(header: &str) -> Option<Self> {
+        let header = header.trim().to_lowercase().replace(['_', '-'], " ");
+        match header.as_str() {
+            "type" | "record type" | "rtype" => Some(RecordField::Type),
+            "name" | "host" | "hostname" | "record name" => Some(RecordField::Name),
+            "content" | "value" | "data" | "target" | "address" => Some(RecordField::Content),
+            "ttl" => Some(RecordField::Ttl),
+            "proxied" | "proxy" | "proxy status" => Some(RecordField::Proxied),
+            "priority" | "prio" | "preference" => Some(RecordField::Priority),
+            "comment" | "comments" | "note" | "notes" => Some(RecordField::Comment),
+            _ => None,
+        }
+    }
+}
+
+/// A CSV file being imported and the column mapped to each field
+#[derive(Debug, Clone)]
+pub struct CsvImport {
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+    /// Column of each field in [`RecordField::ALL`], if mapped
+    mapping: [Option<usize>; RecordField::ALL.len()],
+}
+
+/// Result of converting a CSV row, numbered from 1 for the first row after the header
+pub struct ImportRow {
+    pub line: usize,
+    pub result: Result<CreateDnsRecord, String>,
+}
+
+impl CsvImport {
+    /// Read a CSV file with a header row, guessing the mapping from the headers
+    pub fn new(text: &str) -> Result<Self> {
+        let mut rows = parse_csv(text)?;
+        if rows.is_empty() {
+            bail!("The file is empty");
+        }
+        let headers = rows.remove(0);
+
+        let mut mapping = [None; RecordField::ALL.len()];
+        for (column, header) in headers.iter().enumerate() {
+            if let Some(field) = RecordField::guess(header) {
+                let slot = &mut mapping[field.index()];
+                slot.get_or_insert(column);
+            }
+        }
+
+        Ok(Self {
+            headers,
+            rows,
+            mapping,
+        })
+    }
+
+    pub fn column(&self, field: RecordField) -> Option<usize> {
+        self.mapping[field.index()]
+    }
+
+    pub fn set_column(&mut self, field: RecordField, column: Option<usize>) {
+        self.mapping[field.index()] = column;
+    }
+
+    /// Missing required fields, which keep every row from being imported
+    pub fn unmapped_required(&self) -> Vec<RecordField> {
+        RecordField::ALL
+            .into_iter()
+            .filter(|field| field.is_required() && self.column(*field).is_none())
+            .collect()
+    }
+
+    /// Convert and validate every row with the current mapping
+    pub fn records(&self) -> Vec<ImportRow> {
+        self.rows
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| row.iter().any(|value| !value.trim().is_empty()))
+            .map(|(index, row)| ImportRow {
+                line: index + 1,
+                result: self.record(row),
+            })
+            .collect()
+    }
+
+    fn record(&self, row: &[String]) -> Result<CreateDnsRecord, String> {
+        let value = |field: RecordField| {
+            self.column(field)
+                .and_then(|column| row.get(column))
+                .map(|value| value.trim())
+                .filter(|value| !value.is_empty())
+        };
+
+        let type_name = value(RecordField::Type).ok_or("Missing type")?;
+        let record_type = DnsRecordType::all()
+            .iter()
+            .copied()
+            .find(|t| t.as_str().eq_ignore_ascii_case(type_name))
+            .ok_or_else(|| format!("Unsupported type {}", type_name))?;
+        let name = value(RecordField::Name).ok_or("Missing name")?;
+        let content = value(RecordField::Content).ok_or("Missing content")?;
+        record_type
+            .validate_content(content)
+            .map_err(|e| e.to_string())?;
+
+        let ttl = match value(RecordField::Ttl) {
+            None => 1,
+            Some(ttl) if ttl.eq_ignore_ascii_case("auto") => 1,
+            Some(ttl) => ttl.parse().map_err(|_| format!("Invalid TTL {}", ttl))?,
+        };
+        let proxied = match value(RecordField::Proxied) {
+            None => None,
+            Some(proxied) => Some(parse_bool(proxied)?),
+        };
+        if proxied == Some(true) && !record_type.is_proxiable() {
+            return Err(format!("{} records can't be proxied", record_type));
+        }
+        let priority = match value(RecordField::Priority) {
+            None if record_type.requires_priority() => {
+                return Err(format!("{} records need a priority", record_type));
+            }
+            None => None,
+            Some(priority) => Some(
+                priority
+                    .parse()
+                    .map_err(|_| format!("Invalid priority {}", priority))?,
+            ),
+        };
+
+        Ok(CreateDnsRecord {
+            record_type,
+            name: name.to_string(),
+            content: content.to_string(),
+            ttl,
+            proxied: proxied.or(record_type.is_proxiable().then_some(false)),
+            priority,
+            comment: value(RecordField::Comment).map(String::from),
+            data: None,
+        })
+    }
+}
+
+fn parse_bool(value: &str) -> Result<bool, String> {
+    match value.to_lowercase().as_str() {
+        "true" | "yes" | "y" | "1" | "on" | "proxied" => Ok(true),
+        "false" | "no" | "n" | "0" | "off" | "dns only" => Ok(false),
+        _ => Err(format!("Invalid proxied value {}", value)),
+    }
+}
+
+/// Parse CSV text into rows of fields, following RFC 4180 quoting
+pub fn parse_csv(text: &str) -> Result<Vec<Vec<String>>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = text.trim_start_matches('\u{feff}').chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                c => field.push(c),
+            }
+            continue;
+        }
+
+        match c {
+            '"' if field.is_empty() => in_quotes = true,
+            ',' => row.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' | '\r' => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            c => field.push(c),
+        }
+    }
+    if in_quotes {
+        bail!("Unclosed quote in the last row");
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+
+    Ok(rows)
+}
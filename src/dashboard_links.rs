@@ -0,0 +1,83 @@
+//! Links into the Cloudflare dashboard, for what the app doesn't cover yet
+//!
+//! Dashboard addresses are `https://dash.cloudflare.com/<account id>/<zone name>/<page>`.
+
+use crate::cloudflare::Zone;
+
+/// Pages of a zone in the Cloudflare dashboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DashboardPage {
+    Overview,
+    /// There is no address for a single record; records are found by searching this page
+    DnsRecords,
+    DnsSettings,
+    Analytics,
+    SslTls,
+    Security,
+    Caching,
+    Rules,
+    Speed,
+    Workers,
+    EmailRouting,
+    Network,
+}
+
+impl DashboardPage {
+    /// In the order of the dashboard's sidebar
+    pub const ALL: [DashboardPage; 12] = [
+        DashboardPage::Overview,
+        DashboardPage::Analytics,
+        DashboardPage::DnsRecords,
+        DashboardPage::DnsSettings,
+        DashboardPage::EmailRouting,
+        DashboardPage::SslTls,
+        DashboardPage::Security,
+        DashboardPage::Speed,
+        DashboardPage::Caching,
+        DashboardPage::Workers,
+        DashboardPage::Rules,
+        DashboardPage::Network,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DashboardPage::Overview => "Overview",
+            DashboardPage::DnsRecords => "DNS Records",
+            DashboardPage::DnsSettings => "DNS Settings",
+            DashboardPage::Analytics => "Analytics",
+            DashboardPage::SslTls => "SSL/TLS",
+            DashboardPage::Security => "Security",
+            DashboardPage::Caching => "Caching",
+            DashboardPage::Rules => "Rules",
+            DashboardPage::Speed => "Speed",
+            DashboardPage::Workers => "Workers Routes",
+            DashboardPage::EmailRouting => "Email Routing",
+            DashboardPage::Network => "Network",
+        }
+    }
+
+    /// Path of the page below the zone's overview
+    fn path(&self) -> &'static str {
+        match self {
+            DashboardPage::Overview => "",
+            DashboardPage::DnsRecords => "dns/records",
+            DashboardPage::DnsSettings => "dns/settings",
+            DashboardPage::Analytics => "analytics/traffic",
+            DashboardPage::SslTls => "ssl-tls",
+            DashboardPage::Security => "security",
+            DashboardPage::Caching => "caching/configuration",
+            DashboardPage::Rules => "rules",
+            DashboardPage::Speed => "speed/optimization",
+            DashboardPage::Workers => "workers",
+            DashboardPage::EmailRouting => "email/routing",
+            DashboardPage::Network => "network",
+        }
+    }
+
+    pub fn url(&self, zone: &Zone) -> String {
+        match self.path() {
+            "" => zone.dashboard_url(),
+            path => format!("{}/{}", zone.dashboard_url(), path),
+        }
+    }
+}
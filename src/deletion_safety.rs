@@ -0,0 +1,176 @@
+//! Safety check before records are removed en masse
+//!
+//! The DNS queries for the records and the edge certificates covering them tell whether the
+//! records still serve anything, and the zone's traffic whether emptying it, e.g. before moving
+//! it to another provider, takes down a site still in use. The more is at stake, the more the
+//! confirmation asks for: a plain confirmation, ticking that the warnings were read, or typing
+//! the zone's name.
+
+use std::collections::HashSet;
+
+use crate::analytics::{TrafficTotals, format_count, record_usage};
+use crate::certificates;
+use crate::cloudflare::{CertificatePack, DnsQueryCount, DnsRecord, TrafficPoint};
+
+/// Requests a day above which a zone counts as still in use
+pub const SIGNIFICANT_REQUESTS: u64 = 1_000;
+
+/// Removing this many records asks for acknowledgement even when nothing else is at stake
+const LARGE_REMOVAL: usize = 25;
+
+/// What confirming a removal takes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Friction {
+    Confirm,
+    /// Ticking that the warnings were read
+    Acknowledge,
+    /// Typing the zone's name
+    TypeZoneName,
+}
+
+/// Whether records about to be removed still serve anything
+#[derive(Debug, Clone, Default)]
+pub struct SafetyCheck {
+    /// Records being removed
+    pub removed: usize,
+    /// Records of the zone
+    pub total: usize,
+    /// Requests the zone served over the last 24 hours
+    pub requests: Option<u64>,
+    /// DNS queries for the names and types being removed over the last 24 hours
+    pub queries: Option<u64>,
+    /// Hostnames of the removed records that active edge certificates cover
+    pub certified_hosts: Vec<String>,
+    /// Parts of the check that couldn't be done, e.g. for a token without analytics access
+    pub errors: Vec<String>,
+    /// Whether confirming always takes typing the zone's name, as the profile's guardrails ask
+    pub strict: bool,
+}
+
+impl SafetyCheck {
+    pub fn new(
+        removed: &[DnsRecord],
+        total: usize,
+        traffic: Result<Vec<TrafficPoint>, String>,
+        query_counts: Result<Vec<DnsQueryCount>, String>,
+        certificate_packs: Result<Vec<CertificatePack>, String>,
+    ) -> Self {
+        let mut errors = Vec::new();
+        let requests = match traffic {
+            Ok(points) => Some(TrafficTotals::new(&points).requests),
+            Err(e) => {
+                errors.push(format!("Traffic couldn't be checked: {}", e));
+                None
+            }
+        };
+        let queries = match query_counts {
+            Ok(counts) => Some(
+                record_usage(removed, &counts)
+                    .iter()
+                    .map(|usage| usage.queries)
+                    .sum(),
+            ),
+            Err(e) => {
+                errors.push(format!("DNS queries couldn't be checked: {}", e));
+                None
+            }
+        };
+        let certified_hosts = match certificate_packs {
+            Ok(packs) => certified_hosts(removed, &packs),
+            Err(e) => {
+                errors.push(format!("Certificates couldn't be checked: {}", e));
+                Vec::new()
+            }
+        };
+
+        Self {
+            removed: removed.len(),
+            total,
+            requests,
+            queries,
+            certified_hosts,
+            errors,
+            strict: false,
+        }
+    }
+
+    fn removes_most_of_zone(&self) -> bool {
+        self.removed >= 5 && self.removed * 2 >= self.total
+    }
+
+    /// What is at stake, most serious first
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if let Some(queries) = self.queries.filter(|queries| *queries > 0) {
+            warnings.push(format!(
+                "These records were queried {} times in the last 24 hours",
+                format_count(queries)
+            ));
+        }
+        if !self.certified_hosts.is_empty() {
+            warnings.push(format!(
+                "Active edge certificates cover {}",
+                self.certified_hosts.join(", ")
+            ));
+        }
+        if self.removes_most_of_zone() {
+            warnings.push(format!(
+                "This removes {} of the zone's {} records",
+                self.removed, self.total
+            ));
+            if self.zone_in_use() {
+                warnings.push(format!(
+                    "The zone still served {} requests in the last 24 hours",
+                    format_count(self.requests.unwrap_or_default())
+                ));
+            }
+        }
+        warnings
+    }
+
+    fn zone_in_use(&self) -> bool {
+        self.requests
+            .is_some_and(|requests| requests >= SIGNIFICANT_REQUESTS)
+    }
+
+    /// What confirming takes, growing with the warnings and the records removed
+    pub fn friction(&self) -> Friction {
+        if self.strict {
+            return Friction::TypeZoneName;
+        }
+        // A check that couldn't be done counts as a warning, since nothing is known
+        let stakes = self.warnings().len()
+            + usize::from(!self.errors.is_empty())
+            + usize::from(self.removed >= LARGE_REMOVAL);
+        match stakes {
+            0 => Friction::Confirm,
+            1 | 2 => Friction::Acknowledge,
+            _ => Friction::TypeZoneName,
+        }
+    }
+}
+
+/// Names of `records` serving traffic that an active edge certificate covers
+fn certified_hosts(records: &[DnsRecord], packs: &[CertificatePack]) -> Vec<String> {
+    let hosts: Vec<String> = certificates::edge_certificates(packs)
+        .into_iter()
+        .flat_map(|certificate| certificate.hosts)
+        .collect();
+    let mut seen = HashSet::new();
+    records
+        .iter()
+        .filter(|record| record.record_type.is_proxiable())
+        .map(|record| record.name.to_lowercase())
+        .filter(|name| hosts.iter().any(|host| host_matches(host, name)))
+        .filter(|name| seen.insert(name.clone()))
+        .collect()
+}
+
+/// Whether a certificate host, possibly a wildcard, covers `name`
+fn host_matches(host: &str, name: &str) -> bool {
+    let host = host.to_lowercase();
+    match host.strip_prefix("*.") {
+        Some(parent) => name.split_once('.').is_some_and(|(_, rest)| rest == parent),
+        None => host == name,
+    }
+}
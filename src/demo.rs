@@ -0,0 +1,1185 @@
+//! Fake zones and records for demo mode
+//!
+//! [`CloudflareClient::demo`](crate::cloudflare::CloudflareClient::demo) answers from this
+//! in-memory backend instead of the API, so the app can be tried, shown or screenshotted without
+//! a token. Names use the reserved `example.*` domains and addresses the documentation ranges,
+//! so nothing in it points at real infrastructure. Changes last until the app is closed.
+
+use std::collections::{HashMap, HashSet};
+
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Duration, Utc};
+
+use crate::cloudflare::{
+    Account, Certificate, CertificatePack, CreateDnsRecord, DnsQueryCount, DnsRecord,
+    DnsRecordBatch, DnsRecordType, Dnssec, PageRule, PageRuleAction, PageRuleTarget,
+    PagesDeployment, PagesProject, PagesStage, PagesTrigger, PagesTriggerMetadata, PermissionGroup,
+    RulesetRule, TokenDetails, TokenPolicy, TrafficPoint, Tunnel, TunnelConnection, TunnelIngress,
+    UniversalSsl, UpdateDnsRecord, ValidationRecord, WorkerRoute, WorkerScript, Zone, ZoneAccount,
+    ZonePlan, ZoneSetting,
+};
+use crate::{record_data, redirect_rules, waf_rules};
+
+const ACCOUNT_ID: &str = "demo-account";
+
+/// Nameservers Cloudflare assigns to the demo zones
+const DEMO_NAME_SERVERS: &[&str] = &["ada.ns.cloudflare.com", "bob.ns.cloudflare.com"];
+
+/// Nameservers the demo zones used before they were added to Cloudflare
+const ORIGINAL_NAME_SERVERS: &[&str] =
+    &["dns1.registrar-servers.com", "dns2.registrar-servers.com"];
+
+/// Workers of the demo account
+const WORKER_SCRIPTS: &[&str] = &["api-gateway", "image-resizer", "maintenance-page"];
+
+/// Pages projects of the demo account with their subdomains and custom domains
+const PAGES_PROJECTS: &[(&str, &str, &[&str])] = &[
+    (
+        "example-blog",
+        "example-blog.pages.dev",
+        &["blog.example.com"],
+    ),
+    (
+        "example-shop-preview",
+        "example-shop-preview.pages.dev",
+        &[],
+    ),
+];
+
+/// Connector, `cloudflared` version and data center of a demo tunnel connection
+type DemoConnection = (&'static str, &'static str, &'static str);
+
+/// Tunnels of the demo account with their status and connections; `internal.example.com` is
+/// routed to the first one by a CNAME record
+const TUNNELS: &[(&str, &str, &str, &[DemoConnection])] = &[
+    (
+        "6f2c1d3e",
+        "home-lab",
+        "healthy",
+        &[
+            ("c1", "2024.8.2", "fra06"),
+            ("c1", "2024.8.2", "ams01"),
+            ("c2", "2024.8.2", "fra08"),
+            ("c2", "2024.8.2", "ams08"),
+        ],
+    ),
+    (
+        "9a4b7c21",
+        "office",
+        "degraded",
+        &[("c3", "2024.8.2", "lhr01"), ("c4", "2023.10.0", "lhr04")],
+    ),
+    ("b03e5f88", "old-nas", "down", &[]),
+];
+
+/// Hostname and service of a demo ingress rule
+type DemoIngress = (Option<&'static str>, &'static str);
+
+/// Ingress rules of the demo tunnels configured in the dashboard
+const TUNNEL_INGRESS: &[(&str, &[DemoIngress])] = &[(
+    "9a4b7c21",
+    &[
+        (Some("wiki.example.com"), "http://localhost:8080"),
+        (Some("printer.example.org"), "http://192.168.1.20"),
+        (None, "http_status:404"),
+    ],
+)];
+
+pub struct DemoBackend {
+    zones: Vec<Zone>,
+    records: HashMap<String, Vec<DnsRecord>>,
+    /// Settings changed in demo mode by zone id; the others keep their defaults
+    zone_settings: HashMap<String, Vec<ZoneSetting>>,
+    /// When development mode turns itself off again, by zone id
+    development_mode_ends: HashMap<String, DateTime<Utc>>,
+    page_rules: HashMap<String, Vec<PageRule>>,
+    /// Rules by zone id and ruleset phase
+    phase_rules: HashMap<(String, String), Vec<RulesetRule>>,
+    worker_routes: HashMap<String, Vec<WorkerRoute>>,
+    /// Deployments by Pages project, newest first
+    pages_deployments: HashMap<String, Vec<PagesDeployment>>,
+    /// Zones Universal SSL was turned off for
+    universal_ssl_disabled: HashSet<String>,
+    next_id: u64,
+}
+
+impl DemoBackend {
+    pub fn new() -> Self {
+        let mut backend = Self {
+            zones: Vec::new(),
+            records: HashMap::new(),
+            zone_settings: HashMap::new(),
+            development_mode_ends: HashMap::new(),
+            page_rules: HashMap::new(),
+            phase_rules: HashMap::new(),
+            worker_routes: HashMap::new(),
+            pages_deployments: HashMap::new(),
+            universal_ssl_disabled: HashSet::new(),
+            next_id: 1,
+        };
+
+        use DnsRecordType::*;
+        backend.add_zone(
+            "example.com",
+            "Pro Website",
+            "active",
+            &[
+                (A, "example.com", "203.0.113.10", true),
+                (AAAA, "example.com", "2001:db8::10", true),
+                (CNAME, "www.example.com", "example.com", true),
+                (A, "api.example.com", "203.0.113.20", true),
+                (A, "status.example.com", "198.51.100.7", false),
+                (CNAME, "docs.example.com", "example-docs.github.io", false),
+                (CNAME, "app.example.com", "example-app.herokudns.com", false),
+                (CNAME, "blog.example.com", "example-blog.pages.dev", true),
+                (
+                    CNAME,
+                    "internal.example.com",
+                    "6f2c1d3e.cfargotunnel.com",
+                    true,
+                ),
+                (MX, "example.com", "mx1.example.net", false),
+                (MX, "example.com", "mx2.example.net", false),
+                (
+                    TXT,
+                    "example.com",
+                    "v=spf1 include:_spf.example.net -all",
+                    false,
+                ),
+                (
+                    TXT,
+                    "_dmarc.example.com",
+                    "v=DMARC1; p=quarantine; rua=mailto:dmarc@example.com",
+                    false,
+                ),
+                (
+                    TXT,
+                    "example.com",
+                    "google-site-verification=demo-3Jx9Qm2Lk8Vb",
+                    false,
+                ),
+                (
+                    TXT,
+                    "_github-challenge-example.example.com",
+                    "9c1f2e7a4b",
+                    false,
+                ),
+                (CAA, "example.com", "0 issue \"letsencrypt.org\"", false),
+            ],
+        );
+        backend.add_zone(
+            "example.org",
+            "Free Website",
+            "active",
+            &[
+                (A, "example.org", "198.51.100.40", true),
+                (CNAME, "www.example.org", "example.org", true),
+                (CNAME, "shop.example.org", "shops.example.net", false),
+                (MX, "example.org", "route1.mx.cloudflare.net", false),
+                (
+                    TXT,
+                    "example.org",
+                    "v=spf1 include:_spf.mx.cloudflare.net ~all",
+                    false,
+                ),
+            ],
+        );
+        backend.add_zone(
+            "example.net",
+            "Free Website",
+            "pending",
+            &[
+                (A, "example.net", "192.0.2.1", false),
+                (A, "mx1.example.net", "192.0.2.25", false),
+                (A, "mx2.example.net", "192.0.2.26", false),
+                (NS, "lab.example.net", "ns1.example.org", false),
+            ],
+        );
+
+        // MX priorities, comments and a record managed by Cloudflare, as real zones have them
+        for record in backend.records.values_mut().flatten() {
+            if record.record_type == MX {
+                record.priority = Some(if record.content.starts_with("mx2") {
+                    20
+                } else {
+                    10
+                });
+            }
+            if record.name == "status.example.com" {
+                record.comment = Some("Hosted outside Cloudflare for independence".to_string());
+            }
+        }
+        if let Some(records) = backend.records.get_mut("demo-zone-1")
+            && let Some(record) = records
+                .iter_mut()
+                .find(|record| record.name == "blog.example.com")
+        {
+            record.locked = true;
+        }
+
+        let forward = serde_json::json!({ "url": "https://example.com/$1", "status_code": 301 });
+        for (pattern, action, value, priority) in [
+            ("www.example.com/*", "forwarding_url", forward, 2),
+            (
+                "example.com/assets/*",
+                "cache_level",
+                "cache_everything".into(),
+                1,
+            ),
+        ] {
+            let id = format!("demo-page-rule-{}", backend.next_id);
+            backend.next_id += 1;
+            backend
+                .page_rules
+                .entry("demo-zone-1".to_string())
+                .or_default()
+                .push(PageRule {
+                    id,
+                    targets: vec![PageRuleTarget::matching(pattern)],
+                    actions: vec![PageRuleAction {
+                        id: action.to_string(),
+                        value,
+                    }],
+                    priority,
+                    status: "active".to_string(),
+                });
+        }
+
+        backend.update_phase_rules(
+            "demo-zone-1",
+            redirect_rules::PHASE,
+            &[RulesetRule {
+                id: String::new(),
+                expression: "(http.request.full_uri wildcard \"https://example.com/docs/*\")"
+                    .to_string(),
+                description: "Docs moved to their own subdomain".to_string(),
+                action: "redirect".to_string(),
+                action_parameters: serde_json::json!({
+                    "from_value": {
+                        "target_url": {
+                            "expression": "wildcard_replace(http.request.full_uri, \
+                                \"https://example.com/docs/*\", \"https://docs.example.com/${1}\")"
+                        },
+                        "status_code": 301,
+                        "preserve_query_string": true,
+                    }
+                }),
+                enabled: true,
+            }],
+        );
+        backend.update_phase_rules(
+            "demo-zone-1",
+            waf_rules::PHASE,
+            &[RulesetRule {
+                id: String::new(),
+                expression: "(http.request.uri.path contains \"/wp-login.php\")".to_string(),
+                description: "Nobody runs WordPress here".to_string(),
+                action: "block".to_string(),
+                action_parameters: serde_json::Value::Null,
+                enabled: true,
+            }],
+        );
+        for (pattern, script) in [
+            ("example.com/api/*", Some("api-gateway")),
+            ("example.com/api/health", None),
+            ("example.com/images/*", Some("image-resizer")),
+        ] {
+            backend.create_worker_route(
+                "demo-zone-1",
+                &WorkerRoute {
+                    id: String::new(),
+                    pattern: pattern.to_string(),
+                    script: script.map(str::to_string),
+                },
+            );
+        }
+
+        let now = Utc::now();
+        for (project, environment, branch, message, status, hours_ago) in [
+            (
+                "example-blog",
+                "production",
+                "main",
+                "Publish spring post",
+                "success",
+                3,
+            ),
+            (
+                "example-blog",
+                "preview",
+                "drafts",
+                "Draft summer post",
+                "success",
+                20,
+            ),
+            (
+                "example-blog",
+                "production",
+                "main",
+                "Fix feed links",
+                "success",
+                50,
+            ),
+            (
+                "example-shop-preview",
+                "production",
+                "main",
+                "Upgrade dependencies",
+                "failure",
+                1,
+            ),
+            (
+                "example-shop-preview",
+                "production",
+                "main",
+                "Add cart",
+                "success",
+                30,
+            ),
+        ] {
+            let deployment = backend.pages_deployment(
+                project,
+                environment,
+                branch,
+                message,
+                status,
+                now - Duration::hours(hours_ago),
+            );
+            backend
+                .pages_deployments
+                .entry(project.to_string())
+                .or_default()
+                .push(deployment);
+        }
+
+        backend
+    }
+
+    fn pages_deployment(
+        &mut self,
+        project: &str,
+        environment: &str,
+        branch: &str,
+        commit_message: &str,
+        status: &str,
+        created_on: DateTime<Utc>,
+    ) -> PagesDeployment {
+        let id = format!("{:08x}", 0x5eed_0000 + self.next_id);
+        self.next_id += 1;
+        PagesDeployment {
+            url: format!("https://{}.{}.pages.dev", id, project),
+            id: id.clone(),
+            environment: environment.to_string(),
+            created_on: Some(created_on.to_rfc3339()),
+            latest_stage: Some(PagesStage {
+                name: "deploy".to_string(),
+                status: status.to_string(),
+            }),
+            deployment_trigger: Some(PagesTrigger {
+                metadata: Some(PagesTriggerMetadata {
+                    branch: branch.to_string(),
+                    commit_hash: format!("{}a1b2c3", id),
+                    commit_message: commit_message.to_string(),
+                }),
+            }),
+        }
+    }
+
+    fn add_zone(
+        &mut self,
+        name: &str,
+        plan: &str,
+        status: &str,
+        records: &[(DnsRecordType, &str, &str, bool)],
+    ) {
+        let zone_id = format!("demo-zone-{}", self.zones.len() + 1);
+        self.zones.push(Zone {
+            id: zone_id.clone(),
+            name: name.to_string(),
+            status: status.to_string(),
+            account: ZoneAccount {
+                id: ACCOUNT_ID.to_string(),
+                name: "Demo Account".to_string(),
+            },
+            paused: false,
+            plan: Some(ZonePlan {
+                name: plan.to_string(),
+            }),
+            name_servers: DEMO_NAME_SERVERS.iter().map(|ns| ns.to_string()).collect(),
+            original_name_servers: Some(
+                ORIGINAL_NAME_SERVERS
+                    .iter()
+                    .map(|ns| ns.to_string())
+                    .collect(),
+            ),
+            original_registrar: Some("namecheap, inc.".to_string()),
+            original_dnshost: None,
+            activated_on: (status == "active")
+                .then(|| (Utc::now() - Duration::days(210)).to_rfc3339()),
+        });
+
+        let records = records
+            .iter()
+            .enumerate()
+            .map(|(age, &(record_type, name, content, proxied))| {
+                // Older records first, spread over the last months
+                let created_on = (Utc::now() - Duration::days(200 - age as i64 * 11)).to_rfc3339();
+                DnsRecord {
+                    id: self.next_record_id(),
+                    record_type,
+                    name: name.to_string(),
+                    content: content.to_string(),
+                    ttl: 1,
+                    proxied,
+                    proxiable: record_type.is_proxiable(),
+                    locked: false,
+                    priority: None,
+                    comment: None,
+                    created_on: Some(created_on.clone()),
+                    modified_on: Some(created_on),
+                    data: record_data::parse(record_type, content).ok().flatten(),
+                }
+            })
+            .collect();
+        self.records.insert(zone_id, records);
+    }
+
+    fn next_record_id(&mut self) -> String {
+        let id = format!("demo-record-{}", self.next_id);
+        self.next_id += 1;
+        id
+    }
+
+    pub fn zones(&self) -> Vec<Zone> {
+        self.zones.clone()
+    }
+
+    pub fn accounts(&self) -> Vec<Account> {
+        vec![Account {
+            id: ACCOUNT_ID.to_string(),
+            name: "Demo Account".to_string(),
+        }]
+    }
+
+    /// A token for every zone of the demo account with the permissions the app uses
+    pub fn token_details(&self) -> TokenDetails {
+        let permission_groups = [
+            "DNS Write",
+            "Zone Write",
+            "Zone Settings Write",
+            "Cache Purge",
+            "Page Rules Write",
+            "Zone WAF Write",
+            "Workers Routes Write",
+            "Pages Write",
+            "Cloudflare Tunnel Write",
+            "SSL and Certificates Write",
+            "Analytics Read",
+            "Access: Audit Logs Read",
+        ]
+        .into_iter()
+        .map(|name| PermissionGroup {
+            name: name.to_string(),
+        })
+        .collect();
+
+        TokenDetails {
+            name: "Demo token".to_string(),
+            policies: vec![TokenPolicy {
+                effect: "allow".to_string(),
+                permission_groups,
+                resources: HashMap::from([(
+                    format!("com.cloudflare.api.account.{}", ACCOUNT_ID),
+                    serde_json::json!({ "com.cloudflare.api.account.zone.*": "*" }),
+                )]),
+            }],
+        }
+    }
+
+    pub fn zone(&self, zone_id: &str) -> Result<Zone> {
+        self.zones
+            .iter()
+            .find(|zone| zone.id == zone_id)
+            .cloned()
+            .ok_or_else(|| anyhow!("Zone not found"))
+    }
+
+    /// A pending zone without records, which becomes active on the first activation check
+    pub fn create_zone(&mut self, name: &str, account_id: &str) -> Result<Zone> {
+        if account_id != ACCOUNT_ID {
+            return Err(anyhow!("Account not found"));
+        }
+        if self
+            .zones
+            .iter()
+            .any(|zone| zone.name.eq_ignore_ascii_case(name))
+        {
+            return Err(anyhow!("{} already exists", name));
+        }
+        let zone = Zone {
+            id: format!("demo-zone-added-{}", self.next_id),
+            name: name.to_lowercase(),
+            status: "pending".to_string(),
+            account: ZoneAccount {
+                id: ACCOUNT_ID.to_string(),
+                name: "Demo Account".to_string(),
+            },
+            paused: false,
+            plan: Some(ZonePlan {
+                name: "Free Website".to_string(),
+            }),
+            name_servers: DEMO_NAME_SERVERS.iter().map(|ns| ns.to_string()).collect(),
+            original_name_servers: None,
+            original_registrar: None,
+            original_dnshost: None,
+            activated_on: None,
+        };
+        self.next_id += 1;
+        self.records.insert(zone.id.clone(), Vec::new());
+        self.zones.push(zone.clone());
+        Ok(zone)
+    }
+
+    pub fn activate_zone(&mut self, zone_id: &str) {
+        if let Some(zone) = self.zones.iter_mut().find(|zone| zone.id == zone_id) {
+            zone.status = "active".to_string();
+            zone.activated_on = Some(Utc::now().to_rfc3339());
+        }
+    }
+
+    pub fn delete_zone(&mut self, zone_id: &str) -> Result<()> {
+        let index = self
+            .zones
+            .iter()
+            .position(|zone| zone.id == zone_id)
+            .ok_or_else(|| anyhow!("Zone not found"))?;
+        self.zones.remove(index);
+        self.records.remove(zone_id);
+        Ok(())
+    }
+
+    pub fn records(&self, zone_id: &str) -> Result<Vec<DnsRecord>> {
+        self.records
+            .get(zone_id)
+            .cloned()
+            .ok_or_else(|| anyhow!("Zone not found"))
+    }
+
+    pub fn record(&self, zone_id: &str, record_id: &str) -> Result<DnsRecord> {
+        self.records(zone_id)?
+            .into_iter()
+            .find(|record| record.id == record_id)
+            .ok_or_else(|| anyhow!("Record not found"))
+    }
+
+    pub fn create_record(&mut self, zone_id: &str, record: &CreateDnsRecord) -> Result<DnsRecord> {
+        record
+            .record_type
+            .validate_content(&record.content)
+            .map_err(|e| anyhow!(e))?;
+        let now = Utc::now().to_rfc3339();
+        let data = match &record.data {
+            Some(data) if data.fits(record.record_type) => Some(data.clone()),
+            _ => record_data::parse(record.record_type, &record.content)
+                .ok()
+                .flatten(),
+        };
+        let created = DnsRecord {
+            id: self.next_record_id(),
+            record_type: record.record_type,
+            name: record.name.clone(),
+            content: record.content.clone(),
+            ttl: record.ttl,
+            proxied: record.proxied.unwrap_or(false),
+            proxiable: record.record_type.is_proxiable(),
+            locked: false,
+            priority: record.priority,
+            comment: record.comment.clone(),
+            created_on: Some(now.clone()),
+            modified_on: Some(now),
+            data,
+        };
+        self.records
+            .get_mut(zone_id)
+            .ok_or_else(|| anyhow!("Zone not found"))?
+            .push(created.clone());
+        Ok(created)
+    }
+
+    pub fn update_record(
+        &mut self,
+        zone_id: &str,
+        record_id: &str,
+        update: &UpdateDnsRecord,
+    ) -> Result<DnsRecord> {
+        let record = self
+            .records
+            .get_mut(zone_id)
+            .and_then(|records| records.iter_mut().find(|record| record.id == record_id))
+            .ok_or_else(|| anyhow!("Record not found"))?;
+        if record.locked {
+            return Err(anyhow!("This record is managed by Cloudflare"));
+        }
+
+        if let Some(record_type) = update.record_type {
+            record.record_type = record_type;
+            record.proxiable = record_type.is_proxiable();
+        }
+        if let Some(name) = &update.name {
+            record.name = name.clone();
+        }
+        if let Some(content) = &update.content {
+            record
+                .record_type
+                .validate_content(content)
+                .map_err(|e| anyhow!(e))?;
+            record.content = content.clone();
+        }
+        record.data = match &update.data {
+            Some(data) if data.fits(record.record_type) => Some(data.clone()),
+            _ => record_data::parse(record.record_type, &record.content)
+                .ok()
+                .flatten(),
+        };
+        if let Some(ttl) = update.ttl {
+            record.ttl = ttl;
+        }
+        if let Some(proxied) = update.proxied {
+            record.proxied = proxied && record.proxiable;
+        }
+        if update.priority.is_some() {
+            record.priority = update.priority;
+        }
+        if let Some(comment) = &update.comment {
+            record.comment = (!comment.is_empty()).then(|| comment.clone());
+        }
+        record.modified_on = Some(Utc::now().to_rfc3339());
+        Ok(record.clone())
+    }
+
+    pub fn delete_record(&mut self, zone_id: &str, record_id: &str) -> Result<()> {
+        let records = self
+            .records
+            .get_mut(zone_id)
+            .ok_or_else(|| anyhow!("Zone not found"))?;
+        let index = records
+            .iter()
+            .position(|record| record.id == record_id)
+            .ok_or_else(|| anyhow!("Record not found"))?;
+        if records[index].locked {
+            return Err(anyhow!("This record is managed by Cloudflare"));
+        }
+        records.remove(index);
+        Ok(())
+    }
+
+    /// Apply a batch like the API does: deletions, then updates, then creations, and nothing if
+    /// any of them fails
+    pub fn batch_records(&mut self, zone_id: &str, batch: &DnsRecordBatch) -> Result<()> {
+        let before = self.records(zone_id)?;
+        let result = batch
+            .deletes
+            .iter()
+            .try_for_each(|record_id| self.delete_record(zone_id, record_id))
+            .and_then(|()| {
+                batch.patches.iter().try_for_each(|(record_id, update)| {
+                    self.update_record(zone_id, record_id, update).map(|_| ())
+                })
+            })
+            .and_then(|()| {
+                batch
+                    .posts
+                    .iter()
+                    .try_for_each(|record| self.create_record(zone_id, record).map(|_| ()))
+            });
+        if result.is_err() {
+            self.records.insert(zone_id.to_string(), before);
+        }
+        result
+    }
+
+    pub fn zone_settings(&self, zone_id: &str) -> Vec<ZoneSetting> {
+        self.zone_settings
+            .get(zone_id)
+            .cloned()
+            .unwrap_or_else(default_zone_settings)
+            .into_iter()
+            .map(|setting| self.with_development_mode(zone_id, setting))
+            .collect()
+    }
+
+    pub fn zone_setting(&self, zone_id: &str, setting_id: &str) -> Result<ZoneSetting> {
+        self.zone_settings(zone_id)
+            .into_iter()
+            .find(|setting| setting.id == setting_id)
+            .ok_or_else(|| anyhow!("Unknown zone setting {}", setting_id))
+    }
+
+    pub fn update_zone_settings(
+        &mut self,
+        zone_id: &str,
+        changes: &[ZoneSetting],
+    ) -> Vec<ZoneSetting> {
+        let settings = self
+            .zone_settings
+            .entry(zone_id.to_string())
+            .or_insert_with(default_zone_settings);
+        for change in changes {
+            if let Some(setting) = settings.iter_mut().find(|setting| setting.id == change.id) {
+                setting.value = change.value.clone();
+            }
+            if change.id == "development_mode" {
+                if change.value == "on" {
+                    self.development_mode_ends
+                        .insert(zone_id.to_string(), Utc::now() + Duration::hours(3));
+                } else {
+                    self.development_mode_ends.remove(zone_id);
+                }
+            }
+        }
+        self.zone_settings(zone_id)
+            .into_iter()
+            .filter(|setting| changes.iter().any(|change| change.id == setting.id))
+            .collect()
+    }
+
+    /// Development mode turns itself off three hours after it was turned on
+    fn with_development_mode(&self, zone_id: &str, mut setting: ZoneSetting) -> ZoneSetting {
+        if setting.id == "development_mode" {
+            let remaining = self
+                .development_mode_ends
+                .get(zone_id)
+                .map(|ends| (*ends - Utc::now()).num_seconds())
+                .filter(|&seconds| seconds > 0);
+            setting.value = if remaining.is_some() { "on" } else { "off" }.into();
+            setting.time_remaining = Some(remaining.unwrap_or(0));
+        }
+        setting
+    }
+
+    pub fn page_rules(&self, zone_id: &str) -> Vec<PageRule> {
+        self.page_rules.get(zone_id).cloned().unwrap_or_default()
+    }
+
+    pub fn create_page_rule(&mut self, zone_id: &str, rule: &PageRule) -> PageRule {
+        let rule = PageRule {
+            id: format!("demo-page-rule-{}", self.next_id),
+            ..rule.clone()
+        };
+        self.next_id += 1;
+        self.page_rules
+            .entry(zone_id.to_string())
+            .or_default()
+            .push(rule.clone());
+        rule
+    }
+
+    pub fn update_page_rule(
+        &mut self,
+        zone_id: &str,
+        rule_id: &str,
+        rule: &PageRule,
+    ) -> Result<PageRule> {
+        let existing = self.page_rule_mut(zone_id, rule_id)?;
+        *existing = PageRule {
+            id: rule_id.to_string(),
+            ..rule.clone()
+        };
+        Ok(existing.clone())
+    }
+
+    pub fn set_page_rule_priority(
+        &mut self,
+        zone_id: &str,
+        rule_id: &str,
+        priority: i32,
+    ) -> Result<PageRule> {
+        let existing = self.page_rule_mut(zone_id, rule_id)?;
+        existing.priority = priority;
+        Ok(existing.clone())
+    }
+
+    pub fn delete_page_rule(&mut self, zone_id: &str, rule_id: &str) -> Result<()> {
+        self.page_rule_mut(zone_id, rule_id)?;
+        if let Some(rules) = self.page_rules.get_mut(zone_id) {
+            rules.retain(|rule| rule.id != rule_id);
+        }
+        Ok(())
+    }
+
+    fn page_rule_mut(&mut self, zone_id: &str, rule_id: &str) -> Result<&mut PageRule> {
+        self.page_rules
+            .get_mut(zone_id)
+            .and_then(|rules| rules.iter_mut().find(|rule| rule.id == rule_id))
+            .ok_or_else(|| anyhow!("Page Rule not found"))
+    }
+
+    pub fn worker_routes(&self, zone_id: &str) -> Vec<WorkerRoute> {
+        self.worker_routes.get(zone_id).cloned().unwrap_or_default()
+    }
+
+    pub fn create_worker_route(&mut self, zone_id: &str, route: &WorkerRoute) -> WorkerRoute {
+        let route = WorkerRoute {
+            id: format!("demo-worker-route-{}", self.next_id),
+            ..route.clone()
+        };
+        self.next_id += 1;
+        self.worker_routes
+            .entry(zone_id.to_string())
+            .or_default()
+            .push(route.clone());
+        route
+    }
+
+    pub fn update_worker_route(
+        &mut self,
+        zone_id: &str,
+        route_id: &str,
+        route: &WorkerRoute,
+    ) -> Result<WorkerRoute> {
+        let existing = self
+            .worker_routes
+            .get_mut(zone_id)
+            .and_then(|routes| routes.iter_mut().find(|route| route.id == route_id))
+            .ok_or_else(|| anyhow!("Workers route not found"))?;
+        *existing = WorkerRoute {
+            id: route_id.to_string(),
+            ..route.clone()
+        };
+        Ok(existing.clone())
+    }
+
+    pub fn delete_worker_route(&mut self, zone_id: &str, route_id: &str) -> Result<()> {
+        let routes = self
+            .worker_routes
+            .get_mut(zone_id)
+            .filter(|routes| routes.iter().any(|route| route.id == route_id))
+            .ok_or_else(|| anyhow!("Workers route not found"))?;
+        routes.retain(|route| route.id != route_id);
+        Ok(())
+    }
+
+    pub fn worker_scripts(&self) -> Vec<WorkerScript> {
+        WORKER_SCRIPTS
+            .iter()
+            .map(|name| WorkerScript {
+                id: name.to_string(),
+            })
+            .collect()
+    }
+
+    pub fn pages_projects(&self) -> Vec<PagesProject> {
+        PAGES_PROJECTS
+            .iter()
+            .map(|(name, subdomain, domains)| PagesProject {
+                name: name.to_string(),
+                subdomain: subdomain.to_string(),
+                domains: domains.iter().map(|domain| domain.to_string()).collect(),
+                latest_deployment: self
+                    .pages_deployments
+                    .get(*name)
+                    .and_then(|deployments| deployments.first().cloned()),
+            })
+            .collect()
+    }
+
+    pub fn pages_deployments(&self, project: &str) -> Result<Vec<PagesDeployment>> {
+        self.pages_deployments
+            .get(project)
+            .cloned()
+            .ok_or_else(|| anyhow!("Project not found"))
+    }
+
+    /// Deploy the same commit again, which succeeds in demo mode
+    pub fn retry_pages_deployment(
+        &mut self,
+        project: &str,
+        deployment_id: &str,
+    ) -> Result<PagesDeployment> {
+        let deployment = self
+            .pages_deployments(project)?
+            .into_iter()
+            .find(|deployment| deployment.id == deployment_id)
+            .ok_or_else(|| anyhow!("Deployment not found"))?;
+        let (branch, message) = deployment
+            .source()
+            .map(|source| (source.branch.clone(), source.commit_message.clone()))
+            .unwrap_or_default();
+        let retried = self.pages_deployment(
+            project,
+            &deployment.environment,
+            &branch,
+            &message,
+            "success",
+            Utc::now(),
+        );
+        self.pages_deployments
+            .entry(project.to_string())
+            .or_default()
+            .insert(0, retried.clone());
+        Ok(retried)
+    }
+
+    /// Delete a deployment; like the API, the live production deployment can't be deleted
+    pub fn delete_pages_deployment(&mut self, project: &str, deployment_id: &str) -> Result<()> {
+        let deployments = self
+            .pages_deployments
+            .get_mut(project)
+            .ok_or_else(|| anyhow!("Project not found"))?;
+        let index = deployments
+            .iter()
+            .position(|deployment| deployment.id == deployment_id)
+            .ok_or_else(|| anyhow!("Deployment not found"))?;
+        let live = deployments.iter().position(|deployment| {
+            deployment.environment == "production"
+                && deployment
+                    .latest_stage
+                    .as_ref()
+                    .is_some_and(|stage| stage.status == "success")
+        });
+        if live == Some(index) {
+            return Err(anyhow!(
+                "The latest production deployment can't be deleted while it is live"
+            ));
+        }
+        deployments.remove(index);
+        Ok(())
+    }
+
+    pub fn tunnels(&self) -> Vec<Tunnel> {
+        TUNNELS
+            .iter()
+            .enumerate()
+            .map(|(index, (id, name, status, connections))| Tunnel {
+                id: id.to_string(),
+                name: name.to_string(),
+                status: status.to_string(),
+                conns_inactive_at: connections
+                    .is_empty()
+                    .then(|| (Utc::now() - Duration::days(3)).to_rfc3339()),
+                connections: connections
+                    .iter()
+                    .map(|(client, version, colo)| TunnelConnection {
+                        client_id: format!("demo-connector-{}", client),
+                        client_version: version.to_string(),
+                        colo_name: colo.to_string(),
+                        origin_ip: format!("198.51.100.{}", 40 + index),
+                        // The outdated connector of the degraded tunnel keeps dropping
+                        is_pending_reconnect: *status == "degraded" && version.starts_with("2023"),
+                    })
+                    .collect(),
+            })
+            .collect()
+    }
+
+    pub fn tunnel_ingress(&self, tunnel_id: &str) -> Vec<TunnelIngress> {
+        TUNNEL_INGRESS
+            .iter()
+            .filter(|(id, _)| *id == tunnel_id)
+            .flat_map(|(_, rules)| rules.iter())
+            .map(|(hostname, service)| TunnelIngress {
+                hostname: hostname.map(str::to_string),
+                service: service.to_string(),
+            })
+            .collect()
+    }
+
+    /// Made-up query counts for the zone's records; every fourth record isn't queried at all
+    pub fn dns_query_counts(&self, zone_id: &str) -> Result<Vec<DnsQueryCount>> {
+        let records = self.records(zone_id)?;
+        let mut counts: Vec<DnsQueryCount> = Vec::new();
+        for (index, record) in records.iter().enumerate() {
+            let seed: u64 = record.name.bytes().map(u64::from).sum();
+            if index % 4 == 3
+                || counts.iter().any(|count| {
+                    count.name == record.name && count.record_type == record.record_type.as_str()
+                })
+            {
+                continue;
+            }
+            counts.push(DnsQueryCount {
+                name: record.name.clone(),
+                record_type: record.record_type.to_string(),
+                queries: seed * 7919 % 50_000 + 12,
+            });
+        }
+        counts.sort_by_key(|count| std::cmp::Reverse(count.queries));
+        Ok(counts)
+    }
+
+    /// Made-up traffic peaking every afternoon, from `since` until now
+    pub fn zone_traffic(&self, since: DateTime<Utc>, hourly: bool) -> Vec<TrafficPoint> {
+        let step = if hourly { 3600 } else { 86400 };
+        let mut points = Vec::new();
+        let mut start = since.timestamp() / step * step;
+        while start <= Utc::now().timestamp() {
+            let hours: Vec<i64> = (0..step / 3600).map(|hour| start / 3600 + hour).collect();
+            let requests: u64 = hours
+                .iter()
+                .map(|hour| {
+                    let time_of_day = (hour % 24) as f64 - 15.0;
+                    let rhythm = 1.0 + 0.6 * (time_of_day / 24.0 * std::f64::consts::TAU).cos();
+                    (1200.0 * rhythm) as u64 + (hour * 7919 % 300) as u64
+                })
+                .sum();
+            let threats: u64 = hours.iter().map(|hour| (hour * 31 % 17) as u64 / 8).sum();
+            let cached_requests = requests * (60 + (start / step % 25) as u64) / 100;
+
+            let timestamp = DateTime::from_timestamp(start, 0).unwrap_or_default();
+            points.push(TrafficPoint {
+                timeslot: if hourly {
+                    timestamp.format("%Y-%m-%dT%H:00:00Z").to_string()
+                } else {
+                    timestamp.format("%Y-%m-%d").to_string()
+                },
+                requests,
+                bytes: requests * 48_000,
+                cached_requests,
+                cached_bytes: cached_requests * 52_000,
+                threats,
+            });
+            start += step;
+        }
+        points
+    }
+
+    pub fn phase_rules(&self, zone_id: &str, phase: &str) -> Vec<RulesetRule> {
+        self.phase_rules
+            .get(&(zone_id.to_string(), phase.to_string()))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Replace the rules of a phase, giving new ones an id
+    pub fn update_phase_rules(
+        &mut self,
+        zone_id: &str,
+        phase: &str,
+        rules: &[RulesetRule],
+    ) -> Vec<RulesetRule> {
+        let rules: Vec<RulesetRule> = rules
+            .iter()
+            .map(|rule| {
+                let mut rule = rule.clone();
+                if rule.id.is_empty() {
+                    rule.id = format!("demo-rule-{}", self.next_id);
+                    self.next_id += 1;
+                }
+                rule
+            })
+            .collect();
+        self.phase_rules
+            .insert((zone_id.to_string(), phase.to_string()), rules.clone());
+        rules
+    }
+
+    pub fn dnssec(&self, zone_id: &str) -> Dnssec {
+        Dnssec {
+            status: if zone_id == "demo-zone-1" {
+                "active".to_string()
+            } else {
+                "disabled".to_string()
+            },
+        }
+    }
+
+    /// A universal certificate per active zone, one of them about to expire, and an advanced
+    /// certificate waiting for validation on the first zone
+    pub fn certificate_packs(&self, zone_id: &str) -> Vec<CertificatePack> {
+        let Some(zone) = self
+            .zones
+            .iter()
+            .find(|zone| zone.id == zone_id && zone.status == "active")
+        else {
+            return Vec::new();
+        };
+        let mut packs = Vec::new();
+        if !self.universal_ssl_disabled.contains(zone_id) {
+            let days = if zone_id == "demo-zone-2" { 9 } else { 74 };
+            let hosts = vec![zone.name.clone(), format!("*.{}", zone.name)];
+            packs.push(CertificatePack {
+                id: format!("{}-pack", zone_id),
+                pack_type: "universal".to_string(),
+                hosts: hosts.clone(),
+                status: "active".to_string(),
+                certificates: vec![Certificate {
+                    id: format!("{}-certificate", zone_id),
+                    hosts,
+                    status: "active".to_string(),
+                    expires_on: Some((Utc::now() + Duration::days(days)).to_rfc3339()),
+                    issuer: Some("GoogleTrustServices".to_string()),
+                    signature: Some("ECDSAWithSHA256".to_string()),
+                }],
+                certificate_authority: Some("google".to_string()),
+                validation_method: Some("txt".to_string()),
+                validity_days: Some(90),
+                validation_records: Vec::new(),
+                validation_errors: Vec::new(),
+            });
+        }
+        if zone_id == "demo-zone-1" {
+            let host = format!("shop.{}", zone.name);
+            packs.push(CertificatePack {
+                id: format!("{}-advanced-pack", zone_id),
+                pack_type: "advanced".to_string(),
+                hosts: vec![host.clone()],
+                status: "pending_validation".to_string(),
+                certificates: Vec::new(),
+                certificate_authority: Some("lets_encrypt".to_string()),
+                validation_method: Some("txt".to_string()),
+                validity_days: Some(90),
+                validation_records: vec![ValidationRecord {
+                    status: Some("pending".to_string()),
+                    txt_name: Some(format!("_acme-challenge.{}", host)),
+                    txt_value: Some("Nd8PxTq2vY0hS1e7kLm4cR9aWbZ3fG6jQ5uXoI2nEsA".to_string()),
+                    http_url: None,
+                    http_body: None,
+                }],
+                validation_errors: Vec::new(),
+            });
+        }
+        packs
+    }
+
+    pub fn certificate_pack(&self, zone_id: &str, pack_id: &str) -> Result<CertificatePack> {
+        self.certificate_packs(zone_id)
+            .into_iter()
+            .find(|pack| pack.id == pack_id)
+            .ok_or_else(|| anyhow!("Certificate pack not found"))
+    }
+
+    pub fn universal_ssl(&self, zone_id: &str) -> UniversalSsl {
+        UniversalSsl {
+            enabled: !self.universal_ssl_disabled.contains(zone_id),
+        }
+    }
+
+    pub fn set_universal_ssl(&mut self, zone_id: &str, enabled: bool) -> UniversalSsl {
+        if enabled {
+            self.universal_ssl_disabled.remove(zone_id);
+        } else {
+            self.universal_ssl_disabled.insert(zone_id.to_string());
+        }
+        self.universal_ssl(zone_id)
+    }
+}
+
+fn default_zone_settings() -> Vec<ZoneSetting> {
+    [
+        ("ssl", "full"),
+        ("always_use_https", "on"),
+        ("automatic_https_rewrites", "on"),
+        ("min_tls_version", "1.2"),
+        ("brotli", "on"),
+        ("http3", "on"),
+        ("development_mode", "off"),
+    ]
+    .into_iter()
+    .map(|(id, value)| ZoneSetting {
+        id: id.to_string(),
+        value: value.into(),
+        editable: true,
+        time_remaining: None,
+    })
+    .collect()
+}
@@ -0,0 +1,23 @@
+//! Native desktop notifications, shown even when the window is in the background
+
+use notify_rust::Notification;
+
+const APP_NAME: &str = "Cloudflare DNS Manager";
+
+/// Show a desktop notification
+///
+/// Delivery happens on a separate thread since some platforms block until the
+/// notification server responds. Failures are ignored, as the in-app notification
+/// is always shown as well.
+pub fn show(summary: impl Into<String>, body: impl Into<String>) {
+    let summary = summary.into();
+    let body = body.into();
+
+    std::thread::spawn(move || {
+        let _ = Notification::new()
+            .appname(APP_NAME)
+            .summary(&summary)
+            .body(&body)
+            .show();
+    });
+}
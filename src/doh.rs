@@ -0,0 +1,65 @@
+//! DNS queries over HTTPS through Cloudflare's public resolver, in its JSON format
+//!
+//! Queries are sent with the caller's client, which should take the same proxy route as API
+//! requests, e.g. one from [`crate::network_proxy::http_client`].
+
+use anyhow::{Result, anyhow};
+use reqwest::Client;
+use serde::Deserialize;
+
+const DOH_URL: &str = "https://cloudflare-dns.com/dns-query";
+
+pub const TYPE_A: u16 = 1;
+pub const TYPE_CNAME: u16 = 5;
+pub const TYPE_PTR: u16 = 12;
+pub const TYPE_TXT: u16 = 16;
+
+#[derive(Deserialize)]
+struct DohResponse {
+    #[serde(rename = "Status")]
+    status: u32,
+    #[serde(rename = "Answer", default)]
+    answer: Vec<Answer>,
+}
+
+/// Record in the answer section, e.g. a CNAME followed while answering an A query
+#[derive(Debug, Clone, Deserialize)]
+pub struct Answer {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub record_type: u16,
+    pub data: String,
+}
+
+/// Answer section of a query; a missing name is no answer rather than an error
+pub async fn query(client: &Client, name: &str, record_type: u16) -> Result<Vec<Answer>> {
+    let response: DohResponse = client
+        .get(DOH_URL)
+        .query(&[("name", name), ("type", &record_type.to_string())])
+        .header("accept", "application/dns-json")
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    // 0 is NOERROR and 3 is NXDOMAIN
+    if response.status != 0 && response.status != 3 {
+        return Err(anyhow!(
+            "Lookup of {} failed with status {}",
+            name,
+            response.status
+        ));
+    }
+    Ok(response.answer)
+}
+
+/// Data of the answers of the queried type
+pub async fn query_data(client: &Client, name: &str, record_type: u16) -> Result<Vec<String>> {
+    Ok(query(client, name, record_type)
+        .await?
+        .into_iter()
+        .filter(|answer| answer.record_type == record_type)
+        .map(|answer| answer.data)
+        .collect())
+}
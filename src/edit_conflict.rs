@@ -0,0 +1,108 @@
+//! Merging an update of a record with changes made to it elsewhere since it was loaded
+//!
+//! Saving re-fetches the record first. If it was modified on Cloudflare in the meantime, the
+//! fields changed there and the fields changed in the editor are compared: when they don't
+//! overlap, both sets of changes can be kept; when the same field was changed to different
+//! values on both sides, only one side can win.
+
+use crate::cloudflare::{DnsRecord, UpdateDnsRecord};
+use crate::watch::{FieldChange, diff_update};
+
+/// A record that changed on Cloudflare while it was being edited
+#[derive(Debug, Clone)]
+pub struct UpdateConflict {
+    /// The record as it is on Cloudflare now
+    pub remote: DnsRecord,
+    /// Fields changed on Cloudflare since the record was loaded
+    pub theirs: Vec<FieldChange>,
+    /// Fields changed in the editor
+    pub mine: Vec<FieldChange>,
+    /// Fields changed on both sides to different values
+    pub clashes: Vec<&'static str>,
+}
+
+impl UpdateConflict {
+    /// The update keeping the remote changes and the editor's, unless they clash
+    pub fn merged(&self, update: &UpdateDnsRecord) -> Option<UpdateDnsRecord> {
+        if !self.clashes.is_empty() {
+            return None;
+        }
+
+        let mut merged = full_update(&self.remote);
+        for change in &self.mine {
+            match change.field {
+                "Type" => {
+                    merged.record_type = update.record_type;
+                    // Whether the record can be proxied goes with the type
+                    merged.proxied = update.proxied;
+                }
+                "Name" => merged.name = update.name.clone(),
+                "Content" => merged.content = update.content.clone(),
+                "TTL" => merged.ttl = update.ttl,
+                "Proxy" => merged.proxied = update.proxied,
+                "Priority" => merged.priority = update.priority,
+                "Comment" => merged.comment = update.comment.clone(),
+                _ => {}
+            }
+        }
+        // The remote fields only describe the remote content
+        if merged.record_type != Some(self.remote.record_type)
+            || merged.content.as_deref() != Some(self.remote.data_content().as_str())
+        {
+            merged.data = None;
+        }
+        Some(merged)
+    }
+}
+
+/// Compare the record loaded into the editor with its `remote` version, if fields the editor
+/// shows were modified since
+///
+/// `update` is what the editor would save; names are completed with `zone_name` as in
+/// [`diff_update`].
+pub fn find_conflict(
+    loaded: &DnsRecord,
+    remote: &DnsRecord,
+    update: &UpdateDnsRecord,
+    zone_name: &str,
+) -> Option<UpdateConflict> {
+    if remote.modified_on == loaded.modified_on {
+        return None;
+    }
+
+    let theirs = diff_update(loaded, &full_update(remote), zone_name);
+    if theirs.is_empty() {
+        return None;
+    }
+    let mine = diff_update(loaded, update, zone_name);
+    let clashes = mine
+        .iter()
+        .filter(|change| {
+            theirs
+                .iter()
+                .any(|other| other.field == change.field && other.after != change.after)
+        })
+        .map(|change| change.field)
+        .collect();
+
+    Some(UpdateConflict {
+        remote: remote.clone(),
+        theirs,
+        mine,
+        clashes,
+    })
+}
+
+/// An update setting every field to the record's values
+fn full_update(record: &DnsRecord) -> UpdateDnsRecord {
+    UpdateDnsRecord {
+        record_type: Some(record.record_type),
+        name: Some(record.name.clone()),
+        content: Some(record.data_content()),
+        ttl: Some(record.ttl),
+        proxied: record.record_type.is_proxiable().then_some(record.proxied),
+        priority: record.priority,
+        comment: Some(record.comment.clone().unwrap_or_default()),
+        data: record.data.clone(),
+    }
+}
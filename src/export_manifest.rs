@@ -0,0 +1,97 @@
+//! Manifests of zone file exports, for showing what changed since the previous export
+//!
+//! A manifest keeps the records of the last export of each zone as JSON in the app's data
+//! directory. Comparing it with the next export turns regular exports into a lightweight change
+//! detection that works without the journal.
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::cloudflare::DnsRecord;
+use crate::watch::{FieldChange, RecordChange, diff_record};
+
+/// The records of a zone as of its last export
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ExportManifest {
+    pub exported_at: DateTime<Utc>,
+    pub records: Vec<DnsRecord>,
+}
+
+impl ExportManifest {
+    pub fn new(records: Vec<DnsRecord>) -> Self {
+        Self {
+            exported_at: Utc::now(),
+            records,
+        }
+    }
+
+    /// Records added, modified or deleted since this export, in the order of the export
+    pub fn changes(&self, records: &[DnsRecord]) -> Vec<RecordChange> {
+        let mut changes: Vec<RecordChange> = self
+            .records
+            .iter()
+            .filter_map(|old| match records.iter().find(|r| r.id == old.id) {
+                Some(new) => {
+                    let mut fields = diff_record(old, new);
+                    if old.name != new.name {
+                        let name = FieldChange {
+                            field: "Name",
+                            before: old.name.clone(),
+                            after: new.name.clone(),
+                        };
+                        fields.insert(0, name);
+                    }
+                    (!fields.is_empty()).then(|| RecordChange::Modified {
+                        record: new.clone(),
+                        fields,
+                    })
+                }
+                None => Some(RecordChange::Deleted(old.clone())),
+            })
+            .collect();
+
+        changes.extend(
+            records
+                .iter()
+                .filter(|new| !self.records.iter().any(|old| old.id == new.id))
+                .map(|new| RecordChange::Added(new.clone())),
+        );
+        changes
+    }
+}
+
+fn manifest_path(zone_id: &str) -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| {
+        dir.join("cloudflare-admin")
+            .join("exports")
+            .join(format!("{}.json", zone_id))
+    })
+}
+
+/// The manifest of the zone's last export, if it was exported with manifests enabled
+pub fn load(zone_id: &str) -> Result<Option<ExportManifest>> {
+    let Some(path) = manifest_path(zone_id) else {
+        return Ok(None);
+    };
+    match fs::read_to_string(path) {
+        Ok(json) => Ok(Some(serde_json::from_str(&json)?)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Replace the zone's manifest with the one of the export just saved
+pub fn store(zone_id: &str, manifest: &ExportManifest) -> Result<()> {
+    let Some(path) = manifest_path(zone_id) else {
+        return Ok(());
+    };
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, serde_json::to_string(manifest)?)?;
+    Ok(())
+}
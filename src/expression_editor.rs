@@ -0,0 +1,249 @@
+//! Expression of a ruleset rule being edited, either as the builder's conditions or as text
+//!
+//! Expressions the builder can show open in it; others open as text. Switching between the two
+//! carries the expression over, as far as the builder can show it.
+
+use gpui::{App as GpuiApp, AppContext, Entity, Window};
+use gpui_component::input::InputState;
+
+use crate::rule_expression::{self, Condition, FIELDS, Field, Operator, SyntaxError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExpressionMode {
+    Builder,
+    Text,
+}
+
+/// A condition in the builder, its value in an input
+pub struct ConditionRow {
+    pub field: Field,
+    pub operator: Operator,
+    pub value: Entity<InputState>,
+}
+
+pub struct ExpressionEditor {
+    pub mode: ExpressionMode,
+    /// Groups of conditions; a group matches when all its conditions do, the expression when
+    /// any group does
+    pub groups: Vec<Vec<ConditionRow>>,
+    pub text_input: Entity<InputState>,
+}
+
+impl ExpressionEditor {
+    pub fn new(placeholder: &'static str, window: &mut Window, cx: &mut GpuiApp) -> Self {
+        let text_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .multi_line(true)
+                .rows(4)
+                .placeholder(placeholder)
+        });
+        let mut editor = Self {
+            mode: ExpressionMode::Builder,
+            groups: Vec::new(),
+            text_input,
+        };
+        editor.set_expression("", window, cx);
+        editor
+    }
+
+    /// Edit `expression`, in the builder if it can show it
+    pub fn set_expression(&mut self, expression: &str, window: &mut Window, cx: &mut GpuiApp) {
+        let expression = expression.to_string();
+        let groups = if expression.trim().is_empty() {
+            Some(Vec::new())
+        } else {
+            rule_expression::conditions(&expression)
+        };
+        self.text_input.update(cx, |input, cx| {
+            input.set_value(expression, window, cx);
+        });
+
+        match groups {
+            Some(groups) => {
+                self.mode = ExpressionMode::Builder;
+                self.set_groups(groups, window, cx);
+            }
+            None => {
+                self.mode = ExpressionMode::Text;
+                self.groups.clear();
+            }
+        }
+    }
+
+    fn set_groups(&mut self, groups: Vec<Vec<Condition>>, window: &mut Window, cx: &mut GpuiApp) {
+        self.groups = groups
+            .into_iter()
+            .map(|group| {
+                group
+                    .into_iter()
+                    .map(|condition| {
+                        let row = new_row(condition.field, window, cx);
+                        row.value.update(cx, |input, cx| {
+                            input.set_value(condition.value, window, cx);
+                        });
+                        ConditionRow {
+                            operator: condition.operator,
+                            ..row
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        if self.groups.is_empty() {
+            self.add_group(window, cx);
+        }
+    }
+
+    fn conditions(&self, cx: &GpuiApp) -> Vec<Vec<Condition>> {
+        self.groups
+            .iter()
+            .map(|group| {
+                group
+                    .iter()
+                    .map(|row| Condition {
+                        field: row.field,
+                        operator: row.operator,
+                        value: row.value.read(cx).value().to_string(),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// The expression to save, failing with where it is invalid
+    pub fn expression(&self, cx: &GpuiApp) -> Result<String, String> {
+        match self.mode {
+            ExpressionMode::Builder => rule_expression::build(&self.conditions(cx)),
+            ExpressionMode::Text => {
+                let text = self.text_input.read(cx).value();
+                if let Some(error) = self.syntax_error(cx) {
+                    return Err(error.describe(&text));
+                }
+                Ok(text.trim().to_string())
+            }
+        }
+    }
+
+    /// Whether nothing was entered, in the builder or as text
+    pub fn is_empty(&self, cx: &GpuiApp) -> bool {
+        match self.mode {
+            ExpressionMode::Builder => self
+                .conditions(cx)
+                .iter()
+                .flatten()
+                .all(|condition| condition.value.trim().is_empty()),
+            ExpressionMode::Text => self.text_input.read(cx).value().trim().is_empty(),
+        }
+    }
+
+    /// Syntax error of the expression entered as text
+    pub fn syntax_error(&self, cx: &GpuiApp) -> Option<SyntaxError> {
+        let text = self.text_input.read(cx).value();
+        if self.mode != ExpressionMode::Text || text.trim().is_empty() {
+            return None;
+        }
+        rule_expression::parse(&text).err()
+    }
+
+    /// Switch between the builder and the text, failing if the builder can't show the text
+    pub fn set_mode(
+        &mut self,
+        mode: ExpressionMode,
+        window: &mut Window,
+        cx: &mut GpuiApp,
+    ) -> Result<(), String> {
+        if mode == self.mode {
+            return Ok(());
+        }
+
+        match mode {
+            ExpressionMode::Text => {
+                let expression = if self.is_empty(cx) {
+                    String::new()
+                } else {
+                    rule_expression::build(&self.conditions(cx))?
+                };
+                self.text_input.update(cx, |input, cx| {
+                    input.set_value(expression, window, cx);
+                });
+                self.groups.clear();
+            }
+            ExpressionMode::Builder => {
+                let text = self.text_input.read(cx).value().to_string();
+                let groups = if text.trim().is_empty() {
+                    Vec::new()
+                } else {
+                    rule_expression::conditions(&text).ok_or_else(|| {
+                        "The builder can't show this expression, so it can only be edited as text"
+                            .to_string()
+                    })?
+                };
+                self.set_groups(groups, window, cx);
+            }
+        }
+        self.mode = mode;
+        Ok(())
+    }
+
+    /// Add a condition all requests of the group have to meet too
+    pub fn add_condition(&mut self, group: usize, window: &mut Window, cx: &mut GpuiApp) {
+        let row = new_row(FIELDS[0], window, cx);
+        if let Some(group) = self.groups.get_mut(group) {
+            group.push(row);
+        }
+    }
+
+    /// Add a group of conditions requests can meet instead of the others
+    pub fn add_group(&mut self, window: &mut Window, cx: &mut GpuiApp) {
+        self.groups.push(vec![new_row(FIELDS[0], window, cx)]);
+    }
+
+    pub fn remove_condition(
+        &mut self,
+        group: usize,
+        index: usize,
+        window: &mut Window,
+        cx: &mut GpuiApp,
+    ) {
+        let Some(conditions) = self.groups.get_mut(group) else {
+            return;
+        };
+        if index < conditions.len() {
+            conditions.remove(index);
+        }
+        if conditions.is_empty() {
+            self.groups.remove(group);
+        }
+        if self.groups.is_empty() {
+            self.add_group(window, cx);
+        }
+    }
+
+    /// Compare another field in a condition, keeping its operator if it applies to the field
+    pub fn set_field(&mut self, group: usize, index: usize, field: Field) {
+        if let Some(row) = self.row_mut(group, index) {
+            row.field = field;
+            if !row.operator.applies_to(field.kind) {
+                row.operator = Operator::Equals;
+            }
+        }
+    }
+
+    pub fn set_operator(&mut self, group: usize, index: usize, operator: Operator) {
+        if let Some(row) = self.row_mut(group, index) {
+            row.operator = operator;
+        }
+    }
+
+    fn row_mut(&mut self, group: usize, index: usize) -> Option<&mut ConditionRow> {
+        self.groups.get_mut(group)?.get_mut(index)
+    }
+}
+
+fn new_row(field: Field, window: &mut Window, cx: &mut GpuiApp) -> ConditionRow {
+    ConditionRow {
+        field,
+        operator: Operator::Equals,
+        value: cx.new(|cx| InputState::new(window, cx).placeholder("Value")),
+    }
+}
@@ -0,0 +1,194 @@
+//! DNS record search queries, saved filters, and search history
+
+use serde::{Deserialize, Serialize};
+
+use crate::cloudflare::DnsRecord;
+
+/// Maximum number of recent searches to remember
+pub const MAX_SEARCH_HISTORY: usize = 10;
+
+/// A named search query saved for a zone
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct SavedFilter {
+    pub name: String,
+    pub query: String,
+}
+
+/// A single term of a parsed search query
+#[derive(Debug, Clone, PartialEq)]
+enum QueryTerm {
+    /// `type:A,AAAA` - record type is one of the given types
+    Type(Vec<String>),
+    /// `proxied:yes` / `proxied:no`
+    Proxied(bool),
+    /// `has:comment`
+    HasComment,
+    /// `name:www` - name contains the text
+    Name(String),
+    /// `content:1.2.3.4` - content contains the text
+    Content(String),
+    /// Free text matched against name, content and comment
+    Text(String),
+}
+
+/// A parsed search query
+///
+/// Queries are whitespace-separated terms which must all match. Besides free
+/// text, the following keys are understood: `type:`, `proxied:`, `has:comment`,
+/// `name:` and `content:`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RecordQuery {
+    terms: Vec<QueryTerm>,
+}
+
+impl RecordQuery {
+    pub fn parse(query: &str) -> Self {
+        let terms = query
+            .split_whitespace()
+            .map(|word| {
+                let Some((key, value)) = word.split_once(':') else {
+                    return QueryTerm::Text(word.to_lowercase());
+                };
+                match (key.to_lowercase().as_str(), value.to_lowercase()) {
+                    ("type", value) => QueryTerm::Type(
+                        value
+                            .split(',')
+                            .filter(|t| !t.is_empty())
+                            .map(|t| t.to_string())
+                            .collect(),
+                    ),
+                    ("proxied", value) => {
+                        QueryTerm::Proxied(matches!(value.as_str(), "yes" | "true" | "on"))
+                    }
+                    ("has", value) if value == "comment" => QueryTerm::HasComment,
+                    ("name", value) => QueryTerm::Name(value),
+                    ("content", value) => QueryTerm::Content(value),
+                    _ => QueryTerm::Text(word.to_lowercase()),
+                }
+            })
+            .collect();
+
+        Self { terms }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Check whether a record matches every term of the query
+    pub fn matches(&self, record: &DnsRecord) -> bool {
+        self.terms.iter().all(|term| match term {
+            QueryTerm::Type(types) => types
+                .iter()
+                .any(|t| t.eq_ignore_ascii_case(record.record_type.as_str())),
+            QueryTerm::Proxied(proxied) => record.proxied == *proxied,
+            QueryTerm::HasComment => record.comment.as_ref().is_some_and(|c| !c.is_empty()),
+            QueryTerm::Name(text) => record.name.to_lowercase().contains(text),
+            QueryTerm::Content(text) => record.content.to_lowercase().contains(text),
+            QueryTerm::Text(text) => {
+                record.name.to_lowercase().contains(text)
+                    || record.content.to_lowercase().contains(text)
+                    || record
+                        .comment
+                        .as_ref()
+                        .is_some_and(|c| c.to_lowercase().contains(text))
+            }
+        })
+    }
+}
+
+/// Record types of the query's `type:` term, uppercased
+pub fn query_types(query: &str) -> Vec<String> {
+    query
+        .split_whitespace()
+        .filter_map(|word| word.split_once(':'))
+        .find(|(key, _)| key.eq_ignore_ascii_case("type"))
+        .map(|(_, value)| {
+            value
+                .split(',')
+                .filter(|t| !t.is_empty())
+                .map(|t| t.to_uppercase())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Add a record type to the query's `type:` term, or remove it if it's already there
+///
+/// The rest of the query is kept as is, and the term is dropped once no types are left.
+pub fn toggle_query_type(query: &str, record_type: &str) -> String {
+    let mut types = query_types(query);
+    if let Some(index) = types
+        .iter()
+        .position(|t| t.eq_ignore_ascii_case(record_type))
+    {
+        types.remove(index);
+    } else {
+        types.push(record_type.to_uppercase());
+    }
+
+    let mut words: Vec<String> = query
+        .split_whitespace()
+        .filter(|word| {
+            !word
+                .split_once(':')
+                .is_some_and(|(key, _)| key.eq_ignore_ascii_case("type"))
+        })
+        .map(|word| word.to_string())
+        .collect();
+    if !types.is_empty() {
+        words.insert(0, format!("type:{}", types.join(",")));
+    }
+    words.join(" ")
+}
+
+/// Quick filters shown next to the record type chips: label and the query term they add
+pub const QUICK_FILTERS: &[(&str, &str)] = &[
+    ("Proxied", "proxied:yes"),
+    ("DNS-only", "proxied:no"),
+    ("Has comment", "has:comment"),
+];
+
+/// Whether the query has a term equivalent to `term`, e.g. `proxied:true` for `proxied:yes`
+pub fn has_query_term(query: &str, term: &str) -> bool {
+    let term = RecordQuery::parse(term).terms;
+    RecordQuery::parse(query)
+        .terms
+        .iter()
+        .any(|t| term.contains(t))
+}
+
+/// Add a term to the query, replacing one of the same kind, or remove it if it's already there
+///
+/// Adding `proxied:no` drops `proxied:yes`, since a record can't match both.
+pub fn toggle_query_term(query: &str, term: &str) -> String {
+    let active = has_query_term(query, term);
+    let kind = |text: &str| {
+        RecordQuery::parse(text)
+            .terms
+            .first()
+            .map(std::mem::discriminant)
+    };
+    let term_kind = kind(term);
+
+    let mut words: Vec<&str> = query
+        .split_whitespace()
+        .filter(|word| kind(word) != term_kind)
+        .collect();
+    if !active {
+        words.push(term);
+    }
+    words.join(" ")
+}
+
+/// Add a query to the front of the search history, removing duplicates
+pub fn push_search_history(history: &mut Vec<String>, query: &str) {
+    let query = query.trim();
+    if query.is_empty() {
+        return;
+    }
+
+    history.retain(|q| q != query);
+    history.insert(0, query.to_string());
+    history.truncate(MAX_SEARCH_HISTORY);
+}
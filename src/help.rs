@@ -0,0 +1,122 @@
+//! Help for record fields and settings, shown in popovers next to them
+//!
+//! Kept in one place so the explanations stay consistent and are easy to review.
+
+/// Field or setting that has help
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HelpTopic {
+    RecordType,
+    RecordName,
+    RecordContent,
+    Ttl,
+    Priority,
+    Proxied,
+    Comment,
+    ApiToken,
+    ControlServerPort,
+    ControlServerToken,
+}
+
+/// What a field does, which values it accepts and where to read more
+pub struct Help {
+    pub title: &'static str,
+    pub description: &'static str,
+    pub valid_values: Option<&'static str>,
+    pub docs_url: Option<&'static str>,
+}
+
+impl HelpTopic {
+    pub fn help(&self) -> Help {
+        match self {
+            HelpTopic::RecordType => Help {
+                title: "Type",
+                description: "What the record is used for, e.g. A and AAAA point a name at an \
+                              address and MX receives mail. Changing the type of an existing \
+                              record replaces how resolvers interpret its content.",
+                valid_values: None,
+                docs_url: Some(
+                    "https://developers.cloudflare.com/dns/manage-dns-records/reference/dns-record-types/",
+                ),
+            },
+            HelpTopic::RecordName => Help {
+                title: "Name",
+                description: "The host name the record answers for. Names without the zone are \
+                              completed with it, and @ stands for the zone itself.",
+                valid_values: Some(
+                    "Letters, digits, hyphens and dots; * as the first label for a wildcard",
+                ),
+                docs_url: Some(
+                    "https://developers.cloudflare.com/dns/manage-dns-records/how-to/create-dns-records/",
+                ),
+            },
+            HelpTopic::RecordContent => Help {
+                title: "Content",
+                description: "The value resolvers return for the name. Its format depends on the \
+                              type and is shown below the field.",
+                valid_values: None,
+                docs_url: Some(
+                    "https://developers.cloudflare.com/dns/manage-dns-records/reference/dns-record-types/",
+                ),
+            },
+            HelpTopic::Ttl => Help {
+                title: "TTL",
+                description: "How long resolvers may cache the record, in seconds. Lower values \
+                              spread changes faster at the cost of more queries. Proxied records \
+                              always use Auto.",
+                valid_values: Some("1 for Auto, or 60 to 86400 seconds"),
+                docs_url: Some(
+                    "https://developers.cloudflare.com/dns/manage-dns-records/reference/ttl/",
+                ),
+            },
+            HelpTopic::Priority => Help {
+                title: "Priority",
+                description: "Order in which senders and clients try records of the same name. \
+                              The lowest value is tried first; equal values share the load.",
+                valid_values: Some("0 to 65535"),
+                docs_url: Some(
+                    "https://developers.cloudflare.com/dns/manage-dns-records/how-to/email-records/",
+                ),
+            },
+            HelpTopic::Proxied => Help {
+                title: "Proxy status",
+                description: "Proxied records answer with Cloudflare's addresses, so traffic \
+                              passes through Cloudflare's cache, firewall and DDoS protection and \
+                              the origin stays hidden. DNS only records return the origin as is.",
+                valid_values: Some("A, AAAA and CNAME records only"),
+                docs_url: Some("https://developers.cloudflare.com/dns/proxy-status/"),
+            },
+            HelpTopic::Comment => Help {
+                title: "Comment",
+                description: "A note for whoever maintains the zone. It is not part of DNS \
+                              answers, and the record list search matches it as well.",
+                valid_values: Some("Up to 100 characters on the Free plan, 500 on paid plans"),
+                docs_url: Some(
+                    "https://developers.cloudflare.com/dns/manage-dns-records/reference/record-attributes/",
+                ),
+            },
+            HelpTopic::ApiToken => Help {
+                title: "API Token",
+                description: "The token this app uses to call Cloudflare's API. It is stored in \
+                              the system keychain and only sent to Cloudflare.",
+                valid_values: Some("A token with Zone Read and DNS Edit permissions"),
+                docs_url: Some(
+                    "https://developers.cloudflare.com/fundamentals/api/get-started/create-token/",
+                ),
+            },
+            HelpTopic::ControlServerPort => Help {
+                title: "Port",
+                description: "The port the local automation server listens on. It only accepts \
+                              connections from this computer.",
+                valid_values: Some("1 to 65535; ports below 1024 usually need admin rights"),
+                docs_url: None,
+            },
+            HelpTopic::ControlServerToken => Help {
+                title: "Token",
+                description: "Tools send this token as a bearer token with every request. \
+                              Regenerate it if it leaked; tools using the old one stop working.",
+                valid_values: None,
+                docs_url: None,
+            },
+        }
+    }
+}
@@ -0,0 +1,167 @@
+//! Undo/redo history of the app's actions
+//!
+//! Each history entry holds the command that reverts an action. Running it
+//! yields the command that repeats the action, which moves to the other stack.
+
+use anyhow::Result;
+
+use crate::cloudflare::{CloudflareClient, CreateDnsRecord, UpdateDnsRecord};
+
+/// Maximum number of actions kept in the undo history
+const MAX_HISTORY: usize = 100;
+
+/// A change to a DNS record, run against the API
+#[derive(Debug, Clone)]
+pub enum RecordCommand {
+    Create {
+        zone_id: String,
+        record: CreateDnsRecord,
+    },
+    Update {
+        zone_id: String,
+        record_id: String,
+        record: UpdateDnsRecord,
+    },
+    Delete {
+        zone_id: String,
+        record_id: String,
+    },
+}
+
+impl RecordCommand {
+    /// Run the command, returning the command that reverts it
+    pub async fn run(&self, client: &CloudflareClient) -> Result<RecordCommand> {
+        match self {
+            RecordCommand::Create { zone_id, record } => {
+                let created = client.create_dns_record(zone_id, record).await?;
+                Ok(RecordCommand::Delete {
+                    zone_id: zone_id.clone(),
+                    record_id: created.id,
+                })
+            }
+            RecordCommand::Update {
+                zone_id,
+                record_id,
+                record,
+            } => {
+                let previous = client.get_dns_record(zone_id, record_id).await?;
+                client.update_dns_record(zone_id, record_id, record).await?;
+                Ok(RecordCommand::Update {
+                    zone_id: zone_id.clone(),
+                    record_id: record_id.clone(),
+                    record: UpdateDnsRecord::from(&previous),
+                })
+            }
+            RecordCommand::Delete { zone_id, record_id } => {
+                let previous = client.get_dns_record(zone_id, record_id).await?;
+                client.delete_dns_record(zone_id, record_id).await?;
+                Ok(RecordCommand::Create {
+                    zone_id: zone_id.clone(),
+                    record: CreateDnsRecord::from(&previous),
+                })
+            }
+        }
+    }
+
+    pub fn zone_id(&self) -> &str {
+        match self {
+            RecordCommand::Create { zone_id, .. }
+            | RecordCommand::Update { zone_id, .. }
+            | RecordCommand::Delete { zone_id, .. } => zone_id,
+        }
+    }
+
+    /// Id of the record the command changes, if it already exists
+    pub fn record_id(&self) -> Option<&str> {
+        match self {
+            RecordCommand::Create { .. } => None,
+            RecordCommand::Update { record_id, .. } | RecordCommand::Delete { record_id, .. } => {
+                Some(record_id)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Command {
+    Record(Box<RecordCommand>),
+    /// Switch to an appearance mode, identified by `AppearanceMode::as_str`
+    SetAppearance(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct HistoryEntry {
+    /// Identifies the action while it moves between the stacks
+    pub id: u64,
+    /// What the action did, e.g. `Update A www.example.com`
+    pub description: String,
+    /// Command that reverts the action on the undo stack, or repeats it on the redo stack
+    pub command: Command,
+}
+
+#[derive(Debug, Default)]
+pub struct History {
+    undo: Vec<HistoryEntry>,
+    redo: Vec<HistoryEntry>,
+    next_id: u64,
+}
+
+impl History {
+    /// Record a newly performed action by the command that reverts it, returning its id
+    pub fn record(&mut self, description: String, inverse: Command) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.redo.clear();
+        self.push_undo(HistoryEntry {
+            id,
+            description,
+            command: inverse,
+        });
+        id
+    }
+
+    pub fn push_undo(&mut self, entry: HistoryEntry) {
+        self.undo.push(entry);
+        if self.undo.len() > MAX_HISTORY {
+            self.undo.remove(0);
+        }
+    }
+
+    pub fn push_redo(&mut self, entry: HistoryEntry) {
+        self.redo.push(entry);
+    }
+
+    pub fn pop_undo(&mut self) -> Option<HistoryEntry> {
+        self.undo.pop()
+    }
+
+    /// Take the entry to undo only if it is the most recent action, so undoing it can't
+    /// overwrite later changes
+    pub fn pop_undo_if_latest(&mut self, id: u64) -> Option<HistoryEntry> {
+        if self.undo.last()?.id == id {
+            self.undo.pop()
+        } else {
+            None
+        }
+    }
+
+    pub fn pop_redo(&mut self) -> Option<HistoryEntry> {
+        self.redo.pop()
+    }
+
+    /// Actions that can be undone, oldest first
+    pub fn undo_entries(&self) -> &[HistoryEntry] {
+        &self.undo
+    }
+
+    /// Undone actions that can be redone, most recently undone last
+    pub fn redo_entries(&self) -> &[HistoryEntry] {
+        &self.redo
+    }
+
+    /// Forget all actions, e.g. when switching accounts
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+}
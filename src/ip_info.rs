@@ -0,0 +1,111 @@
+//! Reverse DNS, network and country of IP addresses in A and AAAA records
+//!
+//! Lookups are DNS over HTTPS queries ([`doh`]): PTR records for the reverse name, and Team
+//! Cymru's IP to ASN mapping, which is published as TXT records.
+
+use std::net::IpAddr;
+
+use anyhow::Result;
+use reqwest::Client;
+
+use crate::doh::{self, TYPE_PTR, TYPE_TXT};
+
+/// What is known about an IP address
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IpInfo {
+    pub reverse_dns: Option<String>,
+    pub asn: Option<u32>,
+    /// Name of the organization announcing the address, e.g. `CLOUDFLARENET, US`
+    pub organization: Option<String>,
+    /// Two-letter country code the address is registered in
+    pub country: Option<String>,
+    /// Announced prefix containing the address, e.g. `1.1.1.0/24`
+    pub prefix: Option<String>,
+}
+
+/// Lookup state of an address, cached for as long as the app runs
+#[derive(Debug, Clone)]
+pub enum IpInfoState {
+    Loading,
+    Loaded(IpInfo),
+    Failed(String),
+}
+
+/// Look up the reverse name, ASN and country of an address with `client`
+pub async fn lookup(client: &Client, ip: IpAddr) -> Result<IpInfo> {
+    let mut info = IpInfo {
+        reverse_dns: doh::query_data(client, &reverse_name(ip), TYPE_PTR)
+            .await?
+            .into_iter()
+            .next()
+            .map(|name| name.trim_end_matches('.').to_string()),
+        ..Default::default()
+    };
+
+    // e.g. `13335 | 1.1.1.0/24 | AU | apnic | 2011-08-11`
+    let Some(origin) = doh::query_data(client, &origin_name(ip), TYPE_TXT)
+        .await?
+        .into_iter()
+        .next()
+    else {
+        return Ok(info);
+    };
+    let fields = txt_fields(&origin);
+    // Addresses announced by several networks list all of them, separated by spaces
+    info.asn = fields
+        .first()
+        .and_then(|asns| asns.split_whitespace().next()?.parse().ok());
+    info.prefix = fields.get(1).cloned().filter(|prefix| !prefix.is_empty());
+    info.country = fields.get(2).cloned().filter(|country| !country.is_empty());
+
+    if let Some(asn) = info.asn {
+        // e.g. `13335 | US | arin | 2010-07-14 | CLOUDFLARENET, US`
+        let name = format!("AS{}.asn.cymru.com", asn);
+        info.organization = doh::query_data(client, &name, TYPE_TXT)
+            .await?
+            .first()
+            .and_then(|description| txt_fields(description).pop())
+            .filter(|organization| !organization.is_empty());
+    }
+
+    Ok(info)
+}
+
+/// e.g. `4.3.2.1.in-addr.arpa` for `1.2.3.4`
+fn reverse_name(ip: IpAddr) -> String {
+    match ip {
+        IpAddr::V4(_) => format!("{}.in-addr.arpa", reversed_labels(ip)),
+        IpAddr::V6(_) => format!("{}.ip6.arpa", reversed_labels(ip)),
+    }
+}
+
+/// e.g. `4.3.2.1.origin.asn.cymru.com` for `1.2.3.4`
+fn origin_name(ip: IpAddr) -> String {
+    match ip {
+        IpAddr::V4(_) => format!("{}.origin.asn.cymru.com", reversed_labels(ip)),
+        IpAddr::V6(_) => format!("{}.origin6.asn.cymru.com", reversed_labels(ip)),
+    }
+}
+
+/// Octets of an IPv4 address or nibbles of an IPv6 address in reverse order
+fn reversed_labels(ip: IpAddr) -> String {
+    let labels: Vec<String> = match ip {
+        IpAddr::V4(ip) => ip.octets().iter().rev().map(u8::to_string).collect(),
+        IpAddr::V6(ip) => ip
+            .octets()
+            .iter()
+            .rev()
+            .flat_map(|octet| [octet & 0xf, octet >> 4])
+            .map(|nibble| format!("{:x}", nibble))
+            .collect(),
+    };
+    labels.join(".")
+}
+
+/// Fields of a Team Cymru TXT record, which are separated by `|`
+fn txt_fields(data: &str) -> Vec<String> {
+    data.trim_matches('"')
+        .split('|')
+        .map(|field| field.trim().to_string())
+        .collect()
+}
@@ -0,0 +1,72 @@
+//! Local journal of the record changes made through the app
+//!
+//! The journal is a JSON Lines file in the app's data directory, so it survives restarts and
+//! can be read with other tools.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A change made through the app
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct JournalEntry {
+    pub time: DateTime<Utc>,
+    pub zone_id: String,
+    /// What was changed, e.g. `Update A www.example.com`
+    pub description: String,
+    /// Part of the app that made the change, e.g. `Record editor` or `Control server`
+    pub actor: String,
+}
+
+impl JournalEntry {
+    pub fn new(zone_id: &str, description: impl Into<String>, actor: &str) -> Self {
+        Self {
+            time: Utc::now(),
+            zone_id: zone_id.to_string(),
+            description: description.into(),
+            actor: actor.to_string(),
+        }
+    }
+}
+
+pub fn journal_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("cloudflare-admin").join("journal.jsonl"))
+}
+
+/// Add an entry to the end of the journal
+pub fn append(entry: &JournalEntry) -> Result<()> {
+    let Some(path) = journal_path() else {
+        return Ok(());
+    };
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(entry)?)?;
+    Ok(())
+}
+
+/// Journal entries of a zone, oldest first
+///
+/// Lines that can't be parsed are skipped, so a damaged line doesn't hide the rest.
+pub fn read_zone(zone_id: &str) -> Result<Vec<JournalEntry>> {
+    let Some(path) = journal_path() else {
+        return Ok(Vec::new());
+    };
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+
+    Ok(contents
+        .lines()
+        .filter_map(|line| serde_json::from_str::<JournalEntry>(line).ok())
+        .filter(|entry| entry.zone_id == zone_id)
+        .collect())
+}
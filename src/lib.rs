@@ -0,0 +1,8467 @@
+mod activity;
+mod analytics;
+mod api_errors;
+mod api_usage;
+mod assets;
+mod batch;
+mod cache_purge;
+mod certificates;
+mod changelog;
+mod cloudflare;
+mod control_server;
+mod csv_import;
+mod dashboard_links;
+mod deletion_safety;
+mod demo;
+mod desktop_notification;
+mod doh;
+mod edit_conflict;
+mod export_manifest;
+mod expression_editor;
+mod filter;
+mod help;
+mod history;
+mod ip_info;
+mod journal;
+mod lint;
+mod network_diagnostics;
+mod network_proxy;
+mod page_rules;
+mod pages_projects;
+mod plugins;
+mod portfolio;
+mod profile_settings;
+mod provenance;
+mod qr;
+mod record_data;
+mod record_defaults;
+mod record_export;
+mod redirect_rules;
+mod report;
+mod resolver;
+mod retry_queue;
+mod rule_expression;
+mod scripting;
+mod settings_search;
+mod staging;
+mod startup;
+mod storage;
+mod store;
+mod suggestions;
+mod tags;
+mod tasks;
+mod terraform_import;
+mod third_party;
+mod timestamps;
+mod token_import;
+mod token_scopes;
+mod token_template;
+mod ttl_lowering;
+mod tunnels;
+mod ui;
+mod waf_rules;
+mod watch;
+mod worker_routes;
+mod workspaces;
+mod zone_file;
+mod zone_settings;
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::future::Future;
+use std::net::IpAddr;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use activity::{ActivityEntry, ActivitySource};
+use analytics::TimeRange;
+use api_usage::ApiUsage;
+use batch::{BatchAction, BatchItemStatus, BatchRun};
+use cache_purge::PurgeTarget;
+use certificates::TrackedCertificate;
+use changelog::FeatureLink;
+use chrono::{DateTime, Local, Utc};
+use cloudflare::{
+    Account, CachePurge, CertificatePack, CloudflareClient, CreateDnsRecord, DnsQueryCount,
+    DnsRecord, DnsRecordType, PageRule, PagesDeployment, PagesProject, RulesetRule, TrafficPoint,
+    Tunnel, TunnelIngress, UpdateDnsRecord, WorkerRoute, Zone, ZoneAccount, ZoneSetting,
+};
+use control_server::{ControlServer, ControlServerSettings, RecordChanged};
+use csv_import::{CsvImport, RecordField};
+use deletion_safety::SafetyCheck;
+use export_manifest::ExportManifest;
+use expression_editor::ExpressionEditor;
+use filter::{RecordQuery, SavedFilter};
+use gpui::prelude::*;
+use gpui::{
+    Application, Bounds, Context, Entity, FocusHandle, Hsla, IntoElement, KeyBinding,
+    PathPromptOptions, Render, SharedString, Task, TitlebarOptions, WeakEntity, Window,
+    WindowBounds, WindowOptions, actions, div, px, rgb, size,
+};
+use gpui_component::{
+    ActiveTheme, Root, VirtualListScrollHandle, WindowExt,
+    button::{Button, ButtonVariant},
+    dialog::DialogButtonProps,
+    input::{Input, InputEvent, InputState},
+    menu::ContextMenuExt,
+    notification::Notification,
+    scroll::ScrollableElement,
+    select::{SearchableVec, SelectEvent, SelectGroup, SelectItem, SelectState},
+    theme::{Theme, ThemeMode},
+    v_flex,
+};
+use history::{Command, History, HistoryEntry, RecordCommand};
+use ip_info::IpInfoState;
+use journal::JournalEntry;
+use lint::Lint;
+use network_diagnostics::Diagnostics;
+use network_proxy::{ProxyRoute, ProxySetting};
+use page_rules::PageRuleDraft;
+use plugins::Plugin;
+use portfolio::ZoneHealth;
+use profile_settings::{Accent, ProfileSettings};
+use record_defaults::RecordDefaults;
+use record_export::{ExportFormat, ExportScope};
+use redirect_rules::RedirectDraft;
+use report::ZoneReport;
+use resolver::Resolution;
+use retry_queue::RetryQueue;
+use scripting::{SavedScript, ScriptOutput};
+use staging::{ConflictResolution, StagedChange, StagedConflict};
+use startup::{Snapshot, StartupView};
+use store::{CachedRecords, DataStore, StoreEvent};
+use tasks::{TaskId, TaskManager, TaskProgress};
+use terraform_import::ImportedContext;
+use third_party::{CnameTarget, ThirdParty};
+use timestamps::TimeZoneDisplay;
+use token_import::FoundToken;
+use token_scopes::{Capability, TokenScopes};
+use ttl_lowering::{LoweredTtls, OldAnswer};
+use waf_rules::WafRuleDraft;
+use watch::{FieldChange, RecordChange};
+use worker_routes::WorkerRouteDraft;
+use workspaces::Workspace;
+use zone_file::{ImportPreview, ImportStatus};
+use zone_settings::ZoneSettingKind;
+
+actions!(cloudflare_admin, [Undo, Redo]);
+
+/// How often the record being edited is checked for remote modifications
+const EDIT_POLL_INTERVAL: Duration = Duration::from_secs(15);
+
+/// How often the zone list and development mode are reloaded and the API usage is saved in the
+/// background; records are refreshed by [`App::start_record_refresh`] instead
+const BACKGROUND_REFRESH_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Intervals in seconds the selected zone's records can be refreshed at, as chosen in Settings
+pub const RECORD_REFRESH_INTERVALS: [u64; 4] = [30, 60, 5 * 60, 15 * 60];
+
+/// How often zone health, including certificate expiry, is checked in the background
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// The dashboard warns about the token expiring this many days ahead
+pub const TOKEN_EXPIRY_WARNING_DAYS: i64 = 14;
+
+/// How often a newly added zone is checked for having become active
+const ZONE_ACTIVATION_POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Shown instead of sending changes to locked records, which the API always rejects
+const LOCKED_RECORD_ERROR: &str =
+    "This record is locked by Cloudflare and can't be changed or deleted";
+
+/// Shown instead of sending changes to protected records
+fn protected_record_error(reason: &str) -> String {
+    format!(
+        "{}. Unprotect this record in the editor to change or delete it",
+        reason
+    )
+}
+
+// Application pages
+#[derive(Clone, PartialEq)]
+pub enum Page {
+    TokenSetup,
+    Portfolio,
+    Dashboard,
+    Console,
+    Settings,
+    ZoneSettings,
+    PageRules,
+    RedirectRules,
+    WafRules,
+    WorkerRoutes,
+    PagesProjects,
+    Tunnels,
+    EdgeCertificates,
+    Analytics,
+    NetworkDiagnostics,
+}
+
+// Appearance mode for theme switching
+#[derive(Clone, Copy, PartialEq, Default)]
+pub enum AppearanceMode {
+    Light,
+    Dark,
+    #[default]
+    Auto,
+}
+
+impl AppearanceMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AppearanceMode::Light => "light",
+            AppearanceMode::Dark => "dark",
+            AppearanceMode::Auto => "auto",
+        }
+    }
+
+    pub fn parse(s: &str) -> Self {
+        match s {
+            "light" => AppearanceMode::Light,
+            "dark" => AppearanceMode::Dark,
+            _ => AppearanceMode::Auto,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AppearanceMode::Light => "Light",
+            AppearanceMode::Dark => "Dark",
+            AppearanceMode::Auto => "Auto (System)",
+        }
+    }
+}
+
+/// Base font size used for the interface, for readability at larger text sizes
+#[derive(Clone, Copy, PartialEq, Default)]
+pub enum TextScale {
+    #[default]
+    Default,
+    Large,
+    Larger,
+    Largest,
+}
+
+impl TextScale {
+    pub const ALL: [TextScale; 4] = [
+        TextScale::Default,
+        TextScale::Large,
+        TextScale::Larger,
+        TextScale::Largest,
+    ];
+
+    /// Font size of the theme at the default text size
+    const BASE_FONT_SIZE: f32 = 16.;
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TextScale::Default => "default",
+            TextScale::Large => "large",
+            TextScale::Larger => "larger",
+            TextScale::Largest => "largest",
+        }
+    }
+
+    pub fn parse(s: &str) -> Self {
+        match s {
+            "large" => TextScale::Large,
+            "larger" => TextScale::Larger,
+            "largest" => TextScale::Largest,
+            _ => TextScale::Default,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TextScale::Default => "Default (100%)",
+            TextScale::Large => "Large (115%)",
+            TextScale::Larger => "Larger (130%)",
+            TextScale::Largest => "Largest (150%)",
+        }
+    }
+
+    pub fn font_size(&self) -> gpui::Pixels {
+        let factor = match self {
+            TextScale::Default => 1.,
+            TextScale::Large => 1.15,
+            TextScale::Larger => 1.3,
+            TextScale::Largest => 1.5,
+        };
+        px(Self::BASE_FONT_SIZE * factor)
+    }
+}
+
+// Wrapper for Zone to implement SelectItem
+#[derive(Clone)]
+pub struct ZoneItem {
+    pub zone: Zone,
+    pub pinned: bool,
+    /// For the quick actions of the item's context menu
+    app: WeakEntity<App>,
+}
+
+impl SelectItem for ZoneItem {
+    type Value = String;
+
+    fn title(&self) -> SharedString {
+        SharedString::from(self.zone.name.clone())
+    }
+
+    fn render(&self, _: &mut Window, _: &mut gpui::App) -> impl IntoElement {
+        let zone = self.zone.clone();
+        let pinned = self.pinned;
+        let app = self.app.clone();
+        div()
+            .w_full()
+            .child(self.title())
+            .context_menu(move |menu, _, cx| {
+                ui::zone_context_menu(menu, &zone, pinned, app.clone(), cx)
+            })
+    }
+
+    fn value(&self) -> &Self::Value {
+        &self.zone.id
+    }
+}
+
+/// Zone picker items, grouped by tag
+pub type ZoneSelectItems = SearchableVec<SelectGroup<ZoneItem>>;
+
+// Wrapper for DNS Record Type to implement SelectItem
+#[derive(Clone)]
+pub struct RecordTypeItem {
+    pub record_type: DnsRecordType,
+}
+
+impl SelectItem for RecordTypeItem {
+    type Value = DnsRecordType;
+
+    fn title(&self) -> SharedString {
+        SharedString::from(self.record_type.as_str())
+    }
+
+    fn value(&self) -> &Self::Value {
+        &self.record_type
+    }
+}
+
+// Wrapper for AppearanceMode to implement SelectItem
+#[derive(Clone)]
+pub struct AppearanceModeItem {
+    pub mode: AppearanceMode,
+}
+
+impl SelectItem for AppearanceModeItem {
+    type Value = AppearanceMode;
+
+    fn title(&self) -> SharedString {
+        SharedString::from(self.mode.label())
+    }
+
+    fn value(&self) -> &Self::Value {
+        &self.mode
+    }
+}
+
+#[derive(Clone)]
+pub struct TextScaleItem {
+    pub scale: TextScale,
+}
+
+impl SelectItem for TextScaleItem {
+    type Value = TextScale;
+
+    fn title(&self) -> SharedString {
+        SharedString::from(self.scale.label())
+    }
+
+    fn value(&self) -> &Self::Value {
+        &self.scale
+    }
+}
+
+// Main application state
+pub struct App {
+    pub page: Page,
+    focus_handle: FocusHandle,
+    pub client: Option<CloudflareClient>,
+    /// Names of the API token profiles, e.g. work and personal
+    pub profiles: Vec<String>,
+    /// Profile whose token the client uses
+    pub active_profile: String,
+    /// Settings each profile overrides, by profile name
+    pub profile_settings: HashMap<String, ProfileSettings>,
+    /// TTL new records of the active profile start with, empty for the app-wide default
+    pub profile_ttl_input: Entity<InputState>,
+    /// API requests sent today by profile and zone
+    pub api_usage: Arc<Mutex<ApiUsage>>,
+    /// Permissions and zones of the token, `None` until read or when they can't be
+    pub token_scopes: Option<TokenScopes>,
+    /// Why the token's permissions couldn't be read
+    pub token_scopes_error: Option<String>,
+    /// Whether changes are turned off in Settings; they are as well for tokens that can only
+    /// read
+    pub read_only_setting: bool,
+    /// When the token expires, `None` if it doesn't or until it is read
+    pub token_expires_on: Option<DateTime<Utc>>,
+    /// Tokens of wrangler or flarectl the token setup offers to use
+    pub found_tokens: Vec<FoundToken>,
+    /// What the token being created should allow, while the token setup guides through
+    /// creating one
+    pub token_wizard: Option<Vec<Capability>>,
+    /// Cached zones, records and zone health
+    pub store: Entity<DataStore>,
+    refresh_task: Option<Task<()>>,
+    /// Seconds between refreshes of the selected zone's records, `None` when they aren't
+    /// refreshed automatically
+    pub record_refresh_interval: Option<u64>,
+    record_refresh_task: Option<Task<()>>,
+    pub selected_zone_index: Option<usize>,
+    /// Local tags of each zone by id
+    pub zone_tags: HashMap<String, Vec<String>>,
+    /// Tag the zone picker and portfolio page are filtered by
+    pub zone_tag_filter: Option<String>,
+    /// Id of the account the zone picker and portfolio page are scoped to
+    pub zone_account_filter: Option<String>,
+    /// Named sets of zones, possibly of several profiles
+    pub workspaces: Vec<Workspace>,
+    /// Name of the workspace the zone picker and portfolio page are scoped to
+    pub active_workspace: Option<String>,
+    /// Ids of the zones pinned to the top of the zone picker
+    pub pinned_zones: HashSet<String>,
+    /// Page the app opens to
+    pub startup_view: StartupView,
+    /// Id of the zone viewed last, for opening to it
+    last_zone_id: Option<String>,
+
+    // Adding zones
+    pub zone_name_input: Entity<InputState>,
+    /// Accounts zones can be added to
+    pub accounts: Vec<Account>,
+    /// Account the next zone is added to
+    pub new_zone_account_id: Option<String>,
+    /// Zone added last, followed until it is active
+    pub added_zone: Option<Zone>,
+    zone_activation_task: Option<Task<()>>,
+    pub zone_tags_input: Entity<InputState>,
+    /// Local notes of each zone by id
+    pub zone_notes: HashMap<String, String>,
+    pub zone_notes_input: Entity<InputState>,
+    /// Whether the warnings of the bulk delete dialog were acknowledged
+    pub deletion_acknowledged: bool,
+    /// Zone name typed to confirm deleting records with much at stake
+    pub deletion_confirm_input: Entity<InputState>,
+    /// Records that can't be changed until they are unprotected, with the reason, by id
+    pub protected_records: HashMap<String, String>,
+    /// Whether the portfolio stopped offering to import context from Terraform
+    pub import_offer_dismissed: bool,
+    health_check_task: Option<Task<()>>,
+    /// Expiry notification threshold last shown for each certificate
+    certificate_notifications: HashMap<String, i64>,
+    pub loading: bool,
+    pub error: Option<String>,
+    pub tasks: TaskManager,
+    /// Undo/redo history of record changes and settings
+    pub history: History,
+
+    // UI state
+    pub token_input: Entity<InputState>,
+    pub zone_select: Entity<SelectState<ZoneSelectItems>>,
+
+    // Record editor state
+    pub editing_record: Option<DnsRecord>,
+    pub record_type_select: Entity<SelectState<Vec<RecordTypeItem>>>,
+    pub record_name_input: Entity<InputState>,
+    pub record_content_input: Entity<InputState>,
+    pub record_ttl_input: Entity<InputState>,
+    pub record_priority_input: Entity<InputState>,
+    pub record_proxied: bool,
+    /// Whether the new record form moved on from picking the record type to its fields
+    pub new_record_type_chosen: bool,
+    pub record_comment_input: Entity<InputState>,
+    /// Asks for the ticket a comment refers to with `$ticket` when it's saved
+    ticket_input: Entity<InputState>,
+    /// Ticket entered for the save that asked for it
+    comment_ticket: Option<String>,
+    /// Remote version of the record being edited, if it was modified since it was loaded
+    pub edit_conflict: Option<DnsRecord>,
+    edit_poll_task: Option<Task<()>>,
+
+    // Settings
+    pub settings_token_input: Entity<InputState>,
+    /// Name of the token profile being added
+    pub profile_name_input: Entity<InputState>,
+    /// Name of the workspace being added
+    pub workspace_name_input: Entity<InputState>,
+    /// Filters the sections of the Settings page
+    pub settings_search_input: Entity<InputState>,
+    pub appearance_mode: AppearanceMode,
+    pub appearance_mode_select: Entity<SelectState<Vec<AppearanceModeItem>>>,
+    pub text_scale: TextScale,
+    pub text_scale_select: Entity<SelectState<Vec<TextScaleItem>>>,
+    /// Time zone of full timestamps shown on hover
+    pub time_zone_display: TimeZoneDisplay,
+    /// Refuse to create a record identical to an existing one, instead of asking first
+    pub block_duplicate_records: bool,
+    /// Keep a manifest of each zone file export and show what changed since the previous one
+    pub compare_exports: bool,
+    /// TTL, proxy status and comment new records start with
+    pub record_defaults: RecordDefaults,
+    pub default_ttl_input: Entity<InputState>,
+    pub default_comment_input: Entity<InputState>,
+    pub default_attribution_input: Entity<InputState>,
+
+    // DNS list scroll handle
+    pub dns_list_scroll_handle: VirtualListScrollHandle,
+
+    // Search state
+    pub search_input: Entity<InputState>,
+    pub search_history: Vec<String>,
+    pub saved_filters: Vec<SavedFilter>,
+    pub filter_name_input: Entity<InputState>,
+
+    /// Audit notes included in the printed zone report
+    pub report_notes_input: Entity<InputState>,
+
+    /// CSV file being imported in the CSV import dialog
+    pub csv_import: Option<CsvImport>,
+
+    // What-if resolver
+    pub resolver_host_input: Entity<InputState>,
+    pub resolver_type_select: Entity<SelectState<Vec<RecordTypeItem>>>,
+    pub resolver_result: Option<Resolution>,
+
+    // Watched records
+    pub watched_records: HashSet<String>,
+    /// Records changed by the app since the last load, which are not reported as external changes
+    pub local_record_changes: HashSet<String>,
+    pub watched_changes: Vec<RecordChange>,
+    /// Answers edited records gave before, until resolvers drop them, by record id
+    pub old_answers: HashMap<String, OldAnswer>,
+    old_answer_countdown: Option<Task<()>>,
+
+    /// Ids of the records selected in the DNS list for bulk actions
+    pub selected_records: HashSet<String>,
+    /// Show one row per name in the DNS list, expanding to the name's records
+    pub group_by_name: bool,
+    /// Names whose records are shown in the grouped DNS list
+    pub expanded_names: HashSet<String>,
+    /// Show the selected zone's nameservers under the dashboard header
+    pub zone_details_expanded: bool,
+    /// Inputs and proxy choice of the bulk edit dialog, empty and `None` keep what records have
+    pub bulk_ttl_input: Entity<InputState>,
+    pub bulk_comment_input: Entity<InputState>,
+    pub bulk_proxied: Option<bool>,
+
+    /// Ids of the records unproxied with "Unproxy All" by zone id, so they can be proxied again
+    pub unproxied_records: HashMap<String, Vec<String>>,
+    pub unproxy_confirm_input: Entity<InputState>,
+    /// Records whose TTLs were lowered for a planned change by zone id, to restore them after
+    pub lowered_ttls: HashMap<String, LoweredTtls>,
+    ttl_reminder: Option<Task<()>>,
+
+    /// Collect record changes for review instead of sending them to Cloudflare right away
+    pub staging: bool,
+    /// Changes waiting to be applied, by zone id
+    pub staged_changes: HashMap<String, Vec<StagedChange>>,
+    /// Staged changes of the selected zone whose records changed on Cloudflare, while their
+    /// resolution is chosen
+    pub staged_conflicts: Vec<StagedConflict>,
+    /// Record changes that failed with a transient error and are retried in the background
+    pub retry_queue: RetryQueue,
+    retry_timer: Option<Task<()>>,
+
+    /// Reverse DNS, ASN and country of addresses hovered in the DNS list
+    pub ip_info: HashMap<IpAddr, IpInfoState>,
+    /// Where CNAME targets end up, by target
+    pub cname_targets: HashMap<String, CnameTarget>,
+
+    // Activity panel
+    pub show_activity: bool,
+    pub activity: Vec<ActivityEntry>,
+    /// Zone the loaded activity belongs to
+    activity_zone_id: Option<String>,
+    /// Why the audit log couldn't be loaded; the local journal is still shown
+    pub activity_error: Option<String>,
+    pub activity_source_filter: Option<ActivitySource>,
+    /// Hide audit log entries that don't change DNS records, e.g. zone settings
+    pub activity_records_only: bool,
+    pub activity_filter_input: Entity<InputState>,
+
+    // Zone settings page
+    pub zone_settings: Vec<ZoneSetting>,
+    /// Zone the loaded settings belong to
+    zone_settings_zone_id: Option<String>,
+    /// When development mode turns itself off by zone id, `None` while it's off
+    pub development_mode: HashMap<String, Option<DateTime<Utc>>>,
+    development_mode_countdown: Option<Task<()>>,
+    pub purge_target: PurgeTarget,
+    pub purge_input: Entity<InputState>,
+    pub purge_confirm_input: Entity<InputState>,
+
+    // Page Rules page
+    /// Page Rules of the zone, in the order they are applied in
+    pub page_rules: Vec<PageRule>,
+    /// Zone the loaded Page Rules belong to
+    page_rules_zone_id: Option<String>,
+    pub page_rule_draft: PageRuleDraft,
+    pub page_rule_pattern_input: Entity<InputState>,
+    pub page_rule_destination_input: Entity<InputState>,
+
+    // Redirect Rules page
+    /// Single Redirects of the zone, in the order they are applied in
+    pub redirect_rules: Vec<RulesetRule>,
+    /// Zone the loaded redirects belong to
+    redirect_rules_zone_id: Option<String>,
+    pub redirect_draft: RedirectDraft,
+    pub redirect_description_input: Entity<InputState>,
+    pub redirect_source_input: Entity<InputState>,
+    /// Expression of a redirect matching with one instead of a URL pattern
+    pub redirect_expression: ExpressionEditor,
+    pub redirect_target_input: Entity<InputState>,
+
+    // WAF custom rules page
+    /// Custom rules of the zone, in the order they are applied in
+    pub waf_rules: Vec<RulesetRule>,
+    /// Zone the loaded custom rules belong to
+    waf_rules_zone_id: Option<String>,
+    pub waf_rule_draft: WafRuleDraft,
+    pub waf_description_input: Entity<InputState>,
+    pub waf_expression: ExpressionEditor,
+
+    // Workers Routes page
+    /// Workers routes of the zone, by pattern
+    pub worker_routes: Vec<WorkerRoute>,
+    /// Zone the loaded routes belong to
+    worker_routes_zone_id: Option<String>,
+    /// Names of the Workers of the zone's account, empty if they couldn't be listed
+    pub worker_scripts: Vec<String>,
+    pub worker_route_draft: WorkerRouteDraft,
+    pub worker_route_pattern_input: Entity<InputState>,
+    pub worker_route_script_input: Entity<InputState>,
+
+    // Pages projects page
+    /// Pages projects of the selected zone's account, by name
+    pub pages_projects: Vec<PagesProject>,
+    /// Account the loaded projects belong to
+    pages_account_id: Option<String>,
+    /// Project whose deployments are listed
+    pub expanded_pages_project: Option<String>,
+    /// Deployments of the expanded project, newest first
+    pub pages_deployments: Vec<PagesDeployment>,
+
+    // Tunnels page
+    /// Tunnels of the selected zone's account, by name
+    pub tunnels: Vec<Tunnel>,
+    /// Account the loaded tunnels belong to
+    tunnels_account_id: Option<String>,
+    /// Ingress rules of the tunnels configured in the dashboard, by tunnel id
+    pub tunnel_ingress: HashMap<String, Vec<TunnelIngress>>,
+
+    // Edge certificates page
+    /// Edge certificate packs of the selected zone
+    pub certificate_packs: Vec<CertificatePack>,
+    /// Whether the zone gets a free Universal SSL certificate, once loaded
+    pub universal_ssl: Option<bool>,
+    /// Zone the loaded packs belong to
+    certificate_packs_zone_id: Option<String>,
+
+    // Analytics page
+    pub analytics_range: TimeRange,
+    /// Traffic of the zone per hour or day of the range, oldest first
+    pub traffic: Vec<TrafficPoint>,
+    /// DNS queries of the zone over the range by name and type
+    pub dns_query_counts: Vec<DnsQueryCount>,
+    /// Zone the loaded traffic and queries belong to
+    analytics_zone_id: Option<String>,
+
+    // Bulk operation shown in the batch progress dialog
+    pub batch: Option<BatchRun>,
+    batch_task: Option<TaskId>,
+
+    // Script console
+    pub script_input: Entity<InputState>,
+    pub script_name_input: Entity<InputState>,
+    pub saved_scripts: Vec<SavedScript>,
+    pub script_output: Option<ScriptOutput>,
+
+    // Local control server
+    pub control_server_settings: ControlServerSettings,
+    pub control_server: Option<ControlServer>,
+    pub control_server_port_input: Entity<InputState>,
+    control_server_events: Option<Task<()>>,
+
+    // Network proxy
+    pub proxy_setting: ProxySetting,
+    /// Route API requests take, `None` until it is detected for the automatic proxy setting
+    pub proxy_route: Option<ProxyRoute>,
+    pub proxy_url_input: Entity<InputState>,
+    /// Outcome of the last connectivity check
+    pub proxy_check: Option<Result<String, String>>,
+    /// Results of the network diagnostics, `None` while they run
+    pub network_diagnostics: Option<Diagnostics>,
+
+    // External plugins
+    pub plugins: Vec<Plugin>,
+    /// Plugins that failed to load
+    pub plugin_errors: Vec<String>,
+    /// Fingerprint of the approved manifest of each plugin by id
+    plugin_approvals: HashMap<String, String>,
+}
+
+impl App {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        // Check if we have a stored token for the profile used last
+        let profiles =
+            storage::get_profiles().unwrap_or_else(|_| vec![storage::DEFAULT_PROFILE.to_string()]);
+        let active_profile = storage::get_active_profile()
+            .ok()
+            .flatten()
+            .filter(|profile| profiles.contains(profile))
+            .unwrap_or_else(|| profiles[0].clone());
+        let has_token = storage::has_token(&active_profile);
+        let profile_settings = storage::get_profile_settings().unwrap_or_default();
+        let startup_view = storage::get_startup_view().unwrap_or_default();
+        let last_zone_id = storage::get_last_zone().unwrap_or_default();
+        let initial_page = if !has_token {
+            Page::TokenSetup
+        } else if startup_view.zone_id(last_zone_id.as_deref()).is_some() {
+            Page::Dashboard
+        } else {
+            Page::Portfolio
+        };
+
+        // Create input states
+        let token_input = cx.new(|cx| {
+            InputState::new(window, cx).placeholder("Paste your Cloudflare API token here...")
+        });
+
+        let zone_select = cx.new(|cx| {
+            SelectState::new(ZoneSelectItems::new(Vec::new()), None, window, cx).searchable(true)
+        });
+
+        // Create record type items
+        let record_type_items: Vec<RecordTypeItem> = DnsRecordType::all()
+            .iter()
+            .map(|t| RecordTypeItem { record_type: *t })
+            .collect();
+
+        let record_type_select = cx.new(|cx| {
+            SelectState::new(
+                record_type_items,
+                Some(gpui_component::IndexPath::new(0)),
+                window,
+                cx,
+            )
+        });
+
+        let record_name_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder("Record name (e.g., www)"));
+
+        let record_content_input = cx.new(|cx| InputState::new(window, cx).placeholder("Content"));
+
+        let record_ttl_input = cx.new(|cx| {
+            let mut state = InputState::new(window, cx).placeholder("TTL (1 = auto)");
+            state.set_value("1", window, cx);
+            state
+        });
+
+        let record_priority_input = cx.new(|cx| InputState::new(window, cx).placeholder("e.g. 10"));
+
+        let record_comment_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder("Comment (optional)"));
+        let ticket_input = cx.new(|cx| InputState::new(window, cx).placeholder("e.g. OPS-1234"));
+        let bulk_ttl_input = cx.new(|cx| InputState::new(window, cx).placeholder("Unchanged"));
+        let bulk_comment_input = cx.new(|cx| InputState::new(window, cx).placeholder("Unchanged"));
+
+        let record_defaults = storage::get_record_defaults().unwrap_or_default();
+        let default_ttl_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("1 = auto")
+                .default_value(record_defaults.ttl.to_string())
+        });
+        let profile_ttl = profile_settings
+            .get(&active_profile)
+            .and_then(|settings| settings.default_ttl)
+            .map(|ttl| ttl.to_string())
+            .unwrap_or_default();
+        let profile_ttl_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("As in New Records")
+                .default_value(profile_ttl)
+        });
+        let default_comment_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("e.g. $ticket")
+                .default_value(record_defaults.comment_template.clone())
+        });
+        let default_attribution_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("e.g. (by $user on $date)")
+                .default_value(record_defaults.attribution.clone())
+        });
+
+        let settings_token_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder("Enter new API token..."));
+        let profile_name_input = cx.new(|cx| {
+            InputState::new(window, cx).placeholder("Profile name (e.g., work, personal)")
+        });
+        let workspace_name_input = cx.new(|cx| {
+            InputState::new(window, cx).placeholder("Workspace name (e.g., Client A production)")
+        });
+
+        let search_input = cx.new(|cx| {
+            InputState::new(window, cx).placeholder("Search records (e.g., type:A proxied:no)")
+        });
+
+        let script_input = cx.new(|cx| {
+            // Rhai's syntax is close enough to Rust for highlighting
+            InputState::new(window, cx)
+                .code_editor("rust")
+                .line_number(true)
+                .default_value(scripting::EXAMPLE_SCRIPT)
+        });
+        let script_name_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder("Script name (e.g., Move origin)"));
+
+        let control_server_settings = storage::get_control_server_settings()
+            .ok()
+            .flatten()
+            .unwrap_or_default();
+        let control_server_port = control_server_settings.port.to_string();
+        let control_server_port_input =
+            cx.new(|cx| InputState::new(window, cx).default_value(control_server_port));
+
+        let proxy_setting = storage::get_proxy_setting().unwrap_or_default();
+        // Only the automatic setting has to wait for detection before the first request
+        let proxy_route = network_proxy::configured(&proxy_setting);
+        let proxy_url = match &proxy_setting {
+            ProxySetting::Manual { url } => url.clone(),
+            _ => String::new(),
+        };
+        let proxy_url_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("proxy.example.com:8080")
+                .default_value(proxy_url)
+        });
+
+        let report_notes_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .multi_line(true)
+                .rows(6)
+                .placeholder("Audit notes to include in the report (optional)")
+        });
+
+        let zone_name_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder("Domain (e.g., example.com)"));
+        let zone_tags_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .placeholder("Comma-separated tags (e.g., client-a, production)")
+        });
+        let deletion_confirm_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder("Zone name"));
+        let zone_notes_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .multi_line(true)
+                .rows(3)
+                .placeholder("Notes (e.g., who owns the zone, where it is managed)")
+        });
+
+        let resolver_host_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder("Hostname (e.g., www)"));
+        let resolver_type_select = cx.new(|cx| {
+            SelectState::new(
+                DnsRecordType::all()
+                    .iter()
+                    .map(|t| RecordTypeItem { record_type: *t })
+                    .collect::<Vec<_>>(),
+                None,
+                window,
+                cx,
+            )
+        });
+
+        let unproxy_confirm_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder("Type the zone name to confirm"));
+
+        let activity_filter_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder("Filter by actor or change"));
+        let settings_search_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder("Search settings"));
+
+        let purge_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .multi_line(true)
+                .rows(4)
+                .placeholder(PurgeTarget::default().placeholder())
+        });
+        let purge_confirm_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder("Type the zone name to confirm"));
+
+        let page_rule_pattern_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder("example.com/blog/*"));
+        let page_rule_destination_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder("https://www.example.com/$1"));
+
+        let redirect_description_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder("e.g. Old blog URLs"));
+        let redirect_source_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder("https://example.com/blog/*"));
+        let redirect_target_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder("https://blog.example.com/${1}"));
+
+        let waf_description_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder("e.g. Admin area from abroad"));
+        let redirect_expression = ExpressionEditor::new(
+            "(http.host eq \"old.example.com\" and http.request.uri.path ne \"/\")",
+            window,
+            cx,
+        );
+        let waf_expression = ExpressionEditor::new(
+            "(http.request.uri.path contains \"/admin\" and ip.src.country ne \"DE\")",
+            window,
+            cx,
+        );
+
+        let worker_route_pattern_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder("example.com/api/*"));
+        let worker_route_script_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder("None"));
+
+        let filter_name_input = cx
+            .new(|cx| InputState::new(window, cx).placeholder("Filter name (e.g., Mail records)"));
+
+        // Load saved appearance mode or default to Auto
+        let saved_appearance_mode = storage::get_appearance_mode()
+            .ok()
+            .flatten()
+            .map(|s| AppearanceMode::parse(&s))
+            .unwrap_or_default();
+
+        // Create appearance mode selector items
+        let appearance_mode_items = vec![
+            AppearanceModeItem {
+                mode: AppearanceMode::Auto,
+            },
+            AppearanceModeItem {
+                mode: AppearanceMode::Light,
+            },
+            AppearanceModeItem {
+                mode: AppearanceMode::Dark,
+            },
+        ];
+
+        // Find the index of the saved appearance mode
+        let selected_appearance_index = appearance_mode_items
+            .iter()
+            .position(|item| item.mode == saved_appearance_mode)
+            .map(gpui_component::IndexPath::new);
+
+        let appearance_mode_select = cx.new(|cx| {
+            SelectState::new(appearance_mode_items, selected_appearance_index, window, cx)
+        });
+
+        let saved_text_scale = storage::get_text_scale()
+            .ok()
+            .flatten()
+            .map(|s| TextScale::parse(&s))
+            .unwrap_or_default();
+        let text_scale_items: Vec<TextScaleItem> = TextScale::ALL
+            .into_iter()
+            .map(|scale| TextScaleItem { scale })
+            .collect();
+        let selected_text_scale_index = text_scale_items
+            .iter()
+            .position(|item| item.scale == saved_text_scale)
+            .map(gpui_component::IndexPath::new);
+        let text_scale_select =
+            cx.new(|cx| SelectState::new(text_scale_items, selected_text_scale_index, window, cx));
+
+        let focus_handle = cx.focus_handle();
+        focus_handle.focus(window);
+
+        let (plugins, plugin_errors) = plugins::discover();
+
+        let mut app = Self {
+            page: initial_page,
+            focus_handle,
+            client: None,
+            profiles,
+            active_profile,
+            profile_settings,
+            profile_ttl_input,
+            api_usage: Arc::new(Mutex::new(ApiUsage::load())),
+            token_scopes: None,
+            token_scopes_error: None,
+            read_only_setting: storage::get_read_only().unwrap_or_default(),
+            token_expires_on: None,
+            found_tokens: if has_token {
+                Vec::new()
+            } else {
+                token_import::detect()
+            },
+            token_wizard: None,
+            store: cx.new(|_| DataStore::default()),
+            refresh_task: None,
+            record_refresh_interval: storage::get_record_refresh_interval().ok().flatten(),
+            record_refresh_task: None,
+            selected_zone_index: None,
+            zone_tags: storage::get_zone_tags().unwrap_or_default(),
+            zone_tag_filter: None,
+            zone_account_filter: storage::get_zone_account_filter().unwrap_or_default(),
+            workspaces: storage::get_workspaces().unwrap_or_default(),
+            active_workspace: storage::get_active_workspace().unwrap_or_default(),
+            pinned_zones: storage::get_pinned_zones().unwrap_or_default(),
+            startup_view,
+            last_zone_id,
+            zone_name_input,
+            accounts: Vec::new(),
+            new_zone_account_id: None,
+            added_zone: None,
+            zone_activation_task: None,
+            zone_tags_input,
+            zone_notes: storage::get_zone_notes().unwrap_or_default(),
+            zone_notes_input,
+            deletion_acknowledged: false,
+            deletion_confirm_input,
+            protected_records: storage::get_protected_records().unwrap_or_default(),
+            import_offer_dismissed: storage::get_import_offer_dismissed().unwrap_or_default(),
+            health_check_task: None,
+            certificate_notifications: storage::get_certificate_notifications().unwrap_or_default(),
+            loading: false,
+            error: None,
+            tasks: TaskManager::default(),
+            history: History::default(),
+            token_input,
+            zone_select,
+            editing_record: None,
+            record_type_select,
+            record_name_input,
+            record_content_input,
+            record_ttl_input,
+            record_priority_input,
+            record_proxied: false,
+            new_record_type_chosen: false,
+            record_comment_input,
+            ticket_input,
+            comment_ticket: None,
+            edit_conflict: None,
+            edit_poll_task: None,
+            settings_token_input,
+            profile_name_input,
+            workspace_name_input,
+            settings_search_input,
+            appearance_mode: saved_appearance_mode,
+            appearance_mode_select,
+            text_scale: saved_text_scale,
+            text_scale_select,
+            time_zone_display: storage::get_time_zone_display()
+                .ok()
+                .flatten()
+                .map(|s| TimeZoneDisplay::parse(&s))
+                .unwrap_or_default(),
+            block_duplicate_records: storage::get_block_duplicate_records().unwrap_or_default(),
+            compare_exports: storage::get_compare_exports().unwrap_or_default(),
+            record_defaults,
+            default_ttl_input,
+            default_comment_input,
+            default_attribution_input,
+            dns_list_scroll_handle: VirtualListScrollHandle::new(),
+            search_input,
+            search_history: storage::get_search_history().unwrap_or_default(),
+            saved_filters: Vec::new(),
+            filter_name_input,
+            report_notes_input,
+            csv_import: None,
+            resolver_host_input,
+            resolver_type_select,
+            resolver_result: None,
+            watched_records: storage::get_watched_records().unwrap_or_default(),
+            local_record_changes: HashSet::new(),
+            watched_changes: Vec::new(),
+            old_answers: HashMap::new(),
+            old_answer_countdown: None,
+            selected_records: HashSet::new(),
+            group_by_name: storage::get_group_by_name().unwrap_or_default(),
+            zone_details_expanded: false,
+            expanded_names: HashSet::new(),
+            bulk_ttl_input,
+            bulk_comment_input,
+            bulk_proxied: None,
+            unproxied_records: storage::get_unproxied_records().unwrap_or_default(),
+            lowered_ttls: storage::get_lowered_ttls().unwrap_or_default(),
+            ttl_reminder: None,
+            staging: false,
+            staged_changes: HashMap::new(),
+            staged_conflicts: Vec::new(),
+            retry_queue: RetryQueue::default(),
+            retry_timer: None,
+            unproxy_confirm_input,
+            ip_info: HashMap::new(),
+            cname_targets: HashMap::new(),
+            show_activity: false,
+            activity: Vec::new(),
+            activity_zone_id: None,
+            activity_error: None,
+            activity_source_filter: None,
+            activity_records_only: true,
+            activity_filter_input,
+            zone_settings: Vec::new(),
+            zone_settings_zone_id: None,
+            development_mode: HashMap::new(),
+            development_mode_countdown: None,
+            purge_target: PurgeTarget::default(),
+            purge_input,
+            purge_confirm_input,
+            page_rules: Vec::new(),
+            page_rules_zone_id: None,
+            page_rule_draft: PageRuleDraft::default(),
+            page_rule_pattern_input,
+            page_rule_destination_input,
+            redirect_rules: Vec::new(),
+            redirect_rules_zone_id: None,
+            redirect_draft: RedirectDraft::default(),
+            redirect_description_input,
+            redirect_source_input,
+            redirect_expression,
+            redirect_target_input,
+            waf_rules: Vec::new(),
+            waf_rules_zone_id: None,
+            waf_rule_draft: WafRuleDraft::default(),
+            waf_description_input,
+            waf_expression,
+            worker_routes: Vec::new(),
+            worker_routes_zone_id: None,
+            worker_scripts: Vec::new(),
+            worker_route_draft: WorkerRouteDraft::default(),
+            worker_route_pattern_input,
+            worker_route_script_input,
+            pages_projects: Vec::new(),
+            pages_account_id: None,
+            expanded_pages_project: None,
+            pages_deployments: Vec::new(),
+            tunnels: Vec::new(),
+            tunnels_account_id: None,
+            tunnel_ingress: HashMap::new(),
+            certificate_packs: Vec::new(),
+            universal_ssl: None,
+            certificate_packs_zone_id: None,
+            analytics_range: TimeRange::default(),
+            traffic: Vec::new(),
+            dns_query_counts: Vec::new(),
+            analytics_zone_id: None,
+            batch: None,
+            batch_task: None,
+            script_input,
+            script_name_input,
+            saved_scripts: storage::get_saved_scripts().unwrap_or_default(),
+            script_output: None,
+            control_server_settings,
+            control_server: None,
+            control_server_port_input,
+            control_server_events: None,
+            proxy_setting,
+            proxy_route,
+            proxy_url_input,
+            proxy_check: None,
+            network_diagnostics: None,
+            plugins,
+            plugin_errors,
+            plugin_approvals: storage::get_plugin_approvals().unwrap_or_default(),
+        };
+
+        // Apply the initial theme based on saved appearance mode
+        app.apply_theme(window, cx);
+        // Start the record editor with the defaults for new records
+        app.clear_record_form(window, cx);
+
+        // Re-render when cached data changes, and react to freshly loaded data
+        cx.observe(&app.store, |_, _, cx| cx.notify()).detach();
+        cx.subscribe_in(
+            &app.store,
+            window,
+            |this, _, event: &StoreEvent, window, cx| this.on_store_event(event, window, cx),
+        )
+        .detach();
+        cx.on_app_quit(|this, _| {
+            this.save_api_usage();
+            async {}
+        })
+        .detach();
+
+        // Subscribe to zone selection changes
+        cx.subscribe_in(
+            &app.zone_select,
+            window,
+            |this, _, event: &SelectEvent<ZoneSelectItems>, window, cx| {
+                if let SelectEvent::Confirm(Some(zone_id)) = event {
+                    // Find the index of the selected zone by id
+                    if let Some(index) = this.zones(cx).iter().position(|z| &z.id == zone_id) {
+                        this.select_zone(index, window, cx);
+                    }
+                }
+            },
+        )
+        .detach();
+
+        // Verify a token as soon as it's pasted while guiding through creating one
+        cx.subscribe_in(
+            &app.token_input,
+            window,
+            |this, input, event: &InputEvent, window, cx| {
+                if let InputEvent::Change = event
+                    && this.token_wizard.is_some()
+                    && !this.loading
+                    && token_import::is_token(input.read(cx).value().trim())
+                {
+                    this.save_token(window, cx);
+                }
+            },
+        )
+        .detach();
+
+        // Re-filter the DNS list as the search query changes
+        cx.subscribe_in(
+            &app.search_input,
+            window,
+            |this, _, event: &InputEvent, _window, cx| match event {
+                InputEvent::Change => cx.notify(),
+                InputEvent::PressEnter { .. } => this.remember_search(cx),
+                _ => {}
+            },
+        )
+        .detach();
+
+        // Resolve again when the resolver's hostname is submitted or its record type changes
+        cx.subscribe_in(
+            &app.resolver_host_input,
+            window,
+            |this, _, event: &InputEvent, _window, cx| {
+                if let InputEvent::PressEnter { .. } = event {
+                    this.run_resolver(cx);
+                }
+            },
+        )
+        .detach();
+        cx.subscribe(
+            &app.resolver_type_select,
+            |this, _, event: &SelectEvent<Vec<RecordTypeItem>>, cx| {
+                if let SelectEvent::Confirm(_) = event {
+                    this.run_resolver(cx);
+                }
+            },
+        )
+        .detach();
+
+        cx.subscribe(&app.profile_ttl_input, |this, _, event: &InputEvent, cx| {
+            if let InputEvent::Blur | InputEvent::PressEnter { .. } = event {
+                this.update_profile_ttl_from_input(cx);
+            }
+        })
+        .detach();
+
+        // Save the new record defaults once their inputs are left
+        for input in [
+            &app.default_ttl_input,
+            &app.default_comment_input,
+            &app.default_attribution_input,
+        ] {
+            cx.subscribe(input, |this, _, event: &InputEvent, cx| {
+                if let InputEvent::Blur | InputEvent::PressEnter { .. } = event {
+                    this.update_record_defaults_from_inputs(cx);
+                }
+            })
+            .detach();
+        }
+
+        for input in [&app.activity_filter_input, &app.settings_search_input] {
+            cx.subscribe(input, |_, _, event: &InputEvent, cx| {
+                if let InputEvent::Change = event {
+                    cx.notify();
+                }
+            })
+            .detach();
+        }
+
+        // Subscribe to appearance mode selection changes
+        cx.subscribe_in(
+            &app.appearance_mode_select,
+            window,
+            |this, _, event: &SelectEvent<Vec<AppearanceModeItem>>, window, cx| {
+                if let SelectEvent::Confirm(Some(mode)) = event {
+                    this.set_appearance_mode(*mode, window, cx);
+                }
+            },
+        )
+        .detach();
+
+        cx.subscribe_in(
+            &app.text_scale_select,
+            window,
+            |this, _, event: &SelectEvent<Vec<TextScaleItem>>, window, cx| {
+                if let SelectEvent::Confirm(Some(scale)) = event {
+                    this.set_text_scale(*scale, window, cx);
+                }
+            },
+        )
+        .detach();
+
+        // If we have a token, initialize the client and load zones
+        if has_token && let Ok(Some(token)) = storage::get_token(&app.active_profile) {
+            let client = app.new_client(token);
+            app.set_client(Some(client));
+            // A snapshot that can't be read only costs the fast start, so it's not reported
+            if let Ok(Some(snapshot)) = startup::load()
+                && snapshot.profile == app.active_profile
+            {
+                app.store
+                    .update(cx, |store, cx| store.restore(snapshot, cx));
+            }
+            app.load_zones(true, window, cx);
+            app.load_token_details(window, cx);
+        }
+        // With the automatic setting, requests sent before the proxy is detected go through the
+        // one reqwest finds itself
+        app.detect_proxy(window, cx);
+        // Dialogs can only open once the window's root view exists
+        cx.on_next_frame(window, |this, window, cx| this.show_whats_new(window, cx));
+        if app.control_server_settings.enabled {
+            app.start_control_server(window, cx);
+        }
+
+        app
+    }
+
+    /// Replace the Cloudflare client, which the local control server uses as well
+    fn set_client(&mut self, client: Option<CloudflareClient>) {
+        if let Some(server) = &self.control_server {
+            server.set_client(client.clone());
+        }
+        self.client = client;
+    }
+
+    /// Run `work` as a named background task, then hand its output to `on_done`
+    ///
+    /// While any task is running the app is marked as loading. `work` receives a
+    /// [`TaskProgress`] handle it can use to report progress to the task indicator.
+    pub fn spawn_task<Fut, R>(
+        &mut self,
+        name: &'static str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        work: impl FnOnce(TaskProgress) -> Fut,
+        on_done: impl FnOnce(&mut Self, R, &mut Window, &mut Context<Self>) + 'static,
+    ) -> TaskId
+    where
+        Fut: Future<Output = R> + 'static,
+        R: 'static,
+    {
+        let (id, progress) = self.tasks.prepare(cx.entity().downgrade(), cx.to_async());
+        let future = work(progress.clone());
+
+        let task = cx.spawn_in(window, async move |this, cx| {
+            let output = future.await;
+            cx.update(|window, cx| {
+                this.update(cx, |this, cx| {
+                    this.tasks.finish(id);
+                    this.loading = !this.tasks.is_empty();
+                    on_done(this, output, window, cx);
+                    cx.notify();
+                })
+                .ok();
+            })
+            .ok();
+        });
+
+        self.tasks.insert(id, name.into(), &progress, task);
+        self.loading = true;
+        cx.notify();
+        id
+    }
+
+    /// Cancel a running background task
+    pub fn cancel_task(&mut self, id: TaskId, cx: &mut Context<Self>) {
+        if self.tasks.cancel(id) {
+            self.loading = !self.tasks.is_empty();
+            if self.batch_task == Some(id) {
+                self.batch_task = None;
+                if let Some(batch) = &mut self.batch {
+                    batch.cancel_remaining();
+                }
+            }
+            cx.notify();
+        }
+    }
+
+    /// Fetch the zone list, optionally followed by a zone health check
+    fn load_zones(&mut self, check_health: bool, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(client) = self.client.clone() else {
+            return;
+        };
+
+        self.error = None;
+        self.spawn_task(
+            "Loading zones",
+            window,
+            cx,
+            |_| async move { client.list_zones().await },
+            move |this, result, window, cx| match result {
+                Ok(zones) => {
+                    this.store
+                        .update(cx, |store, cx| store.set_zones(zones, cx));
+                    if check_health {
+                        this.check_zone_health(window, cx);
+                    }
+                    this.start_background_refresh(window, cx);
+                }
+                Err(e) => {
+                    this.error = Some(format!("Failed to load zones: {}", e));
+                }
+            },
+        );
+    }
+
+    /// Read when the token expires and which permissions and zones it has, to warn before it
+    /// expires and hide what it can't use
+    fn load_token_details(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(client) = self.client.clone() else {
+            return;
+        };
+
+        self.token_scopes = None;
+        self.token_scopes_error = None;
+        self.token_expires_on = None;
+        self.sync_control_server_read_only();
+        let profile = self.active_profile.clone();
+        let demo = client.is_demo();
+        self.spawn_task(
+            "Reading token permissions",
+            window,
+            cx,
+            |_| async move {
+                let expiry = client.get_token_expiry().await;
+                let details = client.get_token_details().await;
+                (expiry, details)
+            },
+            move |this, (expiry, details), _, _| {
+                // Another profile or demo mode may have been signed in since
+                if this.active_profile != profile || this.is_demo() != demo {
+                    return;
+                }
+                // The expiry is only a warning, so failing to read it isn't reported
+                this.token_expires_on = expiry.ok().flatten();
+                match details {
+                    Ok(details) => this.token_scopes = Some(TokenScopes::from_details(&details)),
+                    Err(e) => this.token_scopes_error = Some(e.to_string()),
+                }
+                this.sync_control_server_read_only();
+            },
+        );
+    }
+
+    /// When the token expires, if that is within [`TOKEN_EXPIRY_WARNING_DAYS`] or has passed
+    pub fn token_expiring_on(&self) -> Option<DateTime<Utc>> {
+        self.token_expires_on.filter(|expires_on| {
+            *expires_on - Utc::now() <= chrono::Duration::days(TOKEN_EXPIRY_WARNING_DAYS)
+        })
+    }
+
+    /// Whether the token has the permission `capability` needs; assumed while its permissions
+    /// are unknown
+    pub fn can(&self, capability: Capability) -> bool {
+        if capability.changes() && self.read_only() {
+            return false;
+        }
+        self.token_scopes
+            .as_ref()
+            .is_none_or(|scopes| scopes.allows(capability))
+    }
+
+    /// Whether nothing is to be changed on Cloudflare, as turned on in Settings, for the
+    /// profile, or because the token can only read
+    pub fn read_only(&self) -> bool {
+        self.read_only_setting || self.read_only_profile() || self.read_only_token()
+    }
+
+    /// Whether the active profile is always read-only, so read-only mode can't be turned off
+    pub fn read_only_profile(&self) -> bool {
+        self.active_profile_settings().read_only
+    }
+
+    /// Whether the token can only read, so read-only mode can't be turned off
+    pub fn read_only_token(&self) -> bool {
+        self.token_scopes
+            .as_ref()
+            .is_some_and(|scopes| scopes.is_read_only())
+    }
+
+    pub fn set_read_only(&mut self, read_only: bool, cx: &mut Context<Self>) {
+        self.read_only_setting = read_only;
+        self.sync_control_server_read_only();
+
+        if let Err(e) = storage::store_read_only(read_only) {
+            self.error = Some(format!("Failed to save read-only setting: {}", e));
+        }
+        cx.notify();
+    }
+
+    /// Settings the active profile overrides
+    pub fn active_profile_settings(&self) -> ProfileSettings {
+        self.profile_settings
+            .get(&self.active_profile)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Change the settings the active profile overrides and save them
+    fn update_profile_settings(
+        &mut self,
+        cx: &mut Context<Self>,
+        update: impl FnOnce(&mut ProfileSettings),
+    ) {
+        let settings = self
+            .profile_settings
+            .entry(self.active_profile.clone())
+            .or_default();
+        update(settings);
+        if *settings == ProfileSettings::default() {
+            self.profile_settings.remove(&self.active_profile);
+        }
+        self.sync_control_server_read_only();
+        self.sync_control_server_duplicates();
+
+        if let Err(e) = storage::store_profile_settings(&self.profile_settings) {
+            self.error = Some(format!("Failed to save profile settings: {}", e));
+        }
+        cx.notify();
+    }
+
+    pub fn set_profile_accent(
+        &mut self,
+        accent: Option<Accent>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.update_profile_settings(cx, |settings| settings.accent = accent);
+        self.apply_theme(window, cx);
+    }
+
+    pub fn set_profile_read_only(&mut self, read_only: bool, cx: &mut Context<Self>) {
+        self.update_profile_settings(cx, |settings| settings.read_only = read_only);
+    }
+
+    pub fn set_profile_strict_guardrails(&mut self, strict: bool, cx: &mut Context<Self>) {
+        self.update_profile_settings(cx, |settings| settings.strict_guardrails = strict);
+    }
+
+    /// Take the profile's default TTL from its input in the settings
+    fn update_profile_ttl_from_input(&mut self, cx: &mut Context<Self>) {
+        let ttl = self.profile_ttl_input.read(cx).value().trim().to_string();
+        let ttl = if ttl.is_empty() {
+            None
+        } else {
+            match record_defaults::parse_ttl(&ttl) {
+                Ok(ttl) => Some(ttl),
+                Err(e) => {
+                    self.error = Some(e);
+                    cx.notify();
+                    return;
+                }
+            }
+        };
+        self.update_profile_settings(cx, |settings| settings.default_ttl = ttl);
+    }
+
+    /// TTL new records start with, the active profile's if it sets one
+    fn default_ttl(&self) -> u32 {
+        self.active_profile_settings()
+            .default_ttl
+            .unwrap_or(self.record_defaults.ttl)
+    }
+
+    /// The client for changing something on Cloudflare, refused with an error in read-only mode
+    fn writable_client(&mut self, cx: &mut Context<Self>) -> Option<CloudflareClient> {
+        if self.read_only() {
+            self.error = Some(if self.read_only_token() {
+                "The API token can only read, so nothing can be changed".to_string()
+            } else {
+                "Read-only mode is on; turn it off in Settings to make changes".to_string()
+            });
+            cx.notify();
+            return None;
+        }
+        self.client.clone()
+    }
+
+    /// Have the local control server refuse changes as the app does
+    fn sync_control_server_read_only(&self) {
+        if let Some(server) = &self.control_server {
+            server.set_read_only(self.read_only());
+        }
+    }
+
+    /// Have the local control server refuse duplicate records as the app does
+    fn sync_control_server_duplicates(&self) {
+        if let Some(server) = &self.control_server {
+            server.set_block_duplicates(self.blocks_duplicate_records());
+        }
+    }
+
+    /// Have the local server refuse changes to the records protected here
+    fn sync_control_server_protected_records(&self) {
+        if let Some(server) = &self.control_server {
+            server.set_protected_records(&self.protected_records);
+        }
+    }
+
+    /// Periodically refresh the zone list while the app is open; the selected zone's records
+    /// are refreshed at the interval chosen in Settings, if any
+    fn start_background_refresh(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.refresh_task.is_some() {
+            return;
+        }
+        self.schedule_ttl_reminder(window, cx);
+        self.start_record_refresh(window, cx);
+
+        self.refresh_task = Some(cx.spawn_in(window, async move |this, cx| {
+            loop {
+                cx.background_executor()
+                    .timer(BACKGROUND_REFRESH_INTERVAL)
+                    .await;
+                let refreshed = this.update_in(cx, |this, window, cx| {
+                    this.load_zones(false, window, cx);
+                    this.load_development_mode(window, cx);
+                    this.save_api_usage();
+                });
+                if refreshed.is_err() {
+                    break;
+                }
+            }
+        }));
+    }
+
+    /// API requests sent today, without those of an earlier day nothing was sent since
+    pub fn api_usage_today(&self) -> ApiUsage {
+        let mut usage = self.api_usage.lock().unwrap().clone();
+        usage.roll_over(Local::now().date_naive());
+        usage
+    }
+
+    /// Keep today's API request counts for the next launch
+    fn save_api_usage(&mut self) {
+        if let Err(e) = self.api_usage.lock().unwrap().save() {
+            self.error = Some(format!("Failed to save API usage: {}", e));
+        }
+    }
+
+    /// Refresh the selected zone's records at the interval chosen in Settings, if any
+    fn start_record_refresh(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.record_refresh_task = None;
+        let Some(interval) = self.record_refresh_interval else {
+            return;
+        };
+        if self.client.is_none() {
+            return;
+        }
+
+        self.record_refresh_task = Some(cx.spawn_in(window, async move |this, cx| {
+            loop {
+                cx.background_executor()
+                    .timer(Duration::from_secs(interval))
+                    .await;
+                let refreshed = this.update_in(cx, |this, window, cx| {
+                    // Only while the records are on screen, and not on top of other work
+                    if this.page == Page::Dashboard && !this.loading {
+                        this.load_dns_records(window, cx);
+                    }
+                });
+                if refreshed.is_err() {
+                    break;
+                }
+            }
+        }));
+    }
+
+    pub fn set_record_refresh_interval(
+        &mut self,
+        interval: Option<u64>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.record_refresh_interval = interval;
+        self.start_record_refresh(window, cx);
+
+        if let Err(e) = storage::store_record_refresh_interval(interval) {
+            self.error = Some(format!("Failed to save auto-refresh setting: {}", e));
+        }
+        cx.notify();
+    }
+
+    fn on_store_event(&mut self, event: &StoreEvent, window: &mut Window, cx: &mut Context<Self>) {
+        match event {
+            StoreEvent::ZonesLoaded => {
+                let zone_count = self.zones(cx).len();
+                if self
+                    .selected_zone_index
+                    .is_some_and(|index| index >= zone_count)
+                {
+                    self.selected_zone_index = None;
+                }
+                if self.selected_zone_index.is_none() && zone_count > 0 {
+                    let index = self
+                        .startup_zone_index(cx)
+                        .or_else(|| self.first_workspace_zone_index(cx))
+                        .unwrap_or(0);
+                    self.select_zone(index, window, cx);
+                }
+                self.refresh_zone_select(window, cx);
+                self.save_startup_snapshot(cx);
+            }
+            StoreEvent::RecordsLoaded { zone_id, previous } => {
+                let current = self
+                    .store
+                    .read(cx)
+                    .records(zone_id)
+                    .map(|cached| cached.records.clone())
+                    .unwrap_or_default();
+                // Records deleted elsewhere drop out of the selection; the rest stays selected
+                if self
+                    .selected_zone(cx)
+                    .is_some_and(|zone| &zone.id == zone_id)
+                {
+                    self.selected_records
+                        .retain(|id| current.iter().any(|record| &record.id == id));
+                }
+                if let Some(previous) = previous {
+                    let changes = watch::detect_changes(
+                        &self.watched_records,
+                        &self.local_record_changes,
+                        previous,
+                        &current,
+                    );
+                    if !changes.is_empty() {
+                        self.notify_watched_changes(changes, window, cx);
+                    }
+                }
+                self.local_record_changes.clear();
+                self.resolve_cname_targets(zone_id, cx);
+                if self.startup_zone_id() == Some(zone_id.as_str()) {
+                    self.save_startup_snapshot(cx);
+                }
+            }
+        }
+    }
+
+    /// Id of the zone the app opens to, `None` when it opens to the portfolio
+    fn startup_zone_id(&self) -> Option<&str> {
+        self.startup_view.zone_id(self.last_zone_id.as_deref())
+    }
+
+    /// Index of the zone the app opens to, if it still exists
+    fn startup_zone_index(&self, cx: &gpui::App) -> Option<usize> {
+        let zone_id = self.startup_zone_id()?;
+        self.zones(cx).iter().position(|zone| zone.id == zone_id)
+    }
+
+    /// Keep the zones, and the records of the zone the app opens to, for the next launch
+    fn save_startup_snapshot(&mut self, cx: &mut Context<Self>) {
+        // Demo data must not show up when the app opens with a real token
+        if self.client.is_none() || self.is_demo() {
+            return;
+        }
+
+        let store = self.store.read(cx);
+        let cached = self
+            .startup_zone_id()
+            .and_then(|zone_id| Some((zone_id, store.records(zone_id)?)));
+        let snapshot = Snapshot {
+            profile: self.active_profile.clone(),
+            zones: store.zones().to_vec(),
+            zone_id: cached.map(|(zone_id, _)| zone_id.to_string()),
+            records: cached
+                .map(|(_, cached)| cached.records.to_vec())
+                .unwrap_or_default(),
+            fetched_at: cached.map_or_else(Local::now, |(_, cached)| cached.fetched_at),
+        };
+        if let Err(e) = startup::store(&snapshot) {
+            self.error = Some(format!("Failed to save startup data: {}", e));
+        }
+    }
+
+    /// Choose the page the app opens to
+    pub fn set_startup_view(&mut self, view: StartupView, cx: &mut Context<Self>) {
+        if let Err(e) = storage::store_startup_view(&view) {
+            self.error = Some(format!("Failed to save startup page: {}", e));
+        }
+        self.startup_view = view;
+        self.save_startup_snapshot(cx);
+        cx.notify();
+    }
+
+    pub fn zones(&self, cx: &gpui::App) -> Rc<Vec<Zone>> {
+        self.store.read(cx).zones()
+    }
+
+    pub fn selected_zone(&self, cx: &gpui::App) -> Option<Zone> {
+        let index = self.selected_zone_index?;
+        self.store.read(cx).zones().get(index).cloned()
+    }
+
+    /// Cached records of the selected zone, if they have been loaded
+    pub fn selected_zone_records(&self, cx: &gpui::App) -> Option<CachedRecords> {
+        let zone = self.selected_zone(cx)?;
+        self.store.read(cx).records(&zone.id).cloned()
+    }
+
+    /// DNS records of the selected zone
+    pub fn dns_records(&self, cx: &gpui::App) -> Rc<Vec<DnsRecord>> {
+        self.selected_zone_records(cx)
+            .map(|cached| cached.records)
+            .unwrap_or_default()
+    }
+
+    /// Switch to the zone at `index`, keeping the zone picker in sync
+    pub fn select_zone(&mut self, index: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let zones = self.zones(cx);
+        if self.selected_zone_index == Some(index) || index >= zones.len() {
+            return;
+        }
+
+        self.selected_zone_index = Some(index);
+        let zone_id = zones[index].id.clone();
+        if self.last_zone_id.as_ref() != Some(&zone_id) && !self.is_demo() {
+            if let Err(e) = storage::store_last_zone(&zone_id) {
+                self.error = Some(format!("Failed to save last viewed zone: {}", e));
+            }
+            self.last_zone_id = Some(zone_id.clone());
+            if self.startup_view == StartupView::LastZone {
+                self.save_startup_snapshot(cx);
+            }
+        }
+        self.zone_select.update(cx, |state, cx| {
+            state.set_selected_value(&zone_id, window, cx);
+        });
+        self.editing_record = None;
+        self.edit_conflict = None;
+        self.edit_poll_task = None;
+        self.selected_records.clear();
+        self.expanded_names.clear();
+        self.load_saved_filters(cx);
+        self.load_dns_records_cached(window, cx);
+        self.load_development_mode(window, cx);
+        if self.show_activity {
+            self.load_activity(window, cx);
+        }
+    }
+
+    /// Rebuild the zone picker from the zones, grouped by tag and filtered by the tag and
+    /// account filters and the workspace
+    fn refresh_zone_select(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let zones = self.zones(cx);
+        let app = cx.entity().downgrade();
+        let workspace_zone_ids = self.workspace_zone_ids();
+        let groups = tags::group_zones(
+            &zones,
+            &self.zone_tags,
+            self.zone_tag_filter.as_deref(),
+            self.account_filter(cx).as_deref(),
+            workspace_zone_ids.as_ref(),
+        );
+        let groups: Vec<SelectGroup<ZoneItem>> =
+            tags::pin_zones(groups, &zones, &self.pinned_zones)
+                .into_iter()
+                .map(|(title, indices)| {
+                    SelectGroup::new(title).items(indices.into_iter().map(|index| ZoneItem {
+                        zone: zones[index].clone(),
+                        pinned: self.pinned_zones.contains(&zones[index].id),
+                        app: app.clone(),
+                    }))
+                })
+                .collect();
+        let selected_id = self.selected_zone(cx).map(|zone| zone.id);
+
+        self.zone_select.update(cx, |state, cx| {
+            state.set_items(ZoneSelectItems::new(groups), window, cx);
+            match selected_id {
+                Some(zone_id) => state.set_selected_value(&zone_id, window, cx),
+                None => state.set_selected_index(None, window, cx),
+            }
+        });
+    }
+
+    /// Accounts of the zones, sorted by name
+    pub fn zone_accounts(&self, cx: &gpui::App) -> Vec<ZoneAccount> {
+        portfolio::zone_accounts(&self.zones(cx))
+    }
+
+    /// Account the zone picker and portfolio page are scoped to, unless none of the zones belong
+    /// to it anymore
+    pub fn account_filter(&self, cx: &gpui::App) -> Option<String> {
+        let account_id = self.zone_account_filter.as_ref()?;
+        self.zones(cx)
+            .iter()
+            .any(|zone| &zone.account.id == account_id)
+            .then(|| account_id.clone())
+    }
+
+    /// Only show zones of the given account in the zone picker and on the portfolio page
+    pub fn set_zone_account_filter(
+        &mut self,
+        account_id: Option<String>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Err(e) = storage::store_zone_account_filter(account_id.as_deref()) {
+            self.error = Some(format!("Failed to save account filter: {}", e));
+        }
+        self.zone_account_filter = account_id;
+        self.refresh_zone_select(window, cx);
+        cx.notify();
+    }
+
+    /// Workspace the zone lists are scoped to, unless it was deleted
+    pub fn workspace(&self) -> Option<&Workspace> {
+        let name = self.active_workspace.as_ref()?;
+        self.workspaces
+            .iter()
+            .find(|workspace| &workspace.name == name)
+    }
+
+    /// Ids of the active workspace's zones of the profile in use, `None` without a workspace
+    pub fn workspace_zone_ids(&self) -> Option<HashSet<String>> {
+        self.workspace()
+            .map(|workspace| workspace.zone_ids(&self.active_profile))
+    }
+
+    /// Only show the zones of a workspace in the zone picker and on the portfolio page,
+    /// switching to the profile of its zones if it has none of the profile in use
+    pub fn set_workspace(
+        &mut self,
+        name: Option<String>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Err(e) = storage::store_active_workspace(name.as_deref()) {
+            self.error = Some(format!("Failed to save workspace: {}", e));
+        }
+        self.active_workspace = name;
+
+        let other_profile = self.workspace().and_then(|workspace| {
+            let profiles = workspace.profiles();
+            (!profiles.is_empty() && !profiles.contains(&self.active_profile))
+                .then(|| profiles[0].clone())
+        });
+        if let Some(profile) = other_profile
+            && self.profiles.contains(&profile)
+        {
+            self.switch_profile(profile, window, cx);
+            return;
+        }
+
+        // Move off a selected zone the workspace leaves out
+        if let Some(zone_ids) = self.workspace_zone_ids()
+            && self
+                .selected_zone(cx)
+                .is_some_and(|zone| !zone_ids.contains(&zone.id))
+            && let Some(index) = self.first_workspace_zone_index(cx)
+        {
+            self.select_zone(index, window, cx);
+        }
+        self.refresh_zone_select(window, cx);
+        cx.notify();
+    }
+
+    /// Index of the first zone of the active workspace among the zones
+    fn first_workspace_zone_index(&self, cx: &gpui::App) -> Option<usize> {
+        let zone_ids = self.workspace_zone_ids()?;
+        self.zones(cx)
+            .iter()
+            .position(|zone| zone_ids.contains(&zone.id))
+    }
+
+    pub fn open_add_workspace_dialog(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.error = None;
+        self.workspace_name_input.update(cx, |input, cx| {
+            input.set_value("", window, cx);
+        });
+        ui::open_add_workspace_dialog(cx.entity(), window, cx);
+    }
+
+    /// Add an empty workspace with the entered name and switch to it; zones are added from
+    /// their context menu
+    pub fn add_workspace(&mut self, window: &mut Window, cx: &mut Context<Self>) -> bool {
+        let name = self
+            .workspace_name_input
+            .read(cx)
+            .value()
+            .trim()
+            .to_string();
+        if name.is_empty() {
+            return false;
+        }
+        if self
+            .workspaces
+            .iter()
+            .any(|workspace| workspace.name.eq_ignore_ascii_case(&name))
+        {
+            self.error = Some(format!("A workspace named {} already exists", name));
+            cx.notify();
+            return false;
+        }
+
+        self.workspaces.push(Workspace::new(name.clone()));
+        self.save_workspaces();
+        self.set_workspace(Some(name), window, cx);
+        true
+    }
+
+    /// Delete the active workspace, showing all zones again
+    pub fn delete_workspace(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(name) = self.active_workspace.clone() else {
+            return;
+        };
+        self.workspaces.retain(|workspace| workspace.name != name);
+        self.save_workspaces();
+        self.set_workspace(None, window, cx);
+    }
+
+    /// Add a zone of the profile in use to a workspace, or remove it
+    pub fn toggle_workspace_zone(
+        &mut self,
+        name: &str,
+        zone: &Zone,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let profile = self.active_profile.clone();
+        let Some(workspace) = self
+            .workspaces
+            .iter_mut()
+            .find(|workspace| workspace.name == name)
+        else {
+            return;
+        };
+        workspace.toggle(&profile, zone);
+        self.save_workspaces();
+        self.refresh_zone_select(window, cx);
+        cx.notify();
+    }
+
+    fn save_workspaces(&mut self) {
+        if let Err(e) = storage::store_workspaces(&self.workspaces) {
+            self.error = Some(format!("Failed to save workspaces: {}", e));
+        }
+    }
+
+    /// Pin a zone to the top of the zone picker, or unpin it
+    pub fn toggle_pin_zone(&mut self, zone_id: &str, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.pinned_zones.remove(zone_id) {
+            self.pinned_zones.insert(zone_id.to_string());
+        }
+
+        if let Err(e) = storage::store_pinned_zones(&self.pinned_zones) {
+            self.error = Some(format!("Failed to save pinned zones: {}", e));
+        }
+        self.refresh_zone_select(window, cx);
+        cx.notify();
+    }
+
+    /// Only show zones with the given tag in the zone picker and on the portfolio page
+    pub fn set_zone_tag_filter(
+        &mut self,
+        tag: Option<String>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.zone_tag_filter = tag;
+        self.refresh_zone_select(window, cx);
+        cx.notify();
+    }
+
+    pub fn set_zone_tags(
+        &mut self,
+        zone_id: String,
+        zone_tags: Vec<String>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if zone_tags.is_empty() {
+            self.zone_tags.remove(&zone_id);
+        } else {
+            self.zone_tags.insert(zone_id, zone_tags);
+        }
+        if let Err(e) = storage::store_zone_tags(&self.zone_tags) {
+            self.error = Some(format!("Failed to save zone tags: {}", e));
+        }
+
+        // Drop the filter once no zone has its tag anymore
+        if let Some(filter) = &self.zone_tag_filter
+            && !tags::all_tags(&self.zone_tags).contains(filter)
+        {
+            self.zone_tag_filter = None;
+        }
+        self.refresh_zone_select(window, cx);
+        cx.notify();
+    }
+
+    pub fn set_zone_notes(&mut self, zone_id: String, notes: String, cx: &mut Context<Self>) {
+        let notes = notes.trim();
+        if notes.is_empty() {
+            self.zone_notes.remove(&zone_id);
+        } else {
+            self.zone_notes.insert(zone_id, notes.to_string());
+        }
+        if let Err(e) = storage::store_zone_notes(&self.zone_notes) {
+            self.error = Some(format!("Failed to save zone notes: {}", e));
+        }
+        cx.notify();
+    }
+
+    pub fn open_zone_tags_dialog(
+        &mut self,
+        index: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(zone) = self.zones(cx).get(index).cloned() else {
+            return;
+        };
+        let zone_id = zone.id;
+        let title = format!("Tags and notes for {}", zone.name);
+        let current = self
+            .zone_tags
+            .get(&zone_id)
+            .map(|zone_tags| zone_tags.join(", "))
+            .unwrap_or_default();
+        let current_notes = self.zone_notes.get(&zone_id).cloned().unwrap_or_default();
+
+        let tags_input = self.zone_tags_input.clone();
+        tags_input.update(cx, |input, cx| {
+            input.set_value(current, window, cx);
+        });
+        let notes_input = self.zone_notes_input.clone();
+        notes_input.update(cx, |input, cx| {
+            input.set_value(current_notes, window, cx);
+        });
+
+        let app = cx.entity();
+        window.open_dialog(cx, move |dialog, _, _| {
+            let tags_input = tags_input.clone();
+            let notes_input = notes_input.clone();
+            let app = app.clone();
+            let zone_id = zone_id.clone();
+
+            dialog
+                .title(title.clone())
+                .child(
+                    v_flex()
+                        .gap_3()
+                        .child(Input::new(&tags_input))
+                        .child(Input::new(&notes_input)),
+                )
+                .confirm()
+                .on_ok(move |_, window, cx| {
+                    let zone_tags = tags::parse_tags(&tags_input.read(cx).value());
+                    let notes = notes_input.read(cx).value().to_string();
+                    let zone_id = zone_id.clone();
+                    app.update(cx, |this, cx| {
+                        this.set_zone_notes(zone_id.clone(), notes, cx);
+                        this.set_zone_tags(zone_id, zone_tags, window, cx)
+                    });
+                    true
+                })
+        });
+    }
+
+    /// Ask for a domain and account to add as a zone, loading the accounts to choose from
+    pub fn open_add_zone_dialog(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(client) = self.client.clone() else {
+            return;
+        };
+
+        self.added_zone = None;
+        self.error = None;
+        self.zone_name_input.update(cx, |input, cx| {
+            input.set_value("", window, cx);
+        });
+        self.spawn_task(
+            "Loading accounts",
+            window,
+            cx,
+            |_| async move { client.list_accounts().await },
+            |this, result, _, _| match result {
+                Ok(accounts) => {
+                    // Keep the account chosen last if it is still there
+                    if !this
+                        .new_zone_account_id
+                        .as_ref()
+                        .is_some_and(|id| accounts.iter().any(|account| &account.id == id))
+                    {
+                        this.new_zone_account_id =
+                            accounts.first().map(|account| account.id.clone());
+                    }
+                    this.accounts = accounts;
+                }
+                Err(e) => this.error = Some(format!("Failed to load accounts: {}", e)),
+            },
+        );
+        ui::open_add_zone_dialog(cx.entity(), window, cx);
+    }
+
+    pub fn set_new_zone_account(&mut self, account_id: String, cx: &mut Context<Self>) {
+        self.new_zone_account_id = Some(account_id);
+        cx.notify();
+    }
+
+    /// Add the entered domain as a zone, then follow it until it is active
+    pub fn create_zone(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(client) = self.writable_client(cx) else {
+            return;
+        };
+        let Some(account_id) = self.new_zone_account_id.clone() else {
+            return;
+        };
+        let name = self
+            .zone_name_input
+            .read(cx)
+            .value()
+            .trim()
+            .trim_end_matches('.')
+            .to_lowercase();
+        if name.is_empty() {
+            return;
+        }
+
+        self.error = None;
+        self.spawn_task(
+            "Adding zone",
+            window,
+            cx,
+            |_| async move { client.create_zone(&name, &account_id).await },
+            |this, result, window, cx| match result {
+                Ok(zone) => {
+                    window.push_notification(
+                        Notification::success(format!("{} added", zone.name)),
+                        cx,
+                    );
+                    this.added_zone = Some(zone);
+                    this.load_zones(false, window, cx);
+                    this.poll_zone_activation(window, cx);
+                }
+                Err(e) => this.error = Some(format!("Failed to add zone: {}", e)),
+            },
+        );
+    }
+
+    /// Check the added zone's status every minute until its nameservers are in place
+    fn poll_zone_activation(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(client) = self.client.clone() else {
+            return;
+        };
+        let Some(zone_id) = self.added_zone.as_ref().map(|zone| zone.id.clone()) else {
+            return;
+        };
+
+        self.zone_activation_task = Some(cx.spawn_in(window, async move |this, cx| {
+            loop {
+                cx.background_executor()
+                    .timer(ZONE_ACTIVATION_POLL_INTERVAL)
+                    .await;
+
+                // Transient failures are ignored; the next poll will try again
+                let Ok(zone) = client.get_zone(&zone_id).await else {
+                    continue;
+                };
+                let active = zone.status == "active";
+                let updated = this.update_in(cx, |this, window, cx| {
+                    this.update_added_zone(zone, window, cx);
+                });
+                if active || updated.is_err() {
+                    break;
+                }
+            }
+        }));
+    }
+
+    /// Ask Cloudflare to check the added zone's nameservers right away
+    pub fn check_zone_activation(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(client) = self.client.clone() else {
+            return;
+        };
+        let Some(zone_id) = self.added_zone.as_ref().map(|zone| zone.id.clone()) else {
+            return;
+        };
+
+        self.error = None;
+        self.spawn_task(
+            "Checking nameservers",
+            window,
+            cx,
+            |_| async move {
+                client.check_zone_activation(&zone_id).await?;
+                client.get_zone(&zone_id).await
+            },
+            |this, result, window, cx| match result {
+                Ok(zone) => this.update_added_zone(zone, window, cx),
+                Err(e) => this.error = Some(format!("Failed to check nameservers: {}", e)),
+            },
+        );
+    }
+
+    fn update_added_zone(&mut self, zone: Zone, window: &mut Window, cx: &mut Context<Self>) {
+        if self
+            .added_zone
+            .as_ref()
+            .is_none_or(|added| added.id != zone.id)
+        {
+            return;
+        }
+        let activated = zone.status == "active"
+            && self
+                .added_zone
+                .as_ref()
+                .is_some_and(|added| added.status != "active");
+        if activated {
+            self.zone_activation_task = None;
+            window.push_notification(
+                Notification::success(format!("{} is active on Cloudflare", zone.name)),
+                cx,
+            );
+            self.load_zones(false, window, cx);
+        }
+        self.added_zone = Some(zone);
+        cx.notify();
+    }
+
+    /// Open the zone added last
+    pub fn open_added_zone(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(zone_id) = self.added_zone.as_ref().map(|zone| zone.id.clone()) else {
+            return;
+        };
+        if let Some(index) = self.zones(cx).iter().position(|zone| zone.id == zone_id) {
+            self.open_zone(index, window, cx);
+        }
+    }
+
+    /// Check what the selected zone still serves, then ask to type its name before deleting it
+    pub fn open_delete_zone_dialog(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(client) = self.client.clone() else {
+            return;
+        };
+        let Some(zone) = self.selected_zone(cx) else {
+            return;
+        };
+
+        let since = Utc::now() - chrono::Duration::hours(24);
+        self.deletion_confirm_input.update(cx, |input, cx| {
+            input.set_value("", window, cx);
+        });
+        self.spawn_task(
+            "Checking what the zone serves",
+            window,
+            cx,
+            |_| async move {
+                let records = match client.list_dns_records(&zone.id).await {
+                    Ok(records) => records,
+                    Err(e) => return (zone, Err(e)),
+                };
+                let traffic = client.zone_traffic(&zone.id, since, true).await;
+                let query_counts = client.dns_query_counts(&zone.id, since).await;
+                let certificate_packs = client.list_certificate_packs(&zone.id).await;
+                let check = SafetyCheck::new(
+                    &records,
+                    records.len(),
+                    traffic.map_err(|e| e.to_string()),
+                    query_counts.map_err(|e| e.to_string()),
+                    certificate_packs.map_err(|e| e.to_string()),
+                );
+                (zone, Ok(check))
+            },
+            |this, (zone, result), window, cx| match result {
+                Ok(check) => ui::open_delete_zone_dialog(cx.entity(), zone, check, window, cx),
+                Err(e) => this.error = Some(format!("Failed to check zone: {}", e)),
+            },
+        );
+    }
+
+    /// Delete a zone, then return to the portfolio
+    pub fn delete_zone(&mut self, zone: Zone, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(client) = self.writable_client(cx) else {
+            return;
+        };
+
+        self.error = None;
+        self.spawn_task(
+            "Deleting zone",
+            window,
+            cx,
+            |_| async move {
+                let result = client.delete_zone(&zone.id).await;
+                (zone, result)
+            },
+            |this, (zone, result), window, cx| match result {
+                Ok(()) => {
+                    window.push_notification(
+                        Notification::success(format!("{} deleted", zone.name)),
+                        cx,
+                    );
+                    this.zone_tags.remove(&zone.id);
+                    this.zone_notes.remove(&zone.id);
+                    if let Err(e) = storage::store_zone_tags(&this.zone_tags)
+                        .and_then(|_| storage::store_zone_notes(&this.zone_notes))
+                    {
+                        this.error = Some(format!("Failed to save zone tags: {}", e));
+                    }
+                    this.selected_zone_index = None;
+                    this.page = Page::Portfolio;
+                    this.load_zones(false, window, cx);
+                }
+                Err(e) => this.error = Some(format!("Failed to delete zone: {}", e)),
+            },
+        );
+    }
+
+    /// Open a zone from the portfolio page
+    pub fn open_zone(&mut self, index: usize, window: &mut Window, cx: &mut Context<Self>) {
+        self.select_zone(index, window, cx);
+        self.page = Page::Dashboard;
+        cx.notify();
+    }
+
+    /// Fetch DNSSEC and certificate status of every zone for the portfolio page
+    ///
+    /// The check repeats every [`HEALTH_CHECK_INTERVAL`] so certificate expiry is
+    /// noticed while the app stays open.
+    pub fn check_zone_health(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(client) = self.client.clone() else {
+            return;
+        };
+        self.health_check_task = None;
+
+        let zone_ids: Vec<String> = self.zones(cx).iter().map(|zone| zone.id.clone()).collect();
+        self.spawn_task(
+            "Checking zone health",
+            window,
+            cx,
+            |progress| async move {
+                let total = zone_ids.len();
+                for (done, zone_id) in zone_ids.into_iter().enumerate() {
+                    let health = ZoneHealth::fetch(&client, &zone_id).await;
+                    progress.update_app(|this, cx| {
+                        this.store
+                            .update(cx, |store, cx| store.set_zone_health(zone_id, health, cx));
+                    });
+                    progress.set(done + 1, total);
+                }
+            },
+            |this, (), window, cx| {
+                this.notify_expiring_certificates(window, cx);
+                this.health_check_task = Some(cx.spawn_in(window, async move |this, cx| {
+                    cx.background_executor().timer(HEALTH_CHECK_INTERVAL).await;
+                    this.update_in(cx, |this, window, cx| this.check_zone_health(window, cx))
+                        .ok();
+                }));
+            },
+        );
+    }
+
+    /// Show a desktop notification for certificates that reached a new expiry threshold
+    fn notify_expiring_certificates(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let store = self.store.read(cx);
+        let all: Vec<&TrackedCertificate> = store
+            .zone_health()
+            .values()
+            .flat_map(|health| &health.certificates)
+            .collect();
+        // Also forgets the certificates that no longer exist, so the stored map doesn't grow
+        let mut notified = self.certificate_notifications.clone();
+        let due: Vec<String> = certificates::due_notifications(&all, &mut notified, Utc::now())
+            .into_iter()
+            .map(|certificate| certificate.id.clone())
+            .collect();
+        if notified != self.certificate_notifications {
+            if let Err(e) = storage::store_certificate_notifications(&notified) {
+                self.error = Some(format!("Failed to save certificate notifications: {}", e));
+            }
+            self.certificate_notifications = notified;
+        }
+        if due.is_empty() {
+            return;
+        }
+
+        let now = Utc::now();
+        let lines: Vec<String> = self
+            .store
+            .read(cx)
+            .expiring_certificates()
+            .into_iter()
+            .filter(|(_, certificate)| due.contains(&certificate.id))
+            .map(|(zone, certificate)| {
+                let days = certificate.days_until_expiry(now);
+                if days < 0 {
+                    format!("{} ({}) has expired", zone.name, certificate.kind.label())
+                } else {
+                    format!(
+                        "{} ({}) expires in {} days",
+                        zone.name,
+                        certificate.kind.label(),
+                        days
+                    )
+                }
+            })
+            .collect();
+        let message = lines.join("\n");
+        desktop_notification::show("Certificates expiring soon", message.clone());
+
+        let app = cx.entity();
+        window.push_notification(
+            Notification::warning(message)
+                .title("Certificates expiring soon")
+                .autohide(false)
+                .action(move |_, _, _| {
+                    let app = app.clone();
+                    Button::new("view-expiring-certificates")
+                        .label("View")
+                        .on_click(move |_, _, cx| {
+                            app.update(cx, |this, cx| {
+                                this.page = Page::Portfolio;
+                                cx.notify();
+                            });
+                        })
+                }),
+            cx,
+        );
+    }
+
+    /// Show the selected zone's cached records, fetching them if missing or stale
+    pub fn load_dns_records_cached(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(zone) = self.selected_zone(cx) else {
+            return;
+        };
+        if !self.store.read(cx).has_fresh_records(&zone.id) {
+            self.load_dns_records(window, cx);
+        }
+    }
+
+    pub fn load_dns_records(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(client) = self.client.clone() else {
+            return;
+        };
+        let Some(zone) = self.selected_zone(cx) else {
+            return;
+        };
+
+        let zone_id = zone.id;
+        self.error = None;
+        self.spawn_task(
+            "Loading DNS records",
+            window,
+            cx,
+            |_| async move {
+                let result = client.list_dns_records(&zone_id).await;
+                (zone_id, result)
+            },
+            move |this, (zone_id, result), _, cx| match result {
+                Ok(records) => {
+                    this.store
+                        .update(cx, |store, cx| store.set_records(zone_id, records, cx));
+                }
+                Err(e) => {
+                    this.error = Some(format!("Failed to load DNS records: {}", e));
+                }
+            },
+        );
+    }
+
+    pub fn save_token(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let token = self.token_input.read(cx).value().to_string();
+        if token.is_empty() {
+            self.error = Some("Please enter an API token".to_string());
+            cx.notify();
+            return;
+        }
+
+        self.error = None;
+
+        let client = self.new_client(token.clone());
+        let profile = self.active_profile.clone();
+
+        self.spawn_task(
+            "Verifying token",
+            window,
+            cx,
+            |_| async move {
+                let result = client.verify_token().await;
+                (client, result)
+            },
+            move |this, (client, result), window, cx| {
+                match result {
+                    Ok(true) => {
+                        // Token is valid, store it
+                        if let Err(e) = storage::store_token(&profile, &token) {
+                            this.error = Some(format!("Failed to store token: {}", e));
+                        } else {
+                            this.set_client(Some(client));
+                            this.found_tokens.clear();
+                            this.token_wizard = None;
+                            this.page = Page::Portfolio;
+                            this.load_zones(true, window, cx);
+                            this.load_token_details(window, cx);
+                        }
+                    }
+                    Ok(false) => {
+                        this.error = Some("Token is not active".to_string());
+                    }
+                    Err(e) => {
+                        this.error = Some(format!("Failed to verify token: {}", e));
+                    }
+                }
+            },
+        );
+    }
+
+    /// Verify and store a token found in another tool's config, as if it was entered
+    pub fn use_found_token(&mut self, index: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(found) = self.found_tokens.get(index) else {
+            return;
+        };
+        let token = found.token.clone();
+        self.token_input.update(cx, |input, cx| {
+            input.set_value(token, window, cx);
+        });
+        self.save_token(window, cx);
+    }
+
+    /// Guide through creating a token, with everything the app can do picked
+    pub fn open_token_wizard(&mut self, cx: &mut Context<Self>) {
+        self.token_wizard = Some(token_template::offered_capabilities().collect());
+        cx.notify();
+    }
+
+    pub fn close_token_wizard(&mut self, cx: &mut Context<Self>) {
+        self.token_wizard = None;
+        cx.notify();
+    }
+
+    pub fn toggle_token_wizard_capability(
+        &mut self,
+        capability: Capability,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(picked) = &mut self.token_wizard else {
+            return;
+        };
+        // Kept in the order the permissions are listed in
+        *picked = Capability::ALL
+            .into_iter()
+            .filter(|c| picked.contains(c) != (*c == capability))
+            .collect();
+        cx.notify();
+    }
+
+    /// Open Cloudflare's token creation page with the picked permissions filled in
+    pub fn open_token_creation(&mut self, cx: &mut Context<Self>) {
+        let Some(capabilities) = &self.token_wizard else {
+            return;
+        };
+        cx.open_url(&token_template::create_token_url(capabilities));
+    }
+
+    pub fn dismiss_found_tokens(&mut self, cx: &mut Context<Self>) {
+        self.found_tokens.clear();
+        cx.notify();
+    }
+
+    pub fn update_token_from_settings(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let token = self.settings_token_input.read(cx).value().to_string();
+        if token.is_empty() {
+            self.error = Some("Please enter an API token".to_string());
+            cx.notify();
+            return;
+        }
+
+        self.error = None;
+
+        let client = self.new_client(token.clone());
+        let profile = self.active_profile.clone();
+
+        self.spawn_task(
+            "Verifying token",
+            window,
+            cx,
+            |_| async move {
+                let result = client.verify_token().await;
+                (client, result)
+            },
+            move |this, (client, result), window, cx| match result {
+                Ok(true) => {
+                    if let Err(e) = storage::store_token(&profile, &token) {
+                        this.error = Some(format!("Failed to store token: {}", e));
+                    } else {
+                        this.set_client(Some(client));
+                        this.store.update(cx, |store, cx| store.clear(cx));
+                        if let Err(e) = startup::delete() {
+                            this.error = Some(format!("Failed to delete startup data: {}", e));
+                        }
+                        this.selected_zone_index = None;
+                        this.settings_token_input.update(cx, |input, cx| {
+                            input.set_value("", window, cx);
+                        });
+                        this.page = Page::Dashboard;
+                        this.load_zones(true, window, cx);
+                        this.load_token_details(window, cx);
+                        window.push_notification(
+                            Notification::success("API token updated successfully"),
+                            cx,
+                        );
+                    }
+                }
+                Ok(false) => {
+                    this.error = Some("Token is not active".to_string());
+                }
+                Err(e) => {
+                    this.error = Some(format!("Failed to verify token: {}", e));
+                }
+            },
+        );
+    }
+
+    /// Explore the app with fake zones and records instead of a Cloudflare account
+    pub fn start_demo(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.error = None;
+        self.set_client(Some(CloudflareClient::demo()));
+        self.page = Page::Portfolio;
+        self.load_zones(true, window, cx);
+        self.load_token_details(window, cx);
+    }
+
+    pub fn is_demo(&self) -> bool {
+        self.client.as_ref().is_some_and(CloudflareClient::is_demo)
+    }
+
+    /// Leave demo mode, dropping its data; a stored token is kept
+    pub fn exit_demo(&mut self, cx: &mut Context<Self>) {
+        self.disconnect(cx);
+    }
+
+    /// Delete the active profile's token; other profiles stay, and the app switches to the
+    /// first of them
+    pub fn clear_token(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Err(e) = storage::delete_token(&self.active_profile) {
+            self.error = Some(format!("Failed to delete token: {}", e));
+            cx.notify();
+            return;
+        }
+        if let Err(e) = startup::delete() {
+            self.error = Some(format!("Failed to delete startup data: {}", e));
+        }
+
+        if self.profiles.len() > 1 {
+            let removed = self.active_profile.clone();
+            self.profiles.retain(|profile| profile != &removed);
+            if let Err(e) = storage::store_profiles(&self.profiles) {
+                self.error = Some(format!("Failed to save profiles: {}", e));
+            }
+            if self.profile_settings.remove(&removed).is_some()
+                && let Err(e) = storage::store_profile_settings(&self.profile_settings)
+            {
+                self.error = Some(format!("Failed to save profile settings: {}", e));
+            }
+            let next = self.profiles[0].clone();
+            self.switch_profile(next, window, cx);
+        } else {
+            self.disconnect(cx);
+        }
+    }
+
+    /// Sign in with another profile's token, reloading the zones, or ask for its token if it
+    /// has none yet
+    pub fn switch_profile(&mut self, profile: String, window: &mut Window, cx: &mut Context<Self>) {
+        if profile == self.active_profile && self.client.is_some() && !self.is_demo() {
+            return;
+        }
+        if let Err(e) = storage::store_active_profile(&profile) {
+            self.error = Some(format!("Failed to save active profile: {}", e));
+        }
+        self.disconnect(cx);
+        self.active_profile = profile;
+
+        let ttl = self
+            .active_profile_settings()
+            .default_ttl
+            .map(|ttl| ttl.to_string())
+            .unwrap_or_default();
+        self.profile_ttl_input.update(cx, |input, cx| {
+            input.set_value(ttl, window, cx);
+        });
+        self.sync_control_server_read_only();
+        self.sync_control_server_duplicates();
+        self.apply_theme(window, cx);
+
+        match storage::get_token(&self.active_profile) {
+            Ok(Some(token)) => {
+                let client = self.new_client(token);
+                self.set_client(Some(client));
+                self.page = Page::Portfolio;
+                self.load_zones(true, window, cx);
+                self.load_token_details(window, cx);
+            }
+            // Left on the token setup by disconnecting
+            Ok(None) => {}
+            Err(e) => self.error = Some(format!("Failed to read token: {}", e)),
+        }
+        cx.notify();
+    }
+
+    pub fn open_add_profile_dialog(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.error = None;
+        self.profile_name_input.update(cx, |input, cx| {
+            input.set_value("", window, cx);
+        });
+        ui::open_add_profile_dialog(cx.entity(), window, cx);
+    }
+
+    /// Add a profile with the entered name and switch to it, to enter its token
+    pub fn add_profile(&mut self, window: &mut Window, cx: &mut Context<Self>) -> bool {
+        let name = self.profile_name_input.read(cx).value().trim().to_string();
+        if name.is_empty() {
+            return false;
+        }
+        if self
+            .profiles
+            .iter()
+            .any(|profile| profile.eq_ignore_ascii_case(&name))
+        {
+            self.error = Some(format!("A profile named {} already exists", name));
+            cx.notify();
+            return false;
+        }
+
+        self.profiles.push(name.clone());
+        if let Err(e) = storage::store_profiles(&self.profiles) {
+            self.error = Some(format!("Failed to save profiles: {}", e));
+        }
+        self.switch_profile(name, window, cx);
+        true
+    }
+
+    /// Drop the client and everything loaded through it, back to the token setup
+    fn disconnect(&mut self, cx: &mut Context<Self>) {
+        self.set_client(None);
+        self.token_scopes = None;
+        self.token_scopes_error = None;
+        self.token_expires_on = None;
+        self.refresh_task = None;
+        self.record_refresh_task = None;
+        self.history.clear();
+        self.staged_changes.clear();
+        self.retry_queue.clear();
+        self.retry_timer = None;
+        self.development_mode.clear();
+        self.development_mode_countdown = None;
+        self.old_answers.clear();
+        self.old_answer_countdown = None;
+        self.ttl_reminder = None;
+        self.health_check_task = None;
+        self.store.update(cx, |store, cx| store.clear(cx));
+        self.saved_filters.clear();
+        self.selected_zone_index = None;
+        self.page = Page::TokenSetup;
+        cx.notify();
+    }
+
+    /// Priority from the editor, required for types that have one
+    fn record_priority(
+        &self,
+        record_type: DnsRecordType,
+        cx: &Context<Self>,
+    ) -> Result<Option<u16>, String> {
+        if !record_type.requires_priority() {
+            return Ok(None);
+        }
+        let value = self.record_priority_input.read(cx).value();
+        let value = value.trim();
+        if value.is_empty() {
+            return Err(format!("Priority is required for {} records", record_type));
+        }
+        value
+            .parse()
+            .map(Some)
+            .map_err(|_| "Priority must be a number between 0 and 65535".to_string())
+    }
+
+    pub fn create_record(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(zone) = self.selected_zone(cx) else {
+            return;
+        };
+
+        let record_type = self
+            .record_type_select
+            .read(cx)
+            .selected_value()
+            .copied()
+            .unwrap_or(DnsRecordType::A);
+        let name = self.record_name_input.read(cx).value().to_string();
+        let content = self.record_content_input.read(cx).value().to_string();
+        let ttl: u32 = self.record_ttl_input.read(cx).value().parse().unwrap_or(1);
+        let priority = match self.record_priority(record_type, cx) {
+            Ok(priority) => priority,
+            Err(e) => {
+                self.error = Some(e);
+                cx.notify();
+                return;
+            }
+        };
+
+        // Validate
+        if name.is_empty() {
+            self.error = Some("Record name is required".to_string());
+            cx.notify();
+            return;
+        }
+        if content.is_empty() {
+            self.error = Some("Content is required".to_string());
+            cx.notify();
+            return;
+        }
+        if let Err(e) = record_type.validate_content(&content) {
+            self.error = Some(e.to_string());
+            cx.notify();
+            return;
+        }
+        let comment = self.record_comment_input.read(cx).value().to_string();
+        let Some(comment) = self.finished_comment(&comment, Self::create_record, window, cx) else {
+            return;
+        };
+
+        let zone_id = zone.id.clone();
+        let record = CreateDnsRecord {
+            record_type,
+            name,
+            content,
+            ttl,
+            proxied: if record_type.is_proxiable() {
+                Some(self.record_proxied)
+            } else {
+                None
+            },
+            priority,
+            comment,
+            data: None,
+        };
+
+        let duplicate = self
+            .dns_records(cx)
+            .iter()
+            .find(|existing| record.duplicates(existing, &zone.name))
+            .map(|existing| existing.name.clone());
+        if let Some(name) = duplicate {
+            if self.blocks_duplicate_records() {
+                self.error = Some(format!(
+                    "{} already has a {} record with this content",
+                    name, record.record_type
+                ));
+                cx.notify();
+            } else {
+                self.open_duplicate_record_dialog(name, zone_id, record, window, cx);
+            }
+            return;
+        }
+
+        self.submit_new_record(zone_id, record, window, cx);
+    }
+
+    /// Comment entered for records as it's saved; `None` while asking for the ticket it refers
+    /// to, after which `save` runs again
+    fn finished_comment(
+        &mut self,
+        comment: &str,
+        save: fn(&mut Self, &mut Window, &mut Context<Self>),
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<Option<String>> {
+        let ticket = match self.comment_ticket.take() {
+            Some(ticket) => ticket,
+            None if self.record_defaults.needs_ticket(comment) => {
+                self.open_ticket_dialog(save, window, cx);
+                return None;
+            }
+            None => String::new(),
+        };
+        Some(
+            self.record_defaults
+                .finish_comment(comment, Local::now(), &ticket),
+        )
+    }
+
+    fn open_ticket_dialog(
+        &mut self,
+        save: fn(&mut Self, &mut Window, &mut Context<Self>),
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let ticket_input = self.ticket_input.clone();
+        ticket_input.update(cx, |input, cx| {
+            input.set_value("", window, cx);
+        });
+
+        let app = cx.entity();
+        window.open_dialog(cx, move |dialog, _, _| {
+            let ticket_input = ticket_input.clone();
+            let app = app.clone();
+
+            dialog
+                .title("Ticket")
+                .child(
+                    v_flex()
+                        .gap_2()
+                        .child("The comment refers to a ticket. Enter it to save the record.")
+                        .child(Input::new(&ticket_input)),
+                )
+                .confirm()
+                .button_props(DialogButtonProps::default().ok_text("Save"))
+                .on_ok(move |_, window, cx| {
+                    let ticket = ticket_input.read(cx).value().trim().to_string();
+                    if ticket.is_empty() {
+                        return false;
+                    }
+                    app.update(cx, |this, cx| {
+                        this.comment_ticket = Some(ticket);
+                        save(this, window, cx);
+                    });
+                    true
+                })
+        });
+    }
+
+    /// Ask before creating a record that is identical to an existing one
+    fn open_duplicate_record_dialog(
+        &mut self,
+        name: String,
+        zone_id: String,
+        record: CreateDnsRecord,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let app = cx.entity();
+        window.open_dialog(cx, move |dialog, _, _| {
+            let app = app.clone();
+            let zone_id = zone_id.clone();
+            let record = record.clone();
+
+            dialog
+                .title("Duplicate record")
+                .child(format!(
+                    "{} already has a {} record with the content \"{}\". Create another one anyway?",
+                    name, record.record_type, record.content
+                ))
+                .confirm()
+                .button_props(DialogButtonProps::default().ok_text("Create Anyway"))
+                .on_ok(move |_, window, cx| {
+                    app.update(cx, |this, cx| {
+                        this.submit_new_record(zone_id.clone(), record.clone(), window, cx);
+                    });
+                    true
+                })
+        });
+    }
+
+    fn submit_new_record(
+        &mut self,
+        zone_id: String,
+        record: CreateDnsRecord,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.staging {
+            self.stage_changes(&zone_id, vec![StagedChange::create(record)], window, cx);
+            self.clear_record_form(window, cx);
+            return;
+        }
+
+        let description = format!("Create {} {}", record.record_type, record.name);
+        self.error = None;
+        self.run_record_command(
+            "Creating record",
+            description,
+            RecordCommand::Create { zone_id, record },
+            window,
+            cx,
+            |this, result, window, cx| match result {
+                Ok(()) => {
+                    this.clear_record_form(window, cx);
+                    window.push_notification(
+                        Notification::success("DNS record created successfully"),
+                        cx,
+                    );
+                }
+                Err(e) => {
+                    this.error = Some(format!("Failed to create record: {}", e));
+                }
+            },
+        );
+    }
+
+    pub fn update_record(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(zone) = self.selected_zone(cx) else {
+            return;
+        };
+        let Some(editing) = self.editing_record.clone() else {
+            return;
+        };
+        if editing.locked {
+            self.error = Some(LOCKED_RECORD_ERROR.to_string());
+            cx.notify();
+            return;
+        }
+        if let Some(reason) = self.protected_records.get(&editing.id) {
+            self.error = Some(protected_record_error(reason));
+            cx.notify();
+            return;
+        }
+        let record_type = self
+            .record_type_select
+            .read(cx)
+            .selected_value()
+            .copied()
+            .unwrap_or(DnsRecordType::A);
+        let name = self.record_name_input.read(cx).value().to_string();
+        let content = self.record_content_input.read(cx).value().to_string();
+        let ttl: u32 = self.record_ttl_input.read(cx).value().parse().unwrap_or(1);
+        let priority = match self.record_priority(record_type, cx) {
+            Ok(priority) => priority,
+            Err(e) => {
+                self.error = Some(e);
+                cx.notify();
+                return;
+            }
+        };
+
+        // Validate
+        if let Err(e) = record_type.validate_content(&content) {
+            self.error = Some(e.to_string());
+            cx.notify();
+            return;
+        }
+        let comment = self.record_comment_input.read(cx).value().to_string();
+        let Some(comment) = self.finished_comment(&comment, Self::update_record, window, cx) else {
+            return;
+        };
+
+        let zone_id = zone.id.clone();
+        let record = UpdateDnsRecord {
+            record_type: Some(record_type),
+            name: Some(name),
+            content: Some(content),
+            ttl: Some(ttl),
+            proxied: if record_type.is_proxiable() {
+                Some(self.record_proxied)
+            } else {
+                None
+            },
+            priority,
+            comment,
+            // Send the fields Cloudflare returned as they were while the content is unchanged
+            data: (record_type == editing.record_type && content == editing.data_content())
+                .then(|| editing.data.clone())
+                .flatten(),
+        };
+
+        if self.staging {
+            let change = StagedChange::update(editing, record);
+            self.stage_changes(&zone_id, vec![change], window, cx);
+            self.editing_record = None;
+            self.clear_record_form(window, cx);
+            return;
+        }
+
+        let fields = watch::diff_update(&editing, &record, &zone.name);
+        if fields.is_empty() {
+            self.save_record_update(zone_id, &editing, record, window, cx);
+        } else {
+            self.open_update_preview_dialog(zone_id, editing, record, fields, window, cx);
+        }
+    }
+
+    /// Show what an update changes before it's saved, so accidental edits can be caught
+    fn open_update_preview_dialog(
+        &mut self,
+        zone_id: String,
+        editing: DnsRecord,
+        record: UpdateDnsRecord,
+        fields: Vec<FieldChange>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let app = cx.entity();
+        window.open_dialog(cx, move |dialog, _, cx| {
+            let app = app.clone();
+            let zone_id = zone_id.clone();
+            let editing = editing.clone();
+            let record = record.clone();
+
+            dialog
+                .title(format!("Save {} {}?", editing.record_type, editing.name))
+                .child(
+                    v_flex().gap_2().children(
+                        fields
+                            .iter()
+                            .map(|field| ui::render_field_change(field, cx)),
+                    ),
+                )
+                .confirm()
+                .button_props(DialogButtonProps::default().ok_text("Save"))
+                .on_ok(move |_, window, cx| {
+                    app.update(cx, |this, cx| {
+                        this.save_record_update(
+                            zone_id.clone(),
+                            &editing,
+                            record.clone(),
+                            window,
+                            cx,
+                        );
+                    });
+                    true
+                })
+        });
+    }
+
+    /// Re-fetch the edited record before sending its update, so that changes made to it elsewhere
+    /// since it was loaded are merged or overwritten knowingly instead of silently
+    fn save_record_update(
+        &mut self,
+        zone_id: String,
+        editing: &DnsRecord,
+        record: UpdateDnsRecord,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(client) = self.writable_client(cx) else {
+            return;
+        };
+        let zone_name = self
+            .selected_zone(cx)
+            .filter(|zone| zone.id == zone_id)
+            .map(|zone| zone.name)
+            .unwrap_or_default();
+        let editing = editing.clone();
+        let fetch_zone_id = zone_id.clone();
+        let record_id = editing.id.clone();
+
+        self.error = None;
+        self.spawn_task(
+            "Checking for remote changes",
+            window,
+            cx,
+            move |_| async move { client.get_dns_record(&fetch_zone_id, &record_id).await },
+            move |this, result, window, cx| match result {
+                Ok(remote) => {
+                    match edit_conflict::find_conflict(&editing, &remote, &record, &zone_name) {
+                        Some(conflict) => {
+                            this.edit_conflict = Some(remote);
+                            ui::open_update_conflict_dialog(
+                                cx.entity(),
+                                zone_id,
+                                editing,
+                                record,
+                                conflict,
+                                window,
+                                cx,
+                            );
+                        }
+                        None => this.send_record_update(zone_id, &editing, record, window, cx),
+                    }
+                }
+                Err(e) => {
+                    this.error = Some(format!("Failed to check for remote changes: {}", e));
+                }
+            },
+        );
+    }
+
+    /// Send an update of the edited record to Cloudflare
+    pub fn send_record_update(
+        &mut self,
+        zone_id: String,
+        editing: &DnsRecord,
+        record: UpdateDnsRecord,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let description = format!(
+            "Update {} {}",
+            record.record_type.unwrap_or(editing.record_type),
+            editing.name
+        );
+        self.error = None;
+        self.run_record_command(
+            "Updating record",
+            description,
+            RecordCommand::Update {
+                zone_id,
+                record_id: editing.id.clone(),
+                record,
+            },
+            window,
+            cx,
+            |this, result, window, cx| match result {
+                Ok(()) => {
+                    this.editing_record = None;
+                    this.clear_record_form(window, cx);
+                    let notification =
+                        this.undoable_notification("DNS record updated successfully", cx);
+                    window.push_notification(notification, cx);
+                }
+                Err(e) => {
+                    this.error = Some(format!("Failed to update record: {}", e));
+                }
+            },
+        );
+    }
+
+    pub fn delete_record(
+        &mut self,
+        record_id: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(zone) = self.selected_zone(cx) else {
+            return;
+        };
+
+        let records = self.dns_records(cx);
+        let record = records.iter().find(|record| record.id == record_id);
+        if record.is_some_and(|record| record.locked) {
+            self.error = Some(LOCKED_RECORD_ERROR.to_string());
+            cx.notify();
+            return;
+        }
+        if let Some(reason) = self.protected_records.get(record_id) {
+            self.error = Some(protected_record_error(reason));
+            cx.notify();
+            return;
+        }
+        if self.staging
+            && let Some(record) = record
+        {
+            let change = StagedChange::delete(record.clone());
+            self.stage_changes(&zone.id, vec![change], window, cx);
+            return;
+        }
+        let description = record
+            .map(|record| format!("Delete {} {}", record.record_type, record.name))
+            .unwrap_or_else(|| "Delete record".to_string());
+        self.error = None;
+        self.run_record_command(
+            "Deleting record",
+            description,
+            RecordCommand::Delete {
+                zone_id: zone.id,
+                record_id,
+            },
+            window,
+            cx,
+            |this, result, window, cx| match result {
+                Ok(()) => {
+                    let notification =
+                        this.undoable_notification("DNS record deleted successfully", cx);
+                    window.push_notification(notification, cx);
+                }
+                Err(e) => {
+                    this.error = Some(format!("Failed to delete record: {}", e));
+                }
+            },
+        );
+    }
+
+    pub fn set_staging(&mut self, staging: bool, cx: &mut Context<Self>) {
+        self.staging = staging;
+        cx.notify();
+    }
+
+    /// Keep record changes for review instead of running them
+    fn stage_changes(
+        &mut self,
+        zone_id: &str,
+        changes: Vec<StagedChange>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let message = match changes.as_slice() {
+            [change] => format!("Staged: {}", change.label),
+            changes => format!("Staged {} changes", changes.len()),
+        };
+        self.staged_changes
+            .entry(zone_id.to_string())
+            .or_default()
+            .extend(changes);
+        self.error = None;
+        window.push_notification(Notification::info(message), cx);
+        cx.notify();
+    }
+
+    /// Staged changes of the selected zone, in the order they will be applied
+    pub fn zone_staged_changes(&self, cx: &gpui::App) -> &[StagedChange] {
+        self.selected_zone(cx)
+            .and_then(|zone| self.staged_changes.get(&zone.id))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub fn unstage_change(&mut self, index: usize, cx: &mut Context<Self>) {
+        if let Some(zone) = self.selected_zone(cx)
+            && let Some(changes) = self.staged_changes.get_mut(&zone.id)
+            && index < changes.len()
+        {
+            changes.remove(index);
+            if changes.is_empty() {
+                self.staged_changes.remove(&zone.id);
+            }
+        }
+        cx.notify();
+    }
+
+    pub fn discard_staged_changes(&mut self, cx: &mut Context<Self>) {
+        if let Some(zone) = self.selected_zone(cx) {
+            self.staged_changes.remove(&zone.id);
+        }
+        cx.notify();
+    }
+
+    /// Apply the selected zone's staged changes, first asking how to resolve changes of records
+    /// that were modified or deleted on Cloudflare since they were staged
+    pub fn apply_staged_changes(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(client) = self.writable_client(cx) else {
+            return;
+        };
+        let Some(zone) = self.selected_zone(cx) else {
+            return;
+        };
+
+        self.error = None;
+        self.spawn_task(
+            "Checking for remote changes",
+            window,
+            cx,
+            |_| async move {
+                let result = client.list_dns_records(&zone.id).await;
+                (zone.id, result)
+            },
+            |this, (zone_id, result), window, cx| {
+                let records = match result {
+                    Ok(records) => records,
+                    Err(e) => {
+                        this.error = Some(format!("Failed to check for remote changes: {}", e));
+                        return;
+                    }
+                };
+                let Some(changes) = this.staged_changes.get(&zone_id) else {
+                    return;
+                };
+
+                let conflicts = staging::find_conflicts(changes, &records);
+                this.store.update(cx, |store, cx| {
+                    store.set_records(zone_id.clone(), records, cx)
+                });
+                if conflicts.is_empty() {
+                    if let Some(changes) = this.staged_changes.remove(&zone_id) {
+                        this.send_staged_changes(zone_id, changes, window, cx);
+                    }
+                } else {
+                    this.staged_conflicts = conflicts;
+                    ui::open_staged_conflicts_dialog(cx.entity(), window, cx);
+                }
+            },
+        );
+    }
+
+    pub fn set_conflict_resolution(
+        &mut self,
+        conflict: usize,
+        resolution: ConflictResolution,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(conflict) = self.staged_conflicts.get_mut(conflict) {
+            conflict.resolution = resolution;
+        }
+        cx.notify();
+    }
+
+    /// Apply the selected zone's staged changes as their conflicts were resolved
+    pub fn apply_resolved_staged_changes(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let conflicts = std::mem::take(&mut self.staged_conflicts);
+        let Some(zone) = self.selected_zone(cx) else {
+            return;
+        };
+        let Some(changes) = self.staged_changes.remove(&zone.id) else {
+            return;
+        };
+
+        let (apply, keep) = staging::resolve(changes, &conflicts);
+        if !keep.is_empty() {
+            self.staged_changes.insert(zone.id.clone(), keep);
+        }
+        if apply.is_empty() {
+            cx.notify();
+            return;
+        }
+        self.send_staged_changes(zone.id, apply, window, cx);
+    }
+
+    /// Send staged changes to Cloudflare in one batch request, so either all of them are
+    /// applied or none
+    fn send_staged_changes(
+        &mut self,
+        zone_id: String,
+        changes: Vec<StagedChange>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(client) = self.client.clone() else {
+            return;
+        };
+
+        let batch = staging::record_batch(&changes);
+        self.spawn_task(
+            "Applying staged changes",
+            window,
+            cx,
+            |_| async move {
+                let result = client.batch_dns_records(&zone_id, &batch).await;
+                (zone_id, result)
+            },
+            move |this, (zone_id, result), window, cx| {
+                match result {
+                    Ok(()) => {
+                        for change in &changes {
+                            this.add_journal_entry(JournalEntry::new(
+                                &zone_id,
+                                change.label.clone(),
+                                "Staged changes",
+                            ));
+                        }
+                        let message = format!("Applied {} staged changes", changes.len());
+                        window.push_notification(Notification::success(message), cx);
+                    }
+                    Err(e) => {
+                        // Nothing was applied, so the changes stay staged, ahead of any staged
+                        // since
+                        this.staged_changes
+                            .entry(zone_id)
+                            .or_default()
+                            .splice(0..0, changes);
+                        this.error = Some(format!("Failed to apply staged changes: {}", e));
+                    }
+                }
+                this.load_dns_records(window, cx);
+            },
+        );
+    }
+
+    /// Run a record change and add it to the undo history once it succeeds
+    fn run_record_command(
+        &mut self,
+        name: &'static str,
+        description: String,
+        command: RecordCommand,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        on_done: impl FnOnce(&mut Self, anyhow::Result<()>, &mut Window, &mut Context<Self>) + 'static,
+    ) {
+        let Some(client) = self.writable_client(cx) else {
+            return;
+        };
+        let run = self.validate_and_run(client, command, cx);
+
+        self.spawn_task(
+            name,
+            window,
+            cx,
+            |_| run,
+            move |this, (command, result), window, cx| match result {
+                Ok(inverse) => {
+                    this.track_old_answer(&command, &inverse, window, cx);
+                    this.finish_record_command(&command, inverse, description, "Record editor");
+                    on_done(this, Ok(()), window, cx);
+                    this.load_dns_records(window, cx);
+                }
+                Err(e) => {
+                    if retry_queue::is_transient(&e) {
+                        this.offer_retry_later(description, command, e.to_string(), window, cx);
+                    }
+                    on_done(this, Err(e), window, cx);
+                }
+            },
+        );
+    }
+
+    /// Check a record change with the approved validator plugins, then run it
+    fn validate_and_run(
+        &self,
+        client: CloudflareClient,
+        command: RecordCommand,
+        cx: &gpui::App,
+    ) -> impl Future<Output = (RecordCommand, anyhow::Result<RecordCommand>)> + 'static {
+        let zone_name = self
+            .zones(cx)
+            .iter()
+            .find(|zone| zone.id == command.zone_id())
+            .map(|zone| zone.name.clone())
+            .unwrap_or_default();
+        let validators = self.approved_plugins();
+
+        async move {
+            let validation = match &command {
+                RecordCommand::Create { record, .. } => {
+                    plugins::validate_record(&validators, &zone_name, record).await
+                }
+                RecordCommand::Update { record, .. } => {
+                    plugins::validate_record(&validators, &zone_name, record).await
+                }
+                RecordCommand::Delete { .. } => Ok(()),
+            };
+            let result = match validation {
+                Ok(()) => command.run(&client).await,
+                Err(e) => Err(e),
+            };
+            (command, result)
+        }
+    }
+
+    /// Journal a record change that went through and add it to the undo history
+    fn finish_record_command(
+        &mut self,
+        command: &RecordCommand,
+        inverse: RecordCommand,
+        description: String,
+        actor: &str,
+    ) {
+        self.mark_local_change(command, &inverse);
+        self.add_journal_entry(JournalEntry::new(
+            command.zone_id(),
+            description.clone(),
+            actor,
+        ));
+        self.history
+            .record(description, Command::Record(Box::new(inverse)));
+    }
+
+    /// Count down until resolvers drop the answer an updated record gave before, after which
+    /// its old target can be decommissioned
+    fn track_old_answer(
+        &mut self,
+        command: &RecordCommand,
+        inverse: &RecordCommand,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let (
+            RecordCommand::Update {
+                record_id, record, ..
+            },
+            RecordCommand::Update {
+                record: previous, ..
+            },
+        ) = (command, inverse)
+        else {
+            return;
+        };
+        let Some(old_answer) = ttl_lowering::old_answer(previous, record, Utc::now()) else {
+            return;
+        };
+
+        // The countdown stops once no old answers are left
+        let counting_down = !self.old_answers.is_empty();
+        // A second change keeps the first old answer until it expires as well
+        let old_answer = match self.old_answers.remove(record_id) {
+            Some(earlier) if earlier.expires_at > old_answer.expires_at => earlier,
+            _ => old_answer,
+        };
+        self.old_answers.insert(record_id.clone(), old_answer);
+        if !counting_down {
+            self.start_old_answer_countdown(window, cx);
+        }
+    }
+
+    fn start_old_answer_countdown(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.old_answer_countdown = Some(cx.spawn_in(window, async move |this, cx| {
+            loop {
+                cx.background_executor().timer(Duration::from_secs(1)).await;
+                let running = this.update_in(cx, |this, window, cx| {
+                    let now = Utc::now();
+                    let expired: Vec<String> = this
+                        .old_answers
+                        .iter()
+                        .filter(|(_, old_answer)| old_answer.expires_at <= now)
+                        .map(|(record_id, _)| record_id.clone())
+                        .collect();
+                    for record_id in expired {
+                        if let Some(old_answer) = this.old_answers.remove(&record_id) {
+                            window.push_notification(
+                                Notification::info(format!(
+                                    "Resolvers no longer serve the old answer of {}, so its old \
+                                     target can be retired",
+                                    old_answer.record_name
+                                )),
+                                cx,
+                            );
+                        }
+                    }
+                    cx.notify();
+                    !this.old_answers.is_empty()
+                });
+                if !matches!(running, Ok(true)) {
+                    break;
+                }
+            }
+        }));
+    }
+
+    /// Offer to park a change that failed with a transient error in the retry queue
+    fn offer_retry_later(
+        &mut self,
+        description: String,
+        command: RecordCommand,
+        error: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let app = cx.entity();
+        let notification = Notification::error(format!(
+            "{} failed, possibly because of the connection",
+            description
+        ))
+        .action(move |_, _, cx| {
+            let app = app.clone();
+            let description = description.clone();
+            let command = command.clone();
+            let error = error.clone();
+            let notification = cx.entity();
+            Button::new("retry-later")
+                .label("Retry Later")
+                .on_click(move |_, window, cx| {
+                    notification.update(cx, |notification, cx| notification.dismiss(window, cx));
+                    app.update(cx, |this, cx| {
+                        this.park_record_command(
+                            description.clone(),
+                            command.clone(),
+                            error.clone(),
+                            window,
+                            cx,
+                        );
+                    });
+                })
+        });
+        window.push_notification(notification, cx);
+    }
+
+    /// Move a failed change to the retry queue, clearing the editor it was entered in
+    fn park_record_command(
+        &mut self,
+        description: String,
+        command: RecordCommand,
+        error: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let in_editor = match &command {
+            RecordCommand::Create { .. } => self.editing_record.is_none(),
+            RecordCommand::Update { record_id, .. } => self
+                .editing_record
+                .as_ref()
+                .is_some_and(|editing| &editing.id == record_id),
+            RecordCommand::Delete { .. } => false,
+        };
+        if in_editor {
+            self.editing_record = None;
+            self.clear_record_form(window, cx);
+        }
+
+        window.push_notification(
+            Notification::info(format!("{} will be retried in the background", description)),
+            cx,
+        );
+        self.retry_queue
+            .push(description, command, error, Utc::now());
+        self.error = None;
+        self.start_retry_timer(window, cx);
+        cx.notify();
+    }
+
+    /// Run due retries, checking every second until the retry queue is empty
+    fn start_retry_timer(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.retry_timer = Some(cx.spawn_in(window, async move |this, cx| {
+            loop {
+                cx.background_executor().timer(Duration::from_secs(1)).await;
+                let pending = this.update_in(cx, |this, window, cx| {
+                    for id in this.retry_queue.due(Utc::now()) {
+                        this.retry_record_command(id, window, cx);
+                    }
+                    cx.notify();
+                    !this.retry_queue.is_empty()
+                });
+                if !matches!(pending, Ok(true)) {
+                    break;
+                }
+            }
+        }));
+    }
+
+    /// Run a change of the retry queue again right away
+    pub fn retry_record_command(&mut self, id: u64, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(client) = self.writable_client(cx) else {
+            return;
+        };
+        let Some(item) = self.retry_queue.start(id) else {
+            return;
+        };
+        let run = self.validate_and_run(client, item.command, cx);
+        let description = item.description;
+
+        self.spawn_task(
+            "Retrying record change",
+            window,
+            cx,
+            |_| run,
+            move |this, (command, result), window, cx| match result {
+                Ok(inverse) => {
+                    this.retry_queue.succeeded(id);
+                    window.push_notification(
+                        Notification::success(format!("Retried: {}", description)),
+                        cx,
+                    );
+                    this.track_old_answer(&command, &inverse, window, cx);
+                    this.finish_record_command(&command, inverse, description, "Retry queue");
+                    this.load_dns_records(window, cx);
+                }
+                Err(e) => {
+                    let transient = retry_queue::is_transient(&e);
+                    this.retry_queue
+                        .failed(id, e.to_string(), transient, Utc::now());
+                }
+            },
+        );
+    }
+
+    pub fn cancel_retry(&mut self, id: u64, cx: &mut Context<Self>) {
+        self.retry_queue.cancel(id);
+        cx.notify();
+    }
+
+    /// Keep the app's own record changes from being reported as external changes
+    fn mark_local_change(&mut self, command: &RecordCommand, inverse: &RecordCommand) {
+        if let Some(record_id) = command.record_id().or(inverse.record_id()) {
+            self.local_record_changes.insert(record_id.to_string());
+        }
+    }
+
+    pub fn undo(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(entry) = self.history.pop_undo() {
+            self.run_history_entry(entry, true, window, cx);
+        }
+    }
+
+    /// Undo a specific action, as long as nothing was changed after it
+    pub fn undo_action(&mut self, id: u64, window: &mut Window, cx: &mut Context<Self>) {
+        match self.history.pop_undo_if_latest(id) {
+            Some(entry) => self.run_history_entry(entry, true, window, cx),
+            None => window.push_notification(
+                Notification::info(
+                    "Later changes were made since; undo them first from the History dialog",
+                ),
+                cx,
+            ),
+        }
+    }
+
+    /// Success notification for the action just added to the history, with a button that
+    /// undoes it, e.g. to bring back an accidentally deleted record
+    fn undoable_notification(&self, message: &'static str, cx: &mut Context<Self>) -> Notification {
+        let notification = Notification::success(message);
+        let Some(id) = self.history.undo_entries().last().map(|entry| entry.id) else {
+            return notification;
+        };
+
+        let app = cx.entity();
+        notification.action(move |_, _, cx| {
+            let app = app.clone();
+            let notification = cx.entity();
+            Button::new("undo-record-change")
+                .label("Undo")
+                .on_click(move |_, window, cx| {
+                    notification.update(cx, |notification, cx| notification.dismiss(window, cx));
+                    app.update(cx, |this, cx| this.undo_action(id, window, cx));
+                })
+        })
+    }
+
+    pub fn redo(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(entry) = self.history.pop_redo() {
+            self.run_history_entry(entry, false, window, cx);
+        }
+    }
+
+    /// Run the command of an undo (or redo) entry, moving the entry to the other stack
+    fn run_history_entry(
+        &mut self,
+        entry: HistoryEntry,
+        undo: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let push = move |history: &mut History, entry: HistoryEntry| {
+            if undo {
+                history.push_redo(entry);
+            } else {
+                history.push_undo(entry);
+            }
+        };
+        let push_back = move |history: &mut History, entry: HistoryEntry| {
+            if undo {
+                history.push_undo(entry);
+            } else {
+                history.push_redo(entry);
+            }
+        };
+
+        let id = entry.id;
+        match entry.command {
+            Command::SetAppearance(mode) => {
+                let previous = self.appearance_mode.as_str().to_string();
+                let mode = AppearanceMode::parse(&mode);
+                self.appearance_mode_select.update(cx, |state, cx| {
+                    state.set_selected_value(&mode, window, cx);
+                });
+                self.apply_appearance_mode(mode, window, cx);
+                push(
+                    &mut self.history,
+                    HistoryEntry {
+                        id,
+                        description: entry.description,
+                        command: Command::SetAppearance(previous),
+                    },
+                );
+            }
+            Command::Record(command) => {
+                let description = entry.description;
+                let Some(client) = self.writable_client(cx) else {
+                    push_back(
+                        &mut self.history,
+                        HistoryEntry {
+                            id,
+                            description,
+                            command: Command::Record(command),
+                        },
+                    );
+                    return;
+                };
+
+                self.error = None;
+                self.spawn_task(
+                    if undo { "Undoing" } else { "Redoing" },
+                    window,
+                    cx,
+                    |_| async move {
+                        let result = command.run(&client).await;
+                        (command, result)
+                    },
+                    move |this, (command, result), window, cx| match result {
+                        Ok(inverse) => {
+                            this.mark_local_change(&command, &inverse);
+                            this.add_journal_entry(JournalEntry::new(
+                                command.zone_id(),
+                                description.clone(),
+                                if undo { "Undo" } else { "Redo" },
+                            ));
+                            window.push_notification(
+                                Notification::success(format!(
+                                    "{}: {}",
+                                    if undo { "Undone" } else { "Redone" },
+                                    description
+                                )),
+                                cx,
+                            );
+                            push(
+                                &mut this.history,
+                                HistoryEntry {
+                                    id,
+                                    description,
+                                    command: Command::Record(Box::new(inverse)),
+                                },
+                            );
+                            this.load_dns_records(window, cx);
+                        }
+                        Err(e) => {
+                            this.error = Some(format!(
+                                "Failed to {} \"{}\": {}",
+                                if undo { "undo" } else { "redo" },
+                                description,
+                                e
+                            ));
+                            push_back(
+                                &mut this.history,
+                                HistoryEntry {
+                                    id,
+                                    description,
+                                    command: Command::Record(command),
+                                },
+                            );
+                        }
+                    },
+                );
+            }
+        }
+        cx.notify();
+    }
+
+    pub fn edit_record(&mut self, record: DnsRecord, window: &mut Window, cx: &mut Context<Self>) {
+        self.fill_record_form(&record, window, cx);
+        self.editing_record = Some(record);
+        self.edit_conflict = None;
+        self.start_edit_polling(window, cx);
+        cx.notify();
+    }
+
+    /// Start a new record with the fields of an existing one, e.g. to create it under another name
+    pub fn duplicate_record(
+        &mut self,
+        record: &DnsRecord,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.clear_record_form(window, cx);
+        self.fill_record_form(record, window, cx);
+        self.new_record_type_chosen = true;
+        self.error = None;
+        cx.notify();
+    }
+
+    fn fill_record_form(
+        &mut self,
+        record: &DnsRecord,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        // Find the index of the record type
+        let type_index = DnsRecordType::all()
+            .iter()
+            .position(|t| *t == record.record_type)
+            .unwrap_or(0);
+
+        self.record_type_select.update(cx, |state, cx| {
+            state.set_selected_index(Some(gpui_component::IndexPath::new(type_index)), window, cx);
+        });
+
+        self.record_name_input.update(cx, |input, cx| {
+            input.set_value(&record.name, window, cx);
+        });
+
+        let content = record.data_content();
+        self.record_content_input.update(cx, |input, cx| {
+            input.set_value(content, window, cx);
+        });
+
+        self.record_ttl_input.update(cx, |input, cx| {
+            input.set_value(record.ttl.to_string(), window, cx);
+        });
+
+        if let Some(priority) = record.priority {
+            self.record_priority_input.update(cx, |input, cx| {
+                input.set_value(priority.to_string(), window, cx);
+            });
+        } else {
+            self.record_priority_input.update(cx, |input, cx| {
+                input.set_value("", window, cx);
+            });
+        }
+
+        if let Some(comment) = &record.comment {
+            self.record_comment_input.update(cx, |input, cx| {
+                input.set_value(comment, window, cx);
+            });
+        } else {
+            self.record_comment_input.update(cx, |input, cx| {
+                input.set_value("", window, cx);
+            });
+        }
+
+        self.record_proxied = record.proxied;
+    }
+
+    /// Periodically re-fetch the record being edited to detect concurrent modifications
+    fn start_edit_polling(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(client) = self.client.clone() else {
+            return;
+        };
+        let Some(zone) = self.selected_zone(cx) else {
+            return;
+        };
+        let Some(editing) = &self.editing_record else {
+            return;
+        };
+
+        let zone_id = zone.id;
+        let record_id = editing.id.clone();
+
+        self.edit_poll_task = Some(cx.spawn_in(window, async move |this, cx| {
+            loop {
+                cx.background_executor().timer(EDIT_POLL_INTERVAL).await;
+
+                // Transient failures are ignored; the next poll will try again
+                let Ok(remote) = client.get_dns_record(&zone_id, &record_id).await else {
+                    continue;
+                };
+
+                let updated = cx.update(|_window, cx| {
+                    this.update(cx, |this, cx| {
+                        let Some(editing) = &this.editing_record else {
+                            return;
+                        };
+                        if editing.id == remote.id && editing.modified_on != remote.modified_on {
+                            this.edit_conflict = Some(remote);
+                            cx.notify();
+                        }
+                    })
+                });
+                if !matches!(updated, Ok(Ok(()))) {
+                    break;
+                }
+            }
+        }));
+    }
+
+    /// Replace the editor contents with the remote version of a conflicting record
+    pub fn load_remote_record(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(remote) = self.edit_conflict.take() {
+            self.error = None;
+            self.edit_record(remote, window, cx);
+        }
+    }
+
+    pub fn clear_record_form(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.editing_record = None;
+        self.edit_conflict = None;
+        self.edit_poll_task = None;
+        self.record_proxied = false;
+        self.new_record_type_chosen = false;
+
+        self.record_type_select.update(cx, |state, cx| {
+            state.set_selected_index(Some(gpui_component::IndexPath::new(0)), window, cx);
+        });
+        self.record_name_input.update(cx, |input, cx| {
+            input.set_value("", window, cx);
+        });
+        self.record_content_input.update(cx, |input, cx| {
+            input.set_value("", window, cx);
+        });
+        let ttl = self.default_ttl().to_string();
+        self.record_ttl_input.update(cx, |input, cx| {
+            input.set_value(ttl, window, cx);
+        });
+        self.record_priority_input.update(cx, |input, cx| {
+            input.set_value("", window, cx);
+        });
+        let comment = self.record_defaults.comment_template.clone();
+        self.record_comment_input.update(cx, |input, cx| {
+            input.set_value(comment, window, cx);
+        });
+    }
+
+    /// Move the new record form to the fields of `record_type`, or back to picking the type
+    pub fn choose_new_record_type(
+        &mut self,
+        record_type: Option<DnsRecordType>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(record_type) = record_type {
+            let type_index = DnsRecordType::all()
+                .iter()
+                .position(|t| *t == record_type)
+                .unwrap_or(0);
+            self.record_type_select.update(cx, |state, cx| {
+                state.set_selected_index(
+                    Some(gpui_component::IndexPath::new(type_index)),
+                    window,
+                    cx,
+                );
+            });
+        }
+        if let Some(record_type) = record_type {
+            self.record_proxied = self.record_defaults.proxied(record_type);
+        }
+        self.new_record_type_chosen = record_type.is_some();
+        self.error = None;
+        cx.notify();
+    }
+
+    /// DNS records matching the current search query
+    pub fn filtered_records(&self, cx: &gpui::App) -> Rc<Vec<DnsRecord>> {
+        let Some(zone) = self.selected_zone(cx) else {
+            return Rc::default();
+        };
+        let query = self.search_input.read(cx).value();
+        self.store.read(cx).filtered_records(&zone.id, &query)
+    }
+
+    /// Replace the search query, e.g. when picking a saved filter or a recent search
+    pub fn apply_search(&mut self, query: &str, window: &mut Window, cx: &mut Context<Self>) {
+        self.search_input.update(cx, |input, cx| {
+            input.set_value(query, window, cx);
+        });
+        self.remember_search(cx);
+    }
+
+    /// Number of records of each type in the selected zone, in the order of the type picker
+    pub fn record_type_counts(&self, cx: &gpui::App) -> Vec<(DnsRecordType, usize)> {
+        let records = self.dns_records(cx);
+        DnsRecordType::all()
+            .iter()
+            .map(|&record_type| {
+                let count = records
+                    .iter()
+                    .filter(|record| record.record_type == record_type)
+                    .count();
+                (record_type, count)
+            })
+            .filter(|(_, count)| *count > 0)
+            .collect()
+    }
+
+    /// Number of records in the selected zone each quick filter matches, in the order of
+    /// [`filter::QUICK_FILTERS`]
+    pub fn quick_filter_counts(&self, cx: &gpui::App) -> Vec<usize> {
+        let records = self.dns_records(cx);
+        filter::QUICK_FILTERS
+            .iter()
+            .map(|(_, term)| {
+                let query = RecordQuery::parse(term);
+                records
+                    .iter()
+                    .filter(|record| query.matches(record))
+                    .count()
+            })
+            .collect()
+    }
+
+    /// Add or remove a quick filter's term in the search query
+    pub fn toggle_quick_filter(&mut self, term: &str, window: &mut Window, cx: &mut Context<Self>) {
+        let query = self.search_input.read(cx).value().to_string();
+        let query = filter::toggle_query_term(&query, term);
+        self.search_input.update(cx, |input, cx| {
+            input.set_value(query, window, cx);
+        });
+        cx.notify();
+    }
+
+    /// Show or hide a record type by changing the `type:` term of the search query
+    pub fn toggle_type_filter(
+        &mut self,
+        record_type: DnsRecordType,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let query = self.search_input.read(cx).value().to_string();
+        let query = filter::toggle_query_type(&query, record_type.as_str());
+        self.search_input.update(cx, |input, cx| {
+            input.set_value(query, window, cx);
+        });
+        cx.notify();
+    }
+
+    /// Add the current search query to the persisted search history
+    pub fn remember_search(&mut self, cx: &mut Context<Self>) {
+        let query = self.search_input.read(cx).value().to_string();
+        filter::push_search_history(&mut self.search_history, &query);
+
+        if let Err(e) = storage::store_search_history(&self.search_history) {
+            self.error = Some(format!("Failed to save search history: {}", e));
+        }
+        cx.notify();
+    }
+
+    fn load_saved_filters(&mut self, cx: &gpui::App) {
+        self.saved_filters = self
+            .selected_zone(cx)
+            .and_then(|zone| storage::get_saved_filters(&zone.id).ok())
+            .unwrap_or_default();
+    }
+
+    fn store_saved_filters(&mut self, cx: &gpui::App) {
+        let Some(zone) = self.selected_zone(cx) else {
+            return;
+        };
+
+        if let Err(e) = storage::store_saved_filters(&zone.id, &self.saved_filters) {
+            self.error = Some(format!("Failed to save filters: {}", e));
+        }
+    }
+
+    /// Save the current search query as a named filter for the selected zone
+    pub fn save_filter(&mut self, name: String, cx: &mut Context<Self>) {
+        let query = self.search_input.read(cx).value().trim().to_string();
+        if query.is_empty() {
+            return;
+        }
+
+        // Saving under an existing name replaces that filter
+        self.saved_filters.retain(|f| f.name != name);
+        self.saved_filters.push(SavedFilter { name, query });
+        self.store_saved_filters(cx);
+        cx.notify();
+    }
+
+    pub fn delete_saved_filter(&mut self, name: &str, cx: &mut Context<Self>) {
+        self.saved_filters.retain(|f| f.name != name);
+        self.store_saved_filters(cx);
+        cx.notify();
+    }
+
+    pub fn open_save_filter_dialog(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let name_input = self.filter_name_input.clone();
+        name_input.update(cx, |input, cx| {
+            input.set_value("", window, cx);
+        });
+
+        let app = cx.entity();
+        window.open_dialog(cx, move |dialog, _, _| {
+            let name_input = name_input.clone();
+            let app = app.clone();
+
+            dialog
+                .title("Save Filter")
+                .child(Input::new(&name_input))
+                .confirm()
+                .on_ok(move |_, _, cx| {
+                    let name = name_input.read(cx).value().trim().to_string();
+                    if name.is_empty() {
+                        return false;
+                    }
+                    app.update(cx, |this, cx| this.save_filter(name, cx));
+                    true
+                })
+        });
+    }
+
+    /// Look up an address shown in a hover card, unless it was looked up before
+    pub fn load_ip_info(&mut self, ip: IpAddr, cx: &mut Context<Self>) {
+        // Failed lookups are tried again
+        if matches!(
+            self.ip_info.get(&ip),
+            Some(IpInfoState::Loading | IpInfoState::Loaded(_))
+        ) {
+            return;
+        }
+
+        self.ip_info.insert(ip, IpInfoState::Loading);
+        let client = self.http_client();
+        cx.spawn(async move |this, cx| {
+            let state = match ip_info::lookup(&client, ip).await {
+                Ok(info) => IpInfoState::Loaded(info),
+                Err(e) => IpInfoState::Failed(e.to_string()),
+            };
+            this.update(cx, |this, cx| {
+                this.ip_info.insert(ip, state);
+                cx.notify();
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    /// Follow the CNAME targets of a zone that weren't resolved yet, one at a time
+    fn resolve_cname_targets(&mut self, zone_id: &str, cx: &mut Context<Self>) {
+        let Some(cached) = self.store.read(cx).records(zone_id).cloned() else {
+            return;
+        };
+        let mut targets = Vec::new();
+        for record in cached.records.iter() {
+            if record.record_type == DnsRecordType::CNAME
+                && !self.cname_targets.contains_key(&record.content)
+            {
+                // Matched by name until the chain is resolved
+                self.cname_targets.insert(
+                    record.content.clone(),
+                    CnameTarget::unresolved(&record.content),
+                );
+                targets.push(record.content.clone());
+            }
+        }
+        if targets.is_empty() {
+            return;
+        }
+
+        let client = self.http_client();
+        cx.spawn(async move |this, cx| {
+            for target in targets {
+                let resolved = third_party::resolve(&client, &target).await;
+                let updated = this.update(cx, |this, cx| {
+                    this.cname_targets.insert(target, resolved);
+                    cx.notify();
+                });
+                if updated.is_err() {
+                    return;
+                }
+            }
+        })
+        .detach();
+    }
+
+    /// Third-party platforms the selected zone's CNAME records end at, with their record counts
+    pub fn third_party_counts(&self, cx: &gpui::App) -> Vec<(ThirdParty, usize)> {
+        let mut counts: BTreeMap<ThirdParty, usize> = BTreeMap::new();
+        for record in self.dns_records(cx).iter() {
+            if let Some(third_party) = self.cname_third_party(record) {
+                *counts.entry(third_party).or_default() += 1;
+            }
+        }
+        counts.into_iter().collect()
+    }
+
+    pub fn cname_third_party(&self, record: &DnsRecord) -> Option<ThirdParty> {
+        if record.record_type != DnsRecordType::CNAME {
+            return None;
+        }
+        match self.cname_targets.get(&record.content) {
+            Some(target) => target.third_party,
+            None => ThirdParty::detect(&record.content),
+        }
+    }
+
+    /// Selected records of the selected zone, in list order
+    pub fn selected_records(&self, cx: &gpui::App) -> Vec<DnsRecord> {
+        self.dns_records(cx)
+            .iter()
+            .filter(|record| self.selected_records.contains(&record.id))
+            .cloned()
+            .collect()
+    }
+
+    pub fn toggle_record_selection(&mut self, record_id: &str, cx: &mut Context<Self>) {
+        if !self.selected_records.remove(record_id) {
+            self.selected_records.insert(record_id.to_string());
+        }
+        cx.notify();
+    }
+
+    /// Select or deselect several records at once, e.g. all records of a name
+    pub fn set_records_selected(
+        &mut self,
+        record_ids: &[String],
+        selected: bool,
+        cx: &mut Context<Self>,
+    ) {
+        for record_id in record_ids {
+            if selected {
+                self.selected_records.insert(record_id.clone());
+            } else {
+                self.selected_records.remove(record_id);
+            }
+        }
+        cx.notify();
+    }
+
+    /// Switch the DNS list between one row per record and one row per name
+    pub fn toggle_group_by_name(&mut self, cx: &mut Context<Self>) {
+        self.group_by_name = !self.group_by_name;
+        if let Err(e) = storage::store_group_by_name(self.group_by_name) {
+            self.error = Some(format!("Failed to save list setting: {}", e));
+        }
+        cx.notify();
+    }
+
+    /// Show or hide the selected zone's nameservers on the dashboard
+    pub fn toggle_zone_details(&mut self, cx: &mut Context<Self>) {
+        self.zone_details_expanded = !self.zone_details_expanded;
+        cx.notify();
+    }
+
+    /// Show or hide the records of a name in the grouped DNS list
+    pub fn toggle_name_expanded(&mut self, name: &str, cx: &mut Context<Self>) {
+        if !self.expanded_names.remove(name) {
+            self.expanded_names.insert(name.to_string());
+        }
+        cx.notify();
+    }
+
+    /// Select all records matching the search, or clear the selection
+    pub fn select_all_records(&mut self, selected: bool, cx: &mut Context<Self>) {
+        if selected {
+            self.selected_records.extend(
+                self.filtered_records(cx)
+                    .iter()
+                    .map(|record| record.id.clone()),
+            );
+        } else {
+            self.selected_records.clear();
+        }
+        cx.notify();
+    }
+
+    /// Check what the selected records still serve, then ask for confirmation with as much
+    /// friction as is at stake and delete them as a bulk operation
+    pub fn open_bulk_delete_dialog(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(client) = self.client.clone() else {
+            return;
+        };
+        let Some(zone) = self.selected_zone(cx) else {
+            return;
+        };
+        let (locked, records): (Vec<DnsRecord>, Vec<DnsRecord>) = self
+            .selected_records(cx)
+            .into_iter()
+            .partition(|record| record.locked);
+        let (protected, records): (Vec<DnsRecord>, Vec<DnsRecord>) = records
+            .into_iter()
+            .partition(|record| self.protected_records.contains_key(&record.id));
+        if records.is_empty() {
+            if !locked.is_empty() {
+                self.error = Some(LOCKED_RECORD_ERROR.to_string());
+                cx.notify();
+            } else if let Some(record) = protected.first() {
+                self.error = Some(protected_record_error(&self.protected_records[&record.id]));
+                cx.notify();
+            }
+            return;
+        }
+
+        let mut kept = Vec::new();
+        if !locked.is_empty() {
+            kept.push(format!(
+                "{} locked record(s) are managed by Cloudflare and will be kept.",
+                locked.len()
+            ));
+        }
+        if !protected.is_empty() {
+            kept.push(format!(
+                "{} protected record(s) will be kept.",
+                protected.len()
+            ));
+        }
+        let total = self.dns_records(cx).len();
+        let since = Utc::now() - chrono::Duration::hours(24);
+        self.deletion_acknowledged = false;
+        self.deletion_confirm_input.update(cx, |input, cx| {
+            input.set_value("", window, cx);
+        });
+        self.spawn_task(
+            "Checking what the records serve",
+            window,
+            cx,
+            |_| async move {
+                let traffic = client.zone_traffic(&zone.id, since, true).await;
+                let query_counts = client.dns_query_counts(&zone.id, since).await;
+                let certificate_packs = client.list_certificate_packs(&zone.id).await;
+                let check = SafetyCheck::new(
+                    &records,
+                    total,
+                    traffic.map_err(|e| e.to_string()),
+                    query_counts.map_err(|e| e.to_string()),
+                    certificate_packs.map_err(|e| e.to_string()),
+                );
+                (zone, records, check)
+            },
+            move |this, (zone, records, mut check), window, cx| {
+                // Drop the check if another zone was opened meanwhile
+                if this
+                    .selected_zone(cx)
+                    .is_none_or(|selected| selected.id != zone.id)
+                {
+                    return;
+                }
+                check.strict = this.active_profile_settings().strict_guardrails;
+                ui::open_bulk_delete_dialog(
+                    cx.entity(),
+                    zone.name,
+                    records,
+                    kept,
+                    check,
+                    window,
+                    cx,
+                );
+            },
+        );
+    }
+
+    /// Delete records as a bulk operation, or stage their deletion
+    pub fn delete_records(
+        &mut self,
+        records: Vec<DnsRecord>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.selected_records.clear();
+        if self.staging {
+            let changes = records.into_iter().map(StagedChange::delete).collect();
+            if let Some(zone) = self.selected_zone(cx) {
+                self.stage_changes(&zone.id, changes, window, cx);
+            }
+            return;
+        }
+
+        let items: Vec<(String, BatchAction)> = records
+            .iter()
+            .map(|record| {
+                (
+                    format!("Delete {} {}", record.record_type, record.name),
+                    BatchAction::Delete {
+                        record_id: record.id.clone(),
+                    },
+                )
+            })
+            .collect();
+        self.run_batch(format!("Delete {} records", items.len()), items, window, cx);
+    }
+
+    pub fn set_deletion_acknowledged(&mut self, acknowledged: bool, cx: &mut Context<Self>) {
+        self.deletion_acknowledged = acknowledged;
+        cx.notify();
+    }
+
+    /// Open the dialog changing the TTL, proxy status or comment of the selected records
+    pub fn open_bulk_edit_dialog(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.selected_records.is_empty() {
+            return;
+        }
+
+        self.bulk_proxied = None;
+        for input in [&self.bulk_ttl_input, &self.bulk_comment_input] {
+            input.update(cx, |input, cx| input.set_value("", window, cx));
+        }
+        ui::open_bulk_edit_dialog(cx.entity(), window, cx);
+    }
+
+    /// Apply what was entered in the bulk edit dialog to the selected records, leaving out
+    /// locked and protected ones
+    pub fn apply_bulk_edit(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(zone) = self.selected_zone(cx) else {
+            return;
+        };
+        let ttl = self.bulk_ttl_input.read(cx).value().trim().to_string();
+        let ttl = if ttl.is_empty() {
+            None
+        } else {
+            match record_defaults::parse_ttl(&ttl) {
+                Ok(ttl) => Some(ttl),
+                Err(e) => {
+                    self.error = Some(e);
+                    cx.notify();
+                    return;
+                }
+            }
+        };
+        let comment = self.bulk_comment_input.read(cx).value().trim().to_string();
+        let comment = if comment.is_empty() {
+            None
+        } else {
+            let Some(comment) = self.finished_comment(&comment, Self::apply_bulk_edit, window, cx)
+            else {
+                return;
+            };
+            comment
+        };
+
+        let changes: Vec<(DnsRecord, UpdateDnsRecord)> = self
+            .selected_records(cx)
+            .into_iter()
+            .filter(|record| !record.locked && !self.protected_records.contains_key(&record.id))
+            .map(|record| {
+                let proxied = self
+                    .bulk_proxied
+                    .filter(|_| record.record_type.is_proxiable());
+                let update = UpdateDnsRecord {
+                    ttl,
+                    proxied,
+                    comment: comment.clone(),
+                    ..Default::default()
+                };
+                (record, update)
+            })
+            .filter(|(_, update)| {
+                update.ttl.is_some() || update.proxied.is_some() || update.comment.is_some()
+            })
+            .collect();
+        if changes.is_empty() {
+            return;
+        }
+
+        self.error = None;
+        self.selected_records.clear();
+        if self.staging {
+            let changes = changes
+                .into_iter()
+                .map(|(record, update)| StagedChange::update(record, update))
+                .collect();
+            self.stage_changes(&zone.id, changes, window, cx);
+            return;
+        }
+
+        let items: Vec<(String, BatchAction)> = changes
+            .into_iter()
+            .map(|(record, update)| {
+                (
+                    format!("Update {} {}", record.record_type, record.name),
+                    BatchAction::Update {
+                        record_id: record.id,
+                        record: update,
+                    },
+                )
+            })
+            .collect();
+        self.run_batch(format!("Edit {} records", items.len()), items, window, cx);
+    }
+
+    /// Save all, the matching, or the selected records of the selected zone in `format`
+    ///
+    /// Zone files of all records go through [`Self::export_zone_file`], which compares them
+    /// with the previous export if enabled.
+    pub fn export_records(
+        &mut self,
+        format: ExportFormat,
+        scope: ExportScope,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if format == ExportFormat::ZoneFile && scope == ExportScope::All {
+            self.export_zone_file(window, cx);
+            return;
+        }
+        let Some(zone) = self.selected_zone(cx) else {
+            return;
+        };
+        let records = match scope {
+            ExportScope::All => self.dns_records(cx).to_vec(),
+            ExportScope::Filtered => self.filtered_records(cx).to_vec(),
+            ExportScope::Selected => self.selected_records(cx),
+        };
+        if records.is_empty() {
+            return;
+        }
+
+        let header = format!(
+            "{} records of {} exported by Cloudflare DNS Manager on {}",
+            records.len(),
+            zone.name,
+            Local::now().format("%Y-%m-%d %H:%M %Z")
+        );
+        let contents = record_export::export(format, &zone.name, &records, &header);
+        let file_name = format!(
+            "{}{}.{}",
+            zone.name,
+            scope.file_suffix(),
+            format.extension()
+        );
+        self.save_to_file(&file_name, contents, window, cx);
+    }
+
+    pub fn toggle_watch_record(&mut self, record_id: &str, cx: &mut Context<Self>) {
+        if !self.watched_records.remove(record_id) {
+            self.watched_records.insert(record_id.to_string());
+        }
+
+        if let Err(e) = storage::store_watched_records(&self.watched_records) {
+            self.error = Some(format!("Failed to save watched records: {}", e));
+        }
+        cx.notify();
+    }
+
+    /// Protect a record from changes made in this app, or lift its protection
+    pub fn toggle_protect_record(&mut self, record_id: &str, cx: &mut Context<Self>) {
+        if self.protected_records.remove(record_id).is_none() {
+            self.protected_records
+                .insert(record_id.to_string(), "Protected in this app".to_string());
+        }
+        self.sync_control_server_protected_records();
+
+        if let Err(e) = storage::store_protected_records(&self.protected_records) {
+            self.error = Some(format!("Failed to save protected records: {}", e));
+        }
+        cx.notify();
+    }
+
+    fn notify_watched_changes(
+        &mut self,
+        changes: Vec<RecordChange>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let names: Vec<&str> = changes.iter().map(|c| c.record().name.as_str()).collect();
+        let message = format!(
+            "{} watched record(s) changed outside the app: {}",
+            changes.len(),
+            names.join(", ")
+        );
+        desktop_notification::show("Watched DNS records changed", message.clone());
+
+        self.watched_changes = changes;
+
+        let app = cx.entity();
+        window.push_notification(
+            Notification::warning(message)
+                .title("Watched records changed")
+                .autohide(false)
+                .action(move |_, _, _| {
+                    let app = app.clone();
+                    Button::new("view-watched-changes")
+                        .label("View Changes")
+                        .on_click(move |_, window, cx| {
+                            app.update(cx, |this, cx| {
+                                this.open_watched_changes_dialog(window, cx);
+                            });
+                        })
+                }),
+            cx,
+        );
+    }
+
+    pub fn open_watched_changes_dialog(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let changes = self.watched_changes.clone();
+        window.open_dialog(cx, move |dialog, _, cx| {
+            dialog
+                .title("Watched Records Changed")
+                .w(px(560.))
+                .child(ui::render_record_changes(&changes, cx))
+                .alert()
+        });
+    }
+
+    /// Start a bulk operation on the selected zone and show its progress dialog
+    pub fn run_batch(
+        &mut self,
+        title: String,
+        items: Vec<(String, BatchAction)>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(zone) = self.selected_zone(cx) else {
+            return;
+        };
+
+        let indices = (0..items.len()).collect();
+        self.batch = Some(BatchRun::new(title, zone.id, items));
+        self.run_batch_items(indices, window, cx);
+        ui::open_batch_dialog(cx.entity(), window, cx);
+    }
+
+    fn run_batch_items(
+        &mut self,
+        indices: Vec<usize>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(client) = self.writable_client(cx) else {
+            return;
+        };
+        let Some(batch) = &mut self.batch else {
+            return;
+        };
+
+        let zone_id = batch.zone_id.clone();
+        // Scripts, plugins, quick fixes and bulk actions all end up here, so protected records
+        // are skipped here rather than by each of them
+        let protected_records = &self.protected_records;
+        let actions: Vec<(usize, BatchAction)> = indices
+            .into_iter()
+            .filter_map(|index| {
+                let item = &mut batch.items[index];
+                if let Some(reason) = item
+                    .action
+                    .record_id()
+                    .and_then(|record_id| protected_records.get(record_id))
+                {
+                    item.status = BatchItemStatus::Failed(protected_record_error(reason));
+                    return None;
+                }
+                item.status = BatchItemStatus::Pending;
+                Some((index, item.action.clone()))
+            })
+            .collect();
+        batch.running = true;
+
+        let task = self.spawn_task(
+            "Running bulk operation",
+            window,
+            cx,
+            move |progress| async move {
+                let total = actions.len();
+                for (done, (index, action)) in actions.into_iter().enumerate() {
+                    progress.update_app(|this, _| {
+                        this.set_batch_item_status(index, BatchItemStatus::Running);
+                    });
+                    let status = match action.run(&client, &zone_id).await {
+                        Ok(()) => BatchItemStatus::Success,
+                        Err(e) => BatchItemStatus::Failed(e.to_string()),
+                    };
+                    progress.update_app(|this, _| this.set_batch_item_status(index, status));
+                    progress.set(done + 1, total);
+                }
+            },
+            |this, (), window, cx| {
+                this.batch_task = None;
+                if let Some(batch) = &mut this.batch {
+                    batch.running = false;
+                }
+                this.load_dns_records(window, cx);
+            },
+        );
+        self.batch_task = Some(task);
+    }
+
+    fn set_batch_item_status(&mut self, index: usize, status: BatchItemStatus) {
+        let Some(batch) = &mut self.batch else {
+            return;
+        };
+        let Some(item) = batch.items.get_mut(index) else {
+            return;
+        };
+
+        let entry = (status == BatchItemStatus::Success)
+            .then(|| JournalEntry::new(&batch.zone_id, item.label.clone(), "Bulk operation"));
+        item.status = status;
+        if let Some(entry) = entry {
+            self.add_journal_entry(entry);
+        }
+    }
+
+    /// Stop the running bulk operation; items that already completed are kept
+    pub fn cancel_batch(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(task) = self.batch_task {
+            self.cancel_task(task, cx);
+            self.load_dns_records(window, cx);
+        }
+    }
+
+    pub fn retry_failed_batch_items(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let indices = self
+            .batch
+            .as_ref()
+            .map(|batch| batch.failed_indices())
+            .unwrap_or_default();
+        if !indices.is_empty() {
+            self.run_batch_items(indices, window, cx);
+        }
+    }
+
+    pub fn export_batch_results(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(batch) = &self.batch {
+            let csv = batch.to_csv();
+            self.save_to_file("bulk-operation-results.csv", csv, window, cx);
+        }
+    }
+
+    /// Ask for a typed confirmation, listing the origins that become public, before unproxying
+    /// every proxied record of the selected zone
+    pub fn open_unproxy_all_dialog(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(zone) = self.selected_zone(cx) else {
+            return;
+        };
+        let records: Vec<DnsRecord> = self
+            .dns_records(cx)
+            .iter()
+            .filter(|record| record.proxied && !record.locked)
+            .cloned()
+            .collect();
+        if records.is_empty() {
+            window.push_notification(
+                Notification::info(format!("No records of {} are proxied", zone.name)),
+                cx,
+            );
+            return;
+        }
+
+        self.unproxy_confirm_input
+            .update(cx, |input, cx| input.set_value("", window, cx));
+        ui::open_unproxy_all_dialog(cx.entity(), zone.name, records, window, cx);
+    }
+
+    /// Unusual or deprecated setups among the selected zone's records
+    pub fn zone_lints(&self, cx: &gpui::App) -> Vec<Lint> {
+        let Some(zone) = self.selected_zone(cx) else {
+            return Vec::new();
+        };
+        lint::lint_zone(&zone.name, &self.dns_records(cx))
+    }
+
+    pub fn open_lint_dialog(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let lints = self.zone_lints(cx);
+        if lints.is_empty() {
+            return;
+        }
+        ui::open_lint_dialog(cx.entity(), lints, window, cx);
+    }
+
+    /// Turn off the proxy of the given records, remembering them so they can be proxied again
+    pub fn unproxy_records(
+        &mut self,
+        records: Vec<DnsRecord>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(zone) = self.selected_zone(cx) else {
+            return;
+        };
+
+        let ids = self.unproxied_records.entry(zone.id).or_default();
+        for record in &records {
+            if !ids.contains(&record.id) {
+                ids.push(record.id.clone());
+            }
+        }
+        if let Err(e) = storage::store_unproxied_records(&self.unproxied_records) {
+            self.error = Some(format!("Failed to save unproxied records: {}", e));
+        }
+
+        let items = records
+            .iter()
+            .map(|record| BatchAction::set_proxied(record, false))
+            .collect::<Vec<_>>();
+        self.run_batch(
+            format!("Unproxy {} records", items.len()),
+            items,
+            window,
+            cx,
+        );
+    }
+
+    /// Proxy the records of the selected zone again that were unproxied with "Unproxy All"
+    pub fn reproxy_records(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(zone) = self.selected_zone(cx) else {
+            return;
+        };
+        let Some(ids) = self.unproxied_records.remove(&zone.id) else {
+            return;
+        };
+        if let Err(e) = storage::store_unproxied_records(&self.unproxied_records) {
+            self.error = Some(format!("Failed to save unproxied records: {}", e));
+        }
+
+        // Records deleted in the meantime are skipped
+        let items = self
+            .dns_records(cx)
+            .iter()
+            .filter(|record| ids.contains(&record.id) && !record.proxied)
+            .map(|record| BatchAction::set_proxied(record, true))
+            .collect::<Vec<_>>();
+        if items.is_empty() {
+            cx.notify();
+            return;
+        }
+        self.run_batch(format!("Proxy {} records", items.len()), items, window, cx);
+    }
+
+    /// Lower the TTLs of the selected records to `ttl` ahead of a planned change, remembering
+    /// the original TTLs to restore them afterwards
+    pub fn lower_ttls(&mut self, ttl: u32, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(zone) = self.selected_zone(cx) else {
+            return;
+        };
+        let records = ttl_lowering::records_to_lower(&self.selected_records(cx), ttl);
+        if records.is_empty() {
+            window.push_notification(
+                Notification::info(format!(
+                    "The selected records are proxied or already have a TTL of {}s or less",
+                    ttl
+                )),
+                cx,
+            );
+            return;
+        }
+
+        let now = Utc::now();
+        self.lowered_ttls
+            .entry(zone.id)
+            .or_insert_with(|| LoweredTtls::new(now))
+            .add(&records, now);
+        if let Err(e) = storage::store_lowered_ttls(&self.lowered_ttls) {
+            self.error = Some(format!("Failed to save lowered TTLs: {}", e));
+        }
+        self.schedule_ttl_reminder(window, cx);
+
+        let items = records
+            .iter()
+            .map(|record| {
+                (
+                    format!("Lower TTL of {} {}", record.record_type, record.name),
+                    BatchAction::Update {
+                        record_id: record.id.clone(),
+                        record: UpdateDnsRecord {
+                            ttl: Some(ttl),
+                            ..Default::default()
+                        },
+                    },
+                )
+            })
+            .collect::<Vec<_>>();
+        self.run_batch(
+            format!("Lower TTL of {} records", items.len()),
+            items,
+            window,
+            cx,
+        );
+    }
+
+    /// Restore the TTLs the selected zone's records had before they were lowered
+    pub fn restore_ttls(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(zone) = self.selected_zone(cx) else {
+            return;
+        };
+        let Some(lowered) = self.lowered_ttls.remove(&zone.id) else {
+            return;
+        };
+        if let Err(e) = storage::store_lowered_ttls(&self.lowered_ttls) {
+            self.error = Some(format!("Failed to save lowered TTLs: {}", e));
+        }
+
+        // Records deleted or given another TTL in the meantime are skipped
+        let items = self
+            .dns_records(cx)
+            .iter()
+            .filter_map(|record| {
+                let &ttl = lowered.original.get(&record.id)?;
+                (record.ttl != ttl).then(|| {
+                    (
+                        format!("Restore TTL of {} {}", record.record_type, record.name),
+                        BatchAction::Update {
+                            record_id: record.id.clone(),
+                            record: UpdateDnsRecord {
+                                ttl: Some(ttl),
+                                ..Default::default()
+                            },
+                        },
+                    )
+                })
+            })
+            .collect::<Vec<_>>();
+        if items.is_empty() {
+            cx.notify();
+            return;
+        }
+        self.run_batch(
+            format!("Restore TTL of {} records", items.len()),
+            items,
+            window,
+            cx,
+        );
+    }
+
+    /// Wait until the original TTLs of the next zone with lowered TTLs have expired, then remind
+    /// that its planned change can be made
+    fn schedule_ttl_reminder(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(expires_at) = self
+            .lowered_ttls
+            .values()
+            .filter(|lowered| !lowered.reminded)
+            .map(|lowered| lowered.expires_at)
+            .min()
+        else {
+            self.ttl_reminder = None;
+            return;
+        };
+
+        let wait = (expires_at - Utc::now()).to_std().unwrap_or_default();
+        self.ttl_reminder = Some(cx.spawn_in(window, async move |this, cx| {
+            cx.background_executor().timer(wait).await;
+            this.update_in(cx, |this, window, cx| {
+                this.remind_expired_ttls(window, cx);
+            })
+            .ok();
+        }));
+    }
+
+    fn remind_expired_ttls(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let now = Utc::now();
+        let zones = self.zones(cx);
+        let mut zone_names = Vec::new();
+        for (zone_id, lowered) in &mut self.lowered_ttls {
+            if lowered.reminded || !lowered.expired(now) {
+                continue;
+            }
+            lowered.reminded = true;
+            if let Some(zone) = zones.iter().find(|zone| &zone.id == zone_id) {
+                zone_names.push(zone.name.clone());
+            }
+        }
+        if let Err(e) = storage::store_lowered_ttls(&self.lowered_ttls) {
+            self.error = Some(format!("Failed to save lowered TTLs: {}", e));
+        }
+        self.schedule_ttl_reminder(window, cx);
+
+        if !zone_names.is_empty() {
+            let message = format!(
+                "The old TTLs of {} have expired, so planned changes reach everyone within the \
+                 lowered TTL. Restore the TTLs once they're done.",
+                zone_names.join(", ")
+            );
+            desktop_notification::show("Ready for planned changes", message.clone());
+            window.push_notification(
+                Notification::info(message)
+                    .title("Ready for planned changes")
+                    .autohide(false),
+                cx,
+            );
+        }
+        cx.notify();
+    }
+
+    /// Ask for audit notes, then open a printable report of the selected zone
+    pub fn open_zone_report_dialog(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(zone) = self.selected_zone(cx) else {
+            return;
+        };
+
+        let notes_input = self.report_notes_input.clone();
+        let app = cx.entity();
+        window.open_dialog(cx, move |dialog, _, _| {
+            let notes_input = notes_input.clone();
+            let app = app.clone();
+
+            dialog
+                .title(format!("Print Report for {}", zone.name))
+                .child(Input::new(&notes_input))
+                .confirm()
+                .on_ok(move |_, _, cx| {
+                    let notes = notes_input.read(cx).value().to_string();
+                    app.update(cx, |this, cx| this.print_zone_report(&notes, cx));
+                    true
+                })
+        });
+    }
+
+    /// Write the selected zone's report and open it in the browser, which prints it or saves it as PDF
+    fn print_zone_report(&mut self, notes: &str, cx: &mut Context<Self>) {
+        let Some(zone) = self.selected_zone(cx) else {
+            return;
+        };
+        let records = self.dns_records(cx);
+        let store = self.store.read(cx);
+        let report = ZoneReport {
+            zone: &zone,
+            records: &records,
+            health: store.zone_health().get(&zone.id),
+            tags: self
+                .zone_tags
+                .get(&zone.id)
+                .map(Vec::as_slice)
+                .unwrap_or_default(),
+            notes,
+            generated_at: Local::now(),
+        };
+
+        let path = std::env::temp_dir()
+            .join("cloudflare-admin")
+            .join(report.file_name());
+        let html = report.to_html();
+        let result = std::fs::create_dir_all(path.parent().unwrap_or(&path))
+            .and_then(|()| std::fs::write(&path, html));
+
+        match result {
+            Ok(()) => cx.open_url(&format!("file://{}", path.display())),
+            Err(e) => self.error = Some(format!("Failed to create report: {}", e)),
+        }
+        cx.notify();
+    }
+
+    /// Ask for a location with the native save dialog and write `contents` there
+    pub fn save_to_file(
+        &mut self,
+        suggested_name: &str,
+        contents: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.save_to_file_and_then(suggested_name, contents, window, cx, |_, _, _| {});
+    }
+
+    /// Like [`Self::save_to_file`], calling `on_saved` once the file was written
+    pub fn save_to_file_and_then(
+        &mut self,
+        suggested_name: &str,
+        contents: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        on_saved: impl FnOnce(&mut Self, &mut Window, &mut Context<Self>) + 'static,
+    ) {
+        let directory = dirs::download_dir()
+            .or_else(dirs::home_dir)
+            .unwrap_or_default();
+        let path = cx.prompt_for_new_path(&directory, Some(suggested_name));
+
+        cx.spawn_in(window, async move |this, cx| {
+            let Ok(Ok(Some(path))) = path.await else {
+                return;
+            };
+            let result = std::fs::write(&path, contents);
+            cx.update(|window, cx| {
+                this.update(cx, |this, cx| {
+                    match result {
+                        Ok(()) => {
+                            window.push_notification(
+                                Notification::success(format!("Saved to {}", path.display())),
+                                cx,
+                            );
+                            on_saved(this, window, cx);
+                        }
+                        Err(e) => {
+                            this.error = Some(format!("Failed to save file: {}", e));
+                        }
+                    }
+                    cx.notify();
+                })
+                .ok();
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    /// Let the user pick a text file and pass its contents to `on_open`
+    pub fn open_file(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        on_open: impl FnOnce(&mut Self, String, &mut Window, &mut Context<Self>) + 'static,
+    ) {
+        let paths = cx.prompt_for_paths(PathPromptOptions {
+            files: true,
+            directories: false,
+            multiple: false,
+            prompt: None,
+        });
+
+        cx.spawn_in(window, async move |this, cx| {
+            let Ok(Ok(Some(paths))) = paths.await else {
+                return;
+            };
+            let Some(path) = paths.into_iter().next() else {
+                return;
+            };
+            let result = std::fs::read_to_string(&path);
+            cx.update(|window, cx| {
+                this.update(cx, |this, cx| {
+                    match result {
+                        Ok(contents) => on_open(this, contents, window, cx),
+                        Err(e) => {
+                            this.error = Some(format!("Failed to read file: {}", e));
+                        }
+                    }
+                    cx.notify();
+                })
+                .ok();
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    /// Pick a BIND zone file and preview the records it would add to the selected zone
+    pub fn import_zone_file(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.selected_zone(cx).is_none() {
+            return;
+        }
+
+        self.open_file(window, cx, |this, contents, window, cx| {
+            let Some(zone) = this.selected_zone(cx) else {
+                return;
+            };
+            match zone_file::parse(&contents, &zone.name) {
+                Ok(parsed) => {
+                    let preview = ImportPreview::new(parsed, &this.dns_records(cx));
+                    this.open_import_dialog(preview, window, cx);
+                }
+                Err(e) => {
+                    this.error = Some(format!("Failed to read zone file: {}", e));
+                }
+            }
+        });
+    }
+
+    /// Pick a CSV file and open the import wizard to map its columns to record fields
+    pub fn import_csv(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.selected_zone(cx).is_none() {
+            return;
+        }
+
+        self.open_file(
+            window,
+            cx,
+            |this, contents, window, cx| match CsvImport::new(&contents) {
+                Ok(import) => {
+                    this.csv_import = Some(import);
+                    ui::open_csv_import_dialog(cx.entity(), window, cx);
+                }
+                Err(e) => {
+                    this.error = Some(format!("Failed to read CSV file: {}", e));
+                }
+            },
+        );
+    }
+
+    /// Pick a Terraform state and offer to bring its zones' tags, notes and protected records
+    /// over
+    pub fn import_terraform_state(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.open_file(window, cx, |this, contents, window, cx| {
+            match terraform_import::parse_terraform_state(&contents, &this.zones(cx)) {
+                Ok(context) if context.is_empty() => {
+                    this.error = Some(
+                        "The Terraform state has no zones or DNS records of this account"
+                            .to_string(),
+                    );
+                }
+                Ok(context) => this.open_terraform_import_dialog(context, window, cx),
+                Err(e) => {
+                    this.error = Some(format!("Failed to read Terraform state: {}", e));
+                }
+            }
+        });
+    }
+
+    fn open_terraform_import_dialog(
+        &mut self,
+        context: ImportedContext,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let summary = format!(
+            "{} zone(s) will be tagged \"{}\" and get notes on where they are managed, and {} \
+             DNS record(s) managed by Terraform will be protected from changes made here. \
+             Existing tags and notes are kept.",
+            context.zone_tags.len(),
+            terraform_import::TERRAFORM_TAG,
+            context.protected_records.len()
+        );
+
+        let app = cx.entity();
+        window.open_dialog(cx, move |dialog, _, cx| {
+            let app = app.clone();
+            let context = context.clone();
+
+            dialog
+                .title("Import Terraform State")
+                .child(
+                    div()
+                        .text_sm()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(summary.clone()),
+                )
+                .confirm()
+                .button_props(DialogButtonProps::default().ok_text("Import"))
+                .on_ok(move |_, window, cx| {
+                    let context = context.clone();
+                    app.update(cx, |this, cx| {
+                        this.apply_terraform_import(context, window, cx)
+                    });
+                    true
+                })
+        });
+    }
+
+    fn apply_terraform_import(
+        &mut self,
+        context: ImportedContext,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let records = context.protected_records.len();
+        terraform_import::merge(
+            context,
+            &mut self.zone_tags,
+            &mut self.zone_notes,
+            &mut self.protected_records,
+        );
+        self.sync_control_server_protected_records();
+        if let Err(e) = storage::store_zone_tags(&self.zone_tags)
+            .and_then(|_| storage::store_zone_notes(&self.zone_notes))
+            .and_then(|_| storage::store_protected_records(&self.protected_records))
+        {
+            self.error = Some(format!("Failed to save imported context: {}", e));
+        } else {
+            window.push_notification(
+                Notification::success(format!(
+                    "Imported Terraform state, {} record(s) protected",
+                    records
+                )),
+                cx,
+            );
+        }
+        self.dismiss_import_offer(cx);
+        self.refresh_zone_select(window, cx);
+        cx.notify();
+    }
+
+    /// Stop offering to import context from Terraform on the portfolio page
+    pub fn dismiss_import_offer(&mut self, cx: &mut Context<Self>) {
+        self.import_offer_dismissed = true;
+        if let Err(e) = storage::store_import_offer_dismissed(true) {
+            self.error = Some(format!("Failed to save settings: {}", e));
+        }
+        cx.notify();
+    }
+
+    pub fn set_csv_column(
+        &mut self,
+        field: RecordField,
+        column: Option<usize>,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(import) = &mut self.csv_import {
+            import.set_column(field, column);
+            cx.notify();
+        }
+    }
+
+    /// Create the valid rows of the CSV import as a bulk operation
+    pub fn apply_csv_import(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(import) = self.csv_import.take() else {
+            return;
+        };
+        let items: Vec<(String, BatchAction)> = import
+            .records()
+            .into_iter()
+            .filter_map(|row| row.result.ok())
+            .map(|record| {
+                (
+                    format!("Create {} {}", record.record_type, record.name),
+                    BatchAction::Create(record),
+                )
+            })
+            .collect();
+        if !items.is_empty() {
+            self.run_batch(format!("Import {} records", items.len()), items, window, cx);
+        }
+    }
+
+    /// Save the selected zone's records as a BIND zone file
+    pub fn export_zone_file(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(zone) = self.selected_zone(cx) else {
+            return;
+        };
+        let records = self.dns_records(cx);
+        self.save_zone_file(zone, &records, window, cx);
+    }
+
+    /// Save any zone's records as a BIND zone file, fetching them unless they are cached, e.g.
+    /// from the zone picker's quick actions
+    pub fn export_zone(&mut self, zone: Zone, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(cached) = self.store.read(cx).records(&zone.id).cloned() {
+            self.save_zone_file(zone, &cached.records, window, cx);
+            return;
+        }
+        let Some(client) = self.client.clone() else {
+            return;
+        };
+
+        self.error = None;
+        self.spawn_task(
+            "Loading DNS records",
+            window,
+            cx,
+            |_| async move {
+                let result = client.list_dns_records(&zone.id).await;
+                (zone, result)
+            },
+            |this, (zone, result), window, cx| match result {
+                Ok(records) => {
+                    this.save_zone_file(zone.clone(), &records, window, cx);
+                    this.store
+                        .update(cx, |store, cx| store.set_records(zone.id, records, cx));
+                }
+                Err(e) => this.error = Some(format!("Failed to load DNS records: {}", e)),
+            },
+        );
+    }
+
+    /// Save a zone's records as a BIND zone file, listing the changes since its previous export
+    /// if enabled
+    fn save_zone_file(
+        &mut self,
+        zone: Zone,
+        records: &[DnsRecord],
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let header = format!(
+            "Zone file for {} exported by Cloudflare DNS Manager on {}",
+            zone.name,
+            Local::now().format("%Y-%m-%d %H:%M %Z")
+        );
+        let contents = zone_file::export(&zone.name, records, &header);
+        let file_name = format!("{}.zone", zone.name);
+        if !self.compare_exports {
+            self.save_to_file(&file_name, contents, window, cx);
+            return;
+        }
+
+        let previous = match export_manifest::load(&zone.id) {
+            Ok(previous) => previous,
+            Err(e) => {
+                self.error = Some(format!("Failed to read the previous export: {}", e));
+                None
+            }
+        };
+        let manifest = ExportManifest::new(records.to_vec());
+        self.save_to_file_and_then(&file_name, contents, window, cx, move |this, window, cx| {
+            if let Err(e) = export_manifest::store(&zone.id, &manifest) {
+                this.error = Some(format!("Failed to save the export manifest: {}", e));
+            }
+            if let Some(previous) = previous {
+                this.show_export_changes(&zone.name, &previous, &manifest.records, window, cx);
+            }
+        });
+    }
+
+    /// List what changed in a zone between its previous export and the one just saved
+    fn show_export_changes(
+        &mut self,
+        zone_name: &str,
+        previous: &ExportManifest,
+        records: &[DnsRecord],
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let exported_at = previous
+            .exported_at
+            .with_timezone(&Local)
+            .format("%Y-%m-%d %H:%M");
+        let changes = previous.changes(records);
+        if changes.is_empty() {
+            window.push_notification(
+                Notification::info(format!(
+                    "No changes to {} since the export on {}",
+                    zone_name, exported_at
+                )),
+                cx,
+            );
+            return;
+        }
+
+        let title = format!(
+            "{} changes to {} since the export on {}",
+            changes.len(),
+            zone_name,
+            exported_at
+        );
+        window.open_dialog(cx, move |dialog, _, cx| {
+            dialog
+                .title(title.clone())
+                .w(px(560.))
+                .child(
+                    v_flex()
+                        .max_h(px(420.))
+                        .overflow_y_scrollbar()
+                        .child(ui::render_record_changes(&changes, cx)),
+                )
+                .alert()
+        });
+    }
+
+    fn open_import_dialog(
+        &mut self,
+        preview: ImportPreview,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let app = cx.entity();
+        window.open_dialog(cx, move |dialog, _, cx| {
+            let app = app.clone();
+            let new_count = preview.new_records().count();
+            let items: Vec<(String, BatchAction)> = preview
+                .new_records()
+                .map(|record| {
+                    (
+                        format!("Create {} {}", record.record_type, record.name),
+                        BatchAction::Create(record.clone()),
+                    )
+                })
+                .collect();
+            let muted_foreground = cx.theme().muted_foreground;
+
+            dialog
+                .title(format!("Import {} records", new_count))
+                .w(px(640.))
+                .child(
+                    v_flex()
+                        .gap_2()
+                        .child(div().text_sm().text_color(muted_foreground).child(format!(
+                            "{} new, {} already in the zone. {} existing records aren't in the file and are kept.",
+                            new_count,
+                            preview.items.len() - new_count,
+                            preview.only_in_zone
+                        )))
+                        .child(
+                            v_flex()
+                                .max_h(px(360.))
+                                .overflow_y_scrollbar()
+                                .gap_1()
+                                .text_sm()
+                                .children(preview.items.iter().map(|(status, record)| {
+                                    let (marker, color) = match status {
+                                        ImportStatus::New => ("+", cx.theme().success),
+                                        ImportStatus::Exists => ("=", muted_foreground),
+                                    };
+                                    div().text_color(color).child(format!(
+                                        "{} {} {} {}{}",
+                                        marker,
+                                        record.record_type,
+                                        record.name,
+                                        record
+                                            .priority
+                                            .map(|priority| format!("{} ", priority))
+                                            .unwrap_or_default(),
+                                        record.content
+                                    ))
+                                }))
+                                .children(preview.skipped.iter().map(|skipped| {
+                                    div()
+                                        .text_color(cx.theme().warning)
+                                        .child(format!("Skipped: {}", skipped))
+                                })),
+                        ),
+                )
+                .confirm()
+                .button_props(DialogButtonProps::default().ok_text("Import"))
+                .on_ok(move |_, window, cx| {
+                    if items.is_empty() {
+                        return true;
+                    }
+                    let items = items.clone();
+                    app.update(cx, |this, cx| {
+                        this.run_batch(
+                            format!("Import {} records", items.len()),
+                            items,
+                            window,
+                            cx,
+                        );
+                    });
+                    true
+                })
+        });
+    }
+
+    /// Run the script in the console against the selected zone's records on a background
+    /// thread, so a slow one doesn't freeze the window
+    ///
+    /// The changes it collects are only applied by [`App::apply_script_changes`].
+    pub fn run_script(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(zone) = self.selected_zone(cx) else {
+            return;
+        };
+        let source = self.script_input.read(cx).value().to_string();
+        // The records are shared with the UI thread through an `Rc`
+        let records = self.dns_records(cx).to_vec();
+
+        self.script_output = None;
+        let zone_id = zone.id.clone();
+        let output =
+            cx.background_spawn(async move { scripting::run(&source, &zone.name, &records) });
+        self.spawn_task(
+            "Running script",
+            window,
+            cx,
+            |_| output,
+            move |this, output, _, cx| {
+                // Changes to another zone's records can't be applied here
+                if this
+                    .selected_zone(cx)
+                    .is_some_and(|selected| selected.id == zone_id)
+                {
+                    this.script_output = Some(output);
+                }
+            },
+        );
+    }
+
+    pub fn apply_script_changes(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(output) = self.script_output.take() else {
+            return;
+        };
+        if output.changes.is_empty() {
+            return;
+        }
+
+        self.run_batch("Script".to_string(), output.changes, window, cx);
+        cx.notify();
+    }
+
+    pub fn load_saved_script(&mut self, index: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(script) = self.saved_scripts.get(index) else {
+            return;
+        };
+        let source = script.source.clone();
+        self.script_input.update(cx, |input, cx| {
+            input.set_value(source, window, cx);
+        });
+        self.script_output = None;
+        cx.notify();
+    }
+
+    /// Save the console's script to the script library
+    pub fn save_script(&mut self, name: String, cx: &mut Context<Self>) {
+        let source = self.script_input.read(cx).value().to_string();
+
+        // Saving under an existing name replaces that script
+        self.saved_scripts.retain(|s| s.name != name);
+        self.saved_scripts.push(SavedScript { name, source });
+        self.saved_scripts.sort_by(|a, b| a.name.cmp(&b.name));
+        self.store_saved_scripts();
+        cx.notify();
+    }
+
+    pub fn delete_saved_script(&mut self, index: usize, cx: &mut Context<Self>) {
+        if index < self.saved_scripts.len() {
+            self.saved_scripts.remove(index);
+            self.store_saved_scripts();
+            cx.notify();
+        }
+    }
+
+    fn store_saved_scripts(&mut self) {
+        if let Err(e) = storage::store_saved_scripts(&self.saved_scripts) {
+            self.error = Some(format!("Failed to save scripts: {}", e));
+        }
+    }
+
+    pub fn open_save_script_dialog(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let name_input = self.script_name_input.clone();
+        name_input.update(cx, |input, cx| {
+            input.set_value("", window, cx);
+        });
+
+        let app = cx.entity();
+        window.open_dialog(cx, move |dialog, _, _| {
+            let name_input = name_input.clone();
+            let app = app.clone();
+
+            dialog
+                .title("Save Script")
+                .child(Input::new(&name_input))
+                .confirm()
+                .on_ok(move |_, _, cx| {
+                    let name = name_input.read(cx).value().trim().to_string();
+                    if name.is_empty() {
+                        return false;
+                    }
+                    app.update(cx, |this, cx| this.save_script(name, cx));
+                    true
+                })
+        });
+    }
+
+    pub fn set_control_server_enabled(
+        &mut self,
+        enabled: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if enabled {
+            let port = self
+                .control_server_port_input
+                .read(cx)
+                .value()
+                .trim()
+                .parse();
+            let Ok(port) = port else {
+                self.error = Some("Port must be a number between 1 and 65535".to_string());
+                cx.notify();
+                return;
+            };
+            self.control_server_settings.port = port;
+        }
+        self.control_server_settings.enabled = enabled;
+        self.store_control_server_settings();
+
+        if enabled {
+            self.start_control_server(window, cx);
+        } else {
+            self.stop_control_server();
+        }
+        cx.notify();
+    }
+
+    /// Replace the control server's token, invalidating the one tools currently use
+    pub fn regenerate_control_server_token(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.control_server_settings.token = control_server::generate_token();
+        self.store_control_server_settings();
+        if self.control_server.is_some() {
+            self.start_control_server(window, cx);
+        }
+        cx.notify();
+    }
+
+    /// Client for `token` sending requests along the detected proxy route, counting them under
+    /// the active profile
+    fn new_client(&self, token: String) -> CloudflareClient {
+        let client =
+            CloudflareClient::new(token).with_usage(self.api_usage.clone(), &self.active_profile);
+        match &self.proxy_route {
+            Some(route) => client.with_proxy(route),
+            None => client,
+        }
+    }
+
+    /// Client for requests besides the API's, e.g. DNS over HTTPS, along the same proxy route
+    fn http_client(&self) -> reqwest::Client {
+        match &self.proxy_route {
+            Some(route) => network_proxy::http_client(route),
+            None => reqwest::Client::new(),
+        }
+    }
+
+    /// Work out the route API requests take with the proxy setting, and switch the client to it
+    pub fn detect_proxy(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(route) = network_proxy::configured(&self.proxy_setting) {
+            self.set_proxy_route(route);
+            return;
+        }
+        let setting = self.proxy_setting.clone();
+        self.spawn_task(
+            "Detecting proxy",
+            window,
+            cx,
+            |_| async move { network_proxy::detect(&setting, &cloudflare::api_base()).await },
+            |this, route, _, _| this.set_proxy_route(route),
+        );
+    }
+
+    fn set_proxy_route(&mut self, route: ProxyRoute) {
+        if let Some(client) = self.client.clone()
+            && !client.is_demo()
+        {
+            self.set_client(Some(client.with_proxy(&route)));
+        }
+        self.proxy_route = Some(route);
+    }
+
+    pub fn set_proxy_setting(
+        &mut self,
+        setting: ProxySetting,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Err(e) = storage::store_proxy_setting(&setting) {
+            self.error = Some(format!("Failed to save proxy setting: {}", e));
+        }
+        self.proxy_setting = setting;
+        self.proxy_check = None;
+        self.detect_proxy(window, cx);
+        cx.notify();
+    }
+
+    /// Use the proxy entered in Settings for all API requests
+    pub fn set_manual_proxy(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let input = self.proxy_url_input.read(cx).value().to_string();
+        match network_proxy::parse_proxy_url(&input) {
+            Ok(url) => {
+                self.error = None;
+                self.set_proxy_setting(ProxySetting::Manual { url }, window, cx);
+            }
+            Err(e) => {
+                self.error = Some(e);
+                cx.notify();
+            }
+        }
+    }
+
+    /// Detect the proxy route again and try reaching the API along it
+    pub fn check_connectivity(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let setting = self.proxy_setting.clone();
+        self.proxy_check = None;
+        self.spawn_task(
+            "Checking connectivity",
+            window,
+            cx,
+            |_| async move {
+                let route = network_proxy::detect(&setting, &cloudflare::api_base()).await;
+                let result = CloudflareClient::new(String::new())
+                    .with_proxy(&route)
+                    .check_connectivity()
+                    .await;
+                (route, result)
+            },
+            |this, (route, result), _, _| {
+                this.proxy_check = Some(match result {
+                    Ok(elapsed) => Ok(format!(
+                        "Reached the Cloudflare API in {} ms. {}",
+                        elapsed.as_millis(),
+                        route.describe()
+                    )),
+                    Err(e) => Err(format!(
+                        "Couldn't reach the Cloudflare API: {}. {}",
+                        e,
+                        route.describe()
+                    )),
+                });
+                this.set_proxy_route(route);
+            },
+        );
+    }
+
+    /// Show the changes of the releases since the version the app last ran as
+    fn show_whats_new(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Ok(last_seen) = storage::get_last_seen_version() else {
+            return;
+        };
+        if last_seen.as_deref() == Some(changelog::CURRENT_VERSION) {
+            return;
+        }
+        if let Err(e) = storage::store_last_seen_version(changelog::CURRENT_VERSION) {
+            self.error = Some(format!("Failed to save the app version: {}", e));
+        }
+        // A new install has no earlier version to compare with
+        let Some(last_seen) = last_seen else {
+            return;
+        };
+        let releases = changelog::releases_since(&last_seen);
+        if !releases.is_empty() {
+            ui::open_changelog_dialog(cx.entity(), "What's New", releases, window, cx);
+        }
+    }
+
+    /// Show the changes of all releases
+    pub fn open_changelog(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        ui::open_changelog_dialog(cx.entity(), "Changelog", changelog::releases(), window, cx);
+    }
+
+    /// Open the page a feature of the changelog was added at
+    pub fn open_feature(&mut self, link: FeatureLink, window: &mut Window, cx: &mut Context<Self>) {
+        match link {
+            FeatureLink::Settings => {
+                self.page = Page::Settings;
+                cx.notify();
+            }
+            FeatureLink::Console => {
+                self.page = Page::Console;
+                cx.notify();
+            }
+            FeatureLink::NetworkDiagnostics => self.open_network_diagnostics(window, cx),
+            FeatureLink::ZoneSettings => self.open_zone_settings(window, cx),
+            FeatureLink::PageRules => self.open_page_rules(window, cx),
+            FeatureLink::RedirectRules => self.open_redirect_rules(window, cx),
+            FeatureLink::WafRules => self.open_waf_rules(window, cx),
+            FeatureLink::WorkerRoutes => self.open_worker_routes(window, cx),
+            FeatureLink::PagesProjects => self.open_pages_projects(window, cx),
+            FeatureLink::Tunnels => self.open_tunnels(window, cx),
+            FeatureLink::EdgeCertificates => self.open_edge_certificates(window, cx),
+            FeatureLink::Analytics => self.open_analytics(window, cx),
+        }
+    }
+
+    /// Show the network diagnostics and run them
+    pub fn open_network_diagnostics(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.page = Page::NetworkDiagnostics;
+        self.error = None;
+        self.run_network_diagnostics(window, cx);
+    }
+
+    /// Check how the API is reached over IPv4, IPv6 and the proxy route
+    pub fn run_network_diagnostics(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let route = self.proxy_route.clone();
+        self.network_diagnostics = None;
+        self.spawn_task(
+            "Running network diagnostics",
+            window,
+            cx,
+            |_| async move { network_diagnostics::run(&cloudflare::api_base(), route).await },
+            |this, diagnostics, _, _| this.network_diagnostics = Some(diagnostics),
+        );
+    }
+
+    fn store_control_server_settings(&mut self) {
+        if let Err(e) = storage::store_control_server_settings(&self.control_server_settings) {
+            self.error = Some(format!("Failed to save local server settings: {}", e));
+        }
+    }
+
+    fn start_control_server(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        // Free the port before binding it again
+        self.stop_control_server();
+
+        let settings = self.control_server_settings.clone();
+        let client = self.client.clone();
+        let (changes_tx, mut changes_rx) = tokio::sync::mpsc::unbounded_channel();
+
+        self.spawn_task(
+            "Starting local server",
+            window,
+            cx,
+            |_| async move { ControlServer::start(&settings, client, changes_tx).await },
+            move |this, result, window, cx| match result {
+                Ok(server) => {
+                    // The client may have changed while the server was starting
+                    server.set_client(this.client.clone());
+                    server.set_read_only(this.read_only());
+                    server.set_protected_records(&this.protected_records);
+                    server.set_block_duplicates(this.blocks_duplicate_records());
+                    this.control_server = Some(server);
+                    this.control_server_events =
+                        Some(cx.spawn_in(window, async move |this, cx| {
+                            while let Some(change) = changes_rx.recv().await {
+                                let updated = this.update_in(cx, |this, window, cx| {
+                                    this.on_control_server_change(change, window, cx);
+                                });
+                                if updated.is_err() {
+                                    break;
+                                }
+                            }
+                        }));
+                }
+                Err(e) => {
+                    this.error = Some(format!("Failed to start local server: {}", e));
+                }
+            },
+        );
+    }
+
+    fn stop_control_server(&mut self) {
+        self.control_server = None;
+        self.control_server_events = None;
+    }
+
+    /// Refresh the records of the selected zone when a tool changed one of them
+    fn on_control_server_change(
+        &mut self,
+        change: RecordChanged,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self
+            .selected_zone(cx)
+            .is_some_and(|zone| zone.id == change.zone_id)
+        {
+            self.local_record_changes.insert(change.record_id);
+            self.load_dns_records(window, cx);
+        }
+        self.add_journal_entry(JournalEntry::new(
+            &change.zone_id,
+            change.description,
+            "Control server",
+        ));
+    }
+
+    /// Explain how the hostname in the resolver dialog would be answered by the selected zone
+    pub fn run_resolver(&mut self, cx: &mut Context<Self>) {
+        let Some(zone) = self.selected_zone(cx) else {
+            return;
+        };
+        let host = self.resolver_host_input.read(cx).value().to_string();
+        if host.trim().is_empty() {
+            self.resolver_result = None;
+        } else {
+            let record_type = self.resolver_type_select.read(cx).selected_value().copied();
+            self.resolver_result = Some(resolver::resolve(
+                &zone.name,
+                &self.dns_records(cx),
+                &host,
+                record_type,
+            ));
+        }
+        cx.notify();
+    }
+
+    pub fn toggle_activity_panel(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.show_activity = !self.show_activity;
+        if self.show_activity {
+            self.load_activity(window, cx);
+        }
+        cx.notify();
+    }
+
+    /// Load the selected zone's audit log and journal into the activity panel
+    pub fn load_activity(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(client) = self.client.clone() else {
+            return;
+        };
+        let Some(zone) = self.selected_zone(cx) else {
+            return;
+        };
+
+        if self.activity_zone_id.as_ref() != Some(&zone.id) {
+            self.activity.clear();
+        }
+        self.activity_zone_id = Some(zone.id.clone());
+        self.activity_error = None;
+        self.spawn_task(
+            "Loading activity",
+            window,
+            cx,
+            |_| async move {
+                let audit_log = client.list_audit_logs(&zone.account.id, &zone.name).await;
+                let journal = journal::read_zone(&zone.id);
+                (zone.id, audit_log, journal)
+            },
+            |this, (zone_id, audit_log, journal), _, _| {
+                // Ignore results for a zone that is no longer selected
+                if this.activity_zone_id.as_ref() != Some(&zone_id) {
+                    return;
+                }
+
+                let audit_log = audit_log.unwrap_or_else(|e| {
+                    this.activity_error = Some(format!(
+                        "Cloudflare's audit log couldn't be loaded, so only changes made in this app are shown ({}). It requires the Account:Access: Audit Logs:Read permission.",
+                        e
+                    ));
+                    Vec::new()
+                });
+                let journal = journal.unwrap_or_else(|e| {
+                    this.error = Some(format!("Failed to read the change journal: {}", e));
+                    Vec::new()
+                });
+                this.activity = activity::merge(&audit_log, journal);
+            },
+        );
+    }
+
+    pub fn open_zone_settings(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.page = Page::ZoneSettings;
+        self.error = None;
+        self.load_zone_settings(window, cx);
+    }
+
+    /// Load the selected zone's settings for the zone settings page
+    pub fn load_zone_settings(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(client) = self.client.clone() else {
+            return;
+        };
+        let Some(zone) = self.selected_zone(cx) else {
+            return;
+        };
+
+        if self.zone_settings_zone_id.as_ref() != Some(&zone.id) {
+            self.zone_settings.clear();
+        }
+        self.zone_settings_zone_id = Some(zone.id.clone());
+        self.spawn_task(
+            "Loading zone settings",
+            window,
+            cx,
+            |_| async move {
+                let result = client.list_zone_settings(&zone.id).await;
+                (zone.id, result)
+            },
+            |this, (zone_id, result), _, _| {
+                // Ignore results for a zone that is no longer selected
+                if this.zone_settings_zone_id.as_ref() != Some(&zone_id) {
+                    return;
+                }
+                match result {
+                    Ok(settings) => this.zone_settings = settings,
+                    Err(e) => this.error = Some(format!("Failed to load zone settings: {}", e)),
+                }
+            },
+        );
+    }
+
+    pub fn set_zone_setting(
+        &mut self,
+        kind: ZoneSettingKind,
+        value: &'static str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(client) = self.writable_client(cx) else {
+            return;
+        };
+        let Some(zone_id) = self.zone_settings_zone_id.clone() else {
+            return;
+        };
+
+        let change = ZoneSetting {
+            id: kind.id().to_string(),
+            value: value.into(),
+            editable: true,
+            time_remaining: None,
+        };
+        self.error = None;
+        self.spawn_task(
+            "Updating zone settings",
+            window,
+            cx,
+            |_| async move {
+                let result = client.update_zone_settings(&zone_id, &[change]).await;
+                (zone_id, result)
+            },
+            move |this, (zone_id, result), window, cx| match result {
+                Ok(updated) => {
+                    for update in updated {
+                        if update.id == ZoneSettingKind::DevelopmentMode.id() {
+                            this.update_development_mode(zone_id.clone(), &update, window, cx);
+                        }
+                        if let Some(setting) =
+                            this.zone_settings.iter_mut().find(|s| s.id == update.id)
+                        {
+                            setting.value = update.value;
+                        }
+                    }
+                    window.push_notification(
+                        Notification::success(format!(
+                            "{} set to {}",
+                            kind.label(),
+                            kind.value_label(value)
+                        )),
+                        cx,
+                    );
+                }
+                Err(e) => {
+                    this.error = Some(format!("Failed to change {}: {}", kind.label(), e));
+                }
+            },
+        );
+    }
+
+    /// Read the selected zone's development mode for the toggle in the dashboard header
+    fn load_development_mode(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(client) = self.client.clone() else {
+            return;
+        };
+        let Some(zone) = self.selected_zone(cx) else {
+            return;
+        };
+
+        cx.spawn_in(window, async move |this, cx| {
+            let setting_id = ZoneSettingKind::DevelopmentMode.id();
+            // The toggle stays hidden if the token can't read zone settings
+            let Ok(setting) = client.get_zone_setting(&zone.id, setting_id).await else {
+                return;
+            };
+            this.update_in(cx, |this, window, cx| {
+                this.update_development_mode(zone.id, &setting, window, cx);
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    /// Turn development mode of the selected zone on or off
+    pub fn set_development_mode(&mut self, on: bool, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(client) = self.writable_client(cx) else {
+            return;
+        };
+        let Some(zone) = self.selected_zone(cx) else {
+            return;
+        };
+
+        let kind = ZoneSettingKind::DevelopmentMode;
+        let value = if on { "on" } else { "off" };
+        self.error = None;
+        self.spawn_task(
+            "Updating development mode",
+            window,
+            cx,
+            |_| async move {
+                let result = client
+                    .update_zone_setting(&zone.id, kind.id(), value.into())
+                    .await;
+                (zone.id, result)
+            },
+            move |this, (zone_id, result), window, cx| match result {
+                Ok(setting) => {
+                    if this.zone_settings_zone_id.as_ref() == Some(&zone_id)
+                        && let Some(existing) =
+                            this.zone_settings.iter_mut().find(|s| s.id == setting.id)
+                    {
+                        existing.value = setting.value.clone();
+                    }
+                    this.update_development_mode(zone_id, &setting, window, cx);
+                    window.push_notification(
+                        Notification::success(format!(
+                            "{} turned {}",
+                            kind.label(),
+                            kind.value_label(value).to_lowercase()
+                        )),
+                        cx,
+                    );
+                }
+                Err(e) => {
+                    this.error = Some(format!("Failed to change {}: {}", kind.label(), e));
+                }
+            },
+        );
+    }
+
+    /// Remember a zone's development mode from its setting, counting down while it's on
+    fn update_development_mode(
+        &mut self,
+        zone_id: String,
+        setting: &ZoneSetting,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let ends_at = zone_settings::development_mode_ends_at(setting, Utc::now());
+        self.development_mode.insert(zone_id, ends_at);
+        if ends_at.is_some() {
+            self.start_development_mode_countdown(window, cx);
+        }
+        cx.notify();
+    }
+
+    /// Re-render every second while development mode is on somewhere, turning it off when it
+    /// runs out
+    fn start_development_mode_countdown(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.development_mode_countdown = Some(cx.spawn_in(window, async move |this, cx| {
+            loop {
+                cx.background_executor().timer(Duration::from_secs(1)).await;
+                let running = this.update_in(cx, |this, _, cx| {
+                    let now = Utc::now();
+                    for ends_at in this.development_mode.values_mut() {
+                        if ends_at.is_some_and(|ends_at| ends_at <= now) {
+                            *ends_at = None;
+                        }
+                    }
+                    cx.notify();
+                    this.development_mode.values().any(Option::is_some)
+                });
+                if !matches!(running, Ok(true)) {
+                    break;
+                }
+            }
+        }));
+    }
+
+    pub fn open_page_rules(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.page = Page::PageRules;
+        self.error = None;
+        self.new_page_rule(window, cx);
+        self.load_page_rules(window, cx);
+    }
+
+    /// Load the selected zone's Page Rules for the Page Rules page
+    pub fn load_page_rules(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(client) = self.client.clone() else {
+            return;
+        };
+        let Some(zone) = self.selected_zone(cx) else {
+            return;
+        };
+
+        if self.page_rules_zone_id.as_ref() != Some(&zone.id) {
+            self.page_rules.clear();
+        }
+        self.page_rules_zone_id = Some(zone.id.clone());
+        self.spawn_task(
+            "Loading Page Rules",
+            window,
+            cx,
+            |_| async move {
+                let result = client.list_page_rules(&zone.id).await;
+                (zone.id, result)
+            },
+            |this, (zone_id, result), _, _| {
+                // Ignore results for a zone that is no longer selected
+                if this.page_rules_zone_id.as_ref() != Some(&zone_id) {
+                    return;
+                }
+                match result {
+                    Ok(mut rules) => {
+                        page_rules::sort(&mut rules);
+                        this.page_rules = rules;
+                    }
+                    Err(e) => this.error = Some(format!("Failed to load Page Rules: {}", e)),
+                }
+            },
+        );
+    }
+
+    /// Clear the Page Rule editor for a new rule
+    pub fn new_page_rule(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.page_rule_draft = PageRuleDraft::default();
+        self.set_page_rule_inputs("", "", window, cx);
+    }
+
+    pub fn edit_page_rule(&mut self, rule_id: &str, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(rule) = self.page_rules.iter().find(|rule| rule.id == rule_id) else {
+            return;
+        };
+
+        let (draft, destination) = PageRuleDraft::from_rule(rule);
+        let pattern = rule.pattern().to_string();
+        self.page_rule_draft = draft;
+        self.error = None;
+        self.set_page_rule_inputs(&pattern, &destination, window, cx);
+    }
+
+    fn set_page_rule_inputs(
+        &mut self,
+        pattern: &str,
+        destination: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.page_rule_pattern_input.update(cx, |input, cx| {
+            input.set_value(pattern, window, cx);
+        });
+        self.page_rule_destination_input.update(cx, |input, cx| {
+            input.set_value(destination, window, cx);
+        });
+        cx.notify();
+    }
+
+    /// Create the rule in the Page Rule editor, or save the changes to the edited one
+    pub fn save_page_rule(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(client) = self.writable_client(cx) else {
+            return;
+        };
+        let Some(zone_id) = self.page_rules_zone_id.clone() else {
+            return;
+        };
+
+        let pattern = self.page_rule_pattern_input.read(cx).value().to_string();
+        let destination = self
+            .page_rule_destination_input
+            .read(cx)
+            .value()
+            .to_string();
+        let rule = match self.page_rule_draft.build(&pattern, &destination) {
+            Ok(rule) => rule,
+            Err(e) => {
+                self.error = Some(e);
+                cx.notify();
+                return;
+            }
+        };
+
+        let rule_id = self.page_rule_draft.rule_id.clone();
+        self.error = None;
+        self.spawn_task(
+            "Saving Page Rule",
+            window,
+            cx,
+            |_| async move {
+                match rule_id {
+                    Some(rule_id) => client.update_page_rule(&zone_id, &rule_id, &rule).await,
+                    None => client.create_page_rule(&zone_id, &rule).await,
+                }
+            },
+            |this, result, window, cx| match result {
+                Ok(_) => {
+                    window.push_notification(Notification::success("Page Rule saved"), cx);
+                    this.new_page_rule(window, cx);
+                    this.load_page_rules(window, cx);
+                }
+                Err(e) => this.error = Some(format!("Failed to save Page Rule: {}", e)),
+            },
+        );
+    }
+
+    /// Apply the rule at `index` before or after its neighbour
+    pub fn move_page_rule(
+        &mut self,
+        index: usize,
+        up: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(client) = self.writable_client(cx) else {
+            return;
+        };
+        let Some(zone_id) = self.page_rules_zone_id.clone() else {
+            return;
+        };
+        let changes = page_rules::reorder(&self.page_rules, index, up);
+        if changes.is_empty() {
+            return;
+        }
+
+        self.error = None;
+        self.spawn_task(
+            "Reordering Page Rules",
+            window,
+            cx,
+            |_| async move {
+                for (rule_id, priority) in changes {
+                    client
+                        .set_page_rule_priority(&zone_id, &rule_id, priority)
+                        .await?;
+                }
+                anyhow::Ok(())
+            },
+            |this, result, window, cx| {
+                if let Err(e) = result {
+                    this.error = Some(format!("Failed to reorder Page Rules: {}", e));
+                }
+                this.load_page_rules(window, cx);
+            },
+        );
+    }
+
+    /// Ask before deleting a Page Rule
+    pub fn open_delete_page_rule_dialog(
+        &mut self,
+        rule_id: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(rule) = self.page_rules.iter().find(|rule| rule.id == rule_id) else {
+            return;
+        };
+
+        let pattern = rule.pattern().to_string();
+        let app = cx.entity();
+        window.open_dialog(cx, move |dialog, _, _| {
+            let app = app.clone();
+            let rule_id = rule_id.clone();
+
+            dialog
+                .title("Delete Page Rule")
+                .child(format!(
+                    "Requests to {} will no longer get the rule's actions.",
+                    pattern
+                ))
+                .confirm()
+                .button_props(DialogButtonProps::default().ok_text("Delete"))
+                .on_ok(move |_, window, cx| {
+                    app.update(cx, |this, cx| {
+                        this.delete_page_rule(rule_id.clone(), window, cx);
+                    });
+                    true
+                })
+        });
+    }
+
+    fn delete_page_rule(&mut self, rule_id: String, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(client) = self.writable_client(cx) else {
+            return;
+        };
+        let Some(zone_id) = self.page_rules_zone_id.clone() else {
+            return;
+        };
+
+        self.error = None;
+        self.spawn_task(
+            "Deleting Page Rule",
+            window,
+            cx,
+            |_| async move {
+                let result = client.delete_page_rule(&zone_id, &rule_id).await;
+                (rule_id, result)
+            },
+            |this, (rule_id, result), window, cx| match result {
+                Ok(()) => {
+                    if this.page_rule_draft.rule_id.as_ref() == Some(&rule_id) {
+                        this.new_page_rule(window, cx);
+                    }
+                    window.push_notification(Notification::success("Page Rule deleted"), cx);
+                    this.load_page_rules(window, cx);
+                }
+                Err(e) => this.error = Some(format!("Failed to delete Page Rule: {}", e)),
+            },
+        );
+    }
+
+    pub fn open_redirect_rules(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.page = Page::RedirectRules;
+        self.error = None;
+        self.new_redirect(window, cx);
+        self.load_redirect_rules(window, cx);
+    }
+
+    /// Load the selected zone's Single Redirects for the Redirect Rules page
+    pub fn load_redirect_rules(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(client) = self.client.clone() else {
+            return;
+        };
+        let Some(zone) = self.selected_zone(cx) else {
+            return;
+        };
+
+        if self.redirect_rules_zone_id.as_ref() != Some(&zone.id) {
+            self.redirect_rules.clear();
+        }
+        self.redirect_rules_zone_id = Some(zone.id.clone());
+        self.spawn_task(
+            "Loading redirects",
+            window,
+            cx,
+            |_| async move {
+                let result = client
+                    .list_phase_rules(&zone.id, redirect_rules::PHASE)
+                    .await;
+                (zone.id, result)
+            },
+            |this, (zone_id, result), _, _| {
+                // Ignore results for a zone that is no longer selected
+                if this.redirect_rules_zone_id.as_ref() != Some(&zone_id) {
+                    return;
+                }
+                match result {
+                    Ok(rules) => this.redirect_rules = rules,
+                    Err(e) => this.error = Some(format!("Failed to load redirects: {}", e)),
+                }
+            },
+        );
+    }
+
+    /// Clear the redirect editor for a new redirect
+    pub fn new_redirect(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.redirect_draft = RedirectDraft::default();
+        self.set_redirect_inputs("", "", "", window, cx);
+    }
+
+    pub fn edit_redirect(&mut self, rule_id: &str, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(rule) = self.redirect_rules.iter().find(|rule| rule.id == rule_id) else {
+            return;
+        };
+        let Some((draft, source, target)) = RedirectDraft::from_rule(rule) else {
+            self.error = Some(
+                "This rule computes its target with an expression and can only be edited in the \
+                 Cloudflare dashboard"
+                    .to_string(),
+            );
+            cx.notify();
+            return;
+        };
+
+        let description = rule.description.clone();
+        self.redirect_draft = draft;
+        self.error = None;
+        self.set_redirect_inputs(&description, &source, &target, window, cx);
+    }
+
+    fn set_redirect_inputs(
+        &mut self,
+        description: &str,
+        source: &str,
+        target: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.redirect_description_input.update(cx, |input, cx| {
+            input.set_value(description, window, cx);
+        });
+        let (pattern, expression) = if self.redirect_draft.match_expression {
+            ("", source)
+        } else {
+            (source, "")
+        };
+        self.redirect_source_input.update(cx, |input, cx| {
+            input.set_value(pattern, window, cx);
+        });
+        self.redirect_expression
+            .set_expression(expression, window, cx);
+        self.redirect_target_input.update(cx, |input, cx| {
+            input.set_value(target, window, cx);
+        });
+        cx.notify();
+    }
+
+    /// Match the redirect in the editor with an expression instead of a URL pattern, or back,
+    /// starting the expression from the pattern entered
+    pub fn set_redirect_match_expression(
+        &mut self,
+        match_expression: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if match_expression && !self.redirect_draft.match_expression {
+            let pattern = self
+                .redirect_source_input
+                .read(cx)
+                .value()
+                .trim()
+                .to_string();
+            if !pattern.is_empty() && self.redirect_expression.is_empty(cx) {
+                self.redirect_expression.set_expression(
+                    &redirect_rules::pattern_expression(&pattern),
+                    window,
+                    cx,
+                );
+            }
+        }
+        self.redirect_draft.match_expression = match_expression;
+        cx.notify();
+    }
+
+    /// Add the redirect in the editor, or save the changes to the edited one
+    pub fn save_redirect(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let description = self.redirect_description_input.read(cx).value().to_string();
+        let target = self.redirect_target_input.read(cx).value().to_string();
+        let source = if self.redirect_draft.match_expression {
+            self.redirect_expression.expression(cx)
+        } else {
+            Ok(self.redirect_source_input.read(cx).value().to_string())
+        };
+        let rule = match source
+            .and_then(|source| self.redirect_draft.build(&description, &source, &target))
+        {
+            Ok(rule) => rule,
+            Err(e) => {
+                self.error = Some(e);
+                cx.notify();
+                return;
+            }
+        };
+
+        let mut rules = self.redirect_rules.clone();
+        match rules.iter_mut().find(|existing| existing.id == rule.id) {
+            Some(existing) if !rule.id.is_empty() => *existing = rule,
+            _ => rules.push(rule),
+        }
+        self.update_redirect_rules(rules, "Saving redirect", "Redirect saved", true, window, cx);
+    }
+
+    /// Apply the redirect at `index` before or after its neighbour
+    pub fn move_redirect(
+        &mut self,
+        index: usize,
+        up: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let other = if up {
+            index.checked_sub(1)
+        } else {
+            Some(index + 1)
+        };
+        let Some(other) = other.filter(|&other| other < self.redirect_rules.len()) else {
+            return;
+        };
+
+        let mut rules = self.redirect_rules.clone();
+        rules.swap(index, other);
+        self.update_redirect_rules(
+            rules,
+            "Reordering redirects",
+            "Redirects reordered",
+            false,
+            window,
+            cx,
+        );
+    }
+
+    /// Ask before deleting a redirect
+    pub fn open_delete_redirect_dialog(
+        &mut self,
+        rule_id: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(rule) = self.redirect_rules.iter().find(|rule| rule.id == rule_id) else {
+            return;
+        };
+
+        let source = redirect_rules::source_label(rule);
+        let app = cx.entity();
+        window.open_dialog(cx, move |dialog, _, _| {
+            let app = app.clone();
+            let rule_id = rule_id.clone();
+
+            dialog
+                .title("Delete Redirect")
+                .child(format!(
+                    "Requests to {} will no longer be redirected.",
+                    source
+                ))
+                .confirm()
+                .button_props(DialogButtonProps::default().ok_text("Delete"))
+                .on_ok(move |_, window, cx| {
+                    app.update(cx, |this, cx| {
+                        let mut rules = this.redirect_rules.clone();
+                        rules.retain(|rule| rule.id != rule_id);
+                        if this.redirect_draft.rule_id.as_ref() == Some(&rule_id) {
+                            this.new_redirect(window, cx);
+                        }
+                        this.update_redirect_rules(
+                            rules,
+                            "Deleting redirect",
+                            "Redirect deleted",
+                            false,
+                            window,
+                            cx,
+                        );
+                    });
+                    true
+                })
+        });
+    }
+
+    /// Replace the zone's redirects with `rules`, which Cloudflare only takes as a whole, clearing
+    /// the editor afterwards for a saved redirect
+    fn update_redirect_rules(
+        &mut self,
+        rules: Vec<RulesetRule>,
+        task_name: &'static str,
+        success: &'static str,
+        clear_editor: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(client) = self.writable_client(cx) else {
+            return;
+        };
+        let Some(zone_id) = self.redirect_rules_zone_id.clone() else {
+            return;
+        };
+
+        self.error = None;
+        self.spawn_task(
+            task_name,
+            window,
+            cx,
+            |_| async move {
+                let result = client
+                    .update_phase_rules(&zone_id, redirect_rules::PHASE, &rules)
+                    .await;
+                (zone_id, result)
+            },
+            move |this, (zone_id, result), window, cx| {
+                if this.redirect_rules_zone_id.as_ref() != Some(&zone_id) {
+                    return;
+                }
+                match result {
+                    Ok(rules) => {
+                        this.redirect_rules = rules;
+                        window.push_notification(Notification::success(success), cx);
+                        if clear_editor {
+                            this.new_redirect(window, cx);
+                        }
+                    }
+                    Err(e) => this.error = Some(format!("Failed to update redirects: {}", e)),
+                }
+            },
+        );
+    }
+
+    pub fn open_waf_rules(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.page = Page::WafRules;
+        self.error = None;
+        self.new_waf_rule(window, cx);
+        self.load_waf_rules(window, cx);
+    }
+
+    /// Load the selected zone's WAF custom rules for the WAF page
+    pub fn load_waf_rules(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(client) = self.client.clone() else {
+            return;
+        };
+        let Some(zone) = self.selected_zone(cx) else {
+            return;
+        };
+
+        if self.waf_rules_zone_id.as_ref() != Some(&zone.id) {
+            self.waf_rules.clear();
+        }
+        self.waf_rules_zone_id = Some(zone.id.clone());
+        self.spawn_task(
+            "Loading custom rules",
+            window,
+            cx,
+            |_| async move {
+                let result = client.list_phase_rules(&zone.id, waf_rules::PHASE).await;
+                (zone.id, result)
+            },
+            |this, (zone_id, result), _, _| {
+                // Ignore results for a zone that is no longer selected
+                if this.waf_rules_zone_id.as_ref() != Some(&zone_id) {
+                    return;
+                }
+                match result {
+                    Ok(rules) => this.waf_rules = rules,
+                    Err(e) => this.error = Some(format!("Failed to load custom rules: {}", e)),
+                }
+            },
+        );
+    }
+
+    /// Clear the custom rule editor for a new rule
+    pub fn new_waf_rule(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.waf_rule_draft = WafRuleDraft::default();
+        self.set_waf_rule_inputs("", "", window, cx);
+    }
+
+    pub fn edit_waf_rule(&mut self, rule_id: &str, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(rule) = self.waf_rules.iter().find(|rule| rule.id == rule_id) else {
+            return;
+        };
+        let Some(draft) = WafRuleDraft::from_rule(rule) else {
+            self.error = Some(format!(
+                "Rules with the {} action can only be edited in the Cloudflare dashboard",
+                rule.action
+            ));
+            cx.notify();
+            return;
+        };
+
+        let description = rule.description.clone();
+        let expression = rule.expression.clone();
+        self.waf_rule_draft = draft;
+        self.error = None;
+        self.set_waf_rule_inputs(&description, &expression, window, cx);
+    }
+
+    fn set_waf_rule_inputs(
+        &mut self,
+        description: &str,
+        expression: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.waf_description_input.update(cx, |input, cx| {
+            input.set_value(description, window, cx);
+        });
+        self.waf_expression.set_expression(expression, window, cx);
+        cx.notify();
+    }
+
+    /// Add the rule in the custom rule editor, or save the changes to the edited one
+    pub fn save_waf_rule(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let description = self.waf_description_input.read(cx).value().to_string();
+        let rule = match self
+            .waf_expression
+            .expression(cx)
+            .and_then(|expression| self.waf_rule_draft.build(&description, &expression))
+        {
+            Ok(rule) => rule,
+            Err(e) => {
+                self.error = Some(e);
+                cx.notify();
+                return;
+            }
+        };
+
+        let mut rules = self.waf_rules.clone();
+        match rules.iter_mut().find(|existing| existing.id == rule.id) {
+            Some(existing) if !rule.id.is_empty() => *existing = rule,
+            _ => rules.push(rule),
+        }
+        self.update_waf_rules(
+            rules,
+            "Saving custom rule",
+            "Custom rule saved",
+            true,
+            window,
+            cx,
+        );
+    }
+
+    /// Turn the rule at `index` on or off without opening it in the editor
+    pub fn set_waf_rule_enabled(
+        &mut self,
+        index: usize,
+        enabled: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let mut rules = self.waf_rules.clone();
+        let Some(rule) = rules.get_mut(index) else {
+            return;
+        };
+        rule.enabled = enabled;
+        if self.waf_rule_draft.rule_id.as_ref() == Some(&rule.id) {
+            self.waf_rule_draft.enabled = enabled;
+        }
+
+        let success = if enabled {
+            "Custom rule enabled"
+        } else {
+            "Custom rule disabled"
+        };
+        self.update_waf_rules(rules, "Saving custom rule", success, false, window, cx);
+    }
+
+    /// Apply the rule at `index` before or after its neighbour
+    pub fn move_waf_rule(
+        &mut self,
+        index: usize,
+        up: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let other = if up {
+            index.checked_sub(1)
+        } else {
+            Some(index + 1)
+        };
+        let Some(other) = other.filter(|&other| other < self.waf_rules.len()) else {
+            return;
+        };
+
+        let mut rules = self.waf_rules.clone();
+        rules.swap(index, other);
+        self.update_waf_rules(
+            rules,
+            "Reordering custom rules",
+            "Custom rules reordered",
+            false,
+            window,
+            cx,
+        );
+    }
+
+    /// Ask before deleting a custom rule
+    pub fn open_delete_waf_rule_dialog(
+        &mut self,
+        rule_id: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(rule) = self.waf_rules.iter().find(|rule| rule.id == rule_id) else {
+            return;
+        };
+
+        let name = if rule.description.is_empty() {
+            rule.expression.clone()
+        } else {
+            rule.description.clone()
+        };
+        let app = cx.entity();
+        window.open_dialog(cx, move |dialog, _, _| {
+            let app = app.clone();
+            let rule_id = rule_id.clone();
+
+            dialog
+                .title("Delete Custom Rule")
+                .child(format!(
+                    "\"{}\" will no longer apply to requests to this zone.",
+                    name
+                ))
+                .confirm()
+                .button_props(DialogButtonProps::default().ok_text("Delete"))
+                .on_ok(move |_, window, cx| {
+                    app.update(cx, |this, cx| {
+                        let mut rules = this.waf_rules.clone();
+                        rules.retain(|rule| rule.id != rule_id);
+                        if this.waf_rule_draft.rule_id.as_ref() == Some(&rule_id) {
+                            this.new_waf_rule(window, cx);
+                        }
+                        this.update_waf_rules(
+                            rules,
+                            "Deleting custom rule",
+                            "Custom rule deleted",
+                            false,
+                            window,
+                            cx,
+                        );
+                    });
+                    true
+                })
+        });
+    }
+
+    /// Replace the zone's custom rules with `rules`, which Cloudflare only takes as a whole,
+    /// clearing the editor afterwards for a saved rule
+    fn update_waf_rules(
+        &mut self,
+        rules: Vec<RulesetRule>,
+        task_name: &'static str,
+        success: &'static str,
+        clear_editor: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(client) = self.writable_client(cx) else {
+            return;
+        };
+        let Some(zone_id) = self.waf_rules_zone_id.clone() else {
+            return;
+        };
+
+        self.error = None;
+        self.spawn_task(
+            task_name,
+            window,
+            cx,
+            |_| async move {
+                let result = client
+                    .update_phase_rules(&zone_id, waf_rules::PHASE, &rules)
+                    .await;
+                (zone_id, result)
+            },
+            move |this, (zone_id, result), window, cx| {
+                if this.waf_rules_zone_id.as_ref() != Some(&zone_id) {
+                    return;
+                }
+                match result {
+                    Ok(rules) => {
+                        this.waf_rules = rules;
+                        window.push_notification(Notification::success(success), cx);
+                        if clear_editor {
+                            this.new_waf_rule(window, cx);
+                        }
+                    }
+                    Err(e) => this.error = Some(format!("Failed to update custom rules: {}", e)),
+                }
+            },
+        );
+    }
+
+    pub fn open_worker_routes(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.page = Page::WorkerRoutes;
+        self.error = None;
+        self.new_worker_route(window, cx);
+        self.load_worker_routes(window, cx);
+    }
+
+    /// Load the selected zone's Workers routes, and the account's Workers they can run
+    pub fn load_worker_routes(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(client) = self.client.clone() else {
+            return;
+        };
+        let Some(zone) = self.selected_zone(cx) else {
+            return;
+        };
+
+        if self.worker_routes_zone_id.as_ref() != Some(&zone.id) {
+            self.worker_routes.clear();
+            self.worker_scripts.clear();
+        }
+        self.worker_routes_zone_id = Some(zone.id.clone());
+        self.spawn_task(
+            "Loading Workers routes",
+            window,
+            cx,
+            |_| async move {
+                let routes = client.list_worker_routes(&zone.id).await;
+                // Only offered to pick from, so routes are listed without them
+                let scripts = client.list_worker_scripts(&zone.account.id).await;
+                (zone.id, routes, scripts)
+            },
+            |this, (zone_id, routes, scripts), _, _| {
+                // Ignore results for a zone that is no longer selected
+                if this.worker_routes_zone_id.as_ref() != Some(&zone_id) {
+                    return;
+                }
+                if let Ok(scripts) = scripts {
+                    this.worker_scripts = scripts.into_iter().map(|script| script.id).collect();
+                    this.worker_scripts.sort();
+                }
+                match routes {
+                    Ok(mut routes) => {
+                        worker_routes::sort(&mut routes);
+                        this.worker_routes = routes;
+                    }
+                    Err(e) => this.error = Some(format!("Failed to load Workers routes: {}", e)),
+                }
+            },
+        );
+    }
+
+    /// Clear the route editor for a new route
+    pub fn new_worker_route(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.worker_route_draft = WorkerRouteDraft::default();
+        self.set_worker_route_inputs("", "", window, cx);
+    }
+
+    pub fn edit_worker_route(
+        &mut self,
+        route_id: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(route) = self.worker_routes.iter().find(|route| route.id == route_id) else {
+            return;
+        };
+
+        let pattern = route.pattern.clone();
+        let script = route.script.clone().unwrap_or_default();
+        self.worker_route_draft = WorkerRouteDraft {
+            route_id: Some(route.id.clone()),
+        };
+        self.error = None;
+        self.set_worker_route_inputs(&pattern, &script, window, cx);
+    }
+
+    fn set_worker_route_inputs(
+        &mut self,
+        pattern: &str,
+        script: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.worker_route_pattern_input.update(cx, |input, cx| {
+            input.set_value(pattern, window, cx);
+        });
+        self.worker_route_script_input.update(cx, |input, cx| {
+            input.set_value(script, window, cx);
+        });
+        cx.notify();
+    }
+
+    /// Create the route in the route editor, or save the changes to the edited one
+    pub fn save_worker_route(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(client) = self.writable_client(cx) else {
+            return;
+        };
+        let Some(zone) = self.selected_zone(cx) else {
+            return;
+        };
+        if self.worker_routes_zone_id.as_ref() != Some(&zone.id) {
+            return;
+        }
+
+        let pattern = self.worker_route_pattern_input.read(cx).value().to_string();
+        let script = self.worker_route_script_input.read(cx).value().to_string();
+        let route = match self.worker_route_draft.build(&pattern, &script, &zone.name) {
+            Ok(route) => route,
+            Err(e) => {
+                self.error = Some(e);
+                cx.notify();
+                return;
+            }
+        };
+
+        let route_id = self.worker_route_draft.route_id.clone();
+        self.error = None;
+        self.spawn_task(
+            "Saving Workers route",
+            window,
+            cx,
+            |_| async move {
+                match route_id {
+                    Some(route_id) => {
+                        client
+                            .update_worker_route(&zone.id, &route_id, &route)
+                            .await
+                    }
+                    None => client.create_worker_route(&zone.id, &route).await,
+                }
+            },
+            |this, result, window, cx| match result {
+                Ok(_) => {
+                    window.push_notification(Notification::success("Workers route saved"), cx);
+                    this.new_worker_route(window, cx);
+                    this.load_worker_routes(window, cx);
+                }
+                Err(e) => this.error = Some(format!("Failed to save Workers route: {}", e)),
+            },
+        );
+    }
+
+    /// Ask before deleting a Workers route
+    pub fn open_delete_worker_route_dialog(
+        &mut self,
+        route_id: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(route) = self.worker_routes.iter().find(|route| route.id == route_id) else {
+            return;
+        };
+
+        let message = match &route.script {
+            Some(script) => format!(
+                "Requests to {} will no longer run the Worker {}.",
+                route.pattern, script
+            ),
+            None => format!(
+                "Workers of other routes matching {} will run on its requests again.",
+                route.pattern
+            ),
+        };
+        let app = cx.entity();
+        window.open_dialog(cx, move |dialog, _, _| {
+            let app = app.clone();
+            let route_id = route_id.clone();
+
+            dialog
+                .title("Delete Workers Route")
+                .child(message.clone())
+                .confirm()
+                .button_props(DialogButtonProps::default().ok_text("Delete"))
+                .on_ok(move |_, window, cx| {
+                    app.update(cx, |this, cx| {
+                        this.delete_worker_route(route_id.clone(), window, cx);
+                    });
+                    true
+                })
+        });
+    }
+
+    fn delete_worker_route(
+        &mut self,
+        route_id: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(client) = self.writable_client(cx) else {
+            return;
+        };
+        let Some(zone_id) = self.worker_routes_zone_id.clone() else {
+            return;
+        };
+
+        self.error = None;
+        self.spawn_task(
+            "Deleting Workers route",
+            window,
+            cx,
+            |_| async move {
+                let result = client.delete_worker_route(&zone_id, &route_id).await;
+                (route_id, result)
+            },
+            |this, (route_id, result), window, cx| match result {
+                Ok(()) => {
+                    if this.worker_route_draft.route_id.as_ref() == Some(&route_id) {
+                        this.new_worker_route(window, cx);
+                    }
+                    window.push_notification(Notification::success("Workers route deleted"), cx);
+                    this.load_worker_routes(window, cx);
+                }
+                Err(e) => this.error = Some(format!("Failed to delete Workers route: {}", e)),
+            },
+        );
+    }
+
+    /// Show the Pages projects of the selected zone's account
+    pub fn open_pages_projects(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.page = Page::PagesProjects;
+        self.error = None;
+        self.load_pages_projects(window, cx);
+    }
+
+    /// Load the Pages projects of the selected zone's account, and the deployments of the
+    /// expanded project
+    pub fn load_pages_projects(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(client) = self.client.clone() else {
+            return;
+        };
+        let Some(zone) = self.selected_zone(cx) else {
+            return;
+        };
+
+        let account_id = zone.account.id;
+        if self.pages_account_id.as_ref() != Some(&account_id) {
+            self.pages_projects.clear();
+            self.expanded_pages_project = None;
+            self.pages_deployments.clear();
+        }
+        self.pages_account_id = Some(account_id.clone());
+        self.spawn_task(
+            "Loading Pages projects",
+            window,
+            cx,
+            |_| async move {
+                let result = client.list_pages_projects(&account_id).await;
+                (account_id, result)
+            },
+            |this, (account_id, result), window, cx| {
+                // Ignore results for an account that is no longer selected
+                if this.pages_account_id.as_ref() != Some(&account_id) {
+                    return;
+                }
+                match result {
+                    Ok(mut projects) => {
+                        projects.sort_by(|a, b| a.name.cmp(&b.name));
+                        this.pages_projects = projects;
+                        this.load_pages_deployments(window, cx);
+                    }
+                    Err(e) => this.error = Some(format!("Failed to load Pages projects: {}", e)),
+                }
+            },
+        );
+    }
+
+    /// List the deployments of a project, or hide them if they are listed
+    pub fn toggle_pages_project(
+        &mut self,
+        project: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.pages_deployments.clear();
+        if self.expanded_pages_project.as_ref() == Some(&project) {
+            self.expanded_pages_project = None;
+            cx.notify();
+            return;
+        }
+        self.expanded_pages_project = Some(project);
+        self.load_pages_deployments(window, cx);
+    }
+
+    fn load_pages_deployments(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(client) = self.client.clone() else {
+            return;
+        };
+        let (Some(account_id), Some(project)) = (
+            self.pages_account_id.clone(),
+            self.expanded_pages_project.clone(),
+        ) else {
+            return;
+        };
+
+        self.spawn_task(
+            "Loading deployments",
+            window,
+            cx,
+            |_| async move {
+                let result = client.list_pages_deployments(&account_id, &project).await;
+                (project, result)
+            },
+            |this, (project, result), _, _| {
+                if this.expanded_pages_project.as_ref() != Some(&project) {
+                    return;
+                }
+                match result {
+                    Ok(deployments) => this.pages_deployments = deployments,
+                    Err(e) => this.error = Some(format!("Failed to load deployments: {}", e)),
+                }
+            },
+        );
+    }
+
+    /// Run a deployment of a Pages project again
+    pub fn retry_pages_deployment(
+        &mut self,
+        project: String,
+        deployment_id: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(client) = self.writable_client(cx) else {
+            return;
+        };
+        let Some(account_id) = self.pages_account_id.clone() else {
+            return;
+        };
+
+        self.error = None;
+        self.spawn_task(
+            "Retrying deployment",
+            window,
+            cx,
+            |_| async move {
+                client
+                    .retry_pages_deployment(&account_id, &project, &deployment_id)
+                    .await
+            },
+            |this, result, window, cx| match result {
+                Ok(_) => {
+                    window.push_notification(Notification::success("Deployment started"), cx);
+                    this.load_pages_projects(window, cx);
+                }
+                Err(e) => this.error = Some(format!("Failed to retry deployment: {}", e)),
+            },
+        );
+    }
+
+    /// Ask before deleting a deployment of the expanded Pages project
+    pub fn open_delete_pages_deployment_dialog(
+        &mut self,
+        deployment_id: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(project) = self.expanded_pages_project.clone() else {
+            return;
+        };
+        let Some(deployment) = self
+            .pages_deployments
+            .iter()
+            .find(|deployment| deployment.id == deployment_id)
+        else {
+            return;
+        };
+
+        let url = deployment.url.clone();
+        let app = cx.entity();
+        window.open_dialog(cx, move |dialog, _, _| {
+            let app = app.clone();
+            let project = project.clone();
+            let deployment_id = deployment_id.clone();
+
+            dialog
+                .title("Delete Deployment")
+                .child(format!(
+                    "{} will stop serving this deployment of {}.",
+                    url, project
+                ))
+                .confirm()
+                .button_props(DialogButtonProps::default().ok_text("Delete"))
+                .on_ok(move |_, window, cx| {
+                    app.update(cx, |this, cx| {
+                        this.delete_pages_deployment(
+                            project.clone(),
+                            deployment_id.clone(),
+                            window,
+                            cx,
+                        );
+                    });
+                    true
+                })
+        });
+    }
+
+    fn delete_pages_deployment(
+        &mut self,
+        project: String,
+        deployment_id: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(client) = self.writable_client(cx) else {
+            return;
+        };
+        let Some(account_id) = self.pages_account_id.clone() else {
+            return;
+        };
+
+        self.error = None;
+        self.spawn_task(
+            "Deleting deployment",
+            window,
+            cx,
+            |_| async move {
+                client
+                    .delete_pages_deployment(&account_id, &project, &deployment_id)
+                    .await
+            },
+            |this, result, window, cx| match result {
+                Ok(()) => {
+                    window.push_notification(Notification::success("Deployment deleted"), cx);
+                    this.load_pages_projects(window, cx);
+                }
+                Err(e) => this.error = Some(format!("Failed to delete deployment: {}", e)),
+            },
+        );
+    }
+
+    /// Show the Cloudflare Tunnels of the selected zone's account
+    pub fn open_tunnels(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.page = Page::Tunnels;
+        self.error = None;
+        self.load_tunnels(window, cx);
+    }
+
+    /// Load the tunnels of the selected zone's account with the ingress rules of each
+    pub fn load_tunnels(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(client) = self.client.clone() else {
+            return;
+        };
+        let Some(zone) = self.selected_zone(cx) else {
+            return;
+        };
+
+        let account_id = zone.account.id;
+        if self.tunnels_account_id.as_ref() != Some(&account_id) {
+            self.tunnels.clear();
+            self.tunnel_ingress.clear();
+        }
+        self.tunnels_account_id = Some(account_id.clone());
+        self.spawn_task(
+            "Loading tunnels",
+            window,
+            cx,
+            |_| async move {
+                let result = async {
+                    let tunnels = client.list_tunnels(&account_id).await?;
+                    let mut ingress = HashMap::new();
+                    for tunnel in &tunnels {
+                        // Tunnels configured with a local file have no configuration to get
+                        if let Ok(rules) = client.tunnel_ingress(&account_id, &tunnel.id).await {
+                            ingress.insert(tunnel.id.clone(), rules);
+                        }
+                    }
+                    anyhow::Ok((tunnels, ingress))
+                }
+                .await;
+                (account_id, result)
+            },
+            |this, (account_id, result), _, _| {
+                // Ignore results for an account that is no longer selected
+                if this.tunnels_account_id.as_ref() != Some(&account_id) {
+                    return;
+                }
+                match result {
+                    Ok((mut tunnels, ingress)) => {
+                        tunnels.sort_by(|a, b| a.name.cmp(&b.name));
+                        this.tunnels = tunnels;
+                        this.tunnel_ingress = ingress;
+                    }
+                    Err(e) => this.error = Some(format!("Failed to load tunnels: {}", e)),
+                }
+            },
+        );
+    }
+
+    /// Show the edge certificates of the selected zone
+    pub fn open_edge_certificates(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.page = Page::EdgeCertificates;
+        self.error = None;
+        self.load_edge_certificates(window, cx);
+    }
+
+    /// Load the selected zone's certificate packs and whether Universal SSL is on
+    pub fn load_edge_certificates(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(client) = self.client.clone() else {
+            return;
+        };
+        let Some(zone) = self.selected_zone(cx) else {
+            return;
+        };
+
+        if self.certificate_packs_zone_id.as_ref() != Some(&zone.id) {
+            self.certificate_packs.clear();
+            self.universal_ssl = None;
+        }
+        self.certificate_packs_zone_id = Some(zone.id.clone());
+        self.spawn_task(
+            "Loading edge certificates",
+            window,
+            cx,
+            |_| async move {
+                let result = async {
+                    let packs = client.list_certificate_packs(&zone.id).await?;
+                    let universal_ssl = client.get_universal_ssl(&zone.id).await?;
+                    anyhow::Ok((packs, universal_ssl.enabled))
+                }
+                .await;
+                (zone.id, result)
+            },
+            |this, (zone_id, result), _, _| {
+                // Ignore results for a zone that is no longer selected
+                if this.certificate_packs_zone_id.as_ref() != Some(&zone_id) {
+                    return;
+                }
+                match result {
+                    Ok((packs, universal_ssl)) => {
+                        this.certificate_packs = packs;
+                        this.universal_ssl = Some(universal_ssl);
+                    }
+                    Err(e) => this.error = Some(format!("Failed to load edge certificates: {}", e)),
+                }
+            },
+        );
+    }
+
+    /// Get a pending certificate pack again to see how its validation is going
+    pub fn refresh_certificate_pack(
+        &mut self,
+        pack_id: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(client) = self.client.clone() else {
+            return;
+        };
+        let Some(zone) = self.selected_zone(cx) else {
+            return;
+        };
+
+        self.spawn_task(
+            "Checking certificate validation",
+            window,
+            cx,
+            |_| async move {
+                let result = client.get_certificate_pack(&zone.id, &pack_id).await;
+                (zone.id, result)
+            },
+            |this, (zone_id, result), _, _| {
+                if this.certificate_packs_zone_id.as_ref() != Some(&zone_id) {
+                    return;
+                }
+                match result {
+                    Ok(pack) => {
+                        if let Some(existing) =
+                            this.certificate_packs.iter_mut().find(|p| p.id == pack.id)
+                        {
+                            *existing = pack;
+                        }
+                    }
+                    Err(e) => this.error = Some(format!("Failed to get certificate pack: {}", e)),
+                }
+            },
+        );
+    }
+
+    /// Turn Universal SSL on, or ask for confirmation before turning it off, since that
+    /// removes the certificate visitors are served
+    pub fn toggle_universal_ssl(
+        &mut self,
+        enabled: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if enabled {
+            self.set_universal_ssl(true, window, cx);
+            return;
+        }
+
+        let app = cx.entity();
+        window.open_dialog(cx, move |dialog, _, cx| {
+            let app = app.clone();
+
+            dialog
+                .title("Turn off Universal SSL?")
+                .child(
+                    div()
+                        .text_sm()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(
+                            "The Universal SSL certificate will be removed. Unless another \
+                             certificate covers its hostnames, visitors will get TLS errors. \
+                             Turning it back on issues a new certificate, which can take a while.",
+                        ),
+                )
+                .confirm()
+                .button_props(
+                    DialogButtonProps::default()
+                        .ok_text("Turn Off")
+                        .ok_variant(ButtonVariant::Danger),
+                )
+                .on_ok(move |_, window, cx| {
+                    app.update(cx, |this, cx| this.set_universal_ssl(false, window, cx));
+                    true
+                })
+        });
+    }
+
+    fn set_universal_ssl(&mut self, enabled: bool, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(client) = self.writable_client(cx) else {
+            return;
+        };
+        let Some(zone) = self.selected_zone(cx) else {
+            return;
+        };
+
+        self.error = None;
+        self.spawn_task(
+            "Updating Universal SSL",
+            window,
+            cx,
+            move |_| async move {
+                let result = client.set_universal_ssl(&zone.id, enabled).await;
+                (zone.id, result)
+            },
+            |this, (zone_id, result), window, cx| match result {
+                Ok(setting) => {
+                    window.push_notification(
+                        Notification::success(if setting.enabled {
+                            "Universal SSL turned on"
+                        } else {
+                            "Universal SSL turned off"
+                        }),
+                        cx,
+                    );
+                    if this.certificate_packs_zone_id.as_ref() == Some(&zone_id) {
+                        this.universal_ssl = Some(setting.enabled);
+                        this.load_edge_certificates(window, cx);
+                    }
+                }
+                Err(e) => this.error = Some(format!("Failed to change Universal SSL: {}", e)),
+            },
+        );
+    }
+
+    /// Show the Analytics page of the selected zone
+    pub fn open_analytics(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.page = Page::Analytics;
+        self.error = None;
+        self.load_analytics(window, cx);
+    }
+
+    pub fn set_analytics_range(
+        &mut self,
+        range: TimeRange,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.analytics_range == range {
+            return;
+        }
+        self.analytics_range = range;
+        // Hours and days don't mix in one chart
+        self.traffic.clear();
+        self.dns_query_counts.clear();
+        self.load_analytics(window, cx);
+    }
+
+    /// Load the selected zone's traffic and DNS queries over the chosen range for the Analytics
+    /// page
+    pub fn load_analytics(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(client) = self.client.clone() else {
+            return;
+        };
+        let Some(zone) = self.selected_zone(cx) else {
+            return;
+        };
+
+        if self.analytics_zone_id.as_ref() != Some(&zone.id) {
+            self.traffic.clear();
+            self.dns_query_counts.clear();
+        }
+        self.analytics_zone_id = Some(zone.id.clone());
+        let range = self.analytics_range;
+        let since = range.since(Utc::now());
+        self.spawn_task(
+            "Loading analytics",
+            window,
+            cx,
+            {
+                let client = client.clone();
+                let zone_id = zone.id.clone();
+                move |_| async move {
+                    let result = client.zone_traffic(&zone_id, since, range.hourly()).await;
+                    (zone_id, result)
+                }
+            },
+            move |this, (zone_id, result), _, _| {
+                // Ignore results for a zone or range that is no longer shown
+                if this.analytics_zone_id.as_ref() != Some(&zone_id)
+                    || this.analytics_range != range
+                {
+                    return;
+                }
+                match result {
+                    Ok(traffic) => this.traffic = traffic,
+                    Err(e) => this.error = Some(format!("Failed to load analytics: {}", e)),
+                }
+            },
+        );
+        self.spawn_task(
+            "Loading DNS analytics",
+            window,
+            cx,
+            move |_| async move {
+                let result = client.dns_query_counts(&zone.id, since).await;
+                (zone.id, result)
+            },
+            move |this, (zone_id, result), _, _| {
+                if this.analytics_zone_id.as_ref() != Some(&zone_id)
+                    || this.analytics_range != range
+                {
+                    return;
+                }
+                match result {
+                    Ok(counts) => this.dns_query_counts = counts,
+                    Err(e) => this.error = Some(format!("Failed to load DNS analytics: {}", e)),
+                }
+            },
+        );
+    }
+
+    pub fn set_purge_target(
+        &mut self,
+        target: PurgeTarget,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.purge_target = target;
+        self.purge_input.update(cx, |input, cx| {
+            input.set_placeholder(target.placeholder(), window, cx);
+        });
+        cx.notify();
+    }
+
+    /// Confirm purging the selected zone's whole cache, or the items entered for a targeted purge
+    pub fn open_purge_cache_dialog(
+        &mut self,
+        everything: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(zone) = self.selected_zone(cx) else {
+            return;
+        };
+        let purge = if everything {
+            CachePurge::Everything
+        } else {
+            match self.purge_target.parse(&self.purge_input.read(cx).value()) {
+                Ok(purge) => purge,
+                Err(e) => {
+                    self.error = Some(e);
+                    cx.notify();
+                    return;
+                }
+            }
+        };
+
+        self.confirm_purge_cache(zone, purge, window, cx);
+    }
+
+    /// Confirm purging everything cached for any zone, e.g. from the zone picker's quick actions
+    pub fn open_purge_zone_cache_dialog(
+        &mut self,
+        zone: Zone,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.confirm_purge_cache(zone, CachePurge::Everything, window, cx);
+    }
+
+    fn confirm_purge_cache(
+        &mut self,
+        zone: Zone,
+        purge: CachePurge,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.error = None;
+        self.purge_confirm_input
+            .update(cx, |input, cx| input.set_value("", window, cx));
+        ui::open_purge_cache_dialog(cx.entity(), zone, purge, window, cx);
+    }
+
+    pub fn purge_cache(
+        &mut self,
+        zone: Zone,
+        purge: CachePurge,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(client) = self.writable_client(cx) else {
+            return;
+        };
+
+        self.spawn_task(
+            "Purging cache",
+            window,
+            cx,
+            move |_| async move {
+                let result = client.purge_cache(&zone.id, &purge).await;
+                (zone.name, purge, result)
+            },
+            |this, (zone_name, purge, result), window, cx| match result {
+                Ok(()) => {
+                    let message = match cache_purge::purge_items(&purge) {
+                        None => format!("Purged everything cached for {}", zone_name),
+                        Some(items) => {
+                            this.purge_input
+                                .update(cx, |input, cx| input.set_value("", window, cx));
+                            format!("Purged {} cached items of {}", items.len(), zone_name)
+                        }
+                    };
+                    window.push_notification(Notification::success(message), cx);
+                }
+                Err(e) => this.error = Some(format!("Failed to purge cache: {}", e)),
+            },
+        );
+    }
+
+    /// Record a change made through the app in the local journal and the activity panel
+    fn add_journal_entry(&mut self, entry: JournalEntry) {
+        // Changes to demo data never reach Cloudflare, so they stay out of the journal
+        if !self.is_demo()
+            && let Err(e) = journal::append(&entry)
+        {
+            self.error = Some(format!("Failed to write to the change journal: {}", e));
+        }
+        if self.activity_zone_id.as_ref() == Some(&entry.zone_id) {
+            self.activity.insert(0, entry.into());
+        }
+    }
+
+    /// Look for plugins again, e.g. after one was installed
+    pub fn reload_plugins(&mut self, cx: &mut Context<Self>) {
+        (self.plugins, self.plugin_errors) = plugins::discover();
+        cx.notify();
+    }
+
+    /// Whether the user approved the plugin's current manifest
+    pub fn is_plugin_approved(&self, plugin: &Plugin) -> bool {
+        self.plugin_approvals.get(&plugin.id) == Some(&plugin.fingerprint())
+    }
+
+    pub fn approved_plugins(&self) -> Vec<Plugin> {
+        self.plugins
+            .iter()
+            .filter(|plugin| self.is_plugin_approved(plugin))
+            .cloned()
+            .collect()
+    }
+
+    fn set_plugin_approved(&mut self, plugin: &Plugin, approved: bool, cx: &mut Context<Self>) {
+        if approved {
+            self.plugin_approvals
+                .insert(plugin.id.clone(), plugin.fingerprint());
+        } else {
+            self.plugin_approvals.remove(&plugin.id);
+        }
+        if let Err(e) = storage::store_plugin_approvals(&self.plugin_approvals) {
+            self.error = Some(format!("Failed to save plugin approvals: {}", e));
+        }
+        cx.notify();
+    }
+
+    pub fn revoke_plugin(&mut self, index: usize, cx: &mut Context<Self>) {
+        if let Some(plugin) = self.plugins.get(index).cloned() {
+            self.set_plugin_approved(&plugin, false, cx);
+        }
+    }
+
+    /// Ask the user to grant a plugin the permissions listed in its manifest
+    pub fn open_plugin_approval_dialog(
+        &mut self,
+        index: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(plugin) = self.plugins.get(index).cloned() else {
+            return;
+        };
+        let permissions = plugin.permissions();
+
+        let app = cx.entity();
+        window.open_dialog(cx, move |dialog, _, cx| {
+            let app = app.clone();
+            let plugin = plugin.clone();
+
+            dialog
+                .title(format!("Allow {}?", plugin.manifest.name))
+                .child(
+                    v_flex()
+                        .gap_2()
+                        .text_sm()
+                        .when(!plugin.manifest.description.is_empty(), |this| {
+                            this.child(
+                                div()
+                                    .text_color(cx.theme().muted_foreground)
+                                    .child(plugin.manifest.description.clone()),
+                            )
+                        })
+                        .child("Once allowed, this plugin can:")
+                        .children(
+                            permissions
+                                .iter()
+                                .map(|permission| div().child(format!("• {}", permission))),
+                        ),
+                )
+                .confirm()
+                .on_ok(move |_, _, cx| {
+                    app.update(cx, |this, cx| this.set_plugin_approved(&plugin, true, cx));
+                    true
+                })
+        });
+    }
+
+    /// Run a plugin's transformation on the selected zone and review the changes it proposes
+    pub fn run_plugin_transform(
+        &mut self,
+        plugin: Plugin,
+        transform_index: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(zone) = self.selected_zone(cx) else {
+            return;
+        };
+        let Some(transform) = plugin.manifest.transforms.get(transform_index).cloned() else {
+            return;
+        };
+        let records = self.dns_records(cx);
+
+        self.error = None;
+        self.spawn_task(
+            "Running plugin",
+            window,
+            cx,
+            |_| async move {
+                let result = plugin.transform(&transform.id, &zone.name, &records).await;
+                (transform, records, result)
+            },
+            |this, (transform, records, result), window, cx| match result {
+                Ok(changes) if changes.is_empty() => {
+                    window.push_notification(
+                        Notification::info(format!("{} proposed no changes", transform.name)),
+                        cx,
+                    );
+                }
+                Ok(changes) => {
+                    let items = changes
+                        .into_iter()
+                        .map(|change| change.into_batch_item(&records))
+                        .collect();
+                    this.open_plugin_changes_dialog(transform.name, items, window, cx);
+                }
+                Err(e) => {
+                    this.error = Some(format!("Failed to run plugin: {}", e));
+                }
+            },
+        );
+    }
+
+    /// Show the changes proposed by a plugin and apply them as a bulk operation once confirmed
+    fn open_plugin_changes_dialog(
+        &mut self,
+        title: String,
+        items: Vec<(String, BatchAction)>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let app = cx.entity();
+        window.open_dialog(cx, move |dialog, _, _| {
+            let app = app.clone();
+            let title = title.clone();
+            let items = items.clone();
+
+            dialog
+                .title(format!("{}: {} changes", title, items.len()))
+                .child(
+                    v_flex()
+                        .max_h(px(360.))
+                        .overflow_y_scrollbar()
+                        .gap_1()
+                        .text_sm()
+                        .children(items.iter().map(|(label, _)| div().child(label.clone()))),
+                )
+                .confirm()
+                .on_ok(move |_, window, cx| {
+                    let title = title.clone();
+                    let items = items.clone();
+                    app.update(cx, |this, cx| this.run_batch(title, items, window, cx));
+                    true
+                })
+        });
+    }
+
+    /// Export the selected zone's records in a format provided by a plugin
+    pub fn run_plugin_export(
+        &mut self,
+        plugin: Plugin,
+        export_index: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(zone) = self.selected_zone(cx) else {
+            return;
+        };
+        let Some(export) = plugin.manifest.exports.get(export_index).cloned() else {
+            return;
+        };
+        let records = self.dns_records(cx);
+        let file_name = format!("{}.{}", zone.name, export.extension);
+
+        self.error = None;
+        self.spawn_task(
+            "Running plugin",
+            window,
+            cx,
+            |_| async move { plugin.export(&export.id, &zone.name, &records).await },
+            move |this, result, window, cx| match result {
+                Ok(contents) => this.save_to_file(&file_name, contents, window, cx),
+                Err(e) => {
+                    this.error = Some(format!("Failed to export records: {}", e));
+                }
+            },
+        );
+    }
+
+    pub fn apply_theme(&self, window: &mut Window, cx: &mut gpui::App) {
+        match self.appearance_mode {
+            AppearanceMode::Auto => {
+                Theme::sync_system_appearance(Some(window), cx);
+            }
+            AppearanceMode::Light => {
+                Theme::change(ThemeMode::Light, Some(window), cx);
+            }
+            AppearanceMode::Dark => {
+                Theme::change(ThemeMode::Dark, Some(window), cx);
+            }
+        }
+
+        // Changing the theme mode resets the font size and colors
+        let theme = Theme::global_mut(cx);
+        theme.font_size = self.text_scale.font_size();
+        if let Some(accent) = self.active_profile_settings().accent {
+            let color: Hsla = rgb(accent.hex()).into();
+            theme.primary = color;
+            theme.primary_hover = color.opacity(0.9);
+            theme.primary_active = color.opacity(0.8);
+            theme.ring = color;
+        }
+        window.refresh();
+    }
+
+    pub fn set_text_scale(
+        &mut self,
+        scale: TextScale,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.text_scale = scale;
+
+        if let Err(e) = storage::store_text_scale(scale.as_str()) {
+            self.error = Some(format!("Failed to save text size: {}", e));
+        }
+
+        self.apply_theme(window, cx);
+        cx.notify();
+    }
+
+    pub fn set_time_zone_display(&mut self, display: TimeZoneDisplay, cx: &mut Context<Self>) {
+        self.time_zone_display = display;
+
+        if let Err(e) = storage::store_time_zone_display(display.as_str()) {
+            self.error = Some(format!("Failed to save time zone setting: {}", e));
+        }
+        cx.notify();
+    }
+
+    pub fn set_compare_exports(&mut self, compare: bool, cx: &mut Context<Self>) {
+        self.compare_exports = compare;
+
+        if let Err(e) = storage::store_compare_exports(compare) {
+            self.error = Some(format!("Failed to save export setting: {}", e));
+        }
+        cx.notify();
+    }
+
+    /// Take the default TTL and comment template from their inputs in the settings
+    fn update_record_defaults_from_inputs(&mut self, cx: &mut Context<Self>) {
+        let ttl = self.default_ttl_input.read(cx).value().to_string();
+        match record_defaults::parse_ttl(&ttl) {
+            Ok(ttl) => self.record_defaults.ttl = ttl,
+            Err(e) => {
+                self.error = Some(e);
+                cx.notify();
+                return;
+            }
+        }
+        self.record_defaults.comment_template = self
+            .default_comment_input
+            .read(cx)
+            .value()
+            .trim()
+            .to_string();
+        self.record_defaults.attribution = self
+            .default_attribution_input
+            .read(cx)
+            .value()
+            .trim()
+            .to_string();
+        self.store_record_defaults(cx);
+    }
+
+    pub fn set_default_proxied(
+        &mut self,
+        record_type: DnsRecordType,
+        proxied: bool,
+        cx: &mut Context<Self>,
+    ) {
+        self.record_defaults.set_proxied(record_type, proxied);
+        self.store_record_defaults(cx);
+    }
+
+    fn store_record_defaults(&mut self, cx: &mut Context<Self>) {
+        if let Err(e) = storage::store_record_defaults(&self.record_defaults) {
+            self.error = Some(format!("Failed to save new record defaults: {}", e));
+        }
+        cx.notify();
+    }
+
+    /// Whether new records duplicating existing ones are refused instead of asked about
+    fn blocks_duplicate_records(&self) -> bool {
+        self.block_duplicate_records || self.active_profile_settings().strict_guardrails
+    }
+
+    pub fn set_block_duplicate_records(&mut self, block: bool, cx: &mut Context<Self>) {
+        self.block_duplicate_records = block;
+        self.sync_control_server_duplicates();
+
+        if let Err(e) = storage::store_block_duplicate_records(block) {
+            self.error = Some(format!("Failed to save duplicate record setting: {}", e));
+        }
+        cx.notify();
+    }
+
+    pub fn set_appearance_mode(
+        &mut self,
+        mode: AppearanceMode,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if mode == self.appearance_mode {
+            return;
+        }
+        let description = format!("Set appearance to {}", mode.label());
+        let previous = self.appearance_mode.as_str().to_string();
+        self.history
+            .record(description, Command::SetAppearance(previous));
+        self.apply_appearance_mode(mode, window, cx);
+    }
+
+    /// Switch the appearance mode without recording it in the history
+    fn apply_appearance_mode(
+        &mut self,
+        mode: AppearanceMode,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.appearance_mode = mode;
+
+        // Save to storage
+        if let Err(e) = storage::store_appearance_mode(mode.as_str()) {
+            self.error = Some(format!("Failed to save appearance mode: {}", e));
+        }
+
+        // Apply the theme
+        self.apply_theme(window, cx);
+        cx.notify();
+    }
+}
+
+impl Render for App {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .size_full()
+            .track_focus(&self.focus_handle)
+            .key_context("App")
+            .on_action(cx.listener(|this, _: &Undo, window, cx| this.undo(window, cx)))
+            .on_action(cx.listener(|this, _: &Redo, window, cx| this.redo(window, cx)))
+            .bg(cx.theme().background)
+            .text_color(cx.theme().foreground)
+            .when_some(self.active_profile_settings().accent, |this, accent| {
+                this.border_t_4().border_color(rgb(accent.hex()))
+            })
+            .child(match self.page {
+                Page::TokenSetup => ui::render_token_setup(self, window, cx).into_any_element(),
+                Page::Portfolio => ui::render_portfolio(self, window, cx).into_any_element(),
+                Page::Dashboard => ui::render_dashboard(self, window, cx).into_any_element(),
+                Page::Console => ui::render_console(self, window, cx).into_any_element(),
+                Page::Settings => ui::render_settings(self, window, cx).into_any_element(),
+                Page::ZoneSettings => ui::render_zone_settings(self, window, cx).into_any_element(),
+                Page::PageRules => ui::render_page_rules(self, window, cx).into_any_element(),
+                Page::RedirectRules => {
+                    ui::render_redirect_rules(self, window, cx).into_any_element()
+                }
+                Page::WafRules => ui::render_waf_rules(self, window, cx).into_any_element(),
+                Page::WorkerRoutes => ui::render_worker_routes(self, window, cx).into_any_element(),
+                Page::PagesProjects => {
+                    ui::render_pages_projects(self, window, cx).into_any_element()
+                }
+                Page::Tunnels => ui::render_tunnels(self, window, cx).into_any_element(),
+                Page::EdgeCertificates => {
+                    ui::render_edge_certificates(self, window, cx).into_any_element()
+                }
+                Page::Analytics => ui::render_analytics(self, window, cx).into_any_element(),
+                Page::NetworkDiagnostics => {
+                    ui::render_network_diagnostics(self, window, cx).into_any_element()
+                }
+            })
+            .children(Root::render_dialog_layer(window, cx))
+            .children(Root::render_notification_layer(window, cx))
+    }
+}
+
+/// Set up the UI components and key bindings, before the first window opens
+pub fn init(cx: &mut gpui::App) {
+    gpui_component::init(cx);
+    cx.bind_keys([
+        KeyBinding::new("secondary-z", Undo, Some("App")),
+        KeyBinding::new("secondary-shift-z", Redo, Some("App")),
+    ]);
+}
+
+/// Open the app's window and run until it's closed; expects to be called within a Tokio runtime
+pub fn run() {
+    let app = Application::new().with_assets(assets::Assets);
+
+    app.run(move |cx| {
+        init(cx);
+
+        let options = WindowOptions {
+            window_bounds: Some(WindowBounds::Windowed(Bounds::centered(
+                None,
+                size(px(1200.), px(800.)),
+                cx,
+            ))),
+            titlebar: Some(TitlebarOptions {
+                title: Some("Cloudflare DNS Manager".into()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        cx.open_window(options, |window, cx| {
+            let app_view = cx.new(|cx| App::new(window, cx));
+            cx.new(|cx| Root::new(app_view.clone(), window, cx))
+        })
+        .ok();
+    });
+}
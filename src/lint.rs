@@ -0,0 +1,169 @@
+//! Checks for unusual or deprecated record setups in a zone
+//!
+//! Findings explain what is wrong and, where the right change is clear, offer a quick fix that
+//! runs as a bulk operation.
+
+use std::net::IpAddr;
+
+use crate::batch::BatchAction;
+use crate::cloudflare::{DnsRecord, DnsRecordType, UpdateDnsRecord};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintKind {
+    /// SPF record type instead of a TXT record
+    SpfRecordType,
+    /// NS record at the zone apex
+    ApexNameServer,
+    /// MX record whose mail server is a CNAME in the zone
+    MxToCname,
+    /// SRV record whose target is an address instead of a host name
+    SrvTargetAddress,
+}
+
+impl LintKind {
+    pub fn title(&self) -> &'static str {
+        match self {
+            LintKind::SpfRecordType => "Deprecated SPF record type",
+            LintKind::ApexNameServer => "NS record at the zone apex",
+            LintKind::MxToCname => "MX record points at a CNAME",
+            LintKind::SrvTargetAddress => "SRV target is an IP address",
+        }
+    }
+
+    pub fn explanation(&self) -> &'static str {
+        match self {
+            LintKind::SpfRecordType => {
+                "The SPF record type was deprecated by RFC 7208 and most receivers only look up \
+                 TXT records, so this policy is likely ignored."
+            }
+            LintKind::ApexNameServer => {
+                "Cloudflare answers for the apex with its own name servers. NS records at the apex \
+                 are only used with multi-provider DNS, and otherwise disagree with the \
+                 delegation."
+            }
+            LintKind::MxToCname => {
+                "RFC 2181 forbids mail servers that are aliases. Many senders refuse or delay mail \
+                 to them, so the MX record should name the host the CNAME points at."
+            }
+            LintKind::SrvTargetAddress => {
+                "SRV targets must be host names with A or AAAA records. Clients don't connect to \
+                 an address given as the target."
+            }
+        }
+    }
+}
+
+/// A change that resolves a finding
+#[derive(Debug, Clone, PartialEq)]
+pub enum QuickFix {
+    /// Turn the record into a TXT record with the same content
+    ConvertToTxt,
+    /// Remove the record, e.g. because a TXT record with the same policy exists
+    Delete,
+    /// Replace the record's content
+    SetContent(String),
+}
+
+impl QuickFix {
+    pub fn label(&self) -> String {
+        match self {
+            QuickFix::ConvertToTxt => "Convert to TXT".to_string(),
+            QuickFix::Delete => "Delete".to_string(),
+            QuickFix::SetContent(content) => format!("Point at {}", content),
+        }
+    }
+
+    /// The fix as a bulk operation item, labelled for the progress dialog
+    pub fn action(&self, record: &DnsRecord) -> (String, BatchAction) {
+        let description = format!("{} {}", record.record_type, record.name);
+        match self {
+            QuickFix::ConvertToTxt => (
+                format!("Convert {} to TXT", description),
+                BatchAction::Update {
+                    record_id: record.id.clone(),
+                    record: UpdateDnsRecord {
+                        record_type: Some(DnsRecordType::TXT),
+                        ..Default::default()
+                    },
+                },
+            ),
+            QuickFix::Delete => (
+                format!("Delete {}", description),
+                BatchAction::Delete {
+                    record_id: record.id.clone(),
+                },
+            ),
+            QuickFix::SetContent(content) => (
+                format!("Point {} at {}", description, content),
+                BatchAction::Update {
+                    record_id: record.id.clone(),
+                    record: UpdateDnsRecord {
+                        content: Some(content.clone()),
+                        ..Default::default()
+                    },
+                },
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Lint {
+    pub kind: LintKind,
+    pub record: DnsRecord,
+    pub fix: Option<QuickFix>,
+}
+
+/// Findings for a zone's records, in the order of the records
+pub fn lint_zone(zone_name: &str, records: &[DnsRecord]) -> Vec<Lint> {
+    let zone_name = normalize(zone_name);
+
+    records
+        .iter()
+        .filter(|record| !record.locked)
+        .filter_map(|record| {
+            let (kind, fix) = match record.record_type {
+                DnsRecordType::SPF => {
+                    let has_txt = records.iter().any(|other| {
+                        other.record_type == DnsRecordType::TXT
+                            && normalize(&other.name) == normalize(&record.name)
+                            && other.content == record.content
+                    });
+                    let fix = if has_txt {
+                        QuickFix::Delete
+                    } else {
+                        QuickFix::ConvertToTxt
+                    };
+                    (LintKind::SpfRecordType, Some(fix))
+                }
+                DnsRecordType::NS if normalize(&record.name) == zone_name => {
+                    (LintKind::ApexNameServer, Some(QuickFix::Delete))
+                }
+                DnsRecordType::MX => {
+                    let cname = records.iter().find(|other| {
+                        other.record_type == DnsRecordType::CNAME
+                            && normalize(&other.name) == normalize(&record.content)
+                    })?;
+                    let target = cname.content.trim_end_matches('.').to_string();
+                    (LintKind::MxToCname, Some(QuickFix::SetContent(target)))
+                }
+                DnsRecordType::SRV => {
+                    // Content is `weight port target`
+                    let target = record.content.split_whitespace().last()?;
+                    target.trim_end_matches('.').parse::<IpAddr>().ok()?;
+                    (LintKind::SrvTargetAddress, None)
+                }
+                _ => return None,
+            };
+            Some(Lint {
+                kind,
+                record: record.clone(),
+                fix,
+            })
+        })
+        .collect()
+}
+
+fn normalize(name: &str) -> String {
+    name.trim().trim_end_matches('.').to_lowercase()
+}
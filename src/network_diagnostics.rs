@@ -0,0 +1,224 @@
+//! Checks of how well this computer reaches the Cloudflare API
+//!
+//! The API hostname is resolved with the system resolver, then the first address of each IP
+//! family is tried directly, with a TCP connection for the latency and an HTTPS request to the
+//! API. Networks with broken IPv6, or IPv6 only, show up as one family failing. The route of
+//! the proxy setting is checked separately, since a proxy hides which family is used.
+
+use std::net::{IpAddr, SocketAddr, TcpStream, ToSocketAddrs};
+use std::time::{Duration, Instant};
+
+use reqwest::{Client, Url};
+
+use crate::network_proxy::{self, ProxyRoute};
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Family {
+    V4,
+    V6,
+}
+
+impl Family {
+    pub fn label(self) -> &'static str {
+        match self {
+            Family::V4 => "IPv4",
+            Family::V6 => "IPv6",
+        }
+    }
+
+    fn matches(self, address: &IpAddr) -> bool {
+        match self {
+            Family::V4 => address.is_ipv4(),
+            Family::V6 => address.is_ipv6(),
+        }
+    }
+}
+
+/// Addresses the API hostname resolved to
+#[derive(Debug, Clone)]
+pub struct Resolution {
+    pub addresses: Vec<IpAddr>,
+    pub elapsed: Duration,
+}
+
+/// Reachability of the API over one IP family
+#[derive(Debug, Clone)]
+pub struct FamilyCheck {
+    pub family: Family,
+    /// Address that was tried, `None` if the hostname has none of this family
+    pub address: Option<IpAddr>,
+    /// Time to open a TCP connection
+    pub connect: Option<Result<Duration, String>>,
+    /// Time to get an answer to an HTTPS request
+    pub request: Option<Result<Duration, String>>,
+}
+
+impl FamilyCheck {
+    fn reachable(&self) -> bool {
+        matches!(self.request, Some(Ok(_)))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Diagnostics {
+    pub host: String,
+    pub resolution: Result<Resolution, String>,
+    pub families: Vec<FamilyCheck>,
+    /// The route of the proxy setting, if it goes through a proxy, and the time a request
+    /// along it took
+    pub proxy: Option<(ProxyRoute, Result<Duration, String>)>,
+}
+
+impl Diagnostics {
+    /// What the checks mean, e.g. that IPv6 is broken on this network
+    pub fn summary(&self) -> String {
+        if let Err(e) = &self.resolution {
+            return format!(
+                "{} doesn't resolve ({}); check the DNS servers of this network",
+                self.host, e
+            );
+        }
+
+        let reachable: Vec<Family> = self
+            .families
+            .iter()
+            .filter(|check| check.reachable())
+            .map(|check| check.family)
+            .collect();
+        let broken: Vec<Family> = self
+            .families
+            .iter()
+            .filter(|check| check.address.is_some() && !check.reachable())
+            .map(|check| check.family)
+            .collect();
+        let proxy_works = matches!(self.proxy, Some((_, Ok(_))));
+
+        match (reachable.as_slice(), broken.as_slice()) {
+            ([], _) if proxy_works => {
+                "Direct connections to the API fail, but requests through the proxy work"
+                    .to_string()
+            }
+            ([], _) => "The API can't be reached; check the network connection, firewall or proxy"
+                .to_string(),
+            ([family], [other]) => format!(
+                "The API is only reachable over {}; {} is broken on this network, which can \
+                 slow down or fail requests that try it first",
+                family.label(),
+                other.label()
+            ),
+            ([Family::V6], []) => {
+                "The API is reachable over IPv6; this network has no IPv4 addresses for it"
+                    .to_string()
+            }
+            ([family], []) => format!("The API is reachable over {}", family.label()),
+            _ => "The API is reachable over IPv4 and IPv6".to_string(),
+        }
+    }
+}
+
+/// Run all checks against the host of `api_base`, and along `route` if it uses a proxy
+pub async fn run(api_base: &str, route: Option<ProxyRoute>) -> Diagnostics {
+    let url = Url::parse(api_base).ok();
+    let host = url
+        .as_ref()
+        .and_then(|url| url.host_str())
+        .unwrap_or_default()
+        .to_string();
+    let port = url
+        .as_ref()
+        .and_then(|url| url.port_or_known_default())
+        .unwrap_or(443);
+    let check_url = format!("{}/user/tokens/verify", api_base);
+
+    let resolution = resolve(&host, port).await;
+    let mut families = Vec::new();
+    for family in [Family::V4, Family::V6] {
+        let address = resolution
+            .as_ref()
+            .ok()
+            .and_then(|resolution| resolution.addresses.iter().find(|a| family.matches(a)))
+            .copied();
+        let (connect, request) = match address {
+            Some(address) => {
+                let socket = SocketAddr::new(address, port);
+                // Pin the hostname to the address, so the request can't use the other family
+                let client = Client::builder()
+                    .no_proxy()
+                    .resolve(&host, socket)
+                    .build()
+                    .unwrap_or_default();
+                (
+                    Some(connect(socket).await),
+                    Some(time_request(&client, &check_url).await),
+                )
+            }
+            None => (None, None),
+        };
+        families.push(FamilyCheck {
+            family,
+            address,
+            connect,
+            request,
+        });
+    }
+
+    let proxy = match route {
+        Some(route) if route.proxy.is_some() => {
+            let client = network_proxy::http_client(&route);
+            let result = time_request(&client, &check_url).await;
+            Some((route, result))
+        }
+        _ => None,
+    };
+
+    Diagnostics {
+        host,
+        resolution,
+        families,
+        proxy,
+    }
+}
+
+/// Resolve with the system resolver, like requests do
+async fn resolve(host: &str, port: u16) -> Result<Resolution, String> {
+    let host = host.to_string();
+    let started = Instant::now();
+    let addresses = tokio::task::spawn_blocking(move || {
+        (host.as_str(), port)
+            .to_socket_addrs()
+            .map(|addresses| addresses.map(|address| address.ip()).collect::<Vec<_>>())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())?;
+    Ok(Resolution {
+        addresses,
+        elapsed: started.elapsed(),
+    })
+}
+
+async fn connect(address: SocketAddr) -> Result<Duration, String> {
+    tokio::task::spawn_blocking(move || {
+        let started = Instant::now();
+        TcpStream::connect_timeout(&address, CONNECT_TIMEOUT)
+            .map(|_| started.elapsed())
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Any answer from the API counts, the request doesn't carry a token
+async fn time_request(client: &Client, url: &str) -> Result<Duration, String> {
+    let started = Instant::now();
+    client
+        .get(url)
+        .timeout(REQUEST_TIMEOUT)
+        .send()
+        .await
+        .map(|_| started.elapsed())
+        .map_err(|e| format!("{:#}", anyhow::Error::from(e)))
+}
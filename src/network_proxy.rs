@@ -0,0 +1,582 @@
+//! Proxy used for API requests, detected from the OS network settings unless overridden
+//!
+//! Automatic detection looks at the `HTTPS_PROXY` family of environment variables first, then at
+//! the proxy configured in the macOS or Windows network settings or in GNOME. Proxy auto-config
+//! (PAC) scripts can't be run without a JavaScript engine, so a script is only used when every
+//! answer it can give names the same proxy, which covers the common "everything through one
+//! proxy" scripts. Answers only given to hosts without a dot, as in
+//! `if (isPlainHostName(host)) return "DIRECT";`, are left out since the API's host has dots.
+//! Other scripts send requests out directly and the proxy has to be set manually.
+
+use std::time::Duration;
+
+use anyhow::{Context, Result, anyhow};
+use reqwest::{Client, Proxy, Url};
+use serde::{Deserialize, Serialize};
+use tokio::process::Command;
+
+/// Platform tools and PAC scripts taking longer than this are given up on
+const DETECTION_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How API requests reach Cloudflare
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum ProxySetting {
+    /// Use the proxy configured in the environment or the OS
+    #[default]
+    Automatic,
+    /// Never use a proxy
+    Direct,
+    /// Always use this proxy
+    Manual { url: String },
+}
+
+/// The path API requests take, and where it came from
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProxyRoute {
+    /// Proxy URL, `None` for a direct connection
+    pub proxy: Option<String>,
+    /// e.g. `the macOS network settings`
+    pub source: String,
+}
+
+impl ProxyRoute {
+    fn direct(source: impl Into<String>) -> Self {
+        Self {
+            proxy: None,
+            source: source.into(),
+        }
+    }
+
+    fn through(proxy: String, source: impl Into<String>) -> Self {
+        Self {
+            proxy: Some(proxy),
+            source: source.into(),
+        }
+    }
+
+    /// e.g. `Through http://proxy.example.com:8080, from the macOS network settings`
+    pub fn describe(&self) -> String {
+        match &self.proxy {
+            Some(proxy) => format!("Through {}, from {}", proxy, self.source),
+            None => format!("Direct connection, {}", self.source),
+        }
+    }
+}
+
+/// HTTP client sending requests along `route`, and never through a proxy it finds itself
+pub fn http_client(route: &ProxyRoute) -> Client {
+    let builder = Client::builder();
+    let builder = match route.proxy.as_deref().map(Proxy::all) {
+        Some(Ok(proxy)) => builder.proxy(proxy),
+        _ => builder.no_proxy(),
+    };
+    builder.build().unwrap_or_default()
+}
+
+/// Normalize a manually entered proxy, e.g. `proxy:8080` to `http://proxy:8080`
+pub fn parse_proxy_url(input: &str) -> Result<String, String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err("Enter the address of the proxy, e.g. proxy.example.com:8080".to_string());
+    }
+    let url = if input.contains("://") {
+        input.to_string()
+    } else {
+        format!("http://{}", input)
+    };
+    let parsed = Url::parse(&url).map_err(|e| format!("Invalid proxy address: {}", e))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err("Only HTTP and HTTPS proxies are supported".to_string());
+    }
+    if parsed.host_str().is_none_or(str::is_empty) {
+        return Err("The proxy address needs a host".to_string());
+    }
+    Ok(url.trim_end_matches('/').to_string())
+}
+
+/// The route `setting` names itself, `None` for [`ProxySetting::Automatic`], which has to be
+/// detected
+pub fn configured(setting: &ProxySetting) -> Option<ProxyRoute> {
+    match setting {
+        ProxySetting::Direct => Some(ProxyRoute::direct("the proxy is turned off in Settings")),
+        ProxySetting::Manual { url } => Some(ProxyRoute::through(url.clone(), "Settings")),
+        ProxySetting::Automatic => None,
+    }
+}
+
+/// Work out the route requests to `api_base` take with `setting`
+pub async fn detect(setting: &ProxySetting, api_base: &str) -> ProxyRoute {
+    if let Some(route) = configured(setting) {
+        return route;
+    }
+    let host = Url::parse(api_base)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_lowercase))
+        .unwrap_or_default();
+    if let Some(route) = from_environment(&host) {
+        return route;
+    }
+    match system_proxy().await {
+        Some((system, source)) => system.route(&host, source).await,
+        None => ProxyRoute::direct("no proxy is configured"),
+    }
+}
+
+fn from_environment(host: &str) -> Option<ProxyRoute> {
+    let (name, proxy) = ["HTTPS_PROXY", "https_proxy", "ALL_PROXY", "all_proxy"]
+        .into_iter()
+        .find_map(|name| {
+            let value = std::env::var(name).ok()?;
+            let value = value.trim();
+            (!value.is_empty()).then(|| (name, value.to_string()))
+        })?;
+
+    let exceptions: Vec<String> = ["NO_PROXY", "no_proxy"]
+        .into_iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .flat_map(|value| {
+            value
+                .split(',')
+                .map(|entry| entry.trim().to_string())
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    if bypassed(host, &exceptions) {
+        return Some(ProxyRoute::direct(format!(
+            "{} is excluded by the NO_PROXY environment variable",
+            host
+        )));
+    }
+    Some(ProxyRoute::through(
+        parse_proxy_url(&proxy).unwrap_or(proxy),
+        format!("the {} environment variable", name),
+    ))
+}
+
+/// Proxy configured in the OS network settings
+#[derive(Debug, Default, PartialEq)]
+struct SystemProxy {
+    /// Proxy for HTTPS, e.g. `proxy.example.com:8080`
+    https: Option<String>,
+    /// URL of a proxy auto-config script
+    pac_url: Option<String>,
+    /// Hosts that don't go through the proxy
+    exceptions: Vec<String>,
+}
+
+impl SystemProxy {
+    async fn route(self, host: &str, source: &str) -> ProxyRoute {
+        if bypassed(host, &self.exceptions) {
+            return ProxyRoute::direct(format!("{} is excluded in {}", host, source));
+        }
+
+        // Like browsers, an auto-config script takes precedence over a fixed proxy
+        let mut pac_error = None;
+        if let Some(pac_url) = &self.pac_url {
+            match fetch_pac(pac_url)
+                .await
+                .and_then(|script| pac_proxy(&script))
+            {
+                Ok(Some(proxy)) => {
+                    return ProxyRoute::through(
+                        proxy,
+                        format!("the auto-config script {} in {}", pac_url, source),
+                    );
+                }
+                Ok(None) => {
+                    return ProxyRoute::direct(format!(
+                        "the auto-config script {} in {} answers DIRECT",
+                        pac_url, source
+                    ));
+                }
+                Err(e) => pac_error = Some(format!("auto-config script {} {}", pac_url, e)),
+            }
+        }
+
+        match (self.https, pac_error) {
+            (Some(proxy), None) => {
+                ProxyRoute::through(parse_proxy_url(&proxy).unwrap_or(proxy), source.to_string())
+            }
+            (Some(proxy), Some(pac_error)) => ProxyRoute::through(
+                parse_proxy_url(&proxy).unwrap_or(proxy),
+                format!("{}, since its {}", source, pac_error),
+            ),
+            (None, Some(pac_error)) => ProxyRoute::direct(format!(
+                "since the {} from {}; set the proxy manually if one is needed",
+                pac_error, source
+            )),
+            (None, None) => ProxyRoute::direct(format!("no proxy is configured in {}", source)),
+        }
+    }
+}
+
+/// Proxy of the current platform's network settings, with where it came from
+async fn system_proxy() -> Option<(SystemProxy, &'static str)> {
+    if cfg!(target_os = "macos") {
+        let output = run("scutil", &["--proxy"]).await?;
+        Some((parse_scutil(&output), "the macOS network settings"))
+    } else if cfg!(windows) {
+        let output = run(
+            "reg",
+            &[
+                "query",
+                r"HKCU\Software\Microsoft\Windows\CurrentVersion\Internet Settings",
+            ],
+        )
+        .await?;
+        Some((
+            parse_windows_registry(&output),
+            "the Windows proxy settings",
+        ))
+    } else {
+        gnome_proxy()
+            .await
+            .map(|proxy| (proxy, "the GNOME network settings"))
+    }
+}
+
+async fn run(program: &str, args: &[&str]) -> Option<String> {
+    let output = tokio::time::timeout(
+        DETECTION_TIMEOUT,
+        Command::new(program).args(args).kill_on_drop(true).output(),
+    )
+    .await
+    .ok()?
+    .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Parse the output of `scutil --proxy`
+fn parse_scutil(output: &str) -> SystemProxy {
+    let mut values = std::collections::HashMap::new();
+    let mut exceptions = Vec::new();
+    let mut in_exceptions = false;
+    for line in output.lines() {
+        let line = line.trim();
+        if line == "}" {
+            in_exceptions = false;
+            continue;
+        }
+        let Some((key, value)) = line.split_once(" : ") else {
+            continue;
+        };
+        if in_exceptions {
+            exceptions.push(value.trim().to_string());
+        } else if key == "ExceptionsList" {
+            in_exceptions = true;
+        } else {
+            values.insert(key.to_string(), value.trim().to_string());
+        }
+    }
+
+    let enabled = |key: &str| values.get(key).is_some_and(|value| value == "1");
+    SystemProxy {
+        https: enabled("HTTPSEnable")
+            .then(|| {
+                let host = values.get("HTTPSProxy")?;
+                Some(match values.get("HTTPSPort") {
+                    Some(port) => format!("{}:{}", host, port),
+                    None => host.clone(),
+                })
+            })
+            .flatten(),
+        pac_url: enabled("ProxyAutoConfigEnable")
+            .then(|| values.get("ProxyAutoConfigURLString").cloned())
+            .flatten(),
+        exceptions,
+    }
+}
+
+/// Parse the output of `reg query` for the Internet Settings key
+fn parse_windows_registry(output: &str) -> SystemProxy {
+    let value = |name: &str| {
+        output.lines().find_map(|line| {
+            let mut parts = line.split_whitespace();
+            (parts.next()? == name).then_some(())?;
+            parts.next()?;
+            Some(parts.collect::<Vec<_>>().join(" "))
+        })
+    };
+
+    // `ProxyServer` is either one proxy for everything or `http=host:port;https=host:port`
+    let https = (value("ProxyEnable").as_deref() == Some("0x1"))
+        .then(|| value("ProxyServer"))
+        .flatten()
+        .and_then(|server| {
+            if !server.contains('=') {
+                return Some(server);
+            }
+            server.split(';').find_map(|entry| {
+                let (scheme, proxy) = entry.split_once('=')?;
+                scheme
+                    .eq_ignore_ascii_case("https")
+                    .then(|| proxy.to_string())
+            })
+        });
+    SystemProxy {
+        https,
+        pac_url: value("AutoConfigURL"),
+        exceptions: value("ProxyOverride")
+            .map(|list| list.split(';').map(str::to_string).collect())
+            .unwrap_or_default(),
+    }
+}
+
+/// Proxy configured in GNOME, `None` outside of GNOME
+async fn gnome_proxy() -> Option<SystemProxy> {
+    let get = |schema: &'static str, key: &'static str| async move {
+        let output = run("gsettings", &["get", schema, key]).await?;
+        Some(output.trim().trim_matches('\'').to_string())
+    };
+
+    let mut proxy = SystemProxy::default();
+    match get("org.gnome.system.proxy", "mode").await?.as_str() {
+        "manual" => {
+            let host = get("org.gnome.system.proxy.https", "host").await?;
+            let port = get("org.gnome.system.proxy.https", "port").await?;
+            if !host.is_empty() {
+                proxy.https = Some(format!("{}:{}", host, port));
+            }
+        }
+        "auto" => {
+            proxy.pac_url = get("org.gnome.system.proxy", "autoconfig-url")
+                .await
+                .filter(|url| !url.is_empty());
+        }
+        _ => return Some(proxy),
+    }
+    // e.g. `['localhost', '127.0.0.0/8']`
+    if let Some(hosts) = get("org.gnome.system.proxy", "ignore-hosts").await {
+        proxy.exceptions = hosts
+            .trim_matches(|c| c == '[' || c == ']')
+            .split(',')
+            .map(|host| host.trim().trim_matches('\'').to_string())
+            .collect();
+    }
+    Some(proxy)
+}
+
+async fn fetch_pac(url: &str) -> Result<String> {
+    if let Some(path) = url.strip_prefix("file://") {
+        return std::fs::read_to_string(path).context("couldn't be read");
+    }
+    // The script has to be reachable without the proxy it configures
+    let client = Client::builder()
+        .no_proxy()
+        .timeout(DETECTION_TIMEOUT)
+        .build()?;
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .context("couldn't be downloaded")?;
+    if !response.status().is_success() {
+        return Err(anyhow!("couldn't be downloaded ({})", response.status()));
+    }
+    Ok(response.text().await?)
+}
+
+/// The proxy a PAC script sends requests to, `None` for `DIRECT`, if every answer in the
+/// script starts with the same proxy
+fn pac_proxy(script: &str) -> Result<Option<String>> {
+    let mut answers: Vec<&str> = Vec::new();
+    let mut offset = 0;
+    while let Some(start) = script[offset..].find(['"', '\'']).map(|ix| offset + ix) {
+        let quote = &script[start..start + 1];
+        let Some(len) = script[start + 1..].find(quote) else {
+            break;
+        };
+        let text = &script[start + 1..start + 1 + len];
+        offset = start + len + 2;
+        if plain_host_answer(&script[..start]) {
+            continue;
+        }
+        // The first proxy of an answer like `PROXY a:8080; PROXY b:8080; DIRECT`
+        let first = text.split(';').next().unwrap_or_default().trim();
+        let keyword = first.split_whitespace().next().unwrap_or_default();
+        if matches!(
+            keyword.to_ascii_uppercase().as_str(),
+            "PROXY" | "HTTPS" | "DIRECT" | "SOCKS" | "SOCKS5"
+        ) && !answers.contains(&first)
+        {
+            answers.push(first);
+        }
+    }
+
+    match answers.as_slice() {
+        [] => Err(anyhow!("has no answers this app understands")),
+        [answer] => {
+            let mut parts = answer.split_whitespace();
+            match (
+                parts
+                    .next()
+                    .unwrap_or_default()
+                    .to_ascii_uppercase()
+                    .as_str(),
+                parts.next(),
+            ) {
+                ("DIRECT", _) => Ok(None),
+                ("PROXY", Some(proxy)) => Ok(Some(format!("http://{}", proxy))),
+                ("HTTPS", Some(proxy)) => Ok(Some(format!("https://{}", proxy))),
+                _ => Err(anyhow!(
+                    "only answers with SOCKS proxies, which aren't supported"
+                )),
+            }
+        }
+        _ => Err(anyhow!(
+            "picks between several proxies, which needs JavaScript to work out"
+        )),
+    }
+}
+
+/// Whether the answer `before` ends in front of is only given to hosts without a dot, e.g.
+/// `if (isPlainHostName(host)) return "DIRECT";`
+fn plain_host_answer(before: &str) -> bool {
+    let statement: String = before
+        .rsplit([';', '}'])
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    statement.ends_with("if(isPlainHostName(host))return")
+        || statement.ends_with("if(isPlainHostName(host)){return")
+}
+
+/// Whether `host` matches one of the bypass entries of a proxy setting, e.g. `*.example.com`,
+/// `.example.com`, or `<local>` for hosts without a dot
+fn bypassed(host: &str, exceptions: &[String]) -> bool {
+    exceptions.iter().any(|entry| {
+        let entry = entry.trim().to_lowercase();
+        match entry.as_str() {
+            "" => false,
+            "*" => true,
+            "<local>" => !host.contains('.'),
+            _ => {
+                let domain = entry.trim_start_matches("*.").trim_start_matches('.');
+                host == domain || host.ends_with(&format!(".{}", domain))
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pac_script_with_one_proxy() {
+        let script = r#"function FindProxyForURL(url, host) {
+            return "PROXY proxy.example.com:8080; DIRECT";
+        }"#;
+        assert_eq!(
+            pac_proxy(script).unwrap(),
+            Some("http://proxy.example.com:8080".to_string())
+        );
+        assert_eq!(
+            pac_proxy("function FindProxyForURL(url, host) { return 'DIRECT'; }").unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn pac_script_sending_plain_hosts_directly() {
+        let script = r#"function FindProxyForURL(url, host) {
+            if (isPlainHostName(host)) return "DIRECT";
+            return "PROXY p:8080";
+        }"#;
+        assert_eq!(
+            pac_proxy(script).unwrap(),
+            Some("http://p:8080".to_string())
+        );
+
+        let script = r#"function FindProxyForURL(url, host) {
+            if (isPlainHostName(host)) {
+                return "DIRECT";
+            }
+            return "HTTPS p:8443";
+        }"#;
+        assert_eq!(
+            pac_proxy(script).unwrap(),
+            Some("https://p:8443".to_string())
+        );
+    }
+
+    #[test]
+    fn pac_script_picking_between_answers() {
+        let script = r#"function FindProxyForURL(url, host) {
+            if (dnsDomainIs(host, ".corp.example.com")) return "DIRECT";
+            return "PROXY p:8080";
+        }"#;
+        assert!(pac_proxy(script).is_err());
+        assert!(
+            pac_proxy(r#"function FindProxyForURL(url, host) { return "SOCKS s:1080"; }"#).is_err()
+        );
+        assert!(pac_proxy("function FindProxyForURL(url, host) {}").is_err());
+    }
+
+    #[test]
+    fn parses_scutil() {
+        let output = "<dictionary> {
+  ExceptionsList : <array> {
+    0 : *.local
+    1 : 169.254/16
+  }
+  HTTPSEnable : 1
+  HTTPSPort : 8080
+  HTTPSProxy : proxy.example.com
+  ProxyAutoConfigEnable : 0
+  ProxyAutoConfigURLString : http://wpad/wpad.dat
+}";
+        assert_eq!(
+            parse_scutil(output),
+            SystemProxy {
+                https: Some("proxy.example.com:8080".to_string()),
+                pac_url: None,
+                exceptions: vec!["*.local".to_string(), "169.254/16".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn parses_windows_registry() {
+        let output = r"
+HKEY_CURRENT_USER\Software\Microsoft\Windows\CurrentVersion\Internet Settings
+    ProxyEnable    REG_DWORD    0x1
+    ProxyServer    REG_SZ    http=web:80;https=secure:8443
+    ProxyOverride    REG_SZ    *.example.com;<local>
+    AutoConfigURL    REG_SZ    http://wpad/proxy.pac
+";
+        assert_eq!(
+            parse_windows_registry(output),
+            SystemProxy {
+                https: Some("secure:8443".to_string()),
+                pac_url: Some("http://wpad/proxy.pac".to_string()),
+                exceptions: vec!["*.example.com".to_string(), "<local>".to_string()],
+            }
+        );
+
+        let disabled = output.replace("0x1", "0x0");
+        assert_eq!(parse_windows_registry(&disabled).https, None);
+    }
+
+    #[test]
+    fn bypass_entries() {
+        let exceptions = ["*.cloudflare.com".to_string()];
+        assert!(bypassed("api.cloudflare.com", &exceptions));
+        assert!(bypassed("cloudflare.com", &exceptions));
+        assert!(!bypassed("notcloudflare.com", &exceptions));
+        assert!(bypassed(
+            "api.cloudflare.com",
+            &[".cloudflare.com".to_string()]
+        ));
+        assert!(bypassed("intranet", &["<local>".to_string()]));
+        assert!(!bypassed("api.cloudflare.com", &["<local>".to_string()]));
+        assert!(bypassed("api.cloudflare.com", &["*".to_string()]));
+        assert!(!bypassed("api.cloudflare.com", &[String::new()]));
+    }
+}
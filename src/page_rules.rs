@@ -0,0 +1,201 @@
+//! Page Rules of a zone and the form they are edited in
+//!
+//! The form covers the most common actions: forwarding URLs, the cache level and the SSL mode.
+//! Other actions of a rule are listed and kept as they are when it's saved.
+
+use crate::cloudflare::{PageRule, PageRuleAction, PageRuleTarget};
+
+pub const FORWARDING_URL: &str = "forwarding_url";
+pub const CACHE_LEVEL: &str = "cache_level";
+pub const SSL: &str = "ssl";
+
+/// Status codes a forwarding URL can redirect with
+pub const FORWARDING_STATUS_CODES: &[u16] = &[301, 302];
+
+pub const CACHE_LEVELS: &[(&str, &str)] = &[
+    ("bypass", "Bypass"),
+    ("basic", "No Query String"),
+    ("simplified", "Ignore Query String"),
+    ("aggressive", "Standard"),
+    ("cache_everything", "Cache Everything"),
+];
+
+pub const SSL_MODES: &[(&str, &str)] = &[
+    ("off", "Off"),
+    ("flexible", "Flexible"),
+    ("full", "Full"),
+    ("strict", "Full (strict)"),
+];
+
+/// Editable parts of a Page Rule besides the pattern and destination inputs
+#[derive(Debug, Clone)]
+pub struct PageRuleDraft {
+    /// Rule being edited, `None` for a new one
+    pub rule_id: Option<String>,
+    /// Status code of the redirect, `None` without forwarding
+    pub forwarding_status: Option<u16>,
+    pub cache_level: Option<&'static str>,
+    pub ssl: Option<&'static str>,
+    pub enabled: bool,
+    /// Actions the form can't edit, kept as they are
+    pub other_actions: Vec<PageRuleAction>,
+    priority: Option<i32>,
+}
+
+impl Default for PageRuleDraft {
+    fn default() -> Self {
+        Self {
+            rule_id: None,
+            forwarding_status: None,
+            cache_level: None,
+            ssl: None,
+            enabled: true,
+            other_actions: Vec::new(),
+            priority: None,
+        }
+    }
+}
+
+impl PageRuleDraft {
+    /// Draft of an existing rule and the destination of its forwarding URL
+    pub fn from_rule(rule: &PageRule) -> (Self, String) {
+        let mut draft = Self {
+            rule_id: Some(rule.id.clone()),
+            enabled: rule.is_active(),
+            priority: Some(rule.priority),
+            ..Default::default()
+        };
+        let mut destination = String::new();
+
+        for action in &rule.actions {
+            let value = action.value.as_str().unwrap_or_default();
+            match action.id.as_str() {
+                FORWARDING_URL => {
+                    destination = action.value["url"].as_str().unwrap_or_default().to_string();
+                    let status = action.value["status_code"].as_u64().unwrap_or(301) as u16;
+                    draft.forwarding_status = Some(status);
+                }
+                CACHE_LEVEL if known(CACHE_LEVELS, value).is_some() => {
+                    draft.cache_level = known(CACHE_LEVELS, value);
+                }
+                SSL if known(SSL_MODES, value).is_some() => {
+                    draft.ssl = known(SSL_MODES, value);
+                }
+                _ => draft.other_actions.push(action.clone()),
+            }
+        }
+        (draft, destination)
+    }
+
+    /// The rule to save, checking that its actions can be combined
+    pub fn build(&self, pattern: &str, destination: &str) -> Result<PageRule, String> {
+        let pattern = pattern.trim();
+        if pattern.is_empty() {
+            return Err("Enter the URL pattern the rule applies to".to_string());
+        }
+        if pattern.contains(char::is_whitespace) {
+            return Err("URL patterns can't contain spaces".to_string());
+        }
+
+        let mut actions = Vec::new();
+        if let Some(status) = self.forwarding_status {
+            let destination = destination.trim();
+            if !destination.starts_with("http://") && !destination.starts_with("https://") {
+                return Err("The forwarding URL has to start with http:// or https://".to_string());
+            }
+            if self.cache_level.is_some() || self.ssl.is_some() || !self.other_actions.is_empty() {
+                return Err("A forwarding URL can't be combined with other actions".to_string());
+            }
+            actions.push(PageRuleAction {
+                id: FORWARDING_URL.to_string(),
+                value: serde_json::json!({ "url": destination, "status_code": status }),
+            });
+        }
+        if let Some(level) = self.cache_level {
+            actions.push(PageRuleAction {
+                id: CACHE_LEVEL.to_string(),
+                value: level.into(),
+            });
+        }
+        if let Some(mode) = self.ssl {
+            actions.push(PageRuleAction {
+                id: SSL.to_string(),
+                value: mode.into(),
+            });
+        }
+        actions.extend(self.other_actions.iter().cloned());
+        if actions.is_empty() {
+            return Err("Choose at least one action".to_string());
+        }
+
+        Ok(PageRule {
+            id: self.rule_id.clone().unwrap_or_default(),
+            targets: vec![PageRuleTarget::matching(pattern)],
+            actions,
+            // New rules are applied last
+            priority: self.priority.unwrap_or(1),
+            status: if self.enabled { "active" } else { "disabled" }.to_string(),
+        })
+    }
+}
+
+fn known(options: &[(&'static str, &str)], value: &str) -> Option<&'static str> {
+    options
+        .iter()
+        .find(|(option, _)| *option == value)
+        .map(|(option, _)| *option)
+}
+
+fn option_label<'a>(options: &[(&str, &'a str)], value: &'a str) -> &'a str {
+    options
+        .iter()
+        .find(|(option, _)| *option == value)
+        .map(|(_, label)| *label)
+        .unwrap_or(value)
+}
+
+/// Rules in the order they are applied in
+pub fn sort(rules: &mut [PageRule]) {
+    rules.sort_by_key(|rule| std::cmp::Reverse(rule.priority));
+}
+
+/// New priorities after moving the rule at `index` of the sorted `rules` up or down, for the
+/// rules whose priority changes
+pub fn reorder(rules: &[PageRule], index: usize, up: bool) -> Vec<(String, i32)> {
+    let Some(other) = (if up {
+        index.checked_sub(1)
+    } else {
+        Some(index + 1)
+    })
+    .filter(|&other| other < rules.len()) else {
+        return Vec::new();
+    };
+
+    let mut order: Vec<&PageRule> = rules.iter().collect();
+    order.swap(index, other);
+    let count = order.len() as i32;
+    order
+        .into_iter()
+        .enumerate()
+        .map(|(position, rule)| (rule, count - position as i32))
+        .filter(|(rule, priority)| rule.priority != *priority)
+        .map(|(rule, priority)| (rule.id.clone(), priority))
+        .collect()
+}
+
+/// What an action does, e.g. `Forward (301) to https://example.com/$1`
+pub fn action_label(action: &PageRuleAction) -> String {
+    let value = action.value.as_str().unwrap_or_default();
+    match action.id.as_str() {
+        FORWARDING_URL => format!(
+            "Forward ({}) to {}",
+            action.value["status_code"].as_u64().unwrap_or(301),
+            action.value["url"].as_str().unwrap_or_default()
+        ),
+        CACHE_LEVEL => format!("Cache Level: {}", option_label(CACHE_LEVELS, value)),
+        SSL => format!("SSL: {}", option_label(SSL_MODES, value)),
+        id if action.value.is_null() => id.to_string(),
+        id if action.value.is_string() => format!("{}: {}", id, value),
+        id => format!("{}: {}", id, action.value),
+    }
+}
@@ -0,0 +1,66 @@
+//! Cloudflare Pages projects of an account and the state of their deployments
+
+use crate::cloudflare::PagesDeployment;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DeploymentState {
+    Succeeded,
+    Failed,
+    Canceled,
+    /// Queued, building or deploying
+    InProgress,
+}
+
+impl DeploymentState {
+    pub fn of(deployment: &PagesDeployment) -> Self {
+        match deployment
+            .latest_stage
+            .as_ref()
+            .map(|stage| stage.status.as_str())
+        {
+            Some("success") => DeploymentState::Succeeded,
+            Some("failure") => DeploymentState::Failed,
+            Some("canceled") => DeploymentState::Canceled,
+            _ => DeploymentState::InProgress,
+        }
+    }
+}
+
+/// Where a deployment is at, e.g. `Build failed` or `Deploy succeeded`
+pub fn describe(deployment: &PagesDeployment) -> String {
+    let stage = deployment
+        .latest_stage
+        .as_ref()
+        .map(|stage| stage_label(&stage.name))
+        .unwrap_or("Deployment");
+    let state = match DeploymentState::of(deployment) {
+        DeploymentState::Succeeded => "succeeded",
+        DeploymentState::Failed => "failed",
+        DeploymentState::Canceled => "canceled",
+        DeploymentState::InProgress => "in progress",
+    };
+    format!("{} {}", stage, state)
+}
+
+fn stage_label(stage: &str) -> &str {
+    match stage {
+        "queued" => "Queued",
+        "initialize" => "Initialization",
+        "clone_repo" => "Clone",
+        "build" => "Build",
+        "deploy" => "Deploy",
+        _ => stage,
+    }
+}
+
+/// Commit a deployment was built from, e.g. `main · Fix feed links`
+pub fn source_label(deployment: &PagesDeployment) -> Option<String> {
+    let source = deployment.source()?;
+    let message = source.commit_message.lines().next().unwrap_or_default();
+    match (source.branch.is_empty(), message.is_empty()) {
+        (true, true) => None,
+        (false, true) => Some(source.branch.clone()),
+        (true, false) => Some(message.to_string()),
+        (false, false) => Some(format!("{} · {}", source.branch, message)),
+    }
+}
@@ -0,0 +1,356 @@
+//! External plugins contributing record validations, bulk transformations and export formats
+//!
+//! A plugin is a directory in [`plugins_dir`] containing a `plugin.json` manifest and an
+//! executable. For every hook the app runs the executable with the hook name as its last
+//! argument, writes a JSON request to its stdin and reads the response from its stdout.
+//! Plugins only run once the user approved the permissions listed in their manifest.
+
+use std::path::{Component, Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+
+use anyhow::{Context, Result, anyhow, bail};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+use crate::batch::BatchAction;
+use crate::cloudflare::{CreateDnsRecord, DnsRecord, UpdateDnsRecord};
+
+pub const MANIFEST_FILE: &str = "plugin.json";
+
+/// Plugins taking longer than this to answer are stopped
+const PLUGIN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Directory that is searched for plugins
+pub fn plugins_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("cloudflare-admin").join("plugins"))
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct PluginManifest {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub version: String,
+    /// Executable to run, relative to the plugin directory and inside it
+    pub command: String,
+    /// Arguments passed before the hook name
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Whether records are sent to the plugin for validation before they are saved
+    #[serde(default)]
+    pub validate: bool,
+    #[serde(default)]
+    pub transforms: Vec<PluginTransform>,
+    #[serde(default)]
+    pub exports: Vec<PluginExport>,
+}
+
+/// Bulk transformation of the records of a zone
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct PluginTransform {
+    pub id: String,
+    pub name: String,
+}
+
+/// Export format of the records of a zone
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct PluginExport {
+    pub id: String,
+    pub name: String,
+    /// File extension of the exported file, without the dot
+    pub extension: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Plugin {
+    /// Name of the plugin's directory, which identifies it
+    pub id: String,
+    pub dir: PathBuf,
+    pub manifest: PluginManifest,
+    /// Hash of the manifest and the executable as they were loaded
+    fingerprint: String,
+}
+
+/// Change to a record proposed by a transformation
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PluginChange {
+    Create(CreateDnsRecord),
+    Update { id: String, record: UpdateDnsRecord },
+    Delete { id: String },
+}
+
+impl PluginChange {
+    /// Batch action applying the change, labelled for the progress dialog
+    pub fn into_batch_item(self, records: &[DnsRecord]) -> (String, BatchAction) {
+        let action = match self {
+            PluginChange::Create(record) => BatchAction::Create(record),
+            PluginChange::Update { id, record } => BatchAction::Update {
+                record_id: id,
+                record,
+            },
+            PluginChange::Delete { id } => BatchAction::Delete { record_id: id },
+        };
+        (action.label(records), action)
+    }
+}
+
+#[derive(Serialize)]
+struct ValidateRequest<'a, T> {
+    zone: &'a str,
+    record: &'a T,
+}
+
+#[derive(Deserialize)]
+struct ValidateResponse {
+    #[serde(default)]
+    errors: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct RecordsRequest<'a> {
+    /// Id of the transformation or export format
+    id: &'a str,
+    zone: &'a str,
+    records: &'a [DnsRecord],
+}
+
+#[derive(Deserialize)]
+struct TransformResponse {
+    #[serde(default)]
+    changes: Vec<PluginChange>,
+}
+
+/// Find all plugins in [`plugins_dir`], along with errors of plugins that failed to load
+pub fn discover() -> (Vec<Plugin>, Vec<String>) {
+    let mut plugins = Vec::new();
+    let mut errors = Vec::new();
+
+    let Some(dir) = plugins_dir() else {
+        return (plugins, errors);
+    };
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return (plugins, errors);
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_dir() {
+            continue;
+        }
+        match Plugin::load(&path) {
+            Ok(plugin) => plugins.push(plugin),
+            Err(e) => errors.push(format!("{}: {:#}", path.display(), e)),
+        }
+    }
+
+    plugins.sort_by(|a, b| a.manifest.name.cmp(&b.manifest.name));
+    (plugins, errors)
+}
+
+impl Plugin {
+    fn load(dir: &Path) -> Result<Self> {
+        let manifest = std::fs::read_to_string(dir.join(MANIFEST_FILE))
+            .with_context(|| format!("Failed to read {}", MANIFEST_FILE))?;
+        let manifest: PluginManifest = serde_json::from_str(&manifest)
+            .with_context(|| format!("Invalid {}", MANIFEST_FILE))?;
+
+        let mut plugin = Self {
+            id: dir
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            dir: dir.to_path_buf(),
+            manifest,
+            fingerprint: String::new(),
+        };
+        plugin.fingerprint = plugin.current_fingerprint()?;
+        Ok(plugin)
+    }
+
+    /// Identifies the approved manifest and executable, so plugins have to be approved again
+    /// when either changes
+    pub fn fingerprint(&self) -> String {
+        self.fingerprint.clone()
+    }
+
+    fn current_fingerprint(&self) -> Result<String> {
+        let executable = self.executable()?;
+        let contents = std::fs::read(&executable)
+            .with_context(|| format!("Failed to read {}", executable.display()))?;
+
+        let mut hasher = Sha256::new();
+        hasher.update(serde_json::to_vec(&self.manifest)?);
+        hasher.update(contents);
+        Ok(hasher
+            .finalize()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect())
+    }
+
+    /// The executable the manifest names, which has to be inside the plugin directory
+    pub fn executable(&self) -> Result<PathBuf> {
+        let command = Path::new(&self.manifest.command);
+        let relative = command
+            .components()
+            .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+        if self.manifest.command.is_empty() || !relative {
+            bail!(
+                "The command {} is not inside the plugin directory",
+                self.manifest.command
+            );
+        }
+
+        // A symlink could still lead out of the directory
+        let dir = self.dir.canonicalize()?;
+        let executable = dir
+            .join(command)
+            .canonicalize()
+            .with_context(|| format!("Failed to find {}", self.manifest.command))?;
+        if !executable.starts_with(&dir) {
+            bail!(
+                "The command {} is not inside the plugin directory",
+                self.manifest.command
+            );
+        }
+        Ok(executable)
+    }
+
+    /// What the plugin is allowed to do once approved, shown in the permission prompt
+    pub fn permissions(&self) -> Vec<String> {
+        let executable = self
+            .executable()
+            .unwrap_or_else(|_| self.dir.join(&self.manifest.command));
+        let mut permissions = vec![format!("Run {}", executable.display())];
+
+        if self.manifest.validate {
+            permissions.push("Read records before they are saved and reject them".to_string());
+        }
+        if !self.manifest.transforms.is_empty() {
+            permissions.push(format!(
+                "Read all records of the selected zone and propose changes ({})",
+                names(self.manifest.transforms.iter().map(|t| t.name.as_str()))
+            ));
+        }
+        if !self.manifest.exports.is_empty() {
+            permissions.push(format!(
+                "Read all records of the selected zone to export them ({})",
+                names(self.manifest.exports.iter().map(|e| e.name.as_str()))
+            ));
+        }
+
+        permissions
+    }
+
+    /// Errors reported by the plugin for a record about to be saved
+    pub async fn validate<T: Serialize>(&self, zone: &str, record: &T) -> Result<Vec<String>> {
+        let response: ValidateResponse = self
+            .call_json("validate", &ValidateRequest { zone, record })
+            .await?;
+        Ok(response.errors)
+    }
+
+    /// Changes proposed by one of the plugin's transformations
+    pub async fn transform(
+        &self,
+        id: &str,
+        zone: &str,
+        records: &[DnsRecord],
+    ) -> Result<Vec<PluginChange>> {
+        let response: TransformResponse = self
+            .call_json("transform", &RecordsRequest { id, zone, records })
+            .await?;
+        Ok(response.changes)
+    }
+
+    /// Contents of the file exported in one of the plugin's formats
+    pub async fn export(&self, id: &str, zone: &str, records: &[DnsRecord]) -> Result<String> {
+        self.call("export", &RecordsRequest { id, zone, records })
+            .await
+    }
+
+    async fn call_json<R: DeserializeOwned>(
+        &self,
+        hook: &str,
+        request: &impl Serialize,
+    ) -> Result<R> {
+        let output = self.call(hook, request).await?;
+        serde_json::from_str(&output)
+            .with_context(|| format!("{} returned an invalid response", self.manifest.name))
+    }
+
+    /// Run the plugin's executable for a hook and return what it wrote to stdout
+    async fn call(&self, hook: &str, request: &impl Serialize) -> Result<String> {
+        // The executable may have been replaced since it was approved
+        if self.current_fingerprint()? != self.fingerprint {
+            bail!(
+                "{} changed since it was loaded; reload the plugins and approve it again",
+                self.manifest.name
+            );
+        }
+
+        let request = serde_json::to_vec(request)?;
+        let mut child = Command::new(self.executable()?)
+            .args(&self.manifest.args)
+            .arg(hook)
+            .current_dir(&self.dir)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .with_context(|| format!("Failed to start {}", self.manifest.name))?;
+
+        // The request is written while the output is read, so a plugin answering before it read
+        // all of a large request can't block on a full pipe
+        let stdin = child.stdin.take();
+        let write = async move {
+            if let Some(mut stdin) = stdin {
+                stdin.write_all(&request).await?;
+            }
+            // Dropping stdin closes it, so the plugin sees the end of the request
+            Ok::<_, std::io::Error>(())
+        };
+        let (written, output) = tokio::time::timeout(PLUGIN_TIMEOUT, async move {
+            tokio::join!(write, child.wait_with_output())
+        })
+        .await
+        .map_err(|_| anyhow!("{} timed out", self.manifest.name))?;
+        let output = output?;
+        if !output.status.success() {
+            bail!(
+                "{} failed: {}",
+                self.manifest.name,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        written.with_context(|| format!("Failed to send the request to {}", self.manifest.name))?;
+        Ok(String::from_utf8(output.stdout)?)
+    }
+}
+
+/// Run the validations of all given plugins, failing with the first plugin's errors
+pub async fn validate_record<T: Serialize>(
+    plugins: &[Plugin],
+    zone: &str,
+    record: &T,
+) -> Result<()> {
+    for plugin in plugins.iter().filter(|plugin| plugin.manifest.validate) {
+        let errors = plugin.validate(zone, record).await?;
+        if !errors.is_empty() {
+            bail!("{}: {}", plugin.manifest.name, errors.join("; "));
+        }
+    }
+    Ok(())
+}
+
+fn names<'a>(names: impl Iterator<Item = &'a str>) -> String {
+    names.collect::<Vec<_>>().join(", ")
+}
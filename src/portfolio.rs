@@ -0,0 +1,94 @@
+//! Health overview of all zones, shown on the portfolio page
+
+use chrono::{DateTime, Utc};
+
+use crate::certificates::{self, CertificateKind, TrackedCertificate};
+use crate::cloudflare::{CloudflareClient, Zone, ZoneAccount};
+
+/// Certificates expiring within this many days are reported as an issue
+pub const CERTIFICATE_WARNING_DAYS: i64 = 14;
+
+/// Accounts `zones` belong to, sorted by name
+pub fn zone_accounts(zones: &[Zone]) -> Vec<ZoneAccount> {
+    let mut accounts: Vec<ZoneAccount> = Vec::new();
+    for zone in zones {
+        if !accounts.iter().any(|account| account.id == zone.account.id) {
+            accounts.push(zone.account.clone());
+        }
+    }
+    accounts.sort_by_key(|account| account.name.to_lowercase());
+    accounts
+}
+
+/// Health information fetched for a single zone
+#[derive(Debug, Clone, Default)]
+pub struct ZoneHealth {
+    /// DNSSEC status, if it could be fetched
+    pub dnssec: Option<String>,
+    /// Edge and origin certificates of the zone
+    pub certificates: Vec<TrackedCertificate>,
+    /// Errors encountered while checking the zone
+    pub errors: Vec<String>,
+}
+
+impl ZoneHealth {
+    /// Fetch DNSSEC and certificate information for a zone
+    pub async fn fetch(client: &CloudflareClient, zone_id: &str) -> Self {
+        let mut health = ZoneHealth::default();
+
+        match client.get_dnssec(zone_id).await {
+            Ok(dnssec) => health.dnssec = Some(dnssec.status),
+            Err(e) => health.errors.push(e.to_string()),
+        }
+        match client.list_certificate_packs(zone_id).await {
+            Ok(packs) => health
+                .certificates
+                .extend(certificates::edge_certificates(&packs)),
+            Err(e) => health.errors.push(e.to_string()),
+        }
+        match client.list_origin_certificates(zone_id).await {
+            Ok(origin) => health
+                .certificates
+                .extend(certificates::origin_certificates(&origin)),
+            Err(e) => health.errors.push(e.to_string()),
+        }
+
+        health
+    }
+
+    /// Whole days until the earliest edge certificate expires, negative once expired
+    pub fn days_until_expiry(&self, now: DateTime<Utc>) -> Option<i64> {
+        self.certificates
+            .iter()
+            .filter(|certificate| certificate.kind == CertificateKind::Edge)
+            .map(|certificate| certificate.days_until_expiry(now))
+            .min()
+    }
+
+    /// Problems worth looking into, most severe first
+    pub fn issues(&self, zone: &Zone, now: DateTime<Utc>) -> Vec<String> {
+        let mut issues = Vec::new();
+
+        if zone.status != "active" {
+            issues.push(format!("Zone is {}", zone.status));
+        }
+        if zone.paused {
+            issues.push("Cloudflare is paused".to_string());
+        }
+        match self.days_until_expiry(now) {
+            Some(days) if days < 0 => issues.push("Certificate has expired".to_string()),
+            Some(days) if days <= CERTIFICATE_WARNING_DAYS => {
+                issues.push(format!("Certificate expires in {} days", days))
+            }
+            _ => {}
+        }
+        if let Some(dnssec) = &self.dnssec
+            && dnssec != "active"
+        {
+            issues.push(format!("DNSSEC is {}", dnssec));
+        }
+        issues.extend(self.errors.iter().cloned());
+
+        issues
+    }
+}
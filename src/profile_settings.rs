@@ -0,0 +1,65 @@
+//! Settings a token profile overrides, e.g. to lock a client's production account down and tell
+//! it apart from a personal one at a glance
+//!
+//! Read-only mode and strict guardrails only add to the app-wide settings: a profile can be
+//! locked down further, never opened up.
+
+use serde::{Deserialize, Serialize};
+
+/// Color replacing the theme's primary color while a profile is active
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Accent {
+    Red,
+    Orange,
+    Green,
+    Blue,
+    Purple,
+}
+
+impl Accent {
+    pub const ALL: [Accent; 5] = [
+        Accent::Red,
+        Accent::Orange,
+        Accent::Green,
+        Accent::Blue,
+        Accent::Purple,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Accent::Red => "Red",
+            Accent::Orange => "Orange",
+            Accent::Green => "Green",
+            Accent::Blue => "Blue",
+            Accent::Purple => "Purple",
+        }
+    }
+
+    /// The color as `0xRRGGBB`, dark enough for white text on it
+    pub fn hex(self) -> u32 {
+        match self {
+            Accent::Red => 0xdc2626,
+            Accent::Orange => 0xea580c,
+            Accent::Green => 0x16a34a,
+            Accent::Blue => 0x2563eb,
+            Accent::Purple => 0x9333ea,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProfileSettings {
+    /// Color of primary buttons and of a strip along the top of the window, `None` for the
+    /// theme's
+    pub accent: Option<Accent>,
+    /// TTL new records start with, `None` for the one in the New Records settings
+    pub default_ttl: Option<u32>,
+    /// Whether nothing is to be changed on Cloudflare with the profile, even with read-only
+    /// mode off
+    pub read_only: bool,
+    /// Whether deleting records in bulk always takes typing the zone's name, and duplicate
+    /// records are refused instead of asked about
+    pub strict_guardrails: bool,
+}
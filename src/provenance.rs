@@ -0,0 +1,74 @@
+//! Detection of records created by Cloudflare integrations, which are managed elsewhere
+
+use crate::cloudflare::{DnsRecord, DnsRecordType};
+
+/// Integration a record was most likely created by
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Provenance {
+    Tunnel,
+    Pages,
+    Workers,
+    EmailRouting,
+}
+
+impl Provenance {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Provenance::Tunnel => "Tunnel",
+            Provenance::Pages => "Pages",
+            Provenance::Workers => "Workers",
+            Provenance::EmailRouting => "Email Routing",
+        }
+    }
+
+    /// Where the record is managed, shown as a warning in the editor
+    pub fn description(&self) -> &'static str {
+        match self {
+            Provenance::Tunnel => {
+                "This record routes traffic to a Cloudflare Tunnel. It's managed by the tunnel's public hostname settings, which may overwrite or remove it."
+            }
+            Provenance::Pages => {
+                "This record points to a Cloudflare Pages project. It's managed by the project's custom domain settings."
+            }
+            Provenance::Workers => {
+                "This record serves a Cloudflare Worker. It's managed by the Worker's custom domain or route settings."
+            }
+            Provenance::EmailRouting => {
+                "This record delivers mail through Cloudflare Email Routing. Changing it may stop email from arriving."
+            }
+        }
+    }
+
+    /// Guess the integration from the record's name and content
+    pub fn detect(record: &DnsRecord) -> Option<Self> {
+        let content = record
+            .content
+            .trim_end_matches('.')
+            .trim_matches('"')
+            .to_lowercase();
+        let name = record.name.to_lowercase();
+
+        match record.record_type {
+            DnsRecordType::CNAME if content.ends_with(".cfargotunnel.com") => {
+                Some(Provenance::Tunnel)
+            }
+            DnsRecordType::CNAME if content.ends_with(".pages.dev") => Some(Provenance::Pages),
+            DnsRecordType::CNAME if content.ends_with(".workers.dev") => Some(Provenance::Workers),
+            // Placeholder address of proxied hostnames that only run a Worker
+            DnsRecordType::AAAA if record.proxied && content == "100::" => {
+                Some(Provenance::Workers)
+            }
+            DnsRecordType::MX if content.ends_with(".mx.cloudflare.net") => {
+                Some(Provenance::EmailRouting)
+            }
+            DnsRecordType::TXT if content.contains("include:_spf.mx.cloudflare.net") => {
+                Some(Provenance::EmailRouting)
+            }
+            // DKIM key of Email Routing, e.g. `cf2024-1._domainkey`
+            DnsRecordType::TXT if name.starts_with("cf20") && name.contains("._domainkey.") => {
+                Some(Provenance::EmailRouting)
+            }
+            _ => None,
+        }
+    }
+}
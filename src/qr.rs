@@ -0,0 +1,48 @@
+//! QR codes of record contents, for moving verification values to another device
+
+use anyhow::Result;
+use qrcode::{Color, EcLevel, QrCode};
+
+/// Modules of light margin around the code, as required by the QR specification
+pub const QUIET_ZONE: usize = 4;
+
+/// Square grid of QR code modules
+pub struct QrMatrix {
+    /// Number of modules per side, without the quiet zone
+    pub width: usize,
+    dark: Vec<bool>,
+}
+
+impl QrMatrix {
+    /// Encode text with low error correction, which keeps long TXT values scannable
+    pub fn encode(text: &str) -> Result<Self> {
+        let code = QrCode::with_error_correction_level(text, EcLevel::L)?;
+        Ok(Self {
+            width: code.width(),
+            dark: code
+                .to_colors()
+                .into_iter()
+                .map(|color| color == Color::Dark)
+                .collect(),
+        })
+    }
+
+    /// Runs of dark modules in a row as `(start column, length)`
+    pub fn dark_runs(&self, row: usize) -> Vec<(usize, usize)> {
+        let modules = &self.dark[row * self.width..(row + 1) * self.width];
+        let mut runs = Vec::new();
+        let mut column = 0;
+        while column < self.width {
+            if modules[column] {
+                let start = column;
+                while column < self.width && modules[column] {
+                    column += 1;
+                }
+                runs.push((start, column - start));
+            } else {
+                column += 1;
+            }
+        }
+        runs
+    }
+}
@@ -0,0 +1,471 @@
+//! Structured `data` of record types whose content is made of several fields
+//!
+//! The app edits these records as their zone file content, e.g. `3 1 1 2b3c...` for TLSA, and
+//! splits it into the fields the API expects when the record is saved. Records keep the `data`
+//! Cloudflare returns, so saving a record again sends its fields as they were.
+
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+
+use crate::cloudflare::DnsRecordType;
+
+/// Fields of a record's content, by the shape of the types that share it
+///
+/// Cloudflare doesn't tag `data` with the record type, so the fields tell the shapes apart.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum RecordData {
+    /// SRV; the priority is the record's
+    Srv {
+        weight: u16,
+        port: u16,
+        target: String,
+    },
+    Caa {
+        flags: u8,
+        tag: String,
+        value: String,
+    },
+    /// HTTPS and SVCB
+    Svcb {
+        priority: u16,
+        target: String,
+        value: String,
+    },
+    /// TLSA and SMIMEA
+    Tlsa {
+        usage: u8,
+        selector: u8,
+        matching_type: u8,
+        certificate: String,
+    },
+    Sshfp {
+        algorithm: u8,
+        #[serde(rename = "type")]
+        fingerprint_type: u8,
+        fingerprint: String,
+    },
+    Naptr {
+        order: u16,
+        preference: u16,
+        flags: String,
+        service: String,
+        regex: String,
+        replacement: String,
+    },
+    Cert {
+        #[serde(rename = "type")]
+        cert_type: u16,
+        key_tag: u16,
+        algorithm: u8,
+        certificate: String,
+    },
+    Dnskey {
+        flags: u16,
+        protocol: u8,
+        algorithm: u8,
+        public_key: String,
+    },
+    Ds {
+        key_tag: u16,
+        algorithm: u8,
+        digest_type: u8,
+        digest: String,
+    },
+    Loc {
+        lat_degrees: u8,
+        lat_minutes: u8,
+        lat_seconds: f64,
+        lat_direction: String,
+        long_degrees: u8,
+        long_minutes: u8,
+        long_seconds: f64,
+        long_direction: String,
+        /// In meters, as are the size and precisions
+        altitude: f64,
+        size: f64,
+        precision_horz: f64,
+        precision_vert: f64,
+    },
+    /// URI; the priority is the record's
+    Uri { weight: u16, target: String },
+}
+
+impl RecordData {
+    /// Whether these fields are the ones of `record_type`
+    pub fn fits(&self, record_type: DnsRecordType) -> bool {
+        matches!(
+            (self, record_type),
+            (RecordData::Srv { .. }, DnsRecordType::SRV)
+                | (RecordData::Caa { .. }, DnsRecordType::CAA)
+                | (
+                    RecordData::Svcb { .. },
+                    DnsRecordType::HTTPS | DnsRecordType::SVCB
+                )
+                | (
+                    RecordData::Tlsa { .. },
+                    DnsRecordType::TLSA | DnsRecordType::SMIMEA
+                )
+                | (RecordData::Sshfp { .. }, DnsRecordType::SSHFP)
+                | (RecordData::Naptr { .. }, DnsRecordType::NAPTR)
+                | (RecordData::Cert { .. }, DnsRecordType::CERT)
+                | (RecordData::Dnskey { .. }, DnsRecordType::DNSKEY)
+                | (RecordData::Ds { .. }, DnsRecordType::DS)
+                | (RecordData::Loc { .. }, DnsRecordType::LOC)
+                | (RecordData::Uri { .. }, DnsRecordType::URI)
+        )
+    }
+
+    /// The fields as zone file content, which `parse` reads back to the same fields
+    pub fn content(&self) -> String {
+        match self {
+            RecordData::Srv {
+                weight,
+                port,
+                target,
+            } => format!("{} {} {}", weight, port, target),
+            RecordData::Caa { flags, tag, value } => format!("{} {} \"{}\"", flags, tag, value),
+            RecordData::Svcb {
+                priority,
+                target,
+                value,
+            } => format!("{} {} {}", priority, target, value)
+                .trim_end()
+                .to_string(),
+            RecordData::Tlsa {
+                usage,
+                selector,
+                matching_type,
+                certificate,
+            } => format!("{} {} {} {}", usage, selector, matching_type, certificate),
+            RecordData::Sshfp {
+                algorithm,
+                fingerprint_type,
+                fingerprint,
+            } => format!("{} {} {}", algorithm, fingerprint_type, fingerprint),
+            RecordData::Naptr {
+                order,
+                preference,
+                flags,
+                service,
+                regex,
+                replacement,
+            } => format!(
+                "{} {} \"{}\" \"{}\" \"{}\" {}",
+                order, preference, flags, service, regex, replacement
+            ),
+            RecordData::Cert {
+                cert_type,
+                key_tag,
+                algorithm,
+                certificate,
+            } => format!("{} {} {} {}", cert_type, key_tag, algorithm, certificate),
+            RecordData::Dnskey {
+                flags,
+                protocol,
+                algorithm,
+                public_key,
+            } => format!("{} {} {} {}", flags, protocol, algorithm, public_key),
+            RecordData::Ds {
+                key_tag,
+                algorithm,
+                digest_type,
+                digest,
+            } => format!("{} {} {} {}", key_tag, algorithm, digest_type, digest),
+            RecordData::Loc {
+                lat_degrees,
+                lat_minutes,
+                lat_seconds,
+                lat_direction,
+                long_degrees,
+                long_minutes,
+                long_seconds,
+                long_direction,
+                altitude,
+                size,
+                precision_horz,
+                precision_vert,
+            } => format!(
+                "{} {} {} {} {} {} {} {} {}m {}m {}m {}m",
+                lat_degrees,
+                lat_minutes,
+                lat_seconds,
+                lat_direction,
+                long_degrees,
+                long_minutes,
+                long_seconds,
+                long_direction,
+                altitude,
+                size,
+                precision_horz,
+                precision_vert
+            ),
+            RecordData::Uri { weight, target } => format!("{} \"{}\"", weight, target),
+        }
+    }
+}
+
+/// `data` of a record as Cloudflare returns it, dropping fields of a shape this app doesn't know
+/// rather than failing to read the record
+pub fn deserialize_lenient<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<RecordData>, D::Error> {
+    let value = Option::<Value>::deserialize(deserializer)?;
+    Ok(value.and_then(|value| serde_json::from_value(value).ok()))
+}
+
+/// Fields of the record's content, or `None` for types whose content is a single value
+pub fn parse(
+    record_type: DnsRecordType,
+    content: &str,
+) -> Result<Option<RecordData>, &'static str> {
+    let data = match record_type {
+        DnsRecordType::SRV => {
+            let fields: Vec<&str> = content.split_whitespace().collect();
+            let [weight, port, target] = fields.as_slice() else {
+                return Err("Expected: weight port target, e.g. 5 5060 sip.example.com");
+            };
+            RecordData::Srv {
+                weight: number(weight)?,
+                port: number(port)?,
+                target: target.to_string(),
+            }
+        }
+        DnsRecordType::CAA => {
+            let fields = quoted_fields(content);
+            let [flags, tag, value] = fields.as_slice() else {
+                return Err("Expected: flags tag value, e.g. 0 issue \"letsencrypt.org\"");
+            };
+            RecordData::Caa {
+                flags: number(flags)?,
+                tag: tag.clone(),
+                value: value.clone(),
+            }
+        }
+        DnsRecordType::HTTPS | DnsRecordType::SVCB => {
+            let ([priority], rest) = split_fields(content)
+                .ok_or("Expected: priority target [parameters], e.g. 1 . alpn=h2")?;
+            // Parameters are optional, e.g. in alias mode
+            let (target, value) = rest
+                .split_once(char::is_whitespace)
+                .map(|(target, value)| (target, value.trim()))
+                .unwrap_or((rest, ""));
+            RecordData::Svcb {
+                priority: number(priority)?,
+                target: target.to_string(),
+                value: value.to_string(),
+            }
+        }
+        DnsRecordType::TLSA | DnsRecordType::SMIMEA => {
+            let ([usage, selector, matching_type], certificate) = split_fields(content)
+                .ok_or("Expected: usage selector matching-type certificate, e.g. 3 1 1 2b3c...")?;
+            RecordData::Tlsa {
+                usage: number(usage)?,
+                selector: number(selector)?,
+                matching_type: number(matching_type)?,
+                certificate: hex(certificate)?,
+            }
+        }
+        DnsRecordType::SSHFP => {
+            let ([algorithm, fingerprint_type], fingerprint) = split_fields(content)
+                .ok_or("Expected: algorithm type fingerprint, e.g. 4 2 9dc1...")?;
+            RecordData::Sshfp {
+                algorithm: number(algorithm)?,
+                fingerprint_type: number(fingerprint_type)?,
+                fingerprint: hex(fingerprint)?,
+            }
+        }
+        DnsRecordType::NAPTR => {
+            let fields = quoted_fields(content);
+            let [order, preference, flags, service, regex, replacement] = fields.as_slice() else {
+                return Err(
+                    "Expected: order preference flags service regex replacement, e.g. 100 10 \"S\" \"SIP+D2U\" \"\" _sip._udp.example.com",
+                );
+            };
+            RecordData::Naptr {
+                order: number(order)?,
+                preference: number(preference)?,
+                flags: flags.clone(),
+                service: service.clone(),
+                regex: regex.clone(),
+                replacement: replacement.clone(),
+            }
+        }
+        DnsRecordType::CERT => {
+            let ([cert_type, key_tag, algorithm], certificate) = split_fields(content)
+                .ok_or("Expected: type key-tag algorithm certificate, e.g. 1 0 0 MIIB...")?;
+            RecordData::Cert {
+                cert_type: number(cert_type)?,
+                key_tag: number(key_tag)?,
+                algorithm: number(algorithm)?,
+                certificate: without_whitespace(certificate)?,
+            }
+        }
+        DnsRecordType::DNSKEY => {
+            let ([flags, protocol, algorithm], public_key) = split_fields(content)
+                .ok_or("Expected: flags protocol algorithm public-key, e.g. 257 3 13 mdsw...")?;
+            RecordData::Dnskey {
+                flags: number(flags)?,
+                protocol: number(protocol)?,
+                algorithm: number(algorithm)?,
+                public_key: without_whitespace(public_key)?,
+            }
+        }
+        DnsRecordType::DS => {
+            let ([key_tag, algorithm, digest_type], digest) = split_fields(content)
+                .ok_or("Expected: key-tag algorithm digest-type digest, e.g. 2371 13 2 1F98...")?;
+            RecordData::Ds {
+                key_tag: number(key_tag)?,
+                algorithm: number(algorithm)?,
+                digest_type: number(digest_type)?,
+                digest: hex(digest)?,
+            }
+        }
+        DnsRecordType::LOC => parse_loc(content).ok_or(
+            "Expected: latitude N/S longitude E/W altitude, e.g. 51 30 12.7 N 0 7 39.6 W 0m",
+        )?,
+        DnsRecordType::URI => {
+            let fields = quoted_fields(content);
+            let [weight, target] = fields.as_slice() else {
+                return Err("Expected: weight target, e.g. 1 \"https://example.com\"");
+            };
+            RecordData::Uri {
+                weight: number(weight)?,
+                target: target.clone(),
+            }
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(data))
+}
+
+/// Index in `record_type.content_fields()` of the field being typed at the end of `content`
+///
+/// The last field takes the rest of the content, e.g. a certificate split into several parts.
+pub fn typed_field(record_type: DnsRecordType, content: &str) -> usize {
+    let fields = record_type.content_fields().len();
+    if fields == 0 {
+        return 0;
+    }
+    let typed = if record_type == DnsRecordType::LOC {
+        // Each coordinate takes up to three values and ends with its direction
+        content
+            .split_whitespace()
+            .filter(|field| ["N", "S", "E", "W"].contains(&field.to_uppercase().as_str()))
+            .count()
+    } else {
+        let count = quoted_fields(content).len();
+        // A field is finished once a space follows it outside quotes
+        let in_quotes = content.matches('"').count() % 2 == 1;
+        if content.ends_with(char::is_whitespace) && !in_quotes {
+            count
+        } else {
+            count.saturating_sub(1)
+        }
+    };
+    typed.min(fields - 1)
+}
+
+/// The first `N` whitespace-separated fields and the rest of the content, which must not be empty
+fn split_fields<const N: usize>(content: &str) -> Option<([&str; N], &str)> {
+    let mut rest = content.trim();
+    let mut fields = [""; N];
+    for field in &mut fields {
+        let (value, remainder) = rest.split_once(char::is_whitespace)?;
+        *field = value;
+        rest = remainder.trim_start();
+    }
+    (!rest.is_empty()).then_some((fields, rest))
+}
+
+/// Whitespace-separated fields, where quoted fields may contain spaces; quotes are removed
+fn quoted_fields(content: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut chars = content.trim().chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '"' {
+            chars.next();
+            fields.push(chars.by_ref().take_while(|&c| c != '"').collect());
+        } else {
+            let mut field = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() {
+                    break;
+                }
+                field.push(c);
+                chars.next();
+            }
+            fields.push(field);
+        }
+    }
+    fields
+}
+
+fn number<T: std::str::FromStr>(field: &str) -> Result<T, &'static str> {
+    field.parse().map_err(|_| "Invalid number")
+}
+
+/// Hex value that may be split into several fields, as zone files often do
+fn hex(value: &str) -> Result<String, &'static str> {
+    let value = without_whitespace(value)?;
+    if !value.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err("Invalid hex value");
+    }
+    Ok(value)
+}
+
+fn without_whitespace(value: &str) -> Result<String, &'static str> {
+    let value: String = value.split_whitespace().collect();
+    if value.is_empty() {
+        return Err("Value cannot be empty");
+    }
+    Ok(value)
+}
+
+/// e.g. `51 30 12.748 N 0 7 39.611 W 0.00m 1m 10000m 10m`, where minutes, seconds, size and
+/// precisions are optional
+fn parse_loc(content: &str) -> Option<RecordData> {
+    let mut fields = content.split_whitespace();
+
+    let mut coordinate = |directions: [&str; 2]| -> Option<(u8, u8, f64, String)> {
+        let mut parts: Vec<&str> = Vec::new();
+        loop {
+            let field = fields.next()?;
+            if directions.contains(&field.to_uppercase().as_str()) {
+                let degrees = parts.first()?.parse().ok()?;
+                let minutes = parts.get(1).map_or(Some(0), |m| m.parse().ok())?;
+                let seconds = parts.get(2).map_or(Some(0.), |s| s.parse().ok())?;
+                return Some((degrees, minutes, seconds, field.to_uppercase()));
+            }
+            if parts.len() == 3 {
+                return None;
+            }
+            parts.push(field);
+        }
+    };
+    let (lat_degrees, lat_minutes, lat_seconds, lat_direction) = coordinate(["N", "S"])?;
+    let (long_degrees, long_minutes, long_seconds, long_direction) = coordinate(["E", "W"])?;
+
+    let meters = |field: &str| -> Option<f64> { field.trim_end_matches(['m', 'M']).parse().ok() };
+    let altitude = meters(fields.next()?)?;
+    let size = fields.next().map_or(Some(1.), meters)?;
+    let precision_horz = fields.next().map_or(Some(10000.), meters)?;
+    let precision_vert = fields.next().map_or(Some(10.), meters)?;
+
+    Some(RecordData::Loc {
+        lat_degrees,
+        lat_minutes,
+        lat_seconds,
+        lat_direction,
+        long_degrees,
+        long_minutes,
+        long_seconds,
+        long_direction,
+        altitude,
+        size,
+        precision_horz,
+        precision_vert,
+    })
+}
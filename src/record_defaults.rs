@@ -0,0 +1,279 @@
+//! Values new records start with in the editor, and how comments are finished when saved
+//!
+//! Comments can contain `$user`, `$date` and `$ticket`, filled in when the record is saved;
+//! `$ticket` is asked for. The attribution, if set, is appended to every comment saved from the
+//! editor, replacing the one appended when the record was saved before.
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use crate::cloudflare::DnsRecordType;
+
+/// Longest TTL Cloudflare accepts, in seconds
+pub(crate) const MAX_TTL: u32 = 86400;
+/// Shortest TTL other than automatic
+pub(crate) const MIN_TTL: u32 = 60;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RecordDefaults {
+    /// TTL in seconds, 1 for automatic
+    pub ttl: u32,
+    /// Proxiable types whose new records start proxied
+    pub proxied_types: Vec<DnsRecordType>,
+    /// Comment new records start with, variables left in until the record is saved
+    pub comment_template: String,
+    /// Appended to comments saved from the editor, e.g. `(via cloudflare-admin, $user)`
+    pub attribution: String,
+}
+
+impl Default for RecordDefaults {
+    fn default() -> Self {
+        Self {
+            ttl: 1,
+            proxied_types: Vec::new(),
+            comment_template: String::new(),
+            attribution: String::new(),
+        }
+    }
+}
+
+impl RecordDefaults {
+    pub fn proxied(&self, record_type: DnsRecordType) -> bool {
+        record_type.is_proxiable() && self.proxied_types.contains(&record_type)
+    }
+
+    pub fn set_proxied(&mut self, record_type: DnsRecordType, proxied: bool) {
+        self.proxied_types.retain(|&t| t != record_type);
+        if proxied {
+            self.proxied_types.push(record_type);
+        }
+    }
+
+    /// Whether saving `comment` asks for the ticket it refers to
+    pub fn needs_ticket(&self, comment: &str) -> bool {
+        [comment, &self.attribution]
+            .iter()
+            .any(|text| split_variables(text).contains(&Piece::Variable(TICKET)))
+    }
+
+    /// Comment to save for what was entered in the editor, `None` if it's empty
+    pub fn finish_comment(
+        &self,
+        comment: &str,
+        now: DateTime<Local>,
+        ticket: &str,
+    ) -> Option<String> {
+        let attribution = self.attribution.trim();
+        let expanded = expand(attribution, now, ticket);
+        let comment = if attribution.is_empty() {
+            comment.trim()
+        } else {
+            strip_attribution(comment.trim(), attribution, &expanded).trim_end()
+        };
+
+        let mut finished = expand(comment, now, ticket);
+        if !attribution.is_empty() {
+            if !finished.is_empty() {
+                finished.push(' ');
+            }
+            finished.push_str(&expanded);
+        }
+        (!finished.is_empty()).then_some(finished)
+    }
+}
+
+const USER: &str = "user";
+const DATE: &str = "date";
+const TICKET: &str = "ticket";
+
+/// Variables comments can contain
+pub const COMMENT_VARIABLES: &[&str] = &[USER, DATE, TICKET];
+
+#[derive(Debug, PartialEq)]
+enum Piece<'a> {
+    Text(&'a str),
+    Variable(&'static str),
+}
+
+/// Text and variables of a comment; variable names ignore case, so `$USER` works too
+fn split_variables(text: &str) -> Vec<Piece<'_>> {
+    let mut pieces = Vec::new();
+    let mut rest = text;
+    while let Some(index) = rest.find('$') {
+        if index > 0 {
+            pieces.push(Piece::Text(&rest[..index]));
+        }
+        let after = &rest[index + 1..];
+        let variable = COMMENT_VARIABLES.iter().find(|name| {
+            after
+                .get(..name.len())
+                .is_some_and(|prefix| prefix.eq_ignore_ascii_case(name))
+                && !after[name.len()..].starts_with(|c: char| c.is_alphanumeric() || c == '_')
+        });
+        match variable {
+            Some(name) => {
+                pieces.push(Piece::Variable(name));
+                rest = &after[name.len()..];
+            }
+            None => {
+                pieces.push(Piece::Text("$"));
+                rest = after;
+            }
+        }
+    }
+    if !rest.is_empty() {
+        pieces.push(Piece::Text(rest));
+    }
+    pieces
+}
+
+fn expand(text: &str, now: DateTime<Local>, ticket: &str) -> String {
+    split_variables(text)
+        .into_iter()
+        .map(|piece| match piece {
+            Piece::Text(text) => text.to_string(),
+            Piece::Variable(USER) => current_user(),
+            Piece::Variable(DATE) => now.format("%Y-%m-%d").to_string(),
+            Piece::Variable(_) => ticket.to_string(),
+        })
+        .collect()
+}
+
+/// `comment` without an attribution appended when it was saved before, whatever its variables
+/// were filled in with
+///
+/// `expanded` is the attribution as it's filled in now. Only the end of the comment is matched,
+/// as little of it as possible, so the text before the attribution is kept. An attribution made
+/// of variables alone would match whatever the comment ends with, so it's only stripped as
+/// filled in now.
+fn strip_attribution<'a>(comment: &'a str, attribution: &str, expanded: &str) -> &'a str {
+    if let Some(stripped) = comment.strip_suffix(expanded)
+        && (stripped.is_empty() || stripped.ends_with(' '))
+    {
+        return stripped;
+    }
+
+    let pieces = split_variables(attribution);
+    let has_text = pieces
+        .iter()
+        .any(|piece| matches!(piece, Piece::Text(text) if !text.trim().is_empty()));
+    if !has_text {
+        return comment;
+    }
+
+    // Latest start first, for the shortest match
+    let starts = comment
+        .char_indices()
+        .filter(|(_, c)| *c == ' ')
+        .map(|(index, _)| index + 1)
+        .rev()
+        .chain([0]);
+    for start in starts {
+        if matches(&comment[start..], &pieces) {
+            return &comment[..start];
+        }
+    }
+    comment
+}
+
+/// Whether `text` is `pieces` with its variables filled in
+///
+/// Variables are filled in with single words, a user name, a date or a ticket, so they don't
+/// match across spaces.
+fn matches(text: &str, pieces: &[Piece]) -> bool {
+    match pieces.split_first() {
+        None => text.is_empty(),
+        Some((Piece::Text(expected), rest)) => text
+            .strip_prefix(expected)
+            .is_some_and(|text| matches(text, rest)),
+        Some((Piece::Variable(_), rest)) => {
+            let word = text.split(char::is_whitespace).next().unwrap_or_default();
+            word.char_indices()
+                .map(|(index, c)| index + c.len_utf8())
+                .any(|end| matches(&text[end..], rest))
+        }
+    }
+}
+
+/// TTL entered in the settings or the bulk edit dialog, `1` for automatic
+pub fn parse_ttl(text: &str) -> Result<u32, String> {
+    match text.trim().parse() {
+        Ok(ttl) if ttl == 1 || (MIN_TTL..=MAX_TTL).contains(&ttl) => Ok(ttl),
+        _ => Err(format!(
+            "TTL must be 1 for automatic or {} to {} seconds",
+            MIN_TTL, MAX_TTL
+        )),
+    }
+}
+
+/// Name of the user running the app
+pub fn current_user() -> String {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finish(attribution: &str, comment: &str) -> Option<String> {
+        let defaults = RecordDefaults {
+            attribution: attribution.to_string(),
+            ..RecordDefaults::default()
+        };
+        defaults.finish_comment(comment, Local::now(), "")
+    }
+
+    #[test]
+    fn strips_only_the_attribution_at_the_end() {
+        assert_eq!(
+            strip_attribution("Mail relay alice via app", "$user via app", "bob via app"),
+            "Mail relay "
+        );
+        assert_eq!(
+            strip_attribution("alice via app", "$user via app", "bob via app"),
+            ""
+        );
+        assert_eq!(
+            strip_attribution(
+                "Mail relay (alice, 2026-01-02)",
+                "($user, $date)",
+                "(bob, 2026-10-16)"
+            ),
+            "Mail relay "
+        );
+    }
+
+    #[test]
+    fn keeps_comments_without_the_attribution() {
+        assert_eq!(
+            strip_attribution("Mail relay", "$user via app", "alice via app"),
+            "Mail relay"
+        );
+        assert_eq!(
+            strip_attribution("Mail relay", "$user", "alice"),
+            "Mail relay"
+        );
+        assert_eq!(
+            strip_attribution("Mail relay bob", "$user", "alice"),
+            "Mail relay bob"
+        );
+    }
+
+    #[test]
+    fn variable_only_attribution_is_appended() {
+        let saved = finish("$user", "Mail relay").unwrap();
+        assert_eq!(saved, format!("Mail relay {}", current_user()));
+    }
+
+    #[test]
+    fn saving_again_keeps_the_comment() {
+        for attribution in ["$user via app", "$user", "(via app, $user on $date)"] {
+            let saved = finish(attribution, "Mail relay").unwrap();
+            assert!(saved.starts_with("Mail relay"), "{}", saved);
+            assert_eq!(finish(attribution, &saved), Some(saved.clone()));
+        }
+    }
+}
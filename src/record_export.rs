@@ -0,0 +1,89 @@
+//! Export of DNS records as a BIND zone file, CSV, or JSON
+//!
+//! The CSV columns are the ones the CSV import recognizes, so exported files can be imported
+//! into another zone as they are.
+
+use crate::batch::csv_field;
+use crate::cloudflare::DnsRecord;
+use crate::zone_file;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    ZoneFile,
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 3] = [
+        ExportFormat::ZoneFile,
+        ExportFormat::Csv,
+        ExportFormat::Json,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExportFormat::ZoneFile => "BIND Zone File",
+            ExportFormat::Csv => "CSV",
+            ExportFormat::Json => "JSON",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::ZoneFile => "zone",
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+/// Which records of the selected zone an export includes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportScope {
+    All,
+    /// The records matching the search
+    Filtered,
+    Selected,
+}
+
+impl ExportScope {
+    /// Suffix of the suggested file name, e.g. `example.com-selection.csv`
+    pub fn file_suffix(&self) -> &'static str {
+        match self {
+            ExportScope::All => "",
+            ExportScope::Filtered => "-filtered",
+            ExportScope::Selected => "-selection",
+        }
+    }
+}
+
+/// Serialize records of a zone; `header` describes the export where the format has comments
+pub fn export(format: ExportFormat, zone: &str, records: &[DnsRecord], header: &str) -> String {
+    match format {
+        ExportFormat::ZoneFile => zone_file::export(zone, records, header),
+        ExportFormat::Csv => to_csv(records),
+        // Serializing records can't fail
+        ExportFormat::Json => serde_json::to_string_pretty(records).unwrap_or_default(),
+    }
+}
+
+fn to_csv(records: &[DnsRecord]) -> String {
+    let mut csv = String::from("type,name,content,ttl,proxied,priority,comment\n");
+    for record in records {
+        csv.push_str(&format!(
+            "{},{},{},{},{},{},{}\n",
+            record.record_type,
+            csv_field(&record.name),
+            csv_field(&record.data_content()),
+            record.ttl,
+            record.proxied,
+            record
+                .priority
+                .map(|priority| priority.to_string())
+                .unwrap_or_default(),
+            csv_field(record.comment.as_deref().unwrap_or_default())
+        ));
+    }
+    csv
+}
@@ -0,0 +1,225 @@
+//! Single Redirects of a zone, rules of its `http_request_dynamic_redirect` ruleset
+//!
+//! The form matches full URLs against a wildcard pattern, e.g. `https://example.com/old/*`, and
+//! targets either a fixed URL or one filled in from the wildcards with `${1}`, `${2}`, …. Rules
+//! can match with any other expression instead, which is edited with the expression builder.
+
+use crate::cloudflare::RulesetRule;
+use crate::rule_expression::string_literal;
+
+/// Ruleset phase the redirects are rules of
+pub const PHASE: &str = "http_request_dynamic_redirect";
+const REDIRECT: &str = "redirect";
+
+/// Status codes a redirect can respond with
+pub const STATUS_CODES: &[u16] = &[301, 302, 307, 308];
+
+const URI_FIELD: &str = "http.request.full_uri";
+
+/// Editable parts of a redirect besides the description, source and target inputs
+#[derive(Debug, Clone)]
+pub struct RedirectDraft {
+    /// Rule being edited, `None` for a new one
+    pub rule_id: Option<String>,
+    /// Whether the rule matches requests with an expression instead of a URL pattern
+    pub match_expression: bool,
+    pub status_code: u16,
+    pub preserve_query_string: bool,
+    pub enabled: bool,
+}
+
+impl Default for RedirectDraft {
+    fn default() -> Self {
+        Self {
+            rule_id: None,
+            match_expression: false,
+            status_code: 301,
+            preserve_query_string: false,
+            enabled: true,
+        }
+    }
+}
+
+impl RedirectDraft {
+    /// Draft of an existing rule with its source pattern, or expression if it doesn't match a
+    /// pattern, and target URL, `None` for rules the form can't edit, e.g. ones computing the
+    /// target with their own expression
+    pub fn from_rule(rule: &RulesetRule) -> Option<(Self, String, String)> {
+        if rule.action != REDIRECT {
+            return None;
+        }
+        let from_value = &rule.action_parameters["from_value"];
+        let target = target_url(rule)?;
+        let source = source_pattern(&rule.expression);
+
+        let draft = Self {
+            rule_id: Some(rule.id.clone()),
+            match_expression: source.is_none(),
+            status_code: from_value["status_code"].as_u64().unwrap_or(301) as u16,
+            preserve_query_string: from_value["preserve_query_string"]
+                .as_bool()
+                .unwrap_or(false),
+            enabled: rule.enabled,
+        };
+        let source = source.unwrap_or_else(|| rule.expression.clone());
+        Some((draft, source, target))
+    }
+
+    /// The rule to save; `source` is the URL pattern, or the expression if the rule matches one
+    pub fn build(
+        &self,
+        description: &str,
+        source: &str,
+        target: &str,
+    ) -> Result<RulesetRule, String> {
+        let source = source.trim();
+        let expression = if self.match_expression {
+            if source.is_empty() {
+                return Err("Enter the expression of the requests to redirect".to_string());
+            }
+            source.to_string()
+        } else {
+            if source.is_empty() {
+                return Err("Enter the URL pattern to redirect from".to_string());
+            }
+            if source.contains(char::is_whitespace) {
+                return Err("URL patterns can't contain spaces".to_string());
+            }
+            if !source.contains("://") {
+                return Err(
+                    "URL patterns match whole URLs, e.g. https://example.com/old/*".to_string(),
+                );
+            }
+            pattern_expression(source)
+        };
+
+        let target = target.trim();
+        if !target.starts_with("http://") && !target.starts_with("https://") {
+            return Err("The target URL has to start with http:// or https://".to_string());
+        }
+        let target_url = if target.contains("${") {
+            if self.match_expression {
+                return Err("${1}, ${2}, … only work with rules matching a URL pattern".to_string());
+            }
+            serde_json::json!({
+                "expression": format!(
+                    "wildcard_replace({}, {}, {})",
+                    URI_FIELD,
+                    string_literal(source),
+                    string_literal(target)
+                )
+            })
+        } else {
+            serde_json::json!({ "value": target })
+        };
+        if !STATUS_CODES.contains(&self.status_code) {
+            return Err(format!("Redirects can't respond with {}", self.status_code));
+        }
+
+        Ok(RulesetRule {
+            id: self.rule_id.clone().unwrap_or_default(),
+            expression,
+            description: description.trim().to_string(),
+            action: REDIRECT.to_string(),
+            action_parameters: serde_json::json!({
+                "from_value": {
+                    "target_url": target_url,
+                    "status_code": self.status_code,
+                    "preserve_query_string": self.preserve_query_string,
+                }
+            }),
+            enabled: self.enabled,
+        })
+    }
+}
+
+/// What a rule matches: its URL pattern, or its expression
+pub fn source_label(rule: &RulesetRule) -> String {
+    source_pattern(&rule.expression).unwrap_or_else(|| rule.expression.clone())
+}
+
+/// Where a rule redirects to, e.g. `301 to https://example.com/new/${1}, keeping the query string`
+pub fn target_label(rule: &RulesetRule) -> String {
+    if rule.action != REDIRECT {
+        return rule.action.clone();
+    }
+    let from_value = &rule.action_parameters["from_value"];
+    let target = target_url(rule).unwrap_or_else(|| {
+        from_value["target_url"]["expression"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string()
+    });
+    let mut label = format!(
+        "{} to {}",
+        from_value["status_code"].as_u64().unwrap_or(301),
+        target
+    );
+    if from_value["preserve_query_string"].as_bool() == Some(true) {
+        label.push_str(", keeping the query string");
+    }
+    label
+}
+
+/// Expression matching full URLs against a wildcard pattern
+pub fn pattern_expression(pattern: &str) -> String {
+    format!("({} wildcard {})", URI_FIELD, string_literal(pattern))
+}
+
+/// Wildcard pattern of an expression like `(http.request.full_uri wildcard "…")`
+fn source_pattern(expression: &str) -> Option<String> {
+    let expression = expression.trim();
+    let expression = expression
+        .strip_prefix('(')
+        .and_then(|inner| inner.strip_suffix(')'))
+        .unwrap_or(expression)
+        .trim();
+    let rest = expression
+        .strip_prefix(URI_FIELD)?
+        .trim_start()
+        .strip_prefix("wildcard")?
+        .trim_start();
+    let (pattern, rest) = parse_literal(rest)?;
+    rest.trim().is_empty().then_some(pattern)
+}
+
+/// Target of a rule, either a fixed URL or one filled in with `wildcard_replace`
+fn target_url(rule: &RulesetRule) -> Option<String> {
+    let target_url = &rule.action_parameters["from_value"]["target_url"];
+    if let Some(value) = target_url["value"].as_str() {
+        return Some(value.to_string());
+    }
+
+    let rest = target_url["expression"]
+        .as_str()?
+        .trim()
+        .strip_prefix("wildcard_replace(")?
+        .trim_start()
+        .strip_prefix(URI_FIELD)?
+        .trim_start()
+        .strip_prefix(',')?;
+    let (_, rest) = parse_literal(rest.trim_start())?;
+    let rest = rest.trim_start().strip_prefix(',')?;
+    let (target, rest) = parse_literal(rest.trim_start())?;
+    (rest.trim() == ")").then_some(target)
+}
+
+/// The string literal at the start of `text`, quoted or raw, and the text after it
+fn parse_literal(text: &str) -> Option<(String, &str)> {
+    if let Some(raw) = text.strip_prefix("r\"") {
+        let end = raw.find('"')?;
+        return Some((raw[..end].to_string(), &raw[end + 1..]));
+    }
+
+    let quoted = text.strip_prefix('"')?;
+    let mut value = String::new();
+    let mut chars = quoted.char_indices();
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '\\' => value.push(chars.next()?.1),
+            '"' => return Some((value, &quoted[index + 1..])),
+            c => value.push(c),
+        }
+    }
+    None
+}
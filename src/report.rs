@@ -0,0 +1,170 @@
+//! Printable zone reports for client deliverables and compliance archives
+//!
+//! Reports are self-contained HTML documents that open the print dialog when viewed, so
+//! they can be printed or saved as PDF from any browser.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use chrono::{DateTime, Local, Utc};
+
+use crate::cloudflare::{DnsRecord, Zone};
+use crate::portfolio::ZoneHealth;
+
+const STYLE: &str = "
+body { font-family: -apple-system, 'Segoe UI', Helvetica, Arial, sans-serif; color: #111; margin: 2rem; }
+h1 { margin-bottom: 0.25rem; }
+h2 { margin-top: 2rem; border-bottom: 1px solid #ccc; padding-bottom: 0.25rem; }
+.muted { color: #666; }
+table { border-collapse: collapse; width: 100%; font-size: 0.85rem; }
+th, td { text-align: left; padding: 0.3rem 0.5rem; border-bottom: 1px solid #e5e5e5; vertical-align: top; }
+th { background: #f5f5f5; }
+td.content { word-break: break-all; font-family: Menlo, Consolas, monospace; }
+dl { display: grid; grid-template-columns: max-content auto; gap: 0.25rem 1rem; }
+dt { color: #666; }
+.notes { white-space: pre-wrap; }
+@media print { body { margin: 0; } tr { break-inside: avoid; } }
+";
+
+/// Everything shown in a zone report
+pub struct ZoneReport<'a> {
+    pub zone: &'a Zone,
+    pub records: &'a [DnsRecord],
+    pub health: Option<&'a ZoneHealth>,
+    pub tags: &'a [String],
+    /// Free-form audit notes entered when the report was created
+    pub notes: &'a str,
+    pub generated_at: DateTime<Local>,
+}
+
+impl ZoneReport<'_> {
+    pub fn file_name(&self) -> String {
+        format!(
+            "{}-report-{}.html",
+            self.zone.name,
+            self.generated_at.format("%Y-%m-%d")
+        )
+    }
+
+    pub fn to_html(&self) -> String {
+        let mut html = String::new();
+        let zone = self.zone;
+        let generated_at = self.generated_at.format("%Y-%m-%d %H:%M %Z").to_string();
+
+        // Writing to a String can't fail
+        let _ = write!(
+            html,
+            "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>{name} - DNS Report</title>\
+             <style>{STYLE}</style></head><body onload=\"window.print()\">\n\
+             <h1>{name}</h1><div class=\"muted\">DNS report generated {generated_at}</div>\n",
+            name = escape(&zone.name),
+        );
+
+        html.push_str("<h2>Summary</h2><dl>");
+        let mut summary = vec![
+            (
+                "Status",
+                if zone.paused {
+                    "paused".to_string()
+                } else {
+                    zone.status.clone()
+                },
+            ),
+            ("Account", zone.account.name.clone()),
+            (
+                "Plan",
+                zone.plan
+                    .as_ref()
+                    .map(|plan| plan.name.clone())
+                    .unwrap_or_else(|| "Unknown".to_string()),
+            ),
+            ("Records", self.records.len().to_string()),
+            ("Record types", self.record_type_counts()),
+        ];
+        if !self.tags.is_empty() {
+            summary.push(("Tags", self.tags.join(", ")));
+        }
+        if let Some(health) = self.health {
+            summary.push((
+                "DNSSEC",
+                health
+                    .dnssec
+                    .clone()
+                    .unwrap_or_else(|| "Unknown".to_string()),
+            ));
+            let now = Utc::now();
+            for certificate in &health.certificates {
+                summary.push((
+                    certificate.kind.label(),
+                    format!(
+                        "{} - expires {} ({} days)",
+                        certificate.hosts.join(", "),
+                        certificate.expires_on.format("%Y-%m-%d"),
+                        certificate.days_until_expiry(now)
+                    ),
+                ));
+            }
+        }
+        for (label, value) in summary {
+            let _ = write!(html, "<dt>{}</dt><dd>{}</dd>", label, escape(&value));
+        }
+        html.push_str("</dl>\n");
+
+        if !self.notes.trim().is_empty() {
+            let _ = writeln!(
+                html,
+                "<h2>Notes</h2><div class=\"notes\">{}</div>",
+                escape(self.notes.trim())
+            );
+        }
+
+        html.push_str(
+            "<h2>DNS Records</h2><table><thead><tr><th>Type</th><th>Name</th><th>Content</th>\
+             <th>TTL</th><th>Proxied</th><th>Comment</th></tr></thead><tbody>\n",
+        );
+        let mut records: Vec<&DnsRecord> = self.records.iter().collect();
+        records.sort_by(|a, b| {
+            (a.name.as_str(), a.record_type.as_str())
+                .cmp(&(b.name.as_str(), b.record_type.as_str()))
+        });
+        for record in records {
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td>{}</td><td class=\"content\">{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                record.record_type,
+                escape(&record.name),
+                escape(&record.content),
+                if record.ttl == 1 {
+                    "Auto".to_string()
+                } else {
+                    record.ttl.to_string()
+                },
+                if record.proxied { "Yes" } else { "No" },
+                escape(record.comment.as_deref().unwrap_or_default()),
+            );
+        }
+        html.push_str("</tbody></table>\n</body></html>\n");
+
+        html
+    }
+
+    /// e.g. `A: 4, CNAME: 2, TXT: 3`
+    fn record_type_counts(&self) -> String {
+        let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+        for record in self.records {
+            *counts.entry(record.record_type.as_str()).or_default() += 1;
+        }
+        counts
+            .into_iter()
+            .map(|(record_type, count)| format!("{}: {}", record_type, count))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
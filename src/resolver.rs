@@ -0,0 +1,315 @@
+//! "What-if" resolver explaining which records of a zone would answer a DNS query
+//!
+//! Follows the lookup rules of authoritative servers, including wildcards (RFC 4592) and
+//! CNAME chains within the zone, using the zone's records as they are in the app.
+
+use std::collections::HashSet;
+
+use crate::cloudflare::{DnsRecord, DnsRecordType};
+
+/// Longest CNAME chain that is followed before giving up
+const MAX_CNAME_CHAIN: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StepKind {
+    Info,
+    Answer,
+    Warning,
+}
+
+/// One step of the explanation
+#[derive(Debug, Clone)]
+pub struct Step {
+    pub kind: StepKind,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Resolution {
+    /// Fully qualified name that was looked up
+    pub name: String,
+    pub steps: Vec<Step>,
+    /// Records in the answer, in the order they are returned
+    pub answers: Vec<DnsRecord>,
+}
+
+impl Resolution {
+    fn info(&mut self, message: String) {
+        self.steps.push(Step {
+            kind: StepKind::Info,
+            message,
+        });
+    }
+
+    fn warning(&mut self, message: String) {
+        self.steps.push(Step {
+            kind: StepKind::Warning,
+            message,
+        });
+    }
+
+    fn answer(&mut self, record: &DnsRecord, message: String) {
+        self.steps.push(Step {
+            kind: StepKind::Answer,
+            message,
+        });
+        self.answers.push(record.clone());
+    }
+}
+
+/// Explain how a query for `host` would be answered, for `record_type` or any type
+///
+/// Names without the zone's name, like `www`, are treated as relative to the zone.
+pub fn resolve(
+    zone: &str,
+    records: &[DnsRecord],
+    host: &str,
+    record_type: Option<DnsRecordType>,
+) -> Resolution {
+    let zone = normalize(zone);
+    let resolver = Resolver {
+        zone: &zone,
+        records,
+    };
+
+    let mut name = normalize(host);
+    if name.is_empty() || name == "@" {
+        name = zone.clone();
+    } else if !resolver.in_zone(&name) {
+        name = format!("{}.{}", name, zone);
+    }
+
+    let mut resolution = Resolution {
+        name: name.clone(),
+        ..Default::default()
+    };
+    let mut visited = HashSet::new();
+
+    loop {
+        if !resolver.in_zone(&name) {
+            resolution.info(format!(
+                "{} is outside {}, so the answer comes from the servers of that domain",
+                name, zone
+            ));
+            break;
+        }
+        if !visited.insert(name.clone()) {
+            resolution.warning(format!(
+                "CNAME loop: {} was already visited, so the query fails",
+                name
+            ));
+            break;
+        }
+        if visited.len() > MAX_CNAME_CHAIN {
+            resolution.warning(format!(
+                "The CNAME chain is longer than {} names, which most resolvers give up on",
+                MAX_CNAME_CHAIN
+            ));
+            break;
+        }
+
+        let Some(matched) = resolver.lookup(&name, record_type, &mut resolution) else {
+            break;
+        };
+
+        let cname = matched
+            .iter()
+            .find(|record| record.record_type == DnsRecordType::CNAME);
+        if cname.is_some() && matched.len() > 1 {
+            resolution.warning(format!(
+                "{} has a CNAME next to other records; a CNAME must be the only record of a name",
+                name
+            ));
+        }
+
+        if let Some(cname) = cname
+            && record_type != Some(DnsRecordType::CNAME)
+        {
+            let target = normalize(&cname.content);
+            if cname.proxied {
+                resolution.answer(
+                    cname,
+                    format!(
+                        "{} is a proxied CNAME to {}, so Cloudflare answers with its own addresses instead of following the chain",
+                        cname.name, target
+                    ),
+                );
+                break;
+            }
+            resolution.answer(
+                cname,
+                format!("{} is an alias (CNAME) for {}", cname.name, target),
+            );
+            name = target;
+            continue;
+        }
+
+        let answering: Vec<&DnsRecord> = matched
+            .iter()
+            .copied()
+            .filter(|record| record_type.is_none_or(|t| record.record_type == t))
+            .collect();
+        if answering.is_empty() {
+            let types: Vec<&str> = matched
+                .iter()
+                .map(|record| record.record_type.as_str())
+                .collect();
+            resolution.warning(format!(
+                "{} only has {} records, so a query for {} records returns no data",
+                name,
+                types.join(", "),
+                record_type.map(|t| t.as_str()).unwrap_or_default(),
+            ));
+            break;
+        }
+
+        for record in answering {
+            let mut message = format!("{} {} {}", record.record_type, record.name, record.content);
+            if record.name != name {
+                message.push_str(&format!(" (through the wildcard, as {})", name));
+            }
+            if record.proxied {
+                message.push_str("; proxied, so Cloudflare's addresses are returned instead");
+            }
+            resolution.answer(record, message);
+        }
+        break;
+    }
+
+    resolution
+}
+
+struct Resolver<'a> {
+    zone: &'a str,
+    records: &'a [DnsRecord],
+}
+
+impl<'a> Resolver<'a> {
+    fn in_zone(&self, name: &str) -> bool {
+        name == self.zone || name.ends_with(&format!(".{}", self.zone))
+    }
+
+    fn records_named(&self, name: &str) -> Vec<&'a DnsRecord> {
+        self.records
+            .iter()
+            .filter(|record| normalize(&record.name) == name)
+            .collect()
+    }
+
+    /// Whether a name exists, either with records of its own or as the parent of other names
+    fn exists(&self, name: &str) -> bool {
+        let suffix = format!(".{}", name);
+        name == self.zone
+            || self.records.iter().any(|record| {
+                let record_name = normalize(&record.name);
+                record_name == name || record_name.ends_with(&suffix)
+            })
+    }
+
+    /// Records answering for `name`, explaining how they were found; `None` if there are none
+    fn lookup(
+        &self,
+        name: &str,
+        record_type: Option<DnsRecordType>,
+        resolution: &mut Resolution,
+    ) -> Option<Vec<&'a DnsRecord>> {
+        let exact = self.records_named(name);
+        if !exact.is_empty() {
+            resolution.info(format!("{} has {} record(s) of its own", name, exact.len()));
+            self.note_shadowed_wildcard(name, &exact, record_type, resolution);
+            return Some(exact);
+        }
+
+        if self.exists(name) {
+            resolution.warning(format!(
+                "{} has no records, but names below it do, so it exists without data: the query returns no data and wildcards don't apply",
+                name
+            ));
+            return None;
+        }
+
+        let encloser = self.closest_encloser(name);
+        let wildcard_name = format!("*.{}", encloser);
+        let wildcard = self.records_named(&wildcard_name);
+        if wildcard.is_empty() {
+            resolution.warning(format!(
+                "No record is named {} and there is no {} wildcard, so the name doesn't exist (NXDOMAIN)",
+                name, wildcard_name
+            ));
+            // Wildcards higher up don't help once a closer name exists
+            for ancestor in ancestors(&encloser).filter(|ancestor| self.in_zone(ancestor)) {
+                let higher = format!("*.{}", ancestor);
+                if !self.records_named(&higher).is_empty() {
+                    resolution.warning(format!(
+                        "{} doesn't cover {} because {} exists and blocks it",
+                        higher, name, encloser
+                    ));
+                    break;
+                }
+            }
+            return None;
+        }
+
+        resolution.info(format!(
+            "No record is named {}, so the {} wildcard answers for it",
+            name, wildcard_name
+        ));
+        Some(wildcard)
+    }
+
+    /// Longest existing ancestor of a name that doesn't exist itself
+    fn closest_encloser(&self, name: &str) -> String {
+        ancestors(name)
+            .find(|ancestor| self.exists(ancestor))
+            .unwrap_or_else(|| self.zone.to_string())
+    }
+
+    /// Warn when records of `name` keep a wildcard from answering a query it could answer
+    fn note_shadowed_wildcard(
+        &self,
+        name: &str,
+        exact: &[&DnsRecord],
+        record_type: Option<DnsRecordType>,
+        resolution: &mut Resolution,
+    ) {
+        let Some(parent) = ancestors(name).next() else {
+            return;
+        };
+        if !self.in_zone(&parent) {
+            return;
+        }
+        let wildcard_name = format!("*.{}", parent);
+        let wildcard = self.records_named(&wildcard_name);
+        if wildcard.is_empty() {
+            return;
+        }
+
+        let answers_type = |records: &[&DnsRecord]| {
+            records.iter().any(|record| {
+                record_type.is_none_or(|t| record.record_type == t)
+                    || record.record_type == DnsRecordType::CNAME
+            })
+        };
+        if !answers_type(exact) && answers_type(&wildcard) {
+            resolution.warning(format!(
+                "{} shadows the {} wildcard: the wildcard would answer this query, but is ignored because {} has records of its own",
+                name, wildcard_name, name
+            ));
+        } else {
+            resolution.info(format!(
+                "The {} wildcard is ignored for {} because it has records of its own",
+                wildcard_name, name
+            ));
+        }
+    }
+}
+
+/// Parent names of `name`, closest first, e.g. `b.c` and `c` for `a.b.c`
+fn ancestors(name: &str) -> impl Iterator<Item = String> + '_ {
+    name.match_indices('.')
+        .map(|(index, _)| name[index + 1..].to_string())
+}
+
+fn normalize(name: &str) -> String {
+    name.trim().trim_end_matches('.').to_lowercase()
+}
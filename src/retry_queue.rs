@@ -0,0 +1,130 @@
+//! Record changes that failed with a transient error, retried in the background
+//!
+//! Parking a change keeps what was entered in the editor, so a flaky connection doesn't mean
+//! typing it again. Retries back off from 30 seconds up to half an hour; after
+//! [`MAX_AUTOMATIC_ATTEMPTS`] failures, or an error retrying can't fix, the change waits for a
+//! manual retry or to be cancelled.
+
+use chrono::{DateTime, TimeDelta, Utc};
+
+use crate::history::RecordCommand;
+
+/// Failed attempts after which a change is only retried manually
+pub const MAX_AUTOMATIC_ATTEMPTS: u32 = 8;
+const FIRST_BACKOFF_SECONDS: i64 = 30;
+const MAX_BACKOFF_SECONDS: i64 = 30 * 60;
+
+#[derive(Debug, Clone)]
+pub struct RetryItem {
+    pub id: u64,
+    /// e.g. `Create A www.example.com`
+    pub description: String,
+    pub command: RecordCommand,
+    /// Failed attempts, including the one that parked the change
+    pub attempts: u32,
+    pub last_error: String,
+    /// When the change is retried automatically, `None` once only manual retries are left
+    pub next_attempt: Option<DateTime<Utc>>,
+    pub running: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct RetryQueue {
+    items: Vec<RetryItem>,
+    next_id: u64,
+}
+
+impl RetryQueue {
+    pub fn items(&self) -> &[RetryItem] {
+        &self.items
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Park a change that just failed with `error`
+    pub fn push(
+        &mut self,
+        description: String,
+        command: RecordCommand,
+        error: String,
+        now: DateTime<Utc>,
+    ) {
+        self.next_id += 1;
+        self.items.push(RetryItem {
+            id: self.next_id,
+            description,
+            command,
+            attempts: 1,
+            last_error: error,
+            next_attempt: Some(now + backoff(1)),
+            running: false,
+        });
+    }
+
+    /// Changes whose automatic retry is due
+    pub fn due(&self, now: DateTime<Utc>) -> Vec<u64> {
+        self.items
+            .iter()
+            .filter(|item| !item.running && item.next_attempt.is_some_and(|next| next <= now))
+            .map(|item| item.id)
+            .collect()
+    }
+
+    /// Mark a change as running, returning it unless it's running already or gone
+    pub fn start(&mut self, id: u64) -> Option<RetryItem> {
+        let item = self
+            .items
+            .iter_mut()
+            .find(|item| item.id == id && !item.running)?;
+        item.running = true;
+        Some(item.clone())
+    }
+
+    /// Drop a change that went through
+    pub fn succeeded(&mut self, id: u64) {
+        self.items.retain(|item| item.id != id);
+    }
+
+    /// Schedule the next attempt after a failed retry; `transient` errors are retried again
+    pub fn failed(&mut self, id: u64, error: String, transient: bool, now: DateTime<Utc>) {
+        let Some(item) = self.items.iter_mut().find(|item| item.id == id) else {
+            return;
+        };
+        item.running = false;
+        item.attempts += 1;
+        item.last_error = error;
+        item.next_attempt = (transient && item.attempts < MAX_AUTOMATIC_ATTEMPTS)
+            .then(|| now + backoff(item.attempts));
+    }
+
+    pub fn cancel(&mut self, id: u64) {
+        self.items.retain(|item| item.id != id || item.running);
+    }
+
+    pub fn clear(&mut self) {
+        self.items.clear();
+    }
+}
+
+/// Wait before the attempt after `attempts` failed ones, doubling each time
+pub fn backoff(attempts: u32) -> TimeDelta {
+    let seconds = FIRST_BACKOFF_SECONDS
+        .saturating_mul(1 << attempts.saturating_sub(1).min(16))
+        .min(MAX_BACKOFF_SECONDS);
+    TimeDelta::seconds(seconds)
+}
+
+/// Whether an error is likely to go away by itself, e.g. a dropped connection or an outage
+pub fn is_transient(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| {
+        cause.downcast_ref::<reqwest::Error>().is_some_and(|e| {
+            e.is_connect()
+                || e.is_timeout()
+                || e.is_request()
+                || e.is_decode()
+                || e.status().is_some_and(|status| status.is_server_error())
+        })
+    })
+}
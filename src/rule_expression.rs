@@ -0,0 +1,1006 @@
+//! Cloudflare's Rules language, in which ruleset rules express the requests they apply to
+//!
+//! The expression builder edits expressions made of conditions on one field each, e.g.
+//! `http.request.uri.path contains "/admin"`, combined into groups that match when all their
+//! conditions do; the expression matches when any of its groups does. Other expressions are
+//! edited as text, which is checked before saving to point out where it is invalid.
+
+use std::fmt;
+use std::net::IpAddr;
+
+/// What the values of a field are
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldKind {
+    Text,
+    Ip,
+    Number,
+}
+
+/// Field the builder offers conditions on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Field {
+    pub name: &'static str,
+    pub label: &'static str,
+    pub kind: FieldKind,
+}
+
+pub const FIELDS: &[Field] = &[
+    Field {
+        name: "http.host",
+        label: "Hostname",
+        kind: FieldKind::Text,
+    },
+    Field {
+        name: "http.request.uri.path",
+        label: "URI Path",
+        kind: FieldKind::Text,
+    },
+    Field {
+        name: "http.request.uri.query",
+        label: "URI Query String",
+        kind: FieldKind::Text,
+    },
+    Field {
+        name: "http.request.full_uri",
+        label: "Full URI",
+        kind: FieldKind::Text,
+    },
+    Field {
+        name: "http.request.method",
+        label: "Request Method",
+        kind: FieldKind::Text,
+    },
+    Field {
+        name: "http.user_agent",
+        label: "User Agent",
+        kind: FieldKind::Text,
+    },
+    Field {
+        name: "http.referer",
+        label: "Referer",
+        kind: FieldKind::Text,
+    },
+    Field {
+        name: "http.cookie",
+        label: "Cookie",
+        kind: FieldKind::Text,
+    },
+    Field {
+        name: "ip.src",
+        label: "IP Source Address",
+        kind: FieldKind::Ip,
+    },
+    Field {
+        name: "ip.src.country",
+        label: "Country",
+        kind: FieldKind::Text,
+    },
+    Field {
+        name: "ip.src.continent",
+        label: "Continent",
+        kind: FieldKind::Text,
+    },
+    Field {
+        name: "ip.src.asnum",
+        label: "AS Number",
+        kind: FieldKind::Number,
+    },
+];
+
+impl Field {
+    /// A value of the field in the Rules language, e.g. `"/admin"` or `192.0.2.0/24`
+    fn literal(&self, value: &str) -> Result<String, String> {
+        match self.kind {
+            FieldKind::Text => Ok(string_literal(value)),
+            FieldKind::Ip if is_ip(value) => Ok(value.to_string()),
+            FieldKind::Ip => Err(format!("{} is not an IP address or range", value)),
+            FieldKind::Number if value.parse::<i64>().is_ok() => Ok(value.to_string()),
+            FieldKind::Number => Err(format!("{} is not a number", value)),
+        }
+    }
+
+    /// Whether the comparison with the keyword applies to the field
+    fn check_operator(&self, operator: &str) -> Result<(), String> {
+        let text_only = matches!(
+            operator,
+            "contains" | "matches" | "wildcard" | "strict wildcard"
+        );
+        if text_only && self.kind != FieldKind::Text {
+            return Err(format!("{} can't be used with {}", operator, self.name));
+        }
+        Ok(())
+    }
+
+    /// Whether the field can be compared with the value
+    fn check_value(&self, value: &Value) -> Result<(), String> {
+        let values = match value {
+            Value::Set(values) => values.as_slice(),
+            Value::List(_) => return Ok(()),
+            value => std::slice::from_ref(value),
+        };
+        let fits = |value: &Value| match (self.kind, value) {
+            (FieldKind::Text, Value::String(_)) => true,
+            (FieldKind::Ip, Value::Ip(_)) => true,
+            (FieldKind::Number, Value::Int(_)) => true,
+            // Ranges are either of addresses or of numbers
+            (FieldKind::Ip | FieldKind::Number, Value::Range(range)) => {
+                let addresses = range
+                    .split_once("..")
+                    .is_some_and(|(start, _)| start.parse::<IpAddr>().is_ok());
+                addresses == (self.kind == FieldKind::Ip)
+            }
+            _ => false,
+        };
+        if values.iter().all(fits) {
+            return Ok(());
+        }
+        Err(match self.kind {
+            FieldKind::Text => format!("{} is compared with strings in quotes", self.name),
+            FieldKind::Ip => format!("{} is compared with IP addresses or ranges", self.name),
+            FieldKind::Number => format!("{} is compared with numbers", self.name),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Equals,
+    NotEquals,
+    Contains,
+    Wildcard,
+    Matches,
+    StartsWith,
+    EndsWith,
+    In,
+    NotIn,
+    GreaterThan,
+    GreaterOrEqual,
+    LessThan,
+    LessOrEqual,
+}
+
+impl Operator {
+    pub const ALL: &[Operator] = &[
+        Operator::Equals,
+        Operator::NotEquals,
+        Operator::Contains,
+        Operator::Wildcard,
+        Operator::Matches,
+        Operator::StartsWith,
+        Operator::EndsWith,
+        Operator::In,
+        Operator::NotIn,
+        Operator::GreaterThan,
+        Operator::GreaterOrEqual,
+        Operator::LessThan,
+        Operator::LessOrEqual,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Operator::Equals => "equals",
+            Operator::NotEquals => "does not equal",
+            Operator::Contains => "contains",
+            Operator::Wildcard => "wildcard",
+            Operator::Matches => "matches regex",
+            Operator::StartsWith => "starts with",
+            Operator::EndsWith => "ends with",
+            Operator::In => "is in",
+            Operator::NotIn => "is not in",
+            Operator::GreaterThan => "greater than",
+            Operator::GreaterOrEqual => "greater than or equal to",
+            Operator::LessThan => "less than",
+            Operator::LessOrEqual => "less than or equal to",
+        }
+    }
+
+    /// Whether the operator compares values of a kind
+    pub fn applies_to(self, kind: FieldKind) -> bool {
+        match self {
+            Operator::Equals | Operator::NotEquals | Operator::In | Operator::NotIn => true,
+            Operator::Contains
+            | Operator::Wildcard
+            | Operator::Matches
+            | Operator::StartsWith
+            | Operator::EndsWith => kind == FieldKind::Text,
+            Operator::GreaterThan
+            | Operator::GreaterOrEqual
+            | Operator::LessThan
+            | Operator::LessOrEqual => kind == FieldKind::Number,
+        }
+    }
+
+    /// Whether the value is a list of values separated by spaces
+    pub fn takes_list(self) -> bool {
+        matches!(self, Operator::In | Operator::NotIn)
+    }
+
+    /// The operator comparing a field with a value, e.g. `eq`, `None` for the ones written as
+    /// a set or a function
+    fn keyword(self) -> Option<&'static str> {
+        match self {
+            Operator::Equals => Some("eq"),
+            Operator::NotEquals => Some("ne"),
+            Operator::Contains => Some("contains"),
+            Operator::Wildcard => Some("wildcard"),
+            Operator::Matches => Some("matches"),
+            Operator::GreaterThan => Some("gt"),
+            Operator::GreaterOrEqual => Some("ge"),
+            Operator::LessThan => Some("lt"),
+            Operator::LessOrEqual => Some("le"),
+            Operator::StartsWith | Operator::EndsWith | Operator::In | Operator::NotIn => None,
+        }
+    }
+
+    /// The function testing a field, e.g. `starts_with`
+    fn function(self) -> Option<&'static str> {
+        match self {
+            Operator::StartsWith => Some("starts_with"),
+            Operator::EndsWith => Some("ends_with"),
+            _ => None,
+        }
+    }
+}
+
+/// A condition of the builder, its value as entered
+#[derive(Debug, Clone, PartialEq)]
+pub struct Condition {
+    pub field: Field,
+    pub operator: Operator,
+    pub value: String,
+}
+
+impl Condition {
+    /// The condition in the Rules language
+    pub fn expression(&self) -> Result<String, String> {
+        let value = self.value.trim();
+        if value.is_empty() {
+            return Err(format!("Enter a value for {}", self.field.label));
+        }
+        let name = self.field.name;
+
+        if self.operator.takes_list() {
+            let values = value
+                .split_whitespace()
+                .map(|value| self.field.literal(value))
+                .collect::<Result<Vec<_>, _>>()?;
+            let set = format!("{} in {{{}}}", name, values.join(" "));
+            return Ok(if self.operator == Operator::NotIn {
+                format!("not {}", set)
+            } else {
+                set
+            });
+        }
+        let literal = self.field.literal(value)?;
+        match (self.operator.keyword(), self.operator.function()) {
+            (Some(keyword), _) => Ok(format!("{} {} {}", name, keyword, literal)),
+            (_, Some(function)) => Ok(format!("{}({}, {})", function, name, literal)),
+            _ => unreachable!("Every operator is a keyword, a function or a set"),
+        }
+    }
+}
+
+/// The expression of the builder's groups of conditions
+pub fn build(groups: &[Vec<Condition>]) -> Result<String, String> {
+    let groups = groups
+        .iter()
+        .filter(|group| !group.is_empty())
+        .map(|group| {
+            let conditions = group
+                .iter()
+                .map(Condition::expression)
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(format!("({})", conditions.join(" and ")))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    if groups.is_empty() {
+        return Err("Add a condition on the requests the rule applies to".to_string());
+    }
+    Ok(groups.join(" or "))
+}
+
+/// The builder's groups of conditions making up an expression, `None` if it uses more of the
+/// language than the builder shows
+pub fn conditions(expression: &str) -> Option<Vec<Vec<Condition>>> {
+    let groups = match parse(expression).ok()? {
+        Expr::Or(groups) => groups,
+        expr => vec![expr],
+    };
+    groups
+        .into_iter()
+        .map(|group| {
+            let conditions = match group {
+                Expr::And(conditions) => conditions,
+                expr => vec![expr],
+            };
+            conditions.into_iter().map(condition).collect()
+        })
+        .collect()
+}
+
+fn condition(expr: Expr) -> Option<Condition> {
+    let (operand, operator, value) = match expr {
+        Expr::Not(inner) => match *inner {
+            Expr::Compare {
+                operand,
+                operator: "in",
+                value,
+            } => (operand, Operator::NotIn, value),
+            _ => return None,
+        },
+        Expr::Compare {
+            operand,
+            operator,
+            value,
+        } => {
+            let operator = if operator == "in" {
+                Operator::In
+            } else {
+                *Operator::ALL
+                    .iter()
+                    .find(|candidate| candidate.keyword() == Some(operator))?
+            };
+            (operand, operator, value)
+        }
+        Expr::Test(Operand::Call { name, mut args }) if args.len() == 2 => {
+            let operator = *Operator::ALL
+                .iter()
+                .find(|candidate| candidate.function() == Some(name.as_str()))?;
+            let value = match args.pop()? {
+                Argument::Value(value) => value,
+                Argument::Operand(_) => return None,
+            };
+            let operand = match args.pop()? {
+                Argument::Operand(operand) => operand,
+                Argument::Value(_) => return None,
+            };
+            (operand, operator, value)
+        }
+        _ => return None,
+    };
+
+    let Operand::Field(name) = operand else {
+        return None;
+    };
+    let field = *FIELDS.iter().find(|field| field.name == name)?;
+    if !operator.applies_to(field.kind) {
+        return None;
+    }
+    let value = match value {
+        Value::Set(values) if operator.takes_list() => values
+            .iter()
+            .map(|value| field_value(field, value))
+            .collect::<Option<Vec<_>>>()?
+            .join(" "),
+        value if !operator.takes_list() => field_value(field, &value)?,
+        _ => return None,
+    };
+
+    Some(Condition {
+        field,
+        operator,
+        value,
+    })
+}
+
+/// A value as entered in the builder, if it is one of the field's
+fn field_value(field: Field, value: &Value) -> Option<String> {
+    match (field.kind, value) {
+        // Values of a list are separated by spaces
+        (FieldKind::Text, Value::String(value))
+            if !value.is_empty() && !value.contains(char::is_whitespace) =>
+        {
+            Some(value.clone())
+        }
+        (FieldKind::Ip, Value::Ip(value)) => Some(value.clone()),
+        (FieldKind::Number, Value::Int(value)) => Some(value.to_string()),
+        _ => None,
+    }
+}
+
+/// A string literal of the Rules language
+pub fn string_literal(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn is_ip(value: &str) -> bool {
+    match value.split_once('/') {
+        Some((address, prefix)) => {
+            let Ok(address) = address.parse::<IpAddr>() else {
+                return false;
+            };
+            let max = if address.is_ipv4() { 32 } else { 128 };
+            prefix.parse::<u8>().is_ok_and(|prefix| prefix <= max)
+        }
+        None => value.parse::<IpAddr>().is_ok(),
+    }
+}
+
+/// A parsed expression
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Or(Vec<Expr>),
+    Xor(Vec<Expr>),
+    And(Vec<Expr>),
+    Not(Box<Expr>),
+    /// A comparison, e.g. `http.host eq "example.com"`, with the operator's keyword
+    Compare {
+        operand: Operand,
+        operator: &'static str,
+        value: Value,
+    },
+    /// A boolean field or function, e.g. `ssl` or `starts_with(http.host, "www.")`
+    Test(Operand),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operand {
+    /// A field, with the keys and indexes it is accessed with, e.g. `http.request.headers["a"]`
+    Field(String),
+    Call {
+        name: String,
+        args: Vec<Argument>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Argument {
+    Operand(Operand),
+    Value(Value),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    String(String),
+    Int(i64),
+    /// An IP address or range
+    Ip(String),
+    /// A range of numbers or addresses in a set, e.g. `80..443`
+    Range(String),
+    Set(Vec<Value>),
+    /// A named list, e.g. `$blocked_ips`
+    List(String),
+}
+
+/// What makes an expression invalid, and where
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyntaxError {
+    /// Byte offset in the expression
+    pub offset: usize,
+    pub message: String,
+}
+
+impl SyntaxError {
+    fn new(offset: usize, message: impl Into<String>) -> Self {
+        Self {
+            offset,
+            message: message.into(),
+        }
+    }
+
+    /// Line and column of the error in `expression`, both starting at 1
+    pub fn line_column(&self, expression: &str) -> (usize, usize) {
+        let before = &expression[..self.offset.min(expression.len())];
+        let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+        (
+            before.matches('\n').count() + 1,
+            before[line_start..].chars().count() + 1,
+        )
+    }
+
+    /// The error along with its line and column, e.g. for a failed save
+    pub fn describe(&self, expression: &str) -> String {
+        let (line, column) = self.line_column(expression);
+        format!(
+            "Invalid expression at line {}, column {}: {}",
+            line, column, self.message
+        )
+    }
+}
+
+impl fmt::Display for SyntaxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// Comparison operators with the keyword they are normalized to
+const COMPARISONS: &[(&str, &str)] = &[
+    ("eq", "eq"),
+    ("==", "eq"),
+    ("ne", "ne"),
+    ("!=", "ne"),
+    ("lt", "lt"),
+    ("<", "lt"),
+    ("le", "le"),
+    ("<=", "le"),
+    ("gt", "gt"),
+    (">", "gt"),
+    ("ge", "ge"),
+    (">=", "ge"),
+    ("contains", "contains"),
+    ("matches", "matches"),
+    ("~", "matches"),
+    ("wildcard", "wildcard"),
+    ("in", "in"),
+];
+
+const SYMBOLS: &[&str] = &[
+    "==", "!=", "<=", ">=", "&&", "||", "^^", "<", ">", "~", "!", "(", ")", "{", "}", "[", "]",
+    ",", "*",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// A field, function, keyword, number or address
+    Word(String),
+    String(String),
+    Symbol(&'static str),
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Token::Word(word) => word.clone(),
+            Token::String(value) => string_literal(value),
+            Token::Symbol(symbol) => symbol.to_string(),
+        }
+    }
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | ':' | '/' | '$')
+}
+
+/// The tokens of an expression, each with its byte offset
+fn tokenize(text: &str) -> Result<Vec<(usize, Token)>, SyntaxError> {
+    let mut tokens = Vec::new();
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        let offset = text.len() - rest.len();
+        if c.is_whitespace() {
+            rest = &rest[c.len_utf8()..];
+        } else if c == '"' {
+            let (value, after) = quoted_string(&rest[1..])
+                .ok_or_else(|| SyntaxError::new(offset, "A string is missing its closing quote"))?;
+            tokens.push((offset, Token::String(value)));
+            rest = after;
+        } else if let Some(raw) = rest
+            .strip_prefix('r')
+            .filter(|raw| raw.starts_with(['"', '#']))
+        {
+            let (value, after) = raw_string(raw).ok_or_else(|| {
+                SyntaxError::new(offset, "A raw string is missing its closing quote")
+            })?;
+            tokens.push((offset, Token::String(value)));
+            rest = after;
+        } else if let Some(symbol) = SYMBOLS
+            .iter()
+            .copied()
+            .find(|symbol| rest.starts_with(symbol))
+        {
+            tokens.push((offset, Token::Symbol(symbol)));
+            rest = &rest[symbol.len()..];
+        } else if is_word_char(c) {
+            let end = rest.find(|c| !is_word_char(c)).unwrap_or(rest.len());
+            tokens.push((offset, Token::Word(rest[..end].to_string())));
+            rest = &rest[end..];
+        } else {
+            return Err(SyntaxError::new(offset, format!("Unexpected {}", c)));
+        }
+    }
+    Ok(tokens)
+}
+
+/// The string up to the closing quote, and the text after it
+fn quoted_string(text: &str) -> Option<(String, &str)> {
+    let mut value = String::new();
+    let mut chars = text.char_indices();
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '\\' => value.push(chars.next()?.1),
+            '"' => return Some((value, &text[index + 1..])),
+            c => value.push(c),
+        }
+    }
+    None
+}
+
+/// A raw string after its `r`, e.g. `#"…"#`, and the text after it
+fn raw_string(text: &str) -> Option<(String, &str)> {
+    let hashes = text.len() - text.trim_start_matches('#').len();
+    let body = text[hashes..].strip_prefix('"')?;
+    let end = format!("\"{}", "#".repeat(hashes));
+    let close = body.find(&end)?;
+    Some((body[..close].to_string(), &body[close + end.len()..]))
+}
+
+/// Parse an expression, failing with its first syntax error, or a comparison of one of the
+/// builder's fields with a value of another type
+pub fn parse(expression: &str) -> Result<Expr, SyntaxError> {
+    let mut parser = Parser {
+        tokens: tokenize(expression)?,
+        position: 0,
+        end: expression.len(),
+    };
+    if parser.tokens.is_empty() {
+        return Err(SyntaxError::new(0, "The expression is empty"));
+    }
+    let expr = parser.or()?;
+    match parser.peek() {
+        None => Ok(expr),
+        Some(token) => Err(parser.error(format!(
+            "Expected and, or or a closing parenthesis before {}",
+            token.describe()
+        ))),
+    }
+}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    position: usize,
+    /// Length of the expression, where errors at its end are
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(_, token)| token)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.peek().cloned();
+        self.position += 1;
+        token
+    }
+
+    /// Offset of the next token
+    fn offset(&self) -> usize {
+        self.tokens
+            .get(self.position)
+            .map_or(self.end, |(offset, _)| *offset)
+    }
+
+    /// An error at the next token
+    fn error(&self, message: impl Into<String>) -> SyntaxError {
+        SyntaxError::new(self.offset(), message)
+    }
+
+    /// Whether the next token is one of the given words or symbols, consuming it if so
+    fn eat(&mut self, options: &[&str]) -> bool {
+        let found = match self.peek() {
+            Some(Token::Word(word)) => options.contains(&word.as_str()),
+            Some(Token::Symbol(symbol)) => options.contains(symbol),
+            _ => false,
+        };
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, symbol: &str, context: &str) -> Result<(), SyntaxError> {
+        if self.eat(&[symbol]) {
+            return Ok(());
+        }
+        Err(self.error(match self.peek() {
+            Some(token) => format!(
+                "Expected {} {}, found {}",
+                symbol,
+                context,
+                token.describe()
+            ),
+            None => format!("Expected {} {}", symbol, context),
+        }))
+    }
+
+    fn unexpected(&self, expected: &str) -> SyntaxError {
+        self.error(match self.peek() {
+            Some(token) => format!("Expected {}, found {}", expected, token.describe()),
+            None => format!("The expression ends where {} was expected", expected),
+        })
+    }
+
+    fn or(&mut self) -> Result<Expr, SyntaxError> {
+        let mut items = vec![self.xor()?];
+        while self.eat(&["or", "||"]) {
+            items.push(self.xor()?);
+        }
+        Ok(if items.len() == 1 {
+            items.remove(0)
+        } else {
+            Expr::Or(items)
+        })
+    }
+
+    fn xor(&mut self) -> Result<Expr, SyntaxError> {
+        let mut items = vec![self.and()?];
+        while self.eat(&["xor", "^^"]) {
+            items.push(self.and()?);
+        }
+        Ok(if items.len() == 1 {
+            items.remove(0)
+        } else {
+            Expr::Xor(items)
+        })
+    }
+
+    fn and(&mut self) -> Result<Expr, SyntaxError> {
+        let mut items = vec![self.unary()?];
+        while self.eat(&["and", "&&"]) {
+            items.push(self.unary()?);
+        }
+        Ok(if items.len() == 1 {
+            items.remove(0)
+        } else {
+            Expr::And(items)
+        })
+    }
+
+    fn unary(&mut self) -> Result<Expr, SyntaxError> {
+        if self.eat(&["not", "!"]) {
+            return Ok(Expr::Not(Box::new(self.unary()?)));
+        }
+        if self.eat(&["("]) {
+            let expr = self.or()?;
+            self.expect(")", "to close the parenthesis")?;
+            return Ok(expr);
+        }
+
+        let operand = self.operand()?;
+        let field = match &operand {
+            Operand::Field(name) => FIELDS.iter().find(|field| field.name == name),
+            Operand::Call { .. } => None,
+        };
+        let operator_offset = self.offset();
+        let Some(operator) = self.comparison() else {
+            return Ok(Expr::Test(operand));
+        };
+        if let Some(field) = field {
+            field
+                .check_operator(operator)
+                .map_err(|message| SyntaxError::new(operator_offset, message))?;
+        }
+        let value_offset = self.offset();
+        let value = if operator == "in" {
+            self.set()?
+        } else {
+            self.scalar()?
+        };
+        if let Some(field) = field {
+            field
+                .check_value(&value)
+                .map_err(|message| SyntaxError::new(value_offset, message))?;
+        }
+        Ok(Expr::Compare {
+            operand,
+            operator,
+            value,
+        })
+    }
+
+    /// The normalized keyword of the comparison operator next, consuming it
+    fn comparison(&mut self) -> Option<&'static str> {
+        if self.eat(&["strict"]) {
+            // `strict wildcard` is the case-sensitive wildcard
+            return self.eat(&["wildcard"]).then_some("strict wildcard");
+        }
+        let (_, keyword) = COMPARISONS.iter().find(|(operator, _)| match self.peek() {
+            Some(Token::Word(word)) => word == operator,
+            Some(Token::Symbol(symbol)) => symbol == operator,
+            _ => false,
+        })?;
+        self.position += 1;
+        Some(keyword)
+    }
+
+    fn operand(&mut self) -> Result<Operand, SyntaxError> {
+        let name = match self.peek() {
+            Some(Token::Word(word)) if word.starts_with(|c: char| c.is_ascii_alphabetic()) => {
+                word.clone()
+            }
+            _ => return Err(self.unexpected("a field")),
+        };
+        self.position += 1;
+
+        if self.eat(&["("]) {
+            let mut args = Vec::new();
+            if !self.eat(&[")"]) {
+                loop {
+                    args.push(self.argument()?);
+                    if self.eat(&[")"]) {
+                        break;
+                    }
+                    self.expect(",", "between the arguments of a function")?;
+                }
+            }
+            return Ok(Operand::Call { name, args });
+        }
+
+        let mut field = name;
+        while self.eat(&["["]) {
+            let key = match self.next() {
+                Some(Token::String(key)) => string_literal(&key),
+                Some(Token::Word(index)) if index.parse::<u32>().is_ok() => index,
+                Some(Token::Symbol("*")) => "*".to_string(),
+                _ => {
+                    self.position -= 1;
+                    return Err(self.unexpected("a key or an index"));
+                }
+            };
+            self.expect("]", "after the key")?;
+            field.push_str(&format!("[{}]", key));
+        }
+        Ok(Operand::Field(field))
+    }
+
+    fn argument(&mut self) -> Result<Argument, SyntaxError> {
+        match self.peek() {
+            Some(Token::String(_)) => Ok(Argument::Value(self.scalar()?)),
+            Some(Token::Word(word)) if !word.starts_with(|c: char| c.is_ascii_alphabetic()) => {
+                Ok(Argument::Value(self.scalar()?))
+            }
+            _ => Ok(Argument::Operand(self.operand()?)),
+        }
+    }
+
+    /// A string, number or address
+    fn scalar(&mut self) -> Result<Value, SyntaxError> {
+        let value = match self.peek() {
+            Some(Token::String(value)) => Value::String(value.clone()),
+            Some(Token::Word(word)) => match literal_word(word) {
+                Some(value @ (Value::Int(_) | Value::Ip(_))) => value,
+                _ => return Err(self.unexpected("a value")),
+            },
+            _ => return Err(self.unexpected("a value")),
+        };
+        self.position += 1;
+        Ok(value)
+    }
+
+    /// The values after `in`: a set in braces or a named list
+    fn set(&mut self) -> Result<Value, SyntaxError> {
+        if let Some(Token::Word(word)) = self.peek()
+            && let Some(name) = word.strip_prefix('$')
+        {
+            let list = Value::List(name.to_string());
+            self.position += 1;
+            return Ok(list);
+        }
+        self.expect("{", "after in")?;
+
+        let mut values = Vec::new();
+        let start = self.offset();
+        while !self.eat(&["}"]) {
+            let value = match self.peek() {
+                Some(Token::String(value)) => Value::String(value.clone()),
+                Some(Token::Word(word)) => {
+                    literal_word(word).ok_or_else(|| self.unexpected("a value"))?
+                }
+                _ => return Err(self.unexpected("a value or }")),
+            };
+            self.position += 1;
+            values.push(value);
+        }
+        if values.is_empty() {
+            return Err(SyntaxError::new(start, "A set needs at least one value"));
+        }
+        Ok(Value::Set(values))
+    }
+}
+
+/// The number, address or range a word is
+fn literal_word(word: &str) -> Option<Value> {
+    if let Ok(number) = word.parse::<i64>() {
+        return Some(Value::Int(number));
+    }
+    if is_ip(word) {
+        return Some(Value::Ip(word.to_string()));
+    }
+    let (start, end) = word.split_once("..")?;
+    let numbers = start.parse::<i64>().is_ok() && end.parse::<i64>().is_ok();
+    let addresses = start.parse::<IpAddr>().is_ok() && end.parse::<IpAddr>().is_ok();
+    (numbers || addresses).then(|| Value::Range(word.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn condition(field: &str, operator: Operator, value: &str) -> Condition {
+        Condition {
+            field: *FIELDS.iter().find(|f| f.name == field).unwrap(),
+            operator,
+            value: value.to_string(),
+        }
+    }
+
+    #[test]
+    fn builds_groups_of_conditions() {
+        let groups = vec![
+            vec![
+                condition("http.request.uri.path", Operator::StartsWith, "/admin"),
+                condition("ip.src.country", Operator::NotIn, "DE AT"),
+            ],
+            vec![condition("ip.src", Operator::Equals, "192.0.2.0/24")],
+        ];
+        let expression = build(&groups).unwrap();
+        assert_eq!(
+            expression,
+            "(starts_with(http.request.uri.path, \"/admin\") and not ip.src.country in \
+             {\"DE\" \"AT\"}) or (ip.src eq 192.0.2.0/24)"
+        );
+        assert_eq!(conditions(&expression), Some(groups));
+    }
+
+    #[test]
+    fn rejects_values_not_of_the_field() {
+        let groups = vec![vec![condition("ip.src", Operator::In, "192.0.2.1 example")]];
+        assert_eq!(
+            build(&groups),
+            Err("example is not an IP address or range".to_string())
+        );
+    }
+
+    #[test]
+    fn leaves_other_expressions_to_the_text_editor() {
+        let expression =
+            "(http.request.headers[\"x-debug\"][0] eq \"1\" xor ssl) or ip.src in $office";
+        assert!(parse(expression).is_ok());
+        assert_eq!(conditions(expression), None);
+    }
+
+    #[test]
+    fn describes_syntax_errors() {
+        assert_eq!(
+            parse("(http.host eq \"example.com\""),
+            Err(SyntaxError::new(27, "Expected ) to close the parenthesis"))
+        );
+        assert_eq!(
+            parse("http.host eq"),
+            Err(SyntaxError::new(
+                12,
+                "The expression ends where a value was expected"
+            ))
+        );
+        assert_eq!(
+            parse("http.host eq \"a\" http.host eq \"b\""),
+            Err(SyntaxError::new(
+                17,
+                "Expected and, or or a closing parenthesis before http.host"
+            ))
+        );
+    }
+
+    #[test]
+    fn rejects_values_of_another_type() {
+        assert_eq!(
+            parse("ip.src eq \"192.0.2.1\""),
+            Err(SyntaxError::new(
+                10,
+                "ip.src is compared with IP addresses or ranges"
+            ))
+        );
+        assert_eq!(
+            parse("ip.src.asnum in {13335 100..200}").map(|_| ()),
+            Ok(())
+        );
+        assert_eq!(
+            parse("ip.src contains \"192\""),
+            Err(SyntaxError::new(7, "contains can't be used with ip.src"))
+        );
+    }
+
+    #[test]
+    fn locates_errors_by_line_and_column() {
+        let expression = "http.host eq \"a\"\nand ip.src eq 1";
+        let error = parse(expression).unwrap_err();
+        assert_eq!(error.line_column(expression), (2, 15));
+        assert_eq!(
+            error.describe(expression),
+            "Invalid expression at line 2, column 15: \
+             ip.src is compared with IP addresses or ranges"
+        );
+    }
+}
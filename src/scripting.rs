@@ -0,0 +1,180 @@
+//! Script console for one-off bulk changes, written in Rhai
+//!
+//! Scripts don't call the API themselves: `create`, `update` and `delete` only collect
+//! changes, which are reviewed and then applied as a bulk operation.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use rhai::serde::{from_dynamic, to_dynamic};
+use rhai::{Dynamic, Engine, EvalAltResult, ImmutableString, Map, Scope};
+use serde::{Deserialize, Serialize};
+
+use crate::batch::BatchAction;
+use crate::cloudflare::{CreateDnsRecord, DnsRecord, UpdateDnsRecord};
+
+/// Scripts are stopped after this many operations, so endless loops can't hang the app
+const MAX_OPERATIONS: u64 = 1_000_000;
+
+/// Shown in the console before a script is loaded
+pub const EXAMPLE_SCRIPT: &str = r#"// `records` holds the records of the selected zone and `zone` its name.
+// create(#{ type, name, content, ttl, proxied, comment }), update(id, #{ ... })
+// and delete(id) collect changes, which are applied after you review them.
+for record in records {
+    if record.type == "A" && record.content == "192.0.2.1" {
+        print(`Moving ${record.name}`);
+        update(record.id, #{ content: "192.0.2.2" });
+    }
+}
+"#;
+
+/// A script saved in the script library
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct SavedScript {
+    pub name: String,
+    pub source: String,
+}
+
+/// Result of running a script
+#[derive(Debug, Clone, Default)]
+pub struct ScriptOutput {
+    /// Lines printed by the script
+    pub log: Vec<String>,
+    /// Changes collected by the script, labelled for review
+    pub changes: Vec<(String, BatchAction)>,
+    /// Error that stopped the script; its changes are discarded
+    pub error: Option<String>,
+}
+
+type ScriptResult = Result<(), Box<EvalAltResult>>;
+
+/// Run a script against the records of a zone
+pub fn run(source: &str, zone: &str, records: &[DnsRecord]) -> ScriptOutput {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let changes = Rc::new(RefCell::new(Vec::new()));
+    let records = Rc::new(records.to_vec());
+
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+    {
+        let log = log.clone();
+        engine.on_print(move |text| log.borrow_mut().push(text.to_string()));
+    }
+    {
+        let log = log.clone();
+        engine.on_debug(move |text, _, _| log.borrow_mut().push(text.to_string()));
+    }
+    {
+        let changes = changes.clone();
+        engine.register_fn("create", move |fields: Map| -> ScriptResult {
+            let record = create_record(fields)?;
+            changes.borrow_mut().push(BatchAction::Create(record));
+            Ok(())
+        });
+    }
+    {
+        let changes = changes.clone();
+        let records = records.clone();
+        engine.register_fn(
+            "update",
+            move |id: ImmutableString, fields: Map| -> ScriptResult {
+                let record = update_record(&records, &id, fields)?;
+                changes.borrow_mut().push(BatchAction::Update {
+                    record_id: id.to_string(),
+                    record,
+                });
+                Ok(())
+            },
+        );
+    }
+    {
+        let changes = changes.clone();
+        let records = records.clone();
+        engine.register_fn("delete", move |id: ImmutableString| -> ScriptResult {
+            match records.iter().find(|record| record.id == id.as_str()) {
+                None => return Err(format!("No record with id {}", id).into()),
+                Some(record) if record.locked => {
+                    return Err(format!("{} is locked by Cloudflare", record.name).into());
+                }
+                Some(_) => {}
+            }
+            changes.borrow_mut().push(BatchAction::Delete {
+                record_id: id.to_string(),
+            });
+            Ok(())
+        });
+    }
+
+    let mut scope = Scope::new();
+    scope.push_constant("zone", zone.to_string());
+    let result = to_dynamic(records.as_slice()).and_then(|records| {
+        scope.push_constant_dynamic("records", records);
+        engine.run_with_scope(&mut scope, source)
+    });
+    drop(engine);
+
+    let log = log.take();
+    match result {
+        Ok(()) => ScriptOutput {
+            log,
+            changes: changes
+                .take()
+                .into_iter()
+                .map(|action| (action.label(&records), action))
+                .collect(),
+            error: None,
+        },
+        Err(e) => ScriptOutput {
+            log,
+            changes: Vec::new(),
+            error: Some(e.to_string()),
+        },
+    }
+}
+
+fn create_record(mut fields: Map) -> Result<CreateDnsRecord, Box<EvalAltResult>> {
+    fields.entry("ttl".into()).or_insert(Dynamic::from_int(1));
+    let record: CreateDnsRecord = from_dynamic(&fields.into())?;
+    record
+        .record_type
+        .validate_content(&record.content)
+        .map_err(|e| format!("{}: {}", record.name, e))?;
+    Ok(record)
+}
+
+fn update_record(
+    records: &[DnsRecord],
+    id: &str,
+    fields: Map,
+) -> Result<UpdateDnsRecord, Box<EvalAltResult>> {
+    let Some(existing) = records.iter().find(|record| record.id == id) else {
+        return Err(format!("No record with id {}", id).into());
+    };
+    if existing.locked {
+        return Err(format!("{} is locked by Cloudflare", existing.name).into());
+    }
+
+    // Start from the current record so fields the script doesn't set are kept
+    let mut record = UpdateDnsRecord::from(existing);
+    let changes: UpdateDnsRecord = from_dynamic(&fields.into())?;
+    // New content has its fields parsed from it rather than keeping the current ones
+    record.data = if changes.content.is_some() {
+        changes.data
+    } else {
+        changes.data.or(record.data)
+    };
+    record.record_type = changes.record_type.or(record.record_type);
+    record.name = changes.name.or(record.name);
+    record.content = changes.content.or(record.content);
+    record.ttl = changes.ttl.or(record.ttl);
+    record.proxied = changes.proxied.or(record.proxied);
+    record.priority = changes.priority.or(record.priority);
+    record.comment = changes.comment.or(record.comment);
+
+    if let (Some(record_type), Some(content)) = (record.record_type, &record.content) {
+        record_type
+            .validate_content(content)
+            .map_err(|e| format!("{}: {}", existing.name, e))?;
+    }
+    Ok(record)
+}
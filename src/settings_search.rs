@@ -0,0 +1,208 @@
+//! Finding settings by keyword
+//!
+//! Each section of the Settings page lists the labels of its settings and other words people
+//! look them up by, e.g. "dark" for the appearance mode, since the labels alone don't always
+//! contain the word someone searches for.
+
+use std::ops::Range;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsSection {
+    ApiToken,
+    Profile,
+    Appearance,
+    Startup,
+    Records,
+    NewRecords,
+    Network,
+    LocalAutomation,
+    Plugins,
+    About,
+}
+
+impl SettingsSection {
+    /// In the order of the Settings page
+    pub const ALL: [SettingsSection; 10] = [
+        SettingsSection::ApiToken,
+        SettingsSection::Profile,
+        SettingsSection::Appearance,
+        SettingsSection::Startup,
+        SettingsSection::Records,
+        SettingsSection::NewRecords,
+        SettingsSection::Network,
+        SettingsSection::LocalAutomation,
+        SettingsSection::Plugins,
+        SettingsSection::About,
+    ];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            SettingsSection::ApiToken => "API Token",
+            SettingsSection::Profile => "Profile",
+            SettingsSection::Appearance => "Appearance",
+            SettingsSection::Startup => "Startup",
+            SettingsSection::Records => "Records",
+            SettingsSection::NewRecords => "New Records",
+            SettingsSection::Network => "Network",
+            SettingsSection::LocalAutomation => "Local Automation",
+            SettingsSection::Plugins => "Plugins",
+            SettingsSection::About => "About",
+        }
+    }
+
+    /// Labels of the settings in the section
+    fn items(&self) -> &'static [&'static str] {
+        match self {
+            SettingsSection::ApiToken => &[
+                "Update Token",
+                "Add Profile",
+                "Clear Token",
+                "Remove Profile",
+                "Read-only mode",
+                "Permissions",
+            ],
+            SettingsSection::Profile => &[
+                "Accent color",
+                "Default TTL",
+                "Always read-only",
+                "Strict guardrails",
+            ],
+            SettingsSection::Appearance => &["Theme", "Text Size", "Timestamps in UTC"],
+            SettingsSection::Startup => {
+                &["Open to", "Portfolio", "Last viewed zone", "Pinned zone"]
+            }
+            SettingsSection::Records => &[
+                "Auto-refresh",
+                "Block duplicate records",
+                "Changes since the previous export",
+                "Import Terraform State",
+                "Protected records",
+            ],
+            SettingsSection::NewRecords => &["TTL", "Comment", "Attribution", "Proxy new records"],
+            SettingsSection::Network => &[
+                "Automatic",
+                "No Proxy",
+                "Manual",
+                "Test Connection",
+                "Diagnostics",
+                "API usage today",
+            ],
+            SettingsSection::LocalAutomation => {
+                &["Start Server", "Stop Server", "Port", "Token", "Regenerate"]
+            }
+            SettingsSection::Plugins => &["Reload", "Allow", "Revoke"],
+            SettingsSection::About => &["Version", "What's New"],
+        }
+    }
+
+    /// Other words the section is looked up by
+    fn keywords(&self) -> &'static [&'static str] {
+        match self {
+            SettingsSection::ApiToken => &[
+                "credentials",
+                "key",
+                "permissions",
+                "sign out",
+                "profiles",
+                "switch account",
+                "scopes",
+                "zones",
+                "expiry",
+                "view only",
+                "safe",
+            ],
+            SettingsSection::Profile => &[
+                "production",
+                "client",
+                "lock down",
+                "red",
+                "safety",
+                "confirmation",
+                "duplicates",
+            ],
+            SettingsSection::Appearance => &[
+                "dark",
+                "light",
+                "color",
+                "font",
+                "zoom",
+                "time zone",
+                "local time",
+            ],
+            SettingsSection::Startup => &["launch", "start page", "home", "default zone", "pin"],
+            SettingsSection::Records => &[
+                "duplicates",
+                "zone file",
+                "terraform",
+                "protect",
+                "reload",
+                "interval",
+                "polling",
+            ],
+            SettingsSection::NewRecords => &["defaults", "ticket", "proxied", "template"],
+            SettingsSection::Network => &[
+                "http proxy",
+                "https_proxy",
+                "firewall",
+                "connection",
+                "rate limit",
+                "requests",
+                "calls",
+            ],
+            SettingsSection::LocalAutomation => &["http server", "api", "raycast", "alfred"],
+            SettingsSection::Plugins => &["extensions", "validation", "scripts"],
+            SettingsSection::About => &["changelog", "release notes", "update"],
+        }
+    }
+
+    /// Whether every word of `query` is in the section's title, settings or keywords
+    pub fn matches(&self, query: &str) -> bool {
+        let texts: Vec<&str> = [self.title()]
+            .into_iter()
+            .chain(self.items().iter().copied())
+            .chain(self.keywords().iter().copied())
+            .collect();
+        query.split_whitespace().all(|word| {
+            texts
+                .iter()
+                .any(|text| !match_ranges(text, word).is_empty())
+        })
+    }
+
+    /// Labels of the section's settings containing a word of `query`
+    pub fn matching_items(&self, query: &str) -> Vec<&'static str> {
+        self.items()
+            .iter()
+            .copied()
+            .filter(|item| !match_ranges(item, query).is_empty())
+            .collect()
+    }
+}
+
+/// Byte ranges of `text` where the words of `query` occur, ignoring case, for highlighting
+pub fn match_ranges(text: &str, query: &str) -> Vec<Range<usize>> {
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    for word in query.split_whitespace() {
+        for (start, _) in text.char_indices() {
+            let end = start + word.len();
+            if text.is_char_boundary(end)
+                && text
+                    .get(start..end)
+                    .is_some_and(|found| found.eq_ignore_ascii_case(word))
+            {
+                ranges.push(start..end);
+            }
+        }
+    }
+
+    // Merge overlapping matches of different words
+    ranges.sort_by_key(|range| range.start);
+    let mut merged: Vec<Range<usize>> = Vec::new();
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
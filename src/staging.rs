@@ -0,0 +1,246 @@
+//! Record changes staged for review before they are sent to Cloudflare
+//!
+//! In staging mode, creating, editing and deleting records doesn't call the API. The changes
+//! collect per zone, can be reviewed as a diff, and are applied together in one batch request,
+//! so either all of them are applied or none.
+
+use crate::batch::BatchAction;
+use crate::cloudflare::{
+    CreateDnsRecord, DnsRecord, DnsRecordBatch, DnsRecordType, UpdateDnsRecord,
+};
+
+#[derive(Debug, Clone)]
+pub struct StagedChange {
+    /// What the change does, e.g. `Update A www.example.com`
+    pub label: String,
+    pub action: BatchAction,
+    /// The record as it was when the change was staged; `None` for creations
+    pub before: Option<DnsRecord>,
+}
+
+/// A line of a staged change's diff
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiffLine {
+    Removed(String),
+    Added(String),
+}
+
+impl StagedChange {
+    pub fn create(record: CreateDnsRecord) -> Self {
+        Self {
+            label: format!("Create {} {}", record.record_type, record.name),
+            action: BatchAction::Create(record),
+            before: None,
+        }
+    }
+
+    pub fn update(before: DnsRecord, record: UpdateDnsRecord) -> Self {
+        Self {
+            label: format!("Update {} {}", before.record_type, before.name),
+            action: BatchAction::Update {
+                record_id: before.id.clone(),
+                record,
+            },
+            before: Some(before),
+        }
+    }
+
+    pub fn delete(before: DnsRecord) -> Self {
+        Self {
+            label: format!("Delete {} {}", before.record_type, before.name),
+            action: BatchAction::Delete {
+                record_id: before.id.clone(),
+            },
+            before: Some(before),
+        }
+    }
+
+    /// The record before and after the change in zone file notation
+    pub fn diff(&self) -> Vec<DiffLine> {
+        let before = self.before.as_ref().map(|record| {
+            record_line(
+                record.record_type,
+                &record.name,
+                record.ttl,
+                record.priority,
+                &record.content,
+                record.proxied,
+            )
+        });
+        let after = match &self.action {
+            BatchAction::Create(record) => Some(record_line(
+                record.record_type,
+                &record.name,
+                record.ttl,
+                record.priority,
+                &record.content,
+                record.proxied.unwrap_or(false),
+            )),
+            BatchAction::Update { record, .. } => self.before.as_ref().map(|before| {
+                let record_type = record.record_type.unwrap_or(before.record_type);
+                record_line(
+                    record_type,
+                    record.name.as_ref().unwrap_or(&before.name),
+                    record.ttl.unwrap_or(before.ttl),
+                    record.priority.or(before.priority),
+                    record.content.as_ref().unwrap_or(&before.content),
+                    record
+                        .proxied
+                        .unwrap_or(before.proxied && record_type.is_proxiable()),
+                )
+            }),
+            BatchAction::Delete { .. } => None,
+        };
+
+        before
+            .map(DiffLine::Removed)
+            .into_iter()
+            .chain(after.map(DiffLine::Added))
+            .collect()
+    }
+}
+
+/// How a staged change whose record changed on Cloudflare since is applied
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConflictResolution {
+    /// Apply the staged change over the remote one
+    KeepMine,
+    /// Drop the staged change and keep the record as it is on Cloudflare
+    TakeTheirs,
+    /// Leave the staged change staged without applying it
+    Skip,
+}
+
+impl ConflictResolution {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ConflictResolution::KeepMine => "Keep Mine",
+            ConflictResolution::TakeTheirs => "Take Theirs",
+            ConflictResolution::Skip => "Skip",
+        }
+    }
+}
+
+/// A staged change whose record was changed or deleted on Cloudflare after it was staged
+#[derive(Debug, Clone)]
+pub struct StagedConflict {
+    /// Index of the change in the zone's staged changes
+    pub index: usize,
+    /// The record as it is on Cloudflare now, `None` if it was deleted
+    pub remote: Option<DnsRecord>,
+    pub resolution: ConflictResolution,
+}
+
+impl StagedConflict {
+    /// Resolutions that can be chosen; a deleted record can't be changed or deleted anymore
+    pub fn resolutions(&self) -> &'static [ConflictResolution] {
+        if self.remote.is_some() {
+            &[
+                ConflictResolution::KeepMine,
+                ConflictResolution::TakeTheirs,
+                ConflictResolution::Skip,
+            ]
+        } else {
+            &[ConflictResolution::TakeTheirs, ConflictResolution::Skip]
+        }
+    }
+}
+
+/// Staged updates and deletions of records that were modified or deleted since they were staged,
+/// compared with the zone's `current` records
+///
+/// Nothing is overwritten unless chosen: changes of modified records start out skipped, and
+/// those of deleted records taking the deletion.
+pub fn find_conflicts(changes: &[StagedChange], current: &[DnsRecord]) -> Vec<StagedConflict> {
+    changes
+        .iter()
+        .enumerate()
+        .filter_map(|(index, change)| {
+            let before = change.before.as_ref()?;
+            match current.iter().find(|record| record.id == before.id) {
+                Some(remote) if remote.modified_on == before.modified_on => None,
+                Some(remote) => Some(StagedConflict {
+                    index,
+                    remote: Some(remote.clone()),
+                    resolution: ConflictResolution::Skip,
+                }),
+                None => Some(StagedConflict {
+                    index,
+                    remote: None,
+                    resolution: ConflictResolution::TakeTheirs,
+                }),
+            }
+        })
+        .collect()
+}
+
+/// Split staged changes into the ones to apply now and the ones to leave staged, by the
+/// resolutions of their conflicts; changes taking the remote record are dropped
+pub fn resolve(
+    changes: Vec<StagedChange>,
+    conflicts: &[StagedConflict],
+) -> (Vec<StagedChange>, Vec<StagedChange>) {
+    let mut apply = Vec::new();
+    let mut keep = Vec::new();
+    for (index, change) in changes.into_iter().enumerate() {
+        let resolution = conflicts
+            .iter()
+            .find(|conflict| conflict.index == index)
+            .map(|conflict| conflict.resolution);
+        match resolution {
+            None | Some(ConflictResolution::KeepMine) => apply.push(change),
+            Some(ConflictResolution::TakeTheirs) => {}
+            Some(ConflictResolution::Skip) => keep.push(change),
+        }
+    }
+    (apply, keep)
+}
+
+/// The batch request applying `changes`
+///
+/// The API deletes before it updates, so updates of records that are also deleted are left out
+/// rather than failing the whole batch.
+pub fn record_batch(changes: &[StagedChange]) -> DnsRecordBatch {
+    let mut batch = DnsRecordBatch::default();
+    for change in changes {
+        match &change.action {
+            BatchAction::Create(record) => batch.posts.push(record.clone()),
+            BatchAction::Update { record_id, record } => {
+                batch.patches.push((record_id.clone(), record.clone()));
+            }
+            BatchAction::Delete { record_id } => {
+                if !batch.deletes.contains(record_id) {
+                    batch.deletes.push(record_id.clone());
+                }
+            }
+        }
+    }
+    batch
+        .patches
+        .retain(|(record_id, _)| !batch.deletes.contains(record_id));
+    batch
+}
+
+fn record_line(
+    record_type: DnsRecordType,
+    name: &str,
+    ttl: u32,
+    priority: Option<u16>,
+    content: &str,
+    proxied: bool,
+) -> String {
+    let ttl = if ttl == 1 {
+        "auto".to_string()
+    } else {
+        ttl.to_string()
+    };
+    let priority = priority
+        .filter(|_| record_type.requires_priority())
+        .map(|priority| format!("{} ", priority))
+        .unwrap_or_default();
+    let proxied = if proxied { " (proxied)" } else { "" };
+    format!(
+        "{} {} {} {}{}{}",
+        name, ttl, record_type, priority, content, proxied
+    )
+}
@@ -0,0 +1,101 @@
+//! What the app opens to, and the data it shows before the first refresh
+//!
+//! The zone list and the records of the zone the app opens to are kept as JSON in the app's
+//! data directory, so a launch shows them right away while fresh copies load in the
+//! background.
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use crate::cloudflare::{DnsRecord, Zone};
+
+/// Page the app opens to once signed in
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(tag = "view", content = "zone_id", rename_all = "snake_case")]
+pub enum StartupView {
+    #[default]
+    Portfolio,
+    /// The zone viewed when the app was last closed
+    LastZone,
+    /// Always the same zone, by id
+    PinnedZone(String),
+}
+
+impl StartupView {
+    /// Id of the zone to open to, given the one viewed last; `None` for the portfolio
+    pub fn zone_id<'a>(&'a self, last_zone_id: Option<&'a str>) -> Option<&'a str> {
+        match self {
+            StartupView::Portfolio => None,
+            StartupView::LastZone => last_zone_id,
+            StartupView::PinnedZone(zone_id) => Some(zone_id),
+        }
+    }
+
+    pub fn label(&self, zones: &[Zone]) -> String {
+        match self {
+            StartupView::Portfolio => "Portfolio".to_string(),
+            StartupView::LastZone => "Last viewed zone".to_string(),
+            StartupView::PinnedZone(zone_id) => zones
+                .iter()
+                .find(|zone| &zone.id == zone_id)
+                .map(|zone| zone.name.clone())
+                .unwrap_or_else(|| "Pinned zone".to_string()),
+        }
+    }
+}
+
+/// Zones and records as of the last run
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Snapshot {
+    /// Token profile the data was loaded with
+    #[serde(default)]
+    pub profile: String,
+    pub zones: Vec<Zone>,
+    /// Zone whose records are kept, the one the app opens to
+    pub zone_id: Option<String>,
+    pub records: Vec<DnsRecord>,
+    pub fetched_at: DateTime<Local>,
+}
+
+fn snapshot_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("cloudflare-admin").join("startup.json"))
+}
+
+/// The snapshot saved by the last run, if any
+pub fn load() -> Result<Option<Snapshot>> {
+    let Some(path) = snapshot_path() else {
+        return Ok(None);
+    };
+    match fs::read_to_string(path) {
+        Ok(json) => Ok(Some(serde_json::from_str(&json)?)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+pub fn store(snapshot: &Snapshot) -> Result<()> {
+    let Some(path) = snapshot_path() else {
+        return Ok(());
+    };
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, serde_json::to_string(snapshot)?)?;
+    Ok(())
+}
+
+/// Forget the snapshot, e.g. when the API token changes and its zones may no longer be visible
+pub fn delete() -> Result<()> {
+    let Some(path) = snapshot_path() else {
+        return Ok(());
+    };
+    match fs::remove_file(path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e.into()),
+    }
+}
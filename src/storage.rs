@@ -1,22 +1,112 @@
 //! Secure token storage using the OS keyring
+//!
+//! Tokens and small settings are kept in keyring entries. Data that grows with use, such as the
+//! script library, is kept in JSON files in the app's data directory instead, as keyrings limit
+//! the size of an entry.
 
-use anyhow::Result;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use anyhow::{Result, anyhow};
 use keyring::Entry;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::control_server::ControlServerSettings;
+use crate::filter::SavedFilter;
+use crate::network_proxy::ProxySetting;
+use crate::profile_settings::ProfileSettings;
+use crate::record_defaults::RecordDefaults;
+use crate::scripting::SavedScript;
+use crate::startup::StartupView;
+use crate::ttl_lowering::LoweredTtls;
+use crate::workspaces::Workspace;
 
 const SERVICE_NAME: &str = "cloudflare-admin";
 const TOKEN_KEY: &str = "api_token";
 const APPEARANCE_KEY: &str = "appearance_mode";
+const TEXT_SCALE_KEY: &str = "text_scale";
+const TIME_ZONE_DISPLAY_KEY: &str = "time_zone_display";
+/// Keyring entries the search history and saved filters were kept in before they moved to
+/// data files
+const SEARCH_HISTORY_KEY: &str = "search_history";
+const SAVED_FILTERS_KEY_PREFIX: &str = "saved_filters";
+/// Keyring entry the watched records were kept in before they moved to [`WATCHED_RECORDS_FILE`]
+const WATCHED_RECORDS_KEY: &str = "watched_records";
+/// Keyring entry the certificate notifications were kept in before they moved to
+/// [`CERTIFICATE_NOTIFICATIONS_FILE`]
+const CERTIFICATE_NOTIFICATIONS_KEY: &str = "certificate_notifications";
+/// Keyring entry the zone tags were kept in before they moved to [`ZONE_TAGS_FILE`]
+const ZONE_TAGS_KEY: &str = "zone_tags";
+const PINNED_ZONES_KEY: &str = "pinned_zones";
+const PLUGIN_APPROVALS_KEY: &str = "plugin_approvals";
+/// Keyring entry the script library was kept in before it moved to [`SAVED_SCRIPTS_FILE`]
+const SAVED_SCRIPTS_KEY: &str = "saved_scripts";
+const CONTROL_SERVER_KEY: &str = "control_server";
+/// Keyring entry the unproxied records were kept in before they moved to [`UNPROXIED_RECORDS_FILE`]
+const UNPROXIED_RECORDS_KEY: &str = "unproxied_records";
+const BLOCK_DUPLICATE_RECORDS_KEY: &str = "block_duplicate_records";
+const COMPARE_EXPORTS_KEY: &str = "compare_exports";
+const RECORD_DEFAULTS_KEY: &str = "record_defaults";
+const GROUP_BY_NAME_KEY: &str = "group_by_name";
+/// Keyring entry the lowered TTLs were kept in before they moved to [`LOWERED_TTLS_FILE`]
+const LOWERED_TTLS_KEY: &str = "lowered_ttls";
+const PROXY_SETTING_KEY: &str = "proxy_setting";
+const LAST_SEEN_VERSION_KEY: &str = "last_seen_version";
+/// Keyring entries the zone notes and protected records were kept in before they moved to
+/// data files
+const ZONE_NOTES_KEY: &str = "zone_notes";
+const PROTECTED_RECORDS_KEY: &str = "protected_records";
+const IMPORT_OFFER_DISMISSED_KEY: &str = "import_offer_dismissed";
+const ZONE_ACCOUNT_FILTER_KEY: &str = "zone_account_filter";
+const STARTUP_VIEW_KEY: &str = "startup_view";
+const LAST_ZONE_KEY: &str = "last_zone";
+const PROFILES_KEY: &str = "profiles";
+const ACTIVE_PROFILE_KEY: &str = "active_profile";
+const PROFILE_SETTINGS_KEY: &str = "profile_settings";
+/// Keyring entry the workspaces were kept in before they moved to [`WORKSPACES_FILE`]
+const WORKSPACES_KEY: &str = "workspaces";
+const READ_ONLY_KEY: &str = "read_only";
+const RECORD_REFRESH_INTERVAL_KEY: &str = "record_refresh_interval";
+const ACTIVE_WORKSPACE_KEY: &str = "active_workspace";
+
+const SEARCH_HISTORY_FILE: &str = "search_history.json";
+/// Directory with a file of saved filters per zone
+const SAVED_FILTERS_DIR: &str = "saved_filters";
+const SAVED_SCRIPTS_FILE: &str = "saved_scripts.json";
+const ZONE_NOTES_FILE: &str = "zone_notes.json";
+const PROTECTED_RECORDS_FILE: &str = "protected_records.json";
+const WORKSPACES_FILE: &str = "workspaces.json";
+const LOWERED_TTLS_FILE: &str = "lowered_ttls.json";
+const UNPROXIED_RECORDS_FILE: &str = "unproxied_records.json";
+const CERTIFICATE_NOTIFICATIONS_FILE: &str = "certificate_notifications.json";
+const WATCHED_RECORDS_FILE: &str = "watched_records.json";
+const ZONE_TAGS_FILE: &str = "zone_tags.json";
+
+/// Profile whose token is kept under the key used before there were profiles
+pub const DEFAULT_PROFILE: &str = "Default";
 
-/// Store the API token securely in the OS keyring
-pub fn store_token(token: &str) -> Result<()> {
-    let entry = Entry::new(SERVICE_NAME, TOKEN_KEY)?;
+/// Keyring entry of a profile's API token
+fn token_key(profile: &str) -> String {
+    if profile == DEFAULT_PROFILE {
+        TOKEN_KEY.to_string()
+    } else {
+        format!("{}.{}", TOKEN_KEY, profile)
+    }
+}
+
+/// Store a profile's API token securely in the OS keyring
+pub fn store_token(profile: &str, token: &str) -> Result<()> {
+    let entry = Entry::new(SERVICE_NAME, &token_key(profile))?;
     entry.set_password(token)?;
     Ok(())
 }
 
-/// Retrieve the API token from the OS keyring
-pub fn get_token() -> Result<Option<String>> {
-    let entry = Entry::new(SERVICE_NAME, TOKEN_KEY)?;
+/// Retrieve a profile's API token from the OS keyring
+pub fn get_token(profile: &str) -> Result<Option<String>> {
+    let entry = Entry::new(SERVICE_NAME, &token_key(profile))?;
     match entry.get_password() {
         Ok(token) => Ok(Some(token)),
         Err(keyring::Error::NoEntry) => Ok(None),
@@ -24,9 +114,9 @@ pub fn get_token() -> Result<Option<String>> {
     }
 }
 
-/// Delete the API token from the OS keyring
-pub fn delete_token() -> Result<()> {
-    let entry = Entry::new(SERVICE_NAME, TOKEN_KEY)?;
+/// Delete a profile's API token from the OS keyring
+pub fn delete_token(profile: &str) -> Result<()> {
+    let entry = Entry::new(SERVICE_NAME, &token_key(profile))?;
     match entry.delete_credential() {
         Ok(_) => Ok(()),
         Err(keyring::Error::NoEntry) => Ok(()), // Already deleted
@@ -34,9 +124,105 @@ pub fn delete_token() -> Result<()> {
     }
 }
 
-/// Check if a token exists in the keyring
-pub fn has_token() -> bool {
-    get_token().map(|t| t.is_some()).unwrap_or(false)
+/// Check if a profile's token exists in the keyring
+pub fn has_token(profile: &str) -> bool {
+    get_token(profile).map(|t| t.is_some()).unwrap_or(false)
+}
+
+/// Path of a file in the app's data directory
+fn data_file(name: &str) -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join(SERVICE_NAME).join(name))
+}
+
+/// Write `value` to a data file as JSON, and remove the keyring entry it was kept in before
+fn store_data_file<T: Serialize + ?Sized>(name: &str, legacy_key: &str, value: &T) -> Result<()> {
+    let path = data_file(name).ok_or_else(|| anyhow!("No data directory to store {} in", name))?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, serde_json::to_string_pretty(value)?)?;
+
+    let entry = Entry::new(SERVICE_NAME, legacy_key)?;
+    match entry.delete_credential() {
+        Ok(_) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Read a data file, or the keyring entry it was kept in before until it is stored again
+fn get_data_file<T: DeserializeOwned + Default>(name: &str, legacy_key: &str) -> Result<T> {
+    if let Some(path) = data_file(name) {
+        match fs::read_to_string(path) {
+            Ok(json) => return Ok(serde_json::from_str(&json)?),
+            Err(e) if e.kind() == ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    let entry = Entry::new(SERVICE_NAME, legacy_key)?;
+    match entry.get_password() {
+        Ok(json) => Ok(serde_json::from_str(&json)?),
+        Err(keyring::Error::NoEntry) => Ok(T::default()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Store the names of the token profiles
+pub fn store_profiles(profiles: &[String]) -> Result<()> {
+    let entry = Entry::new(SERVICE_NAME, PROFILES_KEY)?;
+    entry.set_password(&serde_json::to_string(profiles)?)?;
+    Ok(())
+}
+
+/// Retrieve the names of the token profiles, at least the default one
+pub fn get_profiles() -> Result<Vec<String>> {
+    let entry = Entry::new(SERVICE_NAME, PROFILES_KEY)?;
+    match entry.get_password() {
+        Ok(json) => {
+            let profiles: Vec<String> = serde_json::from_str(&json)?;
+            if profiles.is_empty() {
+                Ok(vec![DEFAULT_PROFILE.to_string()])
+            } else {
+                Ok(profiles)
+            }
+        }
+        Err(keyring::Error::NoEntry) => Ok(vec![DEFAULT_PROFILE.to_string()]),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Store the name of the profile used last
+pub fn store_active_profile(profile: &str) -> Result<()> {
+    let entry = Entry::new(SERVICE_NAME, ACTIVE_PROFILE_KEY)?;
+    entry.set_password(profile)?;
+    Ok(())
+}
+
+/// Retrieve the name of the profile used last
+pub fn get_active_profile() -> Result<Option<String>> {
+    let entry = Entry::new(SERVICE_NAME, ACTIVE_PROFILE_KEY)?;
+    match entry.get_password() {
+        Ok(profile) => Ok(Some(profile)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Store the settings each profile overrides, by profile name
+pub fn store_profile_settings(settings: &HashMap<String, ProfileSettings>) -> Result<()> {
+    let entry = Entry::new(SERVICE_NAME, PROFILE_SETTINGS_KEY)?;
+    entry.set_password(&serde_json::to_string(settings)?)?;
+    Ok(())
+}
+
+/// Retrieve the settings each profile overrides, by profile name
+pub fn get_profile_settings() -> Result<HashMap<String, ProfileSettings>> {
+    let entry = Entry::new(SERVICE_NAME, PROFILE_SETTINGS_KEY)?;
+    match entry.get_password() {
+        Ok(json) => Ok(serde_json::from_str(&json)?),
+        Err(keyring::Error::NoEntry) => Ok(HashMap::new()),
+        Err(e) => Err(e.into()),
+    }
 }
 
 /// Store the appearance mode preference
@@ -55,3 +241,452 @@ pub fn get_appearance_mode() -> Result<Option<String>> {
         Err(e) => Err(e.into()),
     }
 }
+
+/// Store the text size preference
+pub fn store_text_scale(scale: &str) -> Result<()> {
+    let entry = Entry::new(SERVICE_NAME, TEXT_SCALE_KEY)?;
+    entry.set_password(scale)?;
+    Ok(())
+}
+
+/// Retrieve the text size preference
+pub fn get_text_scale() -> Result<Option<String>> {
+    let entry = Entry::new(SERVICE_NAME, TEXT_SCALE_KEY)?;
+    match entry.get_password() {
+        Ok(scale) => Ok(Some(scale)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Store whether full timestamps are shown in local time or UTC
+pub fn store_time_zone_display(display: &str) -> Result<()> {
+    let entry = Entry::new(SERVICE_NAME, TIME_ZONE_DISPLAY_KEY)?;
+    entry.set_password(display)?;
+    Ok(())
+}
+
+/// Retrieve whether full timestamps are shown in local time or UTC
+pub fn get_time_zone_display() -> Result<Option<String>> {
+    let entry = Entry::new(SERVICE_NAME, TIME_ZONE_DISPLAY_KEY)?;
+    match entry.get_password() {
+        Ok(display) => Ok(Some(display)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Store the recent search queries
+pub fn store_search_history(history: &[String]) -> Result<()> {
+    store_data_file(SEARCH_HISTORY_FILE, SEARCH_HISTORY_KEY, history)
+}
+
+/// Retrieve the recent search queries
+pub fn get_search_history() -> Result<Vec<String>> {
+    get_data_file(SEARCH_HISTORY_FILE, SEARCH_HISTORY_KEY)
+}
+
+/// Store the saved filters of a zone
+pub fn store_saved_filters(zone_id: &str, filters: &[SavedFilter]) -> Result<()> {
+    store_data_file(
+        &format!("{}/{}.json", SAVED_FILTERS_DIR, zone_id),
+        &format!("{}.{}", SAVED_FILTERS_KEY_PREFIX, zone_id),
+        filters,
+    )
+}
+
+/// Retrieve the saved filters of a zone
+pub fn get_saved_filters(zone_id: &str) -> Result<Vec<SavedFilter>> {
+    get_data_file(
+        &format!("{}/{}.json", SAVED_FILTERS_DIR, zone_id),
+        &format!("{}.{}", SAVED_FILTERS_KEY_PREFIX, zone_id),
+    )
+}
+
+/// Store the ids of watched DNS records
+pub fn store_watched_records(record_ids: &HashSet<String>) -> Result<()> {
+    store_data_file(WATCHED_RECORDS_FILE, WATCHED_RECORDS_KEY, record_ids)
+}
+
+/// Retrieve the ids of watched DNS records
+pub fn get_watched_records() -> Result<HashSet<String>> {
+    get_data_file(WATCHED_RECORDS_FILE, WATCHED_RECORDS_KEY)
+}
+
+/// Store the ids of the zones pinned to the top of the zone picker
+pub fn store_pinned_zones(zone_ids: &HashSet<String>) -> Result<()> {
+    let entry = Entry::new(SERVICE_NAME, PINNED_ZONES_KEY)?;
+    entry.set_password(&serde_json::to_string(zone_ids)?)?;
+    Ok(())
+}
+
+/// Retrieve the ids of the zones pinned to the top of the zone picker
+pub fn get_pinned_zones() -> Result<HashSet<String>> {
+    let entry = Entry::new(SERVICE_NAME, PINNED_ZONES_KEY)?;
+    match entry.get_password() {
+        Ok(json) => Ok(serde_json::from_str(&json)?),
+        Err(keyring::Error::NoEntry) => Ok(HashSet::new()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Store the expiry notification threshold last shown for each certificate; certificates that
+/// no longer exist are expected to be dropped first
+pub fn store_certificate_notifications(notified: &HashMap<String, i64>) -> Result<()> {
+    store_data_file(
+        CERTIFICATE_NOTIFICATIONS_FILE,
+        CERTIFICATE_NOTIFICATIONS_KEY,
+        notified,
+    )
+}
+
+/// Retrieve the expiry notification threshold last shown for each certificate
+pub fn get_certificate_notifications() -> Result<HashMap<String, i64>> {
+    get_data_file(
+        CERTIFICATE_NOTIFICATIONS_FILE,
+        CERTIFICATE_NOTIFICATIONS_KEY,
+    )
+}
+
+/// Store the local tags of each zone
+pub fn store_zone_tags(zone_tags: &HashMap<String, Vec<String>>) -> Result<()> {
+    store_data_file(ZONE_TAGS_FILE, ZONE_TAGS_KEY, zone_tags)
+}
+
+/// Retrieve the local tags of each zone
+pub fn get_zone_tags() -> Result<HashMap<String, Vec<String>>> {
+    get_data_file(ZONE_TAGS_FILE, ZONE_TAGS_KEY)
+}
+
+/// Store the local notes of each zone
+pub fn store_zone_notes(zone_notes: &HashMap<String, String>) -> Result<()> {
+    store_data_file(ZONE_NOTES_FILE, ZONE_NOTES_KEY, zone_notes)
+}
+
+/// Retrieve the local notes of each zone
+pub fn get_zone_notes() -> Result<HashMap<String, String>> {
+    get_data_file(ZONE_NOTES_FILE, ZONE_NOTES_KEY)
+}
+
+/// Store the protected records, mapping record ids to why they are protected
+pub fn store_protected_records(protected: &HashMap<String, String>) -> Result<()> {
+    store_data_file(PROTECTED_RECORDS_FILE, PROTECTED_RECORDS_KEY, protected)
+}
+
+/// Retrieve the protected records, mapping record ids to why they are protected
+pub fn get_protected_records() -> Result<HashMap<String, String>> {
+    get_data_file(PROTECTED_RECORDS_FILE, PROTECTED_RECORDS_KEY)
+}
+
+/// Store the approved plugins, mapping plugin ids to the fingerprint of their approved manifest
+pub fn store_plugin_approvals(approvals: &HashMap<String, String>) -> Result<()> {
+    let entry = Entry::new(SERVICE_NAME, PLUGIN_APPROVALS_KEY)?;
+    entry.set_password(&serde_json::to_string(approvals)?)?;
+    Ok(())
+}
+
+/// Retrieve the approved plugins
+pub fn get_plugin_approvals() -> Result<HashMap<String, String>> {
+    let entry = Entry::new(SERVICE_NAME, PLUGIN_APPROVALS_KEY)?;
+    match entry.get_password() {
+        Ok(json) => Ok(serde_json::from_str(&json)?),
+        Err(keyring::Error::NoEntry) => Ok(HashMap::new()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Store the scripts of the script library
+pub fn store_saved_scripts(scripts: &[SavedScript]) -> Result<()> {
+    store_data_file(SAVED_SCRIPTS_FILE, SAVED_SCRIPTS_KEY, scripts)
+}
+
+/// Retrieve the scripts of the script library
+pub fn get_saved_scripts() -> Result<Vec<SavedScript>> {
+    get_data_file(SAVED_SCRIPTS_FILE, SAVED_SCRIPTS_KEY)
+}
+
+/// Store the settings of the local control server, including its token
+pub fn store_control_server_settings(settings: &ControlServerSettings) -> Result<()> {
+    let entry = Entry::new(SERVICE_NAME, CONTROL_SERVER_KEY)?;
+    entry.set_password(&serde_json::to_string(settings)?)?;
+    Ok(())
+}
+
+/// Retrieve the settings of the local control server
+pub fn get_control_server_settings() -> Result<Option<ControlServerSettings>> {
+    let entry = Entry::new(SERVICE_NAME, CONTROL_SERVER_KEY)?;
+    match entry.get_password() {
+        Ok(json) => Ok(Some(serde_json::from_str(&json)?)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Store the ids of records unproxied for maintenance, by zone id
+pub fn store_unproxied_records(records: &HashMap<String, Vec<String>>) -> Result<()> {
+    store_data_file(UNPROXIED_RECORDS_FILE, UNPROXIED_RECORDS_KEY, records)
+}
+
+/// Retrieve the ids of records unproxied for maintenance, by zone id
+pub fn get_unproxied_records() -> Result<HashMap<String, Vec<String>>> {
+    get_data_file(UNPROXIED_RECORDS_FILE, UNPROXIED_RECORDS_KEY)
+}
+
+/// Store the original TTLs of records lowered for a planned change, by zone id
+pub fn store_lowered_ttls(lowered: &HashMap<String, LoweredTtls>) -> Result<()> {
+    store_data_file(LOWERED_TTLS_FILE, LOWERED_TTLS_KEY, lowered)
+}
+
+/// Retrieve the original TTLs of records lowered for a planned change, by zone id
+pub fn get_lowered_ttls() -> Result<HashMap<String, LoweredTtls>> {
+    get_data_file(LOWERED_TTLS_FILE, LOWERED_TTLS_KEY)
+}
+
+/// Store whether creating a duplicate record is blocked instead of asking first
+pub fn store_block_duplicate_records(block: bool) -> Result<()> {
+    let entry = Entry::new(SERVICE_NAME, BLOCK_DUPLICATE_RECORDS_KEY)?;
+    entry.set_password(&block.to_string())?;
+    Ok(())
+}
+
+/// Retrieve whether creating a duplicate record is blocked, asking first by default
+pub fn get_block_duplicate_records() -> Result<bool> {
+    let entry = Entry::new(SERVICE_NAME, BLOCK_DUPLICATE_RECORDS_KEY)?;
+    match entry.get_password() {
+        Ok(block) => Ok(block == "true"),
+        Err(keyring::Error::NoEntry) => Ok(false),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Store whether zone file exports are compared with the previous export
+pub fn store_compare_exports(compare: bool) -> Result<()> {
+    let entry = Entry::new(SERVICE_NAME, COMPARE_EXPORTS_KEY)?;
+    entry.set_password(&compare.to_string())?;
+    Ok(())
+}
+
+/// Retrieve whether zone file exports are compared with the previous export, off by default
+pub fn get_compare_exports() -> Result<bool> {
+    let entry = Entry::new(SERVICE_NAME, COMPARE_EXPORTS_KEY)?;
+    match entry.get_password() {
+        Ok(compare) => Ok(compare == "true"),
+        Err(keyring::Error::NoEntry) => Ok(false),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Store whether the record list groups records by name
+pub fn store_group_by_name(group: bool) -> Result<()> {
+    let entry = Entry::new(SERVICE_NAME, GROUP_BY_NAME_KEY)?;
+    entry.set_password(&group.to_string())?;
+    Ok(())
+}
+
+/// Retrieve whether the record list groups records by name, off by default
+pub fn get_group_by_name() -> Result<bool> {
+    let entry = Entry::new(SERVICE_NAME, GROUP_BY_NAME_KEY)?;
+    match entry.get_password() {
+        Ok(group) => Ok(group == "true"),
+        Err(keyring::Error::NoEntry) => Ok(false),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Store the values new records start with
+pub fn store_record_defaults(defaults: &RecordDefaults) -> Result<()> {
+    let entry = Entry::new(SERVICE_NAME, RECORD_DEFAULTS_KEY)?;
+    entry.set_password(&serde_json::to_string(defaults)?)?;
+    Ok(())
+}
+
+/// Retrieve the values new records start with
+pub fn get_record_defaults() -> Result<RecordDefaults> {
+    let entry = Entry::new(SERVICE_NAME, RECORD_DEFAULTS_KEY)?;
+    match entry.get_password() {
+        Ok(json) => Ok(serde_json::from_str(&json)?),
+        Err(keyring::Error::NoEntry) => Ok(RecordDefaults::default()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Store how API requests reach Cloudflare
+pub fn store_proxy_setting(setting: &ProxySetting) -> Result<()> {
+    let entry = Entry::new(SERVICE_NAME, PROXY_SETTING_KEY)?;
+    entry.set_password(&serde_json::to_string(setting)?)?;
+    Ok(())
+}
+
+/// Retrieve how API requests reach Cloudflare
+pub fn get_proxy_setting() -> Result<ProxySetting> {
+    let entry = Entry::new(SERVICE_NAME, PROXY_SETTING_KEY)?;
+    match entry.get_password() {
+        Ok(json) => Ok(serde_json::from_str(&json)?),
+        Err(keyring::Error::NoEntry) => Ok(ProxySetting::default()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Store the version of the app whose changes were shown
+pub fn store_last_seen_version(version: &str) -> Result<()> {
+    let entry = Entry::new(SERVICE_NAME, LAST_SEEN_VERSION_KEY)?;
+    entry.set_password(version)?;
+    Ok(())
+}
+
+/// Retrieve the version of the app whose changes were shown, `None` before the first run
+pub fn get_last_seen_version() -> Result<Option<String>> {
+    let entry = Entry::new(SERVICE_NAME, LAST_SEEN_VERSION_KEY)?;
+    match entry.get_password() {
+        Ok(version) => Ok(Some(version)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Store whether the offer to import context from existing tooling was dismissed
+pub fn store_import_offer_dismissed(dismissed: bool) -> Result<()> {
+    let entry = Entry::new(SERVICE_NAME, IMPORT_OFFER_DISMISSED_KEY)?;
+    entry.set_password(&dismissed.to_string())?;
+    Ok(())
+}
+
+/// Retrieve whether the offer to import context from existing tooling was dismissed
+pub fn get_import_offer_dismissed() -> Result<bool> {
+    let entry = Entry::new(SERVICE_NAME, IMPORT_OFFER_DISMISSED_KEY)?;
+    match entry.get_password() {
+        Ok(dismissed) => Ok(dismissed == "true"),
+        Err(keyring::Error::NoEntry) => Ok(false),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Store whether read-only mode is turned on
+pub fn store_read_only(read_only: bool) -> Result<()> {
+    let entry = Entry::new(SERVICE_NAME, READ_ONLY_KEY)?;
+    entry.set_password(&read_only.to_string())?;
+    Ok(())
+}
+
+/// Retrieve whether read-only mode is turned on
+pub fn get_read_only() -> Result<bool> {
+    let entry = Entry::new(SERVICE_NAME, READ_ONLY_KEY)?;
+    match entry.get_password() {
+        Ok(read_only) => Ok(read_only == "true"),
+        Err(keyring::Error::NoEntry) => Ok(false),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Store the seconds between automatic refreshes of the records, `None` for none
+pub fn store_record_refresh_interval(interval: Option<u64>) -> Result<()> {
+    let entry = Entry::new(SERVICE_NAME, RECORD_REFRESH_INTERVAL_KEY)?;
+    match interval {
+        Some(interval) => entry.set_password(&interval.to_string())?,
+        None => match entry.delete_credential() {
+            Ok(_) | Err(keyring::Error::NoEntry) => {}
+            Err(e) => return Err(e.into()),
+        },
+    }
+    Ok(())
+}
+
+/// Retrieve the seconds between automatic refreshes of the records, `None` for none
+pub fn get_record_refresh_interval() -> Result<Option<u64>> {
+    let entry = Entry::new(SERVICE_NAME, RECORD_REFRESH_INTERVAL_KEY)?;
+    match entry.get_password() {
+        Ok(interval) => Ok(interval.parse().ok()),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Store the account the zone lists are scoped to, `None` for all accounts
+pub fn store_zone_account_filter(account_id: Option<&str>) -> Result<()> {
+    let entry = Entry::new(SERVICE_NAME, ZONE_ACCOUNT_FILTER_KEY)?;
+    match account_id {
+        Some(account_id) => entry.set_password(account_id)?,
+        None => match entry.delete_credential() {
+            Ok(_) | Err(keyring::Error::NoEntry) => {}
+            Err(e) => return Err(e.into()),
+        },
+    }
+    Ok(())
+}
+
+/// Retrieve the account the zone lists are scoped to, `None` for all accounts
+pub fn get_zone_account_filter() -> Result<Option<String>> {
+    let entry = Entry::new(SERVICE_NAME, ZONE_ACCOUNT_FILTER_KEY)?;
+    match entry.get_password() {
+        Ok(account_id) => Ok(Some(account_id)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Store the page the app opens to
+pub fn store_startup_view(view: &StartupView) -> Result<()> {
+    let entry = Entry::new(SERVICE_NAME, STARTUP_VIEW_KEY)?;
+    entry.set_password(&serde_json::to_string(view)?)?;
+    Ok(())
+}
+
+/// Retrieve the page the app opens to
+pub fn get_startup_view() -> Result<StartupView> {
+    let entry = Entry::new(SERVICE_NAME, STARTUP_VIEW_KEY)?;
+    match entry.get_password() {
+        Ok(json) => Ok(serde_json::from_str(&json)?),
+        Err(keyring::Error::NoEntry) => Ok(StartupView::default()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Store the id of the zone viewed last
+pub fn store_last_zone(zone_id: &str) -> Result<()> {
+    let entry = Entry::new(SERVICE_NAME, LAST_ZONE_KEY)?;
+    entry.set_password(zone_id)?;
+    Ok(())
+}
+
+/// Retrieve the id of the zone viewed last
+pub fn get_last_zone() -> Result<Option<String>> {
+    let entry = Entry::new(SERVICE_NAME, LAST_ZONE_KEY)?;
+    match entry.get_password() {
+        Ok(zone_id) => Ok(Some(zone_id)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Store the workspaces
+pub fn store_workspaces(workspaces: &[Workspace]) -> Result<()> {
+    store_data_file(WORKSPACES_FILE, WORKSPACES_KEY, workspaces)
+}
+
+/// Retrieve the workspaces
+pub fn get_workspaces() -> Result<Vec<Workspace>> {
+    get_data_file(WORKSPACES_FILE, WORKSPACES_KEY)
+}
+
+/// Store the name of the workspace the zone lists are scoped to, `None` for all zones
+pub fn store_active_workspace(name: Option<&str>) -> Result<()> {
+    let entry = Entry::new(SERVICE_NAME, ACTIVE_WORKSPACE_KEY)?;
+    match name {
+        Some(name) => entry.set_password(name)?,
+        None => match entry.delete_credential() {
+            Ok(_) | Err(keyring::Error::NoEntry) => {}
+            Err(e) => return Err(e.into()),
+        },
+    }
+    Ok(())
+}
+
+/// Retrieve the name of the workspace the zone lists are scoped to, `None` for all zones
+pub fn get_active_workspace() -> Result<Option<String>> {
+    let entry = Entry::new(SERVICE_NAME, ACTIVE_WORKSPACE_KEY)?;
+    match entry.get_password() {
+        Ok(name) => Ok(Some(name)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
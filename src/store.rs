@@ -0,0 +1,209 @@
+//! Cache of the Cloudflare data shown by the app's views
+//!
+//! The [`DataStore`] entity owns zones, DNS records and zone health. Views read
+//! from it and are re-rendered when it changes, while [`StoreEvent`]s let the app
+//! react to freshly loaded data.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::time::Duration;
+
+use chrono::{DateTime, Local, Utc};
+use gpui::{Context, EventEmitter};
+
+use crate::certificates::{self, TrackedCertificate};
+use crate::cloudflare::{DnsRecord, Zone};
+use crate::filter::RecordQuery;
+use crate::portfolio::ZoneHealth;
+use crate::startup::Snapshot;
+
+/// Cached records younger than this are shown without fetching them again
+pub const RECORDS_MAX_AGE: Duration = Duration::from_secs(5 * 60);
+
+pub enum StoreEvent {
+    ZonesLoaded,
+    /// Records of a zone were replaced; `previous` holds the records cached before, if any
+    RecordsLoaded {
+        zone_id: String,
+        previous: Option<Rc<Vec<DnsRecord>>>,
+    },
+}
+
+/// DNS records of a zone and when they were fetched
+#[derive(Clone)]
+pub struct CachedRecords {
+    pub records: Rc<Vec<DnsRecord>>,
+    pub fetched_at: DateTime<Local>,
+}
+
+/// Last search result, reused while neither the records nor the query change
+struct FilterMemo {
+    zone_id: String,
+    query: String,
+    generation: u64,
+    records: Rc<Vec<DnsRecord>>,
+}
+
+#[derive(Default)]
+pub struct DataStore {
+    zones: Rc<Vec<Zone>>,
+    records: HashMap<String, CachedRecords>,
+    zone_health: HashMap<String, ZoneHealth>,
+    /// Incremented whenever cached records change, invalidating memoized views
+    generation: u64,
+    filter_memo: RefCell<Option<FilterMemo>>,
+}
+
+impl EventEmitter<StoreEvent> for DataStore {}
+
+impl DataStore {
+    pub fn zones(&self) -> Rc<Vec<Zone>> {
+        self.zones.clone()
+    }
+
+    /// Replace the zone list, dropping cached data of zones that no longer exist
+    pub fn set_zones(&mut self, zones: Vec<Zone>, cx: &mut Context<Self>) {
+        self.records
+            .retain(|zone_id, _| zones.iter().any(|zone| &zone.id == zone_id));
+        self.zone_health
+            .retain(|zone_id, _| zones.iter().any(|zone| &zone.id == zone_id));
+        self.zones = Rc::new(zones);
+        self.generation += 1;
+        cx.emit(StoreEvent::ZonesLoaded);
+        cx.notify();
+    }
+
+    /// Show the zones and records of the last run until fresh ones load; the records keep the
+    /// time they were fetched, so they are refreshed once they are stale
+    pub fn restore(&mut self, snapshot: Snapshot, cx: &mut Context<Self>) {
+        if let Some(zone_id) = snapshot.zone_id {
+            self.records.insert(
+                zone_id,
+                CachedRecords {
+                    records: Rc::new(snapshot.records),
+                    fetched_at: snapshot.fetched_at,
+                },
+            );
+        }
+        self.set_zones(snapshot.zones, cx);
+    }
+
+    pub fn records(&self, zone_id: &str) -> Option<&CachedRecords> {
+        self.records.get(zone_id)
+    }
+
+    /// Whether the cached records of a zone are recent enough to show without a refresh
+    pub fn has_fresh_records(&self, zone_id: &str) -> bool {
+        self.records.get(zone_id).is_some_and(|cached| {
+            (Local::now() - cached.fetched_at)
+                .to_std()
+                .is_ok_and(|age| age < RECORDS_MAX_AGE)
+        })
+    }
+
+    pub fn set_records(
+        &mut self,
+        zone_id: String,
+        records: Vec<DnsRecord>,
+        cx: &mut Context<Self>,
+    ) {
+        // Unchanged records are kept as they are, so the views and the filter memo are too
+        if let Some(cached) = self.records.get_mut(&zone_id)
+            && *cached.records == records
+        {
+            cached.fetched_at = Local::now();
+            return;
+        }
+
+        let previous = self
+            .records
+            .insert(
+                zone_id.clone(),
+                CachedRecords {
+                    records: Rc::new(records),
+                    fetched_at: Local::now(),
+                },
+            )
+            .map(|cached| cached.records);
+        self.generation += 1;
+        cx.emit(StoreEvent::RecordsLoaded { zone_id, previous });
+        cx.notify();
+    }
+
+    /// Records of a zone matching a search query, memoized for the last query
+    pub fn filtered_records(&self, zone_id: &str, query: &str) -> Rc<Vec<DnsRecord>> {
+        let Some(cached) = self.records.get(zone_id) else {
+            return Rc::default();
+        };
+
+        if let Some(memo) = self.filter_memo.borrow().as_ref()
+            && memo.zone_id == zone_id
+            && memo.query == query
+            && memo.generation == self.generation
+        {
+            return memo.records.clone();
+        }
+
+        let parsed = RecordQuery::parse(query);
+        let records = if parsed.is_empty() {
+            cached.records.clone()
+        } else {
+            Rc::new(
+                cached
+                    .records
+                    .iter()
+                    .filter(|record| parsed.matches(record))
+                    .cloned()
+                    .collect(),
+            )
+        };
+
+        *self.filter_memo.borrow_mut() = Some(FilterMemo {
+            zone_id: zone_id.to_string(),
+            query: query.to_string(),
+            generation: self.generation,
+            records: records.clone(),
+        });
+        records
+    }
+
+    pub fn zone_health(&self) -> &HashMap<String, ZoneHealth> {
+        &self.zone_health
+    }
+
+    pub fn set_zone_health(&mut self, zone_id: String, health: ZoneHealth, cx: &mut Context<Self>) {
+        self.zone_health.insert(zone_id, health);
+        cx.notify();
+    }
+
+    /// Certificates of all zones expiring within [`certificates::EXPIRING_SOON_DAYS`], soonest first
+    pub fn expiring_certificates(&self) -> Vec<(Zone, TrackedCertificate)> {
+        let now = Utc::now();
+        let mut expiring: Vec<(Zone, TrackedCertificate)> = self
+            .zones
+            .iter()
+            .filter_map(|zone| Some((zone, self.zone_health.get(&zone.id)?)))
+            .flat_map(|(zone, health)| {
+                health
+                    .certificates
+                    .iter()
+                    .filter(move |certificate| {
+                        certificate.days_until_expiry(now) <= certificates::EXPIRING_SOON_DAYS
+                    })
+                    .map(move |certificate| (zone.clone(), certificate.clone()))
+            })
+            .collect();
+        expiring.sort_by_key(|(_, certificate)| certificate.expires_on);
+        expiring
+    }
+
+    /// Forget all cached data, e.g. when the API token changes
+    pub fn clear(&mut self, cx: &mut Context<Self>) {
+        self.zones = Rc::default();
+        self.records.clear();
+        self.zone_health.clear();
+        self.generation += 1;
+        cx.notify();
+    }
+}
@@ -0,0 +1,126 @@
+//! Suggestions for the record editor's name and content, taken from the records already in the
+//! zone
+//!
+//! Most zones point at a handful of addresses and targets, so offering them while typing saves
+//! retyping them and the typos that come with it.
+
+use crate::cloudflare::{DnsRecord, DnsRecordType, qualified_name};
+
+/// Suggestions shown at most below a field
+const MAX_SUGGESTIONS: usize = 5;
+
+/// A value used by records of the zone, with how many use it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Suggestion {
+    pub value: String,
+    pub uses: usize,
+}
+
+/// Contents of other records of `record_type` containing `typed`, most used first
+///
+/// `editing_id` leaves out the record being edited, and what's typed already is not suggested.
+pub fn content_suggestions(
+    records: &[DnsRecord],
+    record_type: DnsRecordType,
+    typed: &str,
+    editing_id: Option<&str>,
+) -> Vec<Suggestion> {
+    let typed = typed.trim();
+    let needle = typed.to_lowercase();
+    let mut suggestions: Vec<Suggestion> = Vec::new();
+    for record in records {
+        if record.record_type != record_type
+            || Some(record.id.as_str()) == editing_id
+            || record.content == typed
+            || !record.content.to_lowercase().contains(&needle)
+        {
+            continue;
+        }
+        match suggestions
+            .iter_mut()
+            .find(|suggestion| suggestion.value == record.content)
+        {
+            Some(suggestion) => suggestion.uses += 1,
+            None => suggestions.push(Suggestion {
+                value: record.content.clone(),
+                uses: 1,
+            }),
+        }
+    }
+
+    suggestions.sort_by(|a, b| b.uses.cmp(&a.uses).then_with(|| a.value.cmp(&b.value)));
+    suggestions.truncate(MAX_SUGGESTIONS);
+    suggestions
+}
+
+/// Prefixes most zones have or will have a record at
+const COMMON_PREFIXES: &[&str] = &["www", "api", "mail", "app", "blog", "dev", "staging"];
+
+/// A hostname of the zone for the name field
+#[derive(Debug, Clone, PartialEq)]
+pub struct NameSuggestion {
+    pub name: String,
+    /// Records at the name
+    pub records: usize,
+    /// Records at the name of the type being edited
+    pub same_type: usize,
+}
+
+/// Hostnames of the zone and common prefixes under it matching `typed`, names starting with it
+/// and ones with records first
+///
+/// `editing_id` leaves out the record being edited, and what's typed already is not suggested.
+pub fn name_suggestions(
+    zone: &str,
+    records: &[DnsRecord],
+    record_type: DnsRecordType,
+    typed: &str,
+    editing_id: Option<&str>,
+) -> Vec<NameSuggestion> {
+    let typed_name = qualified_name(typed, zone);
+    let zone = zone.trim_end_matches('.').to_lowercase();
+    let typed = typed.trim().trim_end_matches('.').to_lowercase();
+
+    let mut suggestions: Vec<NameSuggestion> = Vec::new();
+    let names = records
+        .iter()
+        .filter(|record| Some(record.id.as_str()) != editing_id)
+        .map(|record| record.name.to_lowercase())
+        .chain(
+            COMMON_PREFIXES
+                .iter()
+                .map(|prefix| format!("{}.{}", prefix, zone)),
+        );
+    for name in names {
+        if suggestions.iter().any(|suggestion| suggestion.name == name) {
+            continue;
+        }
+        let at_name: Vec<&DnsRecord> = records
+            .iter()
+            .filter(|record| {
+                Some(record.id.as_str()) != editing_id && record.name.eq_ignore_ascii_case(&name)
+            })
+            .collect();
+        suggestions.push(NameSuggestion {
+            same_type: at_name
+                .iter()
+                .filter(|record| record.record_type == record_type)
+                .count(),
+            records: at_name.len(),
+            name,
+        });
+    }
+
+    suggestions.retain(|suggestion| {
+        typed.is_empty() || (suggestion.name != typed_name && suggestion.name.contains(&typed))
+    });
+    suggestions.sort_by_key(|suggestion| {
+        (
+            !suggestion.name.starts_with(&typed),
+            suggestion.records == 0,
+            suggestion.name.len(),
+        )
+    });
+    suggestions.truncate(MAX_SUGGESTIONS);
+    suggestions
+}
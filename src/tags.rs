@@ -0,0 +1,109 @@
+//! Local tags for grouping and filtering zones
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use crate::cloudflare::Zone;
+
+/// Title of the group of zones without tags
+pub const UNTAGGED_GROUP: &str = "Untagged";
+
+/// Title of the single group used when no zone is tagged
+pub const ALL_ZONES_GROUP: &str = "All Zones";
+
+/// Title of the group of pinned zones, listed first in the zone picker
+pub const PINNED_GROUP: &str = "Pinned";
+
+/// Parse comma-separated tags, dropping blanks and duplicates
+pub fn parse_tags(input: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in input.split(',').map(|tag| tag.trim().to_lowercase()) {
+        if !tag.is_empty() && !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    tags
+}
+
+/// All tags in use, sorted
+pub fn all_tags(zone_tags: &HashMap<String, Vec<String>>) -> Vec<String> {
+    zone_tags
+        .values()
+        .flatten()
+        .cloned()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// Group zone indices by tag, keeping only zones with the `filter` tag if given, of the
+/// account with id `account` if given, and with ids in `workspace` if given
+///
+/// Zones with several tags appear in each of their groups. Without any tags in
+/// use, all zones form a single group.
+pub fn group_zones(
+    zones: &[Zone],
+    zone_tags: &HashMap<String, Vec<String>>,
+    filter: Option<&str>,
+    account: Option<&str>,
+    workspace: Option<&HashSet<String>>,
+) -> Vec<(String, Vec<usize>)> {
+    let tags_of = |zone: &Zone| zone_tags.get(&zone.id).map(Vec::as_slice).unwrap_or(&[]);
+    let in_scope = || {
+        (0..zones.len()).filter(|&index| {
+            account.is_none_or(|id| zones[index].account.id == id)
+                && workspace.is_none_or(|zone_ids| zone_ids.contains(&zones[index].id))
+        })
+    };
+
+    if let Some(filter) = filter {
+        let indices: Vec<usize> = in_scope()
+            .filter(|&index| tags_of(&zones[index]).iter().any(|tag| tag == filter))
+            .collect();
+        return vec![(filter.to_string(), indices)];
+    }
+
+    let tags = all_tags(zone_tags);
+    if tags.is_empty() {
+        return vec![(ALL_ZONES_GROUP.to_string(), in_scope().collect())];
+    }
+
+    let mut groups: Vec<(String, Vec<usize>)> = tags
+        .into_iter()
+        .filter_map(|tag| {
+            let indices: Vec<usize> = in_scope()
+                .filter(|&index| tags_of(&zones[index]).contains(&tag))
+                .collect();
+            (!indices.is_empty()).then_some((tag, indices))
+        })
+        .collect();
+
+    let untagged: Vec<usize> = in_scope()
+        .filter(|&index| tags_of(&zones[index]).is_empty())
+        .collect();
+    if !untagged.is_empty() {
+        groups.push((UNTAGGED_GROUP.to_string(), untagged));
+    }
+
+    groups
+}
+
+/// Put a group of the pinned zones among `groups` first, in the order of `zones`
+///
+/// Pinned zones stay in their own groups too, like zones with several tags.
+pub fn pin_zones(
+    mut groups: Vec<(String, Vec<usize>)>,
+    zones: &[Zone],
+    pinned: &HashSet<String>,
+) -> Vec<(String, Vec<usize>)> {
+    let mut indices: Vec<usize> = groups
+        .iter()
+        .flat_map(|(_, indices)| indices.iter().copied())
+        .filter(|&index| pinned.contains(&zones[index].id))
+        .collect();
+    indices.sort_unstable();
+    indices.dedup();
+    if !indices.is_empty() {
+        groups.insert(0, (PINNED_GROUP.to_string(), indices));
+    }
+    groups
+}
@@ -0,0 +1,114 @@
+//! Tracking of named background tasks with progress reporting and cancellation
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use gpui::{AsyncApp, Context, SharedString, Task, WeakEntity};
+
+use crate::App;
+
+/// Identifier of a task tracked by [`TaskManager`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TaskId(u64);
+
+/// Handle given to a task's work so it can report how far along it is
+#[derive(Clone)]
+pub struct TaskProgress {
+    progress: Rc<Cell<Option<(usize, usize)>>>,
+    app: WeakEntity<App>,
+    cx: AsyncApp,
+}
+
+impl TaskProgress {
+    /// Report that `done` out of `total` items have been processed
+    pub fn set(&self, done: usize, total: usize) {
+        self.progress.set(Some((done, total)));
+        let mut cx = self.cx.clone();
+        self.app.update(&mut cx, |_, cx| cx.notify()).ok();
+    }
+
+    /// Update the app from within the task's work, e.g. to record per-item results
+    pub fn update_app(&self, f: impl FnOnce(&mut App, &mut Context<App>)) {
+        let mut cx = self.cx.clone();
+        self.app
+            .update(&mut cx, |app, cx| {
+                f(app, cx);
+                cx.notify();
+            })
+            .ok();
+    }
+}
+
+/// A running task
+pub struct TrackedTask {
+    pub id: TaskId,
+    pub name: SharedString,
+    progress: Rc<Cell<Option<(usize, usize)>>>,
+    // Dropping the task cancels it
+    _task: Task<()>,
+}
+
+impl TrackedTask {
+    /// Progress as `(done, total)`, if the task reports it
+    pub fn progress(&self) -> Option<(usize, usize)> {
+        self.progress.get()
+    }
+}
+
+/// Keeps track of the app's running background tasks
+#[derive(Default)]
+pub struct TaskManager {
+    next_id: u64,
+    tasks: Vec<TrackedTask>,
+}
+
+impl TaskManager {
+    /// Reserve an id and create the progress handle for a new task
+    pub fn prepare(&mut self, app: WeakEntity<App>, cx: AsyncApp) -> (TaskId, TaskProgress) {
+        let id = TaskId(self.next_id);
+        self.next_id += 1;
+
+        let progress = TaskProgress {
+            progress: Rc::new(Cell::new(None)),
+            app,
+            cx,
+        };
+        (id, progress)
+    }
+
+    /// Start tracking a spawned task
+    pub fn insert(
+        &mut self,
+        id: TaskId,
+        name: SharedString,
+        progress: &TaskProgress,
+        task: Task<()>,
+    ) {
+        self.tasks.push(TrackedTask {
+            id,
+            name,
+            progress: progress.progress.clone(),
+            _task: task,
+        });
+    }
+
+    /// Stop tracking a finished task
+    pub fn finish(&mut self, id: TaskId) {
+        self.tasks.retain(|t| t.id != id);
+    }
+
+    /// Cancel a task, returning whether it was still running
+    pub fn cancel(&mut self, id: TaskId) -> bool {
+        let len = self.tasks.len();
+        self.tasks.retain(|t| t.id != id);
+        self.tasks.len() != len
+    }
+
+    pub fn tasks(&self) -> &[TrackedTask] {
+        &self.tasks
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+}
@@ -0,0 +1,252 @@
+//! Context imported from the Terraform state of teams that manage zones as code
+//!
+//! Zones in the state are tagged `terraform`, and with the module they are declared in, get a
+//! note saying where they are managed, and the records Terraform manages are protected, so
+//! changes made here don't silently drift from the code. Both the state file itself
+//! (`terraform.tfstate`, format version 4) and the output of `terraform show -json` are read.
+//! flarectl keeps no state or per-zone configuration, so there is nothing to import from it.
+
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use crate::cloudflare::Zone;
+
+/// Tag of zones found in a Terraform state
+pub const TERRAFORM_TAG: &str = "terraform";
+
+/// Tags, notes and protected records read from a Terraform state
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImportedContext {
+    /// Tags by zone id
+    pub zone_tags: HashMap<String, Vec<String>>,
+    /// Notes by zone id
+    pub zone_notes: HashMap<String, String>,
+    /// Why each record is protected, by record id
+    pub protected_records: HashMap<String, String>,
+}
+
+impl ImportedContext {
+    pub fn is_empty(&self) -> bool {
+        self.zone_tags.is_empty() && self.protected_records.is_empty()
+    }
+}
+
+/// A resource of the state with its address, e.g. `module.dns.cloudflare_record.www`
+struct Resource<'a> {
+    address: String,
+    module: Option<String>,
+    kind: &'a str,
+    attributes: &'a Value,
+}
+
+/// Read the Cloudflare zones and DNS records of a Terraform state, keeping zones of `zones`
+pub fn parse_terraform_state(contents: &str, zones: &[Zone]) -> Result<ImportedContext, String> {
+    let state: Value =
+        serde_json::from_str(contents).map_err(|e| format!("Not a Terraform state: {}", e))?;
+    let resources = if let Some(root) = state.pointer("/values/root_module") {
+        let mut resources = Vec::new();
+        show_json_resources(root, &mut resources);
+        resources
+    } else if let Some(list) = state.get("resources").and_then(Value::as_array) {
+        state_file_resources(list)
+    } else {
+        return Err("Not a Terraform state: it has no resources".to_string());
+    };
+
+    let zone_of = |attributes: &Value, id_key: &str, name_key: &str| {
+        let id = attributes.get(id_key).and_then(Value::as_str);
+        let name = attributes.get(name_key).and_then(Value::as_str);
+        zones.iter().find(|zone| {
+            id == Some(zone.id.as_str())
+                || name.is_some_and(|name| name.eq_ignore_ascii_case(&zone.name))
+        })
+    };
+
+    let mut context = ImportedContext::default();
+    let mut managed_records: HashMap<String, usize> = HashMap::new();
+    for resource in &resources {
+        match resource.kind {
+            "cloudflare_zone" => {
+                // Provider v4 names the zone `zone`, v5 `name`
+                let Some(zone) = zone_of(resource.attributes, "id", "zone")
+                    .or_else(|| zone_of(resource.attributes, "id", "name"))
+                else {
+                    continue;
+                };
+                let tags = context.zone_tags.entry(zone.id.clone()).or_default();
+                add_tag(tags, TERRAFORM_TAG);
+                if let Some(module) = &resource.module {
+                    add_tag(tags, module);
+                }
+                add_note(
+                    &mut context.zone_notes,
+                    &zone.id,
+                    format!("Managed by Terraform as {}", resource.address),
+                );
+            }
+            // `cloudflare_record` in provider v4, `cloudflare_dns_record` in v5
+            "cloudflare_record" | "cloudflare_dns_record" => {
+                let Some(zone) = zone_of(resource.attributes, "zone_id", "zone") else {
+                    continue;
+                };
+                let Some(record_id) = resource.attributes.get("id").and_then(Value::as_str) else {
+                    continue;
+                };
+                context.protected_records.insert(
+                    record_id.to_string(),
+                    format!("Managed by Terraform as {}", resource.address),
+                );
+                add_tag(
+                    context.zone_tags.entry(zone.id.clone()).or_default(),
+                    TERRAFORM_TAG,
+                );
+                *managed_records.entry(zone.id.clone()).or_default() += 1;
+            }
+            _ => {}
+        }
+    }
+
+    for (zone_id, count) in managed_records {
+        add_note(
+            &mut context.zone_notes,
+            &zone_id,
+            format!(
+                "{} DNS record{} managed by Terraform",
+                count,
+                if count == 1 { "" } else { "s" }
+            ),
+        );
+    }
+    Ok(context)
+}
+
+/// Resources of a `terraform.tfstate` file, one per instance
+fn state_file_resources(list: &[Value]) -> Vec<Resource<'_>> {
+    let mut resources = Vec::new();
+    for resource in list {
+        if resource.get("mode").and_then(Value::as_str) != Some("managed") {
+            continue;
+        }
+        let kind = resource
+            .get("type")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        let name = resource
+            .get("name")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        let module = resource.get("module").and_then(Value::as_str);
+        let instances = resource
+            .get("instances")
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        for instance in instances {
+            let Some(attributes) = instance.get("attributes") else {
+                continue;
+            };
+            // Instances of `count` and `for_each` resources have an index key
+            let index = match instance.get("index_key") {
+                Some(Value::Number(index)) => format!("[{}]", index),
+                Some(Value::String(key)) => format!("[\"{}\"]", key),
+                _ => String::new(),
+            };
+            let address = format!("{}.{}{}", kind, name, index);
+            resources.push(Resource {
+                address: match module {
+                    Some(module) => format!("{}.{}", module, address),
+                    None => address,
+                },
+                module: module.map(module_name),
+                kind,
+                attributes,
+            });
+        }
+    }
+    resources
+}
+
+/// Resources of a module of `terraform show -json` output and its child modules
+fn show_json_resources<'a>(module: &'a Value, resources: &mut Vec<Resource<'a>>) {
+    let module_address = module.get("address").and_then(Value::as_str);
+    for resource in module
+        .get("resources")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+    {
+        if resource.get("mode").and_then(Value::as_str) != Some("managed") {
+            continue;
+        }
+        let (Some(address), Some(kind), Some(attributes)) = (
+            resource.get("address").and_then(Value::as_str),
+            resource.get("type").and_then(Value::as_str),
+            resource.get("values"),
+        ) else {
+            continue;
+        };
+        resources.push(Resource {
+            address: address.to_string(),
+            module: module_address.map(module_name),
+            kind,
+            attributes,
+        });
+    }
+    for child in module
+        .get("child_modules")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+    {
+        show_json_resources(child, resources);
+    }
+}
+
+/// Name of the innermost module as a tag, e.g. `dns` for `module.zones.module.dns`
+fn module_name(address: &str) -> String {
+    let name = address.rsplit("module.").next().unwrap_or(address);
+    // Drop the index of modules with `count` or `for_each`
+    let name = name.split('[').next().unwrap_or(name);
+    name.trim_end_matches('.').to_lowercase()
+}
+
+fn add_tag(tags: &mut Vec<String>, tag: &str) {
+    if !tags.iter().any(|existing| existing == tag) {
+        tags.push(tag.to_string());
+    }
+}
+
+fn add_note(notes: &mut HashMap<String, String>, zone_id: &str, line: String) {
+    let note = notes.entry(zone_id.to_string()).or_default();
+    if !note.lines().any(|existing| existing == line) {
+        if !note.is_empty() {
+            note.push('\n');
+        }
+        note.push_str(&line);
+    }
+}
+
+/// Merge imported context into what the user already has, keeping existing tags, notes and
+/// protection reasons
+pub fn merge(
+    context: ImportedContext,
+    zone_tags: &mut HashMap<String, Vec<String>>,
+    zone_notes: &mut HashMap<String, String>,
+    protected_records: &mut HashMap<String, String>,
+) {
+    for (zone_id, tags) in context.zone_tags {
+        let existing = zone_tags.entry(zone_id).or_default();
+        for tag in tags {
+            add_tag(existing, &tag);
+        }
+    }
+    for (zone_id, note) in context.zone_notes {
+        for line in note.lines() {
+            add_note(zone_notes, &zone_id, line.to_string());
+        }
+    }
+    for (record_id, reason) in context.protected_records {
+        protected_records.entry(record_id).or_insert(reason);
+    }
+}
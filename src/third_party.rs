@@ -0,0 +1,131 @@
+//! Third-party platforms that CNAME records point at, for an inventory of what a zone depends on
+//!
+//! A target is resolved through [`doh`] and every name in its CNAME chain is matched against
+//! the platforms' known domains, so custom domains that end at a platform are found as well.
+
+use reqwest::Client;
+
+use crate::doh::{self, TYPE_A, TYPE_CNAME};
+
+/// Longest CNAME chain followed; longer chains are most likely loops
+const MAX_CHAIN_LENGTH: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ThirdParty {
+    Heroku,
+    Vercel,
+    Fastly,
+    GitHubPages,
+    Aws,
+}
+
+impl ThirdParty {
+    pub const ALL: [ThirdParty; 5] = [
+        ThirdParty::Heroku,
+        ThirdParty::Vercel,
+        ThirdParty::Fastly,
+        ThirdParty::GitHubPages,
+        ThirdParty::Aws,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ThirdParty::Heroku => "Heroku",
+            ThirdParty::Vercel => "Vercel",
+            ThirdParty::Fastly => "Fastly",
+            ThirdParty::GitHubPages => "GitHub Pages",
+            ThirdParty::Aws => "AWS",
+        }
+    }
+
+    /// Path of the platform's icon in the app's assets
+    pub fn icon_path(&self) -> &'static str {
+        match self {
+            ThirdParty::Heroku => "icons/providers/heroku.svg",
+            ThirdParty::Vercel => "icons/providers/vercel.svg",
+            ThirdParty::Fastly => "icons/providers/fastly.svg",
+            ThirdParty::GitHubPages => "icons/github.svg",
+            ThirdParty::Aws => "icons/providers/aws.svg",
+        }
+    }
+
+    fn domains(&self) -> &'static [&'static str] {
+        match self {
+            ThirdParty::Heroku => &["herokuapp.com", "herokudns.com", "herokussl.com"],
+            ThirdParty::Vercel => &["vercel-dns.com", "vercel.app", "now.sh"],
+            ThirdParty::Fastly => &["fastly.net", "fastlylb.net"],
+            ThirdParty::GitHubPages => &["github.io"],
+            ThirdParty::Aws => &[
+                "amazonaws.com",
+                "cloudfront.net",
+                "awsglobalaccelerator.com",
+                "elasticbeanstalk.com",
+                "amplifyapp.com",
+            ],
+        }
+    }
+
+    /// Platform a single name belongs to
+    pub fn detect(name: &str) -> Option<Self> {
+        let name = normalize(name);
+        Self::ALL.into_iter().find(|third_party| {
+            third_party
+                .domains()
+                .iter()
+                .any(|domain| name == *domain || name.ends_with(&format!(".{}", domain)))
+        })
+    }
+}
+
+/// Where a CNAME target ends up
+#[derive(Debug, Clone, PartialEq)]
+pub struct CnameTarget {
+    /// The target followed by the names it is an alias of, in order
+    pub chain: Vec<String>,
+    /// Platform closest to the end of the chain
+    pub third_party: Option<ThirdParty>,
+}
+
+impl CnameTarget {
+    /// Target whose chain is unknown, matched by its own name only
+    pub fn unresolved(target: &str) -> Self {
+        Self::from_chain(vec![normalize(target)])
+    }
+
+    fn from_chain(chain: Vec<String>) -> Self {
+        let third_party = chain.iter().rev().find_map(|name| ThirdParty::detect(name));
+        Self { chain, third_party }
+    }
+}
+
+/// Follow the CNAME chain of a target with `client`; lookup failures leave the chain as far as
+/// it was followed
+pub async fn resolve(client: &Client, target: &str) -> CnameTarget {
+    let mut chain = vec![normalize(target)];
+
+    // Resolvers return the whole chain in the answer to an A query
+    if let Ok(answers) = doh::query(client, &chain[0], TYPE_A).await {
+        while chain.len() < MAX_CHAIN_LENGTH {
+            let current = chain.last().unwrap();
+            let Some(next) = answers
+                .iter()
+                .find(|answer| {
+                    answer.record_type == TYPE_CNAME && normalize(&answer.name) == *current
+                })
+                .map(|answer| normalize(&answer.data))
+            else {
+                break;
+            };
+            if chain.contains(&next) {
+                break;
+            }
+            chain.push(next);
+        }
+    }
+
+    CnameTarget::from_chain(chain)
+}
+
+fn normalize(name: &str) -> String {
+    name.trim().trim_end_matches('.').to_lowercase()
+}
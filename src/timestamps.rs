@@ -0,0 +1,67 @@
+//! Record timestamps shown as relative times like `3h ago`, with the full time in local time
+//! or UTC
+
+use chrono::{DateTime, Local, Utc};
+
+/// Time zone used for full timestamps
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum TimeZoneDisplay {
+    #[default]
+    Local,
+    Utc,
+}
+
+impl TimeZoneDisplay {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TimeZoneDisplay::Local => "local",
+            TimeZoneDisplay::Utc => "utc",
+        }
+    }
+
+    pub fn parse(s: &str) -> Self {
+        match s {
+            "utc" => TimeZoneDisplay::Utc,
+            _ => TimeZoneDisplay::Local,
+        }
+    }
+
+    /// Full timestamp, e.g. `2024-05-01 14:03:27 UTC` or `2024-05-01 16:03:27 +02:00`
+    pub fn format(&self, time: DateTime<Utc>) -> String {
+        match self {
+            TimeZoneDisplay::Local => time
+                .with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M:%S %:z")
+                .to_string(),
+            TimeZoneDisplay::Utc => time.format("%Y-%m-%d %H:%M:%S UTC").to_string(),
+        }
+    }
+
+    /// Relative time like `5m ago`, or the date for times more than 30 days ago
+    pub fn relative(&self, time: DateTime<Utc>, now: DateTime<Utc>) -> String {
+        let elapsed = now.signed_duration_since(time);
+        if elapsed.num_minutes() < 1 {
+            // Also covers times slightly in the future because of clock skew
+            "just now".to_string()
+        } else if elapsed.num_hours() < 1 {
+            format!("{}m ago", elapsed.num_minutes())
+        } else if elapsed.num_days() < 1 {
+            format!("{}h ago", elapsed.num_hours())
+        } else if elapsed.num_days() <= 30 {
+            format!("{}d ago", elapsed.num_days())
+        } else {
+            match self {
+                TimeZoneDisplay::Local => time.with_timezone(&Local).format("%Y-%m-%d"),
+                TimeZoneDisplay::Utc => time.format("%Y-%m-%d"),
+            }
+            .to_string()
+        }
+    }
+}
+
+/// Parse a timestamp returned by the Cloudflare API
+pub fn parse(timestamp: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(timestamp)
+        .ok()
+        .map(|time| time.with_timezone(&Utc))
+}
@@ -0,0 +1,169 @@
+//! API tokens of other Cloudflare tools found on this machine
+//!
+//! Developers who already use wrangler or flarectl have a token lying around: in the
+//! `CLOUDFLARE_API_TOKEN` (wrangler) or `CF_API_TOKEN` (flarectl) environment variable, in an
+//! `export` of those in a shell profile, since apps launched from the desktop don't see the
+//! shell's environment, or as `api_token` in wrangler's config. The token setup offers to use one
+//! instead of pasting it; nothing is read into the keyring without that consent.
+//!
+//! wrangler's OAuth login (`oauth_token`) is not offered, as it expires within hours.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const TOKEN_VARIABLES: &[&str] = &["CLOUDFLARE_API_TOKEN", "CF_API_TOKEN"];
+
+/// Shell profiles and dotenv files, relative to the home directory
+const SHELL_FILES: &[&str] = &[
+    ".env",
+    ".profile",
+    ".bash_profile",
+    ".bashrc",
+    ".zprofile",
+    ".zshrc",
+];
+
+/// Where a token was found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenSource {
+    /// Name of the environment variable
+    Environment(&'static str),
+    WranglerConfig(PathBuf),
+    /// A shell profile or dotenv file, with the variable set in it
+    ShellFile(PathBuf, &'static str),
+}
+
+impl TokenSource {
+    /// E.g. "CLOUDFLARE_API_TOKEN in ~/.zshrc"
+    pub fn label(&self) -> String {
+        match self {
+            TokenSource::Environment(variable) => format!("the {} environment variable", variable),
+            TokenSource::WranglerConfig(path) => {
+                format!("wrangler's config ({})", display_path(path))
+            }
+            TokenSource::ShellFile(path, variable) => {
+                format!("{} in {}", variable, display_path(path))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FoundToken {
+    pub token: String,
+    pub source: TokenSource,
+}
+
+/// Tokens set up for other tools, each once, in the order above
+pub fn detect() -> Vec<FoundToken> {
+    let mut found: Vec<FoundToken> = Vec::new();
+    let mut add = |token: String, source: TokenSource| {
+        if !found.iter().any(|existing| existing.token == token) {
+            found.push(FoundToken { token, source });
+        }
+    };
+
+    for variable in TOKEN_VARIABLES {
+        if let Ok(token) = std::env::var(variable)
+            && is_token(token.trim())
+        {
+            add(token.trim().to_string(), TokenSource::Environment(variable));
+        }
+    }
+
+    for path in wrangler_config_paths() {
+        if let Ok(contents) = fs::read_to_string(&path)
+            && let Some(token) = parse_wrangler_config(&contents)
+        {
+            add(token, TokenSource::WranglerConfig(path));
+        }
+    }
+
+    if let Some(home) = dirs::home_dir() {
+        for file in SHELL_FILES {
+            let path = home.join(file);
+            if let Ok(contents) = fs::read_to_string(&path) {
+                for (variable, token) in parse_shell_file(&contents) {
+                    add(token, TokenSource::ShellFile(path.clone(), variable));
+                }
+            }
+        }
+    }
+
+    found
+}
+
+/// wrangler 1 kept its config in `~/.wrangler`, later versions in the platform's config or
+/// preferences directory
+fn wrangler_config_paths() -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = [dirs::home_dir(), dirs::config_dir(), dirs::preference_dir()]
+        .into_iter()
+        .flatten()
+        .map(|dir| dir.join(".wrangler").join("config").join("default.toml"))
+        .collect();
+    paths.dedup();
+    paths
+}
+
+/// The `api_token` of a wrangler config, e.g. `api_token = "…"`
+fn parse_wrangler_config(contents: &str) -> Option<String> {
+    contents.lines().find_map(|line| {
+        let (key, value) = line.split_once('=')?;
+        if key.trim() != "api_token" {
+            return None;
+        }
+        let token = unquote(value.trim());
+        is_token(token).then(|| token.to_string())
+    })
+}
+
+/// Token variables set in a shell profile or dotenv file, e.g. `export CF_API_TOKEN=…`
+///
+/// Values made of other variables or commands, e.g. `$(pass cloudflare)`, are skipped, as only
+/// the shell can tell what they are.
+fn parse_shell_file(contents: &str) -> Vec<(&'static str, String)> {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            let line = line.strip_prefix("export ").unwrap_or(line).trim_start();
+            let (name, value) = line.split_once('=')?;
+            let variable = TOKEN_VARIABLES
+                .iter()
+                .find(|variable| **variable == name.trim())?;
+            // Drop a trailing comment
+            let value = value.split(" #").next().unwrap_or(value).trim();
+            let token = unquote(value);
+            is_token(token).then(|| (*variable, token.to_string()))
+        })
+        .collect()
+}
+
+fn unquote(value: &str) -> &str {
+    value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .or_else(|| {
+            value
+                .strip_prefix('\'')
+                .and_then(|value| value.strip_suffix('\''))
+        })
+        .unwrap_or(value)
+}
+
+/// API tokens are 40 characters of letters, digits, `-` and `_`; a looser check keeps
+/// placeholders such as `<your token>` and shell expansions out
+pub fn is_token(value: &str) -> bool {
+    value.len() >= 32
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// `path` with the home directory shortened to `~`
+fn display_path(path: &Path) -> String {
+    match dirs::home_dir().and_then(|home| path.strip_prefix(home).ok().map(Path::to_path_buf)) {
+        Some(relative) => format!("~/{}", relative.display()),
+        None => path.display().to_string(),
+    }
+}
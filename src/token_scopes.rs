@@ -0,0 +1,247 @@
+//! What an API token may do, read from its policies
+//!
+//! Tokens list the permission groups they are granted, e.g. `DNS Write`, and the resources
+//! those apply to: all zones, the zones of some accounts, or single zones. The app hides what
+//! the token can't use; while the token's details are unknown, everything is shown.
+
+use std::collections::{BTreeSet, HashSet};
+
+use serde_json::Value;
+
+use crate::cloudflare::{TokenDetails, Zone};
+
+const ZONE_RESOURCE_PREFIX: &str = "com.cloudflare.api.account.zone.";
+const ACCOUNT_RESOURCE_PREFIX: &str = "com.cloudflare.api.account.";
+
+/// Parts of the app that need a permission
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Capability {
+    ReadDns,
+    EditDns,
+    EditZones,
+    ZoneSettings,
+    PurgeCache,
+    PageRules,
+    Waf,
+    WorkerRoutes,
+    Pages,
+    Tunnels,
+    Certificates,
+    Analytics,
+    AuditLogs,
+}
+
+impl Capability {
+    pub const ALL: [Capability; 13] = [
+        Capability::ReadDns,
+        Capability::EditDns,
+        Capability::EditZones,
+        Capability::ZoneSettings,
+        Capability::PurgeCache,
+        Capability::PageRules,
+        Capability::Waf,
+        Capability::WorkerRoutes,
+        Capability::Pages,
+        Capability::Tunnels,
+        Capability::Certificates,
+        Capability::Analytics,
+        Capability::AuditLogs,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Capability::ReadDns => "Read DNS records",
+            Capability::EditDns => "Edit DNS records",
+            Capability::EditZones => "Add and delete zones",
+            Capability::ZoneSettings => "Zone settings",
+            Capability::PurgeCache => "Purge cache",
+            Capability::PageRules => "Page Rules",
+            Capability::Waf => "WAF rules",
+            Capability::WorkerRoutes => "Workers routes",
+            Capability::Pages => "Pages projects",
+            Capability::Tunnels => "Tunnels",
+            Capability::Certificates => "SSL/TLS certificates",
+            Capability::Analytics => "Analytics",
+            Capability::AuditLogs => "Audit logs",
+        }
+    }
+
+    /// Whether it only makes changes, so it is off in read-only mode
+    pub fn changes(&self) -> bool {
+        matches!(
+            self,
+            Capability::EditDns | Capability::EditZones | Capability::PurgeCache
+        )
+    }
+
+    /// Permission groups granting it, any of which will do
+    fn permission_groups(&self) -> &'static [&'static str] {
+        match self {
+            Capability::ReadDns => &["DNS Read", "DNS Write"],
+            Capability::EditDns => &["DNS Write"],
+            Capability::EditZones => &["Zone Write"],
+            Capability::ZoneSettings => &["Zone Settings Read", "Zone Settings Write"],
+            Capability::PurgeCache => &["Cache Purge"],
+            Capability::PageRules => &["Page Rules Read", "Page Rules Write"],
+            Capability::Waf => &[
+                "Zone WAF Read",
+                "Zone WAF Write",
+                "Firewall Services Read",
+                "Firewall Services Write",
+            ],
+            Capability::WorkerRoutes => &["Workers Routes Read", "Workers Routes Write"],
+            Capability::Pages => &["Pages Read", "Pages Write"],
+            Capability::Tunnels => &[
+                "Cloudflare Tunnel Read",
+                "Cloudflare Tunnel Write",
+                "Argo Tunnel Read",
+                "Argo Tunnel Write",
+            ],
+            Capability::Certificates => {
+                &["SSL and Certificates Read", "SSL and Certificates Write"]
+            }
+            Capability::Analytics => &["Analytics Read"],
+            Capability::AuditLogs => &["Access: Audit Logs Read"],
+        }
+    }
+}
+
+/// Zones a token's permissions apply to
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ZoneScope {
+    pub all: bool,
+    /// Ids of the accounts all of whose zones are included
+    pub accounts: Vec<String>,
+    /// Ids of single zones
+    pub zones: Vec<String>,
+}
+
+impl ZoneScope {
+    fn add(&mut self, resource: &str, value: &Value) {
+        if resource == "com.cloudflare.api.account.zone.*"
+            || resource == "com.cloudflare.api.account.*"
+        {
+            self.all = true;
+        } else if let Some(zone_id) = resource.strip_prefix(ZONE_RESOURCE_PREFIX) {
+            push_unique(&mut self.zones, zone_id);
+        } else if let Some(account_id) = resource.strip_prefix(ACCOUNT_RESOURCE_PREFIX) {
+            match value {
+                // Resources of the account, e.g. all of its zones or some of them
+                Value::Object(nested) => {
+                    for (resource, value) in nested {
+                        if resource == "com.cloudflare.api.account.zone.*" {
+                            push_unique(&mut self.accounts, account_id);
+                        } else {
+                            self.add(resource, value);
+                        }
+                    }
+                }
+                _ => push_unique(&mut self.accounts, account_id),
+            }
+        }
+    }
+
+    /// E.g. "All zones", or "All zones of Acme, example.com"
+    pub fn summary(&self, zones: &[Zone]) -> String {
+        if self.all {
+            return "All zones".to_string();
+        }
+
+        let mut parts: Vec<String> = self
+            .accounts
+            .iter()
+            .map(|account_id| {
+                let name = zones
+                    .iter()
+                    .find(|zone| &zone.account.id == account_id)
+                    .map_or(account_id.as_str(), |zone| zone.account.name.as_str());
+                format!("All zones of {}", name)
+            })
+            .collect();
+        parts.extend(self.zones.iter().map(|zone_id| {
+            zones
+                .iter()
+                .find(|zone| &zone.id == zone_id)
+                .map_or_else(|| zone_id.clone(), |zone| zone.name.clone())
+        }));
+        if parts.is_empty() {
+            "No zones".to_string()
+        } else {
+            parts.join(", ")
+        }
+    }
+}
+
+fn push_unique(ids: &mut Vec<String>, id: &str) {
+    if !ids.iter().any(|existing| existing == id) {
+        ids.push(id.to_string());
+    }
+}
+
+/// Permissions and zones of a token
+#[derive(Debug, Clone)]
+pub struct TokenScopes {
+    pub name: String,
+    /// Names of the permission groups the token is granted, sorted
+    pub permissions: Vec<String>,
+    pub zones: ZoneScope,
+}
+
+impl TokenScopes {
+    pub fn from_details(details: &TokenDetails) -> Self {
+        let mut permissions: BTreeSet<String> = BTreeSet::new();
+        let mut denied: HashSet<String> = HashSet::new();
+        let mut zones = ZoneScope::default();
+        for policy in &details.policies {
+            let groups = policy
+                .permission_groups
+                .iter()
+                .map(|group| group.name.clone());
+            // A deny policy is taken to deny its permissions everywhere, hiding rather than
+            // offering what may fail
+            if policy.effect == "deny" {
+                denied.extend(groups);
+                continue;
+            }
+            permissions.extend(groups);
+            for (resource, value) in &policy.resources {
+                zones.add(resource, value);
+            }
+        }
+
+        Self {
+            name: details.name.clone(),
+            permissions: permissions
+                .into_iter()
+                .filter(|permission| !denied.contains(permission))
+                .collect(),
+            zones,
+        }
+    }
+
+    pub fn allows(&self, capability: Capability) -> bool {
+        capability.permission_groups().iter().any(|group| {
+            self.permissions
+                .iter()
+                .any(|permission| normalize(permission) == normalize(group))
+        })
+    }
+
+    /// Whether the token can only read, so changes would all be refused by the API
+    pub fn is_read_only(&self) -> bool {
+        !self.permissions.is_empty()
+            && self
+                .permissions
+                .iter()
+                .all(|permission| normalize(permission).ends_with(" read"))
+    }
+}
+
+/// Permission group names as the API and the dashboard write them, e.g. `DNS Write` and `DNS Edit`
+fn normalize(name: &str) -> String {
+    let name = name.trim().to_lowercase();
+    match name.strip_suffix(" edit") {
+        Some(stem) => format!("{} write", stem),
+        None => name,
+    }
+}
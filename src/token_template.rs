@@ -0,0 +1,73 @@
+//! Link to Cloudflare's token creation page with the permissions the app needs filled in
+//!
+//! The page reads a template from its query: the permission groups by key and access type,
+//! a token name, and the accounts and zones it covers, here all of them. The zones can still be
+//! narrowed on the page before the token is created.
+
+use reqwest::Url;
+
+use crate::token_scopes::Capability;
+
+const CREATE_TOKEN_URL: &str = "https://dash.cloudflare.com/profile/api-tokens";
+
+/// Name the token is created with
+const TOKEN_NAME: &str = "Cloudflare Admin";
+
+/// Permissions every token needs: listing zones, and reading the token's own permissions so the
+/// app can hide what it can't use
+const BASE_PERMISSIONS: &[(&str, &str)] = &[("zone", "read"), ("api_tokens", "read")];
+
+/// Capabilities offered when creating a token, in the order of [`Capability::ALL`]; reading DNS
+/// records comes with editing them
+pub fn offered_capabilities() -> impl Iterator<Item = Capability> {
+    Capability::ALL
+        .into_iter()
+        .filter(|capability| *capability != Capability::ReadDns)
+}
+
+/// Template keys and access types of the permission groups a capability needs
+fn permission_keys(capability: Capability) -> &'static [(&'static str, &'static str)] {
+    match capability {
+        Capability::ReadDns => &[("dns", "read")],
+        Capability::EditDns => &[("dns", "edit")],
+        Capability::EditZones => &[("zone", "edit")],
+        Capability::ZoneSettings => &[("zone_settings", "edit")],
+        Capability::PurgeCache => &[("cache_purge", "purge")],
+        Capability::PageRules => &[("page_rules", "edit")],
+        Capability::Waf => &[("firewall_services", "edit")],
+        Capability::WorkerRoutes => &[("workers_routes", "edit")],
+        Capability::Pages => &[("page", "edit")],
+        Capability::Tunnels => &[("argotunnel", "edit")],
+        Capability::Certificates => &[("ssl_and_certificates", "edit")],
+        Capability::Analytics => &[("analytics", "read")],
+        Capability::AuditLogs => &[("access_audit_log", "read")],
+    }
+}
+
+/// Token creation page filled in with the permissions of `capabilities`
+pub fn create_token_url(capabilities: &[Capability]) -> String {
+    let keys: Vec<serde_json::Value> = BASE_PERMISSIONS
+        .iter()
+        .chain(
+            capabilities
+                .iter()
+                .flat_map(|capability| permission_keys(*capability)),
+        )
+        .map(|(key, access)| serde_json::json!({ "key": key, "type": access }))
+        .collect();
+
+    Url::parse_with_params(
+        CREATE_TOKEN_URL,
+        [
+            (
+                "permissionGroupKeys",
+                serde_json::Value::from(keys).to_string(),
+            ),
+            ("name", TOKEN_NAME.to_string()),
+            ("accountId", "*".to_string()),
+            ("zoneId", "all".to_string()),
+        ],
+    )
+    .map(String::from)
+    .unwrap_or_else(|_| CREATE_TOKEN_URL.to_string())
+}
@@ -0,0 +1,113 @@
+//! Lowering TTLs ahead of a planned change, such as moving a site to a new origin
+//!
+//! Resolvers keep answers for the TTL they were served with, so lowering a record's TTL only
+//! takes full effect once its old TTL has passed. After that, the change reaches everyone within
+//! the lowered TTL, and the original TTLs can be restored.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::cloudflare::{DnsRecord, UpdateDnsRecord};
+
+/// TTLs records can be lowered to, in seconds
+pub const LOWERED_TTLS: &[u32] = &[60, 120, 300];
+
+/// How long resolvers keep answers of records with automatic TTL, in seconds
+const AUTOMATIC_TTL: u32 = 300;
+
+/// Records of a zone whose TTLs were lowered
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoweredTtls {
+    /// TTLs the records had before they were lowered, by record id
+    pub original: HashMap<String, u32>,
+    /// When the answers resolvers cached with the original TTLs have expired
+    pub expires_at: DateTime<Utc>,
+    /// Whether the reminder that the original TTLs expired was shown
+    #[serde(default)]
+    pub reminded: bool,
+}
+
+impl LoweredTtls {
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            original: HashMap::new(),
+            expires_at: now,
+            reminded: false,
+        }
+    }
+
+    /// Remember the TTLs of records lowered at `now`; records lowered before keep the TTL they
+    /// had first
+    pub fn add(&mut self, records: &[DnsRecord], now: DateTime<Utc>) {
+        for record in records {
+            self.original.entry(record.id.clone()).or_insert(record.ttl);
+            let expires_at = now + Duration::seconds(cached_for(record.ttl).into());
+            if expires_at > self.expires_at {
+                self.expires_at = expires_at;
+                self.reminded = false;
+            }
+        }
+    }
+
+    pub fn expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at <= now
+    }
+}
+
+/// Records that lowering to `ttl` changes: unlocked, unproxied ones with a longer TTL
+///
+/// Proxied records are always answered with Cloudflare's own addresses and automatic TTL.
+pub fn records_to_lower(records: &[DnsRecord], ttl: u32) -> Vec<DnsRecord> {
+    records
+        .iter()
+        .filter(|record| !record.locked && !record.proxied && cached_for(record.ttl) > ttl)
+        .cloned()
+        .collect()
+}
+
+/// Answer a record gave before it was changed, which resolvers may still serve
+#[derive(Debug, Clone)]
+pub struct OldAnswer {
+    pub record_name: String,
+    /// When the last resolver that cached it has dropped it
+    pub expires_at: DateTime<Utc>,
+}
+
+/// The old answer of a record `previous` was changed to `updated` at `now`, if the change gave
+/// it a new answer
+///
+/// Changes behind Cloudflare's proxy, e.g. a new origin, take effect right away, as resolvers
+/// only ever see Cloudflare's addresses. Fields `updated` leaves out are unchanged.
+pub fn old_answer(
+    previous: &UpdateDnsRecord,
+    updated: &UpdateDnsRecord,
+    now: DateTime<Utc>,
+) -> Option<OldAnswer> {
+    fn changed<T: PartialEq>(previous: &Option<T>, updated: &Option<T>) -> bool {
+        updated.is_some() && updated != previous
+    }
+
+    let proxied_before = previous.proxied == Some(true);
+    let proxied_after = updated.proxied.or(previous.proxied) == Some(true);
+    let answer_changed = proxied_before != proxied_after
+        || (!proxied_after
+            && (changed(&previous.content, &updated.content)
+                || changed(&previous.data, &updated.data)
+                || changed(&previous.record_type, &updated.record_type)))
+        || changed(&previous.name, &updated.name);
+    if !answer_changed {
+        return None;
+    }
+
+    Some(OldAnswer {
+        record_name: previous.name.clone().unwrap_or_default(),
+        expires_at: now + Duration::seconds(cached_for(previous.ttl.unwrap_or(1)).into()),
+    })
+}
+
+/// Seconds resolvers keep an answer with `ttl`
+fn cached_for(ttl: u32) -> u32 {
+    if ttl == 1 { AUTOMATIC_TTL } else { ttl }
+}
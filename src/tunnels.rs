@@ -0,0 +1,132 @@
+//! Cloudflare Tunnels of an account and how healthy their connectors are
+//!
+//! A tunnel is run by one or more connectors, `cloudflared` instances, each holding connections
+//! to several Cloudflare data centers. Hostnames reach a tunnel either through ingress rules
+//! configured in the dashboard, or through CNAME records pointing at `<tunnel id>.cfargotunnel.com`
+//! for tunnels configured with a local file.
+
+use crate::cloudflare::{DnsRecord, DnsRecordType, Tunnel, TunnelIngress};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TunnelHealth {
+    Healthy,
+    /// Some connections are down
+    Degraded,
+    /// Was connected before but has no connections now
+    Down,
+    /// Never connected
+    Inactive,
+}
+
+impl TunnelHealth {
+    pub fn of(tunnel: &Tunnel) -> Self {
+        match tunnel.status.as_str() {
+            "healthy" => TunnelHealth::Healthy,
+            "degraded" => TunnelHealth::Degraded,
+            "down" => TunnelHealth::Down,
+            _ => TunnelHealth::Inactive,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TunnelHealth::Healthy => "Healthy",
+            TunnelHealth::Degraded => "Degraded",
+            TunnelHealth::Down => "Down",
+            TunnelHealth::Inactive => "Inactive",
+        }
+    }
+}
+
+/// A `cloudflared` instance running a tunnel
+#[derive(Debug, Clone, PartialEq)]
+pub struct Connector {
+    pub id: String,
+    pub version: String,
+    pub origin_ip: String,
+    /// Data centers the connector is connected to
+    pub colos: Vec<String>,
+    /// Connections that dropped and are reconnecting
+    pub reconnecting: usize,
+}
+
+/// Connectors of a tunnel, from its connections
+pub fn connectors(tunnel: &Tunnel) -> Vec<Connector> {
+    let mut connectors: Vec<Connector> = Vec::new();
+    for connection in &tunnel.connections {
+        let index = match connectors
+            .iter()
+            .position(|connector| connector.id == connection.client_id)
+        {
+            Some(index) => index,
+            None => {
+                connectors.push(Connector {
+                    id: connection.client_id.clone(),
+                    version: connection.client_version.clone(),
+                    origin_ip: connection.origin_ip.clone(),
+                    colos: Vec::new(),
+                    reconnecting: 0,
+                });
+                connectors.len() - 1
+            }
+        };
+        let connector = &mut connectors[index];
+        connector.colos.push(connection.colo_name.clone());
+        if connection.is_pending_reconnect {
+            connector.reconnecting += 1;
+        }
+    }
+    connectors
+}
+
+/// Whether the connectors of a tunnel run different versions of `cloudflared`, which usually
+/// means some weren't upgraded
+pub fn mixed_versions(connectors: &[Connector]) -> bool {
+    connectors
+        .iter()
+        .any(|connector| connector.version != connectors[0].version)
+}
+
+/// A hostname routed to a tunnel
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoutedHostname {
+    pub hostname: String,
+    /// Service behind the tunnel, known for hostnames of ingress rules
+    pub service: Option<String>,
+}
+
+/// Hostnames routed to a tunnel by its ingress rules and by CNAME records in `records`
+pub fn routed_hostnames(
+    tunnel_id: &str,
+    ingress: &[TunnelIngress],
+    records: &[DnsRecord],
+) -> Vec<RoutedHostname> {
+    let mut hostnames: Vec<RoutedHostname> = ingress
+        .iter()
+        .filter_map(|rule| {
+            Some(RoutedHostname {
+                hostname: rule.hostname.clone()?,
+                service: Some(rule.service.clone()),
+            })
+        })
+        .collect();
+
+    let target = format!("{}.cfargotunnel.com", tunnel_id);
+    for record in records {
+        if record.record_type == DnsRecordType::CNAME
+            && record
+                .content
+                .trim_end_matches('.')
+                .eq_ignore_ascii_case(&target)
+            && !hostnames
+                .iter()
+                .any(|routed| routed.hostname.eq_ignore_ascii_case(&record.name))
+        {
+            hostnames.push(RoutedHostname {
+                hostname: record.name.clone(),
+                service: None,
+            });
+        }
+    }
+    hostnames
+}
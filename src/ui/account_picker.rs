@@ -0,0 +1,61 @@
+use gpui::prelude::*;
+use gpui::{Context, ElementId, IntoElement};
+use gpui_component::{
+    Sizable,
+    button::{Button, ButtonVariants},
+    menu::{DropdownMenu, PopupMenuItem},
+};
+
+use crate::App;
+
+/// Dropdown scoping the zone picker and portfolio page to one account, for tokens whose zones
+/// span several
+pub fn render_account_picker(
+    id: impl Into<ElementId>,
+    app: &App,
+    cx: &mut Context<App>,
+) -> impl IntoElement {
+    let entity = cx.entity();
+    let accounts = app.zone_accounts(cx);
+    let account_filter = app.account_filter(cx);
+    let label = account_filter
+        .as_ref()
+        .and_then(|id| accounts.iter().find(|account| &account.id == id))
+        .map(|account| account.name.clone())
+        .unwrap_or_else(|| "All accounts".to_string());
+
+    Button::new(id)
+        .ghost()
+        .small()
+        .label(label)
+        .tooltip("Show the zones of one account")
+        .dropdown_menu(move |menu, _, _| {
+            let all_entity = entity.clone();
+            let mut menu = menu
+                .item(
+                    PopupMenuItem::new("All accounts")
+                        .checked(account_filter.is_none())
+                        .on_click(move |_, window, cx| {
+                            all_entity.update(cx, |this, cx| {
+                                this.set_zone_account_filter(None, window, cx)
+                            });
+                        }),
+                )
+                .separator();
+            for account in &accounts {
+                let entity = entity.clone();
+                let id = account.id.clone();
+                menu = menu.item(
+                    PopupMenuItem::new(account.name.clone())
+                        .checked(account_filter.as_ref() == Some(&account.id))
+                        .on_click(move |_, window, cx| {
+                            let id = id.clone();
+                            entity.update(cx, |this, cx| {
+                                this.set_zone_account_filter(Some(id), window, cx)
+                            });
+                        }),
+                );
+            }
+            menu
+        })
+}
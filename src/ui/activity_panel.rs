@@ -0,0 +1,168 @@
+use gpui::prelude::*;
+use gpui::{Context, FontWeight, IntoElement, Window, div, px};
+use gpui_component::{
+    ActiveTheme, Selectable, Sizable,
+    button::{Button, ButtonVariants},
+    checkbox::Checkbox,
+    h_flex,
+    input::Input,
+    scroll::ScrollableElement,
+    v_flex,
+};
+
+use super::render_timestamp;
+use crate::App;
+use crate::activity::{self, ActivitySource};
+use crate::token_scopes::Capability;
+
+/// Activity feed of the selected zone, shown to the right of the record editor
+pub fn render_activity_panel(
+    app: &mut App,
+    _window: &mut Window,
+    cx: &mut Context<App>,
+) -> impl IntoElement {
+    let query = app.activity_filter_input.read(cx).value().to_string();
+    let source_filter = app.activity_source_filter;
+    let entries = activity::filter(
+        &app.activity,
+        source_filter,
+        app.activity_records_only,
+        &query,
+    );
+    let time_zone_display = app.time_zone_display;
+    let muted_foreground = cx.theme().muted_foreground;
+    let border_color = cx.theme().border;
+
+    let source_button = |id: &'static str, label: &'static str, source: Option<ActivitySource>| {
+        Button::new(id)
+            .ghost()
+            .xsmall()
+            .label(label)
+            .selected(source_filter == source)
+            .on_click(cx.listener(move |this, _, _, cx| {
+                this.activity_source_filter = source;
+                cx.notify();
+            }))
+    };
+
+    v_flex()
+        .w(px(320.))
+        .h_full()
+        .border_l_1()
+        .border_color(border_color)
+        .p_4()
+        .gap_3()
+        .child(
+            h_flex()
+                .items_center()
+                .justify_between()
+                .child(div().font_weight(FontWeight::SEMIBOLD).child("Activity"))
+                .child(
+                    h_flex()
+                        .gap_1()
+                        .child(
+                            Button::new("refresh-activity")
+                                .ghost()
+                                .small()
+                                .icon(gpui_component::IconName::Redo)
+                                .tooltip("Refresh")
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.load_activity(window, cx);
+                                })),
+                        )
+                        .child(
+                            Button::new("close-activity")
+                                .ghost()
+                                .small()
+                                .icon(gpui_component::IconName::PanelRightClose)
+                                .tooltip("Hide activity")
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.toggle_activity_panel(window, cx);
+                                })),
+                        ),
+                ),
+        )
+        .child(
+            h_flex()
+                .gap_1()
+                .child(source_button("activity-all", "All", None))
+                .when(app.can(Capability::AuditLogs), |this| {
+                    this.child(source_button(
+                        "activity-audit-log",
+                        ActivitySource::AuditLog.label(),
+                        Some(ActivitySource::AuditLog),
+                    ))
+                })
+                .child(source_button(
+                    "activity-journal",
+                    ActivitySource::Journal.label(),
+                    Some(ActivitySource::Journal),
+                )),
+        )
+        .child(
+            Checkbox::new("activity-records-only")
+                .small()
+                .label("DNS record changes only")
+                .checked(app.activity_records_only)
+                .on_click(cx.listener(|this, records_only, _, cx| {
+                    this.activity_records_only = *records_only;
+                    cx.notify();
+                })),
+        )
+        .child(
+            Input::new(&app.activity_filter_input)
+                .small()
+                .cleanable(true),
+        )
+        .when_some(app.activity_error.clone(), |this, error| {
+            this.child(
+                div()
+                    .px_3()
+                    .py_2()
+                    .rounded_md()
+                    .bg(cx.theme().warning.opacity(0.1))
+                    .text_xs()
+                    .text_color(cx.theme().warning)
+                    .child(error),
+            )
+        })
+        .child(
+            v_flex()
+                .flex_1()
+                .overflow_y_scrollbar()
+                .when(entries.is_empty(), |this| {
+                    this.child(
+                        div()
+                            .text_sm()
+                            .text_color(muted_foreground)
+                            .child("No activity"),
+                    )
+                })
+                .children(entries.into_iter().enumerate().map(|(index, entry)| {
+                    v_flex()
+                        .py_2()
+                        .gap_1()
+                        .border_b_1()
+                        .border_color(border_color)
+                        .child(div().text_sm().child(entry.description.clone()))
+                        .child(
+                            h_flex()
+                                .gap_2()
+                                .text_xs()
+                                .text_color(muted_foreground)
+                                .child(div().flex_1().truncate().child(format!(
+                                    "{} · {}",
+                                    entry.actor,
+                                    entry.source.label()
+                                )))
+                                .child(render_timestamp(
+                                    ("activity-time", index),
+                                    "",
+                                    entry.time,
+                                    time_zone_display,
+                                    cx,
+                                )),
+                        )
+                })),
+        )
+}
@@ -0,0 +1,63 @@
+use gpui::prelude::*;
+use gpui::{App as GpuiApp, Entity, IntoElement, Window, div, px};
+use gpui_component::{
+    ActiveTheme, Disableable, WindowExt,
+    button::{Button, ButtonVariants},
+    input::Input,
+    v_flex,
+};
+
+use crate::App;
+
+/// Ask for the name of a new token profile, e.g. work or a client's; adding it switches to it so
+/// its token can be entered
+pub fn open_add_profile_dialog(app: Entity<App>, window: &mut Window, cx: &mut GpuiApp) {
+    window.open_dialog(cx, move |dialog, _, cx| {
+        let state = app.read(cx);
+        let profile_name_input = state.profile_name_input.clone();
+        let has_name = !profile_name_input.read(cx).value().trim().is_empty();
+        let error = state.error.clone();
+        let app = app.clone();
+
+        dialog
+            .title("Add Profile")
+            .w(px(420.))
+            .child(
+                v_flex()
+                    .gap_3()
+                    .child(Input::new(&profile_name_input))
+                    .child(
+                        div()
+                            .text_sm()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(
+                                "Each profile keeps its own API token in the keychain. Switch \
+                                 between them from the header.",
+                            ),
+                    )
+                    .when_some(error, |this, error| {
+                        this.child(div().text_sm().text_color(cx.theme().danger).child(error))
+                    }),
+            )
+            .footer(move |_, _, _, _| {
+                vec![
+                    Button::new("add-profile-cancel")
+                        .label("Cancel")
+                        .on_click(|_, window, cx| window.close_dialog(cx)),
+                    Button::new("add-profile")
+                        .primary()
+                        .label("Add Profile")
+                        .disabled(!has_name)
+                        .on_click({
+                            let app = app.clone();
+                            move |_, window, cx| {
+                                let added = app.update(cx, |this, cx| this.add_profile(window, cx));
+                                if added {
+                                    window.close_dialog(cx);
+                                }
+                            }
+                        }),
+                ]
+            })
+    });
+}
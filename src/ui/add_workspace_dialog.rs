@@ -0,0 +1,64 @@
+use gpui::prelude::*;
+use gpui::{App as GpuiApp, Entity, IntoElement, Window, div, px};
+use gpui_component::{
+    ActiveTheme, Disableable, WindowExt,
+    button::{Button, ButtonVariants},
+    input::Input,
+    v_flex,
+};
+
+use crate::App;
+
+/// Ask for the name of a new workspace, e.g. a client's production zones; adding it switches to
+/// it so zones can be added
+pub fn open_add_workspace_dialog(app: Entity<App>, window: &mut Window, cx: &mut GpuiApp) {
+    window.open_dialog(cx, move |dialog, _, cx| {
+        let state = app.read(cx);
+        let workspace_name_input = state.workspace_name_input.clone();
+        let has_name = !workspace_name_input.read(cx).value().trim().is_empty();
+        let error = state.error.clone();
+        let app = app.clone();
+
+        dialog
+            .title("New Workspace")
+            .w(px(420.))
+            .child(
+                v_flex()
+                    .gap_3()
+                    .child(Input::new(&workspace_name_input))
+                    .child(
+                        div()
+                            .text_sm()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(
+                                "A workspace shows only the zones added to it, from any profile. \
+                                 Add zones from their right-click menu.",
+                            ),
+                    )
+                    .when_some(error, |this, error| {
+                        this.child(div().text_sm().text_color(cx.theme().danger).child(error))
+                    }),
+            )
+            .footer(move |_, _, _, _| {
+                vec![
+                    Button::new("add-workspace-cancel")
+                        .label("Cancel")
+                        .on_click(|_, window, cx| window.close_dialog(cx)),
+                    Button::new("add-workspace")
+                        .primary()
+                        .label("Create Workspace")
+                        .disabled(!has_name)
+                        .on_click({
+                            let app = app.clone();
+                            move |_, window, cx| {
+                                let added =
+                                    app.update(cx, |this, cx| this.add_workspace(window, cx));
+                                if added {
+                                    window.close_dialog(cx);
+                                }
+                            }
+                        }),
+                ]
+            })
+    });
+}
@@ -0,0 +1,201 @@
+use gpui::prelude::*;
+use gpui::{App as GpuiApp, Entity, FontWeight, IntoElement, Window, div, px};
+use gpui_component::{
+    ActiveTheme, Disableable, WindowExt,
+    button::{Button, ButtonVariants},
+    h_flex,
+    input::Input,
+    menu::{DropdownMenu, PopupMenuItem},
+    v_flex,
+};
+
+use crate::App;
+use crate::cloudflare::Zone;
+
+/// Ask for a domain and account to add as a zone; once added, show the nameservers to set at
+/// the registrar and whether the zone is active yet
+pub fn open_add_zone_dialog(app: Entity<App>, window: &mut Window, cx: &mut GpuiApp) {
+    window.open_dialog(cx, move |dialog, _, cx| {
+        let state = app.read(cx);
+        let added_zone = state.added_zone.clone();
+        let is_loading = state.loading;
+        let app = app.clone();
+
+        match added_zone {
+            Some(zone) => dialog
+                .title(format!("{} added", zone.name))
+                .w(px(480.))
+                .child(render_added_zone(&zone, cx))
+                .footer(move |_, _, _, _| {
+                    vec![
+                        Button::new("check-zone-activation")
+                            .label("Check Nameservers Now")
+                            .disabled(is_loading || zone.status == "active")
+                            .on_click({
+                                let app = app.clone();
+                                move |_, window, cx| {
+                                    app.update(cx, |this, cx| {
+                                        this.check_zone_activation(window, cx)
+                                    });
+                                }
+                            }),
+                        Button::new("open-added-zone")
+                            .primary()
+                            .label("Open Zone")
+                            .on_click({
+                                let app = app.clone();
+                                move |_, window, cx| {
+                                    window.close_dialog(cx);
+                                    app.update(cx, |this, cx| this.open_added_zone(window, cx));
+                                }
+                            }),
+                    ]
+                }),
+            None => {
+                let accounts = state.accounts.clone();
+                let account_id = state.new_zone_account_id.clone();
+                let account_name = accounts
+                    .iter()
+                    .find(|account| Some(&account.id) == account_id.as_ref())
+                    .map(|account| account.name.clone());
+                let zone_name_input = state.zone_name_input.clone();
+                let has_name = !zone_name_input.read(cx).value().trim().is_empty();
+                let error = state.error.clone();
+
+                dialog
+                    .title("Add Zone")
+                    .w(px(480.))
+                    .child(
+                        v_flex()
+                            .gap_3()
+                            .child(Input::new(&zone_name_input))
+                            .child(
+                                h_flex()
+                                    .gap_3()
+                                    .items_center()
+                                    .child(div().text_sm().child("Account"))
+                                    .child(
+                                        Button::new("new-zone-account")
+                                            .outline()
+                                            .w(px(280.))
+                                            .label(account_name.unwrap_or_else(|| {
+                                                if is_loading {
+                                                    "Loading accounts…".to_string()
+                                                } else {
+                                                    "No accounts".to_string()
+                                                }
+                                            }))
+                                            .dropdown_menu({
+                                                let app = app.clone();
+                                                move |menu, _, _| {
+                                                    let mut menu = menu;
+                                                    for account in &accounts {
+                                                        let app = app.clone();
+                                                        let id = account.id.clone();
+                                                        menu = menu.item(
+                                                            PopupMenuItem::new(
+                                                                account.name.clone(),
+                                                            )
+                                                            .checked(
+                                                                Some(&account.id)
+                                                                    == account_id.as_ref(),
+                                                            )
+                                                            .on_click(move |_, _, cx| {
+                                                                let id = id.clone();
+                                                                app.update(cx, |this, cx| {
+                                                                    this.set_new_zone_account(
+                                                                        id, cx,
+                                                                    )
+                                                                });
+                                                            }),
+                                                        );
+                                                    }
+                                                    menu
+                                                }
+                                            }),
+                                    ),
+                            )
+                            .child(
+                                div()
+                                    .text_sm()
+                                    .text_color(cx.theme().muted_foreground)
+                                    .child(
+                                        "Cloudflare scans the domain's existing DNS records and \
+                                         assigns two nameservers to set at the registrar.",
+                                    ),
+                            )
+                            .when_some(error, |this, error| {
+                                this.child(
+                                    div().text_sm().text_color(cx.theme().danger).child(error),
+                                )
+                            }),
+                    )
+                    .footer(move |_, _, _, _| {
+                        vec![
+                            Button::new("add-zone-cancel")
+                                .label("Cancel")
+                                .on_click(|_, window, cx| window.close_dialog(cx)),
+                            Button::new("add-zone")
+                                .primary()
+                                .label("Add Zone")
+                                .disabled(is_loading || !has_name || account_id.is_none())
+                                .on_click({
+                                    let app = app.clone();
+                                    move |_, window, cx| {
+                                        app.update(cx, |this, cx| this.create_zone(window, cx));
+                                    }
+                                }),
+                        ]
+                    })
+            }
+        }
+    });
+}
+
+fn render_added_zone(zone: &Zone, cx: &GpuiApp) -> impl IntoElement {
+    let muted_foreground = cx.theme().muted_foreground;
+    let active = zone.status == "active";
+
+    v_flex()
+        .gap_3()
+        .child(
+            div()
+                .text_sm()
+                .text_color(if active {
+                    cx.theme().success
+                } else {
+                    cx.theme().warning
+                })
+                .child(if active {
+                    "The zone is active: Cloudflare answers its DNS queries.".to_string()
+                } else {
+                    format!(
+                        "The zone is {} until the registrar points it to Cloudflare. It is \
+                         checked every minute.",
+                        zone.status
+                    )
+                }),
+        )
+        .child(
+            v_flex()
+                .gap_1()
+                .child(
+                    div()
+                        .text_sm()
+                        .text_color(muted_foreground)
+                        .child("Replace the nameservers at the registrar with:"),
+                )
+                .children(zone.name_servers.iter().map(|name_server| {
+                    div()
+                        .font_family("monospace")
+                        .font_weight(FontWeight::MEDIUM)
+                        .child(name_server.clone())
+                })),
+        )
+        .child(
+            div()
+                .text_xs()
+                .text_color(muted_foreground)
+                .child("Nameserver changes can take up to 24 hours to reach everyone."),
+        )
+}
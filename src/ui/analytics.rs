@@ -0,0 +1,290 @@
+use gpui::prelude::*;
+use gpui::{Context, FontWeight, Hsla, IntoElement, Window, div, px};
+use gpui_component::{
+    ActiveTheme, Sizable,
+    button::{Button, ButtonVariants},
+    chart::AreaChart,
+    h_flex,
+    scroll::ScrollableElement,
+    v_flex,
+};
+
+use super::render_status_bar;
+use crate::analytics::{
+    RecordUsage, TimeRange, TrafficTotals, cache_ratio, format_bytes, format_count, record_usage,
+    timeslot_label,
+};
+use crate::cloudflare::TrafficPoint;
+use crate::{App, Page};
+
+/// Labels shown on the time axis of a chart
+const AXIS_LABELS: usize = 4;
+
+/// Requests, bandwidth, threats and cache ratio of the selected zone over a chosen range
+pub fn render_analytics(
+    app: &mut App,
+    _window: &mut Window,
+    cx: &mut Context<App>,
+) -> impl IntoElement {
+    let zone_name = app.selected_zone(cx).map(|zone| zone.name);
+    let error = app.error.clone();
+    let muted_foreground = cx.theme().muted_foreground;
+    let border_color = cx.theme().border;
+    let range = app.analytics_range;
+    let totals = TrafficTotals::new(&app.traffic);
+    let labels: Vec<String> = app
+        .traffic
+        .iter()
+        .map(|point| timeslot_label(&point.timeslot))
+        .collect();
+    let series = |value: fn(&TrafficPoint) -> f64| -> Vec<(String, f64)> {
+        labels
+            .iter()
+            .cloned()
+            .zip(app.traffic.iter().map(value))
+            .collect()
+    };
+
+    let usage = record_usage(&app.dns_records(cx), &app.dns_query_counts);
+
+    let charts = [
+        (
+            "Requests",
+            format_count(totals.requests),
+            series(|point| point.requests as f64),
+            cx.theme().chart_1,
+        ),
+        (
+            "Bandwidth",
+            format_bytes(totals.bytes),
+            series(|point| point.bytes as f64),
+            cx.theme().chart_2,
+        ),
+        (
+            "Threats",
+            format_count(totals.threats),
+            series(|point| point.threats as f64),
+            cx.theme().chart_3,
+        ),
+        (
+            "Cache ratio",
+            format!("{:.1}%", totals.cache_ratio()),
+            series(cache_ratio),
+            cx.theme().chart_4,
+        ),
+    ];
+
+    v_flex()
+        .size_full()
+        .child(
+            // Header
+            h_flex()
+                .w_full()
+                .px_4()
+                .py_3()
+                .border_b_1()
+                .border_color(border_color)
+                .items_center()
+                .gap_3()
+                .child(
+                    Button::new("back")
+                        .ghost()
+                        .icon(gpui_component::IconName::ArrowLeft)
+                        .on_click(cx.listener(|this, _, _, cx| {
+                            this.page = Page::Dashboard;
+                            this.error = None;
+                            cx.notify();
+                        })),
+                )
+                .child(
+                    div()
+                        .text_lg()
+                        .font_weight(FontWeight::SEMIBOLD)
+                        .child("Analytics"),
+                )
+                .when_some(zone_name, |this, zone_name| {
+                    this.child(
+                        div()
+                            .text_sm()
+                            .text_color(muted_foreground)
+                            .child(zone_name),
+                    )
+                })
+                .child(div().flex_1())
+                .child(
+                    h_flex()
+                        .gap_1()
+                        .children(TimeRange::ALL.iter().enumerate().map(|(index, &option)| {
+                            Button::new(("analytics-range", index))
+                                .small()
+                                .map(|this| {
+                                    if option == range {
+                                        this.primary()
+                                    } else {
+                                        this.ghost()
+                                    }
+                                })
+                                .label(option.label())
+                                .on_click(cx.listener(move |this, _, window, cx| {
+                                    this.set_analytics_range(option, window, cx);
+                                }))
+                        })),
+                )
+                .child(
+                    Button::new("refresh-analytics")
+                        .ghost()
+                        .icon(gpui_component::IconName::Redo)
+                        .tooltip("Refresh")
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            this.load_analytics(window, cx);
+                        })),
+                ),
+        )
+        .child(
+            v_flex()
+                .flex_1()
+                .p_6()
+                .gap_4()
+                .overflow_y_scrollbar()
+                .when_some(error, |this, error| {
+                    this.child(div().text_sm().text_color(cx.theme().danger).child(error))
+                })
+                .when(app.traffic.is_empty(), |this| {
+                    this.child(
+                        div()
+                            .text_sm()
+                            .text_color(muted_foreground)
+                            .child(if app.loading {
+                                "Loading analytics…"
+                            } else {
+                                "No traffic in this range."
+                            }),
+                    )
+                })
+                .when(!app.traffic.is_empty(), |this| {
+                    this.child(
+                        h_flex()
+                            .gap_4()
+                            .flex_wrap()
+                            .children(charts.into_iter().map(|(title, total, data, color)| {
+                                render_chart(title, total, data, color, cx)
+                            })),
+                    )
+                })
+                .when(!app.dns_query_counts.is_empty(), |this| {
+                    this.child(render_record_usage(usage, cx))
+                }),
+        )
+        .child(render_status_bar(app, cx))
+}
+
+/// DNS queries per record name and type, least queried first
+fn render_record_usage(usage: Vec<RecordUsage>, cx: &Context<App>) -> impl IntoElement {
+    let unused_count = usage.iter().filter(|usage| usage.queries == 0).count();
+    let muted_foreground = cx.theme().muted_foreground;
+
+    v_flex()
+        .gap_2()
+        .child(
+            div()
+                .font_weight(FontWeight::SEMIBOLD)
+                .child("DNS queries by record"),
+        )
+        .child(
+            div()
+                .text_sm()
+                .text_color(muted_foreground)
+                .child(if unused_count == 0 {
+                    "Every record was queried in this range.".to_string()
+                } else {
+                    format!(
+                        "{} names and types weren't queried in this range and may be unused.",
+                        unused_count
+                    )
+                }),
+        )
+        .child(
+            v_flex()
+                .rounded_md()
+                .border_1()
+                .border_color(cx.theme().border)
+                .children(usage.into_iter().enumerate().map(|(index, usage)| {
+                    let unused = usage.queries == 0;
+                    h_flex()
+                        .gap_3()
+                        .px_3()
+                        .py_1()
+                        .text_sm()
+                        .when(index > 0, |this| {
+                            this.border_t_1().border_color(cx.theme().border)
+                        })
+                        .child(div().w(px(64.)).child(usage.record_type.to_string()))
+                        .child(div().flex_1().truncate().child(usage.name))
+                        .when(usage.records > 1, |this| {
+                            this.child(
+                                div()
+                                    .text_xs()
+                                    .text_color(muted_foreground)
+                                    .child(format!("{} records", usage.records)),
+                            )
+                        })
+                        .child(
+                            div()
+                                .w(px(96.))
+                                .text_right()
+                                .text_color(if unused {
+                                    cx.theme().warning
+                                } else {
+                                    cx.theme().foreground
+                                })
+                                .child(if unused {
+                                    "No queries".to_string()
+                                } else {
+                                    format_count(usage.queries)
+                                }),
+                        )
+                })),
+        )
+}
+
+/// A metric's total over the range with its course as an area chart
+fn render_chart(
+    title: &'static str,
+    total: String,
+    data: Vec<(String, f64)>,
+    color: Hsla,
+    cx: &Context<App>,
+) -> impl IntoElement {
+    let tick_margin = data.len().div_ceil(AXIS_LABELS).max(1);
+
+    v_flex()
+        .w(px(420.))
+        .p_4()
+        .gap_2()
+        .rounded_md()
+        .border_1()
+        .border_color(cx.theme().border)
+        .child(
+            div()
+                .text_sm()
+                .text_color(cx.theme().muted_foreground)
+                .child(title),
+        )
+        .child(
+            div()
+                .text_xl()
+                .font_weight(FontWeight::SEMIBOLD)
+                .child(total),
+        )
+        .child(
+            div().h(px(140.)).child(
+                AreaChart::new(data)
+                    .x(|(label, _): &(String, f64)| label.clone())
+                    .y(|(_, value): &(String, f64)| *value)
+                    .stroke(color)
+                    .fill(color.opacity(0.2))
+                    .linear()
+                    .tick_margin(tick_margin),
+            ),
+        )
+}
@@ -0,0 +1,144 @@
+use gpui::prelude::*;
+use gpui::{App as GpuiApp, Entity, FontWeight, IntoElement, Window, div, px};
+use gpui_component::{
+    ActiveTheme, WindowExt,
+    button::{Button, ButtonVariants},
+    h_flex,
+    progress::Progress,
+    scroll::ScrollableElement,
+    v_flex,
+};
+
+use crate::App;
+use crate::batch::{BatchItem, BatchItemStatus};
+
+/// Open the dialog showing the progress and per-item results of the current bulk operation
+pub fn open_batch_dialog(app: Entity<App>, window: &mut Window, cx: &mut GpuiApp) {
+    window.open_dialog(cx, move |dialog, _, cx| {
+        let Some(batch) = app.read(cx).batch.clone() else {
+            return dialog;
+        };
+
+        let total = batch.items.len();
+        let finished = batch.finished_count();
+        let succeeded = batch.count(|status| *status == BatchItemStatus::Success);
+        let failed = batch.failed_indices().len();
+        let running = batch.running;
+        let percentage = if total == 0 {
+            100.
+        } else {
+            finished as f32 / total as f32 * 100.
+        };
+
+        let summary = format!(
+            "{} of {} done · {} succeeded · {} failed",
+            finished, total, succeeded, failed
+        );
+
+        let app = app.clone();
+        dialog
+            .title(batch.title.clone())
+            .w(px(600.))
+            .child(
+                v_flex()
+                    .gap_3()
+                    .child(
+                        div()
+                            .text_sm()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(summary),
+                    )
+                    .child(Progress::new().value(percentage))
+                    .child(
+                        v_flex()
+                            .max_h(px(360.))
+                            .overflow_y_scrollbar()
+                            .gap_1()
+                            .children(batch.items.iter().map(|item| render_item(item, cx))),
+                    ),
+            )
+            .footer(move |_, _, _, _| {
+                let mut buttons = Vec::new();
+                if running {
+                    buttons.push(Button::new("batch-cancel").label("Cancel").on_click({
+                        let app = app.clone();
+                        move |_, window, cx| {
+                            app.update(cx, |this, cx| this.cancel_batch(window, cx));
+                        }
+                    }));
+                } else {
+                    if failed > 0 {
+                        buttons.push(
+                            Button::new("batch-retry")
+                                .label(format!("Retry Failed ({})", failed))
+                                .on_click({
+                                    let app = app.clone();
+                                    move |_, window, cx| {
+                                        app.update(cx, |this, cx| {
+                                            this.retry_failed_batch_items(window, cx)
+                                        });
+                                    }
+                                }),
+                        );
+                    }
+                    buttons.push(
+                        Button::new("batch-export")
+                            .label("Export Results")
+                            .on_click({
+                                let app = app.clone();
+                                move |_, window, cx| {
+                                    app.update(cx, |this, cx| {
+                                        this.export_batch_results(window, cx)
+                                    });
+                                }
+                            }),
+                    );
+                }
+                buttons.push(
+                    Button::new("batch-close")
+                        .primary()
+                        .label("Close")
+                        .on_click(|_, window, cx| window.close_dialog(cx)),
+                );
+                buttons
+            })
+    });
+}
+
+fn render_item(item: &BatchItem, cx: &GpuiApp) -> impl IntoElement {
+    let status_color = match item.status {
+        BatchItemStatus::Pending | BatchItemStatus::Cancelled => cx.theme().muted_foreground,
+        BatchItemStatus::Running => cx.theme().primary,
+        BatchItemStatus::Success => cx.theme().success,
+        BatchItemStatus::Failed(_) => cx.theme().danger,
+    };
+
+    v_flex()
+        .py_1()
+        .border_b_1()
+        .border_color(cx.theme().border)
+        .child(
+            h_flex()
+                .gap_2()
+                .text_sm()
+                .child(
+                    div()
+                        .w_20()
+                        .flex_shrink_0()
+                        .font_weight(FontWeight::MEDIUM)
+                        .text_color(status_color)
+                        .child(item.status.label().to_string()),
+                )
+                .child(div().flex_1().overflow_hidden().child(item.label.clone())),
+        )
+        .map(|this| match &item.status {
+            BatchItemStatus::Failed(error) => this.child(
+                div()
+                    .pl(px(88.))
+                    .text_xs()
+                    .text_color(cx.theme().danger)
+                    .child(error.clone()),
+            ),
+            _ => this,
+        })
+}
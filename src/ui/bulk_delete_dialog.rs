@@ -0,0 +1,152 @@
+use gpui::prelude::*;
+use gpui::{App as GpuiApp, Entity, FontWeight, IntoElement, Window, div, px};
+use gpui_component::{
+    ActiveTheme, Disableable, WindowExt,
+    button::{Button, ButtonVariants},
+    checkbox::Checkbox,
+    input::Input,
+    scroll::ScrollableElement,
+    v_flex,
+};
+
+use crate::App;
+use crate::cloudflare::DnsRecord;
+use crate::deletion_safety::{Friction, SafetyCheck};
+
+/// Confirm deleting `records`, with the warnings of the safety check and as much friction as
+/// they call for
+///
+/// `kept` explains selected records that won't be deleted.
+pub fn open_bulk_delete_dialog(
+    app: Entity<App>,
+    zone_name: String,
+    records: Vec<DnsRecord>,
+    kept: Vec<String>,
+    check: SafetyCheck,
+    window: &mut Window,
+    cx: &mut GpuiApp,
+) {
+    let warnings = check.warnings();
+    let friction = check.friction();
+
+    window.open_dialog(cx, move |dialog, _, cx| {
+        let muted_foreground = cx.theme().muted_foreground;
+        let warning_color = cx.theme().warning;
+        let acknowledged = app.read(cx).deletion_acknowledged;
+        let confirm_input = app.read(cx).deletion_confirm_input.clone();
+        let confirmed = match friction {
+            Friction::Confirm => true,
+            Friction::Acknowledge => acknowledged,
+            Friction::TypeZoneName => confirm_input.read(cx).value().trim() == zone_name,
+        };
+
+        let app = app.clone();
+        let records = records.clone();
+        dialog
+            .title(format!("Delete {} records?", records.len()))
+            .w(px(520.))
+            .child(
+                v_flex()
+                    .gap_2()
+                    .child(
+                        div()
+                            .text_sm()
+                            .text_color(muted_foreground)
+                            .child("These records will be removed from Cloudflare:"),
+                    )
+                    .child(
+                        v_flex()
+                            .max_h(px(240.))
+                            .overflow_y_scrollbar()
+                            .gap_1()
+                            .text_sm()
+                            .children(records.iter().map(|record| {
+                                div().child(format!(
+                                    "{} {} {}",
+                                    record.record_type, record.name, record.content
+                                ))
+                            })),
+                    )
+                    .children(kept.iter().map(|note| {
+                        div()
+                            .text_sm()
+                            .text_color(muted_foreground)
+                            .child(note.clone())
+                    }))
+                    .when(!warnings.is_empty(), |this| {
+                        this.child(
+                            v_flex()
+                                .gap_1()
+                                .px_3()
+                                .py_2()
+                                .rounded_md()
+                                .bg(warning_color.opacity(0.1))
+                                .text_sm()
+                                .text_color(warning_color)
+                                .child(
+                                    div()
+                                        .font_weight(FontWeight::MEDIUM)
+                                        .child("These records may still be in use"),
+                                )
+                                .children(
+                                    warnings
+                                        .iter()
+                                        .map(|warning| div().child(format!("• {}", warning))),
+                                ),
+                        )
+                    })
+                    .children(check.errors.iter().map(|error| {
+                        div()
+                            .text_xs()
+                            .text_color(muted_foreground)
+                            .child(error.clone())
+                    }))
+                    .map(|this| match friction {
+                        Friction::Confirm => this,
+                        Friction::Acknowledge => this.child(
+                            Checkbox::new("acknowledge-deletion")
+                                .label("I understand what deleting these records affects")
+                                .checked(acknowledged)
+                                .on_click({
+                                    let app = app.clone();
+                                    move |checked, _, cx| {
+                                        app.update(cx, |this, cx| {
+                                            this.set_deletion_acknowledged(*checked, cx)
+                                        });
+                                    }
+                                }),
+                        ),
+                        Friction::TypeZoneName => this.child(
+                            v_flex()
+                                .gap_1()
+                                .child(
+                                    div()
+                                        .text_sm()
+                                        .child(format!("Type {} to confirm", zone_name)),
+                                )
+                                .child(Input::new(&confirm_input)),
+                        ),
+                    }),
+            )
+            .footer(move |_, _, _, _| {
+                vec![
+                    Button::new("bulk-delete-cancel")
+                        .label("Cancel")
+                        .on_click(|_, window, cx| window.close_dialog(cx)),
+                    Button::new("bulk-delete-confirm")
+                        .danger()
+                        .label("Delete")
+                        .disabled(!confirmed)
+                        .on_click({
+                            let app = app.clone();
+                            let records = records.clone();
+                            move |_, window, cx| {
+                                window.close_dialog(cx);
+                                let records = records.clone();
+                                app.update(cx, |this, cx| this.delete_records(records, window, cx));
+                            }
+                        }),
+                ]
+            })
+    });
+}
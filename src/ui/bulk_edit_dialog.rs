@@ -0,0 +1,117 @@
+use gpui::prelude::*;
+use gpui::{App as GpuiApp, Entity, IntoElement, Window, div, px};
+use gpui_component::{
+    ActiveTheme, Sizable, WindowExt,
+    button::{Button, ButtonVariants},
+    h_flex,
+    input::Input,
+    v_flex,
+};
+
+use crate::App;
+
+/// Change the TTL, proxy status or comment of the selected records at once; what's left empty
+/// stays as it is
+pub fn open_bulk_edit_dialog(app: Entity<App>, window: &mut Window, cx: &mut GpuiApp) {
+    window.open_dialog(cx, move |dialog, _, cx| {
+        let state = app.read(cx);
+        let ttl_input = state.bulk_ttl_input.clone();
+        let comment_input = state.bulk_comment_input.clone();
+        let proxied = state.bulk_proxied;
+        let selected_count = state.selected_records.len();
+        let muted_foreground = cx.theme().muted_foreground;
+
+        let app = app.clone();
+        dialog
+            .title(format!("Edit {} records", selected_count))
+            .w(px(480.))
+            .child(
+                v_flex()
+                    .gap_4()
+                    .child(
+                        v_flex()
+                            .gap_1()
+                            .child(div().text_sm().child("TTL"))
+                            .child(Input::new(&ttl_input))
+                            .child(
+                                div()
+                                    .text_xs()
+                                    .text_color(muted_foreground)
+                                    .child("Seconds, or 1 for automatic"),
+                            ),
+                    )
+                    .child(
+                        v_flex()
+                            .gap_1()
+                            .child(div().text_sm().child("Proxy status"))
+                            .child(
+                                h_flex().gap_1().children(
+                                    [
+                                        (None, "Unchanged"),
+                                        (Some(true), "Proxied"),
+                                        (Some(false), "DNS only"),
+                                    ]
+                                    .into_iter()
+                                    .enumerate()
+                                    .map(
+                                        |(index, (value, label))| {
+                                            let app = app.clone();
+                                            Button::new(("bulk-proxied", index))
+                                                .xsmall()
+                                                .map(|this| {
+                                                    if value == proxied {
+                                                        this.primary()
+                                                    } else {
+                                                        this.ghost()
+                                                    }
+                                                })
+                                                .label(label)
+                                                .on_click(move |_, _, cx| {
+                                                    app.update(cx, |this, cx| {
+                                                        this.bulk_proxied = value;
+                                                        cx.notify();
+                                                    });
+                                                })
+                                        },
+                                    ),
+                                ),
+                            )
+                            .child(
+                                div()
+                                    .text_xs()
+                                    .text_color(muted_foreground)
+                                    .child("Only changes records that can be proxied"),
+                            ),
+                    )
+                    .child(
+                        v_flex()
+                            .gap_1()
+                            .child(div().text_sm().child("Comment"))
+                            .child(Input::new(&comment_input))
+                            .child(
+                                div()
+                                    .text_xs()
+                                    .text_color(muted_foreground)
+                                    .child("Replaces the comments of all selected records"),
+                            ),
+                    ),
+            )
+            .footer(move |_, _, _, _| {
+                vec![
+                    Button::new("bulk-edit-cancel")
+                        .label("Cancel")
+                        .on_click(|_, window, cx| window.close_dialog(cx)),
+                    Button::new("bulk-edit-apply")
+                        .primary()
+                        .label("Apply")
+                        .on_click({
+                            let app = app.clone();
+                            move |_, window, cx| {
+                                window.close_dialog(cx);
+                                app.update(cx, |this, cx| this.apply_bulk_edit(window, cx));
+                            }
+                        }),
+                ]
+            })
+    });
+}
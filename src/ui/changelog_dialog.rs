@@ -0,0 +1,99 @@
+use gpui::prelude::*;
+use gpui::{App as GpuiApp, Entity, FontWeight, IntoElement, Window, div, px};
+use gpui_component::{
+    ActiveTheme, Disableable, Sizable, WindowExt,
+    button::{Button, ButtonVariants},
+    h_flex,
+    scroll::ScrollableElement,
+    v_flex,
+};
+
+use crate::App;
+use crate::changelog::Release;
+
+/// Features of `releases`, each with a button opening the page it added
+pub fn open_changelog_dialog(
+    app: Entity<App>,
+    title: &'static str,
+    releases: Vec<Release>,
+    window: &mut Window,
+    cx: &mut GpuiApp,
+) {
+    window.open_dialog(cx, move |dialog, _, cx| {
+        let has_zone = app.read(cx).selected_zone(cx).is_some();
+        let muted_foreground = cx.theme().muted_foreground;
+
+        let mut ix = 0;
+        let app = app.clone();
+        dialog
+            .title(title)
+            .w(px(560.))
+            .child(
+                v_flex()
+                    .max_h(px(460.))
+                    .overflow_y_scrollbar()
+                    .gap_4()
+                    .children(releases.iter().map(|release| {
+                        v_flex()
+                            .gap_2()
+                            .child(
+                                div()
+                                    .text_sm()
+                                    .font_weight(FontWeight::SEMIBOLD)
+                                    .text_color(muted_foreground)
+                                    .child(format!("Version {}", release.version)),
+                            )
+                            .children(release.features.iter().map(|feature| {
+                                ix += 1;
+                                h_flex()
+                                    .gap_3()
+                                    .items_start()
+                                    .child(
+                                        v_flex()
+                                            .flex_1()
+                                            .gap_1()
+                                            .child(
+                                                div()
+                                                    .font_weight(FontWeight::MEDIUM)
+                                                    .child(feature.title.clone()),
+                                            )
+                                            .child(
+                                                div()
+                                                    .text_sm()
+                                                    .text_color(muted_foreground)
+                                                    .child(feature.description.clone()),
+                                            ),
+                                    )
+                                    .when_some(feature.link, |this, link| {
+                                        let app = app.clone();
+                                        let available = has_zone || !link.needs_zone();
+                                        this.child(
+                                            Button::new(("changelog-open", ix))
+                                                .small()
+                                                .ghost()
+                                                .label("Open")
+                                                .disabled(!available)
+                                                .when(!available, |this| {
+                                                    this.tooltip("Select a zone first")
+                                                })
+                                                .on_click(move |_, window, cx| {
+                                                    window.close_dialog(cx);
+                                                    app.update(cx, |this, cx| {
+                                                        this.open_feature(link, window, cx);
+                                                    });
+                                                }),
+                                        )
+                                    })
+                            }))
+                    })),
+            )
+            .footer(|_, _, _, _| {
+                vec![
+                    Button::new("changelog-close")
+                        .primary()
+                        .label("Close")
+                        .on_click(|_, window, cx| window.close_dialog(cx)),
+                ]
+            })
+    });
+}
@@ -0,0 +1,196 @@
+use gpui::prelude::*;
+use gpui::{Context, FontWeight, IntoElement, Window, div, px};
+use gpui_component::{
+    ActiveTheme, Disableable, Sizable,
+    button::{Button, ButtonVariants},
+    h_flex,
+    input::Input,
+    scroll::ScrollableElement,
+    v_flex,
+};
+
+use super::render_status_bar;
+use crate::{App, Page};
+
+/// Script console with the script library on the left and the script's output below the editor
+pub fn render_console(
+    app: &mut App,
+    _window: &mut Window,
+    cx: &mut Context<App>,
+) -> impl IntoElement {
+    let zone_name = app.selected_zone(cx).map(|zone| zone.name);
+    let change_count = app
+        .script_output
+        .as_ref()
+        .map(|output| output.changes.len())
+        .unwrap_or(0);
+
+    v_flex()
+        .size_full()
+        .child(
+            // Header
+            h_flex()
+                .w_full()
+                .px_4()
+                .py_3()
+                .border_b_1()
+                .border_color(cx.theme().border)
+                .items_center()
+                .gap_3()
+                .child(
+                    Button::new("back")
+                        .ghost()
+                        .icon(gpui_component::IconName::ArrowLeft)
+                        .on_click(cx.listener(|this, _, _, cx| {
+                            this.page = Page::Dashboard;
+                            cx.notify();
+                        })),
+                )
+                .child(
+                    div()
+                        .text_lg()
+                        .font_weight(FontWeight::SEMIBOLD)
+                        .child("Script Console"),
+                )
+                .when_some(zone_name.clone(), |this, zone_name| {
+                    this.child(
+                        div()
+                            .text_sm()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(zone_name),
+                    )
+                }),
+        )
+        .child(
+            h_flex()
+                .flex_1()
+                .overflow_hidden()
+                .child(render_script_library(app, cx))
+                .child(
+                    v_flex()
+                        .flex_1()
+                        .h_full()
+                        .p_4()
+                        .gap_3()
+                        .child(div().flex_1().child(Input::new(&app.script_input).h_full()))
+                        .child(
+                            h_flex()
+                                .gap_2()
+                                .child(
+                                    Button::new("run-script")
+                                        .primary()
+                                        .label("Run")
+                                        .disabled(zone_name.is_none())
+                                        .on_click(cx.listener(|this, _, window, cx| {
+                                            this.run_script(window, cx);
+                                        })),
+                                )
+                                .child(
+                                    Button::new("apply-script-changes")
+                                        .label(format!("Apply {} Changes", change_count))
+                                        .disabled(change_count == 0)
+                                        .on_click(cx.listener(|this, _, window, cx| {
+                                            this.apply_script_changes(window, cx);
+                                        })),
+                                )
+                                .child(
+                                    Button::new("save-script")
+                                        .ghost()
+                                        .label("Save to Library")
+                                        .on_click(cx.listener(|this, _, window, cx| {
+                                            this.open_save_script_dialog(window, cx);
+                                        })),
+                                ),
+                        )
+                        .child(render_output(app, cx)),
+                ),
+        )
+        .child(render_status_bar(app, cx))
+}
+
+fn render_script_library(app: &App, cx: &mut Context<App>) -> impl IntoElement {
+    v_flex()
+        .w(px(220.))
+        .h_full()
+        .p_4()
+        .gap_2()
+        .border_r_1()
+        .border_color(cx.theme().border)
+        .child(
+            div()
+                .font_weight(FontWeight::MEDIUM)
+                .child("Script Library"),
+        )
+        .when(app.saved_scripts.is_empty(), |this| {
+            this.child(
+                div()
+                    .text_sm()
+                    .text_color(cx.theme().muted_foreground)
+                    .child("No saved scripts"),
+            )
+        })
+        .child(v_flex().flex_1().gap_1().overflow_y_scrollbar().children(
+            app.saved_scripts.iter().enumerate().map(|(index, script)| {
+                h_flex()
+                    .gap_1()
+                    .items_center()
+                    .child(
+                        Button::new(("load-script", index))
+                            .ghost()
+                            .small()
+                            .flex_1()
+                            .label(script.name.clone())
+                            .on_click(cx.listener(move |this, _, window, cx| {
+                                this.load_saved_script(index, window, cx);
+                            })),
+                    )
+                    .child(
+                        Button::new(("delete-script", index))
+                            .ghost()
+                            .xsmall()
+                            .icon(gpui_component::IconName::Delete)
+                            .tooltip("Delete script")
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                this.delete_saved_script(index, cx);
+                            })),
+                    )
+            }),
+        ))
+}
+
+/// What the last run printed, its error, and the changes it collected
+fn render_output(app: &App, cx: &mut Context<App>) -> impl IntoElement {
+    let muted_foreground = cx.theme().muted_foreground;
+
+    v_flex()
+        .h(px(200.))
+        .p_2()
+        .gap_1()
+        .border_1()
+        .border_color(cx.theme().border)
+        .rounded_md()
+        .text_sm()
+        .font_family("monospace")
+        .overflow_y_scrollbar()
+        .map(|this| {
+            let Some(output) = &app.script_output else {
+                return this.text_color(muted_foreground).child(
+                    "Run the script to preview its changes; nothing is changed until you apply them",
+                );
+            };
+
+            this.children(output.log.iter().map(|line| div().child(line.clone())))
+                .when_some(output.error.clone(), |this, error| {
+                    this.child(div().text_color(cx.theme().danger).child(error))
+                })
+                .when(output.error.is_none(), |this| {
+                    if output.changes.is_empty() {
+                        this.child(div().text_color(muted_foreground).child("No changes"))
+                    } else {
+                        this.children(output.changes.iter().map(|(label, _)| {
+                            div().text_color(cx.theme().primary).child(label.clone())
+                        }))
+                    }
+                })
+        })
+}
@@ -0,0 +1,150 @@
+use gpui::prelude::*;
+use gpui::{App as GpuiApp, Entity, FontWeight, IntoElement, Window, div, px};
+use gpui_component::{
+    ActiveTheme, Disableable, WindowExt,
+    button::{Button, ButtonVariants},
+    h_flex,
+    menu::{DropdownMenu, PopupMenuItem},
+    scroll::ScrollableElement,
+    v_flex,
+};
+
+use crate::App;
+use crate::csv_import::{CsvImport, RecordField};
+
+/// Open the CSV import wizard for the file loaded into `App::csv_import`
+///
+/// Columns are mapped to record fields at the top, and every row is validated below, so
+/// errors show up before anything is created.
+pub fn open_csv_import_dialog(app: Entity<App>, window: &mut Window, cx: &mut GpuiApp) {
+    window.open_dialog(cx, move |dialog, _, cx| {
+        let Some(import) = app.read(cx).csv_import.clone() else {
+            return dialog.title("Import CSV");
+        };
+
+        let rows = import.records();
+        let valid_count = rows.iter().filter(|row| row.result.is_ok()).count();
+        let unmapped = import.unmapped_required();
+
+        let app = app.clone();
+        dialog
+            .title("Import CSV")
+            .w(px(640.))
+            .child(
+                v_flex()
+                    .gap_3()
+                    .child(div().font_weight(FontWeight::MEDIUM).child("Columns"))
+                    .children(
+                        RecordField::ALL
+                            .into_iter()
+                            .map(|field| render_field_mapping(&app, &import, field)),
+                    )
+                    .child(div().font_weight(FontWeight::MEDIUM).child(format!(
+                        "Rows ({} of {} valid)",
+                        valid_count,
+                        rows.len()
+                    )))
+                    .child(
+                        v_flex()
+                            .max_h(px(280.))
+                            .overflow_y_scrollbar()
+                            .gap_1()
+                            .text_sm()
+                            .map(|this| {
+                                if !unmapped.is_empty() {
+                                    let names: Vec<&str> =
+                                        unmapped.iter().map(|field| field.label()).collect();
+                                    return this.child(div().text_color(cx.theme().warning).child(
+                                        format!("Choose the column of: {}", names.join(", ")),
+                                    ));
+                                }
+
+                                this.children(rows.iter().map(|row| {
+                                    match &row.result {
+                                        Ok(record) => div().child(format!(
+                                            "Row {}: {} {} {}",
+                                            row.line,
+                                            record.record_type,
+                                            record.name,
+                                            record.content
+                                        )),
+                                        Err(e) => div()
+                                            .text_color(cx.theme().danger)
+                                            .child(format!("Row {}: {}", row.line, e)),
+                                    }
+                                }))
+                            }),
+                    ),
+            )
+            .footer(move |_, _, _, _| {
+                vec![
+                    Button::new("csv-import-cancel")
+                        .label("Cancel")
+                        .on_click(|_, window, cx| window.close_dialog(cx)),
+                    Button::new("csv-import-create")
+                        .primary()
+                        .label(format!("Create {} Records", valid_count))
+                        .disabled(valid_count == 0 || !unmapped.is_empty())
+                        .on_click({
+                            let app = app.clone();
+                            move |_, window, cx| {
+                                window.close_dialog(cx);
+                                app.update(cx, |this, cx| this.apply_csv_import(window, cx));
+                            }
+                        }),
+                ]
+            })
+    });
+}
+
+/// Field label and a dropdown to choose its column
+fn render_field_mapping(
+    app: &Entity<App>,
+    import: &CsvImport,
+    field: RecordField,
+) -> impl IntoElement {
+    let headers = import.headers.clone();
+    let column = import.column(field);
+    let app = app.clone();
+
+    h_flex()
+        .gap_3()
+        .items_center()
+        .child(div().w(px(100.)).text_sm().child(if field.is_required() {
+            format!("{} *", field.label())
+        } else {
+            field.label().to_string()
+        }))
+        .child(
+            Button::new(("csv-column", field.index()))
+                .outline()
+                .w(px(240.))
+                .label(
+                    column
+                        .and_then(|column| headers.get(column).cloned())
+                        .unwrap_or_else(|| "Not mapped".to_string()),
+                )
+                .dropdown_menu(move |menu, _, _| {
+                    let mut menu = menu;
+                    let options = std::iter::once((None, "Not mapped".to_string())).chain(
+                        headers
+                            .iter()
+                            .enumerate()
+                            .map(|(index, header)| (Some(index), header.clone())),
+                    );
+                    for (option, label) in options {
+                        let app = app.clone();
+                        menu = menu.item(
+                            PopupMenuItem::new(label)
+                                .checked(option == column)
+                                .on_click(move |_, _, cx| {
+                                    app.update(cx, |this, cx| {
+                                        this.set_csv_column(field, option, cx)
+                                    });
+                                }),
+                        );
+                    }
+                    menu
+                }),
+        )
+}
@@ -1,23 +1,117 @@
+use chrono::Utc;
 use gpui::prelude::*;
-use gpui::{Context, FontWeight, IntoElement, Window, div, px};
+use gpui::{Context, FontWeight, IntoElement, SharedString, Window, div, px};
 use gpui_component::{
-    ActiveTheme, Sizable,
+    ActiveTheme, Disableable, Selectable, Sizable,
     button::{Button, ButtonVariants},
+    checkbox::Checkbox,
     h_flex,
+    input::Input,
+    menu::{DropdownMenu, PopupMenuItem},
+    popover::Popover,
     select::Select,
+    switch::Switch,
+    tag::Tag,
     v_flex,
 };
 
-use super::{render_dns_list, render_record_editor};
-use crate::{App, Page};
+use super::{
+    open_history_dialog, open_resolver_dialog, open_retry_queue_dialog, open_staging_dialog,
+    render_account_picker, render_activity_panel, render_dns_list, render_profile_switcher,
+    render_record_editor, render_status_bar, render_third_party_summary, render_workspace_picker,
+};
+use crate::dashboard_links::DashboardPage;
+use crate::record_export::{ExportFormat, ExportScope};
+use crate::token_scopes::Capability;
+use crate::ttl_lowering::LOWERED_TTLS;
+use crate::zone_settings::{ZoneSettingKind, format_remaining};
+use crate::{
+    App, Page,
+    cloudflare::{DnsRecord, DnsRecordType, Zone},
+    filter, timestamps,
+};
 
 pub fn render_dashboard(
     app: &mut App,
     window: &mut Window,
     cx: &mut Context<App>,
 ) -> impl IntoElement {
-    let is_loading = app.loading;
-    let dns_records = app.dns_records.clone();
+    let total_count = app.dns_records(cx).len();
+    let shown_count = app.filtered_records(cx).len();
+    let selected = app.selected_records(cx);
+    let third_party_counts = app.third_party_counts(cx);
+    let type_counts = app.record_type_counts(cx);
+    let quick_filter_counts = app.quick_filter_counts(cx);
+    let lint_count = app.zone_lints(cx).len();
+    let staged_count = app.zone_staged_changes(cx).len();
+    let retry_count = app.retry_queue.items().len();
+    let unproxied_count = app
+        .selected_zone(cx)
+        .and_then(|zone| app.unproxied_records.get(&zone.id))
+        .map(Vec::len)
+        .unwrap_or(0);
+    let lowered_ttls = app
+        .selected_zone(cx)
+        .and_then(|zone| app.lowered_ttls.get(&zone.id))
+        .map(|lowered| {
+            if lowered.expired(Utc::now()) {
+                format!(
+                    "TTLs of {} records are lowered and their old TTLs have expired, so planned changes reach everyone quickly",
+                    lowered.original.len()
+                )
+            } else {
+                format!(
+                    "TTLs of {} records are lowered for a planned change; their old TTLs expire at {}",
+                    lowered.original.len(),
+                    app.time_zone_display.format(lowered.expires_at)
+                )
+            }
+        });
+    let token_expiry = app.token_expiring_on().map(|expires_on| {
+        let remaining = expires_on - Utc::now();
+        if remaining <= chrono::Duration::zero() {
+            format!(
+                "The API token expired on {}; create a new one and update it in Settings",
+                app.time_zone_display.format(expires_on)
+            )
+        } else {
+            format!(
+                "The API token expires in {} ({}); roll it in the Cloudflare dashboard and update it in Settings",
+                match remaining.num_days() {
+                    0 => "less than a day".to_string(),
+                    1 => "1 day".to_string(),
+                    days => format!("{} days", days),
+                },
+                app.time_zone_display.format(expires_on)
+            )
+        }
+    });
+    let can_edit = app.can(Capability::EditDns);
+    let development_mode = app
+        .selected_zone(cx)
+        .and_then(|zone| app.development_mode.get(&zone.id).copied());
+    let all_selected = shown_count > 0
+        && app
+            .filtered_records(cx)
+            .iter()
+            .all(|record| app.selected_records.contains(&record.id));
+    let query = app.search_input.read(cx).value().trim().to_string();
+    let current_filter = app
+        .saved_filters
+        .iter()
+        .find(|f| f.query == query)
+        .map(|f| f.name.clone());
+
+    let undo_tooltip = app
+        .history
+        .undo_entries()
+        .last()
+        .map(|entry| format!("Undo {}", entry.description));
+    let redo_tooltip = app
+        .history
+        .redo_entries()
+        .last()
+        .map(|entry| format!("Redo {}", entry.description));
 
     v_flex()
         .size_full()
@@ -35,28 +129,215 @@ pub fn render_dashboard(
                     h_flex()
                         .gap_3()
                         .items_center()
+                        .child(
+                            Button::new("portfolio")
+                                .ghost()
+                                .icon(gpui_component::IconName::LayoutDashboard)
+                                .tooltip("Portfolio")
+                                .on_click(cx.listener(|this, _, _, cx| {
+                                    this.page = Page::Portfolio;
+                                    cx.notify();
+                                })),
+                        )
                         .child(
                             div()
                                 .text_lg()
                                 .font_weight(FontWeight::SEMIBOLD)
                                 .child("Cloudflare DNS Manager"),
                         )
-                        .map(|this| {
-                            if is_loading {
-                                this.child(
-                                    div()
-                                        .text_sm()
-                                        .text_color(cx.theme().muted_foreground)
-                                        .child("Loading..."),
-                                )
-                            } else {
-                                this
-                            }
+                        .when(app.read_only(), |this| {
+                            this.child(
+                                div()
+                                    .px_2()
+                                    .rounded_md()
+                                    .bg(cx.theme().muted)
+                                    .text_xs()
+                                    .text_color(cx.theme().muted_foreground)
+                                    .child("Read-only"),
+                            )
                         }),
                 )
                 .child(
                     h_flex()
                         .gap_2()
+                        .child(
+                            Button::new("undo")
+                                .ghost()
+                                .icon(gpui_component::IconName::Undo2)
+                                .tooltip(undo_tooltip.clone().unwrap_or("Undo".to_string()))
+                                .disabled(undo_tooltip.is_none())
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.undo(window, cx);
+                                })),
+                        )
+                        .child(
+                            Button::new("redo")
+                                .ghost()
+                                .icon(gpui_component::IconName::Redo2)
+                                .tooltip(redo_tooltip.clone().unwrap_or("Redo".to_string()))
+                                .disabled(redo_tooltip.is_none())
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.redo(window, cx);
+                                })),
+                        )
+                        .child(render_plugin_menu(app, cx))
+                        .child(
+                            Button::new("console")
+                                .ghost()
+                                .icon(gpui_component::IconName::SquareTerminal)
+                                .tooltip("Script Console")
+                                .on_click(cx.listener(|this, _, _, cx| {
+                                    this.page = Page::Console;
+                                    cx.notify();
+                                })),
+                        )
+                        .child(
+                            Button::new("activity")
+                                .ghost()
+                                .icon(if app.show_activity {
+                                    gpui_component::IconName::PanelRightClose
+                                } else {
+                                    gpui_component::IconName::PanelRightOpen
+                                })
+                                .tooltip(if app.show_activity {
+                                    "Hide activity"
+                                } else {
+                                    "Show activity"
+                                })
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.toggle_activity_panel(window, cx);
+                                })),
+                        )
+                        .child(
+                            Button::new("staging")
+                                .map(|this| if app.staging { this.primary() } else { this.ghost() })
+                                .label("Stage Changes")
+                                .tooltip("Collect changes for review instead of applying them")
+                                .on_click(cx.listener(|this, _, _, cx| {
+                                    this.set_staging(!this.staging, cx);
+                                })),
+                        )
+                        .child(Button::new("history").ghost().label("History").on_click(
+                            cx.listener(|_, _, window, cx| {
+                                open_history_dialog(cx.entity(), window, cx);
+                            }),
+                        ))
+                        .when_some(development_mode, |this, ends_at| {
+                            this.child(
+                                Switch::new("development-mode")
+                                    .checked(ends_at.is_some())
+                                    .label(match ends_at {
+                                        Some(ends_at) => format!(
+                                            "Development Mode {}",
+                                            format_remaining(ends_at - Utc::now())
+                                        ),
+                                        None => "Development Mode".to_string(),
+                                    })
+                                    .tooltip(ZoneSettingKind::DevelopmentMode.description())
+                                    .disabled(app.read_only())
+                                    .on_click(cx.listener(|this, on: &bool, window, cx| {
+                                        this.set_development_mode(*on, window, cx);
+                                    })),
+                            )
+                        })
+                        .when(app.can(Capability::PageRules), |this| {
+                            this.child(
+                                Button::new("page-rules")
+                                    .ghost()
+                                    .label("Page Rules")
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.open_page_rules(window, cx);
+                                    })),
+                            )
+                        })
+                        .child(
+                            Button::new("redirect-rules")
+                                .ghost()
+                                .label("Redirects")
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.open_redirect_rules(window, cx);
+                                })),
+                        )
+                        .when(app.can(Capability::Waf), |this| {
+                            this.child(
+                                Button::new("waf-rules")
+                                    .ghost()
+                                    .label("WAF")
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.open_waf_rules(window, cx);
+                                    })),
+                            )
+                        })
+                        .when(app.can(Capability::WorkerRoutes), |this| {
+                            this.child(
+                                Button::new("worker-routes")
+                                    .ghost()
+                                    .label("Workers")
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.open_worker_routes(window, cx);
+                                    })),
+                            )
+                        })
+                        .when(app.can(Capability::Pages), |this| {
+                            this.child(
+                                Button::new("pages-projects")
+                                    .ghost()
+                                    .label("Pages")
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.open_pages_projects(window, cx);
+                                    })),
+                            )
+                        })
+                        .when(app.can(Capability::Tunnels), |this| {
+                            this.child(
+                                Button::new("tunnels")
+                                    .ghost()
+                                    .label("Tunnels")
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.open_tunnels(window, cx);
+                                    })),
+                            )
+                        })
+                        .when(app.can(Capability::Certificates), |this| {
+                            this.child(
+                                Button::new("edge-certificates")
+                                    .ghost()
+                                    .label("SSL/TLS")
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.open_edge_certificates(window, cx);
+                                    })),
+                            )
+                        })
+                        .when(app.can(Capability::Analytics), |this| {
+                            this.child(
+                                Button::new("analytics")
+                                    .ghost()
+                                    .label("Analytics")
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.open_analytics(window, cx);
+                                    })),
+                            )
+                        })
+                        .when(app.can(Capability::ZoneSettings), |this| {
+                            this.child(
+                                Button::new("zone-settings")
+                                    .ghost()
+                                    .icon(gpui_component::IconName::Settings2)
+                                    .tooltip("Zone Settings")
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.open_zone_settings(window, cx);
+                                    })),
+                            )
+                        })
+                        .when(app.profiles.len() > 1, |this| {
+                            this.child(render_profile_switcher("dashboard-profile", app, cx))
+                        })
+                        .when(app.zone_accounts(cx).len() > 1, |this| {
+                            this.child(render_account_picker("dashboard-account", app, cx))
+                        })
+                        .when(!app.workspaces.is_empty(), |this| {
+                            this.child(render_workspace_picker("dashboard-workspace", app, cx))
+                        })
                         .child(
                             Select::new(&app.zone_select)
                                 .w(px(250.))
@@ -73,6 +354,9 @@ pub fn render_dashboard(
                         ),
                 ),
         )
+        .when_some(app.selected_zone(cx), |this, zone| {
+            this.child(render_zone_header(&zone, app, cx))
+        })
         .child(
             // Main content - horizontal split
             h_flex()
@@ -91,22 +375,838 @@ pub fn render_dashboard(
                                 .items_center()
                                 .justify_between()
                                 .child(
-                                    div()
-                                        .font_weight(FontWeight::MEDIUM)
-                                        .child(format!("DNS Records ({})", dns_records.len())),
+                                    h_flex()
+                                        .gap_2()
+                                        .items_center()
+                                        .child(
+                                            Checkbox::new("select-all-records")
+                                                .checked(all_selected)
+                                                .disabled(shown_count == 0)
+                                                .on_click(cx.listener(|this, selected, _, cx| {
+                                                    this.select_all_records(*selected, cx);
+                                                })),
+                                        )
+                                        .child(div().font_weight(FontWeight::MEDIUM).child(
+                                            if shown_count == total_count {
+                                                format!("DNS Records ({})", total_count)
+                                            } else {
+                                                format!(
+                                                    "DNS Records ({} of {})",
+                                                    shown_count, total_count
+                                                )
+                                            },
+                                        )),
                                 )
                                 .child(
-                                    Button::new("refresh")
+                                    h_flex()
+                                        .gap_1()
+                                        .child(
+                                            Button::new("group-by-name")
+                                                .ghost()
+                                                .small()
+                                                .icon(gpui_component::IconName::FolderClosed)
+                                                .selected(app.group_by_name)
+                                                .tooltip(if app.group_by_name {
+                                                    "Show every record on its own row"
+                                                } else {
+                                                    "Group records by name"
+                                                })
+                                                .on_click(cx.listener(|this, _, _, cx| {
+                                                    this.toggle_group_by_name(cx);
+                                                })),
+                                        )
+                                        .child(
+                                            Button::new("resolver")
+                                                .ghost()
+                                                .small()
+                                                .icon(gpui_component::IconName::Globe)
+                                                .tooltip("What-if resolver")
+                                                .disabled(app.selected_zone_index.is_none())
+                                                .on_click(cx.listener(|_, _, window, cx| {
+                                                    open_resolver_dialog(cx.entity(), window, cx);
+                                                })),
+                                        )
+                                        .child(render_import_menu(app, cx))
+                                        .child(render_export_menu(
+                                            app,
+                                            shown_count < total_count,
+                                            !selected.is_empty(),
+                                            cx,
+                                        ))
+                                        .child(
+                                            Button::new("print-report")
+                                                .ghost()
+                                                .small()
+                                                .icon(gpui_component::IconName::File)
+                                                .tooltip("Print zone report")
+                                                .disabled(app.selected_zone_index.is_none())
+                                                .on_click(cx.listener(|this, _, window, cx| {
+                                                    this.open_zone_report_dialog(window, cx);
+                                                })),
+                                        )
+                                        .child(
+                                            Button::new("unproxy-all")
+                                                .ghost()
+                                                .small()
+                                                .icon(gpui_component::IconName::TriangleAlert)
+                                                .tooltip("Unproxy all records")
+                                                .disabled(
+                                                    app.selected_zone_index.is_none() || !can_edit,
+                                                )
+                                                .on_click(cx.listener(|this, _, window, cx| {
+                                                    this.open_unproxy_all_dialog(window, cx);
+                                                })),
+                                        )
+                                        .child(
+                                            Button::new("refresh")
+                                                .ghost()
+                                                .small()
+                                                .icon(gpui_component::IconName::Redo)
+                                                .on_click(cx.listener(|this, _, window, cx| {
+                                                    this.load_dns_records(window, cx);
+                                                })),
+                                        ),
+                                ),
+                        )
+                        .child(
+                            // Search bar with saved filters and recent searches
+                            h_flex()
+                                .gap_2()
+                                .child(Input::new(&app.search_input).cleanable(true).flex_1())
+                                .child(render_search_presets(app, cx))
+                                .child(match current_filter {
+                                    Some(name) => Button::new("delete-filter")
                                         .ghost()
-                                        .small()
-                                        .icon(gpui_component::IconName::Redo)
+                                        .icon(gpui_component::IconName::StarOff)
+                                        .tooltip(format!("Delete saved filter \"{}\"", name))
+                                        .on_click(cx.listener(move |this, _, _, cx| {
+                                            this.delete_saved_filter(&name, cx);
+                                        })),
+                                    None => Button::new("save-filter")
+                                        .ghost()
+                                        .icon(gpui_component::IconName::Star)
+                                        .tooltip("Save as filter")
+                                        .disabled(query.is_empty())
                                         .on_click(cx.listener(|this, _, window, cx| {
-                                            this.load_dns_records(window, cx);
+                                            this.open_save_filter_dialog(window, cx);
                                         })),
-                                ),
+                                }),
                         )
-                        .child(render_dns_list(app, window, cx)),
+                        .when(!type_counts.is_empty(), |this| {
+                            this.child(render_filter_chips(
+                                type_counts,
+                                quick_filter_counts,
+                                &query,
+                                cx,
+                            ))
+                        })
+                        .when_some(token_expiry, |this, message| {
+                            this.child(
+                                h_flex()
+                                    .gap_2()
+                                    .items_center()
+                                    .px_3()
+                                    .py_2()
+                                    .rounded_md()
+                                    .bg(cx.theme().warning.opacity(0.1))
+                                    .child(
+                                        div()
+                                            .flex_1()
+                                            .text_sm()
+                                            .text_color(cx.theme().warning)
+                                            .child(message),
+                                    )
+                                    .child(
+                                        Button::new("update-expiring-token")
+                                            .small()
+                                            .label("Update Token")
+                                            .on_click(cx.listener(|this, _, _, cx| {
+                                                this.page = Page::Settings;
+                                                cx.notify();
+                                            })),
+                                    ),
+                            )
+                        })
+                        .when(unproxied_count > 0, |this| {
+                            this.child(
+                                h_flex()
+                                    .gap_2()
+                                    .items_center()
+                                    .px_3()
+                                    .py_2()
+                                    .rounded_md()
+                                    .bg(cx.theme().danger.opacity(0.1))
+                                    .child(
+                                        div()
+                                            .flex_1()
+                                            .text_sm()
+                                            .text_color(cx.theme().danger)
+                                            .child(format!(
+                                                "{} records were unproxied for maintenance and expose their origins",
+                                                unproxied_count
+                                            )),
+                                    )
+                                    .child(
+                                        Button::new("reproxy-records")
+                                            .small()
+                                            .label("Proxy Again")
+                                            .disabled(!can_edit)
+                                            .on_click(cx.listener(|this, _, window, cx| {
+                                                this.reproxy_records(window, cx);
+                                            })),
+                                    ),
+                            )
+                        })
+                        .when_some(lowered_ttls, |this, message| {
+                            this.child(
+                                h_flex()
+                                    .gap_2()
+                                    .items_center()
+                                    .px_3()
+                                    .py_2()
+                                    .rounded_md()
+                                    .bg(cx.theme().warning.opacity(0.1))
+                                    .child(
+                                        div()
+                                            .flex_1()
+                                            .text_sm()
+                                            .text_color(cx.theme().warning)
+                                            .child(message),
+                                    )
+                                    .child(
+                                        Button::new("restore-ttls")
+                                            .small()
+                                            .label("Restore TTLs")
+                                            .disabled(!can_edit)
+                                            .on_click(cx.listener(|this, _, window, cx| {
+                                                this.restore_ttls(window, cx);
+                                            })),
+                                    ),
+                            )
+                        })
+                        .when(staged_count > 0, |this| {
+                            this.child(
+                                h_flex()
+                                    .gap_2()
+                                    .items_center()
+                                    .px_3()
+                                    .py_2()
+                                    .rounded_md()
+                                    .bg(cx.theme().info.opacity(0.1))
+                                    .child(
+                                        div()
+                                            .flex_1()
+                                            .text_sm()
+                                            .text_color(cx.theme().info)
+                                            .child(format!(
+                                                "{} staged changes are not sent to Cloudflare yet",
+                                                staged_count
+                                            )),
+                                    )
+                                    .child(
+                                        Button::new("review-staged")
+                                            .small()
+                                            .label("Review")
+                                            .on_click(cx.listener(|_, _, window, cx| {
+                                                open_staging_dialog(cx.entity(), window, cx);
+                                            })),
+                                    ),
+                            )
+                        })
+                        .when(retry_count > 0, |this| {
+                            this.child(
+                                h_flex()
+                                    .gap_2()
+                                    .items_center()
+                                    .px_3()
+                                    .py_2()
+                                    .rounded_md()
+                                    .bg(cx.theme().danger.opacity(0.1))
+                                    .child(
+                                        div()
+                                            .flex_1()
+                                            .text_sm()
+                                            .text_color(cx.theme().danger)
+                                            .child(format!(
+                                                "{} failed changes are waiting to be retried",
+                                                retry_count
+                                            )),
+                                    )
+                                    .child(
+                                        Button::new("review-retries")
+                                            .small()
+                                            .label("Review")
+                                            .on_click(cx.listener(|_, _, window, cx| {
+                                                open_retry_queue_dialog(cx.entity(), window, cx);
+                                            })),
+                                    ),
+                            )
+                        })
+                        .when(lint_count > 0, |this| {
+                            this.child(
+                                h_flex()
+                                    .gap_2()
+                                    .items_center()
+                                    .px_3()
+                                    .py_2()
+                                    .rounded_md()
+                                    .bg(cx.theme().warning.opacity(0.1))
+                                    .child(
+                                        div()
+                                            .flex_1()
+                                            .text_sm()
+                                            .text_color(cx.theme().warning)
+                                            .child(format!(
+                                                "{} records use unusual or deprecated setups",
+                                                lint_count
+                                            )),
+                                    )
+                                    .child(
+                                        Button::new("review-lints")
+                                            .small()
+                                            .label("Review")
+                                            .on_click(cx.listener(|this, _, window, cx| {
+                                                this.open_lint_dialog(window, cx);
+                                            })),
+                                    ),
+                            )
+                        })
+                        .when(!third_party_counts.is_empty(), |this| {
+                            this.child(render_third_party_summary(third_party_counts, cx))
+                        })
+                        .child(render_dns_list(app, window, cx))
+                        .when(!selected.is_empty(), |this| {
+                            this.child(render_selection_footer(&selected, can_edit, cx))
+                        }),
                 )
-                .child(render_record_editor(app, window, cx)),
+                .child(render_record_editor(app, window, cx))
+                .when(app.show_activity, |this| {
+                    this.child(render_activity_panel(app, window, cx))
+                }),
         )
+        .child(render_status_bar(app, cx))
+}
+
+/// Status and plan of the selected zone, expanding to its assigned and original nameservers
+fn render_zone_header(zone: &Zone, app: &App, cx: &mut Context<App>) -> impl IntoElement {
+    let expanded = app.zone_details_expanded;
+    let muted_foreground = cx.theme().muted_foreground;
+    let status_tag = match zone.status.as_str() {
+        _ if zone.paused => Tag::warning(),
+        "active" => Tag::success(),
+        "moved" | "deleted" | "deactivated" => Tag::danger(),
+        _ => Tag::warning(),
+    }
+    .small()
+    .child(zone.status_label());
+    let plan = zone
+        .plan
+        .as_ref()
+        .map(|plan| plan.name.clone())
+        .unwrap_or_else(|| "Unknown plan".to_string());
+    let activated_on = zone
+        .activated_on
+        .as_deref()
+        .and_then(timestamps::parse)
+        .map(|time| app.time_zone_display.format(time));
+    let description = zone.status_description();
+    let account = zone.account.name.clone();
+    let registrar = zone.original_registrar.clone();
+    let dns_host = zone.original_dnshost.clone();
+    let original_name_servers = zone.original_name_servers.clone().unwrap_or_default();
+    let pending = zone.status != "active";
+
+    v_flex()
+        .w_full()
+        .px_4()
+        .py_2()
+        .gap_2()
+        .border_b_1()
+        .border_color(cx.theme().border)
+        .child(
+            h_flex()
+                .gap_3()
+                .items_center()
+                .text_sm()
+                .child(
+                    Button::new("toggle-zone-details")
+                        .ghost()
+                        .xsmall()
+                        .icon(if expanded {
+                            gpui_component::IconName::ChevronDown
+                        } else {
+                            gpui_component::IconName::ChevronRight
+                        })
+                        .tooltip(if expanded {
+                            "Hide nameservers"
+                        } else {
+                            "Show nameservers"
+                        })
+                        .on_click(cx.listener(|this, _, _, cx| {
+                            this.toggle_zone_details(cx);
+                        })),
+                )
+                .child(
+                    div()
+                        .font_weight(FontWeight::MEDIUM)
+                        .child(zone.name.clone()),
+                )
+                .child(status_tag)
+                .child(div().text_color(muted_foreground).child(plan))
+                .child(
+                    div()
+                        .text_color(muted_foreground)
+                        .child(zone.name_servers.join(", ")),
+                )
+                .child(
+                    Popover::new("zone-details")
+                        .trigger(
+                            Button::new("zone-details-trigger")
+                                .ghost()
+                                .xsmall()
+                                .icon(gpui_component::IconName::Info),
+                        )
+                        .content(move |_, _, cx| {
+                            let muted_foreground = cx.theme().muted_foreground;
+                            let detail = |label: &'static str, value: String| {
+                                h_flex()
+                                    .gap_2()
+                                    .child(
+                                        div().w(px(110.)).text_color(muted_foreground).child(label),
+                                    )
+                                    .child(value)
+                            };
+
+                            v_flex()
+                                .w(px(340.))
+                                .gap_2()
+                                .text_sm()
+                                .child(description)
+                                .child(detail("Account", account.clone()))
+                                .when_some(activated_on.clone(), |this, activated_on| {
+                                    this.child(detail("Activated", activated_on))
+                                })
+                                .when_some(registrar.clone(), |this, registrar| {
+                                    this.child(detail("Registrar", registrar))
+                                })
+                                .when_some(dns_host.clone(), |this, dns_host| {
+                                    this.child(detail("Previous DNS host", dns_host))
+                                })
+                        }),
+                )
+                .child(
+                    Button::new("open-in-dashboard")
+                        .ghost()
+                        .xsmall()
+                        .icon(gpui_component::IconName::ExternalLink)
+                        .tooltip("Open in the Cloudflare dashboard")
+                        .dropdown_menu({
+                            let zone = zone.clone();
+                            move |menu, _, _| {
+                                let mut menu = menu;
+                                for page in DashboardPage::ALL {
+                                    let url = page.url(&zone);
+                                    menu = menu.item(
+                                        PopupMenuItem::new(page.label())
+                                            .on_click(move |_, _, cx| cx.open_url(&url)),
+                                    );
+                                }
+                                menu
+                            }
+                        }),
+                ),
+        )
+        .when(expanded, |this| {
+            this.child(
+                h_flex()
+                    .gap_6()
+                    .items_start()
+                    .text_sm()
+                    .child(render_name_servers(
+                        "Assigned Cloudflare nameservers",
+                        &zone.name_servers,
+                        cx,
+                    ))
+                    .when(!original_name_servers.is_empty(), |this| {
+                        this.child(render_name_servers(
+                            "Original nameservers",
+                            &original_name_servers,
+                            cx,
+                        ))
+                    })
+                    .when(pending, |this| {
+                        this.child(div().flex_1().text_color(cx.theme().warning).child(
+                            "The registrar must replace the original nameservers with \
+                                     the assigned ones for the zone to become active.",
+                        ))
+                    }),
+            )
+        })
+}
+
+fn render_name_servers(
+    title: &'static str,
+    name_servers: &[String],
+    cx: &Context<App>,
+) -> impl IntoElement {
+    v_flex()
+        .gap_0p5()
+        .child(
+            div()
+                .text_xs()
+                .text_color(cx.theme().muted_foreground)
+                .child(title),
+        )
+        .children(
+            name_servers
+                .iter()
+                .map(|name_server| div().font_family("monospace").child(name_server.clone())),
+        )
+}
+
+/// Toggles for the record types of the zone and the quick filters, with how many records each
+/// matches; they combine with each other and the rest of the search query
+fn render_filter_chips(
+    type_counts: Vec<(DnsRecordType, usize)>,
+    quick_filter_counts: Vec<usize>,
+    query: &str,
+    cx: &mut Context<App>,
+) -> impl IntoElement {
+    let active_types = filter::query_types(query);
+    // A single type has nothing to narrow down to
+    let type_counts = if type_counts.len() > 1 {
+        type_counts
+    } else {
+        Vec::new()
+    };
+
+    h_flex()
+        .gap_1()
+        .flex_wrap()
+        .children(type_counts.into_iter().map(|(record_type, count)| {
+            let active = active_types.iter().any(|t| t == record_type.as_str());
+            Button::new(SharedString::from(format!("type-chip-{}", record_type)))
+                .xsmall()
+                .map(|this| if active { this.primary() } else { this.ghost() })
+                .label(format!("{} {}", record_type, count))
+                .on_click(cx.listener(move |this, _, window, cx| {
+                    this.toggle_type_filter(record_type, window, cx);
+                }))
+        }))
+        .child(div().w_2())
+        .children(
+            filter::QUICK_FILTERS
+                .iter()
+                .zip(quick_filter_counts)
+                .enumerate()
+                .map(|(index, (&(label, term), count))| {
+                    let active = filter::has_query_term(query, term);
+                    Button::new(("quick-filter", index))
+                        .xsmall()
+                        .map(|this| if active { this.primary() } else { this.ghost() })
+                        .label(format!("{} {}", label, count))
+                        .on_click(cx.listener(move |this, _, window, cx| {
+                            this.toggle_quick_filter(term, window, cx);
+                        }))
+                }),
+        )
+}
+
+/// Summary of the selected records with the actions taking all of them, like the selection bar
+/// of a file manager
+fn render_selection_footer(
+    selected: &[DnsRecord],
+    can_edit: bool,
+    cx: &mut Context<App>,
+) -> impl IntoElement {
+    let mut type_counts: Vec<(DnsRecordType, usize)> = Vec::new();
+    for record in selected {
+        match type_counts
+            .iter_mut()
+            .find(|(t, _)| *t == record.record_type)
+        {
+            Some((_, count)) => *count += 1,
+            None => type_counts.push((record.record_type, 1)),
+        }
+    }
+    let proxied_count = selected.iter().filter(|record| record.proxied).count();
+    let muted_foreground = cx.theme().muted_foreground;
+    let entity = cx.entity();
+
+    h_flex()
+        .gap_2()
+        .items_center()
+        .px_3()
+        .py_2()
+        .rounded_md()
+        .border_1()
+        .border_color(cx.theme().border)
+        .bg(cx.theme().secondary)
+        .child(
+            div()
+                .text_sm()
+                .font_weight(FontWeight::MEDIUM)
+                .child(format!("{} selected", selected.len())),
+        )
+        .child(
+            h_flex()
+                .flex_1()
+                .gap_2()
+                .text_xs()
+                .text_color(muted_foreground)
+                .children(
+                    type_counts.into_iter().map(|(record_type, count)| {
+                        div().child(format!("{} {}", record_type, count))
+                    }),
+                )
+                .when(proxied_count > 0, |this| {
+                    this.child(div().child(format!("{} proxied", proxied_count)))
+                }),
+        )
+        .child(
+            Button::new("clear-selection")
+                .ghost()
+                .small()
+                .label("Clear")
+                .on_click(cx.listener(|this, _, _, cx| {
+                    this.select_all_records(false, cx);
+                })),
+        )
+        .when(can_edit, |this| {
+            this.child(
+                Button::new("bulk-edit")
+                    .small()
+                    .label("Bulk Edit")
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.open_bulk_edit_dialog(window, cx);
+                    })),
+            )
+            .child(
+                Button::new("lower-ttls")
+                    .small()
+                    .label("Lower TTLs")
+                    .dropdown_menu({
+                        let entity = entity.clone();
+                        move |menu, _, _| {
+                            LOWERED_TTLS.iter().fold(menu, |menu, &ttl| {
+                                let entity = entity.clone();
+                                menu.item(PopupMenuItem::new(format!("To {}s", ttl)).on_click(
+                                    move |_, window, cx| {
+                                        entity.update(cx, |this, cx| {
+                                            this.lower_ttls(ttl, window, cx)
+                                        });
+                                    },
+                                ))
+                            })
+                        }
+                    }),
+            )
+        })
+        .child(
+            Button::new("export-selected")
+                .small()
+                .label("Export Selection")
+                .dropdown_menu(move |menu, _, _| {
+                    ExportFormat::ALL.iter().fold(menu, |menu, &format| {
+                        let entity = entity.clone();
+                        menu.item(PopupMenuItem::new(format.label()).on_click(
+                            move |_, window, cx| {
+                                entity.update(cx, |this, cx| {
+                                    this.export_records(format, ExportScope::Selected, window, cx)
+                                });
+                            },
+                        ))
+                    })
+                }),
+        )
+        .when(can_edit, |this| {
+            this.child(
+                Button::new("delete-selected")
+                    .danger()
+                    .small()
+                    .label("Delete Selected")
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.open_bulk_delete_dialog(window, cx);
+                    })),
+            )
+        })
+}
+
+/// Export formats for all records of the zone, and for the ones matching the search or
+/// selected if there are any
+fn render_export_menu(
+    app: &App,
+    filtered: bool,
+    has_selection: bool,
+    cx: &mut Context<App>,
+) -> impl IntoElement {
+    let entity = cx.entity();
+    let mut scopes = vec![(ExportScope::All, "All Records")];
+    if filtered {
+        scopes.push((ExportScope::Filtered, "Matching the Search"));
+    }
+    if has_selection {
+        scopes.push((ExportScope::Selected, "Selected Records"));
+    }
+
+    Button::new("export")
+        .ghost()
+        .small()
+        .icon(gpui_component::IconName::ArrowDown)
+        .tooltip("Export records")
+        .disabled(app.selected_zone_index.is_none())
+        .dropdown_menu(move |menu, _, _| {
+            let mut menu = menu;
+            for (index, &(scope, label)) in scopes.iter().enumerate() {
+                if index > 0 {
+                    menu = menu.separator();
+                }
+                menu = menu.label(label);
+                for format in ExportFormat::ALL {
+                    let entity = entity.clone();
+                    menu = menu.item(
+                        PopupMenuItem::new(format!("{}...", format.label())).on_click(
+                            move |_, window, cx| {
+                                entity.update(cx, |this, cx| {
+                                    this.export_records(format, scope, window, cx)
+                                });
+                            },
+                        ),
+                    );
+                }
+            }
+            menu
+        })
+}
+
+fn render_import_menu(app: &App, cx: &mut Context<App>) -> impl IntoElement {
+    let entity = cx.entity();
+
+    Button::new("import")
+        .ghost()
+        .small()
+        .icon(gpui_component::IconName::FolderOpen)
+        .tooltip("Import records")
+        .disabled(app.selected_zone_index.is_none() || !app.can(Capability::EditDns))
+        .dropdown_menu(move |menu, _, _| {
+            let zone_file_entity = entity.clone();
+            let csv_entity = entity.clone();
+            menu.item(
+                PopupMenuItem::new("BIND Zone File...").on_click(move |_, window, cx| {
+                    zone_file_entity.update(cx, |this, cx| this.import_zone_file(window, cx));
+                }),
+            )
+            .item(
+                PopupMenuItem::new("CSV File...").on_click(move |_, window, cx| {
+                    csv_entity.update(cx, |this, cx| this.import_csv(window, cx));
+                }),
+            )
+        })
+}
+
+fn render_search_presets(app: &App, cx: &mut Context<App>) -> impl IntoElement {
+    let saved_filters = app.saved_filters.clone();
+    let search_history = app.search_history.clone();
+    let entity = cx.entity();
+
+    Button::new("search-presets")
+        .ghost()
+        .icon(gpui_component::IconName::ChevronDown)
+        .tooltip("Saved filters and recent searches")
+        .disabled(saved_filters.is_empty() && search_history.is_empty())
+        .dropdown_menu(move |menu, _, _| {
+            let mut menu = menu;
+
+            if !saved_filters.is_empty() {
+                menu = menu.label("Saved Filters");
+                for filter in &saved_filters {
+                    let entity = entity.clone();
+                    let query = filter.query.clone();
+                    menu = menu.item(
+                        PopupMenuItem::new(filter.name.clone())
+                            .icon(gpui_component::IconName::Star)
+                            .on_click(move |_, window, cx| {
+                                entity.update(cx, |this, cx| {
+                                    this.apply_search(&query, window, cx);
+                                });
+                            }),
+                    );
+                }
+            }
+
+            if !search_history.is_empty() {
+                if !saved_filters.is_empty() {
+                    menu = menu.separator();
+                }
+                menu = menu.label("Recent Searches");
+                for query in &search_history {
+                    let entity = entity.clone();
+                    let query = query.clone();
+                    menu = menu.item(PopupMenuItem::new(query.clone()).on_click(
+                        move |_, window, cx| {
+                            entity.update(cx, |this, cx| {
+                                this.apply_search(&query, window, cx);
+                            });
+                        },
+                    ));
+                }
+            }
+
+            menu
+        })
+}
+
+/// Transformations and export formats contributed by approved plugins
+fn render_plugin_menu(app: &App, cx: &mut Context<App>) -> impl IntoElement {
+    let plugins: Vec<_> = app
+        .approved_plugins()
+        .into_iter()
+        .filter(|plugin| {
+            !plugin.manifest.transforms.is_empty() || !plugin.manifest.exports.is_empty()
+        })
+        .collect();
+    let entity = cx.entity();
+
+    Button::new("plugins")
+        .ghost()
+        .label("Plugins")
+        .disabled(plugins.is_empty() || app.selected_zone_index.is_none())
+        .dropdown_menu(move |menu, _, _| {
+            let mut menu = menu;
+
+            for (index, plugin) in plugins.iter().enumerate() {
+                if index > 0 {
+                    menu = menu.separator();
+                }
+                menu = menu.label(plugin.manifest.name.clone());
+                for (transform_index, transform) in plugin.manifest.transforms.iter().enumerate() {
+                    let entity = entity.clone();
+                    let plugin = plugin.clone();
+                    menu = menu.item(PopupMenuItem::new(transform.name.clone()).on_click(
+                        move |_, window, cx| {
+                            let plugin = plugin.clone();
+                            entity.update(cx, |this, cx| {
+                                this.run_plugin_transform(plugin, transform_index, window, cx);
+                            });
+                        },
+                    ));
+                }
+                for (export_index, export) in plugin.manifest.exports.iter().enumerate() {
+                    let entity = entity.clone();
+                    let plugin = plugin.clone();
+                    menu = menu.item(
+                        PopupMenuItem::new(format!("Export as {}", export.name)).on_click(
+                            move |_, window, cx| {
+                                let plugin = plugin.clone();
+                                entity.update(cx, |this, cx| {
+                                    this.run_plugin_export(plugin, export_index, window, cx);
+                                });
+                            },
+                        ),
+                    );
+                }
+            }
+
+            menu
+        })
 }
@@ -0,0 +1,103 @@
+use gpui::prelude::*;
+use gpui::{App as GpuiApp, Entity, FontWeight, IntoElement, Window, div, px};
+use gpui_component::{
+    ActiveTheme, Disableable, WindowExt,
+    button::{Button, ButtonVariants},
+    input::Input,
+    v_flex,
+};
+
+use crate::App;
+use crate::cloudflare::Zone;
+use crate::deletion_safety::SafetyCheck;
+
+/// Confirm deleting `zone` by typing its name, with the warnings of the safety check
+pub fn open_delete_zone_dialog(
+    app: Entity<App>,
+    zone: Zone,
+    check: SafetyCheck,
+    window: &mut Window,
+    cx: &mut GpuiApp,
+) {
+    let warnings = check.warnings();
+
+    window.open_dialog(cx, move |dialog, _, cx| {
+        let muted_foreground = cx.theme().muted_foreground;
+        let warning_color = cx.theme().warning;
+        let confirm_input = app.read(cx).deletion_confirm_input.clone();
+        let confirmed = confirm_input
+            .read(cx)
+            .value()
+            .trim()
+            .eq_ignore_ascii_case(&zone.name);
+
+        let app = app.clone();
+        let zone = zone.clone();
+        dialog
+            .title(format!("Delete {}?", zone.name))
+            .w(px(520.))
+            .child(
+                v_flex()
+                    .gap_2()
+                    .child(div().text_sm().text_color(muted_foreground).child(format!(
+                        "The zone and its {} DNS records, settings and rules will be removed \
+                         from Cloudflare. This can't be undone.",
+                        check.total
+                    )))
+                    .when(!warnings.is_empty(), |this| {
+                        this.child(
+                            v_flex()
+                                .gap_1()
+                                .px_3()
+                                .py_2()
+                                .rounded_md()
+                                .bg(warning_color.opacity(0.1))
+                                .text_sm()
+                                .text_color(warning_color)
+                                .child(
+                                    div()
+                                        .font_weight(FontWeight::MEDIUM)
+                                        .child("This zone may still be in use"),
+                                )
+                                .children(
+                                    warnings
+                                        .iter()
+                                        .map(|warning| div().child(format!("• {}", warning))),
+                                ),
+                        )
+                    })
+                    .children(check.errors.iter().map(|error| {
+                        div()
+                            .text_xs()
+                            .text_color(muted_foreground)
+                            .child(error.clone())
+                    }))
+                    .child(
+                        div()
+                            .text_sm()
+                            .child(format!("Type {} to confirm", zone.name)),
+                    )
+                    .child(Input::new(&confirm_input)),
+            )
+            .footer(move |_, _, _, _| {
+                vec![
+                    Button::new("delete-zone-cancel")
+                        .label("Cancel")
+                        .on_click(|_, window, cx| window.close_dialog(cx)),
+                    Button::new("delete-zone-confirm")
+                        .danger()
+                        .label("Delete Zone")
+                        .disabled(!confirmed)
+                        .on_click({
+                            let app = app.clone();
+                            let zone = zone.clone();
+                            move |_, window, cx| {
+                                window.close_dialog(cx);
+                                let zone = zone.clone();
+                                app.update(cx, |this, cx| this.delete_zone(zone, window, cx));
+                            }
+                        }),
+                ]
+            })
+    });
+}
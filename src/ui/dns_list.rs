@@ -1,33 +1,51 @@
+use std::collections::{HashMap, HashSet};
+use std::net::IpAddr;
 use std::rc::Rc;
 
+use chrono::Utc;
 use gpui::prelude::*;
-use gpui::{Context, FontWeight, IntoElement, Pixels, SharedString, Size, Window, div, px, size};
-use gpui_component::{ActiveTheme, h_flex, orange_500, scroll::Scrollbar, v_flex, v_virtual_list};
+use gpui::{
+    ClickEvent, ClipboardItem, Context, Entity, FontWeight, IntoElement, Pixels, SharedString,
+    Size, Window, div, px, rems, size,
+};
+use gpui_component::{
+    ActiveTheme, Icon, IconName, Sizable, WindowExt,
+    checkbox::Checkbox,
+    h_flex,
+    menu::{ContextMenuExt, PopupMenu, PopupMenuItem},
+    notification::Notification,
+    orange_500,
+    scroll::Scrollbar,
+    tooltip::Tooltip,
+    v_flex, v_virtual_list,
+};
 
-use crate::App;
+use super::{render_ip_address, render_third_party_badge, render_timestamp};
+use crate::cloudflare::{DnsRecord, DnsRecordType, Zone};
+use crate::dashboard_links::DashboardPage;
+use crate::token_scopes::Capability;
+use crate::zone_settings::format_remaining;
+use crate::{App, provenance::Provenance, timestamps};
 
-const ITEM_HEIGHT: Pixels = px(56.);
+/// Row height relative to the theme's font size, so rows grow with the text size setting
+const ITEM_HEIGHT_REMS: f32 = 3.5;
 
 pub fn render_dns_list(
     app: &mut App,
     _window: &mut Window,
     cx: &mut Context<App>,
 ) -> impl IntoElement {
-    let records = app.dns_records.clone();
-    let records_count = records.len();
-    let editing_id = app.editing_record.as_ref().map(|r| r.id.clone());
+    let records = app.filtered_records(cx);
+    let is_filtered = records.len() != app.dns_records(cx).len();
+    let rows = list_rows(&records, app.group_by_name, &app.expanded_names);
     let scroll_handle = &app.dns_list_scroll_handle;
 
-    // Pre-calculate item sizes for virtual list
-    let item_sizes: Rc<Vec<Size<Pixels>>> = Rc::new(
-        (0..records_count)
-            .map(|_| size(px(0.), ITEM_HEIGHT))
-            .collect(),
-    );
+    // Pre-calculate item sizes for virtual list, which needs them in pixels
+    let item_height = (cx.theme().font_size * ITEM_HEIGHT_REMS).round();
+    let item_sizes: Rc<Vec<Size<Pixels>>> =
+        Rc::new((0..rows.len()).map(|_| size(px(0.), item_height)).collect());
 
     let border_color = cx.theme().border;
-    let accent_color = cx.theme().accent;
-    let primary_color = cx.theme().primary;
     let muted_foreground = cx.theme().muted_foreground;
 
     div()
@@ -45,7 +63,11 @@ pub fn render_dns_list(
                         .items_center()
                         .justify_center()
                         .text_color(muted_foreground)
-                        .child("No DNS records found"),
+                        .child(if is_filtered {
+                            "No DNS records match the search"
+                        } else {
+                            "No DNS records found"
+                        }),
                 )
             } else {
                 this.child(
@@ -59,123 +81,29 @@ pub fn render_dns_list(
                                 item_sizes,
                                 move |app, visible_range, _window, cx| {
                                     visible_range
-                                        .map(|ix| {
-                                            let record = &app.dns_records[ix];
-                                            let record_clone = record.clone();
-                                            let is_selected =
-                                                editing_id.as_ref() == Some(&record.id);
-
-                                            div()
-                                                .id(SharedString::from(record.id.clone()))
-                                                .w_full()
-                                                .h(ITEM_HEIGHT)
-                                                .px_3()
-                                                .flex()
-                                                .items_center()
-                                                .border_b_1()
-                                                .border_color(border_color)
-                                                .cursor_pointer()
-                                                .map(|this| {
-                                                    if is_selected {
-                                                        this.bg(accent_color)
-                                                    } else {
-                                                        this
-                                                    }
-                                                })
-                                                .hover(|this| this.bg(accent_color.opacity(0.5)))
-                                                .on_click(cx.listener(
-                                                    move |this, _, window, cx| {
-                                                        this.edit_record(
-                                                            record_clone.clone(),
-                                                            window,
-                                                            cx,
-                                                        );
-                                                    },
-                                                ))
-                                                .child(
-                                                    h_flex()
-                                                        .w_full()
-                                                        .items_center()
-                                                        .gap_3()
-                                                        .child(
-                                                            div()
-                                                                .w(px(50.))
-                                                                .px_2()
-                                                                .py_1()
-                                                                .rounded_sm()
-                                                                .bg(primary_color.opacity(0.1))
-                                                                .text_xs()
-                                                                .font_weight(FontWeight::MEDIUM)
-                                                                .text_color(primary_color)
-                                                                .child(record.record_type.as_str()),
-                                                        )
-                                                        .child(
-                                                            v_flex()
-                                                                .flex_1()
-                                                                .overflow_hidden()
-                                                                .child(
-                                                                    div()
-                                                                        .text_sm()
-                                                                        .font_weight(
-                                                                            FontWeight::MEDIUM,
-                                                                        )
-                                                                        .truncate()
-                                                                        .child(record.name.clone()),
-                                                                )
-                                                                .child(
-                                                                    div()
-                                                                        .text_xs()
-                                                                        .text_color(
-                                                                            muted_foreground,
-                                                                        )
-                                                                        .truncate()
-                                                                        .child(
-                                                                            record.content.clone(),
-                                                                        ),
-                                                                ),
-                                                        )
-                                                        .child(
-                                                            h_flex()
-                                                                .gap_2()
-                                                                .items_center()
-                                                                .map(|this| {
-                                                                    if record.proxied {
-                                                                        this.child(
-                                                                            div()
-                                                                                .px_1()
-                                                                                .py_px()
-                                                                                .rounded_sm()
-                                                                                .bg(orange_500()
-                                                                                    .opacity(0.2))
-                                                                                .text_xs()
-                                                                                .text_color(
-                                                                                    orange_500(),
-                                                                                )
-                                                                                .child("Proxied"),
-                                                                        )
-                                                                    } else {
-                                                                        this
-                                                                    }
-                                                                })
-                                                                .child(
-                                                                    div()
-                                                                        .text_xs()
-                                                                        .text_color(
-                                                                            muted_foreground,
-                                                                        )
-                                                                        .child(
-                                                                            if record.ttl == 1 {
-                                                                                "Auto".to_string()
-                                                                            } else {
-                                                                                format!(
-                                                                                    "{}s",
-                                                                                    record.ttl
-                                                                                )
-                                                                            },
-                                                                        ),
-                                                                ),
-                                                        ),
+                                        .map(|ix| match &rows[ix] {
+                                            ListRow::Record { index, nested } => render_record_row(
+                                                app,
+                                                &records[*index],
+                                                ix,
+                                                *nested,
+                                                item_height,
+                                                cx,
+                                            )
+                                            .into_any_element(),
+                                            ListRow::Group { name, indices } => {
+                                                let group: Vec<&DnsRecord> =
+                                                    indices.iter().map(|&i| &records[i]).collect();
+                                                render_group_row(
+                                                    app,
+                                                    name,
+                                                    &group,
+                                                    ix,
+                                                    item_height,
+                                                    cx,
                                                 )
+                                                .into_any_element()
+                                            }
                                         })
                                         .collect()
                                 },
@@ -197,3 +125,530 @@ pub fn render_dns_list(
             }
         })
 }
+
+/// A row of the list: a record, or in the grouped view a name with several records
+enum ListRow {
+    Record {
+        index: usize,
+        /// Shown under its name's row in the grouped view
+        nested: bool,
+    },
+    Group {
+        name: String,
+        indices: Vec<usize>,
+    },
+}
+
+/// Rows for `records`; grouped, names with several records get a row of their own, followed by
+/// the records if the name is expanded
+fn list_rows(
+    records: &[DnsRecord],
+    group_by_name: bool,
+    expanded: &HashSet<String>,
+) -> Vec<ListRow> {
+    if !group_by_name {
+        return (0..records.len())
+            .map(|index| ListRow::Record {
+                index,
+                nested: false,
+            })
+            .collect();
+    }
+
+    // Names in the order they first appear in
+    let mut groups: Vec<(&str, Vec<usize>)> = Vec::new();
+    let mut positions: HashMap<&str, usize> = HashMap::new();
+    for (index, record) in records.iter().enumerate() {
+        match positions.get(record.name.as_str()) {
+            Some(&position) => groups[position].1.push(index),
+            None => {
+                positions.insert(record.name.as_str(), groups.len());
+                groups.push((record.name.as_str(), vec![index]));
+            }
+        }
+    }
+
+    let mut rows = Vec::new();
+    for (name, indices) in groups {
+        if let [index] = indices[..] {
+            rows.push(ListRow::Record {
+                index,
+                nested: false,
+            });
+            continue;
+        }
+        let nested = expanded.contains(name);
+        rows.push(ListRow::Group {
+            name: name.to_string(),
+            indices: indices.clone(),
+        });
+        if nested {
+            rows.extend(
+                indices
+                    .into_iter()
+                    .map(|index| ListRow::Record { index, nested }),
+            );
+        }
+    }
+    rows
+}
+
+/// Row of a name with several records: the types it has, and whether any of them is proxied
+fn render_group_row(
+    app: &App,
+    name: &str,
+    records: &[&DnsRecord],
+    ix: usize,
+    item_height: Pixels,
+    cx: &mut Context<App>,
+) -> impl IntoElement {
+    let border_color = cx.theme().border;
+    let primary_color = cx.theme().primary;
+    let muted_foreground = cx.theme().muted_foreground;
+    let accent_color = cx.theme().accent;
+    let expanded = app.expanded_names.contains(name);
+    let record_ids: Vec<String> = records.iter().map(|record| record.id.clone()).collect();
+    let all_checked = record_ids
+        .iter()
+        .all(|id| app.selected_records.contains(id));
+    let any_proxied = records.iter().any(|record| record.proxied);
+
+    // Types in the order of the type picker, with how many records of each the name has
+    let type_counts: Vec<(DnsRecordType, usize)> = DnsRecordType::all()
+        .iter()
+        .map(|&record_type| {
+            let count = records
+                .iter()
+                .filter(|record| record.record_type == record_type)
+                .count();
+            (record_type, count)
+        })
+        .filter(|(_, count)| *count > 0)
+        .collect();
+
+    let toggle_name = name.to_string();
+    div()
+        .id(("record-group", ix))
+        .w_full()
+        .h(item_height)
+        .px_3()
+        .flex()
+        .items_center()
+        .border_b_1()
+        .border_color(border_color)
+        .cursor_pointer()
+        .hover(|this| this.bg(accent_color.opacity(0.5)))
+        .on_click(cx.listener(move |this, _, _, cx| {
+            this.toggle_name_expanded(&toggle_name, cx);
+        }))
+        .child(
+            h_flex()
+                .size_full()
+                .items_center()
+                .gap_3()
+                .child(
+                    Checkbox::new(("select-record-group", ix))
+                        .checked(all_checked)
+                        .on_click(cx.listener(move |this, selected, _, cx| {
+                            // Don't expand the name
+                            cx.stop_propagation();
+                            this.set_records_selected(&record_ids, *selected, cx);
+                        })),
+                )
+                .child(
+                    Icon::new(if expanded {
+                        IconName::ChevronDown
+                    } else {
+                        IconName::ChevronRight
+                    })
+                    .small()
+                    .text_color(muted_foreground),
+                )
+                .child(
+                    v_flex()
+                        .flex_1()
+                        .overflow_hidden()
+                        .child(
+                            div()
+                                .text_sm()
+                                .font_weight(FontWeight::MEDIUM)
+                                .truncate()
+                                .child(name.to_string()),
+                        )
+                        .child(
+                            div()
+                                .text_xs()
+                                .text_color(muted_foreground)
+                                .child(format!("{} records", records.len())),
+                        ),
+                )
+                .children(type_counts.into_iter().map(|(record_type, count)| {
+                    div()
+                        .px_2()
+                        .py_1()
+                        .rounded_sm()
+                        .bg(primary_color.opacity(0.1))
+                        .text_xs()
+                        .font_weight(FontWeight::MEDIUM)
+                        .text_color(primary_color)
+                        .child(if count > 1 {
+                            format!("{} ×{}", record_type, count)
+                        } else {
+                            record_type.to_string()
+                        })
+                }))
+                .when(any_proxied, |this| {
+                    this.child(
+                        div()
+                            .px_1()
+                            .py_px()
+                            .rounded_sm()
+                            .bg(orange_500().opacity(0.2))
+                            .text_xs()
+                            .text_color(orange_500())
+                            .child("Proxied"),
+                    )
+                }),
+        )
+}
+
+fn render_record_row(
+    app: &App,
+    record: &DnsRecord,
+    ix: usize,
+    nested: bool,
+    item_height: Pixels,
+    cx: &mut Context<App>,
+) -> impl IntoElement {
+    let time_zone_display = app.time_zone_display;
+    let has_edit_conflict = app.edit_conflict.is_some();
+    let border_color = cx.theme().border;
+    let accent_color = cx.theme().accent;
+    let primary_color = cx.theme().primary;
+    let muted_foreground = cx.theme().muted_foreground;
+    let foreground = cx.theme().foreground;
+    let warning_color = cx.theme().warning;
+
+    let record_clone = record.clone();
+    let is_selected = app
+        .editing_record
+        .as_ref()
+        .is_some_and(|editing| editing.id == record.id);
+    let is_watched = app.watched_records.contains(&record.id);
+    let is_protected = app.protected_records.contains_key(&record.id);
+    let is_checked = app.selected_records.contains(&record.id);
+    let provenance = Provenance::detect(record);
+    let old_answer = app.old_answers.get(&record.id).cloned();
+    let record_id = record.id.clone();
+    let menu_record = record.clone();
+    let menu_app = cx.entity();
+    let third_party = app.cname_third_party(record);
+    let cname_chain = app
+        .cname_targets
+        .get(&record.content)
+        .map(|target| target.chain.join(" → "))
+        .unwrap_or_else(|| record.content.clone());
+    let ip = matches!(record.record_type, DnsRecordType::A | DnsRecordType::AAAA)
+        .then(|| record.content.parse::<IpAddr>().ok())
+        .flatten();
+    let priority = record
+        .record_type
+        .requires_priority()
+        .then_some(record.priority)
+        .flatten();
+
+    div()
+        .id(SharedString::from(record.id.clone()))
+        .w_full()
+        .h(item_height)
+        .px_3()
+        .when(nested, |this| this.pl_8())
+        .flex()
+        .items_center()
+        .border_b_1()
+        .border_color(border_color)
+        .cursor_pointer()
+        .map(|this| {
+            if is_selected {
+                this.bg(accent_color)
+            } else if is_checked {
+                this.bg(accent_color.opacity(0.3))
+            } else {
+                this
+            }
+        })
+        .hover(|this| this.bg(accent_color.opacity(0.5)))
+        .on_click(cx.listener(move |this, event: &ClickEvent, window, cx| {
+            // Cmd/Ctrl-click adds to the selection
+            if event.modifiers().secondary() {
+                this.toggle_record_selection(&record_clone.id, cx);
+            } else {
+                this.edit_record(record_clone.clone(), window, cx);
+            }
+        }))
+        .child(
+            h_flex()
+                .size_full()
+                .items_center()
+                .gap_3()
+                .context_menu(move |menu, _, cx| {
+                    let zone = menu_app.read(cx).selected_zone(cx);
+                    let can_edit = menu_app.read(cx).can(Capability::EditDns);
+                    record_context_menu(menu, &menu_record, zone, can_edit, menu_app.clone())
+                })
+                .child(
+                    Checkbox::new(("select-record", ix))
+                        .checked(is_checked)
+                        .on_click(cx.listener(move |this, _, _, cx| {
+                            // Don't open the record
+                            cx.stop_propagation();
+                            this.toggle_record_selection(&record_id, cx);
+                        })),
+                )
+                .child(
+                    div()
+                        .w(rems(3.125))
+                        .px_2()
+                        .py_1()
+                        .rounded_sm()
+                        .bg(primary_color.opacity(0.1))
+                        .text_xs()
+                        .font_weight(FontWeight::MEDIUM)
+                        .text_color(primary_color)
+                        .child(record.record_type.as_str()),
+                )
+                .child(
+                    v_flex()
+                        .flex_1()
+                        .overflow_hidden()
+                        .child(
+                            div()
+                                .text_sm()
+                                .font_weight(FontWeight::MEDIUM)
+                                .truncate()
+                                .child(record.name.clone()),
+                        )
+                        .child(
+                            h_flex()
+                                .gap_1()
+                                .overflow_hidden()
+                                .text_xs()
+                                .text_color(muted_foreground)
+                                .when_some(priority, |this, priority| {
+                                    this.child(
+                                        div()
+                                            .flex_none()
+                                            .font_weight(FontWeight::MEDIUM)
+                                            .text_color(foreground)
+                                            .child(priority.to_string()),
+                                    )
+                                })
+                                .child(div().truncate().map(|this| match ip {
+                                    Some(ip) => {
+                                        this.child(render_ip_address(("record-ip", ix), ip, cx))
+                                    }
+                                    None => this.child(record.content.clone()),
+                                })),
+                        ),
+                )
+                .child(
+                    h_flex()
+                        .gap_2()
+                        .items_center()
+                        .map(|this| {
+                            if is_selected && has_edit_conflict {
+                                this.child(
+                                    div()
+                                        .px_1()
+                                        .py_px()
+                                        .rounded_sm()
+                                        .bg(warning_color.opacity(0.2))
+                                        .text_xs()
+                                        .text_color(warning_color)
+                                        .child("Changed remotely"),
+                                )
+                            } else {
+                                this
+                            }
+                        })
+                        .when(record.locked, |this| {
+                            this.child(
+                                div()
+                                    .px_1()
+                                    .py_px()
+                                    .rounded_sm()
+                                    .bg(muted_foreground.opacity(0.15))
+                                    .text_xs()
+                                    .text_color(muted_foreground)
+                                    .child("Locked"),
+                            )
+                        })
+                        .when(is_protected, |this| {
+                            this.child(
+                                div()
+                                    .px_1()
+                                    .py_px()
+                                    .rounded_sm()
+                                    .bg(muted_foreground.opacity(0.15))
+                                    .text_xs()
+                                    .text_color(muted_foreground)
+                                    .child("Protected"),
+                            )
+                        })
+                        .when_some(third_party, |this, third_party| {
+                            this.child(render_third_party_badge(
+                                ("record-third-party", ix),
+                                third_party,
+                                cname_chain,
+                                cx,
+                            ))
+                        })
+                        .when_some(provenance, |this, provenance| {
+                            this.child(
+                                div()
+                                    .px_1()
+                                    .py_px()
+                                    .rounded_sm()
+                                    .bg(muted_foreground.opacity(0.15))
+                                    .text_xs()
+                                    .text_color(muted_foreground)
+                                    .child(provenance.label()),
+                            )
+                        })
+                        .map(|this| {
+                            if is_watched {
+                                this.child(
+                                    Icon::new(IconName::Eye)
+                                        .small()
+                                        .text_color(muted_foreground),
+                                )
+                            } else {
+                                this
+                            }
+                        })
+                        .map(|this| {
+                            if record.proxied {
+                                this.child(
+                                    div()
+                                        .px_1()
+                                        .py_px()
+                                        .rounded_sm()
+                                        .bg(orange_500().opacity(0.2))
+                                        .text_xs()
+                                        .text_color(orange_500())
+                                        .child("Proxied"),
+                                )
+                            } else {
+                                this
+                            }
+                        })
+                        .when_some(old_answer, |this, old_answer| {
+                            let expires_at = time_zone_display.format(old_answer.expires_at);
+                            this.child(
+                                div()
+                                    .id(("old-answer", ix))
+                                    .px_1()
+                                    .py_px()
+                                    .rounded_sm()
+                                    .bg(warning_color.opacity(0.15))
+                                    .text_xs()
+                                    .text_color(warning_color)
+                                    .child(format!(
+                                        "Old answer cached {}",
+                                        format_remaining(old_answer.expires_at - Utc::now())
+                                    ))
+                                    .tooltip(move |window, cx| {
+                                        Tooltip::new(format!(
+                                            "Resolvers may serve the record's previous answer \
+                                             until {}, so keep its old target until then",
+                                            expires_at
+                                        ))
+                                        .build(window, cx)
+                                    }),
+                            )
+                        })
+                        .children(
+                            record
+                                .modified_on
+                                .as_deref()
+                                .and_then(timestamps::parse)
+                                .map(|modified_on| {
+                                    render_timestamp(
+                                        ("record-modified-on", ix),
+                                        "",
+                                        modified_on,
+                                        time_zone_display,
+                                        cx,
+                                    )
+                                }),
+                        )
+                        .child(div().text_xs().text_color(muted_foreground).child(
+                            if record.ttl == 1 {
+                                "Auto".to_string()
+                            } else {
+                                format!("{}s", record.ttl)
+                            },
+                        )),
+                ),
+        )
+}
+
+/// Right-click menu of a row, for copying since text in the list can't be selected
+fn record_context_menu(
+    menu: PopupMenu,
+    record: &DnsRecord,
+    zone: Option<Zone>,
+    can_edit: bool,
+    app: Entity<App>,
+) -> PopupMenu {
+    let content = record.content.clone();
+    let name = record.name.clone();
+    let search_name = record.name.clone();
+    let json = serde_json::to_string_pretty(record).unwrap_or_default();
+    let duplicate = record.clone();
+
+    let menu = menu
+        .item(
+            PopupMenuItem::new("Copy Content").on_click(move |_, _, cx| {
+                cx.write_to_clipboard(ClipboardItem::new_string(content.clone()));
+            }),
+        )
+        .item(PopupMenuItem::new("Copy Name").on_click(move |_, _, cx| {
+            cx.write_to_clipboard(ClipboardItem::new_string(name.clone()));
+        }))
+        .item(
+            PopupMenuItem::new("Copy as JSON").on_click(move |_, _, cx| {
+                cx.write_to_clipboard(ClipboardItem::new_string(json.clone()));
+            }),
+        );
+    let menu = if can_edit {
+        menu.separator().item(
+            PopupMenuItem::new("Duplicate").on_click(move |_, window, cx| {
+                app.update(cx, |this, cx| {
+                    this.duplicate_record(&duplicate, window, cx);
+                });
+            }),
+        )
+    } else {
+        menu
+    };
+
+    let Some(zone) = zone else {
+        return menu;
+    };
+    let url = DashboardPage::DnsRecords.url(&zone);
+    menu.item(
+        PopupMenuItem::new("Open in Cloudflare Dashboard").on_click(move |_, window, cx| {
+            // The dashboard can't link to a single record, so its name is ready to search for
+            cx.write_to_clipboard(ClipboardItem::new_string(search_name.clone()));
+            cx.open_url(&url);
+            window.push_notification(
+                Notification::info(format!(
+                    "Copied {} to search for it on the DNS records page",
+                    search_name
+                )),
+                cx,
+            );
+        }),
+    )
+}
@@ -0,0 +1,324 @@
+use chrono::Utc;
+use gpui::prelude::*;
+use gpui::{Context, FontWeight, Hsla, IntoElement, Window, div, px};
+use gpui_component::{
+    ActiveTheme, Sizable,
+    button::{Button, ButtonVariants},
+    h_flex,
+    scroll::ScrollableElement,
+    switch::Switch,
+    tag::Tag,
+    v_flex,
+};
+
+use super::render_status_bar;
+use crate::certificates::{
+    EXPIRING_SOON_DAYS, PackState, certificate_authority_label, pack_type_label, parse_expiry,
+    status_label,
+};
+use crate::cloudflare::{Certificate, CertificatePack};
+use crate::{App, Page};
+
+/// Edge certificate packs of the selected zone with their certificates, and the Universal SSL
+/// switch
+pub fn render_edge_certificates(
+    app: &mut App,
+    _window: &mut Window,
+    cx: &mut Context<App>,
+) -> impl IntoElement {
+    let zone_name = app.selected_zone(cx).map(|zone| zone.name);
+    let error = app.error.clone();
+    let muted_foreground = cx.theme().muted_foreground;
+    let border_color = cx.theme().border;
+    let is_loading = app.loading;
+    let read_only = app.read_only();
+
+    v_flex()
+        .size_full()
+        .child(
+            // Header
+            h_flex()
+                .w_full()
+                .px_4()
+                .py_3()
+                .border_b_1()
+                .border_color(border_color)
+                .items_center()
+                .gap_3()
+                .child(
+                    Button::new("back")
+                        .ghost()
+                        .icon(gpui_component::IconName::ArrowLeft)
+                        .on_click(cx.listener(|this, _, _, cx| {
+                            this.page = Page::Dashboard;
+                            this.error = None;
+                            cx.notify();
+                        })),
+                )
+                .child(
+                    div()
+                        .text_lg()
+                        .font_weight(FontWeight::SEMIBOLD)
+                        .child("Edge Certificates"),
+                )
+                .when_some(zone_name, |this, zone_name| {
+                    this.child(
+                        div()
+                            .text_sm()
+                            .text_color(muted_foreground)
+                            .child(zone_name),
+                    )
+                })
+                .child(div().flex_1())
+                .child(
+                    Button::new("refresh-edge-certificates")
+                        .ghost()
+                        .icon(gpui_component::IconName::Redo)
+                        .tooltip("Refresh")
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            this.load_edge_certificates(window, cx);
+                        })),
+                ),
+        )
+        .child(
+            v_flex()
+                .flex_1()
+                .p_6()
+                .gap_3()
+                .overflow_y_scrollbar()
+                .when_some(error, |this, error| {
+                    this.child(div().text_sm().text_color(cx.theme().danger).child(error))
+                })
+                .when_some(app.universal_ssl, |this, enabled| {
+                    this.child(
+                        h_flex()
+                            .gap_3()
+                            .items_center()
+                            .pb_3()
+                            .border_b_1()
+                            .border_color(border_color)
+                            .child(
+                                v_flex()
+                                    .flex_1()
+                                    .gap_1()
+                                    .child(
+                                        div()
+                                            .font_weight(FontWeight::MEDIUM)
+                                            .child("Universal SSL"),
+                                    )
+                                    .child(div().text_sm().text_color(muted_foreground).child(
+                                        "A free certificate for the zone's apex and first-level \
+                                         subdomains, renewed by Cloudflare",
+                                    )),
+                            )
+                            .child(
+                                Switch::new("universal-ssl")
+                                    .checked(enabled)
+                                    .disabled(read_only)
+                                    .on_click(cx.listener(|this, on: &bool, window, cx| {
+                                        this.toggle_universal_ssl(*on, window, cx);
+                                    })),
+                            ),
+                    )
+                })
+                .when(app.certificate_packs.is_empty(), |this| {
+                    this.child(
+                        div()
+                            .text_sm()
+                            .text_color(muted_foreground)
+                            .child(if is_loading {
+                                "Loading certificates…"
+                            } else {
+                                "This zone has no edge certificates."
+                            }),
+                    )
+                })
+                .children(
+                    app.certificate_packs
+                        .iter()
+                        .enumerate()
+                        .map(|(index, pack)| render_pack(index, pack, cx)),
+                ),
+        )
+        .child(render_status_bar(app, cx))
+}
+
+fn render_pack(index: usize, pack: &CertificatePack, cx: &Context<App>) -> impl IntoElement {
+    let muted_foreground = cx.theme().muted_foreground;
+    let state = PackState::of(pack);
+    let details = [
+        pack.certificate_authority
+            .as_deref()
+            .map(|authority| certificate_authority_label(authority).to_string()),
+        pack.validity_days
+            .map(|days| format!("valid {} days", days)),
+        pack.validation_method
+            .as_deref()
+            .map(|method| format!("{} validation", method.to_uppercase())),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>();
+
+    v_flex()
+        .gap_2()
+        .p_3()
+        .rounded_md()
+        .border_1()
+        .border_color(cx.theme().border)
+        .child(
+            h_flex()
+                .gap_3()
+                .items_center()
+                .child(div().size(px(8.)).rounded_full().bg(state_color(state, cx)))
+                .child(
+                    div()
+                        .font_weight(FontWeight::MEDIUM)
+                        .child(pack_type_label(&pack.pack_type)),
+                )
+                .child(
+                    div()
+                        .text_sm()
+                        .text_color(muted_foreground)
+                        .child(details.join(" · ")),
+                )
+                .child(div().flex_1())
+                .child(
+                    div()
+                        .text_sm()
+                        .text_color(state_color(state, cx))
+                        .child(status_label(&pack.status)),
+                )
+                .when(state == PackState::Pending, |this| {
+                    let pack_id = pack.id.clone();
+                    this.child(
+                        Button::new(("check-certificate-pack", index))
+                            .ghost()
+                            .small()
+                            .label("Check Again")
+                            .on_click(cx.listener(move |this, _, window, cx| {
+                                this.refresh_certificate_pack(pack_id.clone(), window, cx);
+                            })),
+                    )
+                }),
+        )
+        .child(
+            h_flex().gap_1().flex_wrap().children(
+                pack.hosts
+                    .iter()
+                    .map(|host| Tag::secondary().small().child(host.clone())),
+            ),
+        )
+        .children(
+            pack.certificates
+                .iter()
+                .map(|certificate| render_certificate(certificate, cx)),
+        )
+        .when(
+            !pack.validation_records.is_empty() && state == PackState::Pending,
+            |this| {
+                this.child(
+                    v_flex()
+                        .gap_1()
+                        .text_sm()
+                        .child(
+                            div()
+                                .text_color(muted_foreground)
+                                .child("Validation waits for these to be served:"),
+                        )
+                        .children(pack.validation_records.iter().map(|record| {
+                            let (name, value) = match (&record.txt_name, &record.http_url) {
+                                (Some(name), _) => (
+                                    format!("TXT {}", name),
+                                    record.txt_value.clone().unwrap_or_default(),
+                                ),
+                                (None, Some(url)) => {
+                                    (url.clone(), record.http_body.clone().unwrap_or_default())
+                                }
+                                (None, None) => (String::new(), String::new()),
+                            };
+                            h_flex()
+                                .gap_2()
+                                .child(div().child(name))
+                                .child(
+                                    div()
+                                        .flex_1()
+                                        .overflow_hidden()
+                                        .font_family("monospace")
+                                        .text_color(muted_foreground)
+                                        .child(value),
+                                )
+                                .when_some(record.status.as_deref(), |this, status| {
+                                    this.child(
+                                        div()
+                                            .text_color(muted_foreground)
+                                            .child(status_label(status)),
+                                    )
+                                })
+                        })),
+                )
+            },
+        )
+        .children(pack.validation_errors.iter().map(|error| {
+            div()
+                .text_sm()
+                .text_color(cx.theme().danger)
+                .child(error.message.clone())
+        }))
+}
+
+fn render_certificate(certificate: &Certificate, cx: &Context<App>) -> impl IntoElement {
+    let muted_foreground = cx.theme().muted_foreground;
+    let expiry = certificate.expires_on.as_deref().and_then(parse_expiry);
+    let (expiry, expiry_color) = match expiry {
+        Some(expires_on) => {
+            let days = (expires_on - Utc::now()).num_days();
+            let date = expires_on.format("%Y-%m-%d");
+            if days < 0 {
+                (format!("Expired on {}", date), cx.theme().danger)
+            } else if days <= EXPIRING_SOON_DAYS {
+                (
+                    format!("Expires {} (in {} days)", date, days),
+                    cx.theme().warning,
+                )
+            } else {
+                (format!("Expires {}", date), muted_foreground)
+            }
+        }
+        None => ("Expiry unknown".to_string(), muted_foreground),
+    };
+
+    h_flex()
+        .gap_3()
+        .text_sm()
+        .child(
+            div().w(px(160.)).child(
+                certificate
+                    .signature
+                    .clone()
+                    .unwrap_or_else(|| "Certificate".to_string()),
+            ),
+        )
+        .child(
+            div()
+                .flex_1()
+                .overflow_hidden()
+                .text_color(muted_foreground)
+                .child(certificate.issuer.clone().unwrap_or_default()),
+        )
+        .child(
+            div()
+                .text_color(muted_foreground)
+                .child(status_label(&certificate.status)),
+        )
+        .child(div().text_color(expiry_color).child(expiry))
+}
+
+fn state_color(state: PackState, cx: &Context<App>) -> Hsla {
+    match state {
+        PackState::Active => cx.theme().success,
+        PackState::Pending => cx.theme().warning,
+        PackState::Failed => cx.theme().danger,
+        PackState::Inactive => cx.theme().muted_foreground,
+    }
+}
@@ -0,0 +1,270 @@
+use gpui::prelude::*;
+use gpui::{Context, IntoElement, SharedString, div, px};
+use gpui_component::{
+    ActiveTheme, Sizable,
+    button::{Button, ButtonVariants},
+    h_flex,
+    input::Input,
+    menu::{DropdownMenu, PopupMenuItem},
+    v_flex,
+};
+
+use crate::App;
+use crate::expression_editor::{ConditionRow, ExpressionEditor, ExpressionMode};
+use crate::rule_expression::{FIELDS, Operator, SyntaxError};
+
+/// Expression of a ruleset rule, as rows of conditions in groups or as text
+///
+/// `editor_of` picks the edited expression out of the app, for the rows' buttons to change it.
+pub fn render_expression_editor(
+    id: &'static str,
+    editor: &ExpressionEditor,
+    editor_of: fn(&mut App) -> &mut ExpressionEditor,
+    cx: &mut Context<App>,
+) -> impl IntoElement {
+    let muted_foreground = cx.theme().muted_foreground;
+
+    let mode_button = |mode: ExpressionMode, label: &'static str, cx: &mut Context<App>| {
+        Button::new(element_id(id, label))
+            .xsmall()
+            .map(|this| {
+                if editor.mode == mode {
+                    this.primary()
+                } else {
+                    this.ghost()
+                }
+            })
+            .label(label)
+            .on_click(cx.listener(move |this, _, window, cx| {
+                if let Err(e) = editor_of(this).set_mode(mode, window, cx) {
+                    this.error = Some(e);
+                }
+                cx.notify();
+            }))
+    };
+
+    v_flex()
+        .gap_2()
+        .child(
+            h_flex()
+                .gap_1()
+                .child(mode_button(ExpressionMode::Builder, "Builder", cx))
+                .child(mode_button(ExpressionMode::Text, "Expression", cx)),
+        )
+        .map(|this| match editor.mode {
+            ExpressionMode::Builder => this.child(render_builder(id, editor, editor_of, cx)),
+            ExpressionMode::Text => {
+                let text = editor.text_input.read(cx).value();
+                let hint = match editor.syntax_error(cx) {
+                    Some(error) => render_syntax_error(&error, &text, cx).into_any_element(),
+                    None => div()
+                        .text_xs()
+                        .text_color(muted_foreground)
+                        .child("Requests the rule applies to, in Cloudflare's Rules language")
+                        .into_any_element(),
+                };
+                this.child(Input::new(&editor.text_input)).child(hint)
+            }
+        })
+}
+
+/// The error with the line of the expression it is on, marked where it is
+fn render_syntax_error(
+    error: &SyntaxError,
+    expression: &str,
+    cx: &mut Context<App>,
+) -> impl IntoElement {
+    let (line, column) = error.line_column(expression);
+    let text = expression.lines().nth(line - 1).unwrap_or_default();
+
+    v_flex()
+        .text_xs()
+        .text_color(cx.theme().danger)
+        .child(format!(
+            "Line {}, column {}: {}",
+            line, column, error.message
+        ))
+        .child(
+            v_flex()
+                .font_family("monospace")
+                .child(div().whitespace_nowrap().child(text.replace('\t', " ")))
+                .child(
+                    div()
+                        .whitespace_nowrap()
+                        .child(format!("{}^", "\u{a0}".repeat(column - 1))),
+                ),
+        )
+}
+
+fn render_builder(
+    id: &'static str,
+    editor: &ExpressionEditor,
+    editor_of: fn(&mut App) -> &mut ExpressionEditor,
+    cx: &mut Context<App>,
+) -> impl IntoElement {
+    let muted_foreground = cx.theme().muted_foreground;
+    let border_color = cx.theme().border;
+    let takes_list = editor
+        .groups
+        .iter()
+        .flatten()
+        .any(|row| row.operator.takes_list());
+    let mut row_count = 0;
+
+    v_flex()
+        .gap_2()
+        .children(editor.groups.iter().enumerate().map(|(group, rows)| {
+            v_flex()
+                .gap_1()
+                .when(group > 0, |this| {
+                    this.child(div().text_xs().text_color(muted_foreground).child("or"))
+                })
+                .child(
+                    v_flex()
+                        .gap_1()
+                        .p_2()
+                        .rounded_md()
+                        .border_1()
+                        .border_color(border_color)
+                        .children(rows.iter().enumerate().map(|(index, row)| {
+                            let row_id = row_count;
+                            row_count += 1;
+                            v_flex()
+                                .gap_1()
+                                .when(index > 0, |this| {
+                                    this.child(
+                                        div().text_xs().text_color(muted_foreground).child("and"),
+                                    )
+                                })
+                                .child(render_condition(
+                                    id,
+                                    row_id,
+                                    (group, index),
+                                    row,
+                                    editor_of,
+                                    cx,
+                                ))
+                        }))
+                        .child(
+                            Button::new((element_id(id, "and"), group))
+                                .ghost()
+                                .xsmall()
+                                .icon(gpui_component::IconName::Plus)
+                                .label("And")
+                                .on_click(cx.listener(move |this, _, window, cx| {
+                                    editor_of(this).add_condition(group, window, cx);
+                                    cx.notify();
+                                })),
+                        ),
+                )
+        }))
+        .child(
+            h_flex()
+                .gap_2()
+                .items_center()
+                .child(
+                    Button::new(element_id(id, "or"))
+                        .ghost()
+                        .xsmall()
+                        .icon(gpui_component::IconName::Plus)
+                        .label("Or")
+                        .on_click(cx.listener(move |this, _, window, cx| {
+                            editor_of(this).add_group(window, cx);
+                            cx.notify();
+                        })),
+                )
+                .when(takes_list, |this| {
+                    this.child(
+                        div()
+                            .text_xs()
+                            .text_color(muted_foreground)
+                            .child("Separate the values of a list with spaces"),
+                    )
+                }),
+        )
+}
+
+/// Field, operator and value of the condition at `index` in `group` of the builder
+fn render_condition(
+    id: &'static str,
+    row_id: usize,
+    (group, index): (usize, usize),
+    row: &ConditionRow,
+    editor_of: fn(&mut App) -> &mut ExpressionEditor,
+    cx: &mut Context<App>,
+) -> impl IntoElement {
+    let entity = cx.entity();
+    let field_entity = entity.clone();
+    let field = row.field;
+    let operator = row.operator;
+
+    h_flex()
+        .gap_1()
+        .child(
+            Button::new((element_id(id, "field"), row_id))
+                .outline()
+                .xsmall()
+                .w(px(130.))
+                .label(field.label)
+                .dropdown_menu(move |menu, _, _| {
+                    let mut menu = menu;
+                    for &option in FIELDS {
+                        let entity = field_entity.clone();
+                        menu = menu.item(
+                            PopupMenuItem::new(option.label)
+                                .checked(option == field)
+                                .on_click(move |_, _, cx| {
+                                    entity.update(cx, |this, cx| {
+                                        editor_of(this).set_field(group, index, option);
+                                        cx.notify();
+                                    });
+                                }),
+                        );
+                    }
+                    menu
+                }),
+        )
+        .child(
+            Button::new((element_id(id, "operator"), row_id))
+                .outline()
+                .xsmall()
+                .label(operator.label())
+                .dropdown_menu(move |menu, _, _| {
+                    let mut menu = menu;
+                    for &option in Operator::ALL {
+                        if !option.applies_to(field.kind) {
+                            continue;
+                        }
+                        let entity = entity.clone();
+                        menu = menu.item(
+                            PopupMenuItem::new(option.label())
+                                .checked(option == operator)
+                                .on_click(move |_, _, cx| {
+                                    entity.update(cx, |this, cx| {
+                                        editor_of(this).set_operator(group, index, option);
+                                        cx.notify();
+                                    });
+                                }),
+                        );
+                    }
+                    menu
+                }),
+        )
+        .child(div().flex_1().child(Input::new(&row.value)))
+        .child(
+            Button::new((element_id(id, "remove"), row_id))
+                .ghost()
+                .xsmall()
+                .icon(gpui_component::IconName::Close)
+                .tooltip("Remove condition")
+                .on_click(cx.listener(move |this, _, window, cx| {
+                    editor_of(this).remove_condition(group, index, window, cx);
+                    cx.notify();
+                })),
+        )
+}
+
+/// Id of an element of the editor, e.g. `waf-expression-field`
+fn element_id(id: &'static str, part: &str) -> SharedString {
+    SharedString::from(format!("{}-{}", id, part))
+}
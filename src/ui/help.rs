@@ -0,0 +1,48 @@
+use gpui::prelude::*;
+use gpui::{FontWeight, IntoElement, SharedString, div, px};
+use gpui_component::{
+    ActiveTheme, Sizable,
+    button::{Button, ButtonVariants},
+    popover::Popover,
+    v_flex,
+};
+
+use crate::help::HelpTopic;
+
+/// Small "?" button that explains a field or setting in a popover
+pub fn render_help(topic: HelpTopic) -> impl IntoElement {
+    Popover::new(SharedString::from(format!("help-{:?}", topic)))
+        .trigger(Button::new("help").ghost().xsmall().label("?"))
+        .content(move |_, _, cx| {
+            let help = topic.help();
+            let muted_foreground = cx.theme().muted_foreground;
+
+            v_flex()
+                .w(px(280.))
+                .gap_2()
+                .text_sm()
+                .child(div().font_weight(FontWeight::SEMIBOLD).child(help.title))
+                .child(help.description)
+                .when_some(help.valid_values, |this, valid_values| {
+                    this.child(
+                        v_flex()
+                            .child(
+                                div()
+                                    .text_xs()
+                                    .text_color(muted_foreground)
+                                    .child("Valid values"),
+                            )
+                            .child(valid_values),
+                    )
+                })
+                .when_some(help.docs_url, |this, url| {
+                    this.child(
+                        Button::new("help-docs")
+                            .link()
+                            .small()
+                            .label("Cloudflare docs")
+                            .on_click(move |_, _, cx| cx.open_url(url)),
+                    )
+                })
+        })
+}
@@ -0,0 +1,103 @@
+use gpui::prelude::*;
+use gpui::{App as GpuiApp, Entity, FontWeight, IntoElement, Window, div, px};
+use gpui_component::{
+    ActiveTheme, Disableable, WindowExt,
+    button::{Button, ButtonVariants},
+    scroll::ScrollableElement,
+    v_flex,
+};
+
+use crate::App;
+use crate::history::HistoryEntry;
+
+/// Open the dialog listing the actions that can be undone and redone
+pub fn open_history_dialog(app: Entity<App>, window: &mut Window, cx: &mut GpuiApp) {
+    window.open_dialog(cx, move |dialog, _, cx| {
+        let history = &app.read(cx).history;
+        let can_undo = !history.undo_entries().is_empty();
+        let can_redo = !history.redo_entries().is_empty();
+
+        let content = v_flex()
+            .gap_3()
+            .max_h(px(420.))
+            .overflow_y_scrollbar()
+            .child(render_section(
+                "Undo",
+                history.undo_entries(),
+                "Nothing to undo",
+                cx,
+            ))
+            .child(render_section(
+                "Redo",
+                history.redo_entries(),
+                "Nothing to redo",
+                cx,
+            ));
+
+        let app = app.clone();
+        dialog
+            .title("History")
+            .w(px(480.))
+            .child(content)
+            .footer(move |_, _, _, _| {
+                vec![
+                    Button::new("history-undo")
+                        .label("Undo")
+                        .disabled(!can_undo)
+                        .on_click({
+                            let app = app.clone();
+                            move |_, window, cx| {
+                                app.update(cx, |this, cx| this.undo(window, cx));
+                            }
+                        }),
+                    Button::new("history-redo")
+                        .label("Redo")
+                        .disabled(!can_redo)
+                        .on_click({
+                            let app = app.clone();
+                            move |_, window, cx| {
+                                app.update(cx, |this, cx| this.redo(window, cx));
+                            }
+                        }),
+                    Button::new("history-close")
+                        .primary()
+                        .label("Close")
+                        .on_click(|_, window, cx| window.close_dialog(cx)),
+                ]
+            })
+    });
+}
+
+/// Entries of one stack, the next one to run first
+fn render_section(
+    title: &'static str,
+    entries: &[HistoryEntry],
+    empty: &'static str,
+    cx: &GpuiApp,
+) -> impl IntoElement {
+    v_flex()
+        .gap_1()
+        .child(div().font_weight(FontWeight::MEDIUM).child(title))
+        .map(|this| {
+            if entries.is_empty() {
+                this.child(
+                    div()
+                        .text_sm()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(empty),
+                )
+            } else {
+                this.children(entries.iter().rev().enumerate().map(|(index, entry)| {
+                    div()
+                        .py_1()
+                        .text_sm()
+                        .border_b_1()
+                        .border_color(cx.theme().border)
+                        .when(index > 0, |this| {
+                            this.text_color(cx.theme().muted_foreground)
+                        })
+                        .child(entry.description.clone())
+                }))
+            }
+        })
+}
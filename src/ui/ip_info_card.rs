@@ -0,0 +1,83 @@
+use std::net::IpAddr;
+
+use gpui::prelude::*;
+use gpui::{App as GpuiApp, Context, Div, ElementId, FontWeight, IntoElement, Stateful, div};
+use gpui_component::{ActiveTheme, h_flex, tooltip::Tooltip, v_flex};
+
+use crate::App;
+use crate::ip_info::IpInfoState;
+
+/// IP address whose reverse DNS, network and country are shown in a card on hover
+pub fn render_ip_address(
+    id: impl Into<ElementId>,
+    ip: IpAddr,
+    cx: &mut Context<App>,
+) -> Stateful<Div> {
+    let app = cx.entity();
+
+    div()
+        .id(id)
+        .child(ip.to_string())
+        .on_hover(cx.listener(move |this, hovered: &bool, _, cx| {
+            if *hovered {
+                this.load_ip_info(ip, cx);
+            }
+        }))
+        .tooltip(move |window, cx| {
+            let app = app.clone();
+            // Reads the cache on every render, so the card fills in once the lookup is done
+            Tooltip::element(move |_, cx| {
+                render_ip_info_card(ip, app.read(cx).ip_info.get(&ip), cx)
+            })
+            .build(window, cx)
+        })
+}
+
+fn render_ip_info_card(ip: IpAddr, state: Option<&IpInfoState>, cx: &GpuiApp) -> impl IntoElement {
+    let muted_foreground = cx.theme().muted_foreground;
+    let row = |label: &'static str, value: String| {
+        h_flex()
+            .gap_3()
+            .child(div().w_24().text_color(muted_foreground).child(label))
+            .child(value)
+    };
+
+    v_flex()
+        .py_1()
+        .gap_1()
+        .child(div().font_weight(FontWeight::MEDIUM).child(ip.to_string()))
+        .map(|this| match state {
+            None | Some(IpInfoState::Loading) => {
+                this.child(div().text_color(muted_foreground).child("Looking up..."))
+            }
+            Some(IpInfoState::Failed(error)) => this.child(
+                div()
+                    .text_color(cx.theme().danger)
+                    .child(format!("Lookup failed: {}", error)),
+            ),
+            Some(IpInfoState::Loaded(info)) => this
+                .child(row(
+                    "Reverse DNS",
+                    info.reverse_dns
+                        .clone()
+                        .unwrap_or_else(|| "None".to_string()),
+                ))
+                .child(row(
+                    "Network",
+                    match (info.asn, &info.organization) {
+                        (Some(asn), Some(organization)) => format!("AS{} {}", asn, organization),
+                        (Some(asn), None) => format!("AS{}", asn),
+                        (None, _) => "Unknown".to_string(),
+                    },
+                ))
+                .when_some(info.prefix.clone(), |this, prefix| {
+                    this.child(row("Prefix", prefix))
+                })
+                .child(row(
+                    "Country",
+                    info.country
+                        .clone()
+                        .unwrap_or_else(|| "Unknown".to_string()),
+                )),
+        })
+}
@@ -0,0 +1,119 @@
+use gpui::prelude::*;
+use gpui::{App as GpuiApp, Entity, FontWeight, IntoElement, Window, div, px};
+use gpui_component::{
+    ActiveTheme, Disableable, Sizable, WindowExt,
+    button::{Button, ButtonVariants},
+    h_flex,
+    scroll::ScrollableElement,
+    v_flex,
+};
+
+use crate::App;
+use crate::lint::Lint;
+
+/// Findings of the zone's lint pass, each with its explanation and quick fix
+pub fn open_lint_dialog(app: Entity<App>, lints: Vec<Lint>, window: &mut Window, cx: &mut GpuiApp) {
+    window.open_dialog(cx, move |dialog, _, cx| {
+        let muted_foreground = cx.theme().muted_foreground;
+        let fixable: Vec<_> = lints
+            .iter()
+            .filter_map(|lint| Some(lint.fix.as_ref()?.action(&lint.record)))
+            .collect();
+
+        let app = app.clone();
+        dialog
+            .title(format!("{} unusual record setups", lints.len()))
+            .w(px(600.))
+            .child(
+                v_flex()
+                    .max_h(px(420.))
+                    .overflow_y_scrollbar()
+                    .gap_3()
+                    .children(lints.iter().enumerate().map(|(ix, lint)| {
+                        let record = lint.record.clone();
+                        let app = app.clone();
+                        v_flex()
+                            .gap_1()
+                            .pb_3()
+                            .border_b_1()
+                            .border_color(cx.theme().border)
+                            .child(
+                                h_flex()
+                                    .gap_2()
+                                    .items_center()
+                                    .justify_between()
+                                    .child(
+                                        div()
+                                            .text_sm()
+                                            .font_weight(FontWeight::MEDIUM)
+                                            .child(lint.kind.title()),
+                                    )
+                                    .child(match &lint.fix {
+                                        Some(fix) => {
+                                            let action = fix.action(&record);
+                                            Button::new(("lint-fix", ix))
+                                                .small()
+                                                .label(fix.label())
+                                                .on_click(move |_, window, cx| {
+                                                    let action = action.clone();
+                                                    window.close_dialog(cx);
+                                                    app.update(cx, |this, cx| {
+                                                        this.run_batch(
+                                                            action.0.clone(),
+                                                            vec![action],
+                                                            window,
+                                                            cx,
+                                                        );
+                                                    });
+                                                })
+                                        }
+                                        None => Button::new(("lint-edit", ix))
+                                            .small()
+                                            .ghost()
+                                            .label("Edit")
+                                            .on_click(move |_, window, cx| {
+                                                window.close_dialog(cx);
+                                                app.update(cx, |this, cx| {
+                                                    this.edit_record(record.clone(), window, cx);
+                                                });
+                                            }),
+                                    }),
+                            )
+                            .child(div().text_xs().font_family("monospace").child(format!(
+                                "{} {} {}",
+                                lint.record.record_type, lint.record.name, lint.record.content
+                            )))
+                            .child(
+                                div()
+                                    .text_xs()
+                                    .text_color(muted_foreground)
+                                    .child(lint.kind.explanation()),
+                            )
+                    })),
+            )
+            .footer(move |_, _, _, _| {
+                let app = app.clone();
+                let fixable = fixable.clone();
+                vec![
+                    Button::new("lint-close")
+                        .label("Close")
+                        .on_click(|_, window, cx| window.close_dialog(cx)),
+                    Button::new("lint-fix-all")
+                        .primary()
+                        .label(format!("Apply {} Quick Fixes", fixable.len()))
+                        .disabled(fixable.is_empty())
+                        .on_click(move |_, window, cx| {
+                            window.close_dialog(cx);
+                            app.update(cx, |this, cx| {
+                                this.run_batch(
+                                    format!("Apply {} quick fixes", fixable.len()),
+                                    fixable.clone(),
+                                    window,
+                                    cx,
+                                );
+                            });
+                        }),
+                ]
+            })
+    });
+}
@@ -1,11 +1,99 @@
+mod account_picker;
+mod activity_panel;
+mod add_profile_dialog;
+mod add_workspace_dialog;
+mod add_zone_dialog;
+mod analytics;
+mod batch_dialog;
+mod bulk_delete_dialog;
+mod bulk_edit_dialog;
+mod changelog_dialog;
+mod console;
+mod csv_import_dialog;
 mod dashboard;
+mod delete_zone_dialog;
 mod dns_list;
+mod edge_certificates;
+mod expression_editor;
+mod help;
+mod history_dialog;
+mod ip_info_card;
+mod lint_dialog;
+mod network_diagnostics;
+mod page_rules;
+mod pages_projects;
+mod portfolio;
+mod profile_switcher;
+mod purge_cache_dialog;
+mod qr_code;
+mod record_changes;
 mod record_editor;
+mod redirect_rules;
+mod resolver_dialog;
+mod retry_queue_dialog;
 mod settings;
+mod staged_conflicts_dialog;
+mod staging_dialog;
+mod status_bar;
+mod task_indicator;
+mod third_party;
+mod timestamp;
 mod token_setup;
+mod tunnels;
+mod unproxy_dialog;
+mod update_conflict_dialog;
+mod waf_rules;
+mod worker_routes;
+mod workspace_picker;
+mod zone_menu;
+mod zone_settings;
 
+pub use account_picker::render_account_picker;
+pub use activity_panel::render_activity_panel;
+pub use add_profile_dialog::open_add_profile_dialog;
+pub use add_workspace_dialog::open_add_workspace_dialog;
+pub use add_zone_dialog::open_add_zone_dialog;
+pub use analytics::render_analytics;
+pub use batch_dialog::open_batch_dialog;
+pub use bulk_delete_dialog::open_bulk_delete_dialog;
+pub use bulk_edit_dialog::open_bulk_edit_dialog;
+pub use changelog_dialog::open_changelog_dialog;
+pub use console::render_console;
+pub use csv_import_dialog::open_csv_import_dialog;
 pub use dashboard::render_dashboard;
+pub use delete_zone_dialog::open_delete_zone_dialog;
 pub use dns_list::render_dns_list;
+pub use edge_certificates::render_edge_certificates;
+pub use expression_editor::render_expression_editor;
+pub use help::render_help;
+pub use history_dialog::open_history_dialog;
+pub use ip_info_card::render_ip_address;
+pub use lint_dialog::open_lint_dialog;
+pub use network_diagnostics::render_network_diagnostics;
+pub use page_rules::render_page_rules;
+pub use pages_projects::render_pages_projects;
+pub use portfolio::render_portfolio;
+pub use profile_switcher::render_profile_switcher;
+pub use purge_cache_dialog::open_purge_cache_dialog;
+pub use qr_code::render_qr_code;
+pub use record_changes::{render_field_change, render_record_changes};
 pub use record_editor::render_record_editor;
+pub use redirect_rules::render_redirect_rules;
+pub use resolver_dialog::open_resolver_dialog;
+pub use retry_queue_dialog::open_retry_queue_dialog;
 pub use settings::render_settings;
+pub use staged_conflicts_dialog::open_staged_conflicts_dialog;
+pub use staging_dialog::open_staging_dialog;
+pub use status_bar::render_status_bar;
+pub use task_indicator::render_task_indicator;
+pub use third_party::{render_third_party_badge, render_third_party_summary};
+pub use timestamp::render_timestamp;
 pub use token_setup::render_token_setup;
+pub use tunnels::render_tunnels;
+pub use unproxy_dialog::open_unproxy_all_dialog;
+pub use update_conflict_dialog::open_update_conflict_dialog;
+pub use waf_rules::render_waf_rules;
+pub use worker_routes::render_worker_routes;
+pub use workspace_picker::render_workspace_picker;
+pub use zone_menu::zone_context_menu;
+pub use zone_settings::render_zone_settings;
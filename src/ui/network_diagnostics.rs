@@ -0,0 +1,214 @@
+use std::time::Duration;
+
+use gpui::prelude::*;
+use gpui::{Context, FontWeight, IntoElement, Window, div, px};
+use gpui_component::{
+    ActiveTheme, Disableable,
+    button::{Button, ButtonVariants},
+    h_flex,
+    scroll::ScrollableElement,
+    v_flex,
+};
+
+use super::render_status_bar;
+use crate::network_diagnostics::{Diagnostics, FamilyCheck};
+use crate::{App, Page};
+
+/// DNS resolution of the API hostname and its reachability over IPv4, IPv6 and the proxy
+pub fn render_network_diagnostics(
+    app: &mut App,
+    _window: &mut Window,
+    cx: &mut Context<App>,
+) -> impl IntoElement {
+    let muted_foreground = cx.theme().muted_foreground;
+
+    v_flex()
+        .size_full()
+        .child(
+            // Header
+            h_flex()
+                .w_full()
+                .px_4()
+                .py_3()
+                .border_b_1()
+                .border_color(cx.theme().border)
+                .items_center()
+                .gap_3()
+                .child(
+                    Button::new("back")
+                        .ghost()
+                        .icon(gpui_component::IconName::ArrowLeft)
+                        .on_click(cx.listener(|this, _, _, cx| {
+                            this.page = Page::Settings;
+                            this.error = None;
+                            cx.notify();
+                        })),
+                )
+                .child(
+                    div()
+                        .text_lg()
+                        .font_weight(FontWeight::SEMIBOLD)
+                        .child("Network Diagnostics"),
+                )
+                .child(div().flex_1())
+                .child(
+                    Button::new("run-network-diagnostics")
+                        .ghost()
+                        .icon(gpui_component::IconName::Redo)
+                        .tooltip("Run again")
+                        .disabled(app.network_diagnostics.is_none())
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            this.run_network_diagnostics(window, cx);
+                        })),
+                ),
+        )
+        .child(
+            v_flex()
+                .flex_1()
+                .p_6()
+                .gap_4()
+                .max_w(px(720.))
+                .overflow_y_scrollbar()
+                .map(|this| match &app.network_diagnostics {
+                    Some(diagnostics) => this.child(render_results(diagnostics, cx)),
+                    None => this.child(
+                        div()
+                            .text_sm()
+                            .text_color(muted_foreground)
+                            .child("Checking how the Cloudflare API is reached…"),
+                    ),
+                }),
+        )
+        .child(render_status_bar(app, cx))
+}
+
+fn render_results(diagnostics: &Diagnostics, cx: &Context<App>) -> impl IntoElement {
+    let muted_foreground = cx.theme().muted_foreground;
+    let all_reachable = diagnostics
+        .families
+        .iter()
+        .all(|check| check.address.is_none() || matches!(check.request, Some(Ok(_))));
+
+    v_flex()
+        .gap_4()
+        .child(
+            div()
+                .font_weight(FontWeight::MEDIUM)
+                .text_color(if diagnostics.resolution.is_ok() && all_reachable {
+                    cx.theme().success
+                } else {
+                    cx.theme().warning
+                })
+                .child(diagnostics.summary()),
+        )
+        .child(
+            v_flex()
+                .gap_1()
+                .child(section_title(format!(
+                    "DNS resolution of {}",
+                    diagnostics.host
+                )))
+                .child(match &diagnostics.resolution {
+                    Ok(resolution) => div()
+                        .text_sm()
+                        .child(format!(
+                            "{} in {}",
+                            resolution
+                                .addresses
+                                .iter()
+                                .map(|address| address.to_string())
+                                .collect::<Vec<_>>()
+                                .join(", "),
+                            millis(resolution.elapsed)
+                        ))
+                        .into_any_element(),
+                    Err(e) => div()
+                        .text_sm()
+                        .text_color(cx.theme().danger)
+                        .child(e.clone())
+                        .into_any_element(),
+                }),
+        )
+        .children(
+            diagnostics
+                .families
+                .iter()
+                .map(|check| render_family(check, cx)),
+        )
+        .child(
+            v_flex()
+                .gap_1()
+                .child(section_title("Proxy".to_string()))
+                .child(match &diagnostics.proxy {
+                    Some((route, result)) => v_flex()
+                        .gap_1()
+                        .child(
+                            div()
+                                .text_sm()
+                                .text_color(muted_foreground)
+                                .child(route.describe()),
+                        )
+                        .child(render_step("HTTPS request", Some(result), cx))
+                        .into_any_element(),
+                    None => div()
+                        .text_sm()
+                        .text_color(muted_foreground)
+                        .child("Requests don't go through a proxy")
+                        .into_any_element(),
+                }),
+        )
+}
+
+fn render_family(check: &FamilyCheck, cx: &Context<App>) -> impl IntoElement {
+    v_flex()
+        .gap_1()
+        .child(section_title(match check.address {
+            Some(address) => format!("{} ({})", check.family.label(), address),
+            None => check.family.label().to_string(),
+        }))
+        .map(|this| {
+            if check.address.is_none() {
+                this.child(
+                    div()
+                        .text_sm()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(format!(
+                            "The hostname has no {} address",
+                            check.family.label()
+                        )),
+                )
+            } else {
+                this.child(render_step("TCP connection", check.connect.as_ref(), cx))
+                    .child(render_step("HTTPS request", check.request.as_ref(), cx))
+            }
+        })
+}
+
+fn render_step(
+    label: &'static str,
+    result: Option<&Result<Duration, String>>,
+    cx: &Context<App>,
+) -> impl IntoElement {
+    h_flex()
+        .gap_3()
+        .text_sm()
+        .child(div().w(px(120.)).child(label))
+        .child(match result {
+            Some(Ok(elapsed)) => div().text_color(cx.theme().success).child(millis(*elapsed)),
+            Some(Err(e)) => div().text_color(cx.theme().danger).child(e.clone()),
+            None => div()
+                .text_color(cx.theme().muted_foreground)
+                .child("Not tried"),
+        })
+}
+
+fn section_title(title: String) -> impl IntoElement {
+    div()
+        .text_sm()
+        .font_weight(FontWeight::SEMIBOLD)
+        .child(title)
+}
+
+fn millis(elapsed: Duration) -> String {
+    format!("{} ms", elapsed.as_millis())
+}
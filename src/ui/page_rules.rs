@@ -0,0 +1,391 @@
+use gpui::prelude::*;
+use gpui::{Context, FontWeight, IntoElement, Window, div, px};
+use gpui_component::{
+    ActiveTheme, Disableable, Sizable,
+    button::{Button, ButtonVariants},
+    checkbox::Checkbox,
+    h_flex,
+    input::Input,
+    scroll::ScrollableElement,
+    v_flex,
+};
+
+use super::render_status_bar;
+use crate::page_rules::{
+    CACHE_LEVELS, FORWARDING_STATUS_CODES, PageRuleDraft, SSL_MODES, action_label,
+};
+use crate::{App, Page};
+
+/// Page Rules of the selected zone in the order they are applied, with an editor next to them
+pub fn render_page_rules(
+    app: &mut App,
+    _window: &mut Window,
+    cx: &mut Context<App>,
+) -> impl IntoElement {
+    let zone_name = app.selected_zone(cx).map(|zone| zone.name);
+    let error = app.error.clone();
+    let muted_foreground = cx.theme().muted_foreground;
+    let border_color = cx.theme().border;
+    let is_loading = app.loading;
+    let read_only = app.read_only();
+    let rule_count = app.page_rules.len();
+    let editing_id = app.page_rule_draft.rule_id.clone();
+
+    v_flex()
+        .size_full()
+        .child(
+            // Header
+            h_flex()
+                .w_full()
+                .px_4()
+                .py_3()
+                .border_b_1()
+                .border_color(border_color)
+                .items_center()
+                .gap_3()
+                .child(
+                    Button::new("back")
+                        .ghost()
+                        .icon(gpui_component::IconName::ArrowLeft)
+                        .on_click(cx.listener(|this, _, _, cx| {
+                            this.page = Page::Dashboard;
+                            this.error = None;
+                            cx.notify();
+                        })),
+                )
+                .child(
+                    div()
+                        .text_lg()
+                        .font_weight(FontWeight::SEMIBOLD)
+                        .child("Page Rules"),
+                )
+                .when_some(zone_name, |this, zone_name| {
+                    this.child(
+                        div()
+                            .text_sm()
+                            .text_color(muted_foreground)
+                            .child(zone_name),
+                    )
+                })
+                .child(div().flex_1())
+                .child(
+                    Button::new("refresh-page-rules")
+                        .ghost()
+                        .icon(gpui_component::IconName::Redo)
+                        .tooltip("Refresh")
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            this.load_page_rules(window, cx);
+                        })),
+                ),
+        )
+        .child(
+            h_flex()
+                .flex_1()
+                .overflow_hidden()
+                .child(
+                    v_flex()
+                        .flex_1()
+                        .h_full()
+                        .p_6()
+                        .gap_3()
+                        .overflow_y_scrollbar()
+                        .child(div().text_sm().text_color(muted_foreground).child(
+                            "Only the first rule matching a request is applied, from the top.",
+                        ))
+                        .when_some(error, |this, error| {
+                            this.child(div().text_sm().text_color(cx.theme().danger).child(error))
+                        })
+                        .when(rule_count == 0, |this| {
+                            this.child(div().text_sm().text_color(muted_foreground).child(
+                                if is_loading {
+                                    "Loading Page Rules…"
+                                } else {
+                                    "This zone has no Page Rules."
+                                },
+                            ))
+                        })
+                        .children(app.page_rules.iter().enumerate().map(|(index, rule)| {
+                            let rule_id = rule.id.clone();
+                            let delete_id = rule.id.clone();
+                            let selected = editing_id.as_ref() == Some(&rule.id);
+
+                            h_flex()
+                                .id(("page-rule", index))
+                                .gap_3()
+                                .p_3()
+                                .items_center()
+                                .rounded_md()
+                                .border_1()
+                                .border_color(if selected {
+                                    cx.theme().primary
+                                } else {
+                                    border_color
+                                })
+                                .cursor_pointer()
+                                .on_click(cx.listener(move |this, _, window, cx| {
+                                    this.edit_page_rule(&rule_id, window, cx);
+                                }))
+                                .child(
+                                    div()
+                                        .text_sm()
+                                        .text_color(muted_foreground)
+                                        .child(format!("{}", index + 1)),
+                                )
+                                .child(
+                                    v_flex()
+                                        .flex_1()
+                                        .gap_1()
+                                        .child(
+                                            h_flex()
+                                                .gap_2()
+                                                .child(
+                                                    div()
+                                                        .font_family("monospace")
+                                                        .font_weight(FontWeight::MEDIUM)
+                                                        .child(rule.pattern().to_string()),
+                                                )
+                                                .when(!rule.is_active(), |this| {
+                                                    this.child(
+                                                        div()
+                                                            .text_xs()
+                                                            .text_color(muted_foreground)
+                                                            .child("Disabled"),
+                                                    )
+                                                }),
+                                        )
+                                        .children(rule.actions.iter().map(|action| {
+                                            div()
+                                                .text_sm()
+                                                .text_color(muted_foreground)
+                                                .child(action_label(action))
+                                        })),
+                                )
+                                .child(
+                                    Button::new(("move-page-rule-up", index))
+                                        .ghost()
+                                        .xsmall()
+                                        .icon(gpui_component::IconName::ArrowUp)
+                                        .tooltip("Apply earlier")
+                                        .disabled(index == 0 || is_loading || read_only)
+                                        .on_click(cx.listener(move |this, _, window, cx| {
+                                            cx.stop_propagation();
+                                            this.move_page_rule(index, true, window, cx);
+                                        })),
+                                )
+                                .child(
+                                    Button::new(("move-page-rule-down", index))
+                                        .ghost()
+                                        .xsmall()
+                                        .icon(gpui_component::IconName::ArrowDown)
+                                        .tooltip("Apply later")
+                                        .disabled(
+                                            index + 1 == rule_count || is_loading || read_only,
+                                        )
+                                        .on_click(cx.listener(move |this, _, window, cx| {
+                                            cx.stop_propagation();
+                                            this.move_page_rule(index, false, window, cx);
+                                        })),
+                                )
+                                .child(
+                                    Button::new(("delete-page-rule", index))
+                                        .ghost()
+                                        .xsmall()
+                                        .icon(gpui_component::IconName::Delete)
+                                        .tooltip("Delete")
+                                        .disabled(read_only)
+                                        .on_click(cx.listener(move |this, _, window, cx| {
+                                            cx.stop_propagation();
+                                            this.open_delete_page_rule_dialog(
+                                                delete_id.clone(),
+                                                window,
+                                                cx,
+                                            );
+                                        })),
+                                )
+                        })),
+                )
+                .child(render_page_rule_editor(app, cx)),
+        )
+        .child(render_status_bar(app, cx))
+}
+
+fn render_page_rule_editor(app: &App, cx: &mut Context<App>) -> impl IntoElement {
+    let draft = &app.page_rule_draft;
+    let editing = draft.rule_id.is_some();
+    let muted_foreground = cx.theme().muted_foreground;
+
+    let forwarding_options = std::iter::once((None, "Off".to_string()))
+        .chain(
+            FORWARDING_STATUS_CODES
+                .iter()
+                .map(|&status| (Some(status), status.to_string())),
+        )
+        .collect();
+    let cache_level_options = std::iter::once((None, "Not set".to_string()))
+        .chain(
+            CACHE_LEVELS
+                .iter()
+                .map(|&(level, label)| (Some(level), label.to_string())),
+        )
+        .collect();
+    let ssl_options = std::iter::once((None, "Not set".to_string()))
+        .chain(
+            SSL_MODES
+                .iter()
+                .map(|&(mode, label)| (Some(mode), label.to_string())),
+        )
+        .collect();
+
+    v_flex()
+        .w(px(380.))
+        .h_full()
+        .border_l_1()
+        .border_color(cx.theme().border)
+        .p_4()
+        .gap_4()
+        .overflow_y_scrollbar()
+        .child(div().font_weight(FontWeight::SEMIBOLD).child(if editing {
+            "Edit Page Rule"
+        } else {
+            "New Page Rule"
+        }))
+        .child(
+            v_flex()
+                .gap_1()
+                .child(div().text_sm().child("URL pattern"))
+                .child(Input::new(&app.page_rule_pattern_input))
+                .child(
+                    div()
+                        .text_xs()
+                        .text_color(muted_foreground)
+                        .child("Use * to match any text, e.g. example.com/blog/*"),
+                ),
+        )
+        .child(
+            v_flex()
+                .gap_1()
+                .child(div().text_sm().child("Forwarding URL"))
+                .child(render_choice(
+                    "forwarding-status",
+                    forwarding_options,
+                    draft.forwarding_status,
+                    cx,
+                    |draft, status| draft.forwarding_status = status,
+                ))
+                .when(draft.forwarding_status.is_some(), |this| {
+                    this.child(Input::new(&app.page_rule_destination_input))
+                        .child(
+                            div().text_xs().text_color(muted_foreground).child(
+                                "$1, $2, … insert the text matched by the pattern's wildcards",
+                            ),
+                        )
+                }),
+        )
+        .child(
+            v_flex()
+                .gap_1()
+                .child(div().text_sm().child("Cache Level"))
+                .child(render_choice(
+                    "cache-level",
+                    cache_level_options,
+                    draft.cache_level,
+                    cx,
+                    |draft, level| draft.cache_level = level,
+                )),
+        )
+        .child(
+            v_flex()
+                .gap_1()
+                .child(div().text_sm().child("SSL"))
+                .child(render_choice(
+                    "page-rule-ssl",
+                    ssl_options,
+                    draft.ssl,
+                    cx,
+                    |draft, mode| draft.ssl = mode,
+                )),
+        )
+        .when(!draft.other_actions.is_empty(), |this| {
+            this.child(
+                v_flex()
+                    .gap_1()
+                    .child(div().text_sm().child("Other actions"))
+                    .child(
+                        div()
+                            .text_xs()
+                            .text_color(muted_foreground)
+                            .child("Kept as they are when saving"),
+                    )
+                    .children(draft.other_actions.iter().map(|action| {
+                        div()
+                            .text_sm()
+                            .font_family("monospace")
+                            .child(action_label(action))
+                    })),
+            )
+        })
+        .child(
+            Checkbox::new("page-rule-enabled")
+                .label("Enabled")
+                .checked(draft.enabled)
+                .on_click(cx.listener(|this, checked: &bool, _, cx| {
+                    this.page_rule_draft.enabled = *checked;
+                    cx.notify();
+                })),
+        )
+        .child(
+            h_flex()
+                .gap_2()
+                .child(
+                    Button::new("save-page-rule")
+                        .primary()
+                        .label(if editing { "Save Rule" } else { "Create Rule" })
+                        .disabled(app.loading || app.read_only())
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            this.save_page_rule(window, cx);
+                        })),
+                )
+                .when(editing, |this| {
+                    this.child(Button::new("cancel-page-rule").label("Cancel").on_click(
+                        cx.listener(|this, _, window, cx| {
+                            this.error = None;
+                            this.new_page_rule(window, cx);
+                        }),
+                    ))
+                }),
+        )
+}
+
+/// A row of buttons choosing one of `options` for the draft, the chosen one highlighted
+fn render_choice<T: Copy + PartialEq + 'static>(
+    id: &'static str,
+    options: Vec<(T, String)>,
+    chosen: T,
+    cx: &mut Context<App>,
+    choose: fn(&mut PageRuleDraft, T),
+) -> impl IntoElement {
+    h_flex()
+        .gap_1()
+        .flex_wrap()
+        .children(
+            options
+                .into_iter()
+                .enumerate()
+                .map(|(index, (option, label))| {
+                    Button::new((id, index))
+                        .xsmall()
+                        .map(|this| {
+                            if option == chosen {
+                                this.primary()
+                            } else {
+                                this.ghost()
+                            }
+                        })
+                        .label(label)
+                        .on_click(cx.listener(move |this, _, _, cx| {
+                            choose(&mut this.page_rule_draft, option);
+                            cx.notify();
+                        }))
+                }),
+        )
+}
@@ -0,0 +1,344 @@
+use chrono::Utc;
+use gpui::prelude::*;
+use gpui::{Context, FontWeight, Hsla, IntoElement, Window, div, px};
+use gpui_component::{
+    ActiveTheme, Disableable, Sizable,
+    button::{Button, ButtonVariants},
+    h_flex,
+    scroll::ScrollableElement,
+    v_flex,
+};
+
+use super::render_status_bar;
+use crate::cloudflare::{PagesDeployment, PagesProject};
+use crate::pages_projects::{DeploymentState, describe, source_label};
+use crate::{App, Page, timestamps};
+
+/// Pages projects of the selected zone's account with their latest deployments; a project
+/// expands to its deployments
+pub fn render_pages_projects(
+    app: &mut App,
+    _window: &mut Window,
+    cx: &mut Context<App>,
+) -> impl IntoElement {
+    let account_name = app.selected_zone(cx).map(|zone| zone.account.name);
+    let error = app.error.clone();
+    let muted_foreground = cx.theme().muted_foreground;
+    let border_color = cx.theme().border;
+    let is_loading = app.loading;
+
+    v_flex()
+        .size_full()
+        .child(
+            // Header
+            h_flex()
+                .w_full()
+                .px_4()
+                .py_3()
+                .border_b_1()
+                .border_color(border_color)
+                .items_center()
+                .gap_3()
+                .child(
+                    Button::new("back")
+                        .ghost()
+                        .icon(gpui_component::IconName::ArrowLeft)
+                        .on_click(cx.listener(|this, _, _, cx| {
+                            this.page = Page::Dashboard;
+                            this.error = None;
+                            cx.notify();
+                        })),
+                )
+                .child(
+                    div()
+                        .text_lg()
+                        .font_weight(FontWeight::SEMIBOLD)
+                        .child("Pages"),
+                )
+                .when_some(account_name, |this, account_name| {
+                    this.child(
+                        div()
+                            .text_sm()
+                            .text_color(muted_foreground)
+                            .child(account_name),
+                    )
+                })
+                .child(div().flex_1())
+                .child(
+                    Button::new("refresh-pages-projects")
+                        .ghost()
+                        .icon(gpui_component::IconName::Redo)
+                        .tooltip("Refresh")
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            this.load_pages_projects(window, cx);
+                        })),
+                ),
+        )
+        .child(
+            v_flex()
+                .flex_1()
+                .p_6()
+                .gap_3()
+                .overflow_y_scrollbar()
+                .when_some(error, |this, error| {
+                    this.child(div().text_sm().text_color(cx.theme().danger).child(error))
+                })
+                .when(app.pages_projects.is_empty(), |this| {
+                    this.child(
+                        div()
+                            .text_sm()
+                            .text_color(muted_foreground)
+                            .child(if is_loading {
+                                "Loading Pages projects…"
+                            } else {
+                                "This account has no Pages projects."
+                            }),
+                    )
+                })
+                .children(
+                    app.pages_projects
+                        .iter()
+                        .enumerate()
+                        .map(|(index, project)| render_project(app, index, project, cx)),
+                ),
+        )
+        .child(render_status_bar(app, cx))
+}
+
+fn render_project(
+    app: &App,
+    index: usize,
+    project: &PagesProject,
+    cx: &mut Context<App>,
+) -> impl IntoElement {
+    let muted_foreground = cx.theme().muted_foreground;
+    let expanded = app.expanded_pages_project.as_ref() == Some(&project.name);
+    let production_url = format!("https://{}", project.subdomain);
+    let project_name = project.name.clone();
+
+    v_flex()
+        .gap_2()
+        .p_3()
+        .rounded_md()
+        .border_1()
+        .border_color(cx.theme().border)
+        .child(
+            h_flex()
+                .id(("pages-project", index))
+                .gap_3()
+                .items_center()
+                .cursor_pointer()
+                .on_click(cx.listener(move |this, _, window, cx| {
+                    this.toggle_pages_project(project_name.clone(), window, cx);
+                }))
+                .child(
+                    gpui_component::Icon::new(if expanded {
+                        gpui_component::IconName::ChevronDown
+                    } else {
+                        gpui_component::IconName::ChevronRight
+                    })
+                    .small()
+                    .text_color(muted_foreground),
+                )
+                .child(
+                    v_flex()
+                        .flex_1()
+                        .gap_1()
+                        .child(
+                            h_flex()
+                                .gap_2()
+                                .items_center()
+                                .child(
+                                    div()
+                                        .font_weight(FontWeight::MEDIUM)
+                                        .child(project.name.clone()),
+                                )
+                                .child(
+                                    Button::new(("open-pages-project", index))
+                                        .ghost()
+                                        .xsmall()
+                                        .label(project.subdomain.clone())
+                                        .icon(gpui_component::IconName::ExternalLink)
+                                        .on_click(move |_, _, cx| {
+                                            cx.stop_propagation();
+                                            cx.open_url(&production_url);
+                                        }),
+                                ),
+                        )
+                        .when(!project.domains.is_empty(), |this| {
+                            this.child(
+                                div()
+                                    .text_sm()
+                                    .text_color(muted_foreground)
+                                    .child(project.domains.join(", ")),
+                            )
+                        }),
+                )
+                .map(|this| match &project.latest_deployment {
+                    Some(deployment) => this
+                        .child(render_deployment_summary(app, deployment, cx))
+                        .child({
+                            let project = project.name.clone();
+                            let deployment_id = deployment.id.clone();
+                            Button::new(("retry-pages-deployment", index))
+                                .small()
+                                .label("Retry")
+                                .tooltip("Run the latest deployment again")
+                                .disabled(
+                                    app.loading
+                                        || app.read_only()
+                                        || DeploymentState::of(deployment)
+                                            == DeploymentState::InProgress,
+                                )
+                                .on_click(cx.listener(move |this, _, window, cx| {
+                                    cx.stop_propagation();
+                                    this.retry_pages_deployment(
+                                        project.clone(),
+                                        deployment_id.clone(),
+                                        window,
+                                        cx,
+                                    );
+                                }))
+                        }),
+                    None => this.child(
+                        div()
+                            .text_sm()
+                            .text_color(muted_foreground)
+                            .child("Not deployed yet"),
+                    ),
+                }),
+        )
+        .when(expanded, |this| this.child(render_deployments(app, cx)))
+}
+
+/// Status of a deployment with its commit and when it ran
+fn render_deployment_summary(
+    app: &App,
+    deployment: &PagesDeployment,
+    cx: &Context<App>,
+) -> impl IntoElement {
+    let muted_foreground = cx.theme().muted_foreground;
+    let created = deployment
+        .created_on
+        .as_deref()
+        .and_then(timestamps::parse)
+        .map(|time| app.time_zone_display.relative(time, Utc::now()));
+
+    v_flex()
+        .items_end()
+        .gap_1()
+        .child(
+            div()
+                .text_sm()
+                .text_color(state_color(DeploymentState::of(deployment), cx))
+                .child(describe(deployment)),
+        )
+        .child(
+            div()
+                .max_w(px(320.))
+                .truncate()
+                .text_xs()
+                .text_color(muted_foreground)
+                .child(
+                    [source_label(deployment), created]
+                        .into_iter()
+                        .flatten()
+                        .collect::<Vec<_>>()
+                        .join(" · "),
+                ),
+        )
+}
+
+/// Deployments of the expanded project, newest first
+fn render_deployments(app: &App, cx: &mut Context<App>) -> impl IntoElement {
+    let muted_foreground = cx.theme().muted_foreground;
+
+    v_flex()
+        .ml_6()
+        .rounded_md()
+        .border_1()
+        .border_color(cx.theme().border)
+        .when(app.pages_deployments.is_empty(), |this| {
+            this.child(
+                div()
+                    .px_3()
+                    .py_2()
+                    .text_sm()
+                    .text_color(muted_foreground)
+                    .child(if app.loading {
+                        "Loading deployments…"
+                    } else {
+                        "No deployments."
+                    }),
+            )
+        })
+        .children(
+            app.pages_deployments
+                .iter()
+                .enumerate()
+                .map(|(index, deployment)| {
+                    let url = deployment.url.clone();
+                    let deployment_id = deployment.id.clone();
+
+                    h_flex()
+                        .gap_3()
+                        .px_3()
+                        .py_2()
+                        .items_center()
+                        .when(index > 0, |this| {
+                            this.border_t_1().border_color(cx.theme().border)
+                        })
+                        .child(
+                            div()
+                                .w(px(80.))
+                                .text_xs()
+                                .text_color(muted_foreground)
+                                .child(deployment.environment.clone()),
+                        )
+                        .child(
+                            div()
+                                .w(px(80.))
+                                .text_sm()
+                                .font_family("monospace")
+                                .child(deployment.id.chars().take(8).collect::<String>()),
+                        )
+                        .child(
+                            div()
+                                .flex_1()
+                                .child(render_deployment_summary(app, deployment, cx)),
+                        )
+                        .child(
+                            Button::new(("open-pages-deployment", index))
+                                .ghost()
+                                .xsmall()
+                                .icon(gpui_component::IconName::ExternalLink)
+                                .tooltip("Open this deployment")
+                                .on_click(move |_, _, cx| cx.open_url(&url)),
+                        )
+                        .child(
+                            Button::new(("delete-pages-deployment", index))
+                                .ghost()
+                                .xsmall()
+                                .icon(gpui_component::IconName::Delete)
+                                .tooltip("Delete")
+                                .disabled(app.loading || app.read_only())
+                                .on_click(cx.listener(move |this, _, window, cx| {
+                                    this.open_delete_pages_deployment_dialog(
+                                        deployment_id.clone(),
+                                        window,
+                                        cx,
+                                    );
+                                })),
+                        )
+                }),
+        )
+}
+
+fn state_color(state: DeploymentState, cx: &Context<App>) -> Hsla {
+    match state {
+        DeploymentState::Succeeded => cx.theme().success,
+        DeploymentState::Failed => cx.theme().danger,
+        DeploymentState::Canceled => cx.theme().muted_foreground,
+        DeploymentState::InProgress => cx.theme().warning,
+    }
+}
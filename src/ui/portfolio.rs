@@ -0,0 +1,477 @@
+use std::collections::HashSet;
+
+use chrono::Utc;
+use gpui::prelude::*;
+use gpui::{Context, FontWeight, IntoElement, Window, div, px};
+use gpui_component::{
+    ActiveTheme, Sizable,
+    button::{Button, ButtonVariants},
+    h_flex,
+    menu::ContextMenuExt,
+    scroll::ScrollableElement,
+    tag::Tag,
+    v_flex,
+};
+
+use super::{
+    render_account_picker, render_profile_switcher, render_status_bar, render_workspace_picker,
+    zone_context_menu,
+};
+use crate::certificates::EXPIRING_SOON_DAYS;
+use crate::portfolio::CERTIFICATE_WARNING_DAYS;
+use crate::token_scopes::Capability;
+use crate::{App, Page, tags};
+
+const CARD_WIDTH: f32 = 300.;
+
+/// Start page with a health card for every zone
+pub fn render_portfolio(
+    app: &mut App,
+    _window: &mut Window,
+    cx: &mut Context<App>,
+) -> impl IntoElement {
+    let error = app.error.clone();
+    let zones = app.zones(cx);
+    let has_health = !app.store.read(cx).zone_health().is_empty();
+    let workspace_zone_ids = app.workspace_zone_ids();
+    let groups = tags::group_zones(
+        &zones,
+        &app.zone_tags,
+        app.zone_tag_filter.as_deref(),
+        app.account_filter(cx).as_deref(),
+        workspace_zone_ids.as_ref(),
+    );
+    // The workspace has zones of other profiles only, or none yet
+    let empty_workspace = app
+        .workspace()
+        .filter(|_| workspace_zone_ids.as_ref().is_some_and(HashSet::is_empty))
+        .map(|workspace| workspace.name.clone());
+    let show_group_titles = groups.len() > 1 || app.zone_tag_filter.is_some();
+    let has_accounts = app.zone_accounts(cx).len() > 1;
+    let offer_import = !app.import_offer_dismissed && !zones.is_empty();
+
+    v_flex()
+        .size_full()
+        .child(
+            // Header
+            h_flex()
+                .w_full()
+                .px_4()
+                .py_3()
+                .border_b_1()
+                .border_color(cx.theme().border)
+                .items_center()
+                .justify_between()
+                .child(
+                    div()
+                        .text_lg()
+                        .font_weight(FontWeight::SEMIBOLD)
+                        .child("Portfolio"),
+                )
+                .child(
+                    h_flex()
+                        .gap_2()
+                        .when(app.profiles.len() > 1, |this| {
+                            this.child(render_profile_switcher("portfolio-profile", app, cx))
+                        })
+                        .when(has_accounts, |this| {
+                            this.child(render_account_picker("portfolio-account", app, cx))
+                        })
+                        .child(render_workspace_picker("portfolio-workspace", app, cx))
+                        .when(app.can(Capability::EditZones), |this| {
+                            this.child(
+                                Button::new("add-zone")
+                                    .ghost()
+                                    .icon(gpui_component::IconName::Plus)
+                                    .tooltip("Add zone")
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.open_add_zone_dialog(window, cx);
+                                    })),
+                            )
+                        })
+                        .child(
+                            Button::new("refresh-health")
+                                .ghost()
+                                .icon(gpui_component::IconName::Redo)
+                                .tooltip("Check zone health again")
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.check_zone_health(window, cx);
+                                })),
+                        )
+                        .child(
+                            Button::new("settings")
+                                .ghost()
+                                .icon(gpui_component::IconName::Settings)
+                                .on_click(cx.listener(|this, _, _, cx| {
+                                    this.page = Page::Settings;
+                                    cx.notify();
+                                })),
+                        ),
+                ),
+        )
+        .child(
+            v_flex()
+                .flex_1()
+                .p_4()
+                .gap_4()
+                .overflow_y_scrollbar()
+                .when_some(error, |this, error| {
+                    this.child(
+                        h_flex()
+                            .gap_2()
+                            .items_center()
+                            .child(div().text_sm().text_color(cx.theme().danger).child(error))
+                            .child(
+                                Button::new("network-diagnostics")
+                                    .ghost()
+                                    .xsmall()
+                                    .label("Network Diagnostics")
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.open_network_diagnostics(window, cx);
+                                    })),
+                            ),
+                    )
+                })
+                .when(offer_import, |this| this.child(render_import_offer(cx)))
+                .when_some(empty_workspace, |this, name| {
+                    this.child(div().text_sm().text_color(cx.theme().muted_foreground).child(
+                        format!(
+                            "No zones of this profile are in {} yet. Add zones to it from their \
+                             right-click menu.",
+                            name
+                        ),
+                    ))
+                })
+                .map(|this| {
+                    if zones.is_empty() {
+                        this.child(div().text_color(cx.theme().muted_foreground).child(
+                            if app.loading {
+                                "Loading zones..."
+                            } else {
+                                "No zones found"
+                            },
+                        ))
+                    } else {
+                        this.when(has_health, |this| {
+                            this.child(render_expiring_certificates(app, cx))
+                        })
+                        .child(render_tag_filter(app, cx))
+                        .children(groups.into_iter().map(
+                            |(title, indices)| {
+                                v_flex()
+                                    .gap_2()
+                                    .when(show_group_titles, |this| {
+                                        this.child(
+                                            div().font_weight(FontWeight::MEDIUM).child(format!(
+                                                "{} ({})",
+                                                title,
+                                                indices.len()
+                                            )),
+                                        )
+                                    })
+                                    .child(
+                                        h_flex().flex_wrap().items_start().gap_4().children(
+                                            indices
+                                                .into_iter()
+                                                .map(|index| render_zone_card(app, index, cx)),
+                                        ),
+                                    )
+                            },
+                        ))
+                    }
+                }),
+        )
+        .child(render_status_bar(app, cx))
+}
+
+/// Offer to bring tags, notes and protected records over from a Terraform state
+fn render_import_offer(cx: &mut Context<App>) -> impl IntoElement {
+    h_flex()
+        .p_3()
+        .gap_3()
+        .items_center()
+        .rounded_md()
+        .bg(cx.theme().accent.opacity(0.5))
+        .child(div().flex_1().text_sm().child(
+            "Managing zones with Terraform? Import its state to tag those zones and \
+                     protect the records it manages from changes made here.",
+        ))
+        .child(
+            Button::new("import-terraform-state")
+                .small()
+                .primary()
+                .label("Import Terraform State")
+                .on_click(cx.listener(|this, _, window, cx| {
+                    this.import_terraform_state(window, cx);
+                })),
+        )
+        .child(
+            Button::new("dismiss-import-offer")
+                .small()
+                .ghost()
+                .label("Dismiss")
+                .on_click(cx.listener(|this, _, _, cx| {
+                    this.dismiss_import_offer(cx);
+                })),
+        )
+}
+
+/// Buttons to show only the zones with a given tag
+fn render_tag_filter(app: &App, cx: &mut Context<App>) -> impl IntoElement {
+    let all_tags = tags::all_tags(&app.zone_tags);
+    let filter = app.zone_tag_filter.clone();
+
+    h_flex()
+        .gap_1()
+        .flex_wrap()
+        .items_center()
+        .when(!all_tags.is_empty(), |this| {
+            this.child(
+                Button::new("tag-filter-all")
+                    .small()
+                    .label("All")
+                    .map(|this| {
+                        if filter.is_none() {
+                            this.primary()
+                        } else {
+                            this.ghost()
+                        }
+                    })
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.set_zone_tag_filter(None, window, cx);
+                    })),
+            )
+            .children(all_tags.into_iter().enumerate().map(|(index, tag)| {
+                let selected = filter.as_ref() == Some(&tag);
+                Button::new(("tag-filter", index))
+                    .small()
+                    .label(tag.clone())
+                    .map(|this| {
+                        if selected {
+                            this.primary()
+                        } else {
+                            this.ghost()
+                        }
+                    })
+                    .on_click(cx.listener(move |this, _, window, cx| {
+                        this.set_zone_tag_filter(Some(tag.clone()), window, cx);
+                    }))
+            }))
+        })
+}
+
+/// Edge and origin certificates of all zones that expire soon, soonest first
+fn render_expiring_certificates(app: &App, cx: &mut Context<App>) -> impl IntoElement {
+    let now = Utc::now();
+    let expiring = app.store.read(cx).expiring_certificates();
+    let muted_foreground = cx.theme().muted_foreground;
+
+    v_flex()
+        .gap_2()
+        .child(
+            div()
+                .font_weight(FontWeight::MEDIUM)
+                .child("Certificates Expiring Soon"),
+        )
+        .map(|this| {
+            if expiring.is_empty() {
+                return this.child(div().text_sm().text_color(muted_foreground).child(format!(
+                    "No certificates expire within {} days",
+                    EXPIRING_SOON_DAYS
+                )));
+            }
+
+            this.child(
+                v_flex()
+                    .border_1()
+                    .border_color(cx.theme().border)
+                    .rounded_md()
+                    .children(expiring.into_iter().enumerate().map(
+                        |(index, (zone, certificate))| {
+                            let days = certificate.days_until_expiry(now);
+                            let (expiry, color) = if days < 0 {
+                                ("Expired".to_string(), cx.theme().danger)
+                            } else if days <= CERTIFICATE_WARNING_DAYS {
+                                (format!("Expires in {} days", days), cx.theme().warning)
+                            } else {
+                                (format!("Expires in {} days", days), cx.theme().foreground)
+                            };
+                            let url = certificate.dashboard_url(&zone);
+
+                            h_flex()
+                                .px_3()
+                                .py_2()
+                                .gap_3()
+                                .items_center()
+                                .text_sm()
+                                .when(index > 0, |this| {
+                                    this.border_t_1().border_color(cx.theme().border)
+                                })
+                                .child(
+                                    div()
+                                        .w(px(160.))
+                                        .font_weight(FontWeight::MEDIUM)
+                                        .overflow_hidden()
+                                        .child(zone.name.clone()),
+                                )
+                                .child(Tag::secondary().small().child(certificate.kind.label()))
+                                .child(
+                                    div()
+                                        .flex_1()
+                                        .overflow_hidden()
+                                        .text_color(muted_foreground)
+                                        .child(certificate.hosts.join(", ")),
+                                )
+                                .child(div().text_color(color).child(expiry))
+                                .child(
+                                    Button::new(("renew-certificate", index))
+                                        .ghost()
+                                        .small()
+                                        .icon(gpui_component::IconName::ExternalLink)
+                                        .tooltip("Renew in the Cloudflare dashboard")
+                                        .on_click(move |_, _, cx| cx.open_url(&url)),
+                                )
+                        },
+                    )),
+            )
+        })
+}
+
+fn render_zone_card(app: &App, index: usize, cx: &mut Context<App>) -> impl IntoElement {
+    let zone = app.zones(cx)[index].clone();
+    let health = app.store.read(cx).zone_health().get(&zone.id).cloned();
+    let health = health.as_ref();
+    let now = Utc::now();
+    let muted_foreground = cx.theme().muted_foreground;
+    let accent_color = cx.theme().accent;
+
+    let status_tag = if zone.paused {
+        Tag::warning().small().child("Paused")
+    } else if zone.status == "active" {
+        Tag::success().small().child("Active")
+    } else {
+        Tag::warning().small().child(zone.status.clone())
+    };
+    let plan = zone
+        .plan
+        .as_ref()
+        .map(|plan| plan.name.clone())
+        .unwrap_or_else(|| "Unknown plan".to_string());
+    let dnssec = match health.and_then(|health| health.dnssec.as_deref()) {
+        Some("active") => "Enabled".to_string(),
+        Some(status) => status.replace('-', " "),
+        None => "Unknown".to_string(),
+    };
+    let (certificate, certificate_color) = match health.and_then(|h| h.days_until_expiry(now)) {
+        Some(days) if days < 0 => ("Expired".to_string(), cx.theme().danger),
+        Some(days) if days <= CERTIFICATE_WARNING_DAYS => {
+            (format!("Expires in {} days", days), cx.theme().warning)
+        }
+        Some(days) => (format!("Expires in {} days", days), cx.theme().foreground),
+        None => ("Unknown".to_string(), muted_foreground),
+    };
+    let issues = health
+        .map(|health| health.issues(&zone, now))
+        .unwrap_or_default();
+    let zone_tags = app.zone_tags.get(&zone.id).cloned().unwrap_or_default();
+    let notes = app.zone_notes.get(&zone.id).cloned();
+    let menu_zone = zone.clone();
+    let pinned = app.pinned_zones.contains(&zone.id);
+    let menu_app = cx.entity().downgrade();
+
+    let detail = |label: &'static str, value: String| {
+        h_flex()
+            .justify_between()
+            .text_sm()
+            .child(div().text_color(muted_foreground).child(label))
+            .child(value)
+    };
+
+    let card = v_flex()
+        .id(("zone-card", index))
+        .w(px(CARD_WIDTH))
+        .p_4()
+        .gap_2()
+        .border_1()
+        .border_color(if issues.is_empty() {
+            cx.theme().border
+        } else {
+            cx.theme().warning
+        })
+        .rounded_md()
+        .cursor_pointer()
+        .hover(|this| this.bg(accent_color.opacity(0.5)))
+        .on_click(cx.listener(move |this, _, window, cx| {
+            this.open_zone(index, window, cx);
+        }))
+        .child(
+            h_flex()
+                .justify_between()
+                .gap_2()
+                .child(
+                    div()
+                        .font_weight(FontWeight::SEMIBOLD)
+                        .overflow_hidden()
+                        .child(zone.name.clone()),
+                )
+                .child(status_tag),
+        )
+        .child(
+            h_flex()
+                .gap_1()
+                .flex_wrap()
+                .items_center()
+                .children(
+                    zone_tags
+                        .into_iter()
+                        .map(|tag| Tag::secondary().small().child(tag)),
+                )
+                .child(
+                    Button::new(("edit-zone-tags", index))
+                        .ghost()
+                        .xsmall()
+                        .icon(gpui_component::IconName::Plus)
+                        .tooltip("Edit tags and notes")
+                        .on_click(cx.listener(move |this, _, window, cx| {
+                            this.open_zone_tags_dialog(index, window, cx);
+                        })),
+                ),
+        )
+        .when_some(notes, |this, notes| {
+            this.child(div().text_xs().text_color(muted_foreground).child(notes))
+        })
+        .child(detail("Plan", plan))
+        .child(detail("DNSSEC", dnssec))
+        .child(
+            h_flex()
+                .justify_between()
+                .text_sm()
+                .child(div().text_color(muted_foreground).child("Certificate"))
+                .child(div().text_color(certificate_color).child(certificate)),
+        )
+        .child(
+            v_flex()
+                .pt_2()
+                .gap_1()
+                .border_t_1()
+                .border_color(cx.theme().border)
+                .text_xs()
+                .map(|this| {
+                    if health.is_none() {
+                        this.text_color(muted_foreground).child("Checking...")
+                    } else if issues.is_empty() {
+                        this.text_color(cx.theme().success).child("No issues")
+                    } else {
+                        this.text_color(cx.theme().warning)
+                            .children(issues.into_iter().map(|issue| div().child(issue)))
+                    }
+                }),
+        );
+
+    // Wrapped so the context menus of the cards have distinct ids
+    div()
+        .id(("zone-card-menu", index))
+        .child(card.context_menu(move |menu, _, cx| {
+            zone_context_menu(menu, &menu_zone, pinned, menu_app.clone(), cx)
+        }))
+}
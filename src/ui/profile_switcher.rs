@@ -0,0 +1,49 @@
+use gpui::prelude::*;
+use gpui::{Context, ElementId, IntoElement};
+use gpui_component::{
+    Sizable,
+    button::{Button, ButtonVariants},
+    menu::{DropdownMenu, PopupMenuItem},
+};
+
+use crate::App;
+
+/// Dropdown switching between API token profiles, e.g. work and personal, or adding one
+pub fn render_profile_switcher(
+    id: impl Into<ElementId>,
+    app: &App,
+    cx: &mut Context<App>,
+) -> impl IntoElement {
+    let entity = cx.entity();
+    let profiles = app.profiles.clone();
+    let active_profile = app.active_profile.clone();
+
+    Button::new(id)
+        .ghost()
+        .small()
+        .icon(gpui_component::IconName::User)
+        .label(active_profile.clone())
+        .tooltip("Switch API token profile")
+        .dropdown_menu(move |menu, _, _| {
+            let mut menu = menu;
+            for profile in &profiles {
+                let entity = entity.clone();
+                let profile = profile.clone();
+                menu = menu.item(
+                    PopupMenuItem::new(profile.clone())
+                        .checked(profile == active_profile)
+                        .on_click(move |_, window, cx| {
+                            let profile = profile.clone();
+                            entity.update(cx, |this, cx| this.switch_profile(profile, window, cx));
+                        }),
+                );
+            }
+            let add_entity = entity.clone();
+            menu.separator()
+                .item(
+                    PopupMenuItem::new("Add Profile…").on_click(move |_, window, cx| {
+                        add_entity.update(cx, |this, cx| this.open_add_profile_dialog(window, cx));
+                    }),
+                )
+        })
+}
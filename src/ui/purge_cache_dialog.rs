@@ -0,0 +1,93 @@
+use gpui::prelude::*;
+use gpui::{App as GpuiApp, Entity, IntoElement, Window, div, px};
+use gpui_component::{
+    ActiveTheme, Disableable, WindowExt,
+    button::{Button, ButtonVariants},
+    input::Input,
+    scroll::ScrollableElement,
+    v_flex,
+};
+
+use crate::App;
+use crate::cache_purge::purge_items;
+use crate::cloudflare::{CachePurge, Zone};
+
+/// Confirm a cache purge; purging everything has to be confirmed by typing the zone's name
+pub fn open_purge_cache_dialog(
+    app: Entity<App>,
+    zone: Zone,
+    purge: CachePurge,
+    window: &mut Window,
+    cx: &mut GpuiApp,
+) {
+    let zone_name = zone.name.clone();
+    window.open_dialog(cx, move |dialog, _, cx| {
+        let input = app.read(cx).purge_confirm_input.clone();
+        let items = purge_items(&purge).map(<[String]>::to_vec);
+        let confirmed = items.is_some() || input.read(cx).value().trim() == zone_name;
+        let danger = cx.theme().danger;
+        let muted_foreground = cx.theme().muted_foreground;
+
+        let app = app.clone();
+        let zone = zone.clone();
+        let purge = purge.clone();
+        dialog
+            .title(match &items {
+                None => format!("Purge everything cached for {}?", zone_name),
+                Some(items) => format!("Purge {} cached items of {}?", items.len(), zone_name),
+            })
+            .w(px(560.))
+            .child(v_flex().gap_3().map(|this| {
+                match &items {
+                    None => this
+                        .child(div().text_sm().text_color(danger).child(
+                            "Every cached file of the zone is removed. Until the cache is \
+                             filled again all requests reach the origin, which can slow the \
+                             site down or overload the origin.",
+                        ))
+                        .child(
+                            div()
+                                .text_sm()
+                                .child(format!("Type {} to confirm", zone_name)),
+                        )
+                        .child(Input::new(&input)),
+                    Some(items) => this
+                        .child(div().text_sm().text_color(muted_foreground).child(
+                            "Cached copies of these are removed, so their next requests \
+                             reach the origin:",
+                        ))
+                        .child(
+                            v_flex()
+                                .max_h(px(280.))
+                                .overflow_y_scrollbar()
+                                .gap_1()
+                                .text_sm()
+                                .font_family("monospace")
+                                .children(items.iter().map(|item| div().child(item.clone()))),
+                        ),
+                }
+            }))
+            .footer(move |_, _, _, _| {
+                vec![
+                    Button::new("purge-cache-cancel")
+                        .label("Cancel")
+                        .on_click(|_, window, cx| window.close_dialog(cx)),
+                    Button::new("purge-cache-confirm")
+                        .danger()
+                        .label("Purge")
+                        .disabled(!confirmed)
+                        .on_click({
+                            let app = app.clone();
+                            let zone = zone.clone();
+                            let purge = purge.clone();
+                            move |_, window, cx| {
+                                window.close_dialog(cx);
+                                app.update(cx, |this, cx| {
+                                    this.purge_cache(zone.clone(), purge.clone(), window, cx)
+                                });
+                            }
+                        }),
+                ]
+            })
+    });
+}
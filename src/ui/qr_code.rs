@@ -0,0 +1,66 @@
+use std::rc::Rc;
+
+use gpui::prelude::*;
+use gpui::{
+    AnyElement, App as GpuiApp, Bounds, IntoElement, black, canvas, div, fill, point, px, size,
+    white,
+};
+use gpui_component::{ActiveTheme, v_flex};
+
+use crate::qr::{QUIET_ZONE, QrMatrix};
+
+/// Side length of the rendered code, including its quiet zone
+const QR_SIZE: f32 = 240.;
+
+/// QR code of `text`, always dark on light so phones can scan it in either theme
+pub fn render_qr_code(text: &str, cx: &GpuiApp) -> AnyElement {
+    let matrix = match QrMatrix::encode(text) {
+        Ok(matrix) => Rc::new(matrix),
+        Err(e) => {
+            return div()
+                .w(px(QR_SIZE))
+                .text_sm()
+                .text_color(cx.theme().danger)
+                .child(format!("Can't create a QR code: {}", e))
+                .into_any_element();
+        }
+    };
+
+    v_flex()
+        .gap_2()
+        .items_center()
+        .child(
+            canvas(
+                |_, _, _| {},
+                move |bounds, (), window, _| {
+                    window.paint_quad(fill(bounds, white()));
+
+                    let modules = matrix.width + 2 * QUIET_ZONE;
+                    let module_size = bounds.size.width / modules as f32;
+                    for row in 0..matrix.width {
+                        for (column, length) in matrix.dark_runs(row) {
+                            let origin = bounds.origin
+                                + point(
+                                    module_size * (column + QUIET_ZONE) as f32,
+                                    module_size * (row + QUIET_ZONE) as f32,
+                                );
+                            window.paint_quad(fill(
+                                Bounds::new(origin, size(module_size * length as f32, module_size)),
+                                black(),
+                            ));
+                        }
+                    }
+                },
+            )
+            .size(px(QR_SIZE)),
+        )
+        .child(
+            div()
+                .w(px(QR_SIZE))
+                .text_xs()
+                .text_color(cx.theme().muted_foreground)
+                .truncate()
+                .child(text.to_string()),
+        )
+        .into_any_element()
+}
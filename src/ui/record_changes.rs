@@ -0,0 +1,72 @@
+use gpui::prelude::*;
+use gpui::{App, FontWeight, IntoElement, div};
+use gpui_component::{ActiveTheme, h_flex, v_flex};
+
+use crate::watch::{FieldChange, RecordChange};
+
+pub fn render_record_changes(changes: &[RecordChange], cx: &App) -> impl IntoElement {
+    v_flex().gap_3().children(changes.iter().map(|change| {
+        let record = change.record();
+
+        v_flex()
+            .gap_1()
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(
+                        div()
+                            .text_xs()
+                            .font_weight(FontWeight::MEDIUM)
+                            .text_color(cx.theme().primary)
+                            .child(record.record_type.as_str()),
+                    )
+                    .child(
+                        div()
+                            .text_sm()
+                            .font_weight(FontWeight::MEDIUM)
+                            .child(record.name.clone()),
+                    ),
+            )
+            .map(|this| match change {
+                RecordChange::Added(record) => this.child(
+                    div()
+                        .text_sm()
+                        .text_color(cx.theme().success)
+                        .child(format!("Record was added: {}", record.content)),
+                ),
+                RecordChange::Modified { fields, .. } => {
+                    this.children(fields.iter().map(|field| render_field_change(field, cx)))
+                }
+                RecordChange::Deleted(_) => this.child(
+                    div()
+                        .text_sm()
+                        .text_color(cx.theme().danger)
+                        .child("Record was deleted"),
+                ),
+            })
+    }))
+}
+
+pub fn render_field_change(change: &FieldChange, cx: &App) -> impl IntoElement {
+    h_flex()
+        .gap_2()
+        .text_sm()
+        .child(
+            div()
+                .w_20()
+                .text_color(cx.theme().muted_foreground)
+                .child(change.field),
+        )
+        .child(
+            div()
+                .text_color(cx.theme().danger)
+                .line_through()
+                .child(change.before.clone()),
+        )
+        .child(div().text_color(cx.theme().muted_foreground).child("→"))
+        .child(
+            div()
+                .text_color(cx.theme().success)
+                .child(change.after.clone()),
+        )
+}
@@ -1,25 +1,41 @@
 use gpui::prelude::*;
-use gpui::{Context, FontWeight, IntoElement, Window, div, px};
+use gpui::{
+    Context, Corner, Entity, Focusable, FontWeight, IntoElement, MouseButton, SharedString, Window,
+    div, px, rems,
+};
 use gpui_component::{
     ActiveTheme, Disableable, Sizable,
     button::{Button, ButtonVariants},
     checkbox::Checkbox,
     h_flex,
-    input::Input,
+    input::{Input, InputState},
+    popover::Popover,
     scroll::ScrollableElement,
     select::Select,
     v_flex,
 };
 
-use crate::{App, cloudflare::DnsRecordType};
+use super::{render_help, render_qr_code, render_timestamp};
+use crate::{
+    App,
+    cloudflare::{DnsRecordType, qualified_name},
+    help::HelpTopic,
+    provenance::Provenance,
+    record_data, suggestions, timestamps,
+    token_scopes::Capability,
+};
 
 pub fn render_record_editor(
     app: &mut App,
-    _window: &mut Window,
+    window: &mut Window,
     cx: &mut Context<App>,
 ) -> impl IntoElement {
     let is_loading = app.loading;
     let editing = app.editing_record.is_some();
+    let editing_id = app.editing_record.as_ref().map(|r| r.id.clone());
+    let is_watched = editing_id
+        .as_ref()
+        .is_some_and(|id| app.watched_records.contains(id));
     let current_record_type = app
         .record_type_select
         .read(cx)
@@ -27,6 +43,100 @@ pub fn render_record_editor(
         .copied()
         .unwrap_or(DnsRecordType::A);
     let error = app.error.clone();
+    let has_conflict = app.edit_conflict.is_some();
+    let content = app.record_content_input.read(cx).value().to_string();
+    let name = app.record_name_input.read(cx).value().to_string();
+    let records = app.dns_records(cx);
+    let zone_name = app
+        .selected_zone(cx)
+        .map(|zone| zone.name)
+        .unwrap_or_default();
+    // Suggestions are offered while a field is being typed in
+    let name_suggestions = if app.record_name_input.focus_handle(cx).is_focused(window) {
+        suggestions::name_suggestions(
+            &zone_name,
+            &records,
+            current_record_type,
+            &name,
+            editing_id.as_deref(),
+        )
+        .into_iter()
+        .map(|suggestion| {
+            let note = if suggestion.same_type > 0 {
+                format!("{} exists", current_record_type)
+            } else if suggestion.records > 0 {
+                record_count(suggestion.records)
+            } else {
+                "New name".to_string()
+            };
+            (suggestion.name, note, suggestion.same_type > 0)
+        })
+        .collect()
+    } else {
+        Vec::new()
+    };
+    let content_suggestions = if app.record_content_input.focus_handle(cx).is_focused(window) {
+        suggestions::content_suggestions(
+            &records,
+            current_record_type,
+            &content,
+            editing_id.as_deref(),
+        )
+        .into_iter()
+        .map(|suggestion| (suggestion.value, record_count(suggestion.uses), false))
+        .collect()
+    } else {
+        Vec::new()
+    };
+    // Records of the type being edited at the entered name, besides the edited one
+    let existing_at_name = if name.trim().is_empty() {
+        0
+    } else {
+        let qualified = qualified_name(&name, &zone_name);
+        records
+            .iter()
+            .filter(|record| {
+                record.record_type == current_record_type
+                    && Some(&record.id) != editing_id.as_ref()
+                    && record.name.eq_ignore_ascii_case(&qualified)
+            })
+            .count()
+    };
+    let created_on = app
+        .editing_record
+        .as_ref()
+        .and_then(|record| record.created_on.as_deref())
+        .and_then(timestamps::parse);
+    let modified_on = app
+        .editing_record
+        .as_ref()
+        .and_then(|record| record.modified_on.as_deref())
+        .and_then(timestamps::parse);
+    let time_zone_display = app.time_zone_display;
+    let provenance = app.editing_record.as_ref().and_then(Provenance::detect);
+    let is_locked = app
+        .editing_record
+        .as_ref()
+        .is_some_and(|record| record.locked);
+    let protection = editing_id
+        .as_ref()
+        .and_then(|id| app.protected_records.get(id))
+        .cloned();
+    let is_protected = protection.is_some();
+    let read_only_note = if app.read_only_token() {
+        Some("The API token can only read, so records can't be changed.")
+    } else if app.read_only_profile() {
+        Some("This profile is always read-only, so records can't be changed.")
+    } else if app.read_only_setting {
+        Some("Read-only mode is on. Turn it off in Settings to change records.")
+    } else if !app.can(Capability::EditDns) {
+        Some("The API token doesn't have the DNS Write permission, so records can't be changed.")
+    } else {
+        None
+    };
+    let read_only = read_only_note.is_some();
+    // New records start with picking the type, so only the fields it needs are shown
+    let choosing_type = !editing && !app.new_record_type_chosen;
 
     v_flex()
         .w(px(350.))
@@ -41,27 +151,208 @@ pub fn render_record_editor(
                 .items_center()
                 .justify_between()
                 .child(div().font_weight(FontWeight::SEMIBOLD).child(if editing {
-                    "Edit Record"
+                    "Edit Record".to_string()
+                } else if choosing_type {
+                    "New Record".to_string()
                 } else {
-                    "New Record"
+                    format!("New {} Record", current_record_type)
                 }))
+                .when(!editing && !choosing_type, |this| {
+                    this.child(
+                        Button::new("change-record-type")
+                            .ghost()
+                            .small()
+                            .label("Change Type")
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.choose_new_record_type(None, window, cx);
+                            })),
+                    )
+                })
                 .map(|this| {
-                    if editing {
+                    if let Some(record_id) = editing_id {
+                        let protect_record_id = record_id.clone();
                         this.child(
-                            Button::new("cancel-edit")
-                                .ghost()
-                                .small()
-                                .label("Cancel")
-                                .on_click(cx.listener(|this, _, window, cx| {
-                                    this.clear_record_form(window, cx);
-                                    cx.notify();
-                                })),
+                            h_flex()
+                                .gap_1()
+                                .child(
+                                    Button::new("protect-record")
+                                        .ghost()
+                                        .small()
+                                        .label(if is_protected { "Unprotect" } else { "Protect" })
+                                        .tooltip(if is_protected {
+                                            "Allow changing and deleting this record"
+                                        } else {
+                                            "Block changes and deletion of this record"
+                                        })
+                                        .on_click(cx.listener(move |this, _, _, cx| {
+                                            this.toggle_protect_record(&protect_record_id, cx);
+                                        })),
+                                )
+                                .child(
+                                    Button::new("watch-record")
+                                        .ghost()
+                                        .small()
+                                        .icon(if is_watched {
+                                            gpui_component::IconName::EyeOff
+                                        } else {
+                                            gpui_component::IconName::Eye
+                                        })
+                                        .tooltip(if is_watched {
+                                            "Stop watching this record"
+                                        } else {
+                                            "Watch this record for external changes"
+                                        })
+                                        .on_click(cx.listener(move |this, _, _, cx| {
+                                            this.toggle_watch_record(&record_id, cx);
+                                        })),
+                                )
+                                .when(!read_only, |this| {
+                                    this.child(
+                                        Button::new("duplicate-record")
+                                            .ghost()
+                                            .small()
+                                            .icon(gpui_component::IconName::Copy)
+                                            .tooltip("Duplicate as a new record")
+                                            .on_click(cx.listener(|this, _, window, cx| {
+                                                if let Some(record) = this.editing_record.clone() {
+                                                    this.duplicate_record(&record, window, cx);
+                                                }
+                                            })),
+                                    )
+                                })
+                                .child(
+                                    Button::new("cancel-edit")
+                                        .ghost()
+                                        .small()
+                                        .label("Cancel")
+                                        .on_click(cx.listener(|this, _, window, cx| {
+                                            this.clear_record_form(window, cx);
+                                            cx.notify();
+                                        })),
+                                ),
                         )
                     } else {
                         this
                     }
                 }),
         )
+        .when(created_on.is_some() || modified_on.is_some(), |this| {
+            this.child(
+                h_flex()
+                    .gap_3()
+                    .children(created_on.map(|created_on| {
+                        render_timestamp(
+                            "record-created-on",
+                            "Created",
+                            created_on,
+                            time_zone_display,
+                            cx,
+                        )
+                    }))
+                    .children(modified_on.map(|modified_on| {
+                        render_timestamp(
+                            "record-modified-on",
+                            "Modified",
+                            modified_on,
+                            time_zone_display,
+                            cx,
+                        )
+                    })),
+            )
+        })
+        .when_some(read_only_note, |this, note| {
+            this.child(
+                v_flex()
+                    .gap_1()
+                    .px_3()
+                    .py_2()
+                    .rounded_md()
+                    .bg(cx.theme().muted)
+                    .text_sm()
+                    .child(div().font_weight(FontWeight::MEDIUM).child("Read-only"))
+                    .child(div().text_color(cx.theme().muted_foreground).child(note)),
+            )
+        })
+        .when(is_locked, |this| {
+            this.child(
+                v_flex()
+                    .gap_1()
+                    .px_3()
+                    .py_2()
+                    .rounded_md()
+                    .bg(cx.theme().muted)
+                    .text_sm()
+                    .child(div().font_weight(FontWeight::MEDIUM).child("Locked record"))
+                    .child(
+                        div()
+                            .text_color(cx.theme().muted_foreground)
+                            .child("Cloudflare manages this record for one of its products, so it can't be edited or deleted. Change it through the product that created it."),
+                    ),
+            )
+        })
+        .when_some(protection, |this, reason| {
+            this.child(
+                v_flex()
+                    .gap_1()
+                    .px_3()
+                    .py_2()
+                    .rounded_md()
+                    .bg(cx.theme().muted)
+                    .text_sm()
+                    .child(div().font_weight(FontWeight::MEDIUM).child("Protected record"))
+                    .child(
+                        div()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(format!("{}. Unprotect it to change or delete it here.", reason)),
+                    ),
+            )
+        })
+        .when_some(provenance, |this, provenance| {
+            this.child(
+                v_flex()
+                    .gap_1()
+                    .px_3()
+                    .py_2()
+                    .rounded_md()
+                    .bg(cx.theme().warning.opacity(0.1))
+                    .text_sm()
+                    .text_color(cx.theme().warning)
+                    .child(
+                        div()
+                            .font_weight(FontWeight::MEDIUM)
+                            .child(format!("Managed by {}: edit with care", provenance.label())),
+                    )
+                    .child(provenance.description()),
+            )
+        })
+        .map(|this| {
+            if has_conflict {
+                this.child(
+                    v_flex()
+                        .gap_2()
+                        .px_3()
+                        .py_2()
+                        .rounded_md()
+                        .bg(cx.theme().warning.opacity(0.1))
+                        .child(
+                            div()
+                                .text_sm()
+                                .text_color(cx.theme().warning)
+                                .child("This record was changed since it was loaded. Saving shows what changed, to merge or overwrite it."),
+                        )
+                        .child(
+                            Button::new("load-remote-record")
+                                .small()
+                                .label("Load Remote Values")
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.load_remote_record(window, cx);
+                                })),
+                        ),
+                )
+            } else {
+                this
+            }
+        })
         .map(|this| {
             if let Some(err) = error {
                 this.child(
@@ -78,129 +369,367 @@ pub fn render_record_editor(
                 this
             }
         })
-        .child(
-            v_flex()
-                .gap_3()
-                .child(
-                    v_flex()
-                        .gap_1()
-                        .child(
-                            div()
-                                .text_sm()
-                                .font_weight(FontWeight::MEDIUM)
-                                .child("Type"),
-                        )
-                        .child(Select::new(&app.record_type_select).w_full()),
-                )
-                .child(
+        .when(choosing_type, |this| this.child(render_type_picker(cx)))
+        .when(!choosing_type, |this| {
+            this.child(
                     v_flex()
-                        .gap_1()
+                        .gap_3()
+                        .when(editing, |this| {
+                            this.child(
+                                v_flex()
+                                    .gap_1()
+                                    .child(render_field_label("Type", HelpTopic::RecordType))
+                                    .child(
+                                        Select::new(&app.record_type_select)
+                                            .w_full()
+                                            .disabled(is_locked),
+                                    ),
+                            )
+                        })
                         .child(
-                            div()
-                                .text_sm()
-                                .font_weight(FontWeight::MEDIUM)
-                                .child("Name"),
+                            v_flex()
+                                .gap_1()
+                                .child(render_field_label("Name", HelpTopic::RecordName))
+                                .child(Input::new(&app.record_name_input).disabled(is_locked))
+                                .when(!name_suggestions.is_empty(), |this| {
+                                    this.child(render_suggestions(
+                                        "name-suggestion",
+                                        app.record_name_input.clone(),
+                                        name_suggestions,
+                                        cx,
+                                    ))
+                                })
+                                .when(existing_at_name > 0, |this| {
+                                    this.child(
+                                        div().text_xs().text_color(cx.theme().warning).child(
+                                            if existing_at_name == 1 {
+                                                format!(
+                                                    "This name already has a {} record",
+                                                    current_record_type
+                                                )
+                                            } else {
+                                                format!(
+                                                    "This name already has {} {} records",
+                                                    existing_at_name, current_record_type
+                                                )
+                                            },
+                                        ),
+                                    )
+                                }),
                         )
-                        .child(Input::new(&app.record_name_input)),
-                )
-                .child(
-                    v_flex()
-                        .gap_1()
                         .child(
-                            div()
-                                .text_sm()
-                                .font_weight(FontWeight::MEDIUM)
-                                .child("Content"),
-                        )
-                        .child(Input::new(&app.record_content_input)),
-                )
-                .child(
-                    v_flex()
-                        .gap_1()
-                        .child(div().text_sm().font_weight(FontWeight::MEDIUM).child("TTL"))
-                        .child(Input::new(&app.record_ttl_input)),
-                )
-                .map(|this| {
-                    if current_record_type.requires_priority() {
-                        this.child(
                             v_flex()
                                 .gap_1()
                                 .child(
-                                    div()
-                                        .text_sm()
-                                        .font_weight(FontWeight::MEDIUM)
-                                        .child("Priority"),
+                                    h_flex()
+                                        .items_center()
+                                        .justify_between()
+                                        .child(render_field_label(
+                                            current_record_type.content_label(),
+                                            HelpTopic::RecordContent,
+                                        ))
+                                        .child(
+                                            Popover::new("content-qr")
+                                                .anchor(Corner::TopRight)
+                                                .trigger(
+                                                    Button::new("show-qr")
+                                                        .ghost()
+                                                        .xsmall()
+                                                        .label("Show QR")
+                                                        .disabled(content.is_empty()),
+                                                )
+                                                .content({
+                                                    let content = content.clone();
+                                                    move |_, _, cx| render_qr_code(&content, cx)
+                                                }),
+                                        ),
                                 )
-                                .child(Input::new(&app.record_priority_input)),
+                                .child(Input::new(&app.record_content_input).disabled(is_locked))
+                                .when(!content_suggestions.is_empty(), |this| {
+                                    this.child(render_suggestions(
+                                        "content-suggestion",
+                                        app.record_content_input.clone(),
+                                        content_suggestions,
+                                        cx,
+                                    ))
+                                })
+                                .child(render_content_format(
+                                    current_record_type,
+                                    &content,
+                                    cx,
+                                )),
                         )
-                    } else {
-                        this
-                    }
-                })
-                .map(|this| {
-                    if current_record_type.is_proxiable() {
-                        this.child(
-                            Checkbox::new("proxied")
-                                .label("Proxied through Cloudflare")
-                                .checked(app.record_proxied)
-                                .on_click(cx.listener(|this, checked: &bool, _, cx| {
-                                    this.record_proxied = *checked;
-                                    cx.notify();
-                                })),
+                        .child(
+                            v_flex()
+                                .gap_1()
+                                .child(render_field_label("TTL", HelpTopic::Ttl))
+                                .child(Input::new(&app.record_ttl_input).disabled(is_locked)),
                         )
-                    } else {
-                        this
-                    }
-                })
-                .child(
-                    v_flex()
-                        .gap_1()
+                        .map(|this| {
+                            if current_record_type.requires_priority() {
+                                this.child(
+                                    v_flex()
+                                        .gap_1()
+                                        .child(render_field_label(
+                                            "Priority",
+                                            HelpTopic::Priority,
+                                        ))
+                                        .child(
+                                            Input::new(&app.record_priority_input)
+                                                .disabled(is_locked),
+                                        )
+                                        .child(
+                                            div()
+                                                .text_xs()
+                                                .text_color(cx.theme().muted_foreground)
+                                                .child("Required. Lower values are preferred."),
+                                        ),
+                                )
+                            } else {
+                                this
+                            }
+                        })
+                        .map(|this| {
+                            if current_record_type.is_proxiable() {
+                                this.child(
+                                    h_flex()
+                                        .gap_1()
+                                        .items_center()
+                                        .child(
+                                            Checkbox::new("proxied")
+                                                .label("Proxied through Cloudflare")
+                                                .checked(app.record_proxied)
+                                                .disabled(is_locked)
+                                                .on_click(cx.listener(
+                                                    |this, checked: &bool, _, cx| {
+                                                        this.record_proxied = *checked;
+                                                        cx.notify();
+                                                    },
+                                                )),
+                                        )
+                                        .child(render_help(HelpTopic::Proxied)),
+                                )
+                            } else {
+                                this
+                            }
+                        })
                         .child(
-                            div()
-                                .text_sm()
-                                .font_weight(FontWeight::MEDIUM)
-                                .child("Comment"),
+                            v_flex()
+                                .gap_1()
+                                .child(render_field_label("Comment", HelpTopic::Comment))
+                                .child(Input::new(&app.record_comment_input).disabled(is_locked)),
                         )
-                        .child(Input::new(&app.record_comment_input)),
-                )
-                .child(
+                        .child(
+                            h_flex()
+                                .gap_2()
+                                .child(
+                                    Button::new("save-record")
+                                        .primary()
+                                        .flex_1()
+                                        .label(match (editing, app.staging) {
+                                            (true, false) => "Update Record",
+                                            (true, true) => "Stage Update",
+                                            (false, false) => "Create Record",
+                                            (false, true) => "Stage New Record",
+                                        })
+                                        .disabled(
+                                            is_loading
+                                                || has_conflict
+                                                || is_locked
+                                                || is_protected
+                                                || read_only,
+                                        )
+                                        .on_click(cx.listener(|this, _, window, cx| {
+                                            if this.editing_record.is_some() {
+                                                this.update_record(window, cx);
+                                            } else {
+                                                this.create_record(window, cx);
+                                            }
+                                        })),
+                                )
+                                .map(|this| {
+                                    if editing {
+                                        this.child(
+                                            Button::new("delete-record")
+                                                .danger()
+                                                .icon(gpui_component::IconName::Delete)
+                                                .disabled(is_locked || is_protected || read_only)
+                                                .on_click(cx.listener(|this, _, window, cx| {
+                                                    if let Some(record) = &this.editing_record {
+                                                        let record_id = record.id.clone();
+                                                        this.delete_record(record_id, window, cx);
+                                                    }
+                                                })),
+                                        )
+                                    } else {
+                                        this
+                                    }
+                                }),
+                        ),
+            )
+        })
+}
+
+/// Values to fill `input` in with, each with a note on the right, highlighted if `warn` is set
+fn render_suggestions(
+    id: &'static str,
+    input: Entity<InputState>,
+    suggestions: Vec<(String, String, bool)>,
+    cx: &mut Context<App>,
+) -> impl IntoElement {
+    let muted_foreground = cx.theme().muted_foreground;
+    let warning_color = cx.theme().warning;
+    let accent_color = cx.theme().accent;
+
+    v_flex()
+        .p_1()
+        .rounded_md()
+        .border_1()
+        .border_color(cx.theme().border)
+        .children(
+            suggestions
+                .into_iter()
+                .enumerate()
+                .map(|(index, (value, note, warn))| {
+                    let input = input.clone();
+                    let picked = value.clone();
                     h_flex()
+                        .id((id, index))
+                        .px_2()
+                        .py_1()
                         .gap_2()
+                        .rounded_sm()
+                        .text_sm()
+                        .cursor_pointer()
+                        .hover(|this| this.bg(accent_color))
+                        // Keep the focus in the input, which hides the suggestions when it's lost
+                        .on_mouse_down(MouseButton::Left, |_, window, _| window.prevent_default())
+                        .on_click(move |_, window, cx| {
+                            input.update(cx, |input, cx| input.set_value(&picked, window, cx));
+                        })
+                        .child(div().flex_1().truncate().child(value))
                         .child(
-                            Button::new("save-record")
-                                .primary()
-                                .flex_1()
-                                .label(if editing {
-                                    "Update Record"
+                            div()
+                                .text_xs()
+                                .text_color(if warn {
+                                    warning_color
                                 } else {
-                                    "Create Record"
+                                    muted_foreground
                                 })
-                                .disabled(is_loading)
-                                .on_click(cx.listener(|this, _, window, cx| {
-                                    if this.editing_record.is_some() {
-                                        this.update_record(window, cx);
-                                    } else {
-                                        this.create_record(window, cx);
-                                    }
-                                })),
+                                .child(note),
                         )
-                        .map(|this| {
-                            if editing {
-                                this.child(
-                                    Button::new("delete-record")
-                                        .danger()
-                                        .icon(gpui_component::IconName::Delete)
-                                        .on_click(cx.listener(|this, _, window, cx| {
-                                            if let Some(record) = &this.editing_record {
-                                                let record_id = record.id.clone();
-                                                this.delete_record(record_id, window, cx);
-                                            }
-                                        })),
-                                )
-                            } else {
-                                this
-                            }
-                        }),
-                ),
+                }),
         )
 }
+
+/// Format and example of the content of `record_type`, with the field being typed highlighted
+/// and what's wrong with the content so far
+fn render_content_format(
+    record_type: DnsRecordType,
+    content: &str,
+    cx: &Context<App>,
+) -> impl IntoElement {
+    let muted_foreground = cx.theme().muted_foreground;
+    let fields = record_type.content_fields();
+    let typed_field = record_data::typed_field(record_type, content);
+    let problem = if content.is_empty() {
+        None
+    } else {
+        record_type.validate_content(content).err()
+    };
+
+    v_flex()
+        .gap_0p5()
+        .text_xs()
+        .when(!fields.is_empty(), |this| {
+            this.child(h_flex().gap_1p5().font_family("monospace").children(
+                fields.iter().enumerate().map(|(index, field)| {
+                    div()
+                        .when(index == typed_field, |this| {
+                            this.font_weight(FontWeight::SEMIBOLD)
+                                .text_color(cx.theme().foreground)
+                        })
+                        .when(index != typed_field, |this| {
+                            this.text_color(muted_foreground)
+                        })
+                        .child(*field)
+                }),
+            ))
+        })
+        .when_some(record_type.content_example(), |this, example| {
+            this.child(
+                div()
+                    .text_color(muted_foreground)
+                    .child(format!("e.g. {}", example)),
+            )
+        })
+        .when_some(problem, |this, problem| {
+            this.child(div().text_color(cx.theme().warning).child(problem))
+        })
+}
+
+/// e.g. `1 record` or `3 records`
+fn record_count(count: usize) -> String {
+    if count == 1 {
+        "1 record".to_string()
+    } else {
+        format!("{} records", count)
+    }
+}
+
+fn render_field_label(label: impl Into<SharedString>, topic: HelpTopic) -> impl IntoElement {
+    h_flex()
+        .gap_1()
+        .items_center()
+        .child(
+            div()
+                .text_sm()
+                .font_weight(FontWeight::MEDIUM)
+                .child(label.into()),
+        )
+        .child(render_help(topic))
+}
+
+/// First step of a new record: the record types with what each is used for
+fn render_type_picker(cx: &mut Context<App>) -> impl IntoElement {
+    let muted_foreground = cx.theme().muted_foreground;
+    let primary_color = cx.theme().primary;
+    let accent_color = cx.theme().accent;
+
+    v_flex()
+        .gap_1()
+        .child(
+            div()
+                .text_sm()
+                .text_color(muted_foreground)
+                .child("What kind of record do you want to create?"),
+        )
+        .children(DnsRecordType::all().iter().map(|&record_type| {
+            h_flex()
+                .id(SharedString::from(format!("choose-{}", record_type)))
+                .gap_3()
+                .px_2()
+                .py_1p5()
+                .rounded_md()
+                .cursor_pointer()
+                .hover(|this| this.bg(accent_color))
+                .on_click(cx.listener(move |this, _, window, cx| {
+                    this.choose_new_record_type(Some(record_type), window, cx);
+                }))
+                .child(
+                    div()
+                        .w(rems(3.5))
+                        .flex_shrink_0()
+                        .text_xs()
+                        .font_weight(FontWeight::MEDIUM)
+                        .text_color(primary_color)
+                        .child(record_type.as_str()),
+                )
+                .child(
+                    div()
+                        .text_sm()
+                        .text_color(muted_foreground)
+                        .child(record_type.description()),
+                )
+        }))
+}
@@ -0,0 +1,369 @@
+use gpui::prelude::*;
+use gpui::{Context, FontWeight, IntoElement, Window, div, px};
+use gpui_component::{
+    ActiveTheme, Disableable, Sizable,
+    button::{Button, ButtonVariants},
+    checkbox::Checkbox,
+    h_flex,
+    input::Input,
+    scroll::ScrollableElement,
+    v_flex,
+};
+
+use super::{render_expression_editor, render_status_bar};
+use crate::redirect_rules::{STATUS_CODES, source_label, target_label};
+use crate::{App, Page};
+
+/// Single Redirects of the selected zone in the order they are applied, with an editor next to
+/// them
+pub fn render_redirect_rules(
+    app: &mut App,
+    _window: &mut Window,
+    cx: &mut Context<App>,
+) -> impl IntoElement {
+    let zone_name = app.selected_zone(cx).map(|zone| zone.name);
+    let error = app.error.clone();
+    let muted_foreground = cx.theme().muted_foreground;
+    let border_color = cx.theme().border;
+    let is_loading = app.loading;
+    let read_only = app.read_only();
+    let rule_count = app.redirect_rules.len();
+    let editing_id = app.redirect_draft.rule_id.clone();
+
+    v_flex()
+        .size_full()
+        .child(
+            // Header
+            h_flex()
+                .w_full()
+                .px_4()
+                .py_3()
+                .border_b_1()
+                .border_color(border_color)
+                .items_center()
+                .gap_3()
+                .child(
+                    Button::new("back")
+                        .ghost()
+                        .icon(gpui_component::IconName::ArrowLeft)
+                        .on_click(cx.listener(|this, _, _, cx| {
+                            this.page = Page::Dashboard;
+                            this.error = None;
+                            cx.notify();
+                        })),
+                )
+                .child(
+                    div()
+                        .text_lg()
+                        .font_weight(FontWeight::SEMIBOLD)
+                        .child("Redirect Rules"),
+                )
+                .when_some(zone_name, |this, zone_name| {
+                    this.child(
+                        div()
+                            .text_sm()
+                            .text_color(muted_foreground)
+                            .child(zone_name),
+                    )
+                })
+                .child(div().flex_1())
+                .child(
+                    Button::new("refresh-redirect-rules")
+                        .ghost()
+                        .icon(gpui_component::IconName::Redo)
+                        .tooltip("Refresh")
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            this.load_redirect_rules(window, cx);
+                        })),
+                ),
+        )
+        .child(
+            h_flex()
+                .flex_1()
+                .overflow_hidden()
+                .child(
+                    v_flex()
+                        .flex_1()
+                        .h_full()
+                        .p_6()
+                        .gap_3()
+                        .overflow_y_scrollbar()
+                        .child(div().text_sm().text_color(muted_foreground).child(
+                            "Requests are redirected by the first rule matching them, from the top.",
+                        ))
+                        .when_some(error, |this, error| {
+                            this.child(div().text_sm().text_color(cx.theme().danger).child(error))
+                        })
+                        .when(rule_count == 0, |this| {
+                            this.child(div().text_sm().text_color(muted_foreground).child(
+                                if is_loading {
+                                    "Loading redirects…"
+                                } else {
+                                    "This zone has no redirects."
+                                },
+                            ))
+                        })
+                        .children(app.redirect_rules.iter().enumerate().map(|(index, rule)| {
+                            let rule_id = rule.id.clone();
+                            let delete_id = rule.id.clone();
+                            let selected = editing_id.as_ref() == Some(&rule.id);
+
+                            h_flex()
+                                .id(("redirect-rule", index))
+                                .gap_3()
+                                .p_3()
+                                .items_center()
+                                .rounded_md()
+                                .border_1()
+                                .border_color(if selected {
+                                    cx.theme().primary
+                                } else {
+                                    border_color
+                                })
+                                .cursor_pointer()
+                                .on_click(cx.listener(move |this, _, window, cx| {
+                                    this.edit_redirect(&rule_id, window, cx);
+                                }))
+                                .child(
+                                    div()
+                                        .text_sm()
+                                        .text_color(muted_foreground)
+                                        .child(format!("{}", index + 1)),
+                                )
+                                .child(
+                                    v_flex()
+                                        .flex_1()
+                                        .gap_1()
+                                        .when(!rule.description.is_empty(), |this| {
+                                            this.child(
+                                                div()
+                                                    .text_sm()
+                                                    .font_weight(FontWeight::MEDIUM)
+                                                    .child(rule.description.clone()),
+                                            )
+                                        })
+                                        .child(
+                                            h_flex()
+                                                .gap_2()
+                                                .child(
+                                                    div()
+                                                        .font_family("monospace")
+                                                        .child(source_label(rule)),
+                                                )
+                                                .when(!rule.enabled, |this| {
+                                                    this.child(
+                                                        div()
+                                                            .text_xs()
+                                                            .text_color(muted_foreground)
+                                                            .child("Disabled"),
+                                                    )
+                                                }),
+                                        )
+                                        .child(
+                                            div()
+                                                .text_sm()
+                                                .text_color(muted_foreground)
+                                                .child(target_label(rule)),
+                                        ),
+                                )
+                                .child(
+                                    Button::new(("move-redirect-up", index))
+                                        .ghost()
+                                        .xsmall()
+                                        .icon(gpui_component::IconName::ArrowUp)
+                                        .tooltip("Apply earlier")
+                                        .disabled(index == 0 || is_loading || read_only)
+                                        .on_click(cx.listener(move |this, _, window, cx| {
+                                            cx.stop_propagation();
+                                            this.move_redirect(index, true, window, cx);
+                                        })),
+                                )
+                                .child(
+                                    Button::new(("move-redirect-down", index))
+                                        .ghost()
+                                        .xsmall()
+                                        .icon(gpui_component::IconName::ArrowDown)
+                                        .tooltip("Apply later")
+                                        .disabled(index + 1 == rule_count || is_loading || read_only)
+                                        .on_click(cx.listener(move |this, _, window, cx| {
+                                            cx.stop_propagation();
+                                            this.move_redirect(index, false, window, cx);
+                                        })),
+                                )
+                                .child(
+                                    Button::new(("delete-redirect", index))
+                                        .ghost()
+                                        .xsmall()
+                                        .icon(gpui_component::IconName::Delete)
+                                        .tooltip("Delete")
+                                        .disabled(is_loading || read_only)
+                                        .on_click(cx.listener(move |this, _, window, cx| {
+                                            cx.stop_propagation();
+                                            this.open_delete_redirect_dialog(
+                                                delete_id.clone(),
+                                                window,
+                                                cx,
+                                            );
+                                        })),
+                                )
+                        })),
+                )
+                .child(render_redirect_editor(app, cx)),
+        )
+        .child(render_status_bar(app, cx))
+}
+
+fn render_redirect_editor(app: &App, cx: &mut Context<App>) -> impl IntoElement {
+    let draft = &app.redirect_draft;
+    let editing = draft.rule_id.is_some();
+    let muted_foreground = cx.theme().muted_foreground;
+
+    v_flex()
+        .w(px(380.))
+        .h_full()
+        .border_l_1()
+        .border_color(cx.theme().border)
+        .p_4()
+        .gap_4()
+        .overflow_y_scrollbar()
+        .child(div().font_weight(FontWeight::SEMIBOLD).child(if editing {
+            "Edit Redirect"
+        } else {
+            "New Redirect"
+        }))
+        .child(
+            v_flex()
+                .gap_1()
+                .child(div().text_sm().child("Description"))
+                .child(Input::new(&app.redirect_description_input)),
+        )
+        .child(
+            v_flex()
+                .gap_1()
+                .child(
+                    h_flex()
+                        .gap_2()
+                        .items_center()
+                        .child(div().flex_1().text_sm().child("Redirect from"))
+                        .children([(false, "URL pattern"), (true, "Expression")].map(
+                            |(match_expression, label)| {
+                                Button::new(label)
+                                    .xsmall()
+                                    .map(|this| {
+                                        if match_expression == draft.match_expression {
+                                            this.primary()
+                                        } else {
+                                            this.ghost()
+                                        }
+                                    })
+                                    .label(label)
+                                    .on_click(cx.listener(move |this, _, window, cx| {
+                                        this.set_redirect_match_expression(
+                                            match_expression,
+                                            window,
+                                            cx,
+                                        );
+                                    }))
+                            },
+                        )),
+                )
+                .map(|this| {
+                    if draft.match_expression {
+                        this.child(render_expression_editor(
+                            "redirect-expression",
+                            &app.redirect_expression,
+                            |app| &mut app.redirect_expression,
+                            cx,
+                        ))
+                    } else {
+                        this.child(Input::new(&app.redirect_source_input)).child(
+                            div()
+                                .text_xs()
+                                .text_color(muted_foreground)
+                                .child("Whole URLs; use * to match any text"),
+                        )
+                    }
+                }),
+        )
+        .child(
+            v_flex()
+                .gap_1()
+                .child(div().text_sm().child("Redirect to"))
+                .child(Input::new(&app.redirect_target_input))
+                .child(
+                    div()
+                        .text_xs()
+                        .text_color(muted_foreground)
+                        .child("${1}, ${2}, … insert the text matched by the pattern's wildcards"),
+                ),
+        )
+        .child(
+            v_flex()
+                .gap_1()
+                .child(div().text_sm().child("Status code"))
+                .child(
+                    h_flex()
+                        .gap_1()
+                        .flex_wrap()
+                        .children(STATUS_CODES.iter().enumerate().map(|(index, &status)| {
+                            Button::new(("redirect-status", index))
+                                .xsmall()
+                                .map(|this| {
+                                    if status == draft.status_code {
+                                        this.primary()
+                                    } else {
+                                        this.ghost()
+                                    }
+                                })
+                                .label(status.to_string())
+                                .on_click(cx.listener(move |this, _, _, cx| {
+                                    this.redirect_draft.status_code = status;
+                                    cx.notify();
+                                }))
+                        })),
+                ),
+        )
+        .child(
+            Checkbox::new("redirect-preserve-query")
+                .label("Preserve query string")
+                .checked(draft.preserve_query_string)
+                .on_click(cx.listener(|this, checked: &bool, _, cx| {
+                    this.redirect_draft.preserve_query_string = *checked;
+                    cx.notify();
+                })),
+        )
+        .child(
+            Checkbox::new("redirect-enabled")
+                .label("Enabled")
+                .checked(draft.enabled)
+                .on_click(cx.listener(|this, checked: &bool, _, cx| {
+                    this.redirect_draft.enabled = *checked;
+                    cx.notify();
+                })),
+        )
+        .child(
+            h_flex()
+                .gap_2()
+                .child(
+                    Button::new("save-redirect")
+                        .primary()
+                        .label(if editing {
+                            "Save Redirect"
+                        } else {
+                            "Create Redirect"
+                        })
+                        .disabled(app.loading || app.read_only())
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            this.save_redirect(window, cx);
+                        })),
+                )
+                .when(editing, |this| {
+                    this.child(Button::new("cancel-redirect").label("Cancel").on_click(
+                        cx.listener(|this, _, window, cx| {
+                            this.error = None;
+                            this.new_redirect(window, cx);
+                        }),
+                    ))
+                }),
+        )
+}
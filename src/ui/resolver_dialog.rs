@@ -0,0 +1,86 @@
+use gpui::prelude::*;
+use gpui::{App as GpuiApp, Entity, IntoElement, Window, div, px};
+use gpui_component::{
+    ActiveTheme, WindowExt,
+    button::{Button, ButtonVariants},
+    h_flex,
+    input::Input,
+    scroll::ScrollableElement,
+    select::Select,
+    v_flex,
+};
+
+use crate::resolver::{Resolution, StepKind};
+use crate::{App, RecordTypeItem};
+
+/// Open the what-if resolver, which explains how a hostname would be answered by the zone
+pub fn open_resolver_dialog(app: Entity<App>, window: &mut Window, cx: &mut GpuiApp) {
+    window.open_dialog(cx, move |dialog, _, cx| {
+        let state = app.read(cx);
+        let host_input = state.resolver_host_input.clone();
+        let type_select = state.resolver_type_select.clone();
+        let result = state.resolver_result.clone();
+
+        let app = app.clone();
+        dialog
+            .title("What-if Resolver")
+            .w(px(560.))
+            .child(
+                v_flex()
+                    .gap_3()
+                    .child(
+                        div()
+                            .text_sm()
+                            .text_color(cx.theme().muted_foreground)
+                            .child("See which records of the zone would answer a query, following wildcards and CNAMEs"),
+                    )
+                    .child(
+                        h_flex()
+                            .gap_2()
+                            .child(Input::new(&host_input).flex_1())
+                            .child(
+                                Select::<Vec<RecordTypeItem>>::new(&type_select)
+                                    .w(px(120.))
+                                    .placeholder("Any type")
+                                    .cleanable(true),
+                            )
+                            .child(Button::new("resolve").primary().label("Resolve").on_click({
+                                let app = app.clone();
+                                move |_, _, cx| {
+                                    app.update(cx, |this, cx| this.run_resolver(cx));
+                                }
+                            })),
+                    )
+                    .when_some(result, |this, result| {
+                        this.child(render_resolution(&result, cx))
+                    }),
+            )
+    });
+}
+
+fn render_resolution(resolution: &Resolution, cx: &GpuiApp) -> impl IntoElement {
+    v_flex()
+        .gap_1()
+        .max_h(px(360.))
+        .overflow_y_scrollbar()
+        .text_sm()
+        .children(resolution.steps.iter().map(|step| {
+            let (marker, color) = match step.kind {
+                StepKind::Info => ("•", cx.theme().muted_foreground),
+                StepKind::Answer => ("→", cx.theme().primary),
+                StepKind::Warning => ("!", cx.theme().warning),
+            };
+            h_flex()
+                .gap_2()
+                .items_start()
+                .text_color(color)
+                .child(div().w(px(12.)).child(marker))
+                .child(div().flex_1().child(step.message.clone()))
+        }))
+        .child(div().pt_2().font_weight(gpui::FontWeight::MEDIUM).child(
+            match resolution.answers.len() {
+                0 => format!("{}: no answer", resolution.name),
+                count => format!("{}: answered by {} record(s)", resolution.name, count),
+            },
+        ))
+}
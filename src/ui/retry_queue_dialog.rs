@@ -0,0 +1,126 @@
+use chrono::Utc;
+use gpui::prelude::*;
+use gpui::{App as GpuiApp, Entity, FontWeight, IntoElement, Window, div, px};
+use gpui_component::{
+    ActiveTheme, Disableable, Sizable, WindowExt,
+    button::{Button, ButtonVariants},
+    h_flex,
+    scroll::ScrollableElement,
+    v_flex,
+};
+
+use crate::App;
+use crate::zone_settings::format_remaining;
+
+/// Failed record changes waiting to be retried, to retry them right away or cancel them
+pub fn open_retry_queue_dialog(app: Entity<App>, window: &mut Window, cx: &mut GpuiApp) {
+    window.open_dialog(cx, move |dialog, _, cx| {
+        let items = app.read(cx).retry_queue.items().to_vec();
+        let muted_foreground = cx.theme().muted_foreground;
+        let danger = cx.theme().danger;
+        let now = Utc::now();
+
+        let app = app.clone();
+        dialog
+            .title(format!("{} changes waiting to be retried", items.len()))
+            .w(px(640.))
+            .child(
+                v_flex()
+                    .gap_3()
+                    .child(div().text_sm().text_color(muted_foreground).child(
+                        "These changes failed, e.g. because the connection dropped. They are \
+                         retried automatically with growing pauses until they go through.",
+                    ))
+                    .when(items.is_empty(), |this| {
+                        this.child(div().text_sm().child("All changes went through."))
+                    })
+                    .child(
+                        v_flex()
+                            .max_h(px(400.))
+                            .overflow_y_scrollbar()
+                            .gap_2()
+                            .children(items.into_iter().map(|item| {
+                                let status = if item.running {
+                                    "Retrying…".to_string()
+                                } else {
+                                    match item.next_attempt {
+                                        Some(next) => format!(
+                                            "Failed {} times, next attempt in {}",
+                                            item.attempts,
+                                            format_remaining(next - now)
+                                        ),
+                                        None => format!(
+                                            "Failed {} times, no more automatic attempts",
+                                            item.attempts
+                                        ),
+                                    }
+                                };
+                                let retry_app = app.clone();
+                                let cancel_app = app.clone();
+                                let id = item.id;
+
+                                h_flex()
+                                    .gap_2()
+                                    .pb_2()
+                                    .items_center()
+                                    .border_b_1()
+                                    .border_color(cx.theme().border)
+                                    .child(
+                                        v_flex()
+                                            .flex_1()
+                                            .gap_1()
+                                            .child(
+                                                div()
+                                                    .text_sm()
+                                                    .font_weight(FontWeight::MEDIUM)
+                                                    .child(item.description),
+                                            )
+                                            .child(
+                                                div()
+                                                    .text_xs()
+                                                    .text_color(muted_foreground)
+                                                    .child(status),
+                                            )
+                                            .child(
+                                                div()
+                                                    .text_xs()
+                                                    .text_color(danger)
+                                                    .child(item.last_error),
+                                            ),
+                                    )
+                                    .child(
+                                        Button::new(("retry-now", id as usize))
+                                            .xsmall()
+                                            .label("Retry Now")
+                                            .disabled(item.running)
+                                            .on_click(move |_, window, cx| {
+                                                retry_app.update(cx, |this, cx| {
+                                                    this.retry_record_command(id, window, cx);
+                                                });
+                                            }),
+                                    )
+                                    .child(
+                                        Button::new(("cancel-retry", id as usize))
+                                            .ghost()
+                                            .xsmall()
+                                            .label("Cancel")
+                                            .disabled(item.running)
+                                            .on_click(move |_, _, cx| {
+                                                cancel_app.update(cx, |this, cx| {
+                                                    this.cancel_retry(id, cx);
+                                                });
+                                            }),
+                                    )
+                            })),
+                    ),
+            )
+            .footer(|_, _, _, _| {
+                vec![
+                    Button::new("retry-queue-close")
+                        .primary()
+                        .label("Close")
+                        .on_click(|_, window, cx| window.close_dialog(cx)),
+                ]
+            })
+    });
+}
@@ -1,23 +1,46 @@
 use gpui::prelude::*;
-use gpui::{Context, FontWeight, IntoElement, Window, div, px};
+use gpui::{
+    ClipboardItem, Context, FontWeight, HighlightStyle, IntoElement, SharedString, StyledText,
+    Window, div, px,
+};
 use gpui_component::{
-    ActiveTheme, Disableable,
+    ActiveTheme, Disableable, Sizable,
     button::{Button, ButtonVariants},
+    checkbox::Checkbox,
     h_flex,
     input::Input,
+    menu::{DropdownMenu, PopupMenuItem},
+    scroll::ScrollableElement,
     select::Select,
     v_flex,
 };
 
-use crate::{App, AppearanceModeItem, Page};
+use super::render_help;
+use crate::changelog;
+use crate::cloudflare::{DnsRecordType, RATE_LIMIT_REQUESTS};
+use crate::help::HelpTopic;
+use crate::network_proxy::ProxySetting;
+use crate::plugins;
+use crate::profile_settings::Accent;
+use crate::settings_search::{SettingsSection, match_ranges};
+use crate::startup::StartupView;
+use crate::timestamps::TimeZoneDisplay;
+use crate::token_scopes::Capability;
+use crate::{App, AppearanceModeItem, Page, RECORD_REFRESH_INTERVALS, TextScaleItem};
 
 pub fn render_settings(
     app: &mut App,
     _window: &mut Window,
     cx: &mut Context<App>,
 ) -> impl IntoElement {
-    let is_loading = app.loading;
-    let error = app.error.clone();
+    let query = app
+        .settings_search_input
+        .read(cx)
+        .value()
+        .trim()
+        .to_string();
+    let shown = |section: SettingsSection| query.is_empty() || section.matches(&query);
+    let nothing_found = !SettingsSection::ALL.into_iter().any(shown);
 
     v_flex()
         .size_full()
@@ -46,114 +69,1127 @@ pub fn render_settings(
                         .text_lg()
                         .font_weight(FontWeight::SEMIBOLD)
                         .child("Settings"),
+                )
+                .child(div().flex_1())
+                .child(
+                    Input::new(&app.settings_search_input)
+                        .cleanable(true)
+                        .w(px(240.)),
                 ),
         )
         .child(
             v_flex()
+                .flex_1()
                 .p_6()
                 .gap_6()
                 .max_w(px(600.))
-                // API Token section
+                .overflow_y_scrollbar()
+                .when(nothing_found, |this| {
+                    this.child(
+                        div()
+                            .text_sm()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(format!("No settings match \"{}\"", query)),
+                    )
+                })
+                .when(shown(SettingsSection::ApiToken), |this| {
+                    this.child(render_api_token(app, &query, cx))
+                })
+                .when(shown(SettingsSection::Profile), |this| {
+                    this.child(render_profile(app, &query, cx))
+                })
+                .when(shown(SettingsSection::Appearance), |this| {
+                    this.child(render_appearance(app, &query, cx))
+                })
+                .when(shown(SettingsSection::Startup), |this| {
+                    this.child(render_startup(app, &query, cx))
+                })
+                .when(shown(SettingsSection::Records), |this| {
+                    this.child(render_records(app, &query, cx))
+                })
+                .when(shown(SettingsSection::NewRecords), |this| {
+                    this.child(render_record_defaults(app, &query, cx))
+                })
+                .when(shown(SettingsSection::Network), |this| {
+                    this.child(render_network_proxy(app, &query, cx))
+                })
+                .when(shown(SettingsSection::LocalAutomation), |this| {
+                    this.child(render_control_server(app, &query, cx))
+                })
+                .when(shown(SettingsSection::Plugins), |this| {
+                    this.child(render_plugins(app, &query, cx))
+                })
+                .when(shown(SettingsSection::About), |this| {
+                    this.child(render_about(&query, cx))
+                }),
+        )
+}
+
+/// Token the app signs in to Cloudflare with
+fn render_api_token(app: &App, query: &str, cx: &mut Context<App>) -> impl IntoElement {
+    let is_loading = app.loading;
+    let error = app.error.clone();
+
+    v_flex()
+        .gap_4()
+        .child(
+            v_flex()
+                .gap_1()
                 .child(
-                    v_flex()
-                        .gap_4()
-                        .child(
-                            v_flex()
-                                .gap_1()
-                                .child(div().font_weight(FontWeight::SEMIBOLD).child("API Token"))
-                                .child(
-                                    div()
-                                        .text_sm()
-                                        .text_color(cx.theme().muted_foreground)
-                                        .child("Update your Cloudflare API token"),
-                                ),
-                        )
-                        .child(Input::new(&app.settings_token_input))
-                        .map(|this| {
-                            if let Some(err) = error {
-                                this.child(
-                                    div()
-                                        .px_3()
-                                        .py_2()
-                                        .rounded_md()
-                                        .bg(cx.theme().danger.opacity(0.1))
-                                        .text_color(cx.theme().danger)
-                                        .text_sm()
-                                        .child(err),
-                                )
-                            } else {
-                                this
-                            }
+                    h_flex()
+                        .gap_1()
+                        .items_center()
+                        .child(render_section_title(SettingsSection::ApiToken, query, cx))
+                        .child(render_help(HelpTopic::ApiToken)),
+                )
+                .child(
+                    div()
+                        .text_sm()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(if app.profiles.len() > 1 {
+                            format!(
+                                "Update the Cloudflare API token of the {} profile",
+                                app.active_profile
+                            )
+                        } else {
+                            "Update your Cloudflare API token".to_string()
+                        }),
+                ),
+        )
+        .child(Input::new(&app.settings_token_input))
+        .map(|this| {
+            if let Some(err) = error {
+                this.child(
+                    div()
+                        .px_3()
+                        .py_2()
+                        .rounded_md()
+                        .bg(cx.theme().danger.opacity(0.1))
+                        .text_color(cx.theme().danger)
+                        .text_sm()
+                        .child(err),
+                )
+            } else {
+                this
+            }
+        })
+        .child(
+            h_flex()
+                .gap_2()
+                .child(
+                    Button::new("update-token")
+                        .primary()
+                        .label(if is_loading {
+                            "Verifying..."
+                        } else {
+                            "Update Token"
                         })
-                        .child(
+                        .disabled(is_loading)
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            this.update_token_from_settings(window, cx);
+                        })),
+                )
+                .child(
+                    Button::new("settings-add-profile")
+                        .label("Add Profile")
+                        .tooltip("Keep another API token, e.g. for work or a client")
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            this.open_add_profile_dialog(window, cx);
+                        })),
+                )
+                .child(
+                    Button::new("clear-token")
+                        .danger()
+                        .label(if app.profiles.len() > 1 {
+                            "Remove Profile"
+                        } else {
+                            "Clear Token"
+                        })
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            this.clear_token(window, cx);
+                        })),
+                ),
+        )
+        .child(
+            v_flex()
+                .gap_1()
+                .child(
+                    Checkbox::new("read-only")
+                        .label("Read-only mode")
+                        .checked(app.read_only())
+                        .disabled(app.read_only_token() || app.read_only_profile())
+                        .on_click(cx.listener(|this, read_only, _, cx| {
+                            this.set_read_only(*read_only, cx);
+                        })),
+                )
+                .child(
+                    div()
+                        .text_sm()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(if app.read_only_token() {
+                            "The token can only read, so creating, changing and deleting are off"
+                        } else if app.read_only_profile() {
+                            "This profile is always read-only; change that under Profile"
+                        } else {
+                            "Turn off creating, changing and deleting anything on Cloudflare, \
+                             e.g. to look around a production account safely"
+                        }),
+                ),
+        )
+        .when(app.client.is_some(), |this| {
+            this.child(render_token_scopes(app, cx))
+        })
+}
+
+/// What the token may do and on which zones; the app hides what it can't
+fn render_token_scopes(app: &App, cx: &mut Context<App>) -> impl IntoElement {
+    let muted_foreground = cx.theme().muted_foreground;
+    let success = cx.theme().success;
+    let zones = app.zones(cx);
+    let loaded = app.token_scopes.is_some() || app.token_scopes_error.is_some();
+    let expiring = app.token_expiring_on().is_some();
+
+    v_flex()
+        .gap_2()
+        .child(
+            div()
+                .text_sm()
+                .font_weight(FontWeight::MEDIUM)
+                .child("Permissions"),
+        )
+        // Read along with the permissions
+        .when(loaded, |this| {
+            this.child(
+                div()
+                    .text_sm()
+                    .text_color(if expiring {
+                        cx.theme().warning
+                    } else {
+                        muted_foreground
+                    })
+                    .child(match app.token_expires_on {
+                        Some(expires_on) => {
+                            format!("Expires {}", app.time_zone_display.format(expires_on))
+                        }
+                        None => "Doesn't expire".to_string(),
+                    }),
+            )
+        })
+        .map(|this| match (&app.token_scopes, &app.token_scopes_error) {
+            (Some(scopes), _) => {
+                let missing = Capability::ALL
+                    .into_iter()
+                    .any(|capability| !scopes.allows(capability));
+
+                this.child(div().text_sm().text_color(muted_foreground).child(format!(
+                    "{} · {}",
+                    scopes.name,
+                    scopes.zones.summary(&zones)
+                )))
+                .child(
+                    v_flex()
+                        .gap_1()
+                        .children(Capability::ALL.into_iter().map(|capability| {
+                            let allowed = scopes.allows(capability);
                             h_flex()
                                 .gap_2()
+                                .text_sm()
                                 .child(
-                                    Button::new("update-token")
-                                        .primary()
-                                        .label(if is_loading {
-                                            "Verifying..."
+                                    div()
+                                        .w(px(16.))
+                                        .text_color(if allowed {
+                                            success
                                         } else {
-                                            "Update Token"
+                                            muted_foreground
                                         })
-                                        .disabled(is_loading)
-                                        .on_click(cx.listener(|this, _, window, cx| {
-                                            this.update_token_from_settings(window, cx);
-                                        })),
+                                        .child(if allowed { "✓" } else { "–" }),
                                 )
                                 .child(
-                                    Button::new("clear-token")
-                                        .danger()
-                                        .label("Clear Token")
-                                        .on_click(cx.listener(|this, _, _, cx| {
-                                            this.clear_token(cx);
-                                        })),
-                                ),
+                                    div()
+                                        .when(!allowed, |this| this.text_color(muted_foreground))
+                                        .child(capability.label()),
+                                )
+                        })),
+                )
+                .child(
+                    div()
+                        .text_xs()
+                        .text_color(muted_foreground)
+                        .child(format!("Granted: {}", scopes.permissions.join(", "))),
+                )
+                .when(missing, |this| {
+                    this.child(
+                        div()
+                            .text_xs()
+                            .text_color(muted_foreground)
+                            .child("Sections the token lacks a permission for are hidden."),
+                    )
+                })
+            }
+            (None, Some(error)) => {
+                this.child(div().text_sm().text_color(muted_foreground).child(format!(
+                    "The token's permissions couldn't be read, so every section is shown \
+                         ({}). Reading them requires the User › API Tokens › Read permission.",
+                    error
+                )))
+            }
+            (None, None) => this.child(
+                div()
+                    .text_sm()
+                    .text_color(muted_foreground)
+                    .child("Reading the token's permissions…"),
+            ),
+        })
+}
+
+/// Theme, text size and how timestamps are shown
+/// Settings the active profile overrides, e.g. to tell a production account apart and lock it
+/// down
+fn render_profile(app: &App, query: &str, cx: &mut Context<App>) -> impl IntoElement {
+    let muted_foreground = cx.theme().muted_foreground;
+    let settings = app.active_profile_settings();
+    let accent = settings.accent;
+    let accents = [(None, "Theme")]
+        .into_iter()
+        .chain(Accent::ALL.map(|option| (Some(option), option.label())));
+
+    v_flex()
+        .gap_4()
+        .pt_4()
+        .border_t_1()
+        .border_color(cx.theme().border)
+        .child(
+            v_flex()
+                .gap_1()
+                .child(render_section_title(SettingsSection::Profile, query, cx))
+                .child(div().text_sm().text_color(muted_foreground).child(format!(
+                    "Settings of the {} profile that replace the app-wide ones, e.g. to tell \
+                     a production account apart and lock it down",
+                    app.active_profile
+                ))),
+        )
+        .child(
+            v_flex()
+                .gap_1()
+                .child(div().text_sm().child("Accent color"))
+                .child(
+                    h_flex()
+                        .gap_1()
+                        .flex_wrap()
+                        .children(accents.enumerate().map(|(index, (option, label))| {
+                            Button::new(("profile-accent", index))
+                                .xsmall()
+                                .map(|this| {
+                                    if option == accent {
+                                        this.primary()
+                                    } else {
+                                        this.ghost()
+                                    }
+                                })
+                                .label(label)
+                                .on_click(cx.listener(move |this, _, window, cx| {
+                                    this.set_profile_accent(option, window, cx);
+                                }))
+                        })),
+                ),
+        )
+        .child(
+            v_flex()
+                .gap_1()
+                .child(div().text_sm().child("Default TTL"))
+                .child(Input::new(&app.profile_ttl_input).w(px(160.)))
+                .child(
+                    div()
+                        .text_xs()
+                        .text_color(muted_foreground)
+                        .child("TTL new records start with while this profile is active"),
+                ),
+        )
+        .child(
+            Checkbox::new("profile-read-only")
+                .label("Always read-only")
+                .checked(settings.read_only)
+                .on_click(cx.listener(|this, read_only, _, cx| {
+                    this.set_profile_read_only(*read_only, cx);
+                })),
+        )
+        .child(
+            v_flex()
+                .gap_1()
+                .child(
+                    Checkbox::new("profile-strict-guardrails")
+                        .label("Strict guardrails")
+                        .checked(settings.strict_guardrails)
+                        .on_click(cx.listener(|this, strict, _, cx| {
+                            this.set_profile_strict_guardrails(*strict, cx);
+                        })),
+                )
+                .child(div().text_xs().text_color(muted_foreground).child(
+                    "Deleting records in bulk always takes typing the zone's name, and \
+                             duplicate records are refused",
+                )),
+        )
+}
+
+fn render_appearance(app: &App, query: &str, cx: &mut Context<App>) -> impl IntoElement {
+    v_flex()
+        .gap_4()
+        .pt_4()
+        .border_t_1()
+        .border_color(cx.theme().border)
+        .child(
+            v_flex()
+                .gap_1()
+                .child(render_section_title(SettingsSection::Appearance, query, cx))
+                .child(
+                    div()
+                        .text_sm()
+                        .text_color(cx.theme().muted_foreground)
+                        .child("Choose your preferred color theme"),
+                ),
+        )
+        .child(Select::<Vec<AppearanceModeItem>>::new(&app.appearance_mode_select).w(px(200.)))
+        .child(
+            v_flex()
+                .gap_1()
+                .child(div().text_sm().child("Text Size"))
+                .child(Select::<Vec<TextScaleItem>>::new(&app.text_scale_select).w(px(200.))),
+        )
+        .child(
+            Checkbox::new("utc-timestamps")
+                .label("Show timestamps in UTC instead of local time")
+                .checked(app.time_zone_display == TimeZoneDisplay::Utc)
+                .on_click(cx.listener(|this, utc, _, cx| {
+                    this.set_time_zone_display(
+                        if *utc {
+                            TimeZoneDisplay::Utc
+                        } else {
+                            TimeZoneDisplay::Local
+                        },
+                        cx,
+                    );
+                })),
+        )
+}
+
+/// Page the app opens to
+fn render_startup(app: &App, query: &str, cx: &mut Context<App>) -> impl IntoElement {
+    let entity = cx.entity();
+    let zones = app.zones(cx);
+    let startup_view = app.startup_view.clone();
+
+    v_flex()
+        .gap_4()
+        .pt_4()
+        .border_t_1()
+        .border_color(cx.theme().border)
+        .child(
+            v_flex()
+                .gap_1()
+                .child(render_section_title(SettingsSection::Startup, query, cx))
+                .child(
+                    div()
+                        .text_sm()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(
+                            "Choose what the app opens to. The zones and the records of the zone \
+                             it opens to are kept between launches, so they show right away \
+                             while fresh ones load.",
+                        ),
+                ),
+        )
+        .child(
+            h_flex()
+                .gap_3()
+                .items_center()
+                .child(div().text_sm().child("Open to"))
+                .child(
+                    Button::new("startup-view")
+                        .outline()
+                        .w(px(280.))
+                        .label(startup_view.label(&zones))
+                        .dropdown_menu(move |menu, _, _| {
+                            let mut menu = menu;
+                            for view in [StartupView::Portfolio, StartupView::LastZone] {
+                                let entity = entity.clone();
+                                menu = menu.item(
+                                    PopupMenuItem::new(view.label(&zones))
+                                        .checked(view == startup_view)
+                                        .on_click(move |_, _, cx| {
+                                            let view = view.clone();
+                                            entity.update(cx, |this, cx| {
+                                                this.set_startup_view(view, cx)
+                                            });
+                                        }),
+                                );
+                            }
+                            if !zones.is_empty() {
+                                menu = menu.separator();
+                            }
+                            for zone in zones.iter() {
+                                let entity = entity.clone();
+                                let view = StartupView::PinnedZone(zone.id.clone());
+                                menu = menu.item(
+                                    PopupMenuItem::new(zone.name.clone())
+                                        .checked(view == startup_view)
+                                        .on_click(move |_, _, cx| {
+                                            let view = view.clone();
+                                            entity.update(cx, |this, cx| {
+                                                this.set_startup_view(view, cx)
+                                            });
+                                        }),
+                                );
+                            }
+                            menu
+                        }),
+                ),
+        )
+}
+
+/// How records are refreshed, checked, exported and protected
+fn render_records(app: &App, query: &str, cx: &mut Context<App>) -> impl IntoElement {
+    let entity = cx.entity();
+    let refresh_interval = app.record_refresh_interval;
+
+    v_flex()
+        .gap_4()
+        .pt_4()
+        .border_t_1()
+        .border_color(cx.theme().border)
+        .child(
+            v_flex()
+                .gap_1()
+                .child(render_section_title(SettingsSection::Records, query, cx))
+                .child(
+                    div()
+                        .text_sm()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(
+                            "Creating a record with the same type, name and \
+                             content as an existing one asks for confirmation",
                         ),
+                ),
+        )
+        .child(
+            h_flex()
+                .gap_3()
+                .items_center()
+                .child(div().text_sm().child("Auto-refresh"))
+                .child(
+                    Button::new("record-refresh-interval")
+                        .outline()
+                        .w(px(200.))
+                        .label(refresh_interval_label(refresh_interval))
+                        .dropdown_menu(move |menu, _, _| {
+                            let options = std::iter::once(None)
+                                .chain(RECORD_REFRESH_INTERVALS.into_iter().map(Some));
+                            options.fold(menu, |menu, interval| {
+                                let entity = entity.clone();
+                                menu.item(
+                                    PopupMenuItem::new(refresh_interval_label(interval))
+                                        .checked(interval == refresh_interval)
+                                        .on_click(move |_, window, cx| {
+                                            entity.update(cx, |this, cx| {
+                                                this.set_record_refresh_interval(
+                                                    interval, window, cx,
+                                                )
+                                            });
+                                        }),
+                                )
+                            })
+                        }),
                 )
-                // Appearance section
                 .child(
-                    v_flex()
-                        .gap_4()
-                        .pt_4()
-                        .border_t_1()
-                        .border_color(cx.theme().border)
+                    div()
+                        .text_sm()
+                        .text_color(cx.theme().muted_foreground)
+                        .child("Reloads the records on the dashboard, keeping the selection"),
+                ),
+        )
+        .child(
+            Checkbox::new("block-duplicate-records")
+                .label("Block duplicate records instead of asking")
+                .checked(app.block_duplicate_records)
+                .on_click(cx.listener(|this, block, _, cx| {
+                    this.set_block_duplicate_records(*block, cx);
+                })),
+        )
+        .child(
+            Checkbox::new("compare-exports")
+                .label("Show changes since the previous zone file export")
+                .checked(app.compare_exports)
+                .on_click(cx.listener(|this, compare, _, cx| {
+                    this.set_compare_exports(*compare, cx);
+                })),
+        )
+        .child(
+            h_flex()
+                .gap_2()
+                .items_center()
+                .child(
+                    Button::new("import-terraform-state")
+                        .small()
+                        .label("Import Terraform State…")
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            this.import_terraform_state(window, cx);
+                        })),
+                )
+                .child(
+                    div()
+                        .text_sm()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(format!(
+                            "{} protected record(s)",
+                            app.protected_records.len()
+                        )),
+                ),
+        )
+}
+
+/// E.g. "Every 5 minutes", or "Off"
+fn refresh_interval_label(interval: Option<u64>) -> String {
+    match interval {
+        None => "Off".to_string(),
+        Some(60) => "Every minute".to_string(),
+        Some(seconds) if seconds < 60 => format!("Every {} seconds", seconds),
+        Some(seconds) => format!("Every {} minutes", seconds / 60),
+    }
+}
+
+/// Version of the app and what changed in it
+fn render_about(query: &str, cx: &mut Context<App>) -> impl IntoElement {
+    v_flex()
+        .gap_2()
+        .pt_4()
+        .border_t_1()
+        .border_color(cx.theme().border)
+        .child(render_section_title(SettingsSection::About, query, cx))
+        .child(
+            div()
+                .text_sm()
+                .text_color(cx.theme().muted_foreground)
+                .child(format!(
+                    "Cloudflare DNS Manager v{}",
+                    changelog::CURRENT_VERSION
+                )),
+        )
+        .child(
+            div()
+                .text_sm()
+                .text_color(cx.theme().muted_foreground)
+                .child("Built with GPUI and gpui-component"),
+        )
+        .child(
+            h_flex().child(
+                Button::new("open-changelog")
+                    .small()
+                    .label("What's New")
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.open_changelog(window, cx);
+                    })),
+            ),
+        )
+}
+
+/// Title of a settings section with the words of the search highlighted, and the section's
+/// settings that match it
+fn render_section_title(
+    section: SettingsSection,
+    query: &str,
+    cx: &Context<App>,
+) -> impl IntoElement {
+    let highlight = HighlightStyle {
+        background_color: Some(cx.theme().warning.opacity(0.3)),
+        ..Default::default()
+    };
+    let highlighted = move |text: &'static str| {
+        StyledText::new(text).with_highlights(
+            match_ranges(text, query)
+                .into_iter()
+                .map(|range| (range, highlight)),
+        )
+    };
+    let matching = section.matching_items(query);
+
+    v_flex()
+        .gap_1()
+        .child(
+            div()
+                .font_weight(FontWeight::SEMIBOLD)
+                .child(highlighted(section.title())),
+        )
+        .when(!matching.is_empty(), |this| {
+            this.child(
+                h_flex()
+                    .gap_1()
+                    .flex_wrap()
+                    .text_xs()
+                    .children(matching.into_iter().map(|item| {
+                        div()
+                            .px_1()
+                            .rounded_sm()
+                            .border_1()
+                            .border_color(cx.theme().border)
+                            .child(highlighted(item))
+                    })),
+            )
+        })
+}
+
+/// Values new records start with in the editor
+fn render_record_defaults(app: &App, query: &str, cx: &mut Context<App>) -> impl IntoElement {
+    v_flex()
+        .gap_4()
+        .pt_4()
+        .border_t_1()
+        .border_color(cx.theme().border)
+        .child(
+            v_flex()
+                .gap_1()
+                .child(render_section_title(SettingsSection::NewRecords, query, cx))
+                .child(
+                    div()
+                        .text_sm()
+                        .text_color(cx.theme().muted_foreground)
+                        .child("Values the editor starts with when creating a record"),
+                ),
+        )
+        .child(
+            v_flex()
+                .gap_1()
+                .child(div().text_sm().child("TTL"))
+                .child(Input::new(&app.default_ttl_input).w(px(120.))),
+        )
+        .child(
+            v_flex()
+                .gap_1()
+                .child(div().text_sm().child("Comment"))
+                .child(Input::new(&app.default_comment_input))
+                .child(
+                    div()
+                        .text_xs()
+                        .text_color(cx.theme().muted_foreground)
                         .child(
-                            v_flex()
-                                .gap_1()
-                                .child(div().font_weight(FontWeight::SEMIBOLD).child("Appearance"))
-                                .child(
-                                    div()
-                                        .text_sm()
-                                        .text_color(cx.theme().muted_foreground)
-                                        .child("Choose your preferred color theme"),
-                                ),
-                        )
+                            "$user, $date and $ticket are filled in when the record is saved; \
+                             $ticket is asked for",
+                        ),
+                ),
+        )
+        .child(
+            v_flex()
+                .gap_1()
+                .child(div().text_sm().child("Attribution"))
+                .child(Input::new(&app.default_attribution_input))
+                .child(
+                    div()
+                        .text_xs()
+                        .text_color(cx.theme().muted_foreground)
                         .child(
-                            Select::<Vec<AppearanceModeItem>>::new(&app.appearance_mode_select)
-                                .w(px(200.)),
+                            "Appended to every comment saved from the editor, replacing the \
+                             previous one; can use the same variables",
                         ),
+                ),
+        )
+        .child(
+            h_flex().gap_4().children(
+                DnsRecordType::all()
+                    .iter()
+                    .filter(|record_type| record_type.is_proxiable())
+                    .map(|&record_type| {
+                        Checkbox::new(SharedString::from(format!(
+                            "default-proxied-{}",
+                            record_type
+                        )))
+                        .label(format!("Proxy new {} records", record_type))
+                        .checked(app.record_defaults.proxied(record_type))
+                        .on_click(cx.listener(
+                            move |this, proxied, _, cx| {
+                                this.set_default_proxied(record_type, *proxied, cx);
+                            },
+                        ))
+                    }),
+            ),
+        )
+}
+
+/// How API requests reach Cloudflare, with a check of the route they take
+fn render_network_proxy(app: &App, query: &str, cx: &mut Context<App>) -> impl IntoElement {
+    let manual = matches!(app.proxy_setting, ProxySetting::Manual { .. });
+    let modes = [
+        ("proxy-automatic", "Automatic", ProxySetting::Automatic),
+        ("proxy-direct", "No Proxy", ProxySetting::Direct),
+    ];
+
+    v_flex()
+        .gap_4()
+        .pt_4()
+        .border_t_1()
+        .border_color(cx.theme().border)
+        .child(
+            v_flex()
+                .gap_1()
+                .child(render_section_title(SettingsSection::Network, query, cx))
+                .child(
+                    div()
+                        .text_sm()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(
+                            "API requests use the proxy configured in the environment or the \
+                             system network settings, unless it is turned off or set here",
+                        ),
+                ),
+        )
+        .child(
+            h_flex()
+                .gap_1()
+                .children(modes.into_iter().map(|(id, label, setting)| {
+                    Button::new(id)
+                        .small()
+                        .map(|this| {
+                            if app.proxy_setting == setting {
+                                this.primary()
+                            } else {
+                                this.ghost()
+                            }
+                        })
+                        .label(label)
+                        .on_click(cx.listener(move |this, _, window, cx| {
+                            this.set_proxy_setting(setting.clone(), window, cx);
+                        }))
+                }))
+                .child(
+                    Button::new("proxy-manual")
+                        .small()
+                        .map(|this| if manual { this.primary() } else { this.ghost() })
+                        .label("Manual")
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            this.set_manual_proxy(window, cx);
+                        })),
+                ),
+        )
+        .child(
+            h_flex()
+                .gap_2()
+                .items_center()
+                .child(div().text_sm().child("Proxy"))
+                .child(Input::new(&app.proxy_url_input).w(px(260.)))
+                .child(
+                    Button::new("apply-manual-proxy")
+                        .small()
+                        .label(if manual { "Update" } else { "Use This Proxy" })
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            this.set_manual_proxy(window, cx);
+                        })),
+                ),
+        )
+        .child(
+            h_flex()
+                .gap_2()
+                .items_center()
+                .child(
+                    div()
+                        .flex_1()
+                        .text_sm()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(match &app.proxy_route {
+                            Some(route) => route.describe(),
+                            None => "Detecting proxy…".to_string(),
+                        }),
                 )
-                // About section
+                .child(
+                    Button::new("check-connectivity")
+                        .small()
+                        .label("Test Connection")
+                        .disabled(app.loading)
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            this.check_connectivity(window, cx);
+                        })),
+                )
+                .child(
+                    Button::new("network-diagnostics")
+                        .small()
+                        .ghost()
+                        .label("Diagnostics")
+                        .tooltip("Check IPv4, IPv6 and DNS resolution of the API")
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            this.open_network_diagnostics(window, cx);
+                        })),
+                ),
+        )
+        .when_some(app.proxy_check.clone(), |this, check| {
+            this.child(
+                div()
+                    .text_sm()
+                    .text_color(if check.is_ok() {
+                        cx.theme().success
+                    } else {
+                        cx.theme().danger
+                    })
+                    .child(check.unwrap_or_else(|e| e)),
+            )
+        })
+        .child(render_api_usage(app, cx))
+}
+
+/// Requests sent today with each profile's token, by zone
+fn render_api_usage(app: &App, cx: &mut Context<App>) -> impl IntoElement {
+    let muted_foreground = cx.theme().muted_foreground;
+    let usage = app.api_usage_today();
+    let zones = app.zones(cx);
+    // Zones of other profiles aren't loaded, so they go by id
+    let zone_label = |zone_id: Option<&str>| match zone_id {
+        Some(zone_id) => zones
+            .iter()
+            .find(|zone| zone.id == zone_id)
+            .map_or_else(|| zone_id.to_string(), |zone| zone.name.clone()),
+        None => "Account-wide".to_string(),
+    };
+    let profiles: Vec<(String, u32)> = app
+        .profiles
+        .iter()
+        .map(|profile| (profile.clone(), usage.profile_total(profile)))
+        .filter(|(_, total)| *total > 0)
+        .collect();
+
+    v_flex()
+        .gap_2()
+        .child(
+            div()
+                .text_sm()
+                .font_weight(FontWeight::MEDIUM)
+                .child("API usage today"),
+        )
+        .child(div().text_sm().text_color(muted_foreground).child(format!(
+            "Requests the app sent since midnight. Cloudflare allows {} per five minutes, \
+             shared with scripts and other tools using the same account.",
+            RATE_LIMIT_REQUESTS
+        )))
+        .when(profiles.is_empty(), |this| {
+            this.child(
+                div()
+                    .text_sm()
+                    .text_color(muted_foreground)
+                    .child("No requests yet today"),
+            )
+        })
+        .children(profiles.into_iter().map(|(profile, total)| {
+            v_flex()
+                .gap_1()
+                .child(
+                    h_flex()
+                        .justify_between()
+                        .text_sm()
+                        .child(profile.clone())
+                        .child(format!("{} calls", total)),
+                )
+                .children(
+                    usage
+                        .zone_counts(&profile)
+                        .into_iter()
+                        .map(|(zone_id, count)| {
+                            h_flex()
+                                .justify_between()
+                                .pl_3()
+                                .text_xs()
+                                .text_color(muted_foreground)
+                                .child(zone_label(zone_id))
+                                .child(count.to_string())
+                        }),
+                )
+        }))
+}
+
+/// Installed plugins and whether they are allowed to run
+fn render_plugins(app: &App, query: &str, cx: &mut Context<App>) -> impl IntoElement {
+    let plugins_dir = plugins::plugins_dir()
+        .map(|dir| dir.display().to_string())
+        .unwrap_or_default();
+
+    v_flex()
+        .gap_4()
+        .pt_4()
+        .border_t_1()
+        .border_color(cx.theme().border)
+        .child(
+            h_flex()
+                .justify_between()
+                .items_center()
                 .child(
                     v_flex()
-                        .gap_2()
-                        .pt_4()
-                        .border_t_1()
-                        .border_color(cx.theme().border)
-                        .child(div().font_weight(FontWeight::SEMIBOLD).child("About"))
+                        .gap_1()
+                        .child(render_section_title(SettingsSection::Plugins, query, cx))
                         .child(
                             div()
                                 .text_sm()
                                 .text_color(cx.theme().muted_foreground)
-                                .child("Cloudflare DNS Manager v0.1.0"),
-                        )
+                                .child(format!("Plugins are loaded from {}", plugins_dir)),
+                        ),
+                )
+                .child(
+                    Button::new("reload-plugins")
+                        .ghost()
+                        .small()
+                        .label("Reload")
+                        .on_click(cx.listener(|this, _, _, cx| {
+                            this.reload_plugins(cx);
+                        })),
+                ),
+        )
+        .when(app.plugins.is_empty(), |this| {
+            this.child(
+                div()
+                    .text_sm()
+                    .text_color(cx.theme().muted_foreground)
+                    .child("No plugins installed"),
+            )
+        })
+        .children(app.plugins.iter().enumerate().map(|(index, plugin)| {
+            let approved = app.is_plugin_approved(plugin);
+
+            h_flex()
+                .gap_3()
+                .items_center()
+                .justify_between()
+                .child(
+                    v_flex()
+                        .child(div().text_sm().font_weight(FontWeight::MEDIUM).child(
+                            if plugin.manifest.version.is_empty() {
+                                plugin.manifest.name.clone()
+                            } else {
+                                format!("{} {}", plugin.manifest.name, plugin.manifest.version)
+                            },
+                        ))
+                        .when(!plugin.manifest.description.is_empty(), |this| {
+                            this.child(
+                                div()
+                                    .text_xs()
+                                    .text_color(cx.theme().muted_foreground)
+                                    .child(plugin.manifest.description.clone()),
+                            )
+                        }),
+                )
+                .child(if approved {
+                    Button::new(("revoke-plugin", index))
+                        .small()
+                        .label("Revoke")
+                        .on_click(cx.listener(move |this, _, _, cx| {
+                            this.revoke_plugin(index, cx);
+                        }))
+                } else {
+                    Button::new(("allow-plugin", index))
+                        .small()
+                        .primary()
+                        .label("Allow...")
+                        .on_click(cx.listener(move |this, _, window, cx| {
+                            this.open_plugin_approval_dialog(index, window, cx);
+                        }))
+                })
+        }))
+        .children(app.plugin_errors.iter().map(|error| {
+            div()
+                .text_xs()
+                .text_color(cx.theme().danger)
+                .child(error.clone())
+        }))
+}
+
+/// Local HTTP server that lets scripts and launchers use the app's API token
+fn render_control_server(app: &App, query: &str, cx: &mut Context<App>) -> impl IntoElement {
+    let running_port = app.control_server.as_ref().map(|server| server.port);
+    let enabled = app.control_server_settings.enabled;
+    let token = app.control_server_settings.token.clone();
+
+    v_flex()
+        .gap_4()
+        .pt_4()
+        .border_t_1()
+        .border_color(cx.theme().border)
+        .child(
+            v_flex()
+                .gap_1()
+                .child(render_section_title(
+                    SettingsSection::LocalAutomation,
+                    query,
+                    cx,
+                ))
+                .child(
+                    div()
+                        .text_sm()
+                        .text_color(cx.theme().muted_foreground)
                         .child(
-                            div()
-                                .text_sm()
-                                .text_color(cx.theme().muted_foreground)
-                                .child("Built with GPUI and gpui-component"),
+                            "Let local scripts and tools like Raycast or Alfred list, create and \
+                             update records through an HTTP server on this computer",
                         ),
                 ),
         )
+        .child(
+            h_flex()
+                .gap_2()
+                .items_center()
+                .child(div().text_sm().child("Port"))
+                .child(render_help(HelpTopic::ControlServerPort))
+                .child(
+                    Input::new(&app.control_server_port_input)
+                        .w(px(100.))
+                        .disabled(enabled),
+                )
+                .child(if enabled {
+                    Button::new("disable-control-server")
+                        .label("Stop Server")
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            this.set_control_server_enabled(false, window, cx);
+                        }))
+                } else {
+                    Button::new("enable-control-server")
+                        .primary()
+                        .label("Start Server")
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            this.set_control_server_enabled(true, window, cx);
+                        }))
+                })
+                .when_some(running_port, |this, port| {
+                    this.child(
+                        div()
+                            .text_sm()
+                            .text_color(cx.theme().success)
+                            .child(format!("Listening on http://127.0.0.1:{}", port)),
+                    )
+                }),
+        )
+        .when(enabled, |this| {
+            this.child(
+                h_flex()
+                    .gap_2()
+                    .items_center()
+                    .child(div().text_sm().child("Token"))
+                    .child(render_help(HelpTopic::ControlServerToken))
+                    .child(
+                        div()
+                            .flex_1()
+                            .overflow_hidden()
+                            .text_sm()
+                            .font_family("monospace")
+                            .child(token.clone()),
+                    )
+                    .child(
+                        Button::new("copy-control-server-token")
+                            .ghost()
+                            .small()
+                            .icon(gpui_component::IconName::Copy)
+                            .tooltip("Copy token")
+                            .on_click(move |_, _, cx| {
+                                cx.write_to_clipboard(ClipboardItem::new_string(token.clone()));
+                            }),
+                    )
+                    .child(
+                        Button::new("regenerate-control-server-token")
+                            .ghost()
+                            .small()
+                            .label("Regenerate")
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.regenerate_control_server_token(window, cx);
+                            })),
+                    ),
+            )
+        })
 }
@@ -0,0 +1,128 @@
+use gpui::prelude::*;
+use gpui::{App as GpuiApp, Entity, FontWeight, IntoElement, Window, div, px};
+use gpui_component::{
+    ActiveTheme, Sizable, WindowExt,
+    button::{Button, ButtonVariants},
+    h_flex,
+    scroll::ScrollableElement,
+    v_flex,
+};
+
+use super::render_field_change;
+use crate::App;
+use crate::watch::diff_record;
+
+/// Staged changes of records that were changed on Cloudflare since they were staged, each with
+/// a choice of keeping the staged change, taking the remote record, or skipping it for now
+pub fn open_staged_conflicts_dialog(app: Entity<App>, window: &mut Window, cx: &mut GpuiApp) {
+    window.open_dialog(cx, move |dialog, _, cx| {
+        let state = app.read(cx);
+        let changes = state.zone_staged_changes(cx).to_vec();
+        let conflicts = state.staged_conflicts.clone();
+        let muted_foreground = cx.theme().muted_foreground;
+
+        let app = app.clone();
+        dialog
+            .title(format!("{} staged changes conflict", conflicts.len()))
+            .w(px(640.))
+            .child(
+                v_flex()
+                    .gap_3()
+                    .child(div().text_sm().text_color(muted_foreground).child(
+                        "These records were changed on Cloudflare after the changes were staged. \
+                         Staged changes without a conflict are applied together with the ones \
+                         kept.",
+                    ))
+                    .child(
+                        v_flex()
+                            .max_h(px(400.))
+                            .overflow_y_scrollbar()
+                            .gap_2()
+                            .children(conflicts.iter().enumerate().filter_map(|(ix, conflict)| {
+                                let change = changes.get(conflict.index)?;
+                                let remote_changes = match (&change.before, &conflict.remote) {
+                                    (Some(before), Some(remote)) => diff_record(before, remote),
+                                    _ => Vec::new(),
+                                };
+
+                                Some(
+                                    v_flex()
+                                        .gap_1()
+                                        .pb_2()
+                                        .border_b_1()
+                                        .border_color(cx.theme().border)
+                                        .child(
+                                            div()
+                                                .text_sm()
+                                                .font_weight(FontWeight::MEDIUM)
+                                                .child(change.label.clone()),
+                                        )
+                                        .child(div().text_xs().text_color(muted_foreground).child(
+                                            if conflict.remote.is_none() {
+                                                "Deleted on Cloudflare"
+                                            } else {
+                                                "Changed on Cloudflare"
+                                            },
+                                        ))
+                                        .children(
+                                            remote_changes
+                                                .iter()
+                                                .map(|field| render_field_change(field, cx)),
+                                        )
+                                        .child(h_flex().gap_1().children(
+                                            conflict.resolutions().iter().enumerate().map(
+                                                |(option_ix, &resolution)| {
+                                                    let app = app.clone();
+                                                    Button::new((
+                                                        "conflict-resolution",
+                                                        ix * 3 + option_ix,
+                                                    ))
+                                                    .xsmall()
+                                                    .map(|this| {
+                                                        if resolution == conflict.resolution {
+                                                            this.primary()
+                                                        } else {
+                                                            this.ghost()
+                                                        }
+                                                    })
+                                                    .label(resolution.label())
+                                                    .on_click(move |_, _, cx| {
+                                                        app.update(cx, |this, cx| {
+                                                            this.set_conflict_resolution(
+                                                                ix, resolution, cx,
+                                                            );
+                                                        });
+                                                    })
+                                                },
+                                            ),
+                                        )),
+                                )
+                            })),
+                    ),
+            )
+            .footer(move |_, _, _, _| {
+                let cancel_app = app.clone();
+                let apply_app = app.clone();
+                vec![
+                    Button::new("conflicts-cancel").label("Cancel").on_click(
+                        move |_, window, cx| {
+                            window.close_dialog(cx);
+                            cancel_app.update(cx, |this, cx| {
+                                this.staged_conflicts.clear();
+                                cx.notify();
+                            });
+                        },
+                    ),
+                    Button::new("conflicts-apply")
+                        .primary()
+                        .label("Apply")
+                        .on_click(move |_, window, cx| {
+                            window.close_dialog(cx);
+                            apply_app.update(cx, |this, cx| {
+                                this.apply_resolved_staged_changes(window, cx);
+                            });
+                        }),
+                ]
+            })
+    });
+}
@@ -0,0 +1,114 @@
+use gpui::prelude::*;
+use gpui::{App as GpuiApp, Entity, FontWeight, IntoElement, Window, div, px};
+use gpui_component::{
+    ActiveTheme, Disableable, Sizable, WindowExt,
+    button::{Button, ButtonVariants},
+    h_flex,
+    scroll::ScrollableElement,
+    v_flex,
+};
+
+use crate::App;
+use crate::staging::DiffLine;
+use crate::token_scopes::Capability;
+
+/// The selected zone's staged changes as a diff, to apply or discard them together
+pub fn open_staging_dialog(app: Entity<App>, window: &mut Window, cx: &mut GpuiApp) {
+    window.open_dialog(cx, move |dialog, _, cx| {
+        let changes = app.read(cx).zone_staged_changes(cx).to_vec();
+        let count = changes.len();
+        let can_edit = app.read(cx).can(Capability::EditDns);
+        let muted_foreground = cx.theme().muted_foreground;
+        let danger = cx.theme().danger;
+        let success = cx.theme().success;
+
+        let app = app.clone();
+        dialog
+            .title(format!("{} staged changes", count))
+            .w(px(640.))
+            .child(
+                v_flex()
+                    .gap_3()
+                    .child(div().text_sm().text_color(muted_foreground).child(
+                        "Nothing has been sent to Cloudflare yet. Applying sends all changes in \
+                         one request, which deletes records first, then edits, then creates; if \
+                         one change fails, none are applied.",
+                    ))
+                    .child(
+                        v_flex()
+                            .max_h(px(400.))
+                            .overflow_y_scrollbar()
+                            .gap_2()
+                            .children(changes.iter().enumerate().map(|(ix, change)| {
+                                let app = app.clone();
+                                v_flex()
+                                    .gap_1()
+                                    .pb_2()
+                                    .border_b_1()
+                                    .border_color(cx.theme().border)
+                                    .child(
+                                        h_flex()
+                                            .gap_2()
+                                            .items_center()
+                                            .justify_between()
+                                            .child(
+                                                div()
+                                                    .text_sm()
+                                                    .font_weight(FontWeight::MEDIUM)
+                                                    .child(change.label.clone()),
+                                            )
+                                            .child(
+                                                Button::new(("unstage", ix))
+                                                    .ghost()
+                                                    .xsmall()
+                                                    .label("Remove")
+                                                    .on_click(move |_, _, cx| {
+                                                        app.update(cx, |this, cx| {
+                                                            this.unstage_change(ix, cx);
+                                                        });
+                                                    }),
+                                            ),
+                                    )
+                                    .children(change.diff().into_iter().map(|line| {
+                                        let (sign, text, color) = match line {
+                                            DiffLine::Removed(text) => ("-", text, danger),
+                                            DiffLine::Added(text) => ("+", text, success),
+                                        };
+                                        div()
+                                            .text_xs()
+                                            .font_family("monospace")
+                                            .text_color(color)
+                                            .child(format!("{} {}", sign, text))
+                                    }))
+                            })),
+                    ),
+            )
+            .footer(move |_, _, _, _| {
+                let discard_app = app.clone();
+                let apply_app = app.clone();
+                vec![
+                    Button::new("staging-discard")
+                        .danger()
+                        .label("Discard All")
+                        .disabled(count == 0)
+                        .on_click(move |_, window, cx| {
+                            window.close_dialog(cx);
+                            discard_app.update(cx, |this, cx| this.discard_staged_changes(cx));
+                        }),
+                    Button::new("staging-close")
+                        .label("Close")
+                        .on_click(|_, window, cx| window.close_dialog(cx)),
+                    Button::new("staging-apply")
+                        .primary()
+                        .label(format!("Apply {} Changes", count))
+                        .disabled(count == 0 || !can_edit)
+                        .on_click(move |_, window, cx| {
+                            window.close_dialog(cx);
+                            apply_app.update(cx, |this, cx| {
+                                this.apply_staged_changes(window, cx);
+                            });
+                        }),
+                ]
+            })
+    });
+}
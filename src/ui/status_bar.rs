@@ -0,0 +1,103 @@
+use gpui::prelude::*;
+use gpui::{Context, IntoElement, div};
+use gpui_component::{
+    ActiveTheme, Sizable,
+    button::{Button, ButtonVariants},
+    h_flex,
+};
+
+use super::render_task_indicator;
+use crate::App;
+
+pub fn render_status_bar(app: &App, cx: &mut Context<App>) -> impl IntoElement {
+    let muted_foreground = cx.theme().muted_foreground;
+    let is_demo = app.is_demo();
+    let connection = if is_demo {
+        "Demo mode: fake data, nothing is sent to Cloudflare"
+    } else if app.client.is_some() {
+        "Connected"
+    } else {
+        "Not connected"
+    };
+    let zone_name = app
+        .selected_zone(cx)
+        .map(|zone| zone.name)
+        .unwrap_or_else(|| "No zone selected".to_string());
+    let rate_limit = app.client.as_ref().map(|client| client.rate_limit());
+    let usage = app.api_usage_today();
+    let calls_today = usage.profile_total(&app.active_profile);
+    let zone_calls_today = app
+        .selected_zone(cx)
+        .map(|zone| usage.zone_total(&app.active_profile, &zone.id));
+    let cached_records = app.selected_zone_records(cx);
+    let record_count = cached_records
+        .as_ref()
+        .map_or(0, |cached| cached.records.len());
+    let last_refreshed = cached_records
+        .map(|cached| format!("Refreshed {}", cached.fetched_at.format("%H:%M:%S")))
+        .unwrap_or_else(|| "Not refreshed yet".to_string());
+
+    h_flex()
+        .w_full()
+        .px_4()
+        .py_1()
+        .gap_4()
+        .border_t_1()
+        .border_color(cx.theme().border)
+        .items_center()
+        .justify_between()
+        .text_xs()
+        .text_color(muted_foreground)
+        .child(
+            h_flex()
+                .gap_4()
+                .items_center()
+                .child(
+                    div()
+                        .when(is_demo, |this| this.text_color(cx.theme().warning))
+                        .child(connection),
+                )
+                .when(is_demo, |this| {
+                    this.child(
+                        Button::new("exit-demo")
+                            .ghost()
+                            .xsmall()
+                            .label("Exit Demo")
+                            .on_click(cx.listener(|this, _, _, cx| this.exit_demo(cx))),
+                    )
+                })
+                .child(div().child(zone_name))
+                .child(div().child(format!("{} records", record_count)))
+                .child(div().child(last_refreshed)),
+        )
+        .child(
+            h_flex()
+                .gap_4()
+                .items_center()
+                .child(render_task_indicator(app, cx))
+                .when(app.client.is_some() && !is_demo, |this| {
+                    this.child(div().child(match zone_calls_today {
+                        Some(zone_calls) => format!(
+                            "{} API calls today, {} for this zone",
+                            calls_today, zone_calls
+                        ),
+                        None => format!("{} API calls today", calls_today),
+                    }))
+                })
+                .map(|this| {
+                    let Some(rate_limit) = rate_limit else {
+                        return this;
+                    };
+                    // Highlight the budget once less than a tenth of it is left
+                    let low = rate_limit.remaining * 10 < rate_limit.limit;
+                    this.child(
+                        div()
+                            .when(low, |this| this.text_color(cx.theme().warning))
+                            .child(format!(
+                                "API budget {}/{}",
+                                rate_limit.remaining, rate_limit.limit
+                            )),
+                    )
+                }),
+        )
+}
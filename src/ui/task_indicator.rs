@@ -0,0 +1,43 @@
+use gpui::prelude::*;
+use gpui::{Context, IntoElement, div};
+use gpui_component::{
+    ActiveTheme, Sizable,
+    button::{Button, ButtonVariants},
+    h_flex,
+    spinner::Spinner,
+};
+
+use crate::App;
+
+/// Compact indicator of the running background tasks, with a cancel button for the oldest one
+pub fn render_task_indicator(app: &App, cx: &mut Context<App>) -> impl IntoElement {
+    let tasks = app.tasks.tasks();
+
+    h_flex().gap_2().items_center().map(|this| {
+        let Some(task) = tasks.first() else {
+            return this;
+        };
+
+        let task_id = task.id;
+        let mut label = match task.progress() {
+            Some((done, total)) => format!("{} ({}/{})", task.name, done, total),
+            None => format!("{}...", task.name),
+        };
+        if tasks.len() > 1 {
+            label.push_str(&format!(" +{} more", tasks.len() - 1));
+        }
+
+        this.child(Spinner::new().xsmall())
+            .child(div().text_color(cx.theme().muted_foreground).child(label))
+            .child(
+                Button::new("cancel-task")
+                    .ghost()
+                    .xsmall()
+                    .icon(gpui_component::IconName::Close)
+                    .tooltip("Cancel")
+                    .on_click(cx.listener(move |this, _, _, cx| {
+                        this.cancel_task(task_id, cx);
+                    })),
+            )
+    })
+}
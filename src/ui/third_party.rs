@@ -0,0 +1,53 @@
+use gpui::prelude::*;
+use gpui::{App as GpuiApp, Div, ElementId, IntoElement, Stateful, div};
+use gpui_component::{ActiveTheme, Icon, Sizable, h_flex, tooltip::Tooltip};
+
+use crate::third_party::ThirdParty;
+
+/// Platform a CNAME record ends at, with the CNAME chain on hover
+pub fn render_third_party_badge(
+    id: impl Into<ElementId>,
+    third_party: ThirdParty,
+    chain: String,
+    cx: &GpuiApp,
+) -> Stateful<Div> {
+    let muted_foreground = cx.theme().muted_foreground;
+
+    div()
+        .id(id)
+        .child(
+            h_flex()
+                .gap_1()
+                .px_1()
+                .py_px()
+                .rounded_sm()
+                .bg(muted_foreground.opacity(0.15))
+                .text_xs()
+                .text_color(muted_foreground)
+                .child(Icon::empty().path(third_party.icon_path()).xsmall())
+                .child(third_party.label()),
+        )
+        .tooltip(move |window, cx| Tooltip::new(chain.clone()).build(window, cx))
+}
+
+/// Third-party platforms the zone depends on, e.g. `Heroku 3  Vercel 1`
+pub fn render_third_party_summary(
+    counts: Vec<(ThirdParty, usize)>,
+    cx: &GpuiApp,
+) -> impl IntoElement {
+    let muted_foreground = cx.theme().muted_foreground;
+
+    h_flex()
+        .gap_3()
+        .items_center()
+        .text_xs()
+        .text_color(muted_foreground)
+        .child("Depends on")
+        .children(counts.into_iter().map(|(third_party, count)| {
+            h_flex()
+                .gap_1()
+                .items_center()
+                .child(Icon::empty().path(third_party.icon_path()).xsmall())
+                .child(format!("{} {}", third_party.label(), count))
+        }))
+}
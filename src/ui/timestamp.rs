@@ -0,0 +1,29 @@
+use chrono::{DateTime, Utc};
+use gpui::prelude::*;
+use gpui::{App as GpuiApp, Div, ElementId, IntoElement, Stateful, div};
+use gpui_component::{ActiveTheme, tooltip::Tooltip};
+
+use crate::timestamps::TimeZoneDisplay;
+
+/// Relative time like `Modified 3h ago`, with the full timestamp on hover
+pub fn render_timestamp(
+    id: impl Into<ElementId>,
+    prefix: &str,
+    time: DateTime<Utc>,
+    display: TimeZoneDisplay,
+    cx: &GpuiApp,
+) -> Stateful<Div> {
+    let relative = display.relative(time, Utc::now());
+    let full = display.format(time);
+
+    div()
+        .id(id)
+        .text_xs()
+        .text_color(cx.theme().muted_foreground)
+        .child(if prefix.is_empty() {
+            relative
+        } else {
+            format!("{} {}", prefix, relative)
+        })
+        .tooltip(move |window, cx| Tooltip::new(full.clone()).build(window, cx))
+}
@@ -1,13 +1,18 @@
 use gpui::prelude::*;
 use gpui::{Context, FontWeight, IntoElement, Window, div, px};
 use gpui_component::{
-    ActiveTheme, Disableable,
+    ActiveTheme, Disableable, Sizable,
     button::{Button, ButtonVariants},
+    checkbox::Checkbox,
+    h_flex,
     input::Input,
     v_flex,
 };
 
+use super::render_profile_switcher;
 use crate::App;
+use crate::token_scopes::Capability;
+use crate::token_template;
 
 pub fn render_token_setup(
     app: &mut App,
@@ -32,16 +37,30 @@ pub fn render_token_setup(
                         .font_weight(FontWeight::BOLD)
                         .child("Cloudflare DNS Manager"),
                 )
-                .child(
-                    div()
-                        .text_color(cx.theme().muted_foreground)
-                        .child("Enter your Cloudflare API token to get started"),
-                ),
+                .child(div().text_color(cx.theme().muted_foreground).child(
+                    if app.profiles.len() > 1 {
+                        format!(
+                            "Enter the Cloudflare API token of the {} profile",
+                            app.active_profile
+                        )
+                    } else {
+                        "Enter your Cloudflare API token to get started".to_string()
+                    },
+                ))
+                .when(app.profiles.len() > 1, |this| {
+                    this.child(render_profile_switcher("token-setup-profile", app, cx))
+                }),
         )
         .child(
             v_flex()
                 .w(px(400.))
                 .gap_4()
+                .when(!app.found_tokens.is_empty(), |this| {
+                    this.child(render_found_tokens(app, cx))
+                })
+                .when_some(app.token_wizard.clone(), |this, picked| {
+                    this.child(render_token_wizard(&picked, cx))
+                })
                 .child(Input::new(&app.token_input))
                 .map(|this| {
                     if let Some(error) = app.error.clone() {
@@ -72,6 +91,29 @@ pub fn render_token_setup(
                         .on_click(cx.listener(|this, _, window, cx| {
                             this.save_token(window, cx);
                         })),
+                )
+                .when(app.token_wizard.is_none(), |this| {
+                    this.child(
+                        Button::new("open-token-wizard")
+                            .ghost()
+                            .w_full()
+                            .label("Create a Token Step by Step")
+                            .tooltip("Pick what the app may do and create a matching token")
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.open_token_wizard(cx);
+                            })),
+                    )
+                })
+                .child(
+                    Button::new("start-demo")
+                        .ghost()
+                        .w_full()
+                        .label("Try with Demo Data")
+                        .tooltip("Explore the app with fake zones and records, no token needed")
+                        .disabled(is_loading)
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            this.start_demo(window, cx);
+                        })),
                 ),
         )
         .child(
@@ -81,3 +123,129 @@ pub fn render_token_setup(
                 .child("Your token will be stored securely in your system's keychain."),
         )
 }
+
+/// Offer to use the tokens wrangler or flarectl are set up with, instead of pasting one
+fn render_found_tokens(app: &App, cx: &mut Context<App>) -> impl IntoElement {
+    let is_loading = app.loading;
+
+    v_flex()
+        .p_3()
+        .gap_2()
+        .rounded_md()
+        .bg(cx.theme().accent.opacity(0.5))
+        .child(div().text_sm().child(
+            "Found an API token set up for other Cloudflare tools. It is only stored in the \
+             keychain if you use it.",
+        ))
+        .children(app.found_tokens.iter().enumerate().map(|(index, found)| {
+            h_flex()
+                .gap_2()
+                .items_center()
+                .child(
+                    div()
+                        .flex_1()
+                        .text_xs()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(format!("From {}", found.source.label())),
+                )
+                .child(
+                    Button::new(("use-found-token", index))
+                        .small()
+                        .primary()
+                        .label("Use This Token")
+                        .disabled(is_loading)
+                        .on_click(cx.listener(move |this, _, window, cx| {
+                            this.use_found_token(index, window, cx);
+                        })),
+                )
+        }))
+        .child(
+            h_flex().justify_end().child(
+                Button::new("dismiss-found-tokens")
+                    .small()
+                    .ghost()
+                    .label("Dismiss")
+                    .on_click(cx.listener(|this, _, _, cx| {
+                        this.dismiss_found_tokens(cx);
+                    })),
+            ),
+        )
+}
+
+/// Steps to create a token with the permissions the app needs: pick them, open Cloudflare's
+/// token creation page filled in with them, and paste the token, which is verified right away
+fn render_token_wizard(picked: &[Capability], cx: &mut Context<App>) -> impl IntoElement {
+    let muted_foreground = cx.theme().muted_foreground;
+    let step = |number: usize, title: &'static str| {
+        div()
+            .text_sm()
+            .font_weight(FontWeight::MEDIUM)
+            .child(format!("{}. {}", number, title))
+    };
+
+    v_flex()
+        .p_3()
+        .gap_3()
+        .rounded_md()
+        .border_1()
+        .border_color(cx.theme().border)
+        .child(
+            v_flex()
+                .gap_2()
+                .child(step(1, "Choose what the app may do"))
+                .children(token_template::offered_capabilities().enumerate().map(
+                    |(index, capability)| {
+                        // Without editing DNS records there is nothing to manage
+                        let required = capability == Capability::EditDns;
+                        Checkbox::new(("token-permission", index))
+                            .small()
+                            .label(capability.label())
+                            .checked(required || picked.contains(&capability))
+                            .disabled(required)
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                this.toggle_token_wizard_capability(capability, cx);
+                            }))
+                    },
+                )),
+        )
+        .child(
+            v_flex()
+                .gap_2()
+                .child(step(2, "Create the token on Cloudflare"))
+                .child(div().text_xs().text_color(muted_foreground).child(
+                    "The page opens with these permissions filled in for all zones. Narrow the \
+                     zones if you like, then continue to the summary and create the token.",
+                ))
+                .child(
+                    Button::new("open-token-creation")
+                        .small()
+                        .primary()
+                        .label("Open Cloudflare")
+                        .on_click(cx.listener(|this, _, _, cx| {
+                            this.open_token_creation(cx);
+                        })),
+                ),
+        )
+        .child(
+            v_flex()
+                .gap_1()
+                .child(step(3, "Paste the token below"))
+                .child(
+                    div()
+                        .text_xs()
+                        .text_color(muted_foreground)
+                        .child("It is verified as soon as it's pasted."),
+                ),
+        )
+        .child(
+            h_flex().justify_end().child(
+                Button::new("close-token-wizard")
+                    .small()
+                    .ghost()
+                    .label("Cancel")
+                    .on_click(cx.listener(|this, _, _, cx| {
+                        this.close_token_wizard(cx);
+                    })),
+            ),
+        )
+}
@@ -0,0 +1,246 @@
+use chrono::Utc;
+use gpui::prelude::*;
+use gpui::{Context, FontWeight, Hsla, IntoElement, Window, div, px};
+use gpui_component::{
+    ActiveTheme,
+    button::{Button, ButtonVariants},
+    h_flex,
+    scroll::ScrollableElement,
+    v_flex,
+};
+
+use super::render_status_bar;
+use crate::cloudflare::{DnsRecord, Tunnel};
+use crate::tunnels::{TunnelHealth, connectors, mixed_versions, routed_hostnames};
+use crate::{App, Page, timestamps};
+
+/// Cloudflare Tunnels of the selected zone's account with their connectors and the hostnames
+/// routed to them
+pub fn render_tunnels(
+    app: &mut App,
+    _window: &mut Window,
+    cx: &mut Context<App>,
+) -> impl IntoElement {
+    let account_name = app.selected_zone(cx).map(|zone| zone.account.name);
+    let records = app.dns_records(cx);
+    let error = app.error.clone();
+    let muted_foreground = cx.theme().muted_foreground;
+    let border_color = cx.theme().border;
+    let is_loading = app.loading;
+
+    v_flex()
+        .size_full()
+        .child(
+            // Header
+            h_flex()
+                .w_full()
+                .px_4()
+                .py_3()
+                .border_b_1()
+                .border_color(border_color)
+                .items_center()
+                .gap_3()
+                .child(
+                    Button::new("back")
+                        .ghost()
+                        .icon(gpui_component::IconName::ArrowLeft)
+                        .on_click(cx.listener(|this, _, _, cx| {
+                            this.page = Page::Dashboard;
+                            this.error = None;
+                            cx.notify();
+                        })),
+                )
+                .child(
+                    div()
+                        .text_lg()
+                        .font_weight(FontWeight::SEMIBOLD)
+                        .child("Tunnels"),
+                )
+                .when_some(account_name, |this, account_name| {
+                    this.child(
+                        div()
+                            .text_sm()
+                            .text_color(muted_foreground)
+                            .child(account_name),
+                    )
+                })
+                .child(div().flex_1())
+                .child(
+                    Button::new("refresh-tunnels")
+                        .ghost()
+                        .icon(gpui_component::IconName::Redo)
+                        .tooltip("Refresh")
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            this.load_tunnels(window, cx);
+                        })),
+                ),
+        )
+        .child(
+            v_flex()
+                .flex_1()
+                .p_6()
+                .gap_3()
+                .overflow_y_scrollbar()
+                .when_some(error, |this, error| {
+                    this.child(div().text_sm().text_color(cx.theme().danger).child(error))
+                })
+                .when(app.tunnels.is_empty(), |this| {
+                    this.child(
+                        div()
+                            .text_sm()
+                            .text_color(muted_foreground)
+                            .child(if is_loading {
+                                "Loading tunnels…"
+                            } else {
+                                "This account has no tunnels."
+                            }),
+                    )
+                })
+                .children(
+                    app.tunnels
+                        .iter()
+                        .map(|tunnel| render_tunnel(app, tunnel, &records, cx)),
+                )
+                .when(!app.tunnels.is_empty(), |this| {
+                    this.child(div().text_xs().text_color(muted_foreground).child(
+                        "Hostnames routed by CNAME records are only listed for the selected zone.",
+                    ))
+                }),
+        )
+        .child(render_status_bar(app, cx))
+}
+
+fn render_tunnel(
+    app: &App,
+    tunnel: &Tunnel,
+    records: &[DnsRecord],
+    cx: &Context<App>,
+) -> impl IntoElement {
+    let muted_foreground = cx.theme().muted_foreground;
+    let health = TunnelHealth::of(tunnel);
+    let connectors = connectors(tunnel);
+    let hostnames = routed_hostnames(
+        &tunnel.id,
+        app.tunnel_ingress
+            .get(&tunnel.id)
+            .map(Vec::as_slice)
+            .unwrap_or_default(),
+        records,
+    );
+    let inactive_since = tunnel
+        .conns_inactive_at
+        .as_deref()
+        .filter(|_| tunnel.connections.is_empty())
+        .and_then(timestamps::parse)
+        .map(|time| app.time_zone_display.relative(time, Utc::now()));
+
+    v_flex()
+        .gap_2()
+        .p_3()
+        .rounded_md()
+        .border_1()
+        .border_color(cx.theme().border)
+        .child(
+            h_flex()
+                .gap_3()
+                .items_center()
+                .child(
+                    div()
+                        .size(px(8.))
+                        .rounded_full()
+                        .bg(health_color(health, cx)),
+                )
+                .child(
+                    div()
+                        .font_weight(FontWeight::MEDIUM)
+                        .child(tunnel.name.clone()),
+                )
+                .child(
+                    div()
+                        .text_xs()
+                        .font_family("monospace")
+                        .text_color(muted_foreground)
+                        .child(tunnel.id.clone()),
+                )
+                .child(div().flex_1())
+                .child(div().text_sm().text_color(health_color(health, cx)).child(
+                    match inactive_since {
+                        Some(since) => format!("{} · disconnected {}", health.label(), since),
+                        None => health.label().to_string(),
+                    },
+                )),
+        )
+        .when(!connectors.is_empty(), |this| {
+            this.child(
+                v_flex()
+                    .gap_1()
+                    .children(connectors.iter().map(|connector| {
+                        h_flex()
+                            .gap_3()
+                            .text_sm()
+                            .child(
+                                div()
+                                    .w(px(120.))
+                                    .child(format!("cloudflared {}", connector.version)),
+                            )
+                            .child(
+                                div()
+                                    .w(px(120.))
+                                    .text_color(muted_foreground)
+                                    .child(connector.origin_ip.clone()),
+                            )
+                            .child(
+                                div()
+                                    .flex_1()
+                                    .text_color(muted_foreground)
+                                    .child(connector.colos.join(", ")),
+                            )
+                            .when(connector.reconnecting > 0, |this| {
+                                this.child(
+                                    div()
+                                        .text_color(cx.theme().warning)
+                                        .child(format!("{} reconnecting", connector.reconnecting)),
+                                )
+                            })
+                    }))
+                    .when(mixed_versions(&connectors), |this| {
+                        this.child(
+                            div()
+                                .text_xs()
+                                .text_color(cx.theme().warning)
+                                .child("Connectors run different versions of cloudflared"),
+                        )
+                    }),
+            )
+        })
+        .child(if hostnames.is_empty() {
+            div()
+                .text_sm()
+                .text_color(muted_foreground)
+                .child("No hostnames routed")
+                .into_any_element()
+        } else {
+            v_flex()
+                .gap_1()
+                .children(hostnames.into_iter().map(|routed| {
+                    h_flex().gap_2().text_sm().child(routed.hostname).child(
+                        div()
+                            .text_color(muted_foreground)
+                            .child(match routed.service {
+                                Some(service) => format!("→ {}", service),
+                                None => "CNAME record".to_string(),
+                            }),
+                    )
+                }))
+                .into_any_element()
+        })
+}
+
+fn health_color(health: TunnelHealth, cx: &Context<App>) -> Hsla {
+    match health {
+        TunnelHealth::Healthy => cx.theme().success,
+        TunnelHealth::Degraded => cx.theme().warning,
+        TunnelHealth::Down => cx.theme().danger,
+        TunnelHealth::Inactive => cx.theme().muted_foreground,
+    }
+}
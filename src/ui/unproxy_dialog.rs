@@ -0,0 +1,103 @@
+use std::collections::BTreeMap;
+
+use gpui::prelude::*;
+use gpui::{App as GpuiApp, Entity, FontWeight, IntoElement, Window, div, px};
+use gpui_component::{
+    ActiveTheme, Disableable, WindowExt,
+    button::{Button, ButtonVariants},
+    input::Input,
+    scroll::ScrollableElement,
+    v_flex,
+};
+
+use crate::App;
+use crate::cloudflare::DnsRecord;
+
+/// Confirm unproxying all proxied records of a zone by typing its name
+///
+/// The origins that become public are listed with the names pointing at them, so it's clear
+/// what is exposed while Cloudflare is bypassed.
+pub fn open_unproxy_all_dialog(
+    app: Entity<App>,
+    zone_name: String,
+    records: Vec<DnsRecord>,
+    window: &mut Window,
+    cx: &mut GpuiApp,
+) {
+    // Origin (address or host name) and the names pointing at it
+    let mut origins: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for record in &records {
+        origins
+            .entry(record.content.clone())
+            .or_default()
+            .push(record.name.clone());
+    }
+
+    window.open_dialog(cx, move |dialog, _, cx| {
+        let input = app.read(cx).unproxy_confirm_input.clone();
+        let confirmed = input.read(cx).value().trim() == zone_name;
+        let danger = cx.theme().danger;
+        let muted_foreground = cx.theme().muted_foreground;
+
+        let app = app.clone();
+        let records = records.clone();
+        dialog
+            .title(format!("Unproxy all records of {}?", zone_name))
+            .w(px(560.))
+            .child(
+                v_flex()
+                    .gap_3()
+                    .child(div().text_sm().text_color(danger).child(format!(
+                        "{} records will bypass Cloudflare. Their origins become visible in public DNS, and stay known after the records are proxied again:",
+                        records.len()
+                    )))
+                    .child(
+                        v_flex()
+                            .max_h(px(280.))
+                            .overflow_y_scrollbar()
+                            .gap_1()
+                            .text_sm()
+                            .children(origins.iter().map(|(origin, names)| {
+                                v_flex()
+                                    .child(div().font_weight(FontWeight::MEDIUM).child(origin.clone()))
+                                    .child(
+                                        div()
+                                            .text_xs()
+                                            .text_color(muted_foreground)
+                                            .child(names.join(", ")),
+                                    )
+                            })),
+                    )
+                    .child(div().text_sm().text_color(muted_foreground).child(
+                        "Use \"Proxy Again\" above the record list to undo this once maintenance is done.",
+                    ))
+                    .child(
+                        div()
+                            .text_sm()
+                            .child(format!("Type {} to confirm", zone_name)),
+                    )
+                    .child(Input::new(&input)),
+            )
+            .footer(move |_, _, _, _| {
+                vec![
+                    Button::new("unproxy-all-cancel")
+                        .label("Cancel")
+                        .on_click(|_, window, cx| window.close_dialog(cx)),
+                    Button::new("unproxy-all-confirm")
+                        .danger()
+                        .label(format!("Unproxy {} Records", records.len()))
+                        .disabled(!confirmed)
+                        .on_click({
+                            let app = app.clone();
+                            let records = records.clone();
+                            move |_, window, cx| {
+                                window.close_dialog(cx);
+                                app.update(cx, |this, cx| {
+                                    this.unproxy_records(records.clone(), window, cx)
+                                });
+                            }
+                        }),
+                ]
+            })
+    });
+}
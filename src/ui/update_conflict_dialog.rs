@@ -0,0 +1,156 @@
+use gpui::prelude::*;
+use gpui::{App as GpuiApp, Entity, FontWeight, IntoElement, Window, div, px};
+use gpui_component::{
+    ActiveTheme, Disableable, WindowExt,
+    button::{Button, ButtonVariants},
+    v_flex,
+};
+
+use super::render_field_change;
+use crate::App;
+use crate::cloudflare::{DnsRecord, UpdateDnsRecord};
+use crate::edit_conflict::UpdateConflict;
+
+/// The fields of a record changed on Cloudflare since it was loaded into the editor next to the
+/// ones changed in it, with a choice of merging both, overwriting the remote changes, or loading
+/// the remote record instead
+pub fn open_update_conflict_dialog(
+    app: Entity<App>,
+    zone_id: String,
+    editing: DnsRecord,
+    record: UpdateDnsRecord,
+    conflict: UpdateConflict,
+    window: &mut Window,
+    cx: &mut GpuiApp,
+) {
+    let merged = conflict.merged(&record);
+
+    window.open_dialog(cx, move |dialog, _, cx| {
+        let muted_foreground = cx.theme().muted_foreground;
+        let warning_color = cx.theme().warning;
+        let section_title =
+            |title: &'static str| div().text_sm().font_weight(FontWeight::MEDIUM).child(title);
+
+        let app = app.clone();
+        let zone_id = zone_id.clone();
+        let editing = editing.clone();
+        let record = record.clone();
+        let merged = merged.clone();
+        dialog
+            .title(format!(
+                "{} {} was changed elsewhere",
+                editing.record_type, editing.name
+            ))
+            .w(px(560.))
+            .child(
+                v_flex()
+                    .gap_3()
+                    .child(div().text_sm().text_color(muted_foreground).child(
+                        "The record was modified on Cloudflare after it was loaded into the \
+                         editor. Saving as is would overwrite these changes.",
+                    ))
+                    .child(
+                        v_flex()
+                            .gap_1()
+                            .child(section_title("Changed on Cloudflare"))
+                            .children(
+                                conflict
+                                    .theirs
+                                    .iter()
+                                    .map(|field| render_field_change(field, cx)),
+                            ),
+                    )
+                    .child(
+                        v_flex()
+                            .gap_1()
+                            .child(section_title("Your changes"))
+                            .when(conflict.mine.is_empty(), |this| {
+                                this.child(
+                                    div().text_sm().text_color(muted_foreground).child("None"),
+                                )
+                            })
+                            .children(
+                                conflict
+                                    .mine
+                                    .iter()
+                                    .map(|field| render_field_change(field, cx)),
+                            ),
+                    )
+                    .when(!conflict.clashes.is_empty(), |this| {
+                        this.child(
+                            div()
+                                .px_3()
+                                .py_2()
+                                .rounded_md()
+                                .bg(warning_color.opacity(0.1))
+                                .text_sm()
+                                .text_color(warning_color)
+                                .child(format!(
+                                    "Both sides changed {}, so the changes can't be merged. \
+                                     Overwrite them or load the remote record.",
+                                    conflict.clashes.join(", ")
+                                )),
+                        )
+                    }),
+            )
+            .footer(move |_, _, _, _| {
+                let load_app = app.clone();
+                let overwrite_app = app.clone();
+                let merge_app = app.clone();
+                let overwrite_zone_id = zone_id.clone();
+                let merge_zone_id = zone_id.clone();
+                let overwrite_editing = editing.clone();
+                let merge_editing = editing.clone();
+                let record = record.clone();
+                let merged = merged.clone();
+                vec![
+                    Button::new("update-conflict-cancel")
+                        .label("Cancel")
+                        .on_click(|_, window, cx| window.close_dialog(cx)),
+                    Button::new("update-conflict-load")
+                        .label("Load Remote Record")
+                        .on_click(move |_, window, cx| {
+                            window.close_dialog(cx);
+                            load_app.update(cx, |this, cx| this.load_remote_record(window, cx));
+                        }),
+                    Button::new("update-conflict-overwrite")
+                        .danger()
+                        .label("Overwrite")
+                        .on_click(move |_, window, cx| {
+                            window.close_dialog(cx);
+                            let record = record.clone();
+                            overwrite_app.update(cx, |this, cx| {
+                                this.edit_conflict = None;
+                                this.send_record_update(
+                                    overwrite_zone_id.clone(),
+                                    &overwrite_editing,
+                                    record,
+                                    window,
+                                    cx,
+                                );
+                            });
+                        }),
+                    Button::new("update-conflict-merge")
+                        .primary()
+                        .label("Merge")
+                        .disabled(merged.is_none())
+                        .on_click(move |_, window, cx| {
+                            let Some(merged) = merged.clone() else {
+                                return;
+                            };
+                            window.close_dialog(cx);
+                            merge_app.update(cx, |this, cx| {
+                                this.edit_conflict = None;
+                                this.send_record_update(
+                                    merge_zone_id.clone(),
+                                    &merge_editing,
+                                    merged,
+                                    window,
+                                    cx,
+                                );
+                            });
+                        }),
+                ]
+            })
+    });
+}
@@ -0,0 +1,321 @@
+use gpui::prelude::*;
+use gpui::{Context, FontWeight, IntoElement, Window, div, px};
+use gpui_component::{
+    ActiveTheme, Disableable, Sizable,
+    button::{Button, ButtonVariants},
+    checkbox::Checkbox,
+    h_flex,
+    input::Input,
+    scroll::ScrollableElement,
+    switch::Switch,
+    v_flex,
+};
+
+use super::{render_expression_editor, render_status_bar};
+use crate::waf_rules::{ACTIONS, action_label};
+use crate::{App, Page};
+
+/// WAF custom rules of the selected zone in the order they are applied, with an editor next to
+/// them
+pub fn render_waf_rules(
+    app: &mut App,
+    _window: &mut Window,
+    cx: &mut Context<App>,
+) -> impl IntoElement {
+    let zone_name = app.selected_zone(cx).map(|zone| zone.name);
+    let error = app.error.clone();
+    let muted_foreground = cx.theme().muted_foreground;
+    let border_color = cx.theme().border;
+    let is_loading = app.loading;
+    let read_only = app.read_only();
+    let rule_count = app.waf_rules.len();
+    let editing_id = app.waf_rule_draft.rule_id.clone();
+
+    v_flex()
+        .size_full()
+        .child(
+            // Header
+            h_flex()
+                .w_full()
+                .px_4()
+                .py_3()
+                .border_b_1()
+                .border_color(border_color)
+                .items_center()
+                .gap_3()
+                .child(
+                    Button::new("back")
+                        .ghost()
+                        .icon(gpui_component::IconName::ArrowLeft)
+                        .on_click(cx.listener(|this, _, _, cx| {
+                            this.page = Page::Dashboard;
+                            this.error = None;
+                            cx.notify();
+                        })),
+                )
+                .child(
+                    div()
+                        .text_lg()
+                        .font_weight(FontWeight::SEMIBOLD)
+                        .child("WAF Custom Rules"),
+                )
+                .when_some(zone_name, |this, zone_name| {
+                    this.child(
+                        div()
+                            .text_sm()
+                            .text_color(muted_foreground)
+                            .child(zone_name),
+                    )
+                })
+                .child(div().flex_1())
+                .child(
+                    Button::new("refresh-waf-rules")
+                        .ghost()
+                        .icon(gpui_component::IconName::Redo)
+                        .tooltip("Refresh")
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            this.load_waf_rules(window, cx);
+                        })),
+                ),
+        )
+        .child(
+            h_flex()
+                .flex_1()
+                .overflow_hidden()
+                .child(
+                    v_flex()
+                        .flex_1()
+                        .h_full()
+                        .p_6()
+                        .gap_3()
+                        .overflow_y_scrollbar()
+                        .child(div().text_sm().text_color(muted_foreground).child(
+                            "Rules are evaluated from the top; blocks and challenges stop the \
+                             evaluation, skips leave out the rules below.",
+                        ))
+                        .when_some(error, |this, error| {
+                            this.child(div().text_sm().text_color(cx.theme().danger).child(error))
+                        })
+                        .when(rule_count == 0, |this| {
+                            this.child(div().text_sm().text_color(muted_foreground).child(
+                                if is_loading {
+                                    "Loading custom rules…"
+                                } else {
+                                    "This zone has no custom rules."
+                                },
+                            ))
+                        })
+                        .children(app.waf_rules.iter().enumerate().map(|(index, rule)| {
+                            let rule_id = rule.id.clone();
+                            let delete_id = rule.id.clone();
+                            let selected = editing_id.as_ref() == Some(&rule.id);
+
+                            h_flex()
+                                .id(("waf-rule", index))
+                                .gap_3()
+                                .p_3()
+                                .items_center()
+                                .rounded_md()
+                                .border_1()
+                                .border_color(if selected {
+                                    cx.theme().primary
+                                } else {
+                                    border_color
+                                })
+                                .cursor_pointer()
+                                .on_click(cx.listener(move |this, _, window, cx| {
+                                    this.edit_waf_rule(&rule_id, window, cx);
+                                }))
+                                .child(
+                                    div()
+                                        .text_sm()
+                                        .text_color(muted_foreground)
+                                        .child(format!("{}", index + 1)),
+                                )
+                                .child(
+                                    v_flex()
+                                        .flex_1()
+                                        .min_w_0()
+                                        .gap_1()
+                                        .child(
+                                            h_flex()
+                                                .gap_2()
+                                                .child(div().font_weight(FontWeight::MEDIUM).child(
+                                                    if rule.description.is_empty() {
+                                                        "Untitled rule".to_string()
+                                                    } else {
+                                                        rule.description.clone()
+                                                    },
+                                                ))
+                                                .child(
+                                                    div()
+                                                        .text_xs()
+                                                        .text_color(muted_foreground)
+                                                        .child(
+                                                            action_label(&rule.action).to_string(),
+                                                        ),
+                                                ),
+                                        )
+                                        .child(
+                                            div()
+                                                .text_sm()
+                                                .font_family("monospace")
+                                                .text_color(muted_foreground)
+                                                .truncate()
+                                                .child(rule.expression.clone()),
+                                        ),
+                                )
+                                .child(
+                                    Switch::new(("waf-rule-enabled", index))
+                                        .checked(rule.enabled)
+                                        .disabled(is_loading || read_only)
+                                        .tooltip(if rule.enabled { "Enabled" } else { "Disabled" })
+                                        .on_click(cx.listener(
+                                            move |this, enabled: &bool, window, cx| {
+                                                cx.stop_propagation();
+                                                this.set_waf_rule_enabled(
+                                                    index, *enabled, window, cx,
+                                                );
+                                            },
+                                        )),
+                                )
+                                .child(
+                                    Button::new(("move-waf-rule-up", index))
+                                        .ghost()
+                                        .xsmall()
+                                        .icon(gpui_component::IconName::ArrowUp)
+                                        .tooltip("Apply earlier")
+                                        .disabled(index == 0 || is_loading || read_only)
+                                        .on_click(cx.listener(move |this, _, window, cx| {
+                                            cx.stop_propagation();
+                                            this.move_waf_rule(index, true, window, cx);
+                                        })),
+                                )
+                                .child(
+                                    Button::new(("move-waf-rule-down", index))
+                                        .ghost()
+                                        .xsmall()
+                                        .icon(gpui_component::IconName::ArrowDown)
+                                        .tooltip("Apply later")
+                                        .disabled(
+                                            index + 1 == rule_count || is_loading || read_only,
+                                        )
+                                        .on_click(cx.listener(move |this, _, window, cx| {
+                                            cx.stop_propagation();
+                                            this.move_waf_rule(index, false, window, cx);
+                                        })),
+                                )
+                                .child(
+                                    Button::new(("delete-waf-rule", index))
+                                        .ghost()
+                                        .xsmall()
+                                        .icon(gpui_component::IconName::Delete)
+                                        .tooltip("Delete")
+                                        .disabled(is_loading || read_only)
+                                        .on_click(cx.listener(move |this, _, window, cx| {
+                                            cx.stop_propagation();
+                                            this.open_delete_waf_rule_dialog(
+                                                delete_id.clone(),
+                                                window,
+                                                cx,
+                                            );
+                                        })),
+                                )
+                        })),
+                )
+                .child(render_waf_rule_editor(app, cx)),
+        )
+        .child(render_status_bar(app, cx))
+}
+
+fn render_waf_rule_editor(app: &App, cx: &mut Context<App>) -> impl IntoElement {
+    let draft = &app.waf_rule_draft;
+    let editing = draft.rule_id.is_some();
+
+    v_flex()
+        .w(px(380.))
+        .h_full()
+        .border_l_1()
+        .border_color(cx.theme().border)
+        .p_4()
+        .gap_4()
+        .overflow_y_scrollbar()
+        .child(div().font_weight(FontWeight::SEMIBOLD).child(if editing {
+            "Edit Custom Rule"
+        } else {
+            "New Custom Rule"
+        }))
+        .child(
+            v_flex()
+                .gap_1()
+                .child(div().text_sm().child("Description"))
+                .child(Input::new(&app.waf_description_input)),
+        )
+        .child(
+            v_flex()
+                .gap_1()
+                .child(div().text_sm().child("Requests"))
+                .child(render_expression_editor(
+                    "waf-expression",
+                    &app.waf_expression,
+                    |app| &mut app.waf_expression,
+                    cx,
+                )),
+        )
+        .child(
+            v_flex()
+                .gap_1()
+                .child(div().text_sm().child("Action"))
+                .child(
+                    h_flex()
+                        .gap_1()
+                        .flex_wrap()
+                        .children(ACTIONS.iter().enumerate().map(|(index, &(action, label))| {
+                            Button::new(("waf-rule-action", index))
+                                .xsmall()
+                                .map(|this| {
+                                    if action == draft.action {
+                                        this.primary()
+                                    } else {
+                                        this.ghost()
+                                    }
+                                })
+                                .label(label)
+                                .on_click(cx.listener(move |this, _, _, cx| {
+                                    this.waf_rule_draft.action = action;
+                                    cx.notify();
+                                }))
+                        })),
+                ),
+        )
+        .child(
+            Checkbox::new("waf-rule-enabled")
+                .label("Enabled")
+                .checked(draft.enabled)
+                .on_click(cx.listener(|this, checked: &bool, _, cx| {
+                    this.waf_rule_draft.enabled = *checked;
+                    cx.notify();
+                })),
+        )
+        .child(
+            h_flex()
+                .gap_2()
+                .child(
+                    Button::new("save-waf-rule")
+                        .primary()
+                        .label(if editing { "Save Rule" } else { "Create Rule" })
+                        .disabled(app.loading || app.read_only())
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            this.save_waf_rule(window, cx);
+                        })),
+                )
+                .when(editing, |this| {
+                    this.child(Button::new("cancel-waf-rule").label("Cancel").on_click(
+                        cx.listener(|this, _, window, cx| {
+                            this.error = None;
+                            this.new_waf_rule(window, cx);
+                        }),
+                    ))
+                }),
+        )
+}
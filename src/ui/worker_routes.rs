@@ -0,0 +1,255 @@
+use gpui::prelude::*;
+use gpui::{Context, FontWeight, IntoElement, Window, div, px};
+use gpui_component::{
+    ActiveTheme, Disableable, Sizable,
+    button::{Button, ButtonVariants},
+    h_flex,
+    input::Input,
+    scroll::ScrollableElement,
+    v_flex,
+};
+
+use super::render_status_bar;
+use crate::{App, Page};
+
+/// Workers routes of the selected zone with the Workers they run, with an editor next to them
+pub fn render_worker_routes(
+    app: &mut App,
+    _window: &mut Window,
+    cx: &mut Context<App>,
+) -> impl IntoElement {
+    let zone_name = app.selected_zone(cx).map(|zone| zone.name);
+    let error = app.error.clone();
+    let muted_foreground = cx.theme().muted_foreground;
+    let border_color = cx.theme().border;
+    let is_loading = app.loading;
+    let read_only = app.read_only();
+    let editing_id = app.worker_route_draft.route_id.clone();
+
+    v_flex()
+        .size_full()
+        .child(
+            // Header
+            h_flex()
+                .w_full()
+                .px_4()
+                .py_3()
+                .border_b_1()
+                .border_color(border_color)
+                .items_center()
+                .gap_3()
+                .child(
+                    Button::new("back")
+                        .ghost()
+                        .icon(gpui_component::IconName::ArrowLeft)
+                        .on_click(cx.listener(|this, _, _, cx| {
+                            this.page = Page::Dashboard;
+                            this.error = None;
+                            cx.notify();
+                        })),
+                )
+                .child(
+                    div()
+                        .text_lg()
+                        .font_weight(FontWeight::SEMIBOLD)
+                        .child("Workers Routes"),
+                )
+                .when_some(zone_name, |this, zone_name| {
+                    this.child(
+                        div()
+                            .text_sm()
+                            .text_color(muted_foreground)
+                            .child(zone_name),
+                    )
+                })
+                .child(div().flex_1())
+                .child(
+                    Button::new("refresh-worker-routes")
+                        .ghost()
+                        .icon(gpui_component::IconName::Redo)
+                        .tooltip("Refresh")
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            this.load_worker_routes(window, cx);
+                        })),
+                ),
+        )
+        .child(
+            h_flex()
+                .flex_1()
+                .overflow_hidden()
+                .child(
+                    v_flex()
+                        .flex_1()
+                        .h_full()
+                        .p_6()
+                        .gap_3()
+                        .overflow_y_scrollbar()
+                        .child(div().text_sm().text_color(muted_foreground).child(
+                            "When several patterns match a request, the most specific one applies.",
+                        ))
+                        .when_some(error, |this, error| {
+                            this.child(div().text_sm().text_color(cx.theme().danger).child(error))
+                        })
+                        .when(app.worker_routes.is_empty(), |this| {
+                            this.child(div().text_sm().text_color(muted_foreground).child(
+                                if is_loading {
+                                    "Loading Workers routes…"
+                                } else {
+                                    "This zone has no Workers routes."
+                                },
+                            ))
+                        })
+                        .children(app.worker_routes.iter().enumerate().map(|(index, route)| {
+                            let route_id = route.id.clone();
+                            let delete_id = route.id.clone();
+                            let selected = editing_id.as_ref() == Some(&route.id);
+
+                            h_flex()
+                                .id(("worker-route", index))
+                                .gap_3()
+                                .p_3()
+                                .items_center()
+                                .rounded_md()
+                                .border_1()
+                                .border_color(if selected {
+                                    cx.theme().primary
+                                } else {
+                                    border_color
+                                })
+                                .cursor_pointer()
+                                .on_click(cx.listener(move |this, _, window, cx| {
+                                    this.edit_worker_route(&route_id, window, cx);
+                                }))
+                                .child(
+                                    v_flex()
+                                        .flex_1()
+                                        .gap_1()
+                                        .child(
+                                            div()
+                                                .font_family("monospace")
+                                                .font_weight(FontWeight::MEDIUM)
+                                                .child(route.pattern.clone()),
+                                        )
+                                        .child(div().text_sm().text_color(muted_foreground).child(
+                                            match &route.script {
+                                                Some(script) => format!("Runs {}", script),
+                                                None => "Workers off".to_string(),
+                                            },
+                                        )),
+                                )
+                                .child(
+                                    Button::new(("delete-worker-route", index))
+                                        .ghost()
+                                        .xsmall()
+                                        .icon(gpui_component::IconName::Delete)
+                                        .tooltip("Delete")
+                                        .disabled(read_only)
+                                        .on_click(cx.listener(move |this, _, window, cx| {
+                                            cx.stop_propagation();
+                                            this.open_delete_worker_route_dialog(
+                                                delete_id.clone(),
+                                                window,
+                                                cx,
+                                            );
+                                        })),
+                                )
+                        })),
+                )
+                .child(render_worker_route_editor(app, cx)),
+        )
+        .child(render_status_bar(app, cx))
+}
+
+fn render_worker_route_editor(app: &App, cx: &mut Context<App>) -> impl IntoElement {
+    let editing = app.worker_route_draft.route_id.is_some();
+    let muted_foreground = cx.theme().muted_foreground;
+    let script = app
+        .worker_route_script_input
+        .read(cx)
+        .value()
+        .trim()
+        .to_string();
+
+    v_flex()
+        .w(px(380.))
+        .h_full()
+        .border_l_1()
+        .border_color(cx.theme().border)
+        .p_4()
+        .gap_4()
+        .overflow_y_scrollbar()
+        .child(div().font_weight(FontWeight::SEMIBOLD).child(if editing {
+            "Edit Route"
+        } else {
+            "New Route"
+        }))
+        .child(
+            v_flex()
+                .gap_1()
+                .child(div().text_sm().child("URL pattern"))
+                .child(Input::new(&app.worker_route_pattern_input))
+                .child(div().text_xs().text_color(muted_foreground).child(
+                    "Without http:// or https://; * matches any text, e.g. *.example.com/*",
+                )),
+        )
+        .child(
+            v_flex()
+                .gap_1()
+                .child(div().text_sm().child("Worker"))
+                .child(Input::new(&app.worker_route_script_input))
+                .when(!app.worker_scripts.is_empty(), |this| {
+                    this.child(h_flex().gap_1().flex_wrap().children(
+                        app.worker_scripts.iter().enumerate().map(|(index, name)| {
+                            let value = name.clone();
+                            Button::new(("worker-script", index))
+                                .xsmall()
+                                .map(|this| {
+                                    if *name == script {
+                                        this.primary()
+                                    } else {
+                                        this.ghost()
+                                    }
+                                })
+                                .label(name.clone())
+                                .on_click(cx.listener(move |this, _, window, cx| {
+                                    this.worker_route_script_input.update(cx, |input, cx| {
+                                        input.set_value(value.clone(), window, cx);
+                                    });
+                                    cx.notify();
+                                }))
+                        }),
+                    ))
+                })
+                .child(
+                    div()
+                        .text_xs()
+                        .text_color(muted_foreground)
+                        .child("Leave empty to turn Workers off for the pattern"),
+                ),
+        )
+        .child(
+            h_flex()
+                .gap_2()
+                .child(
+                    Button::new("save-worker-route")
+                        .primary()
+                        .label(if editing {
+                            "Save Route"
+                        } else {
+                            "Create Route"
+                        })
+                        .disabled(app.loading || app.read_only())
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            this.save_worker_route(window, cx);
+                        })),
+                )
+                .when(editing, |this| {
+                    this.child(Button::new("cancel-worker-route").label("Cancel").on_click(
+                        cx.listener(|this, _, window, cx| {
+                            this.error = None;
+                            this.new_worker_route(window, cx);
+                        }),
+                    ))
+                }),
+        )
+}
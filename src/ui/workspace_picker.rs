@@ -0,0 +1,77 @@
+use gpui::prelude::*;
+use gpui::{Context, ElementId, IntoElement};
+use gpui_component::{
+    Sizable,
+    button::{Button, ButtonVariants},
+    menu::{DropdownMenu, PopupMenuItem},
+};
+
+use crate::App;
+
+/// Dropdown scoping the zone picker and portfolio page to a workspace, creating one, or deleting
+/// the active one
+pub fn render_workspace_picker(
+    id: impl Into<ElementId>,
+    app: &App,
+    cx: &mut Context<App>,
+) -> impl IntoElement {
+    let entity = cx.entity();
+    let names: Vec<String> = app
+        .workspaces
+        .iter()
+        .map(|workspace| workspace.name.clone())
+        .collect();
+    let active = app.workspace().map(|workspace| workspace.name.clone());
+
+    Button::new(id)
+        .ghost()
+        .small()
+        .icon(gpui_component::IconName::LayoutDashboard)
+        .label(active.clone().unwrap_or_else(|| "All zones".to_string()))
+        .tooltip("Show the zones of a workspace")
+        .dropdown_menu(move |menu, _, _| {
+            let all_entity = entity.clone();
+            let mut menu = menu.item(
+                PopupMenuItem::new("All zones")
+                    .checked(active.is_none())
+                    .on_click(move |_, window, cx| {
+                        all_entity.update(cx, |this, cx| this.set_workspace(None, window, cx));
+                    }),
+            );
+            if !names.is_empty() {
+                menu = menu.separator();
+            }
+            for name in &names {
+                let entity = entity.clone();
+                let name = name.clone();
+                menu = menu.item(
+                    PopupMenuItem::new(name.clone())
+                        .checked(active.as_ref() == Some(&name))
+                        .on_click(move |_, window, cx| {
+                            let name = name.clone();
+                            entity
+                                .update(cx, |this, cx| this.set_workspace(Some(name), window, cx));
+                        }),
+                );
+            }
+
+            let add_entity = entity.clone();
+            menu = menu
+                .separator()
+                .item(
+                    PopupMenuItem::new("New Workspace…").on_click(move |_, window, cx| {
+                        add_entity
+                            .update(cx, |this, cx| this.open_add_workspace_dialog(window, cx));
+                    }),
+                );
+            if let Some(active) = &active {
+                let delete_entity = entity.clone();
+                menu = menu.item(PopupMenuItem::new(format!("Delete {}", active)).on_click(
+                    move |_, window, cx| {
+                        delete_entity.update(cx, |this, cx| this.delete_workspace(window, cx));
+                    },
+                ));
+            }
+            menu
+        })
+}
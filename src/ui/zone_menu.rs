@@ -0,0 +1,104 @@
+use gpui::{App as GpuiApp, ClipboardItem, WeakEntity};
+use gpui_component::menu::{PopupMenu, PopupMenuItem};
+
+use crate::App;
+use crate::cloudflare::Zone;
+use crate::token_scopes::Capability;
+
+/// Right-click menu of a zone in the zone picker or on the portfolio page, acting on the zone
+/// without switching to it
+pub fn zone_context_menu(
+    menu: PopupMenu,
+    zone: &Zone,
+    pinned: bool,
+    app: WeakEntity<App>,
+    cx: &GpuiApp,
+) -> PopupMenu {
+    let purge_zone = zone.clone();
+    let export_zone = zone.clone();
+    let zone_id = zone.id.clone();
+    let pin_zone_id = zone.id.clone();
+    let dashboard_url = zone.dashboard_url();
+    let purge_app = app.clone();
+    let export_app = app.clone();
+    let can_purge = app
+        .upgrade()
+        .is_none_or(|app| app.read(cx).can(Capability::PurgeCache));
+    // Names of the workspaces and whether the zone is in each
+    let workspaces: Vec<(String, bool)> = app
+        .upgrade()
+        .map(|app| {
+            let app = app.read(cx);
+            app.workspaces
+                .iter()
+                .map(|workspace| {
+                    (
+                        workspace.name.clone(),
+                        workspace.contains(&app.active_profile, &zone.id),
+                    )
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let menu = if can_purge {
+        menu.item(
+            PopupMenuItem::new("Purge Cache…").on_click(move |_, window, cx| {
+                let zone = purge_zone.clone();
+                purge_app
+                    .update(cx, |this, cx| {
+                        this.open_purge_zone_cache_dialog(zone, window, cx)
+                    })
+                    .ok();
+            }),
+        )
+    } else {
+        menu
+    };
+    let mut menu = menu
+        .item(
+            PopupMenuItem::new("Open in Cloudflare Dashboard").on_click(move |_, _, cx| {
+                cx.open_url(&dashboard_url);
+            }),
+        )
+        .item(
+            PopupMenuItem::new("Copy Zone ID").on_click(move |_, _, cx| {
+                cx.write_to_clipboard(ClipboardItem::new_string(zone_id.clone()));
+            }),
+        )
+        .item(
+            PopupMenuItem::new("Export Zone File").on_click(move |_, window, cx| {
+                let zone = export_zone.clone();
+                export_app
+                    .update(cx, |this, cx| this.export_zone(zone, window, cx))
+                    .ok();
+            }),
+        )
+        .separator();
+    for (name, added) in workspaces {
+        let workspace_app = app.clone();
+        let zone = zone.clone();
+        let label = if added {
+            format!("Remove from {}", name)
+        } else {
+            format!("Add to {}", name)
+        };
+        menu = menu.item(PopupMenuItem::new(label).on_click(move |_, window, cx| {
+            workspace_app
+                .update(cx, |this, cx| {
+                    this.toggle_workspace_zone(&name, &zone, window, cx)
+                })
+                .ok();
+        }));
+    }
+    menu.item(
+        PopupMenuItem::new(if pinned { "Unpin" } else { "Pin to Top" }).on_click(
+            move |_, window, cx| {
+                app.update(cx, |this, cx| {
+                    this.toggle_pin_zone(&pin_zone_id, window, cx)
+                })
+                .ok();
+            },
+        ),
+    )
+}
@@ -0,0 +1,270 @@
+use gpui::prelude::*;
+use gpui::{Context, FontWeight, IntoElement, SharedString, Window, div, px};
+use gpui_component::{
+    ActiveTheme, Disableable, Sizable,
+    button::{Button, ButtonVariants},
+    h_flex,
+    input::Input,
+    scroll::ScrollableElement,
+    switch::Switch,
+    v_flex,
+};
+
+use super::render_status_bar;
+use crate::cache_purge::PurgeTarget;
+use crate::token_scopes::Capability;
+use crate::zone_settings::{SettingControl, ZoneSettingKind};
+use crate::{App, Page};
+
+/// Common settings of the selected zone, changed one at a time
+pub fn render_zone_settings(
+    app: &mut App,
+    _window: &mut Window,
+    cx: &mut Context<App>,
+) -> impl IntoElement {
+    let zone_name = app.selected_zone(cx).map(|zone| zone.name);
+    let error = app.error.clone();
+    let muted_foreground = cx.theme().muted_foreground;
+    let border_color = cx.theme().border;
+    let is_loading = app.loading;
+    let read_only = app.read_only();
+
+    v_flex()
+        .size_full()
+        .child(
+            // Header
+            h_flex()
+                .w_full()
+                .px_4()
+                .py_3()
+                .border_b_1()
+                .border_color(border_color)
+                .items_center()
+                .gap_3()
+                .child(
+                    Button::new("back")
+                        .ghost()
+                        .icon(gpui_component::IconName::ArrowLeft)
+                        .on_click(cx.listener(|this, _, _, cx| {
+                            this.page = Page::Dashboard;
+                            this.error = None;
+                            cx.notify();
+                        })),
+                )
+                .child(
+                    div()
+                        .text_lg()
+                        .font_weight(FontWeight::SEMIBOLD)
+                        .child("Zone Settings"),
+                )
+                .when_some(zone_name, |this, zone_name| {
+                    this.child(
+                        div()
+                            .text_sm()
+                            .text_color(muted_foreground)
+                            .child(zone_name),
+                    )
+                })
+                .child(div().flex_1())
+                .child(
+                    Button::new("refresh-zone-settings")
+                        .ghost()
+                        .icon(gpui_component::IconName::Redo)
+                        .tooltip("Refresh")
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            this.load_zone_settings(window, cx);
+                        })),
+                ),
+        )
+        .child(
+            v_flex()
+                .flex_1()
+                .p_6()
+                .gap_4()
+                .max_w(px(720.))
+                .overflow_y_scrollbar()
+                .when_some(error, |this, error| {
+                    this.child(div().text_sm().text_color(cx.theme().danger).child(error))
+                })
+                .when(app.zone_settings.is_empty() && is_loading, |this| {
+                    this.child(
+                        div()
+                            .text_sm()
+                            .text_color(muted_foreground)
+                            .child("Loading settings…"),
+                    )
+                })
+                .children(ZoneSettingKind::all().iter().filter_map(|&kind| {
+                    let setting = kind.value(&app.zone_settings)?;
+                    let value = setting.value.as_str().unwrap_or_default().to_string();
+                    let disabled = !setting.editable || is_loading || read_only;
+
+                    let control = match kind.control() {
+                        SettingControl::Toggle => Switch::new(kind.id())
+                            .checked(value == "on")
+                            .disabled(disabled)
+                            .on_click(cx.listener(move |this, on: &bool, window, cx| {
+                                let value = if *on { "on" } else { "off" };
+                                this.set_zone_setting(kind, value, window, cx);
+                            }))
+                            .into_any_element(),
+                        SettingControl::Choice(options) => h_flex()
+                            .gap_1()
+                            .children(options.iter().map(|&(option, label)| {
+                                Button::new(SharedString::from(format!("{}-{}", kind.id(), option)))
+                                    .xsmall()
+                                    .map(|this| {
+                                        if option == value {
+                                            this.primary()
+                                        } else {
+                                            this.ghost()
+                                        }
+                                    })
+                                    .label(label)
+                                    .disabled(disabled)
+                                    .on_click(cx.listener(move |this, _, window, cx| {
+                                        this.set_zone_setting(kind, option, window, cx);
+                                    }))
+                            }))
+                            .into_any_element(),
+                    };
+
+                    Some(
+                        h_flex()
+                            .gap_4()
+                            .pb_4()
+                            .items_center()
+                            .border_b_1()
+                            .border_color(border_color)
+                            .child(
+                                v_flex()
+                                    .flex_1()
+                                    .gap_1()
+                                    .child(
+                                        div().font_weight(FontWeight::MEDIUM).child(kind.label()),
+                                    )
+                                    .child(
+                                        div()
+                                            .text_sm()
+                                            .text_color(muted_foreground)
+                                            .child(kind.description()),
+                                    )
+                                    .when(!setting.editable, |this| {
+                                        this.child(
+                                            div()
+                                                .text_xs()
+                                                .text_color(muted_foreground)
+                                                .child("Not available on this zone's plan"),
+                                        )
+                                    }),
+                            )
+                            .child(control),
+                    )
+                }))
+                .when(app.can(Capability::PurgeCache), |this| {
+                    this.child(render_purge_cache(app, cx))
+                })
+                .when(app.can(Capability::EditZones), |this| {
+                    this.child(render_delete_zone(app, cx))
+                }),
+        )
+        .child(render_status_bar(app, cx))
+}
+
+fn render_purge_cache(app: &App, cx: &mut Context<App>) -> impl IntoElement {
+    let target = app.purge_target;
+
+    v_flex()
+        .gap_3()
+        .pt_2()
+        .child(
+            v_flex()
+                .gap_1()
+                .child(div().font_weight(FontWeight::SEMIBOLD).child("Purge Cache"))
+                .child(
+                    div()
+                        .text_sm()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(
+                            "Remove cached content, so the next requests fetch it from the origin.",
+                        ),
+                ),
+        )
+        .child(
+            h_flex()
+                .gap_1()
+                .children(PurgeTarget::all().iter().map(|&option| {
+                    Button::new(SharedString::from(format!("purge-target-{:?}", option)))
+                        .xsmall()
+                        .map(|this| {
+                            if option == target {
+                                this.primary()
+                            } else {
+                                this.ghost()
+                            }
+                        })
+                        .label(option.label())
+                        .on_click(cx.listener(move |this, _, window, cx| {
+                            this.set_purge_target(option, window, cx);
+                        }))
+                })),
+        )
+        .child(Input::new(&app.purge_input))
+        .child(
+            h_flex()
+                .gap_2()
+                .child(
+                    Button::new("purge-targeted")
+                        .small()
+                        .label(format!("Purge {}", target.label()))
+                        .disabled(app.loading)
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            this.open_purge_cache_dialog(false, window, cx);
+                        })),
+                )
+                .child(
+                    Button::new("purge-everything")
+                        .danger()
+                        .small()
+                        .label("Purge Everything")
+                        .disabled(app.loading)
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            this.open_purge_cache_dialog(true, window, cx);
+                        })),
+                ),
+        )
+}
+
+fn render_delete_zone(app: &App, cx: &mut Context<App>) -> impl IntoElement {
+    v_flex()
+        .gap_3()
+        .pt_4()
+        .border_t_1()
+        .border_color(cx.theme().border)
+        .child(
+            v_flex()
+                .gap_1()
+                .child(div().font_weight(FontWeight::SEMIBOLD).child("Delete Zone"))
+                .child(
+                    div()
+                        .text_sm()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(
+                            "Remove the zone with all of its records and settings from \
+                             Cloudflare, e.g. after moving the domain to another DNS provider.",
+                        ),
+                ),
+        )
+        .child(
+            h_flex().child(
+                Button::new("delete-zone")
+                    .danger()
+                    .small()
+                    .label("Delete Zone…")
+                    .disabled(app.loading)
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.open_delete_zone_dialog(window, cx);
+                    })),
+            ),
+        )
+}
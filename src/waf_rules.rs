@@ -0,0 +1,89 @@
+//! WAF custom rules of a zone, rules of its `http_request_firewall_custom` ruleset
+//!
+//! Rules are edited as an expression in Cloudflare's Rules language and an action. Parameters
+//! of an action, e.g. the custom response of a block, are kept as long as the action stays.
+
+use crate::cloudflare::RulesetRule;
+
+/// Ruleset phase the custom rules are rules of
+pub const PHASE: &str = "http_request_firewall_custom";
+
+const SKIP: &str = "skip";
+
+/// Actions a custom rule can take, with their labels
+pub const ACTIONS: &[(&str, &str)] = &[
+    ("block", "Block"),
+    ("managed_challenge", "Managed Challenge"),
+    ("js_challenge", "JS Challenge"),
+    ("challenge", "Interactive Challenge"),
+    (SKIP, "Skip"),
+    ("log", "Log"),
+];
+
+/// Editable parts of a custom rule besides the description and expression inputs
+#[derive(Debug, Clone)]
+pub struct WafRuleDraft {
+    /// Rule being edited, `None` for a new one
+    pub rule_id: Option<String>,
+    pub action: &'static str,
+    pub enabled: bool,
+    /// Action and its parameters of the edited rule, kept if the action isn't changed
+    original_action: Option<(String, serde_json::Value)>,
+}
+
+impl Default for WafRuleDraft {
+    fn default() -> Self {
+        Self {
+            rule_id: None,
+            action: "block",
+            enabled: true,
+            original_action: None,
+        }
+    }
+}
+
+impl WafRuleDraft {
+    /// Draft of an existing rule, `None` if its action isn't one the form offers
+    pub fn from_rule(rule: &RulesetRule) -> Option<Self> {
+        let (action, _) = ACTIONS.iter().find(|(action, _)| *action == rule.action)?;
+        Some(Self {
+            rule_id: Some(rule.id.clone()),
+            action,
+            enabled: rule.enabled,
+            original_action: Some((rule.action.clone(), rule.action_parameters.clone())),
+        })
+    }
+
+    /// The rule to save
+    pub fn build(&self, description: &str, expression: &str) -> Result<RulesetRule, String> {
+        let expression = expression.trim();
+        if expression.is_empty() {
+            return Err("Enter the expression of the requests the rule applies to".to_string());
+        }
+
+        let action_parameters = match &self.original_action {
+            Some((action, parameters)) if action == self.action => parameters.clone(),
+            // Skip the remaining custom rules, the least a skip rule can do
+            _ if self.action == SKIP => serde_json::json!({ "ruleset": "current" }),
+            _ => serde_json::Value::Null,
+        };
+
+        Ok(RulesetRule {
+            id: self.rule_id.clone().unwrap_or_default(),
+            expression: expression.to_string(),
+            description: description.trim().to_string(),
+            action: self.action.to_string(),
+            action_parameters,
+            enabled: self.enabled,
+        })
+    }
+}
+
+/// Label of an action, e.g. `Managed Challenge`
+pub fn action_label(action: &str) -> &str {
+    ACTIONS
+        .iter()
+        .find(|(id, _)| *id == action)
+        .map(|(_, label)| *label)
+        .unwrap_or(action)
+}
@@ -0,0 +1,180 @@
+//! Detection of changes made to watched DNS records outside the app
+
+use std::collections::HashSet;
+
+use crate::cloudflare::{DnsRecord, UpdateDnsRecord, qualified_name};
+
+/// A single field that differs between two versions of a record
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub field: &'static str,
+    pub before: String,
+    pub after: String,
+}
+
+/// A change to a watched record, or to any record since the last export
+#[derive(Debug, Clone)]
+pub enum RecordChange {
+    /// Only found when comparing with an export
+    Added(DnsRecord),
+    Modified {
+        record: DnsRecord,
+        fields: Vec<FieldChange>,
+    },
+    Deleted(DnsRecord),
+}
+
+impl RecordChange {
+    pub fn record(&self) -> &DnsRecord {
+        match self {
+            RecordChange::Added(record) => record,
+            RecordChange::Modified { record, .. } => record,
+            RecordChange::Deleted(record) => record,
+        }
+    }
+}
+
+/// Compare the watched fields (content, proxy status and TTL) of two versions of a record
+pub fn diff_record(before: &DnsRecord, after: &DnsRecord) -> Vec<FieldChange> {
+    let mut fields = Vec::new();
+
+    if before.content != after.content {
+        fields.push(FieldChange {
+            field: "Content",
+            before: before.content.clone(),
+            after: after.content.clone(),
+        });
+    }
+    if before.proxied != after.proxied {
+        fields.push(FieldChange {
+            field: "Proxied",
+            before: before.proxied.to_string(),
+            after: after.proxied.to_string(),
+        });
+    }
+    if before.ttl != after.ttl {
+        fields.push(FieldChange {
+            field: "TTL",
+            before: before.ttl.to_string(),
+            after: after.ttl.to_string(),
+        });
+    }
+
+    fields
+}
+
+/// Find watched records that changed between two loads of the same zone
+///
+/// Records in `ignored` were changed by the app itself and are not reported.
+pub fn detect_changes(
+    watched: &HashSet<String>,
+    ignored: &HashSet<String>,
+    before: &[DnsRecord],
+    after: &[DnsRecord],
+) -> Vec<RecordChange> {
+    before
+        .iter()
+        .filter(|record| watched.contains(&record.id) && !ignored.contains(&record.id))
+        .filter_map(|old| match after.iter().find(|r| r.id == old.id) {
+            Some(new) => {
+                let fields = diff_record(old, new);
+                (!fields.is_empty()).then(|| RecordChange::Modified {
+                    record: new.clone(),
+                    fields,
+                })
+            }
+            None => Some(RecordChange::Deleted(old.clone())),
+        })
+        .collect()
+}
+
+/// Fields an update changes, to review before it's saved; fields it leaves out don't change
+///
+/// Names are compared as the API completes them with `zone_name`, so `www` is no change from
+/// `www.example.com`.
+pub fn diff_update(
+    before: &DnsRecord,
+    update: &UpdateDnsRecord,
+    zone_name: &str,
+) -> Vec<FieldChange> {
+    let ttl = |ttl: u32| {
+        if ttl == 1 {
+            "Auto".to_string()
+        } else {
+            ttl.to_string()
+        }
+    };
+    let proxied = |proxied: bool| if proxied { "Proxied" } else { "DNS only" }.to_string();
+    let optional = |value: Option<String>| {
+        value
+            .filter(|value| !value.is_empty())
+            .unwrap_or_else(|| "(none)".to_string())
+    };
+
+    let changes = [
+        (
+            "Type",
+            update
+                .record_type
+                .map(|record_type| (before.record_type.to_string(), record_type.to_string())),
+        ),
+        (
+            "Name",
+            update.name.as_ref().map(|name| {
+                (
+                    qualified_name(&before.name, zone_name),
+                    qualified_name(name, zone_name),
+                )
+            }),
+        ),
+        (
+            "Content",
+            update.content.as_ref().map(|content| {
+                // The editor shows the content formatted from the record's fields
+                let before = if *content == before.content {
+                    before.content.clone()
+                } else {
+                    before.data_content()
+                };
+                (before, content.clone())
+            }),
+        ),
+        ("TTL", update.ttl.map(|value| (ttl(before.ttl), ttl(value)))),
+        (
+            "Proxy",
+            update
+                .proxied
+                .map(|value| (proxied(before.proxied), proxied(value))),
+        ),
+        (
+            "Priority",
+            update.priority.map(|priority| {
+                (
+                    optional(before.priority.map(|p| p.to_string())),
+                    priority.to_string(),
+                )
+            }),
+        ),
+        (
+            "Comment",
+            update.comment.as_ref().map(|comment| {
+                (
+                    optional(before.comment.clone()),
+                    optional(Some(comment.clone())),
+                )
+            }),
+        ),
+    ];
+
+    changes
+        .into_iter()
+        .filter_map(|(field, change)| {
+            let (before, after) = change?;
+            (before != after).then_some(FieldChange {
+                field,
+                before,
+                after,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,56 @@
+//! Workers routes of a zone: URL patterns whose requests run a Worker
+//!
+//! A route without a Worker turns Workers off for its pattern, e.g. to exclude a health check
+//! from a route that covers the rest of the path.
+
+use crate::cloudflare::WorkerRoute;
+
+/// Route being edited, `None` for a new one
+#[derive(Debug, Clone, Default)]
+pub struct WorkerRouteDraft {
+    pub route_id: Option<String>,
+}
+
+impl WorkerRouteDraft {
+    /// The route to save; the pattern has to be on a hostname of `zone_name`
+    pub fn build(
+        &self,
+        pattern: &str,
+        script: &str,
+        zone_name: &str,
+    ) -> Result<WorkerRoute, String> {
+        let pattern = pattern.trim();
+        if pattern.is_empty() {
+            return Err("Enter the URL pattern the route applies to".to_string());
+        }
+        if pattern.contains(char::is_whitespace) {
+            return Err("Route patterns can't contain spaces".to_string());
+        }
+        if pattern.contains("://") {
+            return Err("Route patterns don't include http:// or https://".to_string());
+        }
+
+        let host = pattern.split('/').next().unwrap_or_default();
+        let host = host.trim_start_matches('*').trim_start_matches('.');
+        let zone_name = zone_name.to_lowercase();
+        let host = host.to_lowercase();
+        if host != zone_name && !host.ends_with(&format!(".{}", zone_name)) {
+            return Err(format!(
+                "The pattern has to be on {} or a subdomain",
+                zone_name
+            ));
+        }
+
+        let script = script.trim();
+        Ok(WorkerRoute {
+            id: self.route_id.clone().unwrap_or_default(),
+            pattern: pattern.to_string(),
+            script: (!script.is_empty()).then(|| script.to_string()),
+        })
+    }
+}
+
+/// Sort routes by pattern, so routes of the same hostname and path are next to each other
+pub fn sort(routes: &mut [WorkerRoute]) {
+    routes.sort_by(|a, b| a.pattern.cmp(&b.pattern));
+}
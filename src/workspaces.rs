@@ -0,0 +1,74 @@
+//! Named sets of zones, e.g. a client's production zones or personal ones
+//!
+//! A workspace can hold zones of several token profiles. While one is active, the zone picker
+//! and the portfolio page only show its zones of the profile in use; zones are remembered by
+//! profile and id, with the name for when the profile isn't in use.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::cloudflare::Zone;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Workspace {
+    pub name: String,
+    #[serde(default)]
+    pub zones: Vec<WorkspaceZone>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkspaceZone {
+    pub profile: String,
+    pub zone_id: String,
+    pub zone_name: String,
+}
+
+impl Workspace {
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            zones: Vec::new(),
+        }
+    }
+
+    /// Ids of the workspace's zones of `profile`
+    pub fn zone_ids(&self, profile: &str) -> HashSet<String> {
+        self.zones
+            .iter()
+            .filter(|zone| zone.profile == profile)
+            .map(|zone| zone.zone_id.clone())
+            .collect()
+    }
+
+    pub fn contains(&self, profile: &str, zone_id: &str) -> bool {
+        self.zones
+            .iter()
+            .any(|zone| zone.profile == profile && zone.zone_id == zone_id)
+    }
+
+    /// Add `zone` of `profile`, or remove it if it's in the workspace already
+    pub fn toggle(&mut self, profile: &str, zone: &Zone) {
+        if self.contains(profile, &zone.id) {
+            self.zones
+                .retain(|existing| !(existing.profile == profile && existing.zone_id == zone.id));
+        } else {
+            self.zones.push(WorkspaceZone {
+                profile: profile.to_string(),
+                zone_id: zone.id.clone(),
+                zone_name: zone.name.clone(),
+            });
+        }
+    }
+
+    /// Profiles of the workspace's zones, in the order they were first added
+    pub fn profiles(&self) -> Vec<String> {
+        let mut profiles: Vec<String> = Vec::new();
+        for zone in &self.zones {
+            if !profiles.contains(&zone.profile) {
+                profiles.push(zone.profile.clone());
+            }
+        }
+        profiles
+    }
+}
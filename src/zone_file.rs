@@ -0,0 +1,612 @@
+//! Import and export of BIND zone files, e.g. when moving a domain between DNS providers
+
+use std::fmt::Write;
+
+use anyhow::{Result, anyhow, bail};
+
+use crate::cloudflare::{CreateDnsRecord, DnsRecord, DnsRecordType};
+use crate::record_defaults::{MAX_TTL, MIN_TTL};
+
+/// Records read from a zone file
+#[derive(Debug, Clone, Default)]
+pub struct ParsedZoneFile {
+    pub records: Vec<CreateDnsRecord>,
+    /// Entries that were left out, e.g. SOA records, with the reason
+    pub skipped: Vec<String>,
+}
+
+/// Whether an imported record is new to the zone
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImportStatus {
+    New,
+    /// A record with the same type, name and content already exists
+    Exists,
+}
+
+/// Imported records compared to the records of the zone
+#[derive(Debug, Clone)]
+pub struct ImportPreview {
+    pub items: Vec<(ImportStatus, CreateDnsRecord)>,
+    pub skipped: Vec<String>,
+    /// Number of existing records that aren't in the zone file; they are kept
+    pub only_in_zone: usize,
+}
+
+impl ImportPreview {
+    pub fn new(parsed: ParsedZoneFile, existing: &[DnsRecord]) -> Self {
+        let same = |record: &CreateDnsRecord, existing: &DnsRecord| {
+            existing.record_type == record.record_type
+                && existing.name.eq_ignore_ascii_case(&record.name)
+                && existing.content == record.content
+                && existing.priority == record.priority
+        };
+
+        let items = parsed
+            .records
+            .into_iter()
+            .map(|record| {
+                let status = if existing.iter().any(|existing| same(&record, existing)) {
+                    ImportStatus::Exists
+                } else {
+                    ImportStatus::New
+                };
+                (status, record)
+            })
+            .collect::<Vec<_>>();
+        let only_in_zone = existing
+            .iter()
+            .filter(|existing| !items.iter().any(|(_, record)| same(record, existing)))
+            .count();
+
+        Self {
+            items,
+            skipped: parsed.skipped,
+            only_in_zone,
+        }
+    }
+
+    pub fn new_records(&self) -> impl Iterator<Item = &CreateDnsRecord> {
+        self.items
+            .iter()
+            .filter(|(status, _)| *status == ImportStatus::New)
+            .map(|(_, record)| record)
+    }
+}
+
+/// Parse a zone file for `zone`, which is also the default `$ORIGIN`
+///
+/// Names are returned fully qualified without the trailing dot, as the API uses them.
+/// Records without a TTL and without a `$TTL` default use automatic TTL; TTLs Cloudflare doesn't
+/// accept are brought into its range and listed as skipped.
+///
+/// Zone files exported by Cloudflare, e.g. from another account, keep the proxy status in
+/// `cf_tags=cf-proxied:true` comments and the record's comment before it; both are restored.
+pub fn parse(text: &str, zone: &str) -> Result<ParsedZoneFile> {
+    let mut parsed = ParsedZoneFile::default();
+    let mut origin = zone.trim_end_matches('.').to_lowercase();
+    let mut default_ttl: Option<u32> = None;
+    let mut last_owner: Option<String> = None;
+    let cloudflare_export = is_cloudflare_export(text);
+
+    for (line_number, line, comment) in logical_lines(text)? {
+        let at = |e: anyhow::Error| anyhow!("Line {}: {}", line_number, e);
+        let owner_omitted = line.starts_with([' ', '\t']);
+        let mut tokens = tokenize(&line).map_err(at)?;
+        if tokens.is_empty() {
+            continue;
+        }
+
+        match tokens[0].to_ascii_uppercase().as_str() {
+            "$ORIGIN" => {
+                let name = tokens
+                    .get(1)
+                    .ok_or_else(|| at(anyhow!("$ORIGIN needs a name")))?;
+                origin = qualify(name, &origin);
+                continue;
+            }
+            "$TTL" => {
+                let ttl = tokens
+                    .get(1)
+                    .ok_or_else(|| at(anyhow!("$TTL needs a value")))?;
+                default_ttl = Some(parse_ttl(ttl).map_err(at)?);
+                continue;
+            }
+            directive if directive.starts_with('$') => {
+                parsed.skipped.push(format!(
+                    "Line {}: {} is not supported",
+                    line_number, tokens[0]
+                ));
+                continue;
+            }
+            _ => {}
+        }
+
+        let owner = if owner_omitted {
+            last_owner
+                .clone()
+                .ok_or_else(|| at(anyhow!("No owner name")))?
+        } else {
+            qualify(&tokens.remove(0), &origin)
+        };
+        last_owner = Some(owner.clone());
+
+        // TTL and class may appear in either order before the type
+        let mut ttl = None;
+        while let Some(token) = tokens.first() {
+            if token.eq_ignore_ascii_case("IN") {
+                tokens.remove(0);
+            } else if ttl.is_none() && token.starts_with(|c: char| c.is_ascii_digit()) {
+                ttl = Some(parse_ttl(&tokens.remove(0)).map_err(at)?);
+            } else {
+                break;
+            }
+        }
+        let ttl = ttl.or(default_ttl).unwrap_or(1);
+
+        if tokens.is_empty() {
+            bail!("Line {}: Missing record type", line_number);
+        }
+        let type_name = tokens.remove(0).to_ascii_uppercase();
+        let rdata = tokens;
+
+        let Some(record_type) = DnsRecordType::all()
+            .iter()
+            .copied()
+            .find(|t| t.as_str() == type_name)
+        else {
+            let reason = if type_name == "SOA" {
+                "managed by Cloudflare"
+            } else {
+                "not supported"
+            };
+            parsed.skipped.push(format!(
+                "Line {}: {} record of {} ({})",
+                line_number, type_name, owner, reason
+            ));
+            continue;
+        };
+        if record_type == DnsRecordType::NS && owner == zone.trim_end_matches('.').to_lowercase() {
+            parsed.skipped.push(format!(
+                "Line {}: NS record of {} (the zone's name servers are managed by Cloudflare)",
+                line_number, owner
+            ));
+            continue;
+        }
+
+        let mut record = build_record(record_type, owner, ttl, rdata, &origin).map_err(at)?;
+        if cloudflare_export && let Some(comment) = comment {
+            let metadata = CloudflareMetadata::parse(&comment);
+            if let Some(proxied) = metadata.proxied
+                && record_type.is_proxiable()
+            {
+                record.proxied = Some(proxied);
+                // Proxied records always use automatic TTL
+                if proxied {
+                    record.ttl = 1;
+                }
+            }
+            record.comment = metadata.comment;
+            if !metadata.unsupported_tags.is_empty() {
+                parsed.skipped.push(format!(
+                    "Line {}: tags {} of {} {} were left out",
+                    line_number,
+                    metadata.unsupported_tags.join(", "),
+                    record.record_type,
+                    record.name
+                ));
+            }
+        }
+        if record.ttl != 1 && !(MIN_TTL..=MAX_TTL).contains(&record.ttl) {
+            let clamped = record.ttl.clamp(MIN_TTL, MAX_TTL);
+            parsed.skipped.push(format!(
+                "Line {}: TTL {} of {} {} was changed to {}",
+                line_number, record.ttl, record.record_type, record.name, clamped
+            ));
+            record.ttl = clamped;
+        }
+        record
+            .record_type
+            .validate_content(&record.content)
+            .map_err(|e| anyhow!("Line {}: {}", line_number, e))?;
+        parsed.records.push(record);
+    }
+
+    Ok(parsed)
+}
+
+fn build_record(
+    record_type: DnsRecordType,
+    name: String,
+    ttl: u32,
+    rdata: Vec<String>,
+    origin: &str,
+) -> Result<CreateDnsRecord> {
+    let field = |index: usize| {
+        rdata
+            .get(index)
+            .cloned()
+            .ok_or_else(|| anyhow!("Incomplete {} record", record_type))
+    };
+
+    let (content, priority) = match record_type {
+        DnsRecordType::MX => (
+            qualify(&field(1)?, origin),
+            Some(parse_priority(&field(0)?)?),
+        ),
+        DnsRecordType::SRV => (
+            format!(
+                "{} {} {}",
+                field(1)?,
+                field(2)?,
+                qualify(&field(3)?, origin)
+            ),
+            Some(parse_priority(&field(0)?)?),
+        ),
+        DnsRecordType::CNAME | DnsRecordType::NS | DnsRecordType::PTR => {
+            (qualify(&field(0)?, origin), None)
+        }
+        // Character strings of a TXT record are concatenated
+        DnsRecordType::TXT => (rdata.concat(), None),
+        DnsRecordType::CAA => (
+            format!("{} {} \"{}\"", field(0)?, field(1)?, field(2)?),
+            None,
+        ),
+        DnsRecordType::NAPTR => (
+            format!(
+                "{} {} \"{}\" \"{}\" \"{}\" {}",
+                field(0)?,
+                field(1)?,
+                field(2)?,
+                field(3)?,
+                field(4)?,
+                qualify(&field(5)?, origin)
+            ),
+            None,
+        ),
+        DnsRecordType::URI => (
+            format!("{} \"{}\"", field(1)?, field(2)?),
+            Some(parse_priority(&field(0)?)?),
+        ),
+        // Fields of the other types are kept as they are, e.g. a DS digest split in two
+        DnsRecordType::HTTPS
+        | DnsRecordType::SVCB
+        | DnsRecordType::TLSA
+        | DnsRecordType::SSHFP
+        | DnsRecordType::SMIMEA
+        | DnsRecordType::CERT
+        | DnsRecordType::DNSKEY
+        | DnsRecordType::DS
+        | DnsRecordType::LOC => (rdata.join(" "), None),
+        _ => (field(0)?, None),
+    };
+
+    Ok(CreateDnsRecord {
+        record_type,
+        name,
+        content,
+        ttl,
+        proxied: record_type.is_proxiable().then_some(false),
+        priority,
+        comment: None,
+        data: None,
+    })
+}
+
+/// Whether the zone file was exported by Cloudflare, whose comments carry record settings
+fn is_cloudflare_export(text: &str) -> bool {
+    text.contains("cf_tags=") || text.lines().any(|line| line.starts_with(";; Domain:"))
+}
+
+/// Settings Cloudflare writes into the comment of a record in its zone file exports
+#[derive(Debug, Default, PartialEq)]
+struct CloudflareMetadata {
+    proxied: Option<bool>,
+    comment: Option<String>,
+    /// Tags other than the proxy status, e.g. record tags, which can't be imported
+    unsupported_tags: Vec<String>,
+}
+
+impl CloudflareMetadata {
+    /// Parse a comment like `Mail relay cf_tags=cf-proxied:false`
+    fn parse(comment: &str) -> Self {
+        let mut metadata = Self::default();
+        let (text, tags) = match comment.find("cf_tags=") {
+            Some(index) => (&comment[..index], &comment[index + "cf_tags=".len()..]),
+            None => (comment, ""),
+        };
+
+        for tag in tags.split(',').map(str::trim).filter(|tag| !tag.is_empty()) {
+            match tag.split_once(':') {
+                Some(("cf-proxied", value)) => metadata.proxied = Some(value == "true"),
+                _ => metadata.unsupported_tags.push(tag.to_string()),
+            }
+        }
+        let text = text.trim();
+        metadata.comment = (!text.is_empty()).then(|| text.to_string());
+        metadata
+    }
+}
+
+/// TTL written for records with automatic TTL, which Cloudflare serves as 300 seconds
+const AUTO_TTL: u32 = 300;
+
+/// Longest character string of a TXT record
+const MAX_CHARACTER_STRING: usize = 255;
+
+/// Serialize the records of a zone as a BIND zone file
+///
+/// Records of types that can't be represented are written as comments.
+pub fn export(zone: &str, records: &[DnsRecord], header: &str) -> String {
+    let origin = zone.trim_end_matches('.').to_lowercase();
+    let mut records: Vec<&DnsRecord> = records.iter().collect();
+    records.sort_by(|a, b| {
+        (a.name.as_str(), a.record_type.as_str(), a.content.as_str()).cmp(&(
+            b.name.as_str(),
+            b.record_type.as_str(),
+            b.content.as_str(),
+        ))
+    });
+
+    let mut file = String::new();
+    // Writing to a String can't fail
+    let _ = writeln!(file, "; {}", header);
+    let _ = writeln!(file, "$ORIGIN {}.", origin);
+    let _ = writeln!(file, "$TTL {}", AUTO_TTL);
+
+    for record in records {
+        let name = relative_name(&record.name, &origin);
+        let ttl = if record.ttl == 1 {
+            AUTO_TTL
+        } else {
+            record.ttl
+        };
+        let priority = record.priority.unwrap_or(0);
+        let content = record.data_content();
+
+        let rdata = match record.record_type {
+            DnsRecordType::CNAME | DnsRecordType::NS | DnsRecordType::PTR => {
+                absolute_name(&content)
+            }
+            DnsRecordType::MX => format!("{} {}", priority, absolute_name(&content)),
+            DnsRecordType::SRV => {
+                // Content is `weight port target`
+                let mut fields: Vec<String> =
+                    content.split_whitespace().map(String::from).collect();
+                if let Some(target) = fields.last_mut() {
+                    *target = absolute_name(target);
+                }
+                format!("{} {}", priority, fields.join(" "))
+            }
+            DnsRecordType::TXT | DnsRecordType::SPF => character_strings(&content),
+            DnsRecordType::URI => format!("{} {}", priority, content),
+            DnsRecordType::A
+            | DnsRecordType::AAAA
+            | DnsRecordType::CAA
+            | DnsRecordType::HTTPS
+            | DnsRecordType::SVCB
+            | DnsRecordType::TLSA
+            | DnsRecordType::SSHFP
+            | DnsRecordType::NAPTR
+            | DnsRecordType::SMIMEA
+            | DnsRecordType::CERT
+            | DnsRecordType::DNSKEY
+            | DnsRecordType::DS
+            | DnsRecordType::LOC => content,
+            DnsRecordType::Other => {
+                let _ = writeln!(
+                    file,
+                    "; {} {} {} (unsupported record type)",
+                    name, ttl, content
+                );
+                continue;
+            }
+        };
+
+        let _ = writeln!(
+            file,
+            "{}\t{}\tIN\t{}\t{}",
+            name, ttl, record.record_type, rdata
+        );
+    }
+
+    file
+}
+
+/// Owner name relative to the origin, `@` for the origin itself
+fn relative_name(name: &str, origin: &str) -> String {
+    let name = name.trim_end_matches('.').to_lowercase();
+    if name == origin {
+        "@".to_string()
+    } else if let Some(relative) = name.strip_suffix(&format!(".{}", origin)) {
+        relative.to_string()
+    } else {
+        format!("{}.", name)
+    }
+}
+
+fn absolute_name(name: &str) -> String {
+    format!("{}.", name.trim_end_matches('.'))
+}
+
+/// TXT content as quoted character strings of at most 255 bytes
+fn character_strings(content: &str) -> String {
+    // Content that is already quoted is written as it is
+    if content.starts_with('"') && content.ends_with('"') && content.len() > 1 {
+        return content.to_string();
+    }
+
+    let mut strings = Vec::new();
+    let mut current = String::new();
+    for c in content.chars() {
+        if current.len() + c.len_utf8() > MAX_CHARACTER_STRING {
+            strings.push(std::mem::take(&mut current));
+        }
+        current.push(c);
+    }
+    strings.push(current);
+
+    strings
+        .iter()
+        .map(|s| format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\"")))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Lines with comments removed and parenthesized records joined, with their line numbers and
+/// the text of their comments
+fn logical_lines(text: &str) -> Result<Vec<(usize, String, Option<String>)>> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    let mut comment = String::new();
+    let mut start = 0;
+    let mut depth = 0;
+
+    for (index, line) in text.lines().enumerate() {
+        let mut in_quotes = false;
+        let mut escaped = false;
+        let mut stripped = String::new();
+        for (position, c) in line.char_indices() {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_quotes = !in_quotes;
+            } else if !in_quotes {
+                match c {
+                    ';' => {
+                        let text = line[position..].trim_start_matches(';').trim();
+                        if !text.is_empty() {
+                            if !comment.is_empty() {
+                                comment.push(' ');
+                            }
+                            comment.push_str(text);
+                        }
+                        break;
+                    }
+                    '(' => {
+                        depth += 1;
+                        stripped.push(' ');
+                        continue;
+                    }
+                    ')' => {
+                        depth -= 1;
+                        stripped.push(' ');
+                        continue;
+                    }
+                    _ => {}
+                }
+            }
+            stripped.push(c);
+        }
+
+        if current.is_empty() {
+            start = index + 1;
+            current = stripped;
+        } else {
+            current.push(' ');
+            current.push_str(&stripped);
+        }
+        if depth < 0 {
+            bail!("Line {}: Unbalanced parentheses", index + 1);
+        }
+        if depth == 0 {
+            let comment = std::mem::take(&mut comment);
+            if !current.trim().is_empty() {
+                let comment = (!comment.is_empty()).then_some(comment);
+                lines.push((start, std::mem::take(&mut current), comment));
+            }
+            current.clear();
+        }
+    }
+    if depth != 0 {
+        bail!("Line {}: Unclosed parenthesis", start);
+    }
+
+    Ok(lines)
+}
+
+/// Split a line into fields; quoted strings become one field without the quotes
+fn tokenize(line: &str) -> Result<Vec<String>> {
+    let mut tokens = Vec::new();
+    let mut chars = line.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '"' {
+            chars.next();
+            let mut token = String::new();
+            loop {
+                match chars.next() {
+                    Some('\\') => token.extend(chars.next()),
+                    Some('"') => break,
+                    Some(c) => token.push(c),
+                    None => bail!("Unclosed quote"),
+                }
+            }
+            tokens.push(token);
+        } else {
+            let mut token = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() || c == '"' {
+                    break;
+                }
+                token.push(c);
+                chars.next();
+            }
+            tokens.push(token);
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Make a name absolute: `@` is the origin and names without a trailing dot are relative to it
+fn qualify(name: &str, origin: &str) -> String {
+    if name == "@" {
+        origin.to_string()
+    } else if let Some(name) = name.strip_suffix('.') {
+        name.to_lowercase()
+    } else {
+        format!("{}.{}", name.to_lowercase(), origin)
+    }
+}
+
+/// TTL in seconds, also accepting BIND units like `1h30m`
+fn parse_ttl(value: &str) -> Result<u32> {
+    let invalid = || anyhow!("Invalid TTL {}", value);
+    if let Ok(seconds) = value.parse() {
+        return Ok(seconds);
+    }
+
+    let mut total: u32 = 0;
+    let mut number = String::new();
+    for c in value.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let unit = match c.to_ascii_lowercase() {
+            's' => 1,
+            'm' => 60,
+            'h' => 3600,
+            'd' => 86400,
+            'w' => 604800,
+            _ => return Err(invalid()),
+        };
+        let amount: u32 = number.parse().map_err(|_| invalid())?;
+        total = total.saturating_add(amount.saturating_mul(unit));
+        number.clear();
+    }
+    if !number.is_empty() {
+        return Err(invalid());
+    }
+    Ok(total)
+}
+
+fn parse_priority(value: &str) -> Result<u16> {
+    value
+        .parse()
+        .map_err(|_| anyhow!("Invalid priority {}", value))
+}
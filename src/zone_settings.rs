@@ -0,0 +1,150 @@
+//! Common zone settings shown on the zone settings page
+//!
+//! Cloudflare has many more settings; these are the ones most zones need to look at. Their
+//! values are read and written through `/zones/{id}/settings`.
+
+use chrono::{DateTime, TimeDelta, Utc};
+
+use crate::cloudflare::ZoneSetting;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ZoneSettingKind {
+    SslMode,
+    AlwaysUseHttps,
+    AutomaticHttpsRewrites,
+    MinTlsVersion,
+    Brotli,
+    Http3,
+    DevelopmentMode,
+}
+
+/// How a setting is changed
+pub enum SettingControl {
+    /// `on` or `off`
+    Toggle,
+    /// One of the values, each with a label
+    Choice(&'static [(&'static str, &'static str)]),
+}
+
+impl ZoneSettingKind {
+    pub fn all() -> &'static [ZoneSettingKind] {
+        &[
+            ZoneSettingKind::SslMode,
+            ZoneSettingKind::AlwaysUseHttps,
+            ZoneSettingKind::AutomaticHttpsRewrites,
+            ZoneSettingKind::MinTlsVersion,
+            ZoneSettingKind::Brotli,
+            ZoneSettingKind::Http3,
+            ZoneSettingKind::DevelopmentMode,
+        ]
+    }
+
+    /// Id of the setting in the API
+    pub fn id(&self) -> &'static str {
+        match self {
+            ZoneSettingKind::SslMode => "ssl",
+            ZoneSettingKind::AlwaysUseHttps => "always_use_https",
+            ZoneSettingKind::AutomaticHttpsRewrites => "automatic_https_rewrites",
+            ZoneSettingKind::MinTlsVersion => "min_tls_version",
+            ZoneSettingKind::Brotli => "brotli",
+            ZoneSettingKind::Http3 => "http3",
+            ZoneSettingKind::DevelopmentMode => "development_mode",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ZoneSettingKind::SslMode => "SSL/TLS encryption mode",
+            ZoneSettingKind::AlwaysUseHttps => "Always Use HTTPS",
+            ZoneSettingKind::AutomaticHttpsRewrites => "Automatic HTTPS Rewrites",
+            ZoneSettingKind::MinTlsVersion => "Minimum TLS version",
+            ZoneSettingKind::Brotli => "Brotli",
+            ZoneSettingKind::Http3 => "HTTP/3 (with QUIC)",
+            ZoneSettingKind::DevelopmentMode => "Development Mode",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            ZoneSettingKind::SslMode => {
+                "How Cloudflare connects to the origin. Full (strict) validates the origin's \
+                 certificate; Flexible connects over plain HTTP."
+            }
+            ZoneSettingKind::AlwaysUseHttps => "Redirect all HTTP requests to HTTPS.",
+            ZoneSettingKind::AutomaticHttpsRewrites => {
+                "Rewrite HTTP links in pages to HTTPS where possible, avoiding mixed content."
+            }
+            ZoneSettingKind::MinTlsVersion => "Clients using older TLS versions can't connect.",
+            ZoneSettingKind::Brotli => {
+                "Compress responses with Brotli for clients that support it."
+            }
+            ZoneSettingKind::Http3 => "Serve visitors over HTTP/3 where their browser supports it.",
+            ZoneSettingKind::DevelopmentMode => {
+                "Bypass the cache for three hours, e.g. to see origin changes right away. It \
+                 turns itself off afterwards."
+            }
+        }
+    }
+
+    pub fn control(&self) -> SettingControl {
+        match self {
+            ZoneSettingKind::SslMode => SettingControl::Choice(&[
+                ("off", "Off"),
+                ("flexible", "Flexible"),
+                ("full", "Full"),
+                ("strict", "Full (strict)"),
+            ]),
+            ZoneSettingKind::MinTlsVersion => SettingControl::Choice(&[
+                ("1.0", "1.0"),
+                ("1.1", "1.1"),
+                ("1.2", "1.2"),
+                ("1.3", "1.3"),
+            ]),
+            ZoneSettingKind::AlwaysUseHttps
+            | ZoneSettingKind::AutomaticHttpsRewrites
+            | ZoneSettingKind::Brotli
+            | ZoneSettingKind::Http3
+            | ZoneSettingKind::DevelopmentMode => SettingControl::Toggle,
+        }
+    }
+
+    /// The setting's current value among `settings`, if the zone has it
+    pub fn value<'a>(&self, settings: &'a [ZoneSetting]) -> Option<&'a ZoneSetting> {
+        settings.iter().find(|setting| setting.id == self.id())
+    }
+
+    /// Label of a value, e.g. `Full (strict)` for `strict`
+    pub fn value_label(&self, value: &str) -> String {
+        match self.control() {
+            SettingControl::Toggle => if value == "on" { "On" } else { "Off" }.to_string(),
+            SettingControl::Choice(options) => options
+                .iter()
+                .find(|(option, _)| *option == value)
+                .map(|(_, label)| label.to_string())
+                .unwrap_or_else(|| value.to_string()),
+        }
+    }
+}
+
+/// When development mode turns itself off, from its setting; `None` while it's off
+pub fn development_mode_ends_at(
+    setting: &ZoneSetting,
+    now: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    if setting.value.as_str() != Some("on") {
+        return None;
+    }
+    let seconds = setting.time_remaining.filter(|&seconds| seconds > 0)?;
+    Some(now + TimeDelta::seconds(seconds))
+}
+
+/// Time left as a countdown, e.g. `2:59:41`
+pub fn format_remaining(remaining: TimeDelta) -> String {
+    let seconds = remaining.num_seconds().max(0);
+    format!(
+        "{}:{:02}:{:02}",
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    )
+}
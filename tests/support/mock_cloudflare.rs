@@ -0,0 +1,368 @@
+//! An in-memory stand-in for the parts of the Cloudflare API the tested flows use
+//!
+//! One server runs for all tests of a binary, as the app reads its address from the
+//! environment. Each test registers an account under a token of its own, so tests running in
+//! parallel don't see each other's zones and records.
+
+use std::collections::HashMap;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use axum::extract::{Path, State};
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use serde_json::{Value, json};
+use tokio::runtime::Runtime;
+
+#[derive(Default)]
+struct Account {
+    /// Zones as the API returns them
+    zones: Vec<Value>,
+    /// Records by zone id
+    records: HashMap<String, Vec<Value>>,
+}
+
+type Accounts = Arc<Mutex<HashMap<String, Account>>>;
+
+pub struct MockCloudflare {
+    /// What `CLOUDFLARE_API_BASE` is set to, e.g. `http://127.0.0.1:40123/client/v4`
+    pub api_base: String,
+    runtime: Runtime,
+    accounts: Accounts,
+    next_id: AtomicU32,
+}
+
+impl MockCloudflare {
+    /// The server of this test binary, started on first use
+    pub fn shared() -> &'static MockCloudflare {
+        static SERVER: OnceLock<MockCloudflare> = OnceLock::new();
+        SERVER.get_or_init(MockCloudflare::start)
+    }
+
+    fn start() -> Self {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .expect("Failed to start the mock server's runtime");
+        let accounts = Accounts::default();
+
+        let router = Router::new()
+            .route("/client/v4/user/tokens/verify", get(verify_token))
+            .route("/client/v4/zones", get(list_zones))
+            .route(
+                "/client/v4/zones/{zone_id}/dns_records",
+                get(list_records).post(create_record),
+            )
+            .route(
+                "/client/v4/zones/{zone_id}/dns_records/{record_id}",
+                get(get_record).patch(update_record).delete(delete_record),
+            )
+            .fallback(|| async {
+                failure(StatusCode::NOT_FOUND, 7003, "Not implemented by the mock")
+            })
+            .with_state(accounts.clone());
+
+        let listener = runtime
+            .block_on(tokio::net::TcpListener::bind(SocketAddr::from((
+                Ipv4Addr::LOCALHOST,
+                0,
+            ))))
+            .expect("Failed to bind the mock server");
+        let address = listener.local_addr().unwrap();
+        runtime.spawn(async move {
+            axum::serve(listener, router).await.ok();
+        });
+
+        Self {
+            api_base: format!("http://{}/client/v4", address),
+            runtime,
+            accounts,
+            next_id: AtomicU32::new(1),
+        }
+    }
+
+    /// The runtime the app's requests are sent on, as the app's own `main` provides one
+    pub fn runtime(&self) -> &Runtime {
+        &self.runtime
+    }
+
+    fn next_id(&self, prefix: &str) -> String {
+        format!("{}{}", prefix, self.next_id.fetch_add(1, Ordering::Relaxed))
+    }
+
+    /// Register an account with the given zones, returning the token that reaches it
+    pub fn add_account(&self, zone_names: &[&str]) -> String {
+        let token = self.next_id("test-token-");
+        let account_id = self.next_id("account-");
+        let mut account = Account::default();
+        for name in zone_names {
+            let zone_id = self.next_id("zone-");
+            account.zones.push(json!({
+                "id": zone_id,
+                "name": name,
+                "status": "active",
+                "account": { "id": account_id, "name": "Test account" },
+                "name_servers": ["ada.ns.cloudflare.com", "bob.ns.cloudflare.com"],
+            }));
+            account.records.insert(zone_id, Vec::new());
+        }
+        self.accounts.lock().unwrap().insert(token.clone(), account);
+        token
+    }
+
+    /// Add an A record to a zone of the account, as if it was created elsewhere
+    pub fn add_record(&self, token: &str, zone_name: &str, name: &str, content: &str) {
+        let id = self.next_id("record-");
+        let mut accounts = self.accounts.lock().unwrap();
+        let account = accounts.get_mut(token).expect("Unknown token");
+        let zone_id = zone_id(account, zone_name).expect("Unknown zone");
+        let record = new_record(
+            id,
+            &json!({ "type": "A", "name": name, "content": content, "ttl": 1 }),
+            zone_name,
+        );
+        account.records.get_mut(&zone_id).unwrap().push(record);
+    }
+
+    /// Records of a zone of the account as the API has them now
+    pub fn records(&self, token: &str, zone_name: &str) -> Vec<Value> {
+        let accounts = self.accounts.lock().unwrap();
+        let account = accounts.get(token).expect("Unknown token");
+        zone_id(account, zone_name)
+            .and_then(|zone_id| account.records.get(&zone_id).cloned())
+            .unwrap_or_default()
+    }
+}
+
+fn zone_id(account: &Account, zone_name: &str) -> Option<String> {
+    account
+        .zones
+        .iter()
+        .find(|zone| zone["name"] == zone_name)
+        .map(|zone| zone["id"].as_str().unwrap().to_string())
+}
+
+fn zone_name(account: &Account, zone_id: &str) -> Option<String> {
+    account
+        .zones
+        .iter()
+        .find(|zone| zone["id"] == zone_id)
+        .map(|zone| zone["name"].as_str().unwrap().to_string())
+}
+
+/// A record with the fields of a create request, its name completed as the API does
+fn new_record(id: String, body: &Value, zone_name: &str) -> Value {
+    let mut record = json!({
+        "id": id,
+        "type": body["type"],
+        "name": "",
+        "content": body["content"],
+        "ttl": body.get("ttl").cloned().unwrap_or(json!(1)),
+        "proxied": body.get("proxied").cloned().unwrap_or(json!(false)),
+        "proxiable": true,
+        "comment": body.get("comment").cloned().unwrap_or(Value::Null),
+        "created_on": timestamp(),
+        "modified_on": timestamp(),
+    });
+    record["name"] = json!(qualified_name(
+        body["name"].as_str().unwrap_or_default(),
+        zone_name
+    ));
+    record
+}
+
+fn qualified_name(name: &str, zone_name: &str) -> String {
+    if name == "@" || name == zone_name {
+        zone_name.to_string()
+    } else if name.ends_with(&format!(".{}", zone_name)) {
+        name.to_string()
+    } else {
+        format!("{}.{}", name, zone_name)
+    }
+}
+
+/// A new timestamp on every call, so every change gets a `modified_on` of its own
+fn timestamp() -> String {
+    static CHANGES: AtomicU32 = AtomicU32::new(0);
+    let change = CHANGES.fetch_add(1, Ordering::Relaxed);
+    format!("2026-01-01T00:00:00.{:06}Z", change)
+}
+
+fn success(result: Value) -> Response {
+    Json(json!({
+        "success": true,
+        "errors": [],
+        "messages": [],
+        "result": result,
+    }))
+    .into_response()
+}
+
+fn list(result: Vec<Value>) -> Response {
+    let count = result.len();
+    Json(json!({
+        "success": true,
+        "errors": [],
+        "messages": [],
+        "result": result,
+        "result_info": {
+            "page": 1,
+            "per_page": count.max(1),
+            "count": count,
+            "total_count": count,
+            "total_pages": 1,
+        },
+    }))
+    .into_response()
+}
+
+fn failure(status: StatusCode, code: i32, message: &str) -> Response {
+    (
+        status,
+        Json(json!({
+            "success": false,
+            "errors": [{ "code": code, "message": message }],
+            "messages": [],
+            "result": null,
+        })),
+    )
+        .into_response()
+}
+
+fn unauthorized() -> Response {
+    failure(StatusCode::UNAUTHORIZED, 1000, "Invalid API Token")
+}
+
+fn token(headers: &HeaderMap) -> String {
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .unwrap_or_default()
+        .to_string()
+}
+
+async fn verify_token(State(accounts): State<Accounts>, headers: HeaderMap) -> Response {
+    if !accounts.lock().unwrap().contains_key(&token(&headers)) {
+        return unauthorized();
+    }
+    success(json!({ "id": "token-id", "status": "active" }))
+}
+
+async fn list_zones(State(accounts): State<Accounts>, headers: HeaderMap) -> Response {
+    match accounts.lock().unwrap().get(&token(&headers)) {
+        Some(account) => list(account.zones.clone()),
+        None => unauthorized(),
+    }
+}
+
+async fn list_records(
+    State(accounts): State<Accounts>,
+    headers: HeaderMap,
+    Path(zone_id): Path<String>,
+) -> Response {
+    let accounts = accounts.lock().unwrap();
+    let Some(account) = accounts.get(&token(&headers)) else {
+        return unauthorized();
+    };
+    match account.records.get(&zone_id) {
+        Some(records) => list(records.clone()),
+        None => failure(StatusCode::NOT_FOUND, 7003, "Could not route to zone"),
+    }
+}
+
+async fn create_record(
+    State(accounts): State<Accounts>,
+    headers: HeaderMap,
+    Path(zone_id): Path<String>,
+    Json(body): Json<Value>,
+) -> Response {
+    let mut accounts = accounts.lock().unwrap();
+    let Some(account) = accounts.get_mut(&token(&headers)) else {
+        return unauthorized();
+    };
+    let Some(zone_name) = zone_name(account, &zone_id) else {
+        return failure(StatusCode::NOT_FOUND, 7003, "Could not route to zone");
+    };
+    let id = MockCloudflare::shared().next_id("record-");
+    let record = new_record(id, &body, &zone_name);
+    account
+        .records
+        .get_mut(&zone_id)
+        .unwrap()
+        .push(record.clone());
+    success(record)
+}
+
+async fn get_record(
+    State(accounts): State<Accounts>,
+    headers: HeaderMap,
+    Path((zone_id, record_id)): Path<(String, String)>,
+) -> Response {
+    let accounts = accounts.lock().unwrap();
+    let Some(account) = accounts.get(&token(&headers)) else {
+        return unauthorized();
+    };
+    match account
+        .records
+        .get(&zone_id)
+        .and_then(|records| records.iter().find(|record| record["id"] == record_id))
+    {
+        Some(record) => success(record.clone()),
+        None => failure(StatusCode::NOT_FOUND, 81044, "Record does not exist"),
+    }
+}
+
+async fn update_record(
+    State(accounts): State<Accounts>,
+    headers: HeaderMap,
+    Path((zone_id, record_id)): Path<(String, String)>,
+    Json(body): Json<Value>,
+) -> Response {
+    let mut accounts = accounts.lock().unwrap();
+    let Some(account) = accounts.get_mut(&token(&headers)) else {
+        return unauthorized();
+    };
+    let zone_name = zone_name(account, &zone_id).unwrap_or_default();
+    let Some(record) = account
+        .records
+        .get_mut(&zone_id)
+        .and_then(|records| records.iter_mut().find(|record| record["id"] == record_id))
+    else {
+        return failure(StatusCode::NOT_FOUND, 81044, "Record does not exist");
+    };
+
+    for field in ["type", "content", "ttl", "proxied", "priority", "comment"] {
+        if let Some(value) = body.get(field) {
+            record[field] = value.clone();
+        }
+    }
+    if let Some(name) = body["name"].as_str() {
+        record["name"] = json!(qualified_name(name, &zone_name));
+    }
+    record["modified_on"] = json!(timestamp());
+    success(record.clone())
+}
+
+async fn delete_record(
+    State(accounts): State<Accounts>,
+    headers: HeaderMap,
+    Path((zone_id, record_id)): Path<(String, String)>,
+) -> Response {
+    let mut accounts = accounts.lock().unwrap();
+    let Some(account) = accounts.get_mut(&token(&headers)) else {
+        return unauthorized();
+    };
+    let Some(records) = account.records.get_mut(&zone_id) else {
+        return failure(StatusCode::NOT_FOUND, 7003, "Could not route to zone");
+    };
+    let count = records.len();
+    records.retain(|record| record["id"] != record_id);
+    if records.len() == count {
+        return failure(StatusCode::NOT_FOUND, 81044, "Record does not exist");
+    }
+    success(json!({ "id": record_id }))
+}
@@ -0,0 +1,112 @@
+//! The app in a gpui test window, talking to a mock of the Cloudflare API
+
+mod mock_cloudflare;
+
+use std::path::PathBuf;
+use std::sync::Once;
+use std::time::Duration;
+
+use cloudflare_admin::App;
+use gpui::{Context, Entity, TestAppContext, VisualTestContext, Window};
+use gpui_component::Root;
+use gpui_component::input::InputState;
+use tokio::runtime::EnterGuard;
+
+pub use mock_cloudflare::MockCloudflare;
+
+/// How long a flow may take to reach the expected state, in steps of [`STEP`]
+const STEPS: u32 = 500;
+const STEP: Duration = Duration::from_millis(10);
+
+pub struct TestApp<'a> {
+    pub app: Entity<App>,
+    pub cx: &'a mut VisualTestContext,
+    _runtime: EnterGuard<'static>,
+}
+
+/// Open the app with no token set up, as on a first launch
+pub fn open_app(cx: &mut TestAppContext) -> TestApp<'_> {
+    let mock = MockCloudflare::shared();
+    isolate(mock);
+    let runtime = mock.runtime().enter();
+    // The mock answers from other threads
+    cx.executor().allow_parking();
+    cx.update(cloudflare_admin::init);
+
+    let mut app = None;
+    let (_, cx) = cx.add_window_view(|window, cx| {
+        let view = cx.new(|cx| App::new(window, cx));
+        app = Some(view.clone());
+        Root::new(view, window, cx)
+    });
+
+    TestApp {
+        app: app.unwrap(),
+        cx,
+        _runtime: runtime,
+    }
+}
+
+/// Keep the app off the keychain, the user's data directory and Cloudflare
+fn isolate(mock: &MockCloudflare) {
+    static ISOLATE: Once = Once::new();
+    ISOLATE.call_once(|| {
+        let home =
+            std::env::temp_dir().join(format!("cloudflare-admin-tests-{}", std::process::id()));
+        std::fs::create_dir_all(&home).unwrap();
+        keyring::set_default_credential_builder(keyring::mock::default_credential_builder());
+        // SAFETY: every test calls this before the app starts, and the `Once` makes the others
+        // wait until the variables are set
+        unsafe {
+            std::env::set_var("HOME", PathBuf::from(&home));
+            std::env::set_var("XDG_DATA_HOME", home.join("data"));
+            std::env::set_var("XDG_CONFIG_HOME", home.join("config"));
+            std::env::set_var("CLOUDFLARE_API_BASE", &mock.api_base);
+        }
+    });
+}
+
+impl TestApp<'_> {
+    pub fn read<R>(&self, f: impl FnOnce(&App, &gpui::App) -> R) -> R {
+        self.cx.read(|cx| f(self.app.read(cx), cx))
+    }
+
+    pub fn update<R>(
+        &mut self,
+        f: impl FnOnce(&mut App, &mut Window, &mut Context<App>) -> R,
+    ) -> R {
+        let app = self.app.clone();
+        self.cx
+            .update(|window, cx| app.update(cx, |app, cx| f(app, window, cx)))
+    }
+
+    /// Type `value` into one of the app's inputs
+    pub fn set_input(&mut self, input: impl FnOnce(&App) -> Entity<InputState>, value: &str) {
+        let value = value.to_string();
+        self.update(|app, window, cx| {
+            input(app).update(cx, |state, cx| state.set_value(value, window, cx));
+        });
+    }
+
+    /// Run the app's tasks until `done`, waiting for the mock server's responses in between
+    pub fn run_until(&mut self, what: &str, done: impl Fn(&App, &gpui::App) -> bool) {
+        for _ in 0..STEPS {
+            self.cx.run_until_parked();
+            if self.read(&done) {
+                return;
+            }
+            std::thread::sleep(STEP);
+        }
+        let error = self.read(|app, _| app.error.clone());
+        panic!("Timed out waiting for {} (app error: {:?})", what, error);
+    }
+
+    /// Set up `token` as on the token setup page and wait for its zones
+    pub fn set_up_token(&mut self, token: &str) {
+        self.set_input(|app| app.token_input.clone(), token);
+        self.update(|app, window, cx| app.save_token(window, cx));
+        self.run_until("the zones to load", |app, cx| {
+            !app.zones(cx).is_empty() && app.selected_zone(cx).is_some()
+        });
+    }
+}
@@ -0,0 +1,117 @@
+//! Token setup, zone selection and record changes, driven through the app's state against a
+//! mock of the Cloudflare API
+
+mod support;
+
+use cloudflare_admin::Page;
+use gpui::TestAppContext;
+
+use support::{MockCloudflare, open_app};
+
+#[gpui::test]
+fn token_setup_opens_the_portfolio(cx: &mut TestAppContext) {
+    let mock = MockCloudflare::shared();
+    let token = mock.add_account(&["example.com", "example.org"]);
+    let mut app = open_app(cx);
+    assert!(app.read(|app, _| app.page == Page::TokenSetup));
+
+    app.set_up_token(&token);
+
+    assert!(app.read(|app, _| app.page == Page::Portfolio));
+    let zones = app.read(|app, cx| {
+        app.zones(cx)
+            .iter()
+            .map(|zone| zone.name.clone())
+            .collect::<Vec<_>>()
+    });
+    assert_eq!(zones.len(), 2);
+    assert!(zones.contains(&"example.com".to_string()));
+}
+
+#[gpui::test]
+fn token_setup_rejects_an_unknown_token(cx: &mut TestAppContext) {
+    let mut app = open_app(cx);
+
+    app.set_input(|app| app.token_input.clone(), "not-a-registered-token");
+    app.update(|app, window, cx| app.save_token(window, cx));
+    app.run_until("the token to be rejected", |app, _| app.error.is_some());
+
+    assert!(app.read(|app, _| app.page == Page::TokenSetup));
+}
+
+#[gpui::test]
+fn selecting_a_zone_loads_its_records(cx: &mut TestAppContext) {
+    let mock = MockCloudflare::shared();
+    let token = mock.add_account(&["example.com", "example.org"]);
+    mock.add_record(&token, "example.org", "www", "192.0.2.1");
+    let mut app = open_app(cx);
+    app.set_up_token(&token);
+
+    let index = app
+        .read(|app, cx| {
+            app.zones(cx)
+                .iter()
+                .position(|zone| zone.name == "example.org")
+        })
+        .unwrap();
+    app.update(|app, window, cx| app.select_zone(index, window, cx));
+    app.run_until("the zone's records to load", |app, cx| {
+        app.dns_records(cx)
+            .iter()
+            .any(|record| record.name == "www.example.org")
+    });
+
+    assert_eq!(
+        app.read(|app, cx| app.selected_zone(cx).map(|zone| zone.name)),
+        Some("example.org".to_string())
+    );
+}
+
+#[gpui::test]
+fn records_can_be_created_updated_and_deleted(cx: &mut TestAppContext) {
+    let mock = MockCloudflare::shared();
+    let token = mock.add_account(&["example.com"]);
+    let mut app = open_app(cx);
+    app.set_up_token(&token);
+    app.run_until("the zone's records to load", |app, cx| {
+        app.selected_zone_records(cx).is_some()
+    });
+
+    // Create
+    app.set_input(|app| app.record_name_input.clone(), "www");
+    app.set_input(|app| app.record_content_input.clone(), "192.0.2.1");
+    app.update(|app, window, cx| app.create_record(window, cx));
+    app.run_until("the record to be created", |app, cx| {
+        app.dns_records(cx)
+            .iter()
+            .any(|record| record.name == "www.example.com")
+    });
+    let records = mock.records(&token, "example.com");
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0]["content"], "192.0.2.1");
+
+    // Update, confirming the preview of the changed fields
+    let record = app.read(|app, cx| app.dns_records(cx)[0].clone());
+    let record_id = record.id.clone();
+    app.update(|app, window, cx| app.edit_record(record, window, cx));
+    app.set_input(|app| app.record_content_input.clone(), "192.0.2.7");
+    app.update(|app, window, cx| app.update_record(window, cx));
+    app.cx.run_until_parked();
+    app.cx.simulate_keystrokes("enter");
+    app.run_until("the record to be updated", |app, cx| {
+        app.dns_records(cx)
+            .iter()
+            .any(|record| record.content == "192.0.2.7")
+    });
+    assert_eq!(
+        mock.records(&token, "example.com")[0]["content"],
+        "192.0.2.7"
+    );
+
+    // Delete
+    app.update(|app, window, cx| app.delete_record(record_id, window, cx));
+    app.run_until("the record to be deleted", |app, cx| {
+        app.dns_records(cx).is_empty()
+    });
+    assert!(mock.records(&token, "example.com").is_empty());
+}